@@ -26,7 +26,9 @@ use zksync_contracts::getters_facet_contract;
 use zksync_dal::{ConnectionPool, Core};
 use zksync_object_store::ObjectStoreFactory;
 use zksync_operator_signer::OperatorSigner;
-use zksync_types::{settlement::SettlementLayer, Address, L1BatchNumber, L2_BRIDGEHUB_ADDRESS};
+use zksync_types::{
+    settlement::SettlementLayer, Address, L1BatchNumber, L2BlockNumber, L2_BRIDGEHUB_ADDRESS,
+};
 
 #[derive(Debug, Parser)]
 #[command(author = "Matter Labs", version, about = "Block revert utility", long_about = None)]
@@ -83,12 +85,20 @@ enum Command {
         nonce: u64,
     },
 
-    /// Rolls back internal database state to a previous L1 batch.
+    /// Rolls back internal database state to a previous L1 batch, or to a previous L2 block in the last open L1 batch.
     #[command(name = "rollback-db")]
     RollbackDB {
         /// L1 batch number used to roll back to.
-        #[arg(long)]
-        l1_batch_number: u32,
+        #[arg(long, required_unless_present = "l2_block_number")]
+        l1_batch_number: Option<u32>,
+        /// L2 block number used to roll back to. The block must belong to the last open (i.e., not sealed) L1 batch;
+        /// only Postgres is rolled back in this case.
+        #[arg(
+            long,
+            conflicts_with = "l1_batch_number",
+            requires = "rollback_postgres"
+        )]
+        l2_block_number: Option<u32>,
         /// Flag that specifies if Postgres DB should be rolled back.
         #[arg(long)]
         rollback_postgres: bool,
@@ -107,6 +117,10 @@ enum Command {
         /// Flag that allows to roll back already executed blocks. It's ultra dangerous and required only for fixing external nodes.
         #[arg(long)]
         allow_executed_block_reversion: bool,
+        /// Prints what would be deleted or reset in each of the selected storages as a JSON object
+        /// without changing anything.
+        #[arg(long)]
+        plan: bool,
     },
 
     /// Clears failed L1 transactions.
@@ -306,14 +320,16 @@ async fn main() -> anyhow::Result<()> {
         }
         Command::RollbackDB {
             l1_batch_number,
+            l2_block_number,
             rollback_postgres,
             rollback_tree,
             rollback_sk_cache,
             rollback_vm_runners_cache,
             rollback_snapshots,
             allow_executed_block_reversion,
+            plan,
         } => {
-            if !rollback_tree && rollback_postgres {
+            if !plan && l1_batch_number.is_some() && !rollback_tree && rollback_postgres {
                 println!("You want to roll back Postgres DB without rolling back tree.");
                 println!(
                     "If the tree is not yet rolled back to this L1 batch, then the only way \
@@ -328,7 +344,7 @@ async fn main() -> anyhow::Result<()> {
                 }
             }
 
-            if allow_executed_block_reversion {
+            if allow_executed_block_reversion && !plan {
                 println!("You want to roll back already executed blocks. It's impossible to restore them for the main node");
                 println!("Make sure you are doing it ONLY for external node");
                 println!("Are you sure? Print y/n");
@@ -338,6 +354,8 @@ async fn main() -> anyhow::Result<()> {
                 if input[0] != b'y' && input[0] != b'Y' {
                     std::process::exit(0);
                 }
+            }
+            if allow_executed_block_reversion {
                 block_reverter.allow_rolling_back_executed_batches();
             }
 
@@ -389,9 +407,29 @@ async fn main() -> anyhow::Result<()> {
                 }
             }
 
-            block_reverter
-                .roll_back(L1BatchNumber(l1_batch_number))
-                .await?;
+            match (l1_batch_number, l2_block_number) {
+                (Some(l1_batch_number), _) if plan => {
+                    let plan = block_reverter.plan(L1BatchNumber(l1_batch_number)).await?;
+                    println!("{}", serde_json::to_string_pretty(&plan)?);
+                }
+                (Some(l1_batch_number), _) => {
+                    block_reverter
+                        .roll_back(L1BatchNumber(l1_batch_number))
+                        .await?;
+                }
+                (None, Some(l2_block_number)) if plan => {
+                    let plan = block_reverter
+                        .plan_l2_blocks_rollback(L2BlockNumber(l2_block_number))
+                        .await?;
+                    println!("{}", serde_json::to_string_pretty(&plan)?);
+                }
+                (None, Some(l2_block_number)) => {
+                    block_reverter
+                        .roll_back_l2_blocks(L2BlockNumber(l2_block_number))
+                        .await?;
+                }
+                (None, None) => unreachable!("enforced by clap"),
+            }
         }
         Command::ClearFailedL1Transactions => {
             block_reverter.clear_failed_l1_transactions().await?;
@@ -0,0 +1,164 @@
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::Context as _;
+use clap::Parser;
+use serde::Deserialize;
+use zksync_config::{
+    configs::{GenesisConfigWrapper, PostgresSecrets},
+    full_config_schema,
+    sources::{ConfigFilePaths, ConfigSources},
+    EthConfig, PostgresConfig,
+};
+use zksync_dal::{ConnectionPool, Core};
+use zksync_eth_client::BaseFees;
+use zksync_eth_sender::replay::{self, ReplayParams, ReplayVariant};
+use zksync_types::{commitment::L1BatchCommitmentMode, L1BatchNumber};
+
+#[derive(Debug, Parser)]
+#[command(author = "Matter Labs", version, about = "Replays eth_sender decisions against historical L1 fees", long_about = None)]
+struct Cli {
+    /// Path to yaml config. If set, it will be used instead of env vars
+    #[arg(long)]
+    config_path: Option<PathBuf>,
+    /// Path to yaml secrets config. If set, it will be used instead of env vars
+    #[arg(long)]
+    secrets_path: Option<PathBuf>,
+    /// Path to yaml genesis config. If set, it will be used instead of env vars
+    #[arg(long)]
+    genesis_path: Option<PathBuf>,
+    /// First L1 batch to replay.
+    #[arg(long)]
+    from_l1_batch: u32,
+    /// Last L1 batch to replay (inclusive).
+    #[arg(long)]
+    to_l1_batch: u32,
+    /// Path to a JSON array with recorded L1 fees, one `{ "base_fee_per_gas": _, "base_fee_per_blob_gas": _ }`
+    /// object per L1 block.
+    #[arg(long)]
+    fee_history_path: PathBuf,
+    /// Path to a yaml file with config overrides (e.g., `eth.sender.max_aggregated_blocks_to_execute`) defining
+    /// a config variant to replay. Can be specified multiple times; the config without overrides is always replayed
+    /// as the `baseline` variant.
+    #[arg(long = "variant")]
+    variant_paths: Vec<PathBuf>,
+    /// Interval between L1 blocks in seconds.
+    #[arg(long, default_value_t = 12)]
+    l1_block_time_secs: u64,
+    /// Number of L1 blocks after inclusion until a transaction is considered finalized.
+    #[arg(long, default_value_t = 64)]
+    finality_blocks: u64,
+}
+
+impl Cli {
+    /// **Important.** This method is blocking.
+    fn config_sources(&self, variant_path: Option<&PathBuf>) -> anyhow::Result<ConfigSources> {
+        let config_file_paths = ConfigFilePaths {
+            general: self.config_path.clone(),
+            secrets: self.secrets_path.clone(),
+            genesis: self.genesis_path.clone(),
+            ..ConfigFilePaths::default()
+        };
+        let sources = config_file_paths.into_config_sources("ZKSYNC_")?;
+        match variant_path {
+            Some(path) => sources.with_yaml(path),
+            None => Ok(sources),
+        }
+    }
+
+    fn variant(&self, variant_path: Option<PathBuf>) -> anyhow::Result<ReplayVariant> {
+        let name = match &variant_path {
+            Some(path) => path
+                .file_stem()
+                .with_context(|| format!("invalid variant path: {path:?}"))?
+                .to_string_lossy()
+                .into_owned(),
+            None => "baseline".to_owned(),
+        };
+        let config_sources =
+            tokio::task::block_in_place(|| self.config_sources(variant_path.as_ref()))?;
+        let schema = full_config_schema();
+        let mut repo = config_sources.build_repository(&schema);
+        let eth: EthConfig = repo.parse()?;
+        Ok(ReplayVariant {
+            name,
+            sender: eth.sender,
+            gas_adjuster: eth.gas_adjuster,
+        })
+    }
+}
+
+/// Fees of a single L1 block as recorded in the fee history file.
+#[derive(Debug, Deserialize)]
+struct RecordedFees {
+    base_fee_per_gas: u64,
+    #[serde(default)]
+    base_fee_per_blob_gas: u64,
+}
+
+impl From<RecordedFees> for BaseFees {
+    fn from(fees: RecordedFees) -> Self {
+        Self {
+            base_fee_per_gas: fees.base_fee_per_gas,
+            base_fee_per_blob_gas: fees.base_fee_per_blob_gas.into(),
+            l2_pubdata_price: 0.into(),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opts = Cli::parse();
+
+    let config_sources = tokio::task::block_in_place(|| opts.config_sources(None))?;
+    let _guard = config_sources
+        .observability()?
+        .install_with_logs(zksync_vlog::Logs::disable_default_logs)?;
+
+    let schema = full_config_schema();
+    let mut repo = config_sources.build_repository(&schema);
+    let genesis_config = repo.parse::<GenesisConfigWrapper>()?.genesis;
+    let postgres_config: PostgresConfig = repo.parse()?;
+    let database_secrets: PostgresSecrets = repo.parse()?;
+    let commitment_mode = genesis_config.map_or(L1BatchCommitmentMode::Rollup, |config| {
+        config.l1_batch_commit_data_generator_mode
+    });
+
+    let fee_history = tokio::fs::read(&opts.fee_history_path)
+        .await
+        .with_context(|| {
+            format!(
+                "failed reading fee history from {:?}",
+                opts.fee_history_path
+            )
+        })?;
+    let fee_history: Vec<RecordedFees> =
+        serde_json::from_slice(&fee_history).context("failed parsing fee history")?;
+    let params = ReplayParams {
+        l1_batches: L1BatchNumber(opts.from_l1_batch)..=L1BatchNumber(opts.to_l1_batch),
+        fee_history: fee_history.into_iter().map(BaseFees::from).collect(),
+        l1_block_time: Duration::from_secs(opts.l1_block_time_secs),
+        finality_blocks: opts.finality_blocks,
+        commitment_mode,
+    };
+
+    let connection_pool = ConnectionPool::<Core>::builder(
+        database_secrets.master_url()?,
+        postgres_config.max_connections()?,
+    )
+    .build()
+    .await
+    .context("failed to build a connection pool")?;
+
+    let variant_paths = [None]
+        .into_iter()
+        .chain(opts.variant_paths.iter().cloned().map(Some));
+    for variant_path in variant_paths {
+        let variant = opts.variant(variant_path)?;
+        tracing::info!("Replaying variant `{}`", variant.name);
+        let report = replay::replay(&connection_pool, &params, &variant)
+            .await
+            .with_context(|| format!("failed replaying variant `{}`", variant.name))?;
+        println!("{report}");
+    }
+    Ok(())
+}
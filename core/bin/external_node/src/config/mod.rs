@@ -102,7 +102,7 @@ impl LocalConfig {
     fn mock(temp_dir: &tempfile::TempDir, test_pool: &ConnectionPool<Core>) -> Self {
         use zksync_config::configs::{
             consensus::ConsensusSecrets, database::MerkleTreeConfig, secrets::PostgresSecrets,
-            ContractVerifierSecrets, ExperimentalDBConfig,
+            ContractVerifierSecrets, ExperimentalDBConfig, RocksdbCheckpointsConfig,
         };
 
         let mut api = ApiConfig::for_tests();
@@ -118,6 +118,7 @@ impl LocalConfig {
                 state_keeper_db_path: temp_dir.path().join("state_keeper_cache"),
                 merkle_tree: MerkleTreeConfig::for_tests(temp_dir.path().join("tree")),
                 experimental: ExperimentalDBConfig::default(),
+                checkpoints: RocksdbCheckpointsConfig::default(),
             },
             prometheus: PrometheusConfig::default(),
             postgres: PostgresConfig {
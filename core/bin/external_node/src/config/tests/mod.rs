@@ -243,6 +243,7 @@ fn parsing_from_full_env() {
         EN_MERKLE_TREE_STALLED_WRITES_TIMEOUT_SEC=15
         # MIGRATION NEEDED: was `EN_MERKLE_TREE_REPAIR_STALE_KEYS` (w/o `experimental` infix)
         EN_EXPERIMENTAL_MERKLE_TREE_REPAIR_STALE_KEYS=true
+        EN_EXPERIMENTAL_MERKLE_TREE_CROSS_CHECK_PATH=/db/cross-check
         # NEW PARAMS: In MerkleTreeConfig; not used
         EN_MERKLE_TREE_MODE=Lightweight
 
@@ -275,6 +276,9 @@ fn parsing_from_full_env() {
         EN_PRUNING_CHUNK_SIZE=5
         EN_PRUNING_REMOVAL_DELAY_SEC=120
         EN_PRUNING_DATA_RETENTION_SEC=86400
+        EN_PRUNING_ARCHIVE_INTERVAL=500
+        EN_PRUNING_CALL_TRACES_RETENTION_SEC=3600
+        EN_PRUNING_STORAGE_LOGS_RETENTION_SEC=7200
 
         EN_GATEWAY_URL=https://127.0.0.1:3150/
         EN_BRIDGE_ADDRESSES_REFRESH_INTERVAL_SEC=300
@@ -290,6 +294,9 @@ fn parsing_from_full_env() {
         EN_HEALTHCHECK_PORT=2952
         EN_ETH_CLIENT_URL=https://127.0.0.1:8545/
         EN_MAIN_NODE_URL=https://127.0.0.1:3050/
+        EN_UPSTREAM_URLS=https://127.0.0.1:3060/,https://127.0.0.1:3070/
+        EN_UPSTREAM_MAX_LAG=5
+        EN_UPSTREAM_POLL_INTERVAL_SEC=3
         EN_STATE_CACHE_PATH=/db/state-keeper
         EN_MERKLE_TREE_PATH=/db/merkle-tree
 
@@ -307,6 +314,9 @@ fn parsing_from_full_env() {
         EN_API_WEB3_JSON_RPC_TREE_API_REQUEST_TIMEOUT_SEC=45
         # Tree component config
         EN_TREE_API_PORT=2955
+        EN_API_MERKLE_TREE_SECONDARY_PATH=/db/tree-replica
+        EN_API_MERKLE_TREE_REPLICA_CATCH_UP_INTERVAL_MS=500
+        EN_API_MERKLE_TREE_REPLICA_MAX_LAG=5
 
         EN_API_WEB3_JSON_RPC_SEND_RAW_TX_SYNC_MAX_TIMEOUT_MS=10000
         EN_API_WEB3_JSON_RPC_SEND_RAW_TX_SYNC_DEFAULT_TIMEOUT_MS=2000
@@ -387,6 +397,12 @@ fn test_parsing_general_config(source: impl ConfigSource + Clone) {
 
     let config: MerkleTreeApiConfig = tester.for_config().test_complete(source.clone()).unwrap();
     assert_eq!(config.port, 2955);
+    assert_eq!(
+        config.secondary_path.unwrap().as_os_str(),
+        "/db/tree-replica"
+    );
+    assert_eq!(config.replica_catch_up_interval, Duration::from_millis(500));
+    assert_eq!(config.replica_max_lag, 5);
 
     let db_config: DBConfig = tester.for_config().test_complete(source.clone()).unwrap();
     assert_eq!(
@@ -408,6 +424,10 @@ fn test_parsing_general_config(source: impl ConfigSource + Clone) {
 
     let config = db_config.experimental;
     assert!(config.merkle_tree_repair_stale_keys);
+    assert_eq!(
+        config.merkle_tree_cross_check_path.as_deref(),
+        Some(Path::new("/db/cross-check"))
+    );
     assert_eq!(
         config.state_keeper_db_block_cache_capacity,
         ByteSize(256 << 20)
@@ -476,6 +496,15 @@ fn test_parsing_general_config(source: impl ConfigSource + Clone) {
     assert_eq!(config.chunk_size, NonZeroU32::new(5).unwrap());
     assert_eq!(config.removal_delay, Duration::from_secs(120));
     assert_eq!(config.data_retention, Duration::from_secs(86_400));
+    assert_eq!(config.archive_interval, NonZeroU32::new(500));
+    assert_eq!(
+        config.call_traces_retention,
+        Some(Duration::from_secs(3_600))
+    );
+    assert_eq!(
+        config.storage_logs_retention,
+        Some(Duration::from_secs(7_200))
+    );
 
     let config: TimestampAsserterConfig =
         tester.for_config().test_complete(source.clone()).unwrap();
@@ -502,6 +531,17 @@ fn test_parsing_general_config(source: impl ConfigSource + Clone) {
     assert_eq!(config.gateway_chain_id, Some(SLChainId(277)));
     assert_eq!(config.l2_chain_id, L2ChainId::from(270));
     assert_eq!(config.main_node_url.expose_str(), "https://127.0.0.1:3050/");
+    let upstream_urls: Vec<_> = config
+        .upstream_urls
+        .iter()
+        .map(|url| url.expose_str())
+        .collect();
+    assert_eq!(
+        upstream_urls,
+        ["https://127.0.0.1:3060/", "https://127.0.0.1:3070/"]
+    );
+    assert_eq!(config.upstream_max_lag, 5);
+    assert_eq!(config.upstream_poll_interval, Duration::from_secs(3));
 
     let secrets: Secrets = tester.for_config().test(source.clone()).unwrap();
     assert_eq!(
@@ -1,4 +1,4 @@
-use std::{collections::HashSet, env, str::FromStr};
+use std::{collections::HashSet, env, str::FromStr, thread};
 
 use anyhow::Context as _;
 use clap::Parser;
@@ -6,8 +6,11 @@ use node_builder::ExternalNodeBuilder;
 use smart_config::Prefixed;
 use zksync_config::{
     cli::ConfigArgs,
+    configs::node_sync::ReorgRecoveryConfig,
     sources::{ConfigFilePaths, ConfigSources},
 };
+use zksync_node_framework::service::{TaskError, ZkStackServiceError};
+use zksync_reorg_detector::Error as ReorgDetectorError;
 use zksync_types::L1BatchNumber;
 
 use crate::config::{generate_consensus_secrets, ExternalNodeConfig, LocalConfig};
@@ -158,7 +161,7 @@ fn main() -> anyhow::Result<()> {
     let runtime = tokio_runtime()?;
 
     // Initial setup.
-    let opt = Cli::parse();
+    let mut opt = Cli::parse();
     let schema = LocalConfig::schema().context("Internal error: cannot build config schema")?;
     let config_sources = opt.config_sources(Some("EN_"))?;
 
@@ -170,7 +173,7 @@ fn main() -> anyhow::Result<()> {
     let repo = config_sources.build_repository(&schema);
 
     let mut revert_to_l1_batch = None;
-    if let Some(cmd) = opt.command {
+    if let Some(cmd) = opt.command.take() {
         match cmd {
             Command::GenerateSecrets => {
                 generate_consensus_secrets();
@@ -194,8 +197,82 @@ fn main() -> anyhow::Result<()> {
         return Ok(());
     }
 
-    let node = ExternalNodeBuilder::on_runtime(runtime, config)
-        .build(opt.components.0.into_iter().collect())?;
-    node.run(observability)?;
-    Ok(())
+    let components: Vec<_> = opt.components.0.iter().copied().collect();
+    if !config.local.node_sync.reorg_recovery.enabled {
+        let node = ExternalNodeBuilder::on_runtime(runtime, config).build(components)?;
+        node.run(observability)?;
+        return Ok(());
+    }
+
+    // With automatic reorg recovery, the node is restarted in-process after the reorg detector has detected a reorg.
+    // The rollback itself (bounded by the configured max depth) is performed by storage initialization on restart.
+    let recovery_config = config.local.node_sync.reorg_recovery.clone();
+    let mut initial_node_params = Some((runtime, config));
+    let result = run_with_reorg_recovery(&recovery_config, || {
+        let (runtime, config) = match initial_node_params.take() {
+            Some(params) => params,
+            None => {
+                let config_sources = opt.config_sources(Some("EN_"))?;
+                let config = ExternalNodeConfig::new(
+                    config_sources.build_repository(&schema),
+                    opt.enable_consensus,
+                )?;
+                (tokio_runtime()?, config)
+            }
+        };
+        let node = ExternalNodeBuilder::on_runtime(runtime, config).build(components.clone())?;
+        Ok(node.run(()))
+    });
+
+    // Make sure that the shutdown happens in the `tokio` context.
+    let runtime = tokio_runtime()?;
+    let _rt_guard = runtime.enter();
+    drop(observability);
+    result
+}
+
+/// Runs the node returned by `run_node`, restarting it after the reorg detector has detected a reorg. The number
+/// of restarts is bounded, and restarts are delayed with an exponential backoff.
+fn run_with_reorg_recovery(
+    recovery_config: &ReorgRecoveryConfig,
+    mut run_node: impl FnMut() -> anyhow::Result<Result<(), ZkStackServiceError>>,
+) -> anyhow::Result<()> {
+    let max_restarts = recovery_config.max_restarts;
+    let mut backoff = recovery_config.restart_backoff;
+    let mut restart_count = 0;
+    loop {
+        match run_node()? {
+            Err(err) if is_reorg_detected(&err) => {
+                if restart_count >= max_restarts {
+                    return Err(anyhow::Error::from(err).context(format!(
+                        "node was already restarted {restart_count} times after detected reorgs; \
+                         check the node state and restart it manually"
+                    )));
+                }
+                restart_count += 1;
+                tracing::warn!(
+                    "{err}; restarting the node in {backoff:?} to roll back storage \
+                     (restart {restart_count}/{max_restarts})"
+                );
+                thread::sleep(backoff);
+                backoff = backoff.saturating_mul(2);
+            }
+            result => return result.map_err(anyhow::Error::from),
+        }
+    }
+}
+
+/// Checks whether the node has stopped solely because the reorg detector has detected a reorg.
+fn is_reorg_detected(err: &ZkStackServiceError) -> bool {
+    let ZkStackServiceError::Task(errors) = err else {
+        return false;
+    };
+    !errors.0.is_empty()
+        && errors.0.iter().all(|err| match err {
+            TaskError::TaskFailed(_, err) => matches!(
+                err.downcast_ref::<ReorgDetectorError>(),
+                Some(ReorgDetectorError::ReorgDetected(_))
+            ),
+            _ => false,
+        })
 }
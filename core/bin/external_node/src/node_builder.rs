@@ -10,11 +10,7 @@ use zksync_block_reverter::{
 };
 use zksync_commitment_generator::node::CommitmentGeneratorLayer;
 use zksync_config::{
-    configs::{
-        api::{MerkleTreeApiConfig, Namespace},
-        database::MerkleTreeMode,
-        DataAvailabilitySecrets,
-    },
+    configs::{api::Namespace, database::MerkleTreeMode, DataAvailabilitySecrets},
     DAClientConfig,
 };
 use zksync_consistency_checker::node::ConsistencyCheckerLayer;
@@ -22,7 +18,10 @@ use zksync_da_clients::node::{
     AvailWiringLayer, CelestiaWiringLayer, EigenWiringLayer, NoDAClientWiringLayer,
     ObjectStorageClientWiringLayer,
 };
-use zksync_dal::node::{PoolsLayer, PostgresMetricsLayer};
+use zksync_dal::{
+    node::{PoolsLayer, PostgresMetricsLayer},
+    pruning_dal::PrunedDataCategory,
+};
 use zksync_eth_client::node::BridgeAddressesUpdaterLayer;
 use zksync_logs_bloom_backfill::node::LogsBloomBackfillLayer;
 use zksync_metadata_calculator::{
@@ -41,13 +40,17 @@ use zksync_node_db_pruner::node::PruningLayer;
 use zksync_node_fee_model::node::MainNodeFeeParamsFetcherLayer;
 use zksync_node_framework::service::{ZkStackService, ZkStackServiceBuilder};
 use zksync_node_storage_init::{
-    node::{external_node_strategy::ExternalNodeInitStrategyLayer, NodeStorageInitializerLayer},
-    SnapshotRecoveryConfig,
+    node::{
+        external_node_strategy::ExternalNodeInitStrategyLayer, NodeStorageInitializerLayer,
+        SnapshotArchiveServerLayer,
+    },
+    SnapshotArchiveConfig, SnapshotPeersConfig, SnapshotRecoveryConfig,
+    TreeCheckpointRestoreConfig,
 };
 use zksync_node_sync::node::{
     BatchStatusUpdaterLayer, BatchTransactionUpdaterLayer, DataAvailabilityFetcherLayer,
-    ExternalIOLayer, MiniblockPrecommitFetcherLayer, SyncStateUpdaterLayer, TreeDataFetcherLayer,
-    ValidateChainIdsLayer,
+    ExternalIOLayer, FastSyncLayer, MiniblockPrecommitFetcherLayer, SyncStateUpdaterLayer,
+    TreeDataFetcherLayer, UpstreamMonitorLayer, ValidateChainIdsLayer,
 };
 use zksync_reorg_detector::node::ReorgDetectorLayer;
 use zksync_settlement_layer_data::{ENConfig, SettlementLayerData};
@@ -137,8 +140,17 @@ impl ExternalNodeBuilder {
             networks.main_node_url.clone(),
             networks.main_node_rate_limit_rps,
             networks.l2_chain_id,
-        );
+        )
+        .with_upstreams(networks.upstream_urls.clone(), networks.upstream_max_lag);
         self.node.add_layer(layer);
+
+        if !networks.upstream_urls.is_empty() {
+            self.node.add_layer(UpstreamMonitorLayer::new(
+                networks.l1_chain_id,
+                networks.l2_chain_id,
+                networks.upstream_poll_interval,
+            ));
+        }
         Ok(self)
     }
 
@@ -216,7 +228,8 @@ impl ExternalNodeBuilder {
         let state_keeper_layer = StateKeeperLayer::new(
             self.config.local.db.state_keeper_db_path.clone(),
             rocksdb_options,
-        );
+        )
+        .with_checkpoints(self.config.local.db.checkpoints.clone());
         self.node
             .add_layer(io_layer)
             .add_layer(persistence_layer)
@@ -225,6 +238,17 @@ impl ExternalNodeBuilder {
         Ok(self)
     }
 
+    fn add_fast_sync_layer(mut self) -> anyhow::Result<Self> {
+        let config = self.config.local.node_sync.fast_sync.clone();
+        tracing::info!(
+            "Fast sync is enabled; L1 batches will be applied without re-executing transactions \
+             until the node is within {} batches from the main node",
+            config.tip_distance
+        );
+        self.node.add_layer(FastSyncLayer::new(config));
+        Ok(self)
+    }
+
     fn add_consensus_layer(mut self) -> anyhow::Result<Self> {
         let config = self.config.consensus.clone();
         let secrets = self.config.local.secrets.consensus.clone();
@@ -246,6 +270,12 @@ impl ExternalNodeBuilder {
                 config.removal_delay,
                 config.chunk_size.get(),
                 config.data_retention,
+            )
+            .with_archive_interval(config.archive_interval)
+            .with_category_retention(PrunedDataCategory::CallTraces, config.call_traces_retention)
+            .with_category_retention(
+                PrunedDataCategory::StorageLogs,
+                config.storage_logs_retention,
             );
             self.node.add_layer(layer);
         } else {
@@ -254,6 +284,15 @@ impl ExternalNodeBuilder {
         Ok(self)
     }
 
+    fn add_snapshot_archive_server_layer(mut self) -> anyhow::Result<Self> {
+        let config = &self.config.local.snapshot_recovery.serving;
+        if let Some(archive_path) = &config.archive_path {
+            let layer = SnapshotArchiveServerLayer::new(archive_path.clone(), config.bind_addr);
+            self.node.add_layer(layer);
+        }
+        Ok(self)
+    }
+
     fn add_validate_chain_ids_layer(mut self) -> anyhow::Result<Self> {
         let config = &self.config.local.networks;
         let layer = ValidateChainIdsLayer::new(config.l1_chain_id, config.l2_chain_id);
@@ -371,13 +410,12 @@ impl ExternalNodeBuilder {
             MetadataCalculatorConfig::from_configs(&config, state_keeper, &snapshot_recovery.tree);
 
         // Configure basic tree layer.
-        let mut layer = MetadataCalculatorLayer::new(metadata_calculator_config);
+        let mut layer = MetadataCalculatorLayer::new(metadata_calculator_config)
+            .with_checkpoints(self.config.local.db.checkpoints.clone());
 
         // Add tree API if needed.
         if with_tree_api {
-            let merkle_tree_api_config = MerkleTreeApiConfig {
-                port: self.config.local.api.merkle_tree.port,
-            };
+            let merkle_tree_api_config = self.config.local.api.merkle_tree.clone();
             layer = layer.with_tree_api_config(merkle_tree_api_config);
         }
 
@@ -392,6 +430,17 @@ impl ExternalNodeBuilder {
             layer = layer.with_stale_keys_repair();
         }
 
+        // Add tree cross-checking if requested.
+        if let Some(path) = &self
+            .config
+            .local
+            .db
+            .experimental
+            .merkle_tree_cross_check_path
+        {
+            layer = layer.with_cross_check(path.clone());
+        }
+
         // Add tree pruning if needed.
         let pruning = &self.config.local.pruning;
         if pruning.enabled {
@@ -412,9 +461,7 @@ impl ExternalNodeBuilder {
             include_indices_and_filters_in_block_cache: config
                 .include_indices_and_filters_in_block_cache,
         };
-        let api_config = MerkleTreeApiConfig {
-            port: self.config.local.api.merkle_tree.port,
-        };
+        let api_config = self.config.local.api.merkle_tree.clone();
         self.node
             .add_layer(TreeApiServerLayer::new(reader_config, api_config));
         Ok(self)
@@ -493,15 +540,46 @@ impl ExternalNodeBuilder {
     /// the precondition will prevent node from starting until the database is initialized.
     fn add_storage_initialization_layer(mut self, kind: LayerKind) -> anyhow::Result<Self> {
         let config = &self.config.local.snapshot_recovery;
-        let snapshot_recovery_config = config.enabled.then_some(SnapshotRecoveryConfig {
+        let db_config = &self.config.local.db;
+        let reorg_recovery = &self.config.local.node_sync.reorg_recovery;
+        let snapshot_archive_config = match &config.archive_path {
+            Some(path) => Some(SnapshotArchiveConfig {
+                path: path.clone(),
+                root_hash: config.archive_root_hash.context(
+                    "`snapshot_recovery.archive_root_hash` must be set if `archive_path` is set",
+                )?,
+            }),
+            None => None,
+        };
+        let snapshot_recovery_config = config.enabled.then(|| SnapshotRecoveryConfig {
             snapshot_l1_batch_override: config.l1_batch,
             drop_storage_key_preimages: config.drop_storage_key_preimages,
             object_store_config: config.object_store.clone(),
+            tree_verification_path: db_config
+                .merkle_tree
+                .path
+                .with_extension("snapshot_verification"),
+            archive: snapshot_archive_config,
+            peers: (!config.peers.is_empty()).then(|| SnapshotPeersConfig {
+                urls: config.peers.clone(),
+                request_timeout: config.peer_request_timeout,
+            }),
         });
+        let tree_checkpoint_restore_config = db_config
+            .checkpoints
+            .object_store
+            .clone()
+            .filter(|_| db_config.checkpoints.restore_on_init)
+            .map(|object_store_config| TreeCheckpointRestoreConfig {
+                tree_path: db_config.merkle_tree.path.clone(),
+                object_store_config,
+            });
         self.node.add_layer(ExternalNodeInitStrategyLayer {
             l2_chain_id: self.config.local.networks.l2_chain_id,
             max_postgres_concurrency: config.postgres.max_concurrency,
             snapshot_recovery_config,
+            tree_checkpoint_restore_config,
+            max_reorg_revert_depth: reorg_recovery.enabled.then_some(reorg_recovery.max_depth),
         });
         let mut layer = NodeStorageInitializerLayer::new();
         if matches!(kind, LayerKind::Precondition) {
@@ -694,6 +772,15 @@ impl ExternalNodeBuilder {
                         .add_data_availability_fetcher_layer()?;
                 }
                 Component::Core => {
+                    if self.config.local.node_sync.fast_sync.enabled {
+                        // Root hashes of fast-synced L1 batches are verified against the ones computed by the Merkle tree.
+                        anyhow::ensure!(
+                            components.contains(&Component::Tree),
+                            "Fast sync requires the Tree component to verify synced L1 batches"
+                        );
+                        self = self.add_fast_sync_layer()?;
+                    }
+
                     // Main tasks
                     self = self
                         .add_state_keeper_layer()?
@@ -704,7 +791,8 @@ impl ExternalNodeBuilder {
                         .add_batch_transaction_fetcher_layer()?
                         .add_transaction_finality_updater_layer()?
                         .add_miniblock_precommit_fetcher_layer()?
-                        .add_logs_bloom_backfill_layer()?;
+                        .add_logs_bloom_backfill_layer()?
+                        .add_snapshot_archive_server_layer()?;
                 }
             }
         }
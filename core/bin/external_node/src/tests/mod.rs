@@ -171,3 +171,70 @@ async fn reverting_node_state_no_op() {
     .expect("node panicked")
     .unwrap();
 }
+
+fn reorg_detected_error() -> ZkStackServiceError {
+    let err = ReorgDetectorError::ReorgDetected(L1BatchNumber(5));
+    ZkStackServiceError::Task(
+        vec![TaskError::TaskFailed("reorg_detector".into(), err.into())].into(),
+    )
+}
+
+#[test]
+fn detecting_reorg_errors() {
+    assert!(is_reorg_detected(&reorg_detected_error()));
+
+    let other_err = TaskError::TaskFailed("state_keeper".into(), anyhow::anyhow!("oops"));
+    let err = ZkStackServiceError::Task(vec![other_err].into());
+    assert!(!is_reorg_detected(&err));
+    assert!(!is_reorg_detected(&ZkStackServiceError::NoTasks));
+}
+
+fn reorg_recovery_config(max_restarts: u32) -> ReorgRecoveryConfig {
+    ReorgRecoveryConfig {
+        enabled: true,
+        max_depth: 10,
+        max_restarts,
+        restart_backoff: Duration::ZERO,
+    }
+}
+
+#[test]
+fn node_is_restarted_after_detected_reorg() {
+    let mut run_count = 0;
+    run_with_reorg_recovery(&reorg_recovery_config(3), || {
+        run_count += 1;
+        Ok(if run_count <= 2 {
+            Err(reorg_detected_error())
+        } else {
+            Ok(())
+        })
+    })
+    .unwrap();
+    assert_eq!(run_count, 3);
+}
+
+#[test]
+fn node_restarts_after_reorgs_are_bounded() {
+    let mut run_count = 0;
+    let err = run_with_reorg_recovery(&reorg_recovery_config(2), || {
+        run_count += 1;
+        Ok(Err(reorg_detected_error()))
+    })
+    .unwrap_err();
+    assert_eq!(run_count, 3);
+    assert!(
+        format!("{err:#}").contains("restarted 2 times"),
+        "Unexpected error: {err:#}"
+    );
+}
+
+#[test]
+fn node_is_not_restarted_after_other_errors() {
+    let mut run_count = 0;
+    run_with_reorg_recovery(&reorg_recovery_config(3), || {
+        run_count += 1;
+        Ok(Err(ZkStackServiceError::NoTasks))
+    })
+    .unwrap_err();
+    assert_eq!(run_count, 1);
+}
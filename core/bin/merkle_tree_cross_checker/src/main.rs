@@ -0,0 +1,186 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use anyhow::Context as _;
+use clap::Parser;
+use serde::Serialize;
+use tempfile::TempDir;
+use zksync_config::{configs::PostgresSecrets, full_config_schema, sources::ConfigFilePaths};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_merkle_tree::domain::ZkSyncTree;
+use zksync_metadata_calculator::{
+    load_l1_batch_tree_instructions, TreeCrossChecker, TreeDivergence,
+};
+use zksync_storage::RocksDB;
+use zksync_types::L1BatchNumber;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Merkle tree cross-checker",
+    long_about = "Replays storage logs for L1 batches from Postgres into the legacy and ZK OS Merkle trees \
+                  and compares their leaves and enumeration indices after each batch"
+)]
+struct Cli {
+    /// Directory to store the replayed trees in. If the directory already contains trees from a previous run,
+    /// replaying continues from the last L1 batch processed by both trees. If not specified, a temporary directory is used.
+    #[arg(long)]
+    db_path: Option<PathBuf>,
+    /// Last L1 batch to cross-check (inclusive). If not specified, all sealed L1 batches are checked.
+    #[arg(long = "until-l1-batch")]
+    until_l1_batch: Option<u32>,
+    /// Path to write a JSON divergence report to. If not specified, the report is printed to stdout.
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// Path to the configuration file.
+    #[arg(long)]
+    config_path: Option<PathBuf>,
+    /// Path to the secrets file.
+    #[arg(long)]
+    secrets_path: Option<PathBuf>,
+}
+
+/// Cross-checking report.
+#[derive(Debug, Serialize)]
+struct CrossCheckReport {
+    /// First L1 batch cross-checked during this run.
+    first_l1_batch: L1BatchNumber,
+    /// Last L1 batch cross-checked during this run, or `None` if no batches were checked.
+    last_checked_l1_batch: Option<L1BatchNumber>,
+    /// First divergence between the trees, if any.
+    divergence: Option<TreeDivergence>,
+}
+
+impl Cli {
+    async fn run(self, pool: &ConnectionPool<Core>) -> anyhow::Result<()> {
+        let temp_dir;
+        let db_path = if let Some(path) = &self.db_path {
+            path.as_path()
+        } else {
+            temp_dir = TempDir::new().context("failed creating temporary directory")?;
+            temp_dir.path()
+        };
+        tracing::info!("Replaying Merkle trees at {db_path:?}");
+
+        let (mut legacy_tree, mut checker) = open_trees(db_path)?;
+        let first_l1_batch = checker.next_l1_batch_number()?;
+
+        let mut storage = pool.connection_tagged("merkle_tree_cross_checker").await?;
+        let earliest_l1_batch = storage
+            .blocks_dal()
+            .get_earliest_l1_batch_number()
+            .await?
+            .context("Postgres contains no L1 batches")?;
+        anyhow::ensure!(
+            earliest_l1_batch <= first_l1_batch,
+            "earliest L1 batch in Postgres is #{earliest_l1_batch}, but replaying requires L1 batches \
+             starting from #{first_l1_batch}; snapshot-recovered or pruned databases are not supported"
+        );
+        let last_l1_batch = match self.until_l1_batch {
+            Some(number) => L1BatchNumber(number),
+            None => storage
+                .blocks_dal()
+                .get_sealed_l1_batch_number()
+                .await?
+                .context("Postgres contains no sealed L1 batches")?,
+        };
+        tracing::info!("Cross-checking L1 batches #{first_l1_batch}..=#{last_l1_batch}");
+
+        let started_at = Instant::now();
+        let mut report = CrossCheckReport {
+            first_l1_batch,
+            last_checked_l1_batch: None,
+            divergence: None,
+        };
+        for number in first_l1_batch.0..=last_l1_batch.0 {
+            let l1_batch_number = L1BatchNumber(number);
+            let instructions = load_l1_batch_tree_instructions(&mut storage, l1_batch_number)
+                .await?
+                .with_context(|| format!("L1 batch #{l1_batch_number} is missing in Postgres"))?;
+            legacy_tree
+                .process_l1_batch(&instructions)
+                .with_context(|| format!("failed processing L1 batch #{l1_batch_number}"))?;
+            legacy_tree.save().context("failed saving legacy tree")?;
+
+            report.divergence =
+                checker.check_l1_batch(l1_batch_number, &instructions, &legacy_tree.reader())?;
+            report.last_checked_l1_batch = Some(l1_batch_number);
+            if report.divergence.is_some() {
+                break;
+            }
+            tracing::info!("Cross-checked L1 batch #{l1_batch_number}");
+        }
+        tracing::info!("Finished cross-checking in {:?}", started_at.elapsed());
+
+        let serialized_report =
+            serde_json::to_string_pretty(&report).context("failed serializing report")?;
+        if let Some(output) = &self.output {
+            fs::write(output, serialized_report)
+                .with_context(|| format!("failed writing report to {output:?}"))?;
+        } else {
+            println!("{serialized_report}");
+        }
+
+        if let Some(divergence) = report.divergence {
+            anyhow::bail!("Merkle trees diverge: {divergence}");
+        }
+        Ok(())
+    }
+}
+
+/// Opens the legacy and ZK OS trees at the specified directory and brings them to the same L1 batch.
+fn open_trees(db_path: &Path) -> anyhow::Result<(ZkSyncTree, TreeCrossChecker)> {
+    let db = RocksDB::new(&db_path.join("legacy"))
+        .context("failed initializing legacy Merkle tree RocksDB")?;
+    let mut legacy_tree =
+        ZkSyncTree::new_lightweight(db.into()).context("cannot initialize legacy Merkle tree")?;
+    let mut checker = TreeCrossChecker::open(&db_path.join("zk_os"))?;
+
+    let legacy_next_l1_batch = legacy_tree.next_l1_batch_number();
+    let zk_os_next_l1_batch = checker.next_l1_batch_number()?;
+    if zk_os_next_l1_batch < legacy_next_l1_batch {
+        tracing::info!("Rolling back legacy Merkle tree to L1 batch #{zk_os_next_l1_batch}");
+        let last_l1_batch_to_keep = zk_os_next_l1_batch.checked_sub(1).with_context(|| {
+            format!(
+                "ZK OS Merkle tree is empty, while the legacy tree is not; remove {:?} to replay trees from scratch",
+                db_path.join("legacy")
+            )
+        })?;
+        legacy_tree.roll_back_logs(L1BatchNumber(last_l1_batch_to_keep))?;
+    } else if legacy_next_l1_batch < zk_os_next_l1_batch {
+        tracing::info!("Truncating ZK OS Merkle tree to L1 batch #{legacy_next_l1_batch}");
+        checker.truncate(legacy_next_l1_batch)?;
+    }
+    Ok((legacy_tree, checker))
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let config_file_paths = ConfigFilePaths {
+        general: cli.config_path.clone(),
+        secrets: cli.secrets_path.clone(),
+        ..ConfigFilePaths::default()
+    };
+    let config_sources =
+        tokio::task::spawn_blocking(|| config_file_paths.into_config_sources("ZKSYNC_")).await??;
+    let _observability_guard = config_sources.observability()?.install()?;
+
+    let schema = full_config_schema();
+    let mut repo = config_sources.build_repository(&schema);
+    let database_secrets: PostgresSecrets = repo.parse()?;
+    let pool = ConnectionPool::<Core>::singleton(
+        database_secrets
+            .master_url()
+            .context("Master DB URL is absent")?,
+    )
+    .build()
+    .await?;
+
+    cli.run(&pool).await
+}
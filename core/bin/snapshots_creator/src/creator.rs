@@ -25,6 +25,8 @@ use crate::tests::HandleEvent;
 struct SnapshotProgress {
     version: SnapshotVersion,
     l1_batch_number: L1BatchNumber,
+    /// L1 batch of the base snapshot for incremental snapshots.
+    base_l1_batch_number: Option<L1BatchNumber>,
     /// `true` if the snapshot is new (i.e., its progress is not recovered from Postgres).
     is_new_snapshot: bool,
     chunk_count: u64,
@@ -32,10 +34,16 @@ struct SnapshotProgress {
 }
 
 impl SnapshotProgress {
-    fn new(version: SnapshotVersion, l1_batch_number: L1BatchNumber, chunk_count: u64) -> Self {
+    fn new(
+        version: SnapshotVersion,
+        l1_batch_number: L1BatchNumber,
+        base_l1_batch_number: Option<L1BatchNumber>,
+        chunk_count: u64,
+    ) -> Self {
         Self {
             version,
             l1_batch_number,
+            base_l1_batch_number,
             is_new_snapshot: true,
             chunk_count,
             remaining_chunk_ids: (0..chunk_count).collect(),
@@ -53,6 +61,7 @@ impl SnapshotProgress {
         Self {
             version: snapshot.version,
             l1_batch_number: snapshot.l1_batch_number,
+            base_l1_batch_number: snapshot.base_l1_batch_number,
             is_new_snapshot: false,
             chunk_count: snapshot.storage_logs_filepaths.len() as u64,
             remaining_chunk_ids,
@@ -82,6 +91,7 @@ impl SnapshotCreator {
         semaphore: &Semaphore,
         progress: &SnapshotProgress,
         l2_block_number: L2BlockNumber,
+        base_l2_block_number: Option<L2BlockNumber>,
         chunk_id: u64,
     ) -> anyhow::Result<()> {
         let chunk_count = progress.chunk_count;
@@ -136,6 +146,29 @@ impl SnapshotCreator {
                 self.store_storage_logs_chunk(l1_batch_number, chunk_id, logs)
                     .await?
             }
+            SnapshotVersion::Version2 => {
+                let base_l2_block_number =
+                    base_l2_block_number.context("incremental snapshot has no base")?;
+                let logs = conn
+                    .snapshots_creator_dal()
+                    .get_changed_storage_logs_chunk(
+                        base_l2_block_number,
+                        l2_block_number,
+                        l1_batch_number,
+                        hashed_keys_range,
+                    )
+                    .await
+                    .context("error fetching changed storage logs")?;
+                drop(conn);
+
+                let latency = latency.observe();
+                tracing::info!(
+                    "Loaded chunk {chunk_id} ({} changed logs) from Postgres in {latency:?}",
+                    logs.len()
+                );
+                self.store_storage_logs_chunk(l1_batch_number, chunk_id, logs)
+                    .await?
+            }
         };
 
         let mut master_conn = self
@@ -189,6 +222,7 @@ impl SnapshotCreator {
     async fn process_factory_deps(
         &self,
         l2_block_number: L2BlockNumber,
+        base_l2_block_number: Option<L2BlockNumber>,
         l1_batch_number: L1BatchNumber,
     ) -> anyhow::Result<String> {
         let mut conn = self.connect_to_replica().await?;
@@ -196,10 +230,15 @@ impl SnapshotCreator {
         tracing::info!("Loading factory deps from Postgres...");
         let latency =
             METRICS.factory_deps_processing_duration[&FactoryDepsStage::LoadFromPostgres].start();
-        let factory_deps = conn
-            .snapshots_creator_dal()
-            .get_all_factory_deps(l2_block_number)
-            .await?;
+        let factory_deps = if let Some(base_l2_block_number) = base_l2_block_number {
+            conn.snapshots_creator_dal()
+                .get_new_factory_deps(base_l2_block_number, l2_block_number)
+                .await?
+        } else {
+            conn.snapshots_creator_dal()
+                .get_all_factory_deps(l2_block_number)
+                .await?
+        };
         drop(conn);
         let latency = latency.observe();
         tracing::info!("Loaded {} factory deps in {latency:?}", factory_deps.len());
@@ -240,7 +279,7 @@ impl SnapshotCreator {
         min_chunk_count: u64,
         conn: &mut Connection<'_, Core>,
     ) -> anyhow::Result<Option<SnapshotProgress>> {
-        let snapshot_version = SnapshotVersion::try_from(config.version)
+        let mut snapshot_version = SnapshotVersion::try_from(config.version)
             .context("invalid snapshot version specified in config")?;
 
         // Sanity check: the selected L1 batch should have Merkle tree data; otherwise, it could be impossible
@@ -255,27 +294,61 @@ impl SnapshotCreator {
                 )
             })?;
 
-        let distinct_storage_logs_keys_count = conn
-            .snapshots_creator_dal()
-            .get_distinct_storage_logs_keys_count(l1_batch_number)
-            .await?;
+        let mut base_l1_batch_number = None;
+        if snapshot_version.is_incremental() {
+            let complete_snapshots = conn.snapshots_dal().get_all_complete_snapshots().await?;
+            base_l1_batch_number = complete_snapshots
+                .snapshots_l1_batch_numbers
+                .into_iter()
+                .find(|&number| number < l1_batch_number);
+            if base_l1_batch_number.is_none() {
+                tracing::info!(
+                    "No complete snapshots preceding L1 batch #{l1_batch_number}; creating a full snapshot instead of an incremental one"
+                );
+                snapshot_version = SnapshotVersion::Version1;
+            }
+        }
+
+        let storage_logs_count = if let Some(base_l1_batch_number) = base_l1_batch_number {
+            let base_l2_block_number =
+                Self::last_l2_block_in_batch(conn, base_l1_batch_number).await?;
+            let l2_block_number = Self::last_l2_block_in_batch(conn, l1_batch_number).await?;
+            conn.snapshots_creator_dal()
+                .get_changed_storage_logs_count(base_l2_block_number, l2_block_number)
+                .await?
+        } else {
+            conn.snapshots_creator_dal()
+                .get_distinct_storage_logs_keys_count(l1_batch_number)
+                .await?
+        };
         let chunk_size = config.storage_logs_chunk_size;
         // We force the minimum number of chunks to avoid situations where only one chunk is created in tests.
-        let chunk_count = distinct_storage_logs_keys_count
-            .div_ceil(chunk_size)
-            .max(min_chunk_count);
+        let chunk_count = storage_logs_count.div_ceil(chunk_size).max(min_chunk_count);
 
         tracing::info!(
-            "Selected storage logs chunking for L1 batch {l1_batch_number}: \
+            "Selected storage logs chunking for L1 batch {l1_batch_number} (base: {base_l1_batch_number:?}): \
             {chunk_count} chunks of expected size {chunk_size}"
         );
         Ok(Some(SnapshotProgress::new(
             snapshot_version,
             l1_batch_number,
+            base_l1_batch_number,
             chunk_count,
         )))
     }
 
+    async fn last_l2_block_in_batch(
+        conn: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<L2BlockNumber> {
+        let (_, last_l2_block_number) = conn
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(l1_batch_number)
+            .await?
+            .with_context(|| format!("No L2 blocks for L1 batch #{l1_batch_number}"))?;
+        Ok(last_l2_block_number)
+    }
+
     /// Returns `Ok(None)` if a snapshot should not be created / resumed.
     async fn load_or_initialize_snapshot_progress(
         &self,
@@ -364,11 +437,14 @@ impl SnapshotCreator {
         };
 
         let mut conn = self.connect_to_replica().await?;
-        let (_, last_l2_block_number_in_batch) = conn
-            .blocks_dal()
-            .get_l2_block_range_of_l1_batch(progress.l1_batch_number)
-            .await?
-            .context("No L2 blocks for L1 batch")?;
+        let last_l2_block_number_in_batch =
+            Self::last_l2_block_in_batch(&mut conn, progress.l1_batch_number).await?;
+        let base_l2_block_number = match progress.base_l1_batch_number {
+            Some(base_l1_batch_number) => {
+                Some(Self::last_l2_block_in_batch(&mut conn, base_l1_batch_number).await?)
+            }
+            None => None,
+        };
         drop(conn);
 
         METRICS.storage_logs_chunks_count.set(progress.chunk_count);
@@ -380,7 +456,11 @@ impl SnapshotCreator {
 
         if progress.is_new_snapshot {
             let factory_deps_output_file = self
-                .process_factory_deps(last_l2_block_number_in_batch, progress.l1_batch_number)
+                .process_factory_deps(
+                    last_l2_block_number_in_batch,
+                    base_l2_block_number,
+                    progress.l1_batch_number,
+                )
                 .await?;
 
             let mut master_conn = self
@@ -394,6 +474,7 @@ impl SnapshotCreator {
                     progress.l1_batch_number,
                     progress.chunk_count,
                     &factory_deps_output_file,
+                    progress.base_l1_batch_number,
                 )
                 .await?;
         }
@@ -411,6 +492,7 @@ impl SnapshotCreator {
                     &semaphore,
                     &progress,
                     last_l2_block_number_in_batch,
+                    base_l2_block_number,
                     chunk_id,
                 )
             });
@@ -0,0 +1,134 @@
+//! Export of snapshots into single-file archives.
+
+use std::path::Path;
+
+use anyhow::Context as _;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_object_store::{
+    Bucket, ObjectStore, SnapshotArchiveManifest, SnapshotArchiveMetadata, SnapshotArchiveWriter,
+    StoredObject,
+};
+use zksync_types::{
+    snapshots::{
+        SnapshotFactoryDependencies, SnapshotHeader, SnapshotStorageLogsChunk,
+        SnapshotStorageLogsChunkMetadata, SnapshotStorageLogsStorageKey,
+    },
+    L1BatchNumber, H256,
+};
+
+/// Exports a complete full snapshot into a single-file archive at the specified `path`. If `l1_batch_number`
+/// is not specified, the newest complete snapshot is exported.
+///
+/// Incremental snapshots cannot be exported since they are not self-contained.
+pub(crate) async fn export_snapshot(
+    pool: &ConnectionPool<Core>,
+    blob_store: &dyn ObjectStore,
+    l1_batch_number: Option<L1BatchNumber>,
+    path: &Path,
+) -> anyhow::Result<SnapshotArchiveManifest> {
+    let mut conn = pool.connection_tagged("snapshots_creator").await?;
+    let l1_batch_number = match l1_batch_number {
+        Some(number) => number,
+        None => {
+            let snapshots = conn.snapshots_dal().get_all_complete_snapshots().await?;
+            *snapshots
+                .snapshots_l1_batch_numbers
+                .first()
+                .context("there are no complete snapshots to export")?
+        }
+    };
+    let snapshot = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(l1_batch_number)
+        .await?
+        .with_context(|| format!("snapshot for L1 batch #{l1_batch_number} doesn't exist"))?;
+    anyhow::ensure!(
+        snapshot.is_complete(),
+        "snapshot for L1 batch #{l1_batch_number} is not complete"
+    );
+    anyhow::ensure!(
+        !snapshot.version.is_incremental(),
+        "snapshot for L1 batch #{l1_batch_number} is incremental (based on L1 batch #{:?}); only full snapshots can be exported",
+        snapshot.base_l1_batch_number
+    );
+
+    let l1_batch_header = conn
+        .blocks_dal()
+        .get_l1_batch_header(l1_batch_number)
+        .await?
+        .with_context(|| format!("L1 batch #{l1_batch_number} is missing"))?;
+    let l1_batch_root_hash = conn
+        .blocks_dal()
+        .get_l1_batch_state_root(l1_batch_number)
+        .await?
+        .with_context(|| format!("root hash for L1 batch #{l1_batch_number} is missing"))?;
+    let (_, l2_block_number) = conn
+        .blocks_dal()
+        .get_l2_block_range_of_l1_batch(l1_batch_number)
+        .await?
+        .with_context(|| format!("missing L2 blocks for L1 batch #{l1_batch_number}"))?;
+    let l2_block_header = conn
+        .blocks_dal()
+        .get_l2_block_header(l2_block_number)
+        .await?
+        .with_context(|| format!("L2 block #{l2_block_number} is missing"))?;
+    let protocol_version = l2_block_header
+        .protocol_version
+        .with_context(|| format!("L2 block #{l2_block_number} doesn't have protocol version"))?;
+    let tokens = conn
+        .tokens_web3_dal()
+        .get_all_tokens(Some(l2_block_number))
+        .await?;
+    drop(conn);
+
+    let mut writer = SnapshotArchiveWriter::create(path).await?;
+    let factory_deps_key = SnapshotFactoryDependencies::encode_key(l1_batch_number);
+    copy_object(blob_store, &mut writer, &factory_deps_key).await?;
+    let mut storage_logs_chunks = Vec::with_capacity(snapshot.storage_logs_filepaths.len());
+    for chunk_id in 0..snapshot.storage_logs_filepaths.len() as u64 {
+        let key = SnapshotStorageLogsChunk::<H256>::encode_key(SnapshotStorageLogsStorageKey {
+            l1_batch_number,
+            chunk_id,
+        });
+        copy_object(blob_store, &mut writer, &key).await?;
+        storage_logs_chunks.push(SnapshotStorageLogsChunkMetadata {
+            chunk_id,
+            filepath: key,
+        });
+    }
+
+    let metadata = SnapshotArchiveMetadata {
+        header: SnapshotHeader {
+            version: snapshot.version.into(),
+            l1_batch_number,
+            l2_block_number,
+            storage_logs_chunks,
+            factory_deps_filepath: factory_deps_key,
+            base_l1_batch_number: None,
+        },
+        l1_batch_timestamp: l1_batch_header.timestamp,
+        l1_batch_root_hash,
+        l2_block_timestamp: l2_block_header.timestamp,
+        l2_block_hash: l2_block_header.hash,
+        protocol_version,
+        tokens,
+    };
+    let manifest = writer.finish(metadata).await?;
+    tracing::info!(
+        "Exported snapshot for L1 batch #{l1_batch_number} with root hash {l1_batch_root_hash:?} to `{}`",
+        path.display()
+    );
+    Ok(manifest)
+}
+
+async fn copy_object(
+    blob_store: &dyn ObjectStore,
+    writer: &mut SnapshotArchiveWriter,
+    key: &str,
+) -> anyhow::Result<()> {
+    let data = blob_store
+        .get_raw(Bucket::StorageSnapshot, key)
+        .await
+        .with_context(|| format!("failed getting snapshot object `{key}`"))?;
+    writer.add(key, &data).await
+}
@@ -8,6 +8,12 @@
 //!
 //! It is assumed that the snapshot creator is run as a singleton process (no more than 1 instance
 //! at a time).
+//!
+//! # Export
+//!
+//! If the `--export-path` arg is specified, the creator doesn't create a new snapshot. Instead, it exports
+//! an existing complete snapshot into a single-file archive, which can be used to recover a node without
+//! access to the object store or the main node API.
 
 use anyhow::Context as _;
 use structopt::StructOpt;
@@ -24,6 +30,7 @@ use zksync_object_store::ObjectStoreFactory;
 use crate::creator::SnapshotCreator;
 
 mod creator;
+mod export;
 mod metrics;
 #[cfg(test)]
 mod tests;
@@ -55,6 +62,11 @@ struct Opt {
     /// Path to the secrets file.
     #[structopt(long)]
     secrets_path: Option<std::path::PathBuf>,
+
+    /// Instead of creating a new snapshot, export an existing complete snapshot to a single-file archive at the specified path.
+    /// The snapshot is selected using the `l1_batch_number` config param; if it's not set, the newest complete snapshot is exported.
+    #[structopt(long)]
+    export_path: Option<std::path::PathBuf>,
 }
 
 #[tokio::main]
@@ -94,18 +106,28 @@ async fn main() -> anyhow::Result<()> {
     .build()
     .await?;
 
-    let master_pool = ConnectionPool::<Core>::singleton(database_secrets.master_url()?)
-        .build()
+    if let Some(export_path) = &opt.export_path {
+        export::export_snapshot(
+            &replica_pool,
+            &*blob_store,
+            creator_config.l1_batch_number,
+            export_path,
+        )
         .await?;
-
-    let creator = SnapshotCreator {
-        blob_store,
-        master_pool,
-        replica_pool,
-        #[cfg(test)]
-        event_listener: Box::new(()),
-    };
-    creator.run(creator_config, MIN_CHUNK_COUNT).await?;
+    } else {
+        let master_pool = ConnectionPool::<Core>::singleton(database_secrets.master_url()?)
+            .build()
+            .await?;
+
+        let creator = SnapshotCreator {
+            blob_store,
+            master_pool,
+            replica_pool,
+            #[cfg(test)]
+            event_listener: Box::new(()),
+        };
+        creator.run(creator_config, MIN_CHUNK_COUNT).await?;
+    }
 
     tracing::info!("Finished running snapshot creator!");
     stop_sender.send(true).ok();
@@ -13,14 +13,14 @@ use rand::{thread_rng, Rng};
 use test_casing::test_casing;
 use zksync_config::{ObjectStoreConfig, SnapshotsCreatorConfig};
 use zksync_dal::{Connection, CoreDal};
-use zksync_object_store::{MockObjectStore, ObjectStore};
+use zksync_object_store::{MockObjectStore, ObjectStore, SnapshotArchive};
 use zksync_types::{
     block::{L1BatchHeader, L1BatchTreeData, L2BlockHeader},
     commitment::PubdataParams,
     settlement::SettlementLayer,
     snapshots::{
         SnapshotFactoryDependencies, SnapshotFactoryDependency, SnapshotStorageLog,
-        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey, SnapshotVersion,
     },
     AccountTreeId, Address, L1BatchNumber, L2BlockNumber, ProtocolVersion, StorageKey, StorageLog,
     H256,
@@ -643,3 +643,184 @@ async fn creator_fails_if_specified_l1_batch_is_missing() {
         .await
         .unwrap_err();
 }
+
+#[tokio::test]
+async fn persisting_incremental_snapshot() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let base_l1_batch_number = L1BatchNumber(4);
+    let mut config = test_config();
+    config.l1_batch_number = Some(base_l1_batch_number);
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    let config = SnapshotsCreatorConfig {
+        version: 2,
+        ..test_config()
+    };
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert!(snapshot_metadata.is_complete(), "{snapshot_metadata:#?}");
+    assert_eq!(snapshot_metadata.version, SnapshotVersion::Version2);
+    assert_eq!(
+        snapshot_metadata.base_l1_batch_number,
+        Some(base_l1_batch_number)
+    );
+
+    // Storage logs are never overwritten in `prepare_postgres()`, so changed logs are exactly the new ones.
+    let mut actual_logs = HashSet::new();
+    for chunk_id in 0..MIN_CHUNK_COUNT {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: snapshot_l1_batch_number,
+            chunk_id,
+        };
+        let chunk: SnapshotStorageLogsChunk = object_store.get(key).await.unwrap();
+        actual_logs.extend(chunk.storage_logs);
+    }
+    let expected_logs: HashSet<_> = expected_outputs
+        .storage_logs
+        .iter()
+        .filter(|log| log.l1_batch_number_of_initial_write > base_l1_batch_number)
+        .cloned()
+        .collect();
+    assert_eq!(actual_logs, expected_logs);
+
+    let SnapshotFactoryDependencies { factory_deps } =
+        object_store.get(base_l1_batch_number).await.unwrap();
+    let base_deps: HashSet<_> = factory_deps.into_iter().collect();
+    let SnapshotFactoryDependencies { factory_deps } =
+        object_store.get(snapshot_l1_batch_number).await.unwrap();
+    let new_deps: HashSet<_> = factory_deps.into_iter().collect();
+    assert_eq!(new_deps.len(), 40);
+    assert!(base_deps.is_disjoint(&new_deps));
+    let all_deps: HashSet<_> = base_deps.union(&new_deps).cloned().collect();
+    assert_eq!(all_deps, expected_outputs.deps);
+}
+
+#[tokio::test]
+async fn incremental_snapshot_without_base_is_full() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let config = SnapshotsCreatorConfig {
+        version: 2,
+        ..test_config()
+    };
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert_eq!(snapshot_metadata.version, SnapshotVersion::Version1);
+    assert_eq!(snapshot_metadata.base_l1_batch_number, None);
+    assert_storage_logs(&*object_store, snapshot_l1_batch_number, &expected_outputs).await;
+}
+
+#[tokio::test]
+async fn exporting_snapshot_to_archive() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(test_config(), MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let archive_path = temp_dir.path().join("snapshot.bin");
+    let manifest = export::export_snapshot(&pool, &*object_store, None, &archive_path)
+        .await
+        .unwrap();
+    let metadata = &manifest.snapshot;
+    assert_eq!(metadata.header.l1_batch_number, snapshot_l1_batch_number);
+    assert_eq!(metadata.header.l2_block_number, L2BlockNumber(8));
+    assert_eq!(
+        metadata.header.storage_logs_chunks.len(),
+        MIN_CHUNK_COUNT as usize
+    );
+    assert_eq!(metadata.l1_batch_root_hash, H256::zero());
+    assert_eq!(
+        metadata.l2_block_hash,
+        H256::from_low_u64_be(u64::from(metadata.header.l2_block_number.0))
+    );
+
+    let archive = SnapshotArchive::open(&archive_path).await.unwrap();
+    assert_eq!(*archive.manifest(), manifest);
+    assert_storage_logs(&archive, snapshot_l1_batch_number, &expected_outputs).await;
+    let SnapshotFactoryDependencies { factory_deps } = (&archive as &dyn ObjectStore)
+        .get(snapshot_l1_batch_number)
+        .await
+        .unwrap();
+    let actual_deps: HashSet<_> = factory_deps.into_iter().collect();
+    assert_eq!(actual_deps, expected_outputs.deps);
+
+    // The archive cannot be overwritten.
+    export::export_snapshot(&pool, &*object_store, None, &archive_path)
+        .await
+        .unwrap_err();
+}
+
+#[tokio::test]
+async fn incremental_snapshot_cannot_be_exported() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let mut config = test_config();
+    config.l1_batch_number = Some(L1BatchNumber(4));
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let config = SnapshotsCreatorConfig {
+        version: 2,
+        ..test_config()
+    };
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let archive_path = temp_dir.path().join("snapshot.bin");
+    let err = export::export_snapshot(&pool, &*object_store, None, &archive_path)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("incremental"), "{err:#}");
+
+    export::export_snapshot(&pool, &*object_store, Some(L1BatchNumber(4)), &archive_path)
+        .await
+        .unwrap();
+}
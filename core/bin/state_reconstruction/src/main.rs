@@ -0,0 +1,145 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use anyhow::Context as _;
+use clap::Parser;
+use zksync_config::{
+    configs::{da_client::DAClientConfig, GenesisConfigWrapper, L1Secrets, PostgresSecrets},
+    full_config_schema,
+    sources::ConfigFilePaths,
+    ContractsConfig, PostgresConfig,
+};
+use zksync_da_client::DataAvailabilityClient;
+use zksync_da_clients::object_store::ObjectStoreDAClient;
+use zksync_dal::{ConnectionPool, Core};
+use zksync_eth_client::clients::{Client, L1};
+use zksync_state_reconstruction::{ReconstructionParams, StateReconstructor};
+use zksync_types::{commitment::L1BatchCommitmentMode, L1BatchNumber};
+
+#[derive(Debug, Parser)]
+#[command(author = "Matter Labs", version, about = "Reconstructs state from pubdata committed on L1", long_about = None)]
+struct Cli {
+    /// Path to yaml config. If set, it will be used instead of env vars
+    #[arg(long)]
+    config_path: Option<PathBuf>,
+    /// Path to yaml contracts config. If set, it will be used instead of env vars
+    #[arg(long)]
+    contracts_config_path: Option<PathBuf>,
+    /// Path to yaml secrets config. If set, it will be used instead of env vars
+    #[arg(long)]
+    secrets_path: Option<PathBuf>,
+    /// Path to yaml genesis config. If set, it will be used instead of env vars
+    #[arg(long)]
+    genesis_path: Option<PathBuf>,
+    /// Path to the RocksDB directory with the reconstructed Merkle tree. Reconstruction resumes
+    /// from the last L1 batch in the tree.
+    #[arg(long)]
+    merkle_tree_path: PathBuf,
+    /// L1 block to start looking for commit transactions from.
+    #[arg(long, default_value_t = 0)]
+    from_l1_block: u64,
+    /// Max number of L1 blocks to query logs for in a single request.
+    #[arg(long, default_value_t = 50_000)]
+    logs_block_range: u64,
+    /// Last L1 batch to reconstruct. If not set, all executed L1 batches are reconstructed.
+    #[arg(long)]
+    to_l1_batch: Option<u32>,
+    /// URL of the beacon node API; required if pubdata was published in blobs.
+    #[arg(long)]
+    beacon_api_url: Option<String>,
+    /// Duration of a beacon chain slot in seconds.
+    #[arg(long, default_value_t = 12)]
+    seconds_per_slot: u64,
+    /// Path to a JSON object mapping L1 batch numbers to blob IDs on the DA layer (validium chains only),
+    /// e.g. exported from the `data_availability` table of the main node.
+    #[arg(long)]
+    da_blob_ids_path: Option<PathBuf>,
+}
+
+async fn create_da_client(
+    config: Option<DAClientConfig>,
+) -> anyhow::Result<Option<Box<dyn DataAvailabilityClient>>> {
+    Ok(match config {
+        None | Some(DAClientConfig::NoDA) => None,
+        Some(DAClientConfig::ObjectStore(config)) => {
+            Some(Box::new(ObjectStoreDAClient::new(config).await?))
+        }
+        Some(_) => {
+            anyhow::bail!("fetching pubdata is only supported for the object store DA client")
+        }
+    })
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opts = Cli::parse();
+
+    let config_file_paths = ConfigFilePaths {
+        general: opts.config_path,
+        secrets: opts.secrets_path,
+        genesis: opts.genesis_path,
+        contracts: opts.contracts_config_path,
+        ..ConfigFilePaths::default()
+    };
+    let config_sources =
+        tokio::task::spawn_blocking(|| config_file_paths.into_config_sources("ZKSYNC_")).await??;
+
+    let _guard = config_sources
+        .observability()?
+        .install_with_logs(zksync_vlog::Logs::disable_default_logs)?;
+
+    let schema = full_config_schema();
+    let mut repo = config_sources.build_repository(&schema);
+    let genesis_config = repo.parse::<GenesisConfigWrapper>()?.genesis;
+    let contracts: ContractsConfig = repo.parse()?;
+    let da_client_config: Option<DAClientConfig> = repo.parse_opt()?;
+    let postgres_config: PostgresConfig = repo.parse()?;
+    let database_secrets: PostgresSecrets = repo.parse()?;
+    let l1_secrets: L1Secrets = repo.parse()?;
+
+    let commitment_mode = genesis_config.map_or(L1BatchCommitmentMode::Rollup, |config| {
+        config.l1_batch_commit_data_generator_mode
+    });
+    let da_blob_ids = match &opts.da_blob_ids_path {
+        Some(path) => {
+            let json = tokio::fs::read(path)
+                .await
+                .with_context(|| format!("failed reading DA blob IDs from {path:?}"))?;
+            let blob_ids: HashMap<u32, String> =
+                serde_json::from_slice(&json).context("failed parsing DA blob IDs")?;
+            blob_ids
+                .into_iter()
+                .map(|(number, blob_id)| (L1BatchNumber(number), blob_id))
+                .collect()
+        }
+        None => HashMap::new(),
+    };
+    let params = ReconstructionParams {
+        diamond_proxy_addr: contracts.l1.diamond_proxy_addr,
+        commitment_mode,
+        from_l1_block: opts.from_l1_block,
+        logs_block_range: opts.logs_block_range,
+        to_l1_batch: opts.to_l1_batch.map(L1BatchNumber),
+        merkle_tree_path: opts.merkle_tree_path,
+        beacon_api_url: opts.beacon_api_url,
+        seconds_per_slot: opts.seconds_per_slot,
+        da_blob_ids,
+    };
+
+    let l1_client: Client<L1> = Client::http(l1_secrets.l1_rpc_url.context("no L1 RPC URL")?)
+        .context("Ethereum client")?
+        .build();
+    let da_client = create_da_client(da_client_config).await?;
+    let connection_pool = ConnectionPool::<Core>::builder(
+        database_secrets.master_url()?,
+        postgres_config.max_connections()?,
+    )
+    .build()
+    .await
+    .context("failed to build a connection pool")?;
+
+    let reconstructor =
+        StateReconstructor::new(params, Box::new(l1_client), connection_pool, da_client)?;
+    let last_l1_batch = reconstructor.run().await?;
+    tracing::info!("State is reconstructed and verified up to L1 batch #{last_l1_batch}");
+    Ok(())
+}
@@ -21,7 +21,7 @@ use zksync_config::{
             ecosystem::L1SpecificContracts,
             SettlementLayerSpecificContracts,
         },
-        da_client::DAClientConfig,
+        da_client::{redundant::RedundantDABackend, DAClientConfig},
         secrets::DataAvailabilitySecrets,
         snapshot_recovery::TreeRecoveryConfig,
         wallets::Wallets,
@@ -32,7 +32,7 @@ use zksync_config::{
 use zksync_contract_verification_server::node::ContractVerificationApiLayer;
 use zksync_da_clients::node::{
     AvailWiringLayer, CelestiaWiringLayer, EigenWiringLayer, NoDAClientWiringLayer,
-    ObjectStorageClientWiringLayer,
+    ObjectStorageClientWiringLayer, RedundantDAClientWiringLayer,
 };
 use zksync_da_dispatcher::node::DataAvailabilityDispatcherLayer;
 use zksync_dal::node::{PoolsLayer, PostgresMetricsLayer};
@@ -48,8 +48,8 @@ use zksync_gateway_migrator::node::GatewayMigratorLayer;
 use zksync_house_keeper::node::HouseKeeperLayer;
 use zksync_logs_bloom_backfill::node::LogsBloomBackfillLayer;
 use zksync_metadata_calculator::{
-    node::{MetadataCalculatorLayer, TreeApiClientLayer},
-    MetadataCalculatorConfig,
+    node::{MetadataCalculatorLayer, TreeApiClientLayer, TreeApiServerLayer},
+    MerkleTreeReaderConfig, MetadataCalculatorConfig,
 };
 use zksync_node_api_server::{
     node::{
@@ -63,8 +63,9 @@ use zksync_node_api_server::{
 use zksync_node_consensus::node::MainNodeConsensusLayer;
 use zksync_node_fee_model::node::{GasAdjusterLayer, L1GasLayer};
 use zksync_node_framework::service::{ZkStackService, ZkStackServiceBuilder};
-use zksync_node_storage_init::node::{
-    main_node_strategy::MainNodeInitStrategyLayer, NodeStorageInitializerLayer,
+use zksync_node_storage_init::{
+    node::{main_node_strategy::MainNodeInitStrategyLayer, NodeStorageInitializerLayer},
+    TreeCheckpointRestoreConfig,
 };
 use zksync_object_store::node::ObjectStoreLayer;
 use zksync_proof_data_handler::node::ProofDataHandlerLayer;
@@ -127,6 +128,14 @@ impl MainNodeBuilder {
                 DAClientConfig::Celestia(_) => PubdataType::Celestia,
                 DAClientConfig::Eigen(_) => PubdataType::Eigen,
                 DAClientConfig::ObjectStore(_) => PubdataType::ObjectStore,
+                DAClientConfig::Redundant(config) => match config.primary {
+                    Some(RedundantDABackend::Avail) => PubdataType::Avail,
+                    Some(RedundantDABackend::Celestia) => PubdataType::Celestia,
+                    Some(RedundantDABackend::Eigen) => PubdataType::Eigen,
+                    Some(RedundantDABackend::ObjectStore) => PubdataType::ObjectStore,
+                    // Without a primary backend, inclusion data isn't verified on the settlement layer.
+                    None => PubdataType::NoDA,
+                },
                 DAClientConfig::NoDA => PubdataType::NoDA,
             }),
         }
@@ -163,6 +172,10 @@ impl MainNodeBuilder {
             Some(DAClientConfig::Avail(_)) => L2DACommitmentScheme::PubdataKeccak256,
             Some(DAClientConfig::Celestia(_)) => L2DACommitmentScheme::PubdataKeccak256,
             Some(DAClientConfig::Eigen(_)) => L2DACommitmentScheme::PubdataKeccak256,
+            Some(DAClientConfig::Redundant(config)) => match config.primary {
+                None | Some(RedundantDABackend::ObjectStore) => L2DACommitmentScheme::EmptyNoDA,
+                Some(_) => L2DACommitmentScheme::PubdataKeccak256,
+            },
             None => {
                 tracing::info!("DAClientConfig is not specified, setting L2DACommitmentScheme to BlobsAndPubdataKeccak256");
                 L2DACommitmentScheme::BlobsAndPubdataKeccak256
@@ -211,11 +224,17 @@ impl MainNodeBuilder {
         let gas_adjuster = try_load_config!(self.configs.eth).gas_adjuster;
         let operator = try_load_config!(self.wallets.operator);
         let blob_operator = self.wallets.blob_operator.clone();
-        self.node.add_layer(PKSigningEthClientLayer::new(
-            gas_adjuster,
-            operator,
-            blob_operator,
-        ));
+        self.node.add_layer(
+            PKSigningEthClientLayer::new(gas_adjuster, operator, blob_operator)
+                .with_additional_operators(
+                    self.wallets.additional_operators.clone(),
+                    self.wallets.additional_blob_operators.clone(),
+                )
+                .with_additional_gcp_kms_operators(
+                    self.wallets.additional_gcp_kms_operators.clone(),
+                    self.wallets.additional_gcp_kms_blob_operators.clone(),
+                ),
+        );
         Ok(self)
     }
 
@@ -276,15 +295,44 @@ impl MainNodeBuilder {
             &state_keeper_config,
             &TreeRecoveryConfig::default(), // Tree recovery is not relevant for the main node
         );
-        let mut layer = MetadataCalculatorLayer::new(metadata_calculator_config);
+        let mut layer = MetadataCalculatorLayer::new(metadata_calculator_config)
+            .with_checkpoints(self.configs.db_config.checkpoints.clone());
         if with_tree_api {
             let merkle_tree_api_config = try_load_config!(self.configs.api_config).merkle_tree;
             layer = layer.with_tree_api_config(merkle_tree_api_config);
         }
+        if let Some(path) = &self
+            .configs
+            .db_config
+            .experimental
+            .merkle_tree_cross_check_path
+        {
+            layer = layer.with_cross_check(path.clone());
+        }
         self.node.add_layer(layer);
         Ok(self)
     }
 
+    fn add_tree_api_replica_layer(mut self) -> anyhow::Result<Self> {
+        let config = &self.configs.db_config.merkle_tree;
+        let reader_config = MerkleTreeReaderConfig {
+            db_path: config.path.clone(),
+            max_open_files: config.max_open_files,
+            multi_get_chunk_size: config.multi_get_chunk_size,
+            block_cache_capacity: config.block_cache_size.0 as usize,
+            include_indices_and_filters_in_block_cache: config
+                .include_indices_and_filters_in_block_cache,
+        };
+        let api_config = try_load_config!(self.configs.api_config).merkle_tree;
+        anyhow::ensure!(
+            api_config.secondary_path.is_some(),
+            "Merkle tree API cannot be started without a tree component, unless `api.merkle_tree.secondary_path` is set"
+        );
+        self.node
+            .add_layer(TreeApiServerLayer::new(reader_config, api_config));
+        Ok(self)
+    }
+
     fn add_state_keeper_layer(mut self) -> anyhow::Result<Self> {
         // Bytecode compression is currently mandatory for the transactions processed by the sequencer.
         const OPTIONAL_BYTECODE_COMPRESSION: bool = false;
@@ -318,7 +366,8 @@ impl MainNodeBuilder {
             max_open_files: db_config.experimental.state_keeper_db_max_open_files,
         };
         let state_keeper_layer =
-            StateKeeperLayer::new(db_config.state_keeper_db_path, rocksdb_options);
+            StateKeeperLayer::new(db_config.state_keeper_db_path, rocksdb_options)
+                .with_checkpoints(db_config.checkpoints);
         self.node
             .add_layer(persistence_layer)
             .add_layer(mempool_io_layer)
@@ -454,7 +503,9 @@ impl MainNodeBuilder {
             ),
             self.configs.timestamp_asserter_config.clone(),
         );
-        let layer = layer.with_vm_mode(vm_config.api_fast_vm_mode);
+        let layer = layer
+            .with_vm_mode(vm_config.api_fast_vm_mode)
+            .with_da_backpressure(sk_config.da_backpressure.clone());
         self.node.add_layer(layer);
         Ok(self)
     }
@@ -634,6 +685,22 @@ impl MainNodeBuilder {
             return Ok(self);
         }
 
+        if let DAClientConfig::Redundant(mut config) = da_client_config {
+            let secrets = match self.secrets.data_availability.clone() {
+                None => None,
+                Some(DataAvailabilitySecrets::Redundant(secrets)) => Some(secrets),
+                Some(_) => bail!("invalid pair of da_client and da_secrets"),
+            };
+            if let Some(eigen_config) = &mut config.eigen {
+                if eigen_config.eigenda_eth_rpc.is_none() {
+                    eigen_config.eigenda_eth_rpc = self.secrets.l1.l1_rpc_url.clone();
+                }
+            }
+            self.node
+                .add_layer(RedundantDAClientWiringLayer::new(config, secrets));
+            return Ok(self);
+        }
+
         let da_client_secrets = try_load_config!(self.secrets.data_availability);
         match (da_client_config, da_client_secrets) {
             (DAClientConfig::Avail(config), DataAvailabilitySecrets::Avail(secret)) => {
@@ -759,9 +826,21 @@ impl MainNodeBuilder {
     fn add_storage_initialization_layer(mut self, kind: LayerKind) -> anyhow::Result<Self> {
         let eth_watcher_config = try_load_config!(self.configs.eth).watcher;
 
+        let db_config = &self.configs.db_config;
+        let tree_checkpoint_restore_config = db_config
+            .checkpoints
+            .object_store
+            .clone()
+            .filter(|_| db_config.checkpoints.restore_on_init)
+            .map(|object_store_config| TreeCheckpointRestoreConfig {
+                tree_path: db_config.merkle_tree.path.clone(),
+                object_store_config,
+            });
+
         self.node.add_layer(MainNodeInitStrategyLayer {
             genesis: self.genesis_config.clone(),
             event_expiration_blocks: eth_watcher_config.event_expiration_blocks,
+            tree_checkpoint_restore_config,
         });
         let mut layer = NodeStorageInitializerLayer::new();
         if matches!(kind, LayerKind::Precondition) {
@@ -866,11 +945,12 @@ impl MainNodeBuilder {
                     self = self.add_metadata_calculator_layer(with_tree_api)?;
                 }
                 Component::TreeApi => {
-                    anyhow::ensure!(
-                        components.contains(&Component::Tree),
-                        "Merkle tree API cannot be started without a tree component"
-                    );
-                    // Do nothing, will be handled by the `Tree` component.
+                    if components.contains(&Component::Tree) {
+                        // Do nothing, will be handled by the `Tree` component.
+                    } else {
+                        // Serve the tree from a secondary RocksDB instance.
+                        self = self.add_tree_api_replica_layer()?;
+                    }
                 }
                 Component::EthProofManager => {
                     self = self.add_eth_proof_manager_layer()?;
@@ -31,6 +31,39 @@ pub struct DataAvailabilityBlob {
     pub sent_at: DateTime<Utc>,
 }
 
+/// Represents a chunk of a blob split because of the blob size limit of the data availability layer.
+#[derive(Debug, Clone)]
+pub struct DataAvailabilityChunk {
+    pub l1_batch_number: L1BatchNumber,
+    pub chunk_index: usize,
+    pub dispatch_request_id: String,
+    pub blob_id: Option<String>,
+    pub inclusion_data: Option<Vec<u8>>,
+    pub sent_at: DateTime<Utc>,
+}
+
+/// Represents the state of a blob dispatched to one of the backends of a redundant DA client.
+#[derive(Debug, Clone)]
+pub struct DataAvailabilityBackendBlob {
+    pub l1_batch_number: L1BatchNumber,
+    pub backend: String,
+    pub dispatch_request_id: Option<String>,
+    pub blob_id: Option<String>,
+    pub inclusion_data: Option<Vec<u8>>,
+    /// Last dispatch error, if the blob couldn't be dispatched to the backend.
+    pub error: Option<String>,
+    pub sent_at: Option<DateTime<Utc>>,
+}
+
+/// Sealed L1 batches that are not committed and await inclusion in the data availability layer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DataAvailabilityBacklog {
+    /// Number of L1 batches awaiting DA inclusion.
+    pub pending_l1_batches: u32,
+    /// Seal time of the oldest L1 batch awaiting DA inclusion.
+    pub oldest_pending_sealed_at: Option<DateTime<Utc>>,
+}
+
 /// Represents the data availability details of a certain batch. Intended to be used in the API.
 /// This struct is only used once blob_id is confirmed.
 #[derive(Debug, Clone)]
@@ -2,6 +2,7 @@ use std::{
     collections::{HashMap, HashSet},
     net::{Ipv6Addr, SocketAddr},
     num::{NonZeroU32, NonZeroUsize},
+    path::PathBuf,
     str::FromStr,
     time::Duration,
 };
@@ -41,7 +42,10 @@ impl ApiConfig {
                 hard_time_limit: None,
                 expose_config: false,
             },
-            merkle_tree: MerkleTreeApiConfig { port: 3053 },
+            merkle_tree: MerkleTreeApiConfig {
+                port: 3053,
+                ..MerkleTreeApiConfig::default()
+            },
         }
     }
 }
@@ -475,10 +479,23 @@ impl ContractVerificationApiConfig {
 
 /// Configuration for the Merkle tree API.
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+#[config(derive(Default))]
 pub struct MerkleTreeApiConfig {
     /// Port to bind the Merkle tree API server to.
     #[config(default_t = 3_072)]
     pub port: u16,
+    /// Directory for a secondary RocksDB instance. If set, a standalone Merkle tree API server (i.e., one running
+    /// without the tree component) opens the tree RocksDB as a secondary instance in this directory and periodically catches up
+    /// with the primary instance. This allows running the server concurrently with the tree component, e.g. in another process.
+    /// The directory must differ from the tree DB path.
+    pub secondary_path: Option<PathBuf>,
+    /// Interval between catching up the secondary RocksDB instance with the primary one. Only used if `secondary_path` is set.
+    #[config(default_t = Duration::from_secs(1))]
+    pub replica_catch_up_interval: Duration,
+    /// Maximum lag (in L1 batches) of the secondary instance relative to the primary tree, after which the server is reported
+    /// as affected in the health check. Only used if `secondary_path` is set.
+    #[config(default_t = 10)]
+    pub replica_max_lag: u32,
 }
 
 #[cfg(test)]
@@ -565,7 +582,12 @@ mod tests {
                 hard_time_limit: Some(Duration::from_millis(2_000)),
                 expose_config: true,
             },
-            merkle_tree: MerkleTreeApiConfig { port: 8082 },
+            merkle_tree: MerkleTreeApiConfig {
+                port: 8082,
+                secondary_path: Some("/db/tree-replica".into()),
+                replica_catch_up_interval: Duration::from_millis(500),
+                replica_max_lag: 5,
+            },
         }
     }
 
@@ -620,6 +642,9 @@ mod tests {
             API_HEALTHCHECK_HARD_TIME_LIMIT_MS=2000
             API_HEALTHCHECK_EXPOSE_CONFIG=true
             API_MERKLE_TREE_PORT=8082
+            API_MERKLE_TREE_SECONDARY_PATH=/db/tree-replica
+            API_MERKLE_TREE_REPLICA_CATCH_UP_INTERVAL_MS=500
+            API_MERKLE_TREE_REPLICA_MAX_LAG=5
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -687,6 +712,9 @@ mod tests {
             expose_config: true
           merkle_tree:
             port: 8082
+            secondary_path: /db/tree-replica
+            replica_catch_up_interval_ms: 500
+            replica_max_lag: 5
         "#;
 
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
@@ -753,6 +781,9 @@ mod tests {
             expose_config: true
           merkle_tree:
             port: 8082
+            secondary_path: /db/tree-replica
+            replica_catch_up_interval: 500ms
+            replica_max_lag: 5
         "#;
 
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
@@ -1,4 +1,4 @@
-use std::{collections::HashSet, time::Duration};
+use std::{collections::HashSet, num::NonZeroU32, time::Duration};
 
 use serde::{Deserialize, Serialize};
 use smart_config::{
@@ -175,6 +175,9 @@ pub struct StateKeeperConfig {
     /// Allowed deployers for L2 transactions.
     #[config(nest)]
     pub deployment_allowlist: Option<DeploymentAllowlist>,
+    /// Backpressure applied if L1 batches are not included in the DA layer in time.
+    #[config(nest)]
+    pub da_backpressure: DaBackpressureConfig,
 }
 
 impl StateKeeperConfig {
@@ -199,10 +202,34 @@ impl StateKeeperConfig {
             fee_model_version: FeeModelVersion::V2,
             validation_computational_gas_limit: 300000,
             deployment_allowlist: None,
+            da_backpressure: DaBackpressureConfig::default(),
         }
     }
 }
 
+/// Backpressure applied by the state keeper if the DA layer lags behind. Once one of the configured thresholds
+/// for L1 batches awaiting DA inclusion is exceeded, the state keeper stops including new transactions
+/// (and the API rejects submitted transactions) until the backlog is reduced. Backpressure is disabled
+/// if no thresholds are configured.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+#[config(derive(Default))]
+pub struct DaBackpressureConfig {
+    /// Max number of sealed L1 batches awaiting DA inclusion.
+    pub max_pending_l1_batches: Option<NonZeroU32>,
+    /// Max age of the oldest sealed L1 batch awaiting DA inclusion.
+    pub max_pending_l1_batch_age: Option<Duration>,
+    /// Interval between checking the DA backlog.
+    #[config(default_t = Duration::from_secs(10))]
+    pub check_interval: Duration,
+}
+
+impl DaBackpressureConfig {
+    /// Checks whether backpressure is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.max_pending_l1_batches.is_some() || self.max_pending_l1_batch_age.is_some()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 #[config(derive(Default))]
 pub struct CircuitBreakerConfig {
@@ -319,6 +346,11 @@ mod tests {
                 http_file_url: "http://deployment-allowlist/".to_owned(),
                 refresh_interval: Duration::from_secs(120),
             })),
+            da_backpressure: DaBackpressureConfig {
+                max_pending_l1_batches: NonZeroU32::new(20),
+                max_pending_l1_batch_age: Some(Duration::from_secs(1800)),
+                check_interval: Duration::from_secs(5),
+            },
         }
     }
 
@@ -353,6 +385,9 @@ mod tests {
             CHAIN_STATE_KEEPER_DEPLOYMENT_ALLOWLIST_SOURCE=Dynamic
             CHAIN_STATE_KEEPER_DEPLOYMENT_ALLOWLIST_HTTP_FILE_URL=http://deployment-allowlist/
             CHAIN_STATE_KEEPER_DEPLOYMENT_ALLOWLIST_REFRESH_INTERVAL=2 min
+            CHAIN_STATE_KEEPER_DA_BACKPRESSURE_MAX_PENDING_L1_BATCHES=20
+            CHAIN_STATE_KEEPER_DA_BACKPRESSURE_MAX_PENDING_L1_BATCH_AGE=30 min
+            CHAIN_STATE_KEEPER_DA_BACKPRESSURE_CHECK_INTERVAL=5s
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -393,6 +428,10 @@ mod tests {
             source: Url
             http_file_url: http://deployment-allowlist/
             refresh_interval_secs: 120
+          da_backpressure:
+            max_pending_l1_batches: 20
+            max_pending_l1_batch_age_sec: 1800
+            check_interval_ms: 5000
         "#;
 
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
@@ -432,6 +471,10 @@ mod tests {
             source: Url
             http_file_url: http://deployment-allowlist/
             refresh_interval: 2min
+          da_backpressure:
+            max_pending_l1_batches: 20
+            max_pending_l1_batch_age: 30 min
+            check_interval: 5s
         "#;
 
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
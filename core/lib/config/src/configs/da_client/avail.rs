@@ -5,7 +5,10 @@ use smart_config::{
     metadata::TimeUnit,
     DescribeConfig, DeserializeConfig,
 };
-use zksync_basic_types::secrets::{APIKey, SeedPhrase};
+use zksync_basic_types::{
+    secrets::{APIKey, SeedPhrase},
+    Address,
+};
 
 #[derive(Clone, Debug, PartialEq, DescribeConfig, DeserializeConfig)]
 #[config(tag = "avail_client_type")]
@@ -24,6 +27,9 @@ pub struct AvailConfig {
     /// further restrict via [`Self::timeout`].
     #[config(default_t = Duration::from_secs(60))]
     pub api_client_timeout: Duration,
+    /// Address of the VectorX contract on the settlement layer attesting Avail data roots. Required to verify
+    /// inclusion data (see `da_dispatcher.verify_inclusion_data`).
+    pub vectorx_contract_address: Option<Address>,
     #[config(flatten)]
     pub config: AvailClientConfig,
 }
@@ -1,7 +1,13 @@
 use std::time::Duration;
 
-use smart_config::{de::FromSecretString, DescribeConfig, DeserializeConfig};
-use zksync_basic_types::secrets::PrivateKey;
+use smart_config::{
+    de::{FromSecretString, Optional},
+    DescribeConfig, DeserializeConfig,
+};
+use zksync_basic_types::{
+    secrets::{APIKey, PrivateKey},
+    Address,
+};
 
 #[derive(Clone, Debug, PartialEq, DescribeConfig, DeserializeConfig)]
 pub struct CelestiaConfig {
@@ -10,10 +16,19 @@ pub struct CelestiaConfig {
     pub chain_id: String,
     #[config(default_t = Duration::from_secs(30))]
     pub timeout: Duration,
+    /// Address of the Blobstream contract on L1 attesting Celestia blocks. Required to verify inclusion data
+    /// (see `da_dispatcher.verify_inclusion_data`).
+    pub blobstream_contract_address: Option<Address>,
+    /// URL of the JSON-RPC API of a Celestia data availability node (bridge, full or light node) used to fetch
+    /// blob inclusion proofs. Required if `blobstream_contract_address` is set.
+    pub node_rpc_url: Option<String>,
 }
 
 #[derive(Clone, Debug, DescribeConfig, DeserializeConfig)]
 pub struct CelestiaSecrets {
     #[config(with = FromSecretString)]
     pub private_key: PrivateKey,
+    /// Auth token for the Celestia node JSON-RPC API (see `node_rpc_url`).
+    #[config(with = Optional(FromSecretString))]
+    pub node_rpc_auth_token: Option<APIKey>,
 }
@@ -1,10 +1,12 @@
 use smart_config::{DescribeConfig, DeserializeConfig};
 
+use self::redundant::RedundantDAConfig;
 use crate::{AvailConfig, CelestiaConfig, EigenConfig, ObjectStoreConfig};
 
 pub mod avail;
 pub mod celestia;
 pub mod eigen;
+pub mod redundant;
 
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 #[config(tag = "client")]
@@ -13,6 +15,8 @@ pub enum DAClientConfig {
     Celestia(CelestiaConfig),
     Eigen(EigenConfig),
     ObjectStore(ObjectStoreConfig),
+    /// Dispatches each blob to several DA layers.
+    Redundant(RedundantDAConfig),
     #[config(alias = "NoDa")]
     NoDA,
 }
@@ -33,7 +37,7 @@ mod tests {
         ConfigRepository, ConfigSchema, Environment, Yaml,
     };
 
-    use super::{avail::AvailClientConfig, *};
+    use super::{avail::AvailClientConfig, redundant::RedundantDABackend, *};
     use crate::configs::{object_store::ObjectStoreMode, DataAvailabilitySecrets, Secrets};
 
     #[test]
@@ -52,6 +56,10 @@ mod tests {
           DA_MODE="GCS"
           DA_MAX_RETRIES="5"
           DA_LOCAL_MIRROR_PATH="/var/cache"
+          DA_ENCRYPTION_KEYS_PATH="/etc/da_keys.json"
+          DA_ENCRYPTION_KEY_ID="1"
+          DA_ENCRYPTION_COMPRESSION_LEVEL="3"
+          DA_ENCRYPTION_ALLOW_PLAINTEXT_OBJECTS="true"
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -76,6 +84,11 @@ mod tests {
           file_backed_base_path: ./chains/era/artifacts/
           max_retries: 10
           local_mirror_path: /var/cache
+          encryption:
+            keys_path: /etc/da_keys.json
+            key_id: "1"
+            compression_level: 3
+            allow_plaintext_objects: true
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
 
@@ -159,6 +172,7 @@ mod tests {
           max_retries: 4
           referer_header: zksync
           dispatch_timeout: 2s
+          vectorx_contract_address: "0x0000000000000000000000000000000000000123"
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
 
@@ -259,6 +273,7 @@ mod tests {
           api_node_url: wss://turing-rpc.avail.so/ws
           app_id: 123456
           max_blocks_to_look_back: 5
+          vectorx_contract_address: "0x0000000000000000000000000000000000000123"
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
 
@@ -403,4 +418,38 @@ mod tests {
             .unwrap();
         assert_eigen_config(&config);
     }
+
+    #[test]
+    fn redundant_config_from_yaml() {
+        let yaml = r#"
+          client: Redundant
+          primary: Celestia
+          min_successful_backends: 2
+          celestia:
+            api_node_url: http://localhost:9090
+            namespace: "0x1234567890abcdef"
+            chain_id: mocha-4
+          object_store:
+            mode: FileBacked
+            file_backed_base_path: ./chains/era/artifacts/
+            max_retries: 10
+        "#;
+        let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
+
+        let config = test::<DAClientConfig>(yaml).unwrap();
+        let DAClientConfig::Redundant(config) = config else {
+            panic!("unexpected config: {config:?}");
+        };
+        assert_eq!(config.primary, Some(RedundantDABackend::Celestia));
+        assert_eq!(
+            config.backends(),
+            [
+                RedundantDABackend::Celestia,
+                RedundantDABackend::ObjectStore
+            ]
+        );
+        assert_eq!(config.required_backends(), 2);
+        assert_eq!(config.celestia.unwrap().chain_id, "mocha-4");
+        assert_eq!(config.object_store.unwrap().max_retries, 10);
+    }
 }
@@ -0,0 +1,80 @@
+use std::num::NonZeroUsize;
+
+use serde::{Deserialize, Serialize};
+use smart_config::{
+    de::{Serde, WellKnown},
+    DescribeConfig, DeserializeConfig,
+};
+
+use crate::{
+    configs::da_client::{avail::AvailSecrets, celestia::CelestiaSecrets, eigen::EigenSecrets},
+    AvailConfig, CelestiaConfig, EigenConfig, ObjectStoreConfig,
+};
+
+/// DA layer used as a backend of the redundant DA client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RedundantDABackend {
+    Avail,
+    Celestia,
+    Eigen,
+    ObjectStore,
+}
+
+impl WellKnown for RedundantDABackend {
+    type Deserializer = Serde![str];
+    const DE: Self::Deserializer = Serde![str];
+}
+
+/// Configuration of the DA client dispatching each blob to several DA layers.
+#[derive(Clone, Debug, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct RedundantDAConfig {
+    /// Backend whose inclusion data is verified on the settlement layer. If set, the backend must be configured,
+    /// and a blob is only considered available once it succeeds in this backend. If not set, inclusion data
+    /// is not verified on the settlement layer, and any `min_successful_backends` backends are sufficient.
+    pub primary: Option<RedundantDABackend>,
+    /// Minimum number of backends (including the primary one, if any) that must have dispatched and finalized
+    /// a blob for it to be considered available. If not set, all configured backends must succeed.
+    pub min_successful_backends: Option<NonZeroUsize>,
+    #[config(nest)]
+    pub avail: Option<AvailConfig>,
+    #[config(nest)]
+    pub celestia: Option<CelestiaConfig>,
+    #[config(nest)]
+    pub eigen: Option<EigenConfig>,
+    #[config(nest)]
+    pub object_store: Option<ObjectStoreConfig>,
+}
+
+impl RedundantDAConfig {
+    /// Returns the configured backends.
+    pub fn backends(&self) -> Vec<RedundantDABackend> {
+        let backends = [
+            (RedundantDABackend::Avail, self.avail.is_some()),
+            (RedundantDABackend::Celestia, self.celestia.is_some()),
+            (RedundantDABackend::Eigen, self.eigen.is_some()),
+            (RedundantDABackend::ObjectStore, self.object_store.is_some()),
+        ];
+        backends
+            .into_iter()
+            .filter_map(|(backend, is_configured)| is_configured.then_some(backend))
+            .collect()
+    }
+
+    /// Returns the number of backends that must succeed for a blob to be considered available.
+    pub fn required_backends(&self) -> usize {
+        let backend_count = self.backends().len();
+        self.min_successful_backends
+            .map_or(backend_count, |count| count.get().min(backend_count))
+    }
+}
+
+/// Secrets for the backends of the redundant DA client.
+#[derive(Clone, Debug, DescribeConfig, DeserializeConfig)]
+pub struct RedundantDASecrets {
+    #[config(nest)]
+    pub avail: Option<AvailSecrets>,
+    #[config(nest)]
+    pub celestia: Option<CelestiaSecrets>,
+    #[config(nest)]
+    pub eigen: Option<EigenSecrets>,
+}
@@ -24,6 +24,16 @@ pub struct DADispatcherConfig {
     /// have at least dummy inclusion data.
     #[config(default)]
     pub inclusion_verification_transition_enabled: bool,
+    /// Split pubdata exceeding the blob size limit of the DA client into several blobs. The inclusion data
+    /// of such blobs is combined as described in `zksync_da_client::chunking`, so it must be supported
+    /// by the L1 DA validator. If disabled, `max_pubdata_per_batch` must not exceed the blob size limit.
+    #[config(default)]
+    pub split_oversized_blobs: bool,
+    /// Independently verify inclusion data received from the DA client against the DA layer attestations
+    /// before it's used in commit transactions. Blobs failing the verification are dispatched again.
+    /// Must be supported by the DA client; ignored if `use_dummy_inclusion_data` is set.
+    #[config(default)]
+    pub verify_inclusion_data: bool,
 }
 
 #[cfg(test)]
@@ -39,6 +49,8 @@ mod tests {
             max_retries: 7,
             use_dummy_inclusion_data: true,
             inclusion_verification_transition_enabled: false,
+            split_oversized_blobs: true,
+            verify_inclusion_data: true,
         }
     }
 
@@ -50,6 +62,8 @@ mod tests {
             DA_DISPATCHER_MAX_RETRIES=7
             DA_DISPATCHER_USE_DUMMY_INCLUSION_DATA="true"
             DA_DISPATCHER_INCLUSION_VERIFICATION_TRANSITION_ENABLED="false"
+            DA_DISPATCHER_SPLIT_OVERSIZED_BLOBS="true"
+            DA_DISPATCHER_VERIFY_INCLUSION_DATA="true"
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -67,6 +81,8 @@ mod tests {
           max_retries: 7
           use_dummy_inclusion_data: true
           inclusion_verification_transition_enabled: false
+          split_oversized_blobs: true
+          verify_inclusion_data: true
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let config: DADispatcherConfig = test_complete(yaml).unwrap();
@@ -1,4 +1,8 @@
-use std::{num::NonZeroU32, path::PathBuf, time::Duration};
+use std::{
+    num::{NonZeroU32, NonZeroUsize},
+    path::PathBuf,
+    time::Duration,
+};
 
 use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
@@ -9,7 +13,7 @@ use smart_config::{
     ByteSize, DescribeConfig, DeserializeConfig,
 };
 
-use crate::configs::ExperimentalDBConfig;
+use crate::configs::{ExperimentalDBConfig, ObjectStoreConfig};
 
 /// Mode of operation for the Merkle tree.
 ///
@@ -93,6 +97,30 @@ impl MerkleTreeConfig {
     }
 }
 
+/// Configuration of RocksDB checkpoints for the Merkle tree and state keeper cache. Checkpoints are created
+/// at a consistent L1 batch and are uploaded to the object store; they can be used to restore RocksDB instances
+/// without a full rebuild or snapshot recovery.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+#[config(derive(Default))]
+pub struct RocksdbCheckpointsConfig {
+    /// Interval in L1 batches between Merkle tree checkpoints. If not specified, tree checkpoints are not created.
+    pub merkle_tree_interval: Option<NonZeroU32>,
+    /// Interval in L1 batches between state keeper cache checkpoints. If not specified, cache checkpoints
+    /// are not created.
+    pub state_keeper_cache_interval: Option<NonZeroU32>,
+    /// Number of most recent checkpoints of each kind retained in the object store. Older checkpoints are removed
+    /// after a new checkpoint is uploaded.
+    #[config(default_t = NonZeroUsize::new(3).unwrap())]
+    pub retained_count: NonZeroUsize,
+    /// Whether to restore the Merkle tree and state keeper cache from the latest suitable checkpoint
+    /// if the corresponding RocksDB instance is absent on node initialization.
+    #[config(default)]
+    pub restore_on_init: bool,
+    /// Object store used to store checkpoints. If not specified, checkpoints are neither created nor restored.
+    #[config(nest)]
+    pub object_store: Option<ObjectStoreConfig>,
+}
+
 /// Database configuration.
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 pub struct DBConfig {
@@ -105,6 +133,9 @@ pub struct DBConfig {
     /// Experimental parts of the config.
     #[config(nest)]
     pub experimental: ExperimentalDBConfig,
+    /// RocksDB checkpoints configuration.
+    #[config(nest)]
+    pub checkpoints: RocksdbCheckpointsConfig,
 }
 
 /// Collection of different database URLs and general PostgreSQL options.
@@ -155,6 +186,7 @@ mod tests {
     };
 
     use super::*;
+    use crate::configs::object_store::ObjectStoreMode;
 
     fn assert_db_config(config: &DBConfig) {
         assert_eq!(config.state_keeper_db_path.as_os_str(), "/db/state_keeper");
@@ -176,6 +208,27 @@ mod tests {
             NonZeroU32::new(100)
         );
         assert!(config.experimental.merkle_tree_repair_stale_keys);
+        assert_eq!(
+            config.experimental.merkle_tree_cross_check_path.as_deref(),
+            Some(Path::new("/db/cross_check"))
+        );
+
+        let checkpoints = &config.checkpoints;
+        assert_eq!(checkpoints.merkle_tree_interval, NonZeroU32::new(100));
+        assert_eq!(
+            checkpoints.state_keeper_cache_interval,
+            NonZeroU32::new(500)
+        );
+        assert_eq!(checkpoints.retained_count, NonZeroUsize::new(5).unwrap());
+        assert!(checkpoints.restore_on_init);
+        let object_store = checkpoints.object_store.as_ref().unwrap();
+        assert_eq!(
+            object_store.mode,
+            ObjectStoreMode::FileBacked {
+                file_backed_base_path: "/db/checkpoints".into(),
+            }
+        );
+        assert_eq!(object_store.max_retries, 3);
     }
 
     #[test]
@@ -194,8 +247,21 @@ mod tests {
             DATABASE_EXPERIMENTAL_PROCESSING_DELAY_MS=0
             DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_MAX_OPEN_FILES=100
             DATABASE_EXPERIMENTAL_MERKLE_TREE_REPAIR_STALE_KEYS=true
+            DATABASE_EXPERIMENTAL_MERKLE_TREE_CROSS_CHECK_PATH=/db/cross_check
             DATABASE_EXPERIMENTAL_PROTECTIVE_READS_PERSISTENCE_ENABLED=false
             DATABASE_EXPERIMENTAL_INCLUDE_INDICES_AND_FILTERS_IN_BLOCK_CACHE=false
+            DATABASE_CHECKPOINTS_MERKLE_TREE_INTERVAL=100
+            DATABASE_CHECKPOINTS_STATE_KEEPER_CACHE_INTERVAL=500
+            DATABASE_CHECKPOINTS_RETAINED_COUNT=5
+            DATABASE_CHECKPOINTS_RESTORE_ON_INIT=true
+            DATABASE_CHECKPOINTS_OBJECT_STORE_MODE=FileBacked
+            DATABASE_CHECKPOINTS_OBJECT_STORE_FILE_BACKED_BASE_PATH=/db/checkpoints
+            DATABASE_CHECKPOINTS_OBJECT_STORE_MAX_RETRIES=3
+            DATABASE_CHECKPOINTS_OBJECT_STORE_LOCAL_MIRROR_PATH=/db/mirror
+            DATABASE_CHECKPOINTS_OBJECT_STORE_ENCRYPTION_KEYS_PATH=/db/keys.json
+            DATABASE_CHECKPOINTS_OBJECT_STORE_ENCRYPTION_KEY_ID=main
+            DATABASE_CHECKPOINTS_OBJECT_STORE_ENCRYPTION_COMPRESSION_LEVEL=3
+            DATABASE_CHECKPOINTS_OBJECT_STORE_ENCRYPTION_ALLOW_PLAINTEXT_OBJECTS=true
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -224,7 +290,23 @@ mod tests {
             processing_delay_ms: 0
             include_indices_and_filters_in_block_cache: false
             merkle_tree_repair_stale_keys: true
+            merkle_tree_cross_check_path: /db/cross_check
             state_keeper_db_max_open_files: 100
+          checkpoints:
+            merkle_tree_interval: 100
+            state_keeper_cache_interval: 500
+            retained_count: 5
+            restore_on_init: true
+            object_store:
+              mode: FileBacked
+              file_backed_base_path: /db/checkpoints
+              max_retries: 3
+              local_mirror_path: /db/mirror
+              encryption:
+                keys_path: /db/keys.json
+                key_id: main
+                compression_level: 3
+                allow_plaintext_objects: true
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let config: DBConfig = Tester::default()
@@ -254,7 +336,23 @@ mod tests {
             state_keeper_db_block_cache_capacity: 64 MB
             reads_persistence_enabled: false
             merkle_tree_repair_stale_keys: true
+            merkle_tree_cross_check_path: /db/cross_check
             state_keeper_db_max_open_files: 100
+          checkpoints:
+            merkle_tree_interval: 100
+            state_keeper_cache_interval: 500
+            retained_count: 5
+            restore_on_init: true
+            object_store:
+              mode: FileBacked
+              file_backed_base_path: /db/checkpoints
+              max_retries: 3
+              local_mirror_path: /db/mirror
+              encryption:
+                keys_path: /db/keys.json
+                key_id: main
+                compression_level: 3
+                allow_plaintext_objects: true
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let config: DBConfig = Tester::default()
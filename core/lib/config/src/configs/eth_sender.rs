@@ -3,11 +3,11 @@ use std::time::Duration;
 use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
 use smart_config::{
-    de::{Serde, WellKnown},
+    de::{Optional, Serde, WellKnown},
     metadata::TimeUnit,
     DescribeConfig, DeserializeConfig,
 };
-use zksync_basic_types::{pubdata_da::PubdataSendingMode, Address, H256};
+use zksync_basic_types::{pubdata_da::PubdataSendingMode, url::SensitiveUrl, Address, H256};
 use zksync_crypto_primitives::K256PrivateKey;
 
 use crate::{utils::Fallback, EthWatchConfig};
@@ -73,6 +73,7 @@ impl EthConfig {
                 num_samples_for_blob_base_fee_estimate: 10,
                 internal_pubdata_pricing_multiplier: 1.0,
                 max_blob_base_fee: u64::MAX,
+                fee_sources: None,
             },
             watcher: EthWatchConfig {
                 confirmations_for_eth_event: None,
@@ -300,6 +301,48 @@ pub struct GasAdjusterConfig {
     /// Max blob base fee that is allowed to be used.
     #[config(default_t = u64::MAX)]
     pub max_blob_base_fee: u64,
+    /// External fee sources blended with the fee history of the settlement layer client.
+    /// If not specified, only the settlement layer client is used.
+    #[config(nest)]
+    pub fee_sources: Option<FeeSourcesConfig>,
+}
+
+/// Configuration of external fee sources used by `GasAdjuster`. Samples from all configured sources
+/// (including the settlement layer client) are aggregated by taking a median after outlier rejection;
+/// the result is clamped relative to the value reported by the settlement layer client.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+#[config(derive(Default))]
+pub struct FeeSourcesConfig {
+    /// RPC URL of a secondary L1 provider whose `eth_feeHistory` is used as a fee source.
+    #[config(secret, with = Optional(Serde![str]))]
+    pub fee_history_rpc_url: Option<SensitiveUrl>,
+    /// Number of recent blocks the secondary provider fee history is requested for.
+    #[config(default_t = 20)]
+    pub fee_history_block_count: usize,
+    /// Percentile (0..=100) of base fees over the requested blocks used as the sample.
+    #[config(default_t = 50)]
+    pub fee_history_percentile: u8,
+    /// URL of a gas station API returning a JSON object with fee estimates.
+    pub gas_station_url: Option<String>,
+    /// JSON pointer to the base fee (in gwei) in the gas station response.
+    #[config(default_t = "/baseFee".into())]
+    pub gas_station_base_fee_pointer: String,
+    /// JSON pointer to the priority fee (in gwei) in the gas station response. If the value is missing,
+    /// the gas station is not used as a priority fee source.
+    #[config(default_t = "/priorityFee".into())]
+    pub gas_station_priority_fee_pointer: String,
+    /// Timeout for a single request to an external fee source.
+    #[config(default_t = Duration::from_secs(5))]
+    pub request_timeout: Duration,
+    /// Samples deviating from the median of all samples by more than this fraction are rejected as outliers.
+    #[config(default_t = 0.5)]
+    pub max_relative_deviation: f64,
+    /// Lower bound for the aggregated value relative to the settlement layer client value.
+    #[config(default_t = 0.5)]
+    pub min_ratio_to_primary: f64,
+    /// Upper bound for the aggregated value relative to the settlement layer client value.
+    #[config(default_t = 2.0)]
+    pub max_ratio_to_primary: f64,
 }
 
 #[cfg(test)]
@@ -359,6 +402,20 @@ mod tests {
                 num_samples_for_blob_base_fee_estimate: 10,
                 internal_pubdata_pricing_multiplier: 1.0,
                 max_blob_base_fee: 1000,
+                fee_sources: Some(FeeSourcesConfig {
+                    fee_history_rpc_url: Some(
+                        "https://ethereum-rpc.publicnode.com/".parse().unwrap(),
+                    ),
+                    fee_history_block_count: 30,
+                    fee_history_percentile: 60,
+                    gas_station_url: Some("https://gas.example.com/api".into()),
+                    gas_station_base_fee_pointer: "/data/baseFee".into(),
+                    gas_station_priority_fee_pointer: "/data/priorityFee".into(),
+                    request_timeout: Duration::from_secs(3),
+                    max_relative_deviation: 0.25,
+                    min_ratio_to_primary: 0.8,
+                    max_ratio_to_primary: 1.5,
+                }),
             },
             watcher: EthWatchConfig {
                 confirmations_for_eth_event: Some(0),
@@ -391,6 +448,16 @@ mod tests {
             ETH_SENDER_GAS_ADJUSTER_INTERNAL_PUBDATA_PRICING_MULTIPLIER="1.0"
             ETH_SENDER_GAS_ADJUSTER_INTERNAL_ENFORCED_L1_GAS_PRICE=10000000
             ETH_SENDER_GAS_ADJUSTER_INTERNAL_ENFORCED_PUBDATA_PRICE=5000000
+            ETH_SENDER_GAS_ADJUSTER_FEE_SOURCES_FEE_HISTORY_RPC_URL="https://ethereum-rpc.publicnode.com/"
+            ETH_SENDER_GAS_ADJUSTER_FEE_SOURCES_FEE_HISTORY_BLOCK_COUNT="30"
+            ETH_SENDER_GAS_ADJUSTER_FEE_SOURCES_FEE_HISTORY_PERCENTILE="60"
+            ETH_SENDER_GAS_ADJUSTER_FEE_SOURCES_GAS_STATION_URL="https://gas.example.com/api"
+            ETH_SENDER_GAS_ADJUSTER_FEE_SOURCES_GAS_STATION_BASE_FEE_POINTER="/data/baseFee"
+            ETH_SENDER_GAS_ADJUSTER_FEE_SOURCES_GAS_STATION_PRIORITY_FEE_POINTER="/data/priorityFee"
+            ETH_SENDER_GAS_ADJUSTER_FEE_SOURCES_REQUEST_TIMEOUT_SEC="3"
+            ETH_SENDER_GAS_ADJUSTER_FEE_SOURCES_MAX_RELATIVE_DEVIATION="0.25"
+            ETH_SENDER_GAS_ADJUSTER_FEE_SOURCES_MIN_RATIO_TO_PRIMARY="0.8"
+            ETH_SENDER_GAS_ADJUSTER_FEE_SOURCES_MAX_RATIO_TO_PRIMARY="1.5"
             ETH_SENDER_SENDER_AGGREGATED_PROOF_SIZES="1,5"
             ETH_SENDER_SENDER_MAX_AGGREGATED_BLOCKS_TO_COMMIT="3"
             ETH_SENDER_SENDER_MAX_AGGREGATED_BLOCKS_TO_EXECUTE="4"
@@ -473,6 +540,17 @@ mod tests {
             internal_enforced_l1_gas_price: 10000000
             internal_enforced_pubdata_price: 5000000
             max_blob_base_fee: 1000
+            fee_sources:
+              fee_history_rpc_url: https://ethereum-rpc.publicnode.com/
+              fee_history_block_count: 30
+              fee_history_percentile: 60
+              gas_station_url: https://gas.example.com/api
+              gas_station_base_fee_pointer: /data/baseFee
+              gas_station_priority_fee_pointer: /data/priorityFee
+              request_timeout: 3 sec
+              max_relative_deviation: 0.25
+              min_ratio_to_primary: 0.8
+              max_ratio_to_primary: 1.5
           watcher:
             confirmations_for_eth_event: 0
             eth_node_poll_interval: 300
@@ -535,6 +613,17 @@ mod tests {
             internal_enforced_l1_gas_price: 10000000
             internal_enforced_pubdata_price: 5000000
             max_blob_base_fee: 1000
+            fee_sources:
+              fee_history_rpc_url: https://ethereum-rpc.publicnode.com/
+              fee_history_block_count: 30
+              fee_history_percentile: 60
+              gas_station_url: https://gas.example.com/api
+              gas_station_base_fee_pointer: /data/baseFee
+              gas_station_priority_fee_pointer: /data/priorityFee
+              request_timeout: 3s
+              max_relative_deviation: 0.25
+              min_ratio_to_primary: 0.8
+              max_ratio_to_primary: 1.5
           watcher:
             confirmations_for_eth_event: 0
             eth_node_poll_interval: 300ms
@@ -18,6 +18,11 @@ pub struct ExperimentalDBConfig {
     /// Enables the stale keys repair task for the Merkle tree.
     #[config(default)]
     pub merkle_tree_repair_stale_keys: bool,
+    /// Path to the RocksDB directory of a `zk_os_merkle_tree` instance used to cross-check the Merkle tree.
+    /// If set, the metadata calculator replays the storage logs of each processed L1 batch into this tree
+    /// and reports the first divergence in leaves or enumeration indices. Only leaves touched by each batch
+    /// (and the total leaf counts) are compared, so divergences in untouched leaves are detected lazily.
+    pub merkle_tree_cross_check_path: Option<PathBuf>,
 }
 
 /// Configuration for the VM playground (an experimental component that's unlikely to ever be stabilized).
@@ -25,7 +25,7 @@ mod tests {
     };
 
     use super::*;
-    use crate::configs::object_store::ObjectStoreMode;
+    use crate::configs::object_store::{ObjectStoreEncryptionConfig, ObjectStoreMode};
 
     fn expected_config() -> FriProverConfig {
         FriProverConfig {
@@ -40,6 +40,12 @@ mod tests {
                 },
                 max_retries: 5,
                 local_mirror_path: Some("/var/cache".into()),
+                encryption: Some(ObjectStoreEncryptionConfig {
+                    keys_path: "/etc/prover_keys.json".into(),
+                    key_id: "1".to_owned(),
+                    compression_level: 3,
+                    allow_plaintext_objects: true,
+                }),
             },
         }
     }
@@ -56,6 +62,10 @@ mod tests {
             FRI_PROVER_PROVER_OBJECT_STORE_GCS_CREDENTIAL_FILE_PATH="/path/to/credentials1.json"
             FRI_PROVER_PROVER_OBJECT_STORE_MAX_RETRIES="5"
             FRI_PROVER_PROVER_OBJECT_STORE_LOCAL_MIRROR_PATH="/var/cache"
+            FRI_PROVER_PROVER_OBJECT_STORE_ENCRYPTION_KEYS_PATH="/etc/prover_keys.json"
+            FRI_PROVER_PROVER_OBJECT_STORE_ENCRYPTION_KEY_ID="1"
+            FRI_PROVER_PROVER_OBJECT_STORE_ENCRYPTION_COMPRESSION_LEVEL="3"
+            FRI_PROVER_PROVER_OBJECT_STORE_ENCRYPTION_ALLOW_PLAINTEXT_OBJECTS="true"
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -78,6 +88,11 @@ mod tests {
             gcs_credential_file_path: /path/to/credentials1.json
             max_retries: 5
             local_mirror_path: /var/cache
+            encryption:
+              keys_path: /etc/prover_keys.json
+              key_id: "1"
+              compression_level: 3
+              allow_plaintext_objects: true
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let config: FriProverConfig = Tester::default()
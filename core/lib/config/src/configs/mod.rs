@@ -10,7 +10,7 @@ pub use self::{
     contracts::chain::ContractsConfig,
     da_client::{avail::AvailConfig, celestia::CelestiaConfig, eigen::EigenConfig, DAClientConfig},
     da_dispatcher::DADispatcherConfig,
-    database::{DBConfig, PostgresConfig},
+    database::{DBConfig, PostgresConfig, RocksdbCheckpointsConfig},
     en::remote::RemoteENConfig,
     eth_sender::{EthConfig, GasAdjusterConfig},
     eth_watch::EthWatchConfig,
@@ -25,7 +25,7 @@ pub use self::{
     general::{full_config_schema, GeneralConfig},
     genesis::{GenesisConfig, GenesisConfigWrapper},
     node_sync::NodeSyncConfig,
-    object_store::ObjectStoreConfig,
+    object_store::{ObjectStoreConfig, ObjectStoreEncryptionConfig},
     observability::{ObservabilityConfig, OpentelemetryConfig},
     proof_data_handler::ProofDataHandlerConfig,
     prover_job_monitor::ProverJobMonitorConfig,
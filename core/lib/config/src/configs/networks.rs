@@ -1,8 +1,11 @@
 use std::{num::NonZeroUsize, time::Duration};
 
+use serde::{de::Error as DeError, Deserialize};
+use serde_json::Value;
 use smart_config::{
-    de::{Optional, Serde},
-    DescribeConfig, DeserializeConfig,
+    de::{DeserializeContext, DeserializeParam, Optional, Serde},
+    metadata::{BasicTypes, ParamMetadata},
+    DescribeConfig, DeserializeConfig, ErrorWithOrigin,
 };
 use zksync_basic_types::{url::SensitiveUrl, Address, L1ChainId, L2ChainId, SLChainId};
 
@@ -15,6 +18,33 @@ pub struct SharedL1ContractsConfig {
     pub diamond_proxy_addr: Option<Address>,
 }
 
+/// Deserializes a comma-separated list of URLs.
+#[derive(Debug)]
+struct SensitiveUrlsDeserializer;
+
+impl DeserializeParam<Vec<SensitiveUrl>> for SensitiveUrlsDeserializer {
+    const EXPECTING: BasicTypes = BasicTypes::STRING;
+
+    fn deserialize_param(
+        &self,
+        ctx: DeserializeContext<'_>,
+        param: &'static ParamMetadata,
+    ) -> Result<Vec<SensitiveUrl>, ErrorWithOrigin> {
+        let de = ctx.current_value_deserializer(param.name)?;
+        let urls = String::deserialize(de)?;
+        urls.split(',')
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(|url| url.parse().map_err(DeError::custom))
+            .collect()
+    }
+
+    fn serialize_param(&self, param: &Vec<SensitiveUrl>) -> Value {
+        let urls: Vec<_> = param.iter().map(SensitiveUrl::expose_str).collect();
+        Value::String(urls.join(","))
+    }
+}
+
 /// Temporary config for initializing external node, will be completely replaced by consensus config later.
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 pub struct NetworksConfig {
@@ -33,6 +63,17 @@ pub struct NetworksConfig {
     /// Rate limiting configuration for the L2 peer node.
     #[config(default_t = NonZeroUsize::new(100).unwrap())]
     pub main_node_rate_limit_rps: NonZeroUsize,
+    /// Additional L2 nodes serving the `en` namespace (e.g., other external nodes) to sync from, specified
+    /// as a comma-separated list. Upstreams are preferred in the specified order as long as they don't lag behind;
+    /// the main node is used as a fallback. Upstreams must have full history of the chain starting from genesis.
+    #[config(secret, default, with = SensitiveUrlsDeserializer)]
+    pub upstream_urls: Vec<SensitiveUrl>,
+    /// Maximum lag (in L2 blocks) of an upstream relative to the most advanced upstream for it to be used.
+    #[config(default_t = 10)]
+    pub upstream_max_lag: u32,
+    /// Interval between checking chain IDs, genesis and the latest L2 block of upstreams.
+    #[config(default_t = Duration::from_secs(5))]
+    pub upstream_poll_interval: Duration,
 
     #[config(default_t = Duration::from_secs(60))]
     pub bridge_addresses_refresh_interval: Duration,
@@ -45,6 +86,9 @@ impl NetworksConfig {
             l1_chain_id: L1ChainId(9),
             main_node_url: "http://localhost:3050/".parse().unwrap(),
             main_node_rate_limit_rps: 100.try_into().unwrap(),
+            upstream_urls: vec![],
+            upstream_max_lag: 10,
+            upstream_poll_interval: Duration::from_secs(5),
             bridge_addresses_refresh_interval: Duration::from_secs(60),
             gateway_chain_id: None,
         }
@@ -64,6 +108,12 @@ mod tests {
             gateway_chain_id: Some(SLChainId(123)),
             main_node_url: "http://127.0.0.1:3050/".parse().unwrap(),
             main_node_rate_limit_rps: NonZeroUsize::new(200).unwrap(),
+            upstream_urls: vec![
+                "http://127.0.0.1:3060/".parse().unwrap(),
+                "http://127.0.0.1:3070/".parse().unwrap(),
+            ],
+            upstream_max_lag: 5,
+            upstream_poll_interval: Duration::from_secs(3),
             bridge_addresses_refresh_interval: Duration::from_secs(15),
         }
     }
@@ -86,6 +136,9 @@ mod tests {
             EN_GATEWAY_CHAIN_ID=123
             EN_MAIN_NODE_URL=http://127.0.0.1:3050/
             EN_MAIN_NODE_RATE_LIMIT_RPS=200
+            EN_UPSTREAM_URLS=http://127.0.0.1:3060/,http://127.0.0.1:3070/
+            EN_UPSTREAM_MAX_LAG=5
+            EN_UPSTREAM_POLL_INTERVAL="3s"
             EN_BRIDGE_ADDRESSES_REFRESH_INTERVAL="15s"
         "#;
         let env = Environment::from_dotenv("test.env", env)
@@ -103,6 +156,9 @@ mod tests {
         let yaml = r#"
             main_node_url: http://127.0.0.1:3050/
             main_node_rate_limit_rps: 200
+            upstream_urls: http://127.0.0.1:3060/,http://127.0.0.1:3070/
+            upstream_max_lag: 5
+            upstream_poll_interval: 3s
             gateway_url: null
             l2_chain_id: 271
             l1_chain_id: 9
@@ -123,6 +179,9 @@ mod tests {
           external_node:
             main_node_url: http://127.0.0.1:3050/
             main_node_rate_limit_rps: 200
+            upstream_urls: http://127.0.0.1:3060/,http://127.0.0.1:3070/
+            upstream_max_lag: 5
+            upstream_poll_interval: 3s
             gateway_url: null
             l2_chain_id: 271
             l1_chain_id: 9
@@ -1,4 +1,7 @@
-use std::{num::NonZeroU64, time::Duration};
+use std::{
+    num::{NonZeroU32, NonZeroU64},
+    time::Duration,
+};
 
 use smart_config::{DescribeConfig, DeserializeConfig};
 
@@ -15,6 +18,54 @@ pub struct NodeSyncConfig {
     /// Toggle for disabling seal criteria validation in case of some issues / forced proved batches
     #[config(default_t = true)]
     pub validate_seal_criteria: bool,
+    /// Fast sync mode applying L2 block outputs fetched from the main node instead of re-executing transactions.
+    #[config(nest)]
+    pub fast_sync: FastSyncConfig,
+    /// Automatic recovery from reorgs detected by the reorg detector.
+    #[config(nest)]
+    pub reorg_recovery: ReorgRecoveryConfig,
+}
+
+/// Configuration for the fast sync mode. In this mode, the node fetches storage writes, events and receipts
+/// for each L2 block from the main node and persists them directly. Each applied L1 batch is verified against
+/// the state root hash from L1 or the main node; the node switches to full transaction re-execution
+/// once it is close to the main node.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+#[config(derive(Default))]
+pub struct FastSyncConfig {
+    /// Whether fast sync is enabled.
+    #[config(default)]
+    pub enabled: bool,
+    /// Distance to the last L1 batch sealed on the main node (in L1 batches) at which fast sync stops
+    /// and the node switches to re-executing transactions.
+    #[config(default_t = 10)]
+    pub tip_distance: u32,
+    /// Max number of applied L1 batches with unverified state root hashes. If this number is reached,
+    /// fast sync waits for the Merkle tree to catch up.
+    #[config(default_t = NonZeroU32::new(10).unwrap())]
+    pub max_unverified_batches: NonZeroU32,
+}
+
+/// Configuration for automatic recovery from reorgs. If enabled, the node rolls back its storage
+/// (Postgres, RocksDB caches and the Merkle tree) to the last L1 batch common with the main node
+/// and resumes syncing, instead of requiring operator action.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+#[config(derive(Default))]
+pub struct ReorgRecoveryConfig {
+    /// Whether automatic reorg recovery is enabled.
+    #[config(default)]
+    pub enabled: bool,
+    /// Max number of L1 batches that can be rolled back automatically. Deeper reorgs still require
+    /// operator action.
+    #[config(default_t = 10)]
+    pub max_depth: u32,
+    /// Max number of in-process node restarts after detected reorgs during the node process lifetime. Once this number
+    /// is exceeded, the node exits with an error.
+    #[config(default_t = 3)]
+    pub max_restarts: u32,
+    /// Delay before the first in-process restart. The delay is doubled for each following restart.
+    #[config(default_t = Duration::from_secs(5))]
+    pub restart_backoff: Duration,
 }
 
 #[cfg(test)]
@@ -28,6 +79,17 @@ mod tests {
             batch_transaction_updater_interval: Duration::from_secs(2),
             batch_transaction_updater_batch_size: NonZeroU64::new(100).unwrap(),
             validate_seal_criteria: false,
+            fast_sync: FastSyncConfig {
+                enabled: true,
+                tip_distance: 5,
+                max_unverified_batches: NonZeroU32::new(3).unwrap(),
+            },
+            reorg_recovery: ReorgRecoveryConfig {
+                enabled: true,
+                max_depth: 3,
+                max_restarts: 5,
+                restart_backoff: Duration::from_secs(1),
+            },
         }
     }
 
@@ -37,6 +99,13 @@ mod tests {
             NODE_SYNC_BATCH_TRANSACTION_UPDATER_INTERVAL=2sec
             NODE_SYNC_BATCH_TRANSACTION_UPDATER_BATCH_SIZE=100
             NODE_SYNC_VALIDATE_SEAL_CRITERIA=false
+            NODE_SYNC_FAST_SYNC_ENABLED=true
+            NODE_SYNC_FAST_SYNC_TIP_DISTANCE=5
+            NODE_SYNC_FAST_SYNC_MAX_UNVERIFIED_BATCHES=3
+            NODE_SYNC_REORG_RECOVERY_ENABLED=true
+            NODE_SYNC_REORG_RECOVERY_MAX_DEPTH=3
+            NODE_SYNC_REORG_RECOVERY_MAX_RESTARTS=5
+            NODE_SYNC_REORG_RECOVERY_RESTART_BACKOFF=1sec
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -52,6 +121,15 @@ mod tests {
           batch_transaction_updater_interval: 2sec
           batch_transaction_updater_batch_size: 100
           validate_seal_criteria: false
+          fast_sync:
+            enabled: true
+            tip_distance: 5
+            max_unverified_batches: 3
+          reorg_recovery:
+            enabled: true
+            max_depth: 3
+            max_restarts: 5
+            restart_backoff: 1sec
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let config: NodeSyncConfig = test_complete(yaml).unwrap();
@@ -19,6 +19,28 @@ pub struct ObjectStoreConfig {
     /// **Important.** Mirroring logic assumes that objects in the underlying store are immutable. If this is not the case,
     /// the mirrored objects may become stale.
     pub local_mirror_path: Option<PathBuf>,
+    /// Client-side compression and encryption of stored objects. If not specified, objects are stored as is.
+    #[config(nest)]
+    pub encryption: Option<ObjectStoreEncryptionConfig>,
+}
+
+/// Configuration of client-side compression and encryption for an object store. Objects are compressed with zstd
+/// and encrypted with AES-256-GCM. Objects stored without encryption can still be read unless
+/// `allow_plaintext_objects` is disabled.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct ObjectStoreEncryptionConfig {
+    /// Path to a JSON file with encryption keys, which maps key IDs to hex-encoded 32-byte AES-256 keys.
+    pub keys_path: PathBuf,
+    /// ID of the key used to encrypt new objects; must be present in the keys file. Other keys in the file
+    /// are only used to decrypt objects, which allows rotating keys.
+    pub key_id: String,
+    /// zstd compression level used for new objects.
+    #[config(default_t = 3)]
+    pub compression_level: i32,
+    /// Whether objects stored without encryption (e.g., before encryption was enabled) can be read. Should be
+    /// disabled once such objects are migrated, so that unencrypted objects are rejected.
+    #[config(default_t = true)]
+    pub allow_plaintext_objects: bool,
 }
 
 impl Default for ObjectStoreConfig {
@@ -35,6 +57,7 @@ impl ObjectStoreConfig {
             },
             max_retries: 5,
             local_mirror_path: None,
+            encryption: None,
         }
     }
 }
@@ -82,6 +105,37 @@ pub enum ObjectStoreMode {
         /// Allows specifying bucket region (inferred from the env by default).
         region: Option<String>,
     },
+    /// Azure Blob Storage container with storage account access key authentication.
+    AzureWithSharedKey {
+        /// Name of the container.
+        bucket_base_url: String,
+        /// Name of the storage account.
+        azure_account_name: String,
+        /// Path to the file with the storage account access key.
+        azure_account_key_file_path: PathBuf,
+        /// Allows overriding Azure Blob Storage endpoint, e.g. to use a local Azurite emulator.
+        endpoint: Option<String>,
+    },
+    /// Azure Blob Storage container with shared access signature (SAS) authentication.
+    AzureWithSasToken {
+        /// Name of the container.
+        bucket_base_url: String,
+        /// Name of the storage account.
+        azure_account_name: String,
+        /// Path to the file with the SAS token.
+        azure_sas_token_file_path: PathBuf,
+        /// Allows overriding Azure Blob Storage endpoint, e.g. to use a local Azurite emulator.
+        endpoint: Option<String>,
+    },
+    /// Azure Blob Storage container with ambient authentication (e.g., via a managed identity).
+    AzureWithManagedIdentity {
+        /// Name of the container.
+        bucket_base_url: String,
+        /// Name of the storage account.
+        azure_account_name: String,
+        /// Allows overriding Azure Blob Storage endpoint.
+        endpoint: Option<String>,
+    },
     /// Stores files in a local filesystem. Mostly useful for local testing.
     #[config(default)]
     FileBacked {
@@ -107,6 +161,12 @@ mod tests {
             },
             max_retries: 5,
             local_mirror_path: Some("/var/cache".into()),
+            encryption: Some(ObjectStoreEncryptionConfig {
+                keys_path: "/etc/object_store_keys.json".into(),
+                key_id: "2024-01".to_owned(),
+                compression_level: 5,
+                allow_plaintext_objects: false,
+            }),
         }
     }
 
@@ -118,6 +178,10 @@ mod tests {
             OBJECT_STORE_GCS_CREDENTIAL_FILE_PATH="/path/to/credentials.json"
             OBJECT_STORE_MAX_RETRIES="5"
             OBJECT_STORE_LOCAL_MIRROR_PATH="/var/cache"
+            OBJECT_STORE_ENCRYPTION_KEYS_PATH="/etc/object_store_keys.json"
+            OBJECT_STORE_ENCRYPTION_KEY_ID="2024-01"
+            OBJECT_STORE_ENCRYPTION_COMPRESSION_LEVEL="5"
+            OBJECT_STORE_ENCRYPTION_ALLOW_PLAINTEXT_OBJECTS="false"
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -153,6 +217,10 @@ mod tests {
             PUBLIC_OBJECT_STORE_MODE="GCSAnonymousReadOnly"
             PUBLIC_OBJECT_STORE_MAX_RETRIES="3"
             PUBLIC_OBJECT_STORE_LOCAL_MIRROR_PATH=/var/cache
+            PUBLIC_OBJECT_STORE_ENCRYPTION_KEYS_PATH=/etc/object_store_keys.json
+            PUBLIC_OBJECT_STORE_ENCRYPTION_KEY_ID=1
+            PUBLIC_OBJECT_STORE_ENCRYPTION_COMPRESSION_LEVEL=3
+            PUBLIC_OBJECT_STORE_ENCRYPTION_ALLOW_PLAINTEXT_OBJECTS=true
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -166,6 +234,7 @@ mod tests {
                 bucket_base_url: "/public_base_url".to_owned(),
             }
         );
+        assert_eq!(config.encryption.unwrap().key_id, "1");
     }
 
     #[test]
@@ -179,6 +248,11 @@ mod tests {
           file_backed_base_path: ./chains/era/artifacts/
           max_retries: 10
           local_mirror_path: /var/cache
+          encryption:
+            keys_path: /etc/object_store_keys.json
+            key_id: '2024-01'
+            compression_level: 3
+            allow_plaintext_objects: true
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let config: ObjectStoreConfig = test_complete(yaml).unwrap();
@@ -190,6 +264,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn azure_from_env() {
+        let env = r#"
+            OBJECT_STORE_MODE="AzureWithSharedKey"
+            OBJECT_STORE_BUCKET_BASE_URL="artifacts"
+            OBJECT_STORE_AZURE_ACCOUNT_NAME="devstoreaccount1"
+            OBJECT_STORE_AZURE_ACCOUNT_KEY_FILE_PATH="/path/to/account_key"
+            OBJECT_STORE_ENDPOINT="http://127.0.0.1:10000/devstoreaccount1"
+            OBJECT_STORE_MAX_RETRIES="3"
+            OBJECT_STORE_LOCAL_MIRROR_PATH="/var/cache"
+            OBJECT_STORE_ENCRYPTION_KEYS_PATH="/etc/object_store_keys.json"
+            OBJECT_STORE_ENCRYPTION_KEY_ID="2024-01"
+            OBJECT_STORE_ENCRYPTION_COMPRESSION_LEVEL="5"
+            OBJECT_STORE_ENCRYPTION_ALLOW_PLAINTEXT_OBJECTS="false"
+        "#;
+        let env = Environment::from_dotenv("test.env", env)
+            .unwrap()
+            .strip_prefix("OBJECT_STORE_");
+
+        let config: ObjectStoreConfig = test_complete(env).unwrap();
+        assert_eq!(
+            config.mode,
+            ObjectStoreMode::AzureWithSharedKey {
+                bucket_base_url: "artifacts".to_owned(),
+                azure_account_name: "devstoreaccount1".to_owned(),
+                azure_account_key_file_path: "/path/to/account_key".into(),
+                endpoint: Some("http://127.0.0.1:10000/devstoreaccount1".to_owned()),
+            }
+        );
+    }
+
+    #[test]
+    fn azure_from_yaml() {
+        let yaml = r#"
+          mode: AzureWithSasToken
+          bucket_base_url: artifacts
+          azure_account_name: account
+          azure_sas_token_file_path: /path/to/sas_token
+        "#;
+        let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
+        let config: ObjectStoreConfig = test(yaml).unwrap();
+        assert_eq!(
+            config.mode,
+            ObjectStoreMode::AzureWithSasToken {
+                bucket_base_url: "artifacts".to_owned(),
+                azure_account_name: "account".to_owned(),
+                azure_sas_token_file_path: "/path/to/sas_token".into(),
+                endpoint: None,
+            }
+        );
+    }
+
     #[test]
     fn public_bucket_from_yaml_with_enum_coercion() {
         let yaml = r#"
@@ -1,6 +1,11 @@
-use std::time::Duration;
+use std::{num::NonZeroU32, time::Duration};
 
-use smart_config::{metadata::TimeUnit, DescribeConfig, DeserializeConfig};
+use smart_config::{
+    de::{Optional, Serde},
+    metadata::TimeUnit,
+    DescribeConfig, DeserializeConfig,
+};
+use zksync_basic_types::L2ChainId;
 
 /// Config used for running ProverJobMonitor.
 /// It handles configuration for setup of the binary (like database connections, prometheus) and configuration for jobs that are being ran.
@@ -53,6 +58,25 @@ pub struct ProverJobMonitorConfig {
     /// The interval between runs for Witness Job Queuer.
     #[config(default_t = Duration::from_secs(10))]
     pub witness_job_queuer_run_interval: Duration,
+    /// The interval between runs for Object Store Garbage Collector.
+    #[config(default_t = 10 * TimeUnit::Minutes)]
+    pub object_store_gc_run_interval: Duration,
+    /// Maximum number of L1 batches processed per artifact kind by Object Store Garbage Collector in a single run.
+    #[config(default_t = NonZeroU32::new(100).unwrap())]
+    pub object_store_gc_max_l1_batches_per_run: NonZeroU32,
+    /// Chain whose artifacts are collected by Object Store Garbage Collector. Must correspond to the core database
+    /// used to check whether L1 batches are executed. If not set, Object Store Garbage Collector is disabled.
+    #[config(with = Optional(Serde![int]))]
+    pub object_store_gc_chain_id: Option<L2ChainId>,
+    /// Number of the latest executed and proven L1 batches for which witness inputs are retained in the object store.
+    /// If not set, witness inputs are never removed.
+    pub object_store_gc_witness_inputs_retained_batches: Option<u32>,
+    /// Number of the latest executed and proven L1 batches for which circuits and aggregation witness jobs
+    /// are retained in the object store. If not set, they are never removed.
+    pub object_store_gc_circuits_retained_batches: Option<u32>,
+    /// Number of the latest executed and proven L1 batches for which FRI and final proofs are retained
+    /// in the object store. If not set, proofs are never removed.
+    pub object_store_gc_proofs_retained_batches: Option<u32>,
     /// HTTP port of the ProverJobMonitor to send requests to.
     pub http_port: u16,
 }
@@ -80,6 +104,12 @@ mod tests {
             prover_queue_reporter_run_interval: Duration::from_secs(10),
             witness_generator_queue_reporter_run_interval: Duration::from_secs(10),
             witness_job_queuer_run_interval: Duration::from_secs(10),
+            object_store_gc_run_interval: Duration::from_secs(600),
+            object_store_gc_max_l1_batches_per_run: NonZeroU32::new(50).unwrap(),
+            object_store_gc_chain_id: Some(L2ChainId::from(270)),
+            object_store_gc_witness_inputs_retained_batches: Some(100),
+            object_store_gc_circuits_retained_batches: Some(0),
+            object_store_gc_proofs_retained_batches: Some(1000),
             http_port: 3074,
         }
     }
@@ -102,6 +132,12 @@ mod tests {
             PROVER_JOB_MONITOR_PROVER_QUEUE_REPORTER_RUN_INTERVAL_MS=10000
             PROVER_JOB_MONITOR_WITNESS_GENERATOR_QUEUE_REPORTER_RUN_INTERVAL_MS=10000
             PROVER_JOB_MONITOR_WITNESS_JOB_QUEUER_RUN_INTERVAL_MS=10000
+            PROVER_JOB_MONITOR_OBJECT_STORE_GC_RUN_INTERVAL_MS=600000
+            PROVER_JOB_MONITOR_OBJECT_STORE_GC_MAX_L1_BATCHES_PER_RUN=50
+            PROVER_JOB_MONITOR_OBJECT_STORE_GC_CHAIN_ID=270
+            PROVER_JOB_MONITOR_OBJECT_STORE_GC_WITNESS_INPUTS_RETAINED_BATCHES=100
+            PROVER_JOB_MONITOR_OBJECT_STORE_GC_CIRCUITS_RETAINED_BATCHES=0
+            PROVER_JOB_MONITOR_OBJECT_STORE_GC_PROOFS_RETAINED_BATCHES=1000
             PROVER_JOB_MONITOR_HTTP_PORT=3074
         "#;
         let env = Environment::from_dotenv("test.env", env)
@@ -130,6 +166,12 @@ mod tests {
           prover_queue_reporter_run_interval_ms: 10000
           witness_generator_queue_reporter_run_interval_ms: 10000
           witness_job_queuer_run_interval_ms: 10000
+          object_store_gc_run_interval_ms: 600000
+          object_store_gc_max_l1_batches_per_run: 50
+          object_store_gc_chain_id: 270
+          object_store_gc_witness_inputs_retained_batches: 100
+          object_store_gc_circuits_retained_batches: 0
+          object_store_gc_proofs_retained_batches: 1000
           http_port: 3074
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
@@ -155,6 +197,12 @@ mod tests {
           prover_queue_reporter_run_interval: '10 sec'
           witness_generator_queue_reporter_run_interval: '10s'
           witness_job_queuer_run_interval: '10s'
+          object_store_gc_run_interval: '10 min'
+          object_store_gc_max_l1_batches_per_run: 50
+          object_store_gc_chain_id: 270
+          object_store_gc_witness_inputs_retained_batches: 100
+          object_store_gc_circuits_retained_batches: 0
+          object_store_gc_proofs_retained_batches: 1000
           http_port: 3074
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
@@ -23,6 +23,19 @@ pub struct PruningConfig {
     /// If set to 0, L1 batches will not be retained based on their timestamp.
     #[config(default_t = 1 * TimeUnit::Hours)]
     pub data_retention: Duration,
+    /// If set, storage logs are retained beyond the pruning horizon for every L1 batch with number divisible by this value
+    /// (an *archive checkpoint*). This allows reading storage state (e.g., via `eth_getBalance` or `eth_getStorageAt`)
+    /// at the last L2 block of each checkpoint batch, while the remaining historical data is pruned.
+    /// Changing this value only affects L1 batches pruned after the change.
+    pub archive_interval: Option<NonZeroU32>,
+    /// If set, call traces are pruned once the L1 batch timestamp is this old, which can be earlier than other data
+    /// is pruned (i.e., this value should be less than `data_retention` to have effect). Traces for pruned transactions
+    /// are no longer available via `debug_trace*` RPC methods, while blocks, transactions, receipts and events are retained.
+    pub call_traces_retention: Option<Duration>,
+    /// If set, historical storage logs are pruned once the L1 batch timestamp is this old, similarly to `call_traces_retention`.
+    /// Storage state (e.g., for `eth_call` or `eth_getBalance`) cannot be read for pruned L2 blocks, except for
+    /// archive checkpoints (see `archive_interval`).
+    pub storage_logs_retention: Option<Duration>,
 }
 
 #[cfg(test)]
@@ -37,6 +50,9 @@ mod tests {
             chunk_size: NonZeroU32::new(10).unwrap(),
             removal_delay: Duration::from_secs(60),
             data_retention: Duration::from_secs(3600),
+            archive_interval: NonZeroU32::new(1_000),
+            call_traces_retention: Some(Duration::from_secs(600)),
+            storage_logs_retention: Some(Duration::from_secs(1_800)),
         }
     }
 
@@ -47,6 +63,9 @@ mod tests {
             EN_PRUNING_DATA_RETENTION_SEC=3600
             EN_PRUNING_CHUNK_SIZE=10
             EN_PRUNING_REMOVAL_DELAY_SEC=60
+            EN_PRUNING_ARCHIVE_INTERVAL=1000
+            EN_PRUNING_CALL_TRACES_RETENTION_SEC=600
+            EN_PRUNING_STORAGE_LOGS_RETENTION=30 min
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -63,6 +82,9 @@ mod tests {
             data_retention_sec: 3600
             enabled: true
             removal_delay_sec: 60
+            archive_interval: 1000
+            call_traces_retention_sec: 600
+            storage_logs_retention: 30 min
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let config: PruningConfig = test_complete(yaml).unwrap();
@@ -1,10 +1,15 @@
-use std::num::NonZeroUsize;
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    num::NonZeroUsize,
+    path::PathBuf,
+    time::Duration,
+};
 
 use smart_config::{
-    de::{Optional, Serde},
+    de::{Delimited, Optional, Serde},
     DescribeConfig, DeserializeConfig,
 };
-use zksync_basic_types::L1BatchNumber;
+use zksync_basic_types::{L1BatchNumber, H256};
 
 use crate::ObjectStoreConfig;
 
@@ -35,6 +40,18 @@ pub struct PostgresRecoveryConfig {
     pub max_concurrency: NonZeroUsize,
 }
 
+/// Serving a local snapshot archive to peer nodes, so that they can recover from it without accessing the object store.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+#[config(derive(Default))]
+pub struct SnapshotServingConfig {
+    /// Path to a local snapshot archive to serve to peers. If not set, the archive is not served.
+    pub archive_path: Option<PathBuf>,
+    /// Socket address to bind the snapshot archive server to. By default, the server listens on all interfaces
+    /// on port 3076.
+    #[config(default_t = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 3_076))]
+    pub bind_addr: SocketAddr,
+}
+
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 #[config(derive(Default))]
 pub struct SnapshotRecoveryConfig {
@@ -50,6 +67,29 @@ pub struct SnapshotRecoveryConfig {
     /// This is a temporary flag that will eventually be removed together with version 0 snapshot support.
     #[config(default)]
     pub drop_storage_key_preimages: bool,
+    /// Path to a local snapshot archive to recover from (such an archive can be created by the snapshots creator
+    /// with the `--export-path` arg). If set, the snapshot is read from the archive instead of being fetched
+    /// via the main node API and the object store; `archive_root_hash` must be set as well. The archive is accessed
+    /// on each node start while this param is set; it can be unset once recovery is completed.
+    ///
+    /// The archive must be obtained from a trusted source: only the storage logs are verified (against
+    /// `archive_root_hash`), while the rest of snapshot metadata (the L2 block hash, timestamps, protocol version
+    /// and tokens) is taken from the archive manifest without verification.
+    pub archive_path: Option<PathBuf>,
+    /// Trusted root hash of the snapshot L1 batch in the archive specified by `archive_path`. Recovered storage logs
+    /// are verified against this hash. Other snapshot metadata in the archive is not covered by this hash.
+    pub archive_root_hash: Option<H256>,
+    /// Base URLs of peer nodes serving a snapshot archive (see `serving`), specified as a comma-separated list.
+    /// If set, the newest snapshot served by peers is fetched from them in parallel instead of the object store.
+    /// Peers are untrusted: fetched objects are checked against the archive manifest, and recovered storage logs
+    /// are verified against the L1 batch root hash from the main node. Ignored if `archive_path` is set.
+    #[config(default, with = Delimited(","))]
+    pub peers: Vec<String>,
+    /// Timeout for a single request to a snapshot peer.
+    #[config(default_t = Duration::from_secs(60))]
+    pub peer_request_timeout: Duration,
+    #[config(nest)]
+    pub serving: SnapshotServingConfig,
     #[config(nest)]
     pub tree: TreeRecoveryConfig,
     #[config(nest)]
@@ -71,6 +111,17 @@ mod tests {
             enabled: false,
             l1_batch: Some(L1BatchNumber(1234)),
             drop_storage_key_preimages: true,
+            archive_path: Some("/snapshots/era.bin".into()),
+            archive_root_hash: Some(H256::repeat_byte(0x11)),
+            peers: vec![
+                "http://peer-1:3076".to_owned(),
+                "http://peer-2:3076".to_owned(),
+            ],
+            peer_request_timeout: Duration::from_secs(30),
+            serving: SnapshotServingConfig {
+                archive_path: Some("/snapshots/served.bin".into()),
+                bind_addr: "127.0.0.1:3080".parse().unwrap(),
+            },
             tree: TreeRecoveryConfig {
                 chunk_size: 250000,
                 parallel_persistence_buffer: Some(NonZeroUsize::new(4).unwrap()),
@@ -84,6 +135,7 @@ mod tests {
                 },
                 max_retries: 100,
                 local_mirror_path: None,
+                encryption: None,
             }),
         }
     }
@@ -112,6 +164,12 @@ mod tests {
             EN_SNAPSHOTS_RECOVERY_ENABLED=false
             EN_SNAPSHOTS_RECOVERY_L1_BATCH=1234
             EN_SNAPSHOTS_RECOVERY_DROP_STORAGE_KEY_PREIMAGES=true
+            EN_SNAPSHOTS_RECOVERY_ARCHIVE_PATH=/snapshots/era.bin
+            EN_SNAPSHOTS_RECOVERY_ARCHIVE_ROOT_HASH=0x1111111111111111111111111111111111111111111111111111111111111111
+            EN_SNAPSHOTS_RECOVERY_PEERS=http://peer-1:3076,http://peer-2:3076
+            EN_SNAPSHOTS_RECOVERY_PEER_REQUEST_TIMEOUT=30s
+            EN_SNAPSHOTS_RECOVERY_SERVING_ARCHIVE_PATH=/snapshots/served.bin
+            EN_SNAPSHOTS_RECOVERY_SERVING_BIND_ADDR=127.0.0.1:3080
             EN_SNAPSHOTS_RECOVERY_TREE_CHUNK_SIZE=250000
             EN_SNAPSHOTS_RECOVERY_TREE_PARALLEL_PERSISTENCE_BUFFER=4
 
@@ -136,6 +194,15 @@ mod tests {
           enabled: false
           l1_batch: 1234
           drop_storage_key_preimages: true
+          archive_path: /snapshots/era.bin
+          archive_root_hash: '0x1111111111111111111111111111111111111111111111111111111111111111'
+          peers:
+            - http://peer-1:3076
+            - http://peer-2:3076
+          peer_request_timeout: 30s
+          serving:
+            archive_path: /snapshots/served.bin
+            bind_addr: 127.0.0.1:3080
           postgres:
             max_concurrency: 10
           tree:
@@ -9,6 +9,9 @@ use crate::ObjectStoreConfig;
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 pub struct SnapshotsCreatorConfig {
     /// Version of snapshots to create.
+    ///
+    /// Version 2 creates incremental snapshots containing only changes since the newest complete snapshot
+    /// with a lesser L1 batch number. If there is no such snapshot, a full (version 1) snapshot is created instead.
     // Raw integer version is used because `SnapshotVersion` is defined in `zksync_types` crate.
     #[config(default)]
     pub version: u16,
@@ -68,6 +71,7 @@ mod tests {
                 },
                 max_retries: 100,
                 local_mirror_path: None,
+                encryption: None,
             },
         }
     }
@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use serde::{de::Error as DeError, Deserialize};
 use serde_json::Value;
 use smart_config::{
@@ -46,6 +48,66 @@ impl DeserializeParam<K256PrivateKey> for K256PrivateKeyDeserializer {
     }
 }
 
+/// Deserializes a comma-separated list of private keys.
+#[derive(Debug)]
+struct K256PrivateKeysDeserializer;
+
+impl DeserializeParam<Vec<K256PrivateKey>> for K256PrivateKeysDeserializer {
+    const EXPECTING: BasicTypes = BasicTypes::STRING;
+
+    fn deserialize_param(
+        &self,
+        ctx: DeserializeContext<'_>,
+        param: &'static ParamMetadata,
+    ) -> Result<Vec<K256PrivateKey>, ErrorWithOrigin> {
+        let de = ctx.current_value_deserializer(param.name)?;
+        let keys = String::deserialize(de)?;
+        keys.split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(|key| {
+                let key = H256::from_str(key).map_err(DeError::custom)?;
+                K256PrivateKey::from_bytes(key).map_err(DeError::custom)
+            })
+            .collect()
+    }
+
+    fn serialize_param(&self, param: &Vec<K256PrivateKey>) -> Value {
+        let keys: Vec<_> = param
+            .iter()
+            .map(|key| format!("{:?}", H256(*key.expose_secret().as_ref())))
+            .collect();
+        Value::String(keys.join(","))
+    }
+}
+
+/// Deserializes a comma-separated list of GCP KMS resource names.
+#[derive(Debug)]
+struct GcpKmsResourcesDeserializer;
+
+impl DeserializeParam<Vec<String>> for GcpKmsResourcesDeserializer {
+    const EXPECTING: BasicTypes = BasicTypes::STRING;
+
+    fn deserialize_param(
+        &self,
+        ctx: DeserializeContext<'_>,
+        param: &'static ParamMetadata,
+    ) -> Result<Vec<String>, ErrorWithOrigin> {
+        let de = ctx.current_value_deserializer(param.name)?;
+        let resources = String::deserialize(de)?;
+        Ok(resources
+            .split(',')
+            .map(str::trim)
+            .filter(|resource| !resource.is_empty())
+            .map(str::to_owned)
+            .collect())
+    }
+
+    fn serialize_param(&self, param: &Vec<String>) -> Value {
+        Value::String(param.join(","))
+    }
+}
+
 /// Wallet configuration supporting both local private keys and GCP KMS keys.
 ///
 /// Exactly one of `private_key` or `gcp_kms_resource` must be provided.
@@ -176,6 +238,23 @@ pub struct Wallets {
     /// Wallet for the SL operator when using blob commitments.
     #[config(nest)]
     pub blob_operator: Option<Wallet>,
+    /// Private keys of additional accounts sharing the load of `operator` when settling on L1.
+    /// Specified as a comma-separated list; each account has an independent nonce sequence.
+    #[config(secret, default, with = K256PrivateKeysDeserializer)]
+    pub additional_operators: Vec<K256PrivateKey>,
+    /// Private keys of additional accounts sharing the load of `blob_operator` (i.e., sending commit transactions).
+    /// Ignored if `blob_operator` is not set.
+    #[config(secret, default, with = K256PrivateKeysDeserializer)]
+    pub additional_blob_operators: Vec<K256PrivateKey>,
+    /// GCP KMS resource names of additional accounts sharing the load of `operator`,
+    /// in addition to `additional_operators`. Specified as a comma-separated list;
+    /// account addresses are fetched from KMS on startup.
+    #[config(secret, default, with = GcpKmsResourcesDeserializer)]
+    pub additional_gcp_kms_operators: Vec<String>,
+    /// GCP KMS resource names of additional accounts sharing the load of `blob_operator`,
+    /// in addition to `additional_blob_operators`. Ignored if `blob_operator` is not set.
+    #[config(secret, default, with = GcpKmsResourcesDeserializer)]
+    pub additional_gcp_kms_blob_operators: Vec<String>,
     /// Fee account.
     #[config(nest)]
     pub fee_account: Option<AddressWallet>,
@@ -192,6 +271,10 @@ impl Wallets {
             blob_operator: Some(
                 Wallet::from_private_key_bytes(H256::repeat_byte(0x2), None).unwrap(),
             ),
+            additional_operators: vec![],
+            additional_blob_operators: vec![],
+            additional_gcp_kms_operators: vec![],
+            additional_gcp_kms_blob_operators: vec![],
             fee_account: Some(AddressWallet::from_address(H160::repeat_byte(0x3))),
             token_multiplier_setter: Some(
                 Wallet::from_private_key_bytes(H256::repeat_byte(0x4), None).unwrap(),
@@ -264,6 +347,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parsing_additional_operators() {
+        let yaml = r#"
+            operator:
+              private_key: 0xf00bf4165f9e1a67841b981949033c06c1423dab34c33d6d1237ae14d85bd729
+            additional_operators: "0xc9ee945b2f6d4c462a743f5af3904a4ee78aec0218f1f4f3c53d0bfbf809b520, 0xe338cadae0f665139a7a4f2b846b91e188a2d100dcd34f58771c903cd2b08cd1"
+        "#;
+        let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
+
+        let wallets: Wallets = test(yaml).unwrap();
+        let addresses: Vec<Address> = wallets
+            .additional_operators
+            .iter()
+            .map(K256PrivateKey::address)
+            .collect();
+        assert_eq!(
+            addresses,
+            [
+                "0x5927c313861c01b82a026e35d93cc787e5356c0f"
+                    .parse()
+                    .unwrap(),
+                "0x7ea53e0f1eb0b3b578aeda336b2c3a778e04eebf"
+                    .parse()
+                    .unwrap(),
+            ]
+        );
+        assert!(wallets.additional_blob_operators.is_empty());
+        assert!(wallets.additional_gcp_kms_operators.is_empty());
+    }
+
+    #[test]
+    fn parsing_additional_gcp_kms_operators() {
+        let yaml = r#"
+            operator:
+              address: 0xabcf96e1ee478481042a0c4e34cdceceae01b154
+              gcp_kms_resource: "projects/my-project/locations/us-central1/keyRings/my-ring/cryptoKeys/my-key/cryptoKeyVersions/1"
+            additional_operators: "0xc9ee945b2f6d4c462a743f5af3904a4ee78aec0218f1f4f3c53d0bfbf809b520"
+            additional_gcp_kms_operators: "projects/my-project/locations/us-central1/keyRings/my-ring/cryptoKeys/key-2/cryptoKeyVersions/1, projects/my-project/locations/us-central1/keyRings/my-ring/cryptoKeys/key-3/cryptoKeyVersions/1"
+            additional_gcp_kms_blob_operators: "projects/my-project/locations/us-central1/keyRings/my-ring/cryptoKeys/key-4/cryptoKeyVersions/1"
+        "#;
+        let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
+
+        let wallets: Wallets = test(yaml).unwrap();
+        assert_eq!(wallets.additional_operators.len(), 1);
+        assert_eq!(
+            wallets.additional_gcp_kms_operators,
+            [
+                "projects/my-project/locations/us-central1/keyRings/my-ring/cryptoKeys/key-2/cryptoKeyVersions/1",
+                "projects/my-project/locations/us-central1/keyRings/my-ring/cryptoKeys/key-3/cryptoKeyVersions/1",
+            ]
+        );
+        assert_eq!(
+            wallets.additional_gcp_kms_blob_operators,
+            ["projects/my-project/locations/us-central1/keyRings/my-ring/cryptoKeys/key-4/cryptoKeyVersions/1"]
+        );
+    }
+
     /// `private_key` can be omitted when using GCP KMS.
     #[test]
     fn parsing_gcp_kms_wallet() {
@@ -0,0 +1,141 @@
+//! Splitting of pubdata exceeding the blob size limit of a DA layer into several blobs (chunks).
+//!
+//! A split blob is tracked as a single logical blob, with the following formats:
+//!
+//! - **Blob ID** is [`CHUNKED_ID_PREFIX`] followed by a JSON array with blob IDs of the chunks
+//!   in the chunk order, e.g. `chunked:["0x01...","0x02..."]`. Dispatch request IDs of the chunks
+//!   are combined in the same way.
+//! - **Inclusion data** is the ABI encoding of the inclusion data of the chunks in the chunk order,
+//!   i.e. `abi.encode(bytes[] chunksInclusionData)`. It has to be verified by an L1 DA validator
+//!   aware of this format.
+//!
+//! Pubdata of a split blob is the concatenation of the chunks' data in the chunk order.
+
+use anyhow::Context as _;
+use zksync_types::ethabi;
+
+use crate::{
+    types::{DAError, InclusionData},
+    DataAvailabilityClient,
+};
+
+/// Prefix of the blob ID (or dispatch request ID) of a split blob.
+pub const CHUNKED_ID_PREFIX: &str = "chunked:";
+
+/// Splits `data` into chunks no larger than `blob_size_limit`.
+pub fn split_blob(data: &[u8], blob_size_limit: usize) -> Vec<Vec<u8>> {
+    assert!(blob_size_limit > 0, "blob size limit must be positive");
+    data.chunks(blob_size_limit).map(<[u8]>::to_vec).collect()
+}
+
+/// Encodes the blob ID (or dispatch request ID) of a split blob from the IDs of its chunks.
+pub fn encode_chunked_id(chunk_ids: &[String]) -> String {
+    let ids = serde_json::to_string(chunk_ids).expect("failed serializing chunk IDs");
+    format!("{CHUNKED_ID_PREFIX}{ids}")
+}
+
+/// Decodes IDs of the chunks from the blob ID (or dispatch request ID) of a split blob. Returns `Ok(None)`
+/// if the ID doesn't correspond to a split blob.
+pub fn decode_chunked_id(id: &str) -> anyhow::Result<Option<Vec<String>>> {
+    let Some(ids) = id.strip_prefix(CHUNKED_ID_PREFIX) else {
+        return Ok(None);
+    };
+    let ids: Vec<String> = serde_json::from_str(ids).context("invalid chunked ID")?;
+    anyhow::ensure!(!ids.is_empty(), "chunked ID has no chunks");
+    Ok(Some(ids))
+}
+
+/// Combines inclusion data of the chunks into the inclusion data of the split blob.
+pub fn combine_inclusion_data(chunks_inclusion_data: Vec<Vec<u8>>) -> Vec<u8> {
+    let chunks = chunks_inclusion_data
+        .into_iter()
+        .map(ethabi::Token::Bytes)
+        .collect();
+    ethabi::encode(&[ethabi::Token::Array(chunks)])
+}
+
+/// Splits the inclusion data of a split blob into the inclusion data of its chunks.
+pub fn split_inclusion_data(inclusion_data: &[u8]) -> anyhow::Result<Vec<Vec<u8>>> {
+    let param_type = ethabi::ParamType::Array(Box::new(ethabi::ParamType::Bytes));
+    let mut tokens =
+        ethabi::decode(&[param_type], inclusion_data).context("invalid combined inclusion data")?;
+    let chunks = tokens
+        .pop()
+        .and_then(ethabi::Token::into_array)
+        .context("invalid combined inclusion data")?;
+    chunks
+        .into_iter()
+        .map(|chunk| chunk.into_bytes().context("invalid chunk inclusion data"))
+        .collect()
+}
+
+/// Fetches inclusion data for all chunks of a split blob and combines it. Returns `Ok(None)` if inclusion data
+/// is not available for at least one chunk.
+pub async fn get_chunked_inclusion_data(
+    client: &dyn DataAvailabilityClient,
+    chunk_blob_ids: &[String],
+) -> Result<Option<InclusionData>, DAError> {
+    let mut chunks_inclusion_data = Vec::with_capacity(chunk_blob_ids.len());
+    for blob_id in chunk_blob_ids {
+        let Some(inclusion_data) = client.get_inclusion_data(blob_id).await? else {
+            return Ok(None);
+        };
+        chunks_inclusion_data.push(inclusion_data.data);
+    }
+    Ok(Some(InclusionData {
+        data: combine_inclusion_data(chunks_inclusion_data),
+    }))
+}
+
+/// Fetches data of all chunks of a split blob and concatenates it. Returns `Ok(None)` if data is not available
+/// for at least one chunk.
+pub async fn get_chunked_blob_data(
+    client: &dyn DataAvailabilityClient,
+    chunk_blob_ids: &[String],
+) -> Result<Option<Vec<u8>>, DAError> {
+    let mut data = vec![];
+    for blob_id in chunk_blob_ids {
+        let Some(chunk) = client.get_blob_data(blob_id).await? else {
+            return Ok(None);
+        };
+        data.extend(chunk);
+    }
+    Ok(Some(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splitting_blob() {
+        let data: Vec<u8> = (0..10).collect();
+        assert_eq!(split_blob(&data, 20), [data.clone()]);
+        assert_eq!(
+            split_blob(&data, 4),
+            [vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![8, 9]]
+        );
+        assert_eq!(split_blob(&data, 4).concat(), data);
+        assert!(split_blob(&[], 4).is_empty());
+    }
+
+    #[test]
+    fn chunked_id_roundtrip() {
+        let ids = vec!["0x01".to_owned(), "{\"height\":1}".to_owned()];
+        let id = encode_chunked_id(&ids);
+        assert!(id.starts_with(CHUNKED_ID_PREFIX));
+        assert_eq!(decode_chunked_id(&id).unwrap(), Some(ids));
+
+        assert_eq!(decode_chunked_id("0x01").unwrap(), None);
+        decode_chunked_id("chunked:[]").unwrap_err();
+        decode_chunked_id("chunked:0x01").unwrap_err();
+    }
+
+    #[test]
+    fn combined_inclusion_data_roundtrip() {
+        let chunks = vec![vec![1; 33], vec![], vec![2; 5]];
+        let combined = combine_inclusion_data(chunks.clone());
+        assert_eq!(split_inclusion_data(&combined).unwrap(), chunks);
+        split_inclusion_data(&[1, 2, 3]).unwrap_err();
+    }
+}
@@ -3,8 +3,11 @@ use std::fmt;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 
-use crate::types::{ClientType, DAError, DispatchResponse, FinalityResponse, InclusionData};
+use crate::types::{
+    ClientType, DAError, DispatchResponse, FinalityResponse, InclusionData, InclusionVerification,
+};
 
+pub mod chunking;
 #[cfg(feature = "node_framework")]
 pub mod node;
 pub mod types;
@@ -29,12 +32,48 @@ pub trait DataAvailabilityClient: Sync + Send + fmt::Debug {
     /// Fetches the inclusion data for a given blob_id.
     async fn get_inclusion_data(&self, blob_id: &str) -> Result<Option<InclusionData>, DAError>;
 
+    /// Independently verifies the inclusion data returned by [`Self::get_inclusion_data()`] for the specified blob
+    /// against the attestations of the DA layer (e.g., on the settlement layer). Only called if
+    /// [`Self::supports_inclusion_verification()`] returns `true`.
+    async fn verify_inclusion_data(
+        &self,
+        blob_id: &str,
+        _inclusion_data: &InclusionData,
+    ) -> Result<InclusionVerification, DAError> {
+        Err(DAError {
+            error: anyhow::anyhow!(
+                "verifying inclusion data for blob {blob_id} is not supported by the DA client"
+            ),
+            is_retriable: false,
+        })
+    }
+
+    /// Returns whether the client supports [verifying](Self::verify_inclusion_data()) inclusion data.
+    fn supports_inclusion_verification(&self) -> bool {
+        false
+    }
+
+    /// Fetches the data of a dispatched blob by its ID. Returns `Ok(None)` if the blob is not found.
+    /// Used to reconstruct the state from the published pubdata; not all clients support it.
+    async fn get_blob_data(&self, blob_id: &str) -> Result<Option<Vec<u8>>, DAError> {
+        Err(DAError {
+            error: anyhow::anyhow!("fetching blob {blob_id} is not supported by the DA client"),
+            is_retriable: false,
+        })
+    }
+
     /// Clones the client and wraps it in a Box.
     fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient>;
 
     /// Returns the maximum size of the blob (in bytes) that can be dispatched. None means no limit.
     fn blob_size_limit(&self) -> Option<usize>;
 
+    /// Returns whether the client supports dispatching several blobs for the same L1 batch, which is required
+    /// to [split](crate::chunking) pubdata exceeding [`Self::blob_size_limit()`].
+    fn supports_chunked_dispatch(&self) -> bool {
+        true
+    }
+
     /// Returns the name of the client implementation.
     fn client_type(&self) -> ClientType;
 
@@ -55,6 +55,17 @@ pub struct InclusionData {
     pub data: Vec<u8>,
 }
 
+/// Outcome of verifying [`InclusionData`] against the attestations of the data availability layer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InclusionVerification {
+    /// Inclusion data is consistent with the attestations.
+    Verified,
+    /// Attestations covering the blob are not available yet; verification should be repeated later.
+    Pending,
+    /// Inclusion data contradicts the attestations; the blob should be dispatched again.
+    Invalid(String),
+}
+
 pub enum ClientType {
     NoDA,
     Avail,
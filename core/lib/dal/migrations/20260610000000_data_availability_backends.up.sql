@@ -0,0 +1,16 @@
+-- Per-backend dispatch state for the redundant DA client, which dispatches each blob to several DA layers.
+CREATE TABLE IF NOT EXISTS data_availability_backends
+(
+    l1_batch_number     BIGINT    NOT NULL REFERENCES l1_batches (number) ON DELETE CASCADE,
+    backend             TEXT      NOT NULL,
+
+    dispatch_request_id TEXT,
+    blob_id             TEXT,
+    inclusion_data      BYTEA,
+    error               TEXT,
+    sent_at             TIMESTAMP,
+
+    created_at          TIMESTAMP NOT NULL,
+    updated_at          TIMESTAMP NOT NULL,
+    PRIMARY KEY (l1_batch_number, backend)
+);
@@ -0,0 +1 @@
+ALTER TABLE snapshots ADD COLUMN IF NOT EXISTS base_l1_batch_number BIGINT;
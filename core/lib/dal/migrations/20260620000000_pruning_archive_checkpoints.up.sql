@@ -0,0 +1,22 @@
+-- Archive checkpoints retained by Postgres pruning. For each checkpoint, storage logs required to read the storage state
+-- as of the last L2 block of the L1 batch are retained after the L1 batch is pruned. Together with a checkpoint, metadata
+-- necessary to execute calls on top of it is copied since the corresponding `miniblocks` and `l1_batches` rows are pruned.
+CREATE TABLE IF NOT EXISTS pruning_archive_checkpoints
+(
+    l1_batch_number           BIGINT      NOT NULL PRIMARY KEY,
+    miniblock_number          BIGINT      NOT NULL UNIQUE,
+    miniblock_hash            BYTEA       NOT NULL,
+    protocol_version          INT,
+    l1_gas_price              BIGINT      NOT NULL,
+    l2_fair_gas_price         BIGINT      NOT NULL,
+    fair_pubdata_price        BIGINT,
+    l1_batch_timestamp        BIGINT      NOT NULL,
+    settlement_layer_type     TEXT        NOT NULL,
+    settlement_layer_chain_id BIGINT      NOT NULL,
+    interop_fee               NUMERIC(80) NOT NULL,
+    -- Information about the preceding L2 block; NULL if it is not available (e.g., for the genesis block).
+    prev_miniblock_hash       BYTEA,
+    prev_miniblock_timestamp  BIGINT,
+    prev_txs_rolling_hash     BYTEA,
+    created_at                TIMESTAMP   NOT NULL
+);
@@ -0,0 +1,11 @@
+-- Pruning log for data categories (e.g., call traces) that can be pruned ahead of the main Postgres pruning.
+-- Has the same semantics as `pruning_log`, but is scoped by the data category.
+CREATE TABLE IF NOT EXISTS pruning_category_log
+(
+    category         TEXT       NOT NULL,
+    type             prune_type NOT NULL,
+    pruned_l1_batch  BIGINT     NOT NULL,
+    pruned_miniblock BIGINT     NOT NULL,
+    created_at       TIMESTAMP  NOT NULL,
+    PRIMARY KEY (category, type, pruned_l1_batch)
+);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS en_reorg_reverts;
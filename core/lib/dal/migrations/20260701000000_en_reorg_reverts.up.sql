@@ -0,0 +1,8 @@
+-- Audit log of automatic rollbacks performed by the external node after detecting a reorg on the main node.
+CREATE TABLE IF NOT EXISTS en_reorg_reverts
+(
+    id                     BIGSERIAL PRIMARY KEY,
+    reverted_from_l1_batch BIGINT    NOT NULL,
+    last_retained_l1_batch BIGINT    NOT NULL,
+    created_at             TIMESTAMP NOT NULL
+);
@@ -0,0 +1,11 @@
+-- Cursor of the external node fast sync: the next applied L1 batch whose state root hash is not verified yet.
+-- NULL if all L1 batches applied by fast sync are verified.
+CREATE TABLE IF NOT EXISTS fast_sync_info
+(
+    next_l1_batch_to_verify BIGINT,
+    created_at              TIMESTAMP NOT NULL,
+    updated_at              TIMESTAMP NOT NULL
+);
+
+INSERT INTO fast_sync_info(next_l1_batch_to_verify, created_at, updated_at)
+VALUES (NULL, NOW(), NOW());
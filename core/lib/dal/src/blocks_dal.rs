@@ -7,6 +7,7 @@ use std::{
 
 use anyhow::Context as _;
 use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
+use serde::Serialize;
 use sqlx::types::chrono::{DateTime, Utc};
 use zksync_config::configs::eth_sender::ProverType;
 use zksync_db_connection::{
@@ -69,6 +70,26 @@ pub struct TxForPrecommit {
     pub is_success: bool,
 }
 
+/// Numbers of rows affected by rolling back Postgres data to a certain L2 block and L1 batch.
+/// Returned by [`BlocksDal::count_rows_to_roll_back()`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RollbackRowCounts {
+    /// Transactions that would be reset to the mempool state.
+    pub transactions: u64,
+    pub call_traces: u64,
+    pub events: u64,
+    pub l2_to_l1_logs: u64,
+    pub factory_deps: u64,
+    pub storage_logs: u64,
+    pub l2_blocks: u64,
+    pub eth_txs: u64,
+    pub snapshots: u64,
+    pub l1_batches: u64,
+    pub initial_writes: u64,
+    pub vm_runner_protective_reads: u64,
+    pub vm_runner_bwip: u64,
+}
+
 impl BlocksDal<'_, '_> {
     pub async fn get_consistency_checker_last_processed_l1_batch(
         &mut self,
@@ -109,6 +130,74 @@ impl BlocksDal<'_, '_> {
         Ok(())
     }
 
+    /// Returns the next L1 batch applied by fast sync that is awaiting state root hash verification, or `None`
+    /// if all applied batches are verified.
+    pub async fn get_fast_sync_next_l1_batch_to_verify(
+        &mut self,
+    ) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                next_l1_batch_to_verify
+            FROM
+                fast_sync_info
+            "#
+        )
+        .instrument("get_fast_sync_next_l1_batch_to_verify")
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row
+            .next_l1_batch_to_verify
+            .map(|number| L1BatchNumber(number as u32)))
+    }
+
+    pub async fn set_fast_sync_next_l1_batch_to_verify(
+        &mut self,
+        l1_batch_number: Option<L1BatchNumber>,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE fast_sync_info
+            SET
+                next_l1_batch_to_verify = $1,
+                updated_at = NOW()
+            "#,
+            l1_batch_number.map(|number| i64::from(number.0)),
+        )
+        .instrument("set_fast_sync_next_l1_batch_to_verify")
+        .report_latency()
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Moves the fast sync verification cursor back if it points after `last_l1_batch_to_keep`, so that L1 batches
+    /// re-applied after a rollback are verified.
+    pub async fn roll_back_fast_sync_next_l1_batch_to_verify(
+        &mut self,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE fast_sync_info
+            SET
+                next_l1_batch_to_verify = $1,
+                updated_at = NOW()
+            WHERE
+                next_l1_batch_to_verify > $1
+            "#,
+            i64::from(last_l1_batch_to_keep.0) + 1,
+        )
+        .instrument("roll_back_fast_sync_next_l1_batch_to_verify")
+        .report_latency()
+        .with_arg("last_l1_batch_to_keep", &last_l1_batch_to_keep)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     pub async fn is_genesis_needed(&mut self) -> DalResult<bool> {
         let count = sqlx::query!(
             r#"
@@ -3402,6 +3491,175 @@ impl BlocksDal<'_, '_> {
         Ok(())
     }
 
+    /// Counts rows that would be deleted or reset when rolling back Postgres data so that `last_l2_block_to_keep`
+    /// and `last_l1_batch_to_keep` are the last retained L2 block and L1 batch, respectively. Doesn't change any data.
+    pub async fn count_rows_to_roll_back(
+        &mut self,
+        last_l2_block_to_keep: L2BlockNumber,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> DalResult<RollbackRowCounts> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        transactions
+                    WHERE
+                        miniblock_number > $1
+                ) AS "transactions!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        call_traces
+                    INNER JOIN transactions ON call_traces.tx_hash = transactions.hash
+                    WHERE
+                        transactions.miniblock_number > $1
+                ) AS "call_traces!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        events
+                    WHERE
+                        miniblock_number > $1
+                ) AS "events!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        l2_to_l1_logs
+                    WHERE
+                        miniblock_number > $1
+                ) AS "l2_to_l1_logs!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        factory_deps
+                    WHERE
+                        miniblock_number > $1
+                ) AS "factory_deps!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        storage_logs
+                    WHERE
+                        miniblock_number > $1
+                ) AS "storage_logs!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        miniblocks
+                    WHERE
+                        number > $1
+                ) AS "l2_blocks!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        eth_txs
+                    WHERE
+                        id IN (
+                            (
+                                SELECT
+                                    eth_commit_tx_id
+                                FROM
+                                    l1_batches
+                                WHERE
+                                    number > $2
+                            )
+                            UNION
+                            (
+                                SELECT
+                                    eth_prove_tx_id
+                                FROM
+                                    l1_batches
+                                WHERE
+                                    number > $2
+                            )
+                            UNION
+                            (
+                                SELECT
+                                    eth_execute_tx_id
+                                FROM
+                                    l1_batches
+                                WHERE
+                                    number > $2
+                            )
+                        )
+                ) AS "eth_txs!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        snapshots
+                    WHERE
+                        l1_batch_number > $2
+                ) AS "snapshots!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        l1_batches
+                    WHERE
+                        number > $2
+                ) AS "l1_batches!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        initial_writes
+                    WHERE
+                        l1_batch_number > $2
+                ) AS "initial_writes!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        vm_runner_protective_reads
+                    WHERE
+                        l1_batch_number > $2
+                ) AS "vm_runner_protective_reads!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        vm_runner_bwip
+                    WHERE
+                        l1_batch_number > $2
+                ) AS "vm_runner_bwip!"
+            "#,
+            i64::from(last_l2_block_to_keep.0),
+            i64::from(last_l1_batch_to_keep.0)
+        )
+        .instrument("count_rows_to_roll_back")
+        .with_arg("last_l2_block_to_keep", &last_l2_block_to_keep)
+        .with_arg("last_l1_batch_to_keep", &last_l1_batch_to_keep)
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(RollbackRowCounts {
+            transactions: row.transactions as u64,
+            call_traces: row.call_traces as u64,
+            events: row.events as u64,
+            l2_to_l1_logs: row.l2_to_l1_logs as u64,
+            factory_deps: row.factory_deps as u64,
+            storage_logs: row.storage_logs as u64,
+            l2_blocks: row.l2_blocks as u64,
+            eth_txs: row.eth_txs as u64,
+            snapshots: row.snapshots as u64,
+            l1_batches: row.l1_batches as u64,
+            initial_writes: row.initial_writes as u64,
+            vm_runner_protective_reads: row.vm_runner_protective_reads as u64,
+            vm_runner_bwip: row.vm_runner_bwip as u64,
+        })
+    }
+
     /// Deletes all L1 batches from the storage so that the specified batch number is the last one left.
     pub async fn delete_l1_batches(&mut self, last_batch_to_keep: L1BatchNumber) -> DalResult<()> {
         self.delete_l1_batches_inner(Some(last_batch_to_keep)).await
@@ -6,12 +6,17 @@ use zksync_db_connection::{
 use zksync_types::{
     commitment::PubdataType,
     l2_to_l1_log::L2ToL1Log,
-    pubdata_da::{DataAvailabilityBlob, DataAvailabilityDetails},
+    pubdata_da::{
+        DataAvailabilityBackendBlob, DataAvailabilityBacklog, DataAvailabilityBlob,
+        DataAvailabilityChunk, DataAvailabilityDetails,
+    },
     Address, L1BatchNumber,
 };
 
 use crate::{
-    models::storage_data_availability::{L1BatchDA, StorageDABlob, StorageDADetails},
+    models::storage_data_availability::{
+        L1BatchDA, StorageDABackendBlob, StorageDABlob, StorageDAChunk, StorageDADetails,
+    },
     Core,
 };
 
@@ -267,6 +272,362 @@ impl DataAvailabilityDal<'_, '_> {
         Ok(())
     }
 
+    /// Inserts the dispatch request ID for a chunk of the split blob for the given L1 batch.
+    pub async fn insert_l1_batch_da_chunk_request_id(
+        &mut self,
+        number: L1BatchNumber,
+        chunk_index: usize,
+        dispatch_request_id: &str,
+        sent_at: chrono::NaiveDateTime,
+    ) -> DalResult<()> {
+        let update_result = sqlx::query!(
+            r#"
+            INSERT INTO
+            data_availability_chunks (
+                l1_batch_number,
+                chunk_index,
+                dispatch_request_id,
+                sent_at,
+                created_at,
+                updated_at
+            )
+            VALUES
+            ($1, $2, $3, $4, NOW(), NOW())
+            ON CONFLICT DO NOTHING
+            "#,
+            i64::from(number.0),
+            chunk_index as i32,
+            dispatch_request_id,
+            sent_at,
+        )
+        .instrument("insert_l1_batch_da_chunk_request_id")
+        .with_arg("number", &number)
+        .with_arg("chunk_index", &chunk_index)
+        .with_arg("dispatch_request_id", &dispatch_request_id)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+
+        if update_result.rows_affected() == 0 {
+            tracing::error!(
+                "L1 batch #{number}: DA chunk #{chunk_index} was attempted to be inserted twice"
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns chunks of the split blob for the given L1 batch ordered by the chunk index. Returns an empty vector
+    /// if the blob for the batch is not split (or was not dispatched yet).
+    pub async fn get_l1_batch_da_chunks(
+        &mut self,
+        number: L1BatchNumber,
+    ) -> DalResult<Vec<DataAvailabilityChunk>> {
+        let rows = sqlx::query_as!(
+            StorageDAChunk,
+            r#"
+            SELECT
+                l1_batch_number,
+                chunk_index,
+                dispatch_request_id,
+                blob_id,
+                inclusion_data,
+                sent_at
+            FROM
+                data_availability_chunks
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                chunk_index
+            "#,
+            i64::from(number.0),
+        )
+        .instrument("get_l1_batch_da_chunks")
+        .with_arg("number", &number)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Sets the blob ID for a chunk of the split blob for the given L1 batch.
+    pub async fn set_chunk_blob_id(
+        &mut self,
+        number: L1BatchNumber,
+        chunk_index: usize,
+        blob_id: &str,
+    ) -> DalResult<()> {
+        let update_result = sqlx::query!(
+            r#"
+            UPDATE data_availability_chunks
+            SET
+                blob_id = $1,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $2
+                AND chunk_index = $3
+            "#,
+            blob_id,
+            i64::from(number.0),
+            chunk_index as i32,
+        )
+        .instrument("set_chunk_blob_id")
+        .with_arg("number", &number)
+        .with_arg("chunk_index", &chunk_index)
+        .with_arg("blob_id", &blob_id)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+
+        if update_result.rows_affected() == 0 {
+            tracing::error!(
+                "L1 batch #{number}: blob_id for DA chunk #{chunk_index} wasn't updated"
+            );
+        }
+        Ok(())
+    }
+
+    /// Saves the inclusion data for a chunk of the split blob for the given L1 batch.
+    pub async fn save_chunk_inclusion_data(
+        &mut self,
+        number: L1BatchNumber,
+        chunk_index: usize,
+        inclusion_data: &[u8],
+    ) -> DalResult<()> {
+        let update_result = sqlx::query!(
+            r#"
+            UPDATE data_availability_chunks
+            SET
+                inclusion_data = $1,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $2
+                AND chunk_index = $3
+            "#,
+            inclusion_data,
+            i64::from(number.0),
+            chunk_index as i32,
+        )
+        .instrument("save_chunk_inclusion_data")
+        .with_arg("number", &number)
+        .with_arg("chunk_index", &chunk_index)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+
+        if update_result.rows_affected() == 0 {
+            tracing::error!(
+                "L1 batch #{number}: inclusion data for DA chunk #{chunk_index} wasn't updated"
+            );
+        }
+        Ok(())
+    }
+
+    /// Removes a chunk of the split blob for the given L1 batch, so that it's dispatched again.
+    pub async fn remove_data_availability_chunk(
+        &mut self,
+        number: L1BatchNumber,
+        chunk_index: usize,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM data_availability_chunks
+            WHERE
+                l1_batch_number = $1
+                AND chunk_index = $2
+            "#,
+            i64::from(number.0),
+            chunk_index as i32,
+        )
+        .instrument("remove_data_availability_chunk")
+        .with_arg("number", &number)
+        .with_arg("chunk_index", &chunk_index)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Records the outcome of dispatching the blob for the given L1 batch to one of the backends
+    /// of a redundant DA client. Exactly one of `dispatch_request_id` and `error` is expected to be set;
+    /// the previously recorded outcome (if any) is overwritten.
+    pub async fn save_backend_dispatch(
+        &mut self,
+        number: L1BatchNumber,
+        backend: &str,
+        dispatch_request_id: Option<&str>,
+        error: Option<&str>,
+        sent_at: chrono::NaiveDateTime,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            data_availability_backends (
+                l1_batch_number,
+                backend,
+                dispatch_request_id,
+                error,
+                sent_at,
+                created_at,
+                updated_at
+            )
+            VALUES
+            ($1, $2, $3, $4, $5, NOW(), NOW())
+            ON CONFLICT (l1_batch_number, backend) DO
+            UPDATE
+            SET
+            dispatch_request_id = excluded.dispatch_request_id,
+            error = excluded.error,
+            sent_at = excluded.sent_at,
+            updated_at = NOW()
+            "#,
+            i64::from(number.0),
+            backend,
+            dispatch_request_id,
+            error,
+            sent_at,
+        )
+        .instrument("save_backend_dispatch")
+        .with_arg("number", &number)
+        .with_arg("backend", &backend)
+        .with_arg("dispatch_request_id", &dispatch_request_id)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes the blob for the given L1 batch and backend of a redundant DA client, so that it's dispatched
+    /// to the backend again.
+    pub async fn remove_backend_blob(
+        &mut self,
+        number: L1BatchNumber,
+        backend: &str,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM data_availability_backends
+            WHERE
+                l1_batch_number = $1
+                AND backend = $2
+            "#,
+            i64::from(number.0),
+            backend,
+        )
+        .instrument("remove_backend_blob")
+        .with_arg("number", &number)
+        .with_arg("backend", &backend)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Sets the blob ID for the given L1 batch and backend of a redundant DA client.
+    pub async fn set_backend_blob_id(
+        &mut self,
+        number: L1BatchNumber,
+        backend: &str,
+        blob_id: &str,
+    ) -> DalResult<()> {
+        let update_result = sqlx::query!(
+            r#"
+            UPDATE data_availability_backends
+            SET
+                blob_id = $1,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $2
+                AND backend = $3
+            "#,
+            blob_id,
+            i64::from(number.0),
+            backend,
+        )
+        .instrument("set_backend_blob_id")
+        .with_arg("number", &number)
+        .with_arg("backend", &backend)
+        .with_arg("blob_id", &blob_id)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+
+        if update_result.rows_affected() == 0 {
+            tracing::error!(
+                "L1 batch #{number}: blob_id for DA backend `{backend}` wasn't updated"
+            );
+        }
+        Ok(())
+    }
+
+    /// Saves the inclusion data for the given L1 batch and backend of a redundant DA client.
+    pub async fn save_backend_inclusion_data(
+        &mut self,
+        number: L1BatchNumber,
+        backend: &str,
+        inclusion_data: &[u8],
+    ) -> DalResult<()> {
+        let update_result = sqlx::query!(
+            r#"
+            UPDATE data_availability_backends
+            SET
+                inclusion_data = $1,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $2
+                AND backend = $3
+            "#,
+            inclusion_data,
+            i64::from(number.0),
+            backend,
+        )
+        .instrument("save_backend_inclusion_data")
+        .with_arg("number", &number)
+        .with_arg("backend", &backend)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+
+        if update_result.rows_affected() == 0 {
+            tracing::error!(
+                "L1 batch #{number}: inclusion data for DA backend `{backend}` wasn't updated"
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the state of the blobs dispatched to the backends of a redundant DA client for the given L1 batch.
+    pub async fn get_backend_blobs(
+        &mut self,
+        number: L1BatchNumber,
+    ) -> DalResult<Vec<DataAvailabilityBackendBlob>> {
+        let rows = sqlx::query_as!(
+            StorageDABackendBlob,
+            r#"
+            SELECT
+                l1_batch_number,
+                backend,
+                dispatch_request_id,
+                blob_id,
+                inclusion_data,
+                error,
+                sent_at
+            FROM
+                data_availability_backends
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                backend
+            "#,
+            i64::from(number.0),
+        )
+        .instrument("get_backend_blobs")
+        .with_arg("number", &number)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
     /// Assumes that the L1 batches are sorted by number, and returns the first one that is ready for DA dispatch.
     pub async fn get_first_da_blob_awaiting_inclusion(
         &mut self,
@@ -348,6 +709,38 @@ impl DataAvailabilityDal<'_, '_> {
             .collect())
     }
 
+    /// Returns the backlog of sealed L1 batches that are not committed yet and await inclusion in the DA layer
+    /// (i.e., either weren't dispatched yet, or were dispatched, but don't have inclusion data).
+    pub async fn get_da_backlog(&mut self) -> DalResult<DataAvailabilityBacklog> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "pending_l1_batches!",
+                MIN(l1_batches.sealed_at) AS "oldest_pending_sealed_at"
+            FROM
+                l1_batches
+            LEFT JOIN
+                data_availability
+                ON data_availability.l1_batch_number = l1_batches.number
+            WHERE
+                l1_batches.eth_commit_tx_id IS NULL
+                AND l1_batches.number != 0
+                AND l1_batches.pubdata_input IS NOT NULL
+                AND l1_batches.sealed_at IS NOT NULL
+                AND data_availability.inclusion_data IS NULL
+            "#
+        )
+        .instrument("get_da_backlog")
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(DataAvailabilityBacklog {
+            pending_l1_batches: row.pending_l1_batches as u32,
+            oldest_pending_sealed_at: row.oldest_pending_sealed_at.map(|time| time.and_utc()),
+        })
+    }
+
     pub async fn get_da_details_by_batch_number(
         &mut self,
         number: L1BatchNumber,
@@ -476,3 +869,83 @@ impl DataAvailabilityDal<'_, '_> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::ProtocolVersion;
+
+    use super::*;
+    use crate::{tests::create_l1_batch_header, ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn storing_da_chunks() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch_header(1))
+            .await
+            .unwrap();
+
+        let number = L1BatchNumber(1);
+        let mut dal = conn.data_availability_dal();
+        assert!(dal.get_l1_batch_da_chunks(number).await.unwrap().is_empty());
+
+        // Chunks may be dispatched out of order (e.g., if a chunk is re-dispatched); they must be returned in order.
+        let sent_at = chrono::Utc::now().naive_utc();
+        for chunk_index in [2, 0, 1] {
+            dal.insert_l1_batch_da_chunk_request_id(
+                number,
+                chunk_index,
+                &format!("request{chunk_index}"),
+                sent_at,
+            )
+            .await
+            .unwrap();
+        }
+        // Repeated insertion must not overwrite the chunk.
+        dal.insert_l1_batch_da_chunk_request_id(number, 1, "other", sent_at)
+            .await
+            .unwrap();
+
+        let chunks = dal.get_l1_batch_da_chunks(number).await.unwrap();
+        let chunk_indices: Vec<_> = chunks.iter().map(|chunk| chunk.chunk_index).collect();
+        assert_eq!(chunk_indices, [0, 1, 2]);
+        for chunk in &chunks {
+            assert_eq!(chunk.l1_batch_number, number);
+            assert_eq!(
+                chunk.dispatch_request_id,
+                format!("request{}", chunk.chunk_index)
+            );
+            assert_eq!(chunk.blob_id, None);
+            assert_eq!(chunk.inclusion_data, None);
+        }
+
+        dal.set_chunk_blob_id(number, 1, "blob1").await.unwrap();
+        dal.save_chunk_inclusion_data(number, 1, b"inclusion1")
+            .await
+            .unwrap();
+        let chunks = dal.get_l1_batch_da_chunks(number).await.unwrap();
+        assert_eq!(chunks[1].blob_id.as_deref(), Some("blob1"));
+        assert_eq!(
+            chunks[1].inclusion_data.as_deref(),
+            Some(&b"inclusion1"[..])
+        );
+        assert_eq!(chunks[0].blob_id, None);
+        assert_eq!(chunks[2].blob_id, None);
+
+        dal.remove_data_availability_chunk(number, 1).await.unwrap();
+        let chunks = dal.get_l1_batch_da_chunks(number).await.unwrap();
+        let chunk_indices: Vec<_> = chunks.iter().map(|chunk| chunk.chunk_index).collect();
+        assert_eq!(chunk_indices, [0, 2]);
+        // Chunks for other batches are not affected.
+        assert!(dal
+            .get_l1_batch_da_chunks(L1BatchNumber(2))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use std::{convert::TryFrom, num::NonZeroU64, str::FromStr};
+use std::{collections::HashMap, convert::TryFrom, num::NonZeroU64, ops, str::FromStr};
 
 use anyhow::Context as _;
 use zksync_db_connection::{
@@ -1132,6 +1132,144 @@ impl EthSenderDal<'_, '_> {
         Ok(row.map(|a| a.nonce as u64 + 1))
     }
 
+    /// Returns the sender and nonce of the latest unconfirmed transaction of the specified type, if any.
+    /// A new operation of the same type must be included after this transaction.
+    pub async fn get_latest_unconfirmed_tx_sender(
+        &mut self,
+        tx_type: AggregatedActionType,
+        is_gateway: bool,
+    ) -> sqlx::Result<Option<(Address, u64)>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                from_addr AS "from_addr!",
+                nonce
+            FROM
+                eth_txs
+            WHERE
+                tx_type = $1
+                AND is_gateway = $2
+                AND confirmed_eth_tx_history_id IS NULL
+                AND from_addr IS NOT NULL
+            ORDER BY
+                id DESC
+            LIMIT
+                1
+            "#,
+            tx_type.to_string(),
+            is_gateway,
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+
+        Ok(row.map(|row| (Address::from_slice(&row.from_addr), row.nonce as u64)))
+    }
+
+    /// Returns senders and nonces of unconfirmed transactions of the specified type (commit or prove)
+    /// for L1 batches in the specified range.
+    pub async fn get_unconfirmed_tx_senders_for_l1_batches(
+        &mut self,
+        l1_batches: ops::RangeInclusive<L1BatchNumber>,
+        tx_type: AggregatedActionType,
+    ) -> sqlx::Result<Vec<(Address, u64)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT
+                eth_txs.from_addr AS "from_addr!",
+                eth_txs.nonce
+            FROM
+                l1_batches
+            JOIN eth_txs ON eth_txs.id IN (l1_batches.eth_commit_tx_id, l1_batches.eth_prove_tx_id)
+            WHERE
+                l1_batches.number BETWEEN $1 AND $2
+                AND eth_txs.tx_type = $3
+                AND eth_txs.confirmed_eth_tx_history_id IS NULL
+                AND eth_txs.from_addr IS NOT NULL
+            "#,
+            i64::from(l1_batches.start().0),
+            i64::from(l1_batches.end().0),
+            tx_type.to_string(),
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (Address::from_slice(&row.from_addr), row.nonce as u64))
+            .collect())
+    }
+
+    /// Returns the number of unconfirmed transactions and the greatest nonce among them for each operator account.
+    pub async fn get_unconfirmed_txs_by_sender(
+        &mut self,
+        is_gateway: bool,
+    ) -> sqlx::Result<HashMap<Address, (usize, u64)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                from_addr AS "from_addr!",
+                COUNT(*) AS "count!",
+                MAX(nonce) AS "max_nonce!"
+            FROM
+                eth_txs
+            WHERE
+                is_gateway = $1
+                AND confirmed_eth_tx_history_id IS NULL
+                AND from_addr IS NOT NULL
+            GROUP BY
+                from_addr
+            "#,
+            is_gateway,
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let address = Address::from_slice(&row.from_addr);
+                (address, (row.count as usize, row.max_nonce as u64))
+            })
+            .collect())
+    }
+
+    /// Returns all operator accounts that have unconfirmed or not yet finalized transactions.
+    pub async fn get_unfinalized_tx_senders(
+        &mut self,
+        is_gateway: bool,
+    ) -> sqlx::Result<Vec<Address>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                from_addr AS "from_addr!"
+            FROM
+                eth_txs
+            WHERE
+                is_gateway = $1
+                AND from_addr IS NOT NULL
+                AND confirmed_eth_tx_history_id IS NULL
+            UNION
+            SELECT
+                eth_txs.from_addr
+            FROM
+                eth_txs
+            JOIN eth_txs_history ON eth_txs.confirmed_eth_tx_history_id = eth_txs_history.id
+            WHERE
+                eth_txs.is_gateway = $1
+                AND eth_txs.from_addr IS NOT NULL
+                AND eth_txs_history.finality_status != 'finalized'
+            "#,
+            is_gateway,
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Address::from_slice(&row.from_addr))
+            .collect())
+    }
+
     pub async fn mark_failed_transaction(&mut self, eth_tx_id: u32) -> sqlx::Result<()> {
         sqlx::query!(
             r#"
@@ -194,6 +194,32 @@ impl FactoryDepsDal<'_, '_> {
         .collect())
     }
 
+    /// Returns factory deps inserted for the specified L2 block as a map of `(bytecode_hash, bytecode)` entries.
+    pub async fn get_factory_deps_for_l2_block(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<HashMap<H256, Vec<u8>>> {
+        Ok(sqlx::query!(
+            r#"
+            SELECT
+                bytecode_hash,
+                bytecode
+            FROM
+                factory_deps
+            WHERE
+                miniblock_number = $1
+            "#,
+            i64::from(l2_block_number.0)
+        )
+        .instrument("get_factory_deps_for_l2_block")
+        .with_arg("l2_block_number", &l2_block_number)
+        .fetch_all(self.storage)
+        .await?
+        .into_iter()
+        .map(|row| (H256::from_slice(&row.bytecode_hash), row.bytecode))
+        .collect())
+    }
+
     /// Removes all factory deps with a miniblock number strictly greater than the specified `block_number`.
     pub async fn roll_back_factory_deps(&mut self, block_number: L2BlockNumber) -> DalResult<()> {
         sqlx::query!(
@@ -23,11 +23,12 @@ use crate::{
     interop_roots_dal::InteropRootDal, proof_generation_dal::ProofGenerationDal,
     protocol_versions_dal::ProtocolVersionsDal,
     protocol_versions_web3_dal::ProtocolVersionsWeb3Dal, pruning_dal::PruningDal,
-    server_notifications::ServerNotificationsDal, snapshot_recovery_dal::SnapshotRecoveryDal,
-    snapshots_creator_dal::SnapshotsCreatorDal, snapshots_dal::SnapshotsDal,
-    storage_logs_dal::StorageLogsDal, storage_logs_dedup_dal::StorageLogsDedupDal,
-    storage_web3_dal::StorageWeb3Dal, sync_dal::SyncDal, system_dal::SystemDal,
-    tokens_dal::TokensDal, tokens_web3_dal::TokensWeb3Dal, transactions_dal::TransactionsDal,
+    reorg_reverts_dal::ReorgRevertsDal, server_notifications::ServerNotificationsDal,
+    snapshot_recovery_dal::SnapshotRecoveryDal, snapshots_creator_dal::SnapshotsCreatorDal,
+    snapshots_dal::SnapshotsDal, storage_logs_dal::StorageLogsDal,
+    storage_logs_dedup_dal::StorageLogsDedupDal, storage_web3_dal::StorageWeb3Dal,
+    sync_dal::SyncDal, system_dal::SystemDal, tokens_dal::TokensDal,
+    tokens_web3_dal::TokensWeb3Dal, transactions_dal::TransactionsDal,
     transactions_web3_dal::TransactionsWeb3Dal, vm_runner_dal::VmRunnerDal,
 };
 
@@ -57,6 +58,7 @@ pub mod proof_generation_dal;
 pub mod protocol_versions_dal;
 pub mod protocol_versions_web3_dal;
 pub mod pruning_dal;
+pub mod reorg_reverts_dal;
 mod server_notifications;
 pub mod snapshot_recovery_dal;
 pub mod snapshots_creator_dal;
@@ -157,6 +159,8 @@ where
     fn eth_proof_manager_dal(&mut self) -> EthProofManagerDal<'_, 'a>;
 
     fn external_node_config_dal(&mut self) -> ExternalNodeConfigDal<'_, 'a>;
+
+    fn reorg_reverts_dal(&mut self) -> ReorgRevertsDal<'_, 'a>;
 }
 
 #[derive(Clone, Debug)]
@@ -304,6 +308,10 @@ impl<'a> CoreDal<'a> for Connection<'a, Core> {
         ExternalNodeConfigDal { storage: self }
     }
 
+    fn reorg_reverts_dal(&mut self) -> ReorgRevertsDal<'_, 'a> {
+        ReorgRevertsDal { storage: self }
+    }
+
     fn eth_proof_manager_dal(&mut self) -> EthProofManagerDal<'_, 'a> {
         EthProofManagerDal { storage: self }
     }
@@ -1,7 +1,10 @@
 use chrono::{DateTime, NaiveDateTime, Utc};
 use zksync_types::{
     l2_to_l1_log::L2ToL1Log,
-    pubdata_da::{DataAvailabilityBlob, DataAvailabilityDetails},
+    pubdata_da::{
+        DataAvailabilityBackendBlob, DataAvailabilityBlob, DataAvailabilityChunk,
+        DataAvailabilityDetails,
+    },
     Address, L1BatchNumber,
 };
 
@@ -27,6 +30,56 @@ impl From<StorageDABlob> for DataAvailabilityBlob {
     }
 }
 
+/// Represents a chunk of a split blob in the data availability layer.
+#[derive(Debug, Clone)]
+pub(crate) struct StorageDAChunk {
+    pub l1_batch_number: i64,
+    pub chunk_index: i32,
+    pub dispatch_request_id: String,
+    pub blob_id: Option<String>,
+    pub inclusion_data: Option<Vec<u8>>,
+    pub sent_at: NaiveDateTime,
+}
+
+impl From<StorageDAChunk> for DataAvailabilityChunk {
+    fn from(chunk: StorageDAChunk) -> DataAvailabilityChunk {
+        DataAvailabilityChunk {
+            l1_batch_number: L1BatchNumber(chunk.l1_batch_number as u32),
+            chunk_index: chunk.chunk_index as usize,
+            dispatch_request_id: chunk.dispatch_request_id,
+            blob_id: chunk.blob_id,
+            inclusion_data: chunk.inclusion_data,
+            sent_at: chunk.sent_at.and_utc(),
+        }
+    }
+}
+
+/// Represents a blob dispatched to one of the backends of a redundant DA client.
+#[derive(Debug, Clone)]
+pub(crate) struct StorageDABackendBlob {
+    pub l1_batch_number: i64,
+    pub backend: String,
+    pub dispatch_request_id: Option<String>,
+    pub blob_id: Option<String>,
+    pub inclusion_data: Option<Vec<u8>>,
+    pub error: Option<String>,
+    pub sent_at: Option<NaiveDateTime>,
+}
+
+impl From<StorageDABackendBlob> for DataAvailabilityBackendBlob {
+    fn from(blob: StorageDABackendBlob) -> DataAvailabilityBackendBlob {
+        DataAvailabilityBackendBlob {
+            l1_batch_number: L1BatchNumber(blob.l1_batch_number as u32),
+            backend: blob.backend,
+            dispatch_request_id: blob.dispatch_request_id,
+            blob_id: blob.blob_id,
+            inclusion_data: blob.inclusion_data,
+            error: blob.error,
+            sent_at: blob.sent_at.map(|sent_at| sent_at.and_utc()),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StorageDADetails {
     pub blob_id: String,
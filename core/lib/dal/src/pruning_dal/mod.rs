@@ -1,9 +1,20 @@
-use std::ops;
+use std::{fmt, num::NonZeroU32, ops};
 
-use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
-use zksync_types::{L1BatchNumber, L2BlockNumber, H256};
+use zksync_db_connection::{
+    connection::Connection,
+    error::DalResult,
+    instrument::{InstrumentExt, Instrumented},
+};
+use zksync_types::{
+    fee_model::BatchFeeInput, settlement::SettlementLayer, AccountTreeId, L1BatchNumber,
+    L2BlockNumber, ProtocolVersionId, StorageKey, H256, SYSTEM_CONTEXT_ADDRESS,
+    SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION, U256,
+};
 
-use crate::Core;
+use crate::{
+    models::{bigdecimal_to_u256, parse_protocol_version, storage_block::to_settlement_layer},
+    Core,
+};
 
 #[cfg(test)]
 mod tests;
@@ -40,6 +51,104 @@ impl PruningInfo {
     }
 }
 
+/// Archive checkpoint recorded by Postgres pruning, i.e. the last L2 block of an L1 batch for which the storage state is retained
+/// after the batch is pruned.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArchiveCheckpoint {
+    pub l1_batch: L1BatchNumber,
+    pub l2_block: L2BlockNumber,
+    /// Block metadata necessary to execute calls on top of the checkpoint.
+    pub metadata: ArchiveCheckpointMetadata,
+}
+
+/// Metadata of an [`ArchiveCheckpoint`] copied from the `miniblocks` and `l1_batches` tables before they are pruned.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArchiveCheckpointMetadata {
+    pub l2_block_hash: H256,
+    pub protocol_version: Option<ProtocolVersionId>,
+    pub batch_fee_input: BatchFeeInput,
+    /// Timestamp of the checkpoint L1 batch (i.e., of its first L2 block).
+    pub l1_batch_timestamp: u64,
+    pub settlement_layer: SettlementLayer,
+    pub interop_fee: U256,
+    /// Information about the L2 block preceding the checkpoint. `None` if the checkpoint is the genesis block,
+    /// or if the preceding block was pruned before the checkpoint was recorded.
+    pub prev_l2_block: Option<ArchivedL2Block>,
+}
+
+/// Information about the L2 block preceding an [`ArchiveCheckpoint`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArchivedL2Block {
+    pub hash: H256,
+    pub timestamp: u64,
+    /// Rolling hash of transactions stored in the system context after the block.
+    pub txs_rolling_hash: H256,
+}
+
+/// Category of data that can be pruned ahead of the main Postgres pruning (i.e., with a shorter retention period).
+/// Pruning a category is tracked separately from the main pruning; see [`PruningDal::get_category_pruning_info()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrunedDataCategory {
+    /// Call traces returned by `debug_trace*` RPC methods.
+    CallTraces,
+    /// Storage logs overwritten by newer logs. Pruning these logs makes historical storage reads (`eth_getBalance`,
+    /// `eth_call` etc.) impossible for the pruned L2 blocks, except for archive checkpoints.
+    StorageLogs,
+}
+
+impl PrunedDataCategory {
+    pub const ALL: [Self; 2] = [Self::CallTraces, Self::StorageLogs];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CallTraces => "call_traces",
+            Self::StorageLogs => "storage_logs",
+        }
+    }
+}
+
+impl fmt::Display for PrunedDataCategory {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
+/// Pruning information for all [`PrunedDataCategory`]s. Note that the main pruning implicitly prunes all categories;
+/// this isn't reflected in this info.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CategoryPruningInfo {
+    pub call_traces: PruningInfo,
+    pub storage_logs: PruningInfo,
+}
+
+impl CategoryPruningInfo {
+    pub fn get(&self, category: PrunedDataCategory) -> &PruningInfo {
+        match category {
+            PrunedDataCategory::CallTraces => &self.call_traces,
+            PrunedDataCategory::StorageLogs => &self.storage_logs,
+        }
+    }
+
+    fn get_mut(&mut self, category: PrunedDataCategory) -> &mut PruningInfo {
+        match category {
+            PrunedDataCategory::CallTraces => &mut self.call_traces,
+            PrunedDataCategory::StorageLogs => &mut self.storage_logs,
+        }
+    }
+}
+
+/// Returns the part of `l2_blocks` that is not hard-pruned according to the `category_info`.
+fn retain_unpruned(
+    l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+    category_info: &PruningInfo,
+) -> Option<ops::RangeInclusive<L2BlockNumber>> {
+    let start = match category_info.last_hard_pruned {
+        Some(info) => (*l2_blocks.start()).max(info.l2_block + 1),
+        None => *l2_blocks.start(),
+    };
+    (start <= *l2_blocks.end()).then(|| start..=*l2_blocks.end())
+}
+
 /// Statistics about a single hard pruning iteration.
 #[derive(Debug, Default)]
 pub struct HardPruningStats {
@@ -194,44 +303,57 @@ impl PruningDal<'_, '_> {
         last_l1_batch_to_prune: L1BatchNumber,
         last_l2_block_to_prune: L2BlockNumber,
     ) -> DalResult<HardPruningStats> {
-        let row = sqlx::query!(
-            r#"
-            SELECT
-                MIN(number) AS first_miniblock_to_prune
-            FROM
-                miniblocks
-            WHERE
-                l1_batch_number <= $1
-            "#,
-            i64::from(last_l1_batch_to_prune.0),
+        self.hard_prune_batches_range_with_archive(
+            last_l1_batch_to_prune,
+            last_l2_block_to_prune,
+            None,
         )
-        .instrument("hard_prune_batches_range#get_miniblocks_range")
-        .with_arg("last_l1_batch_to_prune", &last_l1_batch_to_prune)
-        .report_latency()
-        .fetch_one(self.storage)
-        .await?;
+        .await
+    }
 
-        let Some(first_l2_block_to_prune) = row.first_miniblock_to_prune else {
+    /// Same as [`Self::hard_prune_batches_range()`], but additionally records archive checkpoints for pruned L1 batches
+    /// with numbers divisible by `archive_interval`. Storage logs necessary to read the storage state as of the last L2 block
+    /// of a checkpoint L1 batch are retained (this is true for previously recorded checkpoints even if `archive_interval`
+    /// is not specified).
+    ///
+    /// Does not insert pruning logs; the caller is responsible to do this!
+    pub async fn hard_prune_batches_range_with_archive(
+        &mut self,
+        last_l1_batch_to_prune: L1BatchNumber,
+        last_l2_block_to_prune: L2BlockNumber,
+        archive_interval: Option<NonZeroU32>,
+    ) -> DalResult<HardPruningStats> {
+        let Some(first_l2_block_to_prune) = self
+            .get_first_l2_block_to_prune(last_l1_batch_to_prune)
+            .await?
+        else {
             return Ok(HardPruningStats::default());
         };
+        let l2_blocks_to_prune = first_l2_block_to_prune..=last_l2_block_to_prune;
+        // Data categories may be pruned ahead of the main pruning; we don't need to prune them again.
+        let category_info = self.get_category_pruning_info().await?;
 
-        let first_l2_block_to_prune = L2BlockNumber(first_l2_block_to_prune as u32);
-
-        let deleted_events = self
-            .delete_events(first_l2_block_to_prune..=last_l2_block_to_prune)
-            .await?;
+        let deleted_events = self.delete_events(l2_blocks_to_prune.clone()).await?;
         let deleted_l2_to_l1_logs = self
-            .delete_l2_to_l1_logs(first_l2_block_to_prune..=last_l2_block_to_prune)
+            .delete_l2_to_l1_logs(l2_blocks_to_prune.clone())
             .await?;
-        let deleted_call_traces = self
-            .delete_call_traces(first_l2_block_to_prune..=last_l2_block_to_prune)
-            .await?;
-        self.clear_transaction_fields(first_l2_block_to_prune..=last_l2_block_to_prune)
+        let deleted_call_traces =
+            match retain_unpruned(l2_blocks_to_prune.clone(), &category_info.call_traces) {
+                Some(l2_blocks) => self.delete_call_traces(l2_blocks).await?,
+                None => 0,
+            };
+        self.clear_transaction_fields(l2_blocks_to_prune.clone())
             .await?;
 
-        let deleted_storage_logs = self
-            .prune_storage_logs(first_l2_block_to_prune..=last_l2_block_to_prune)
-            .await?;
+        if let Some(archive_interval) = archive_interval {
+            self.insert_archive_checkpoints(last_l1_batch_to_prune, archive_interval)
+                .await?;
+        }
+        let deleted_storage_logs =
+            match retain_unpruned(l2_blocks_to_prune, &category_info.storage_logs) {
+                Some(l2_blocks) => self.prune_storage_logs_with_archive(l2_blocks).await?,
+                None => 0,
+            };
         let deleted_l1_batches = self.delete_l1_batches(last_l1_batch_to_prune).await?;
         let deleted_l2_blocks = self.delete_l2_blocks(last_l2_block_to_prune).await?;
 
@@ -246,6 +368,32 @@ impl PruningDal<'_, '_> {
         Ok(stats)
     }
 
+    async fn get_first_l2_block_to_prune(
+        &mut self,
+        last_l1_batch_to_prune: L1BatchNumber,
+    ) -> DalResult<Option<L2BlockNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MIN(number) AS first_miniblock_to_prune
+            FROM
+                miniblocks
+            WHERE
+                l1_batch_number <= $1
+            "#,
+            i64::from(last_l1_batch_to_prune.0),
+        )
+        .instrument("hard_prune_batches_range#get_miniblocks_range")
+        .with_arg("last_l1_batch_to_prune", &last_l1_batch_to_prune)
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(row
+            .first_miniblock_to_prune
+            .map(|number| L2BlockNumber(number as u32)))
+    }
+
     async fn delete_events(
         &mut self,
         l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
@@ -354,10 +502,264 @@ impl PruningDal<'_, '_> {
         Ok(execution_result.rows_affected())
     }
 
-    /// Removes storage logs overwritten by the specified new logs.
+    /// Records archive checkpoints for all non-pruned L1 batches up to `last_l1_batch_to_prune` with numbers divisible
+    /// by `archive_interval`. Together with a checkpoint, the block metadata necessary to execute calls on top of it is copied
+    /// since the `miniblocks` and `l1_batches` rows are removed by pruning.
+    async fn insert_archive_checkpoints(
+        &mut self,
+        last_l1_batch_to_prune: L1BatchNumber,
+        archive_interval: NonZeroU32,
+    ) -> DalResult<u64> {
+        let txs_rolling_hash_key = StorageKey::new(
+            AccountTreeId::new(SYSTEM_CONTEXT_ADDRESS),
+            SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION,
+        )
+        .hashed_key();
+
+        let execution_result = sqlx::query!(
+            r#"
+            INSERT INTO
+            pruning_archive_checkpoints (
+                l1_batch_number,
+                miniblock_number,
+                miniblock_hash,
+                protocol_version,
+                l1_gas_price,
+                l2_fair_gas_price,
+                fair_pubdata_price,
+                l1_batch_timestamp,
+                settlement_layer_type,
+                settlement_layer_chain_id,
+                interop_fee,
+                prev_miniblock_hash,
+                prev_miniblock_timestamp,
+                prev_txs_rolling_hash,
+                created_at
+            )
+            SELECT
+                checkpoints.l1_batch_number,
+                miniblocks.number,
+                miniblocks.hash,
+                miniblocks.protocol_version,
+                miniblocks.l1_gas_price,
+                miniblocks.l2_fair_gas_price,
+                miniblocks.fair_pubdata_price,
+                checkpoints.l1_batch_timestamp,
+                l1_batches.settlement_layer_type,
+                l1_batches.settlement_layer_chain_id,
+                l1_batches.interop_fee,
+                prev_miniblocks.hash,
+                prev_miniblocks.timestamp,
+                (
+                    SELECT
+                        value
+                    FROM
+                        storage_logs
+                    WHERE
+                        hashed_key = $3
+                        AND miniblock_number < miniblocks.number
+                    ORDER BY
+                        miniblock_number DESC,
+                        operation_number DESC
+                    LIMIT
+                        1
+                ),
+                NOW()
+            FROM
+                (
+                    SELECT
+                        l1_batch_number,
+                        MAX(number) AS miniblock_number,
+                        MIN(timestamp) AS l1_batch_timestamp
+                    FROM
+                        miniblocks
+                    WHERE
+                        l1_batch_number <= $1
+                        AND l1_batch_number % $2 = 0
+                    GROUP BY
+                        l1_batch_number
+                ) AS checkpoints
+            JOIN miniblocks ON miniblocks.number = checkpoints.miniblock_number
+            JOIN l1_batches ON l1_batches.number = checkpoints.l1_batch_number
+            LEFT JOIN miniblocks AS prev_miniblocks
+                ON prev_miniblocks.number = checkpoints.miniblock_number - 1
+            ON CONFLICT (l1_batch_number) DO NOTHING
+            "#,
+            i64::from(last_l1_batch_to_prune.0),
+            i64::from(archive_interval.get()),
+            txs_rolling_hash_key.as_bytes()
+        )
+        .instrument("hard_prune_batches_range#insert_archive_checkpoints")
+        .with_arg("last_l1_batch_to_prune", &last_l1_batch_to_prune)
+        .with_arg("archive_interval", &archive_interval)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
+    /// Returns the archive checkpoint for the specified L2 block, i.e., information about the L1 batch for which the storage state
+    /// as of this block is retained after the L1 batch is pruned. Returns `None` if the L2 block is not an archive checkpoint.
+    pub async fn get_archive_checkpoint(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<Option<ArchiveCheckpoint>> {
+        let instrumentation = Instrumented::new("get_archive_checkpoint")
+            .with_arg("l2_block_number", &l2_block_number);
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                miniblock_hash,
+                protocol_version,
+                l1_gas_price,
+                l2_fair_gas_price,
+                fair_pubdata_price,
+                l1_batch_timestamp,
+                settlement_layer_type,
+                settlement_layer_chain_id,
+                interop_fee,
+                prev_miniblock_hash,
+                prev_miniblock_timestamp,
+                prev_txs_rolling_hash
+            FROM
+                pruning_archive_checkpoints
+            WHERE
+                miniblock_number = $1
+            "#,
+            i64::from(l2_block_number.0)
+        )
+        .instrument("get_archive_checkpoint")
+        .with_arg("l2_block_number", &l2_block_number)
+        .fetch_optional(self.storage)
+        .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let protocol_version = row
+            .protocol_version
+            .map(parse_protocol_version)
+            .transpose()
+            .map_err(|err| instrumentation.constraint_error(err.into()))?;
+        let settlement_layer =
+            to_settlement_layer(row.settlement_layer_type, row.settlement_layer_chain_id)
+                .map_err(|err| instrumentation.constraint_error(err))?;
+        let prev_l2_block = match (row.prev_miniblock_hash, row.prev_miniblock_timestamp) {
+            (Some(hash), Some(timestamp)) => Some(ArchivedL2Block {
+                hash: H256::from_slice(&hash),
+                timestamp: timestamp as u64,
+                txs_rolling_hash: row
+                    .prev_txs_rolling_hash
+                    .map_or_else(H256::zero, |hash| H256::from_slice(&hash)),
+            }),
+            _ => None,
+        };
+        let metadata = ArchiveCheckpointMetadata {
+            l2_block_hash: H256::from_slice(&row.miniblock_hash),
+            protocol_version,
+            batch_fee_input: BatchFeeInput::from_protocol_version(
+                protocol_version,
+                row.l1_gas_price as u64,
+                row.l2_fair_gas_price as u64,
+                row.fair_pubdata_price.map(|price| price as u64),
+            ),
+            l1_batch_timestamp: row.l1_batch_timestamp as u64,
+            settlement_layer,
+            interop_fee: bigdecimal_to_u256(row.interop_fee),
+            prev_l2_block,
+        };
+
+        Ok(Some(ArchiveCheckpoint {
+            l1_batch: L1BatchNumber(row.l1_batch_number as u32),
+            l2_block: l2_block_number,
+            metadata,
+        }))
+    }
+
+    /// Returns L2 blocks of archive checkpoints in the specified range, together with the latest archive checkpoint
+    /// preceding the range.
+    async fn get_archive_checkpoints(
+        &mut self,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<(Option<L2BlockNumber>, Vec<L2BlockNumber>)> {
+        let preceding_row = sqlx::query!(
+            r#"
+            SELECT
+                MAX(miniblock_number) AS "miniblock_number"
+            FROM
+                pruning_archive_checkpoints
+            WHERE
+                miniblock_number < $1
+            "#,
+            i64::from(l2_blocks.start().0)
+        )
+        .instrument("hard_prune_batches_range#get_preceding_archive_checkpoint")
+        .with_arg("l2_blocks", &l2_blocks)
+        .fetch_one(self.storage)
+        .await?;
+        let preceding_checkpoint = preceding_row
+            .miniblock_number
+            .map(|number| L2BlockNumber(number as u32));
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                miniblock_number
+            FROM
+                pruning_archive_checkpoints
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            ORDER BY
+                miniblock_number
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0)
+        )
+        .instrument("hard_prune_batches_range#get_archive_checkpoints")
+        .with_arg("l2_blocks", &l2_blocks)
+        .fetch_all(self.storage)
+        .await?;
+        let checkpoints = rows
+            .into_iter()
+            .map(|row| L2BlockNumber(row.miniblock_number as u32))
+            .collect();
+        Ok((preceding_checkpoint, checkpoints))
+    }
+
+    /// Prunes storage logs in the specified range, splitting it at archive checkpoints so that logs necessary to read
+    /// the storage state at each checkpoint are retained.
+    async fn prune_storage_logs_with_archive(
+        &mut self,
+        l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<u64> {
+        let (mut retained_up_to, checkpoints) = self
+            .get_archive_checkpoints(l2_blocks_to_prune.clone())
+            .await?;
+
+        let mut deleted_storage_logs = 0;
+        let mut segment_start = *l2_blocks_to_prune.start();
+        let segment_ends = checkpoints.into_iter().chain([*l2_blocks_to_prune.end()]);
+        for segment_end in segment_ends {
+            if segment_start > segment_end {
+                // Can happen if the range ends with a checkpoint.
+                break;
+            }
+            deleted_storage_logs += self
+                .prune_storage_logs(segment_start..=segment_end, retained_up_to)
+                .await?;
+            retained_up_to = Some(segment_end);
+            segment_start = segment_end + 1;
+        }
+        Ok(deleted_storage_logs)
+    }
+
+    /// Removes storage logs overwritten by the specified new logs. Logs up to and including the `retained_up_to` L2 block
+    /// (the latest preceding archive checkpoint) are not removed.
     async fn prune_storage_logs(
         &mut self,
         l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+        retained_up_to: Option<L2BlockNumber>,
     ) -> DalResult<u64> {
         // Storage log pruning is designed to use deterministic indexes and thus have predictable performance.
         //
@@ -389,14 +791,17 @@ impl PruningDal<'_, '_> {
             WHERE
                 storage_logs.hashed_key = new_logs.hashed_key
                 AND storage_logs.miniblock_number <= $2
+                AND storage_logs.miniblock_number > $3
                 AND (storage_logs.miniblock_number, storage_logs.operation_number)
                 < (new_logs.miniblock_number, new_logs.operation_number)
             "#,
             i64::from(l2_blocks_to_prune.start().0),
-            i64::from(l2_blocks_to_prune.end().0)
+            i64::from(l2_blocks_to_prune.end().0),
+            retained_up_to.map_or(-1, |block| i64::from(block.0))
         )
         .instrument("hard_prune_batches_range#prune_storage_logs")
         .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
+        .with_arg("retained_up_to", &retained_up_to)
         .report_latency()
         .execute(self.storage)
         .await?;
@@ -470,4 +875,154 @@ impl PruningDal<'_, '_> {
         .await?;
         Ok(())
     }
+
+    /// Returns pruning information for all data categories pruned ahead of the main pruning.
+    pub async fn get_category_pruning_info(&mut self) -> DalResult<CategoryPruningInfo> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                category,
+                type = 'Soft' AS "is_soft!",
+                MAX(pruned_l1_batch) AS "pruned_l1_batch!",
+                MAX(pruned_miniblock) AS "pruned_miniblock!"
+            FROM
+                pruning_category_log
+            GROUP BY
+                category,
+                type
+            "#
+        )
+        .instrument("get_category_pruning_info")
+        .report_latency()
+        .fetch_all(self.storage)
+        .await?;
+
+        let mut info = CategoryPruningInfo::default();
+        for row in rows {
+            let Some(category) = PrunedDataCategory::ALL
+                .into_iter()
+                .find(|category| category.as_str() == row.category)
+            else {
+                tracing::warn!("Unknown pruned data category: {}", row.category);
+                continue;
+            };
+            let l1_batch = L1BatchNumber(row.pruned_l1_batch as u32);
+            let l2_block = L2BlockNumber(row.pruned_miniblock as u32);
+            let category_info = info.get_mut(category);
+            if row.is_soft {
+                category_info.last_soft_pruned = Some(SoftPruningInfo { l1_batch, l2_block });
+            } else {
+                category_info.last_hard_pruned = Some(HardPruningInfo {
+                    l1_batch,
+                    l2_block,
+                    l1_batch_root_hash: None,
+                });
+            }
+        }
+        Ok(info)
+    }
+
+    pub async fn insert_category_soft_pruning_log(
+        &mut self,
+        category: PrunedDataCategory,
+        last_l1_batch_to_prune: L1BatchNumber,
+        last_l2_block_to_prune: L2BlockNumber,
+    ) -> DalResult<()> {
+        self.insert_category_pruning_log(
+            category,
+            PruneType::Soft,
+            last_l1_batch_to_prune,
+            last_l2_block_to_prune,
+        )
+        .await
+    }
+
+    pub async fn insert_category_hard_pruning_log(
+        &mut self,
+        category: PrunedDataCategory,
+        last_l1_batch_to_prune: L1BatchNumber,
+        last_l2_block_to_prune: L2BlockNumber,
+    ) -> DalResult<()> {
+        self.insert_category_pruning_log(
+            category,
+            PruneType::Hard,
+            last_l1_batch_to_prune,
+            last_l2_block_to_prune,
+        )
+        .await
+    }
+
+    async fn insert_category_pruning_log(
+        &mut self,
+        category: PrunedDataCategory,
+        prune_type: PruneType,
+        last_l1_batch_to_prune: L1BatchNumber,
+        last_l2_block_to_prune: L2BlockNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            pruning_category_log (
+                category,
+                type,
+                pruned_l1_batch,
+                pruned_miniblock,
+                created_at
+            )
+            VALUES
+            ($1, $2, $3, $4, NOW())
+            "#,
+            category.as_str(),
+            prune_type as PruneType,
+            i64::from(last_l1_batch_to_prune.0),
+            i64::from(last_l2_block_to_prune.0)
+        )
+        .instrument("insert_category_pruning_log")
+        .with_arg("category", &category)
+        .with_arg("last_l1_batch_to_prune", &last_l1_batch_to_prune)
+        .with_arg("last_l2_block_to_prune", &last_l2_block_to_prune)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Prunes data of the specified category up to and including the specified L1 batch / L2 block, ahead of the main pruning.
+    /// Data already pruned by the main pruning or by previous category pruning is skipped. Returns the number of pruned entities.
+    /// For storage logs, archive checkpoints are handled in the same way as in [`Self::hard_prune_batches_range_with_archive()`].
+    ///
+    /// Does not insert pruning logs; the caller is responsible to do this!
+    pub async fn hard_prune_category(
+        &mut self,
+        category: PrunedDataCategory,
+        last_l1_batch_to_prune: L1BatchNumber,
+        last_l2_block_to_prune: L2BlockNumber,
+        archive_interval: Option<NonZeroU32>,
+    ) -> DalResult<u64> {
+        let Some(first_l2_block_to_prune) = self
+            .get_first_l2_block_to_prune(last_l1_batch_to_prune)
+            .await?
+        else {
+            return Ok(0);
+        };
+        let category_info = self.get_category_pruning_info().await?;
+        let Some(l2_blocks_to_prune) = retain_unpruned(
+            first_l2_block_to_prune..=last_l2_block_to_prune,
+            category_info.get(category),
+        ) else {
+            return Ok(0);
+        };
+
+        match category {
+            PrunedDataCategory::CallTraces => self.delete_call_traces(l2_blocks_to_prune).await,
+            PrunedDataCategory::StorageLogs => {
+                if let Some(archive_interval) = archive_interval {
+                    self.insert_archive_checkpoints(last_l1_batch_to_prune, archive_interval)
+                        .await?;
+                }
+                self.prune_storage_logs_with_archive(l2_blocks_to_prune)
+                    .await
+            }
+        }
+    }
 }
@@ -393,6 +393,214 @@ async fn storage_logs_pruning_works_correctly() {
     assert_eq!(stats.deleted_storage_logs, 2);
 }
 
+#[tokio::test]
+async fn storage_logs_are_retained_for_archive_checkpoints() {
+    const ARCHIVE_INTERVAL: NonZeroU32 = NonZeroU32::new(3).unwrap();
+
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    let mut transaction = conn.start_transaction().await.unwrap();
+    // L1 batch #N contains L2 blocks #2N and #(2N + 1), so archive checkpoints are L2 blocks #1, #7, #13 and #19.
+    insert_realistic_l1_batches(&mut transaction, 10).await;
+    let logs = [
+        (2, random_storage_log(1, 1)),
+        (5, random_storage_log(1, 2)),
+        (8, random_storage_log(1, 3)),
+        (9, random_storage_log(2, 4)),
+        (14, random_storage_log(2, 5)),
+        (15, random_storage_log(1, 6)),
+    ];
+    for (l2_block_number, log) in logs {
+        insert_l2_block_storage_logs(&mut transaction, L2BlockNumber(l2_block_number), vec![log])
+            .await;
+    }
+
+    let stats = transaction
+        .pruning_dal()
+        .hard_prune_batches_range_with_archive(
+            L1BatchNumber(4),
+            L2BlockNumber(9),
+            Some(ARCHIVE_INTERVAL),
+        )
+        .await
+        .unwrap();
+    // Only the log at L2 block #2 is removed; the log at L2 block #5 is the latest one for the key at checkpoint #7.
+    assert_eq!(stats.deleted_storage_logs, 1);
+
+    let stats = transaction
+        .pruning_dal()
+        .hard_prune_batches_range_with_archive(
+            L1BatchNumber(9),
+            L2BlockNumber(19),
+            Some(ARCHIVE_INTERVAL),
+        )
+        .await
+        .unwrap();
+    assert_eq!(stats.deleted_storage_logs, 0);
+    let actual_logs = transaction
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(actual_logs.len(), 5);
+
+    for (l2_block_number, expected_l1_batch) in
+        [(1, Some(0)), (7, Some(3)), (8, None), (19, Some(9))]
+    {
+        let checkpoint = transaction
+            .pruning_dal()
+            .get_archive_checkpoint(L2BlockNumber(l2_block_number))
+            .await
+            .unwrap();
+        let l1_batch = checkpoint.map(|checkpoint| checkpoint.l1_batch);
+        assert_eq!(l1_batch, expected_l1_batch.map(L1BatchNumber));
+    }
+
+    // Block metadata must be available even though the `miniblocks` and `l1_batches` rows are pruned.
+    let checkpoint = transaction
+        .pruning_dal()
+        .get_archive_checkpoint(L2BlockNumber(7))
+        .await
+        .unwrap()
+        .expect("no checkpoint");
+    let metadata = checkpoint.metadata;
+    let expected_header = create_l2_block_header(7);
+    assert_eq!(metadata.l2_block_hash, expected_header.hash);
+    assert_eq!(metadata.protocol_version, expected_header.protocol_version);
+    assert_eq!(metadata.batch_fee_input, expected_header.batch_fee_input);
+    // Timestamp of the first L2 block in L1 batch #3
+    assert_eq!(metadata.l1_batch_timestamp, 6);
+    let prev_l2_block = metadata.prev_l2_block.expect("no previous block");
+    assert_eq!(prev_l2_block.hash, create_l2_block_header(6).hash);
+    assert_eq!(prev_l2_block.timestamp, 6);
+
+    let expected_values = [
+        (7, random_storage_log(1, 2)),
+        (7, random_storage_log(2, 0)),
+        (13, random_storage_log(1, 3)),
+        (13, random_storage_log(2, 4)),
+        (19, random_storage_log(1, 6)),
+        (19, random_storage_log(2, 5)),
+    ];
+    for (l2_block_number, expected_log) in expected_values {
+        let value = transaction
+            .storage_web3_dal()
+            .get_historical_value_unchecked(
+                expected_log.key.hashed_key(),
+                L2BlockNumber(l2_block_number),
+            )
+            .await
+            .unwrap();
+        assert_eq!(value, expected_log.value, "L2 block #{l2_block_number}");
+    }
+
+    // Subsequent pruning without archiving retains logs for the existing checkpoints.
+    insert_l1_batch(&mut transaction, L1BatchNumber(10)).await;
+    insert_l2_block(&mut transaction, L2BlockNumber(20), L1BatchNumber(10)).await;
+    insert_l2_block(&mut transaction, L2BlockNumber(21), L1BatchNumber(10)).await;
+    insert_l2_block_storage_logs(
+        &mut transaction,
+        L2BlockNumber(20),
+        vec![random_storage_log(1, 7), random_storage_log(2, 8)],
+    )
+    .await;
+    let stats = transaction
+        .pruning_dal()
+        .hard_prune_batches_range(L1BatchNumber(10), L2BlockNumber(21))
+        .await
+        .unwrap();
+    assert_eq!(stats.deleted_storage_logs, 0);
+}
+
+#[tokio::test]
+async fn storage_logs_can_be_pruned_ahead_of_l1_batches() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    let mut transaction = conn.start_transaction().await.unwrap();
+    insert_realistic_l1_batches(&mut transaction, 10).await;
+    insert_l2_block_storage_logs(
+        &mut transaction,
+        L2BlockNumber(0),
+        vec![random_storage_log(1, 1), random_storage_log(2, 2)],
+    )
+    .await;
+    insert_l2_block_storage_logs(
+        &mut transaction,
+        L2BlockNumber(1),
+        vec![random_storage_log(1, 3)],
+    )
+    .await;
+    insert_l2_block_storage_logs(
+        &mut transaction,
+        L2BlockNumber(15),
+        vec![random_storage_log(2, 4)],
+    )
+    .await;
+
+    let category = PrunedDataCategory::StorageLogs;
+    let mut dal = transaction.pruning_dal();
+    dal.insert_category_soft_pruning_log(category, L1BatchNumber(4), L2BlockNumber(9))
+        .await
+        .unwrap();
+    let deleted_logs = dal
+        .hard_prune_category(category, L1BatchNumber(4), L2BlockNumber(9), None)
+        .await
+        .unwrap();
+    assert_eq!(deleted_logs, 1);
+    dal.insert_category_hard_pruning_log(category, L1BatchNumber(4), L2BlockNumber(9))
+        .await
+        .unwrap();
+
+    let info = dal.get_category_pruning_info().await.unwrap();
+    assert_eq!(info.call_traces, PruningInfo::default());
+    let storage_logs_info = info.get(category);
+    assert!(storage_logs_info.is_caught_up());
+    assert_eq!(
+        storage_logs_info.last_hard_pruned,
+        Some(HardPruningInfo {
+            l1_batch: L1BatchNumber(4),
+            l2_block: L2BlockNumber(9),
+            l1_batch_root_hash: None,
+        })
+    );
+    // The main pruning is not affected.
+    assert_eq!(
+        dal.get_pruning_info().await.unwrap(),
+        PruningInfo::default()
+    );
+    assert_l1_batches_exist(&mut transaction, L1BatchNumber(1)..=L1BatchNumber(9)).await;
+
+    let actual_logs = transaction
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_l2_block_storage_logs_equal(L2BlockNumber(0), &actual_logs, &[random_storage_log(2, 2)]);
+    assert_l2_block_storage_logs_equal(L2BlockNumber(1), &actual_logs, &[random_storage_log(1, 3)]);
+
+    // Pruning the category again is a no-op.
+    let deleted_logs = transaction
+        .pruning_dal()
+        .hard_prune_category(category, L1BatchNumber(4), L2BlockNumber(9), None)
+        .await
+        .unwrap();
+    assert_eq!(deleted_logs, 0);
+
+    // The main pruning skips the already pruned storage logs.
+    let stats = transaction
+        .pruning_dal()
+        .hard_prune_batches_range(L1BatchNumber(4), L2BlockNumber(9))
+        .await
+        .unwrap();
+    assert_eq!(stats.deleted_storage_logs, 0);
+    assert!(stats.deleted_events > 0);
+
+    let stats = transaction
+        .pruning_dal()
+        .hard_prune_batches_range(L1BatchNumber(9), L2BlockNumber(19))
+        .await
+        .unwrap();
+    assert_eq!(stats.deleted_storage_logs, 1);
+}
+
 #[tokio::test]
 async fn l1_batches_can_be_hard_pruned() {
     let pool = ConnectionPool::<Core>::test_pool().await;
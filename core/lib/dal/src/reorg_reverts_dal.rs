@@ -0,0 +1,126 @@
+use sqlx::types::chrono::NaiveDateTime;
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::L1BatchNumber;
+
+use crate::Core;
+
+/// Rollback automatically performed by the external node after detecting a reorg.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReorgRevert {
+    /// Last sealed L1 batch before the rollback.
+    pub reverted_from_l1_batch: L1BatchNumber,
+    /// Last L1 batch retained after the rollback.
+    pub last_retained_l1_batch: L1BatchNumber,
+    pub created_at: NaiveDateTime,
+}
+
+impl ReorgRevert {
+    /// Returns the number of rolled back L1 batches.
+    pub fn depth(&self) -> u32 {
+        self.reverted_from_l1_batch
+            .0
+            .saturating_sub(self.last_retained_l1_batch.0)
+    }
+}
+
+/// DAL for the audit log of automatic reorg rollbacks on the external node.
+#[derive(Debug)]
+pub struct ReorgRevertsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl ReorgRevertsDal<'_, '_> {
+    pub async fn insert_revert(
+        &mut self,
+        reverted_from_l1_batch: L1BatchNumber,
+        last_retained_l1_batch: L1BatchNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            en_reorg_reverts (reverted_from_l1_batch, last_retained_l1_batch, created_at)
+            VALUES
+            ($1, $2, NOW())
+            "#,
+            i64::from(reverted_from_l1_batch.0),
+            i64::from(last_retained_l1_batch.0)
+        )
+        .instrument("insert_revert")
+        .with_arg("reverted_from_l1_batch", &reverted_from_l1_batch)
+        .with_arg("last_retained_l1_batch", &last_retained_l1_batch)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the latest recorded rollback, if any.
+    pub async fn get_latest_revert(&mut self) -> DalResult<Option<ReorgRevert>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                reverted_from_l1_batch,
+                last_retained_l1_batch,
+                created_at
+            FROM
+                en_reorg_reverts
+            ORDER BY
+                id DESC
+            LIMIT
+                1
+            "#
+        )
+        .instrument("get_latest_revert")
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| ReorgRevert {
+            reverted_from_l1_batch: L1BatchNumber(row.reverted_from_l1_batch as u32),
+            last_retained_l1_batch: L1BatchNumber(row.last_retained_l1_batch as u32),
+            created_at: row.created_at,
+        }))
+    }
+
+    /// Returns the total number of recorded rollbacks.
+    pub async fn get_revert_count(&mut self) -> DalResult<u64> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                en_reorg_reverts
+            "#
+        )
+        .instrument("get_revert_count")
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row.count as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn recording_reverts() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.reorg_reverts_dal();
+        assert_eq!(dal.get_latest_revert().await.unwrap(), None);
+        assert_eq!(dal.get_revert_count().await.unwrap(), 0);
+
+        dal.insert_revert(L1BatchNumber(10), L1BatchNumber(7))
+            .await
+            .unwrap();
+        dal.insert_revert(L1BatchNumber(12), L1BatchNumber(11))
+            .await
+            .unwrap();
+
+        let latest_revert = dal.get_latest_revert().await.unwrap().unwrap();
+        assert_eq!(latest_revert.reverted_from_l1_batch, L1BatchNumber(12));
+        assert_eq!(latest_revert.last_retained_l1_batch, L1BatchNumber(11));
+        assert_eq!(latest_revert.depth(), 1);
+        assert_eq!(dal.get_revert_count().await.unwrap(), 2);
+    }
+}
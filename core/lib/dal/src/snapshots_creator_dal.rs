@@ -109,6 +109,106 @@ impl SnapshotsCreatorDal<'_, '_> {
         Ok(storage_logs)
     }
 
+    /// Returns an upper bound on the number of storage keys changed in the `(base_l2_block_number, l2_block_number]`
+    /// L2 block range. Used to select chunking for incremental snapshots.
+    pub async fn get_changed_storage_logs_count(
+        &mut self,
+        base_l2_block_number: L2BlockNumber,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<u64> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                storage_logs
+            WHERE
+                miniblock_number > $1
+                AND miniblock_number <= $2
+            "#,
+            i64::from(base_l2_block_number.0),
+            i64::from(l2_block_number.0)
+        )
+        .instrument("get_changed_storage_logs_count")
+        .with_arg("base_l2_block_number", &base_l2_block_number)
+        .with_arg("l2_block_number", &l2_block_number)
+        .report_latency()
+        .expect_slow_query()
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row.count as u64)
+    }
+
+    /// Constructs a `storage_logs` chunk for an incremental snapshot, i.e., only includes keys changed in
+    /// the `(base_l2_block_number, l2_block_number]` L2 block range. Values correspond to the state AFTER processing
+    /// `[0..l1_batch_number]` batches. `l2_block_number` MUST be the last L2 block of the `l1_batch_number` batch.
+    pub async fn get_changed_storage_logs_chunk(
+        &mut self,
+        base_l2_block_number: L2BlockNumber,
+        l2_block_number: L2BlockNumber,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys_range: std::ops::RangeInclusive<H256>,
+    ) -> DalResult<Vec<SnapshotStorageLog>> {
+        // See `get_storage_logs_chunk()` for the explanation of filtering by `l1_batch_number`.
+        let storage_logs = sqlx::query!(
+            r#"
+            SELECT
+                storage_logs.hashed_key AS "hashed_key!",
+                storage_logs.value AS "value!",
+                initial_writes.l1_batch_number AS "l1_batch_number!",
+                initial_writes.index
+            FROM
+                (
+                    SELECT
+                        hashed_key,
+                        MAX(ARRAY[miniblock_number, operation_number]::INT []) AS op
+                    FROM
+                        storage_logs
+                    WHERE
+                        miniblock_number > $1
+                        AND miniblock_number <= $2
+                        AND hashed_key >= $4
+                        AND hashed_key <= $5
+                    GROUP BY
+                        hashed_key
+                    ORDER BY
+                        hashed_key
+                ) AS keys
+            INNER JOIN storage_logs
+                ON
+                    keys.hashed_key = storage_logs.hashed_key
+                    AND storage_logs.miniblock_number = keys.op[1]
+                    AND storage_logs.operation_number = keys.op[2]
+            INNER JOIN initial_writes ON keys.hashed_key = initial_writes.hashed_key
+            WHERE
+                initial_writes.l1_batch_number <= $3
+            "#,
+            i64::from(base_l2_block_number.0),
+            i64::from(l2_block_number.0),
+            i64::from(l1_batch_number.0),
+            hashed_keys_range.start().as_bytes(),
+            hashed_keys_range.end().as_bytes()
+        )
+        .instrument("get_changed_storage_logs_chunk")
+        .with_arg("base_l2_block_number", &base_l2_block_number)
+        .with_arg("l2_block_number", &l2_block_number)
+        .with_arg("min_hashed_key", &hashed_keys_range.start())
+        .with_arg("max_hashed_key", &hashed_keys_range.end())
+        .report_latency()
+        .expect_slow_query()
+        .fetch_all(self.storage)
+        .await?
+        .iter()
+        .map(|row| SnapshotStorageLog {
+            key: H256::from_slice(&row.hashed_key),
+            value: H256::from_slice(&row.value),
+            l1_batch_number_of_initial_write: L1BatchNumber(row.l1_batch_number as u32),
+            enumeration_index: row.index as u64,
+        })
+        .collect();
+        Ok(storage_logs)
+    }
+
     /// Same as [`Self::get_storage_logs_chunk()`], but returns full keys.
     #[deprecated(
         note = "will fail if called on a node restored from a v1 snapshot; use `get_storage_logs_chunk()` instead"
@@ -208,6 +308,39 @@ impl SnapshotsCreatorDal<'_, '_> {
             .map(|row| (H256::from_slice(&row.bytecode_hash), row.bytecode))
             .collect())
     }
+
+    /// Returns factory dependencies added in the `(base_l2_block_number, l2_block_number]` L2 block range.
+    pub async fn get_new_factory_deps(
+        &mut self,
+        base_l2_block_number: L2BlockNumber,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<Vec<(H256, Vec<u8>)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                bytecode_hash,
+                bytecode
+            FROM
+                factory_deps
+            WHERE
+                miniblock_number > $1
+                AND miniblock_number <= $2
+            "#,
+            i64::from(base_l2_block_number.0),
+            i64::from(l2_block_number.0),
+        )
+        .instrument("get_new_factory_deps")
+        .with_arg("base_l2_block_number", &base_l2_block_number)
+        .with_arg("l2_block_number", &l2_block_number)
+        .report_latency()
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (H256::from_slice(&row.bytecode_hash), row.bytecode))
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -289,6 +422,38 @@ mod tests {
             .unwrap();
         assert_eq!(log_row_count, (logs.len() + all_new_logs_len) as u64);
         assert_logs_for_snapshot(&mut conn, L2BlockNumber(1), L1BatchNumber(1), &logs).await;
+
+        // Only logs from the second L2 block should be returned for an incremental snapshot.
+        let changed_log_count = conn
+            .snapshots_creator_dal()
+            .get_changed_storage_logs_count(L2BlockNumber(1), L2BlockNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(changed_log_count, all_new_logs_len as u64);
+        let changed_logs = conn
+            .snapshots_creator_dal()
+            .get_changed_storage_logs_chunk(
+                L2BlockNumber(1),
+                L2BlockNumber(2),
+                L1BatchNumber(2),
+                H256::zero()..=H256::repeat_byte(0xff),
+            )
+            .await
+            .unwrap();
+        assert_eq!(changed_logs.len(), all_new_logs_len);
+        for log in &changed_logs {
+            let expected_log = all_new_logs
+                .iter()
+                .find(|expected| expected.key.hashed_key() == log.key)
+                .unwrap();
+            assert_eq!(log.value, expected_log.value);
+            let expected_l1_batch = if new_written_keys.contains(&log.key) {
+                L1BatchNumber(2)
+            } else {
+                L1BatchNumber(1)
+            };
+            assert_eq!(log.l1_batch_number_of_initial_write, expected_l1_batch);
+        }
     }
 
     async fn assert_logs_for_snapshot(
@@ -14,6 +14,7 @@ use crate::Core;
 struct StorageSnapshotMetadata {
     version: i32,
     l1_batch_number: i64,
+    base_l1_batch_number: Option<i64>,
     storage_logs_filepaths: Vec<String>,
     factory_deps_filepath: String,
 }
@@ -28,6 +29,9 @@ impl TryFrom<StorageSnapshotMetadata> for SnapshotMetadata {
         Ok(Self {
             version,
             l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            base_l1_batch_number: row
+                .base_l1_batch_number
+                .map(|number| L1BatchNumber(number as u32)),
             storage_logs_filepaths: row
                 .storage_logs_filepaths
                 .into_iter()
@@ -50,6 +54,7 @@ impl SnapshotsDal<'_, '_> {
         l1_batch_number: L1BatchNumber,
         storage_logs_chunk_count: u64,
         factory_deps_filepaths: &str,
+        base_l1_batch_number: Option<L1BatchNumber>,
     ) -> DalResult<()> {
         sqlx::query!(
             r#"
@@ -59,20 +64,23 @@ impl SnapshotsDal<'_, '_> {
                 l1_batch_number,
                 storage_logs_filepaths,
                 factory_deps_filepath,
+                base_l1_batch_number,
                 created_at,
                 updated_at
             )
             VALUES
-            ($1, $2, ARRAY_FILL(''::TEXT, ARRAY[$3::INTEGER]), $4, NOW(), NOW())
+            ($1, $2, ARRAY_FILL(''::TEXT, ARRAY[$3::INTEGER]), $4, $5, NOW(), NOW())
             "#,
             version as i32,
             l1_batch_number.0 as i32,
             storage_logs_chunk_count as i32,
             factory_deps_filepaths,
+            base_l1_batch_number.map(|number| i64::from(number.0)),
         )
         .instrument("add_snapshot")
         .with_arg("version", &version)
         .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("base_l1_batch_number", &base_l1_batch_number)
         .report_latency()
         .execute(self.storage)
         .await?;
@@ -142,6 +150,7 @@ impl SnapshotsDal<'_, '_> {
             SELECT
                 VERSION,
                 L1_BATCH_NUMBER,
+                BASE_L1_BATCH_NUMBER,
                 FACTORY_DEPS_FILEPATH,
                 STORAGE_LOGS_FILEPATHS
             FROM
@@ -169,6 +178,7 @@ impl SnapshotsDal<'_, '_> {
             SELECT
                 VERSION,
                 L1_BATCH_NUMBER,
+                BASE_L1_BATCH_NUMBER,
                 FACTORY_DEPS_FILEPATH,
                 STORAGE_LOGS_FILEPATHS
             FROM
@@ -186,6 +196,40 @@ impl SnapshotsDal<'_, '_> {
         .await
     }
 
+    /// Returns metadata for all snapshots after the specified L1 batch number, i.e. the snapshots
+    /// that would be removed by [`Self::delete_snapshots_after()`].
+    pub async fn get_snapshots_after(
+        &mut self,
+        last_retained_l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<SnapshotMetadata>> {
+        sqlx::query_as!(
+            StorageSnapshotMetadata,
+            r#"
+            SELECT
+                version,
+                l1_batch_number,
+                base_l1_batch_number,
+                factory_deps_filepath,
+                storage_logs_filepaths
+            FROM
+                snapshots
+            WHERE
+                l1_batch_number > $1
+            ORDER BY
+                l1_batch_number
+            "#,
+            last_retained_l1_batch_number.0 as i32
+        )
+        .try_map(SnapshotMetadata::try_from)
+        .instrument("get_snapshots_after")
+        .with_arg(
+            "last_retained_l1_batch_number",
+            &last_retained_l1_batch_number,
+        )
+        .fetch_all(self.storage)
+        .await
+    }
+
     /// Deletes all snapshots after the specified L1 batch number and returns their metadata.
     pub async fn delete_snapshots_after(
         &mut self,
@@ -200,6 +244,7 @@ impl SnapshotsDal<'_, '_> {
             RETURNING
             version,
             l1_batch_number,
+            base_l1_batch_number,
             factory_deps_filepath,
             storage_logs_filepaths
             "#,
@@ -233,6 +278,7 @@ mod tests {
             l1_batch_number,
             2,
             "gs:///bucket/factory_deps.bin",
+            None,
         )
         .await
         .expect("Failed to add snapshot");
@@ -278,6 +324,7 @@ mod tests {
             l1_batch_number,
             2,
             "gs:///bucket/factory_deps.bin",
+            None,
         )
         .await
         .unwrap();
@@ -341,6 +388,7 @@ mod tests {
             l1_batch_number,
             2,
             "gs:///bucket/factory_deps.bin",
+            None,
         )
         .await
         .expect("Failed to add snapshot");
@@ -379,4 +427,44 @@ mod tests {
             ]
         );
     }
+
+    #[tokio::test]
+    async fn adding_incremental_snapshot() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.snapshots_dal();
+        dal.add_snapshot(
+            SnapshotVersion::Version1,
+            L1BatchNumber(100),
+            1,
+            "gs:///bucket/factory_deps.bin",
+            None,
+        )
+        .await
+        .unwrap();
+        dal.add_snapshot(
+            SnapshotVersion::Version2,
+            L1BatchNumber(150),
+            1,
+            "gs:///bucket/factory_deps_150.bin",
+            Some(L1BatchNumber(100)),
+        )
+        .await
+        .unwrap();
+
+        let base_metadata = dal
+            .get_snapshot_metadata(L1BatchNumber(100))
+            .await
+            .unwrap()
+            .expect("snapshot is not persisted");
+        assert_eq!(base_metadata.base_l1_batch_number, None);
+        let metadata = dal
+            .get_newest_snapshot_metadata()
+            .await
+            .unwrap()
+            .expect("snapshot is not persisted");
+        assert_eq!(metadata.version, SnapshotVersion::Version2);
+        assert_eq!(metadata.l1_batch_number, L1BatchNumber(150));
+        assert_eq!(metadata.base_l1_batch_number, Some(L1BatchNumber(100)));
+    }
 }
@@ -209,6 +209,63 @@ impl StorageLogsDal<'_, '_> {
             .collect())
     }
 
+    /// Returns storage logs persisted for the specified L2 block in the order of their insertion.
+    pub async fn get_storage_logs_for_l2_block(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<Vec<StorageLog>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                address AS "address!",
+                key AS "key!",
+                value
+            FROM
+                storage_logs
+            WHERE
+                miniblock_number = $1
+            ORDER BY
+                operation_number
+            "#,
+            i64::from(l2_block_number.0)
+        )
+        .instrument("get_storage_logs_for_l2_block")
+        .with_arg("l2_block_number", &l2_block_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let key = StorageKey::new(
+                    AccountTreeId::new(Address::from_slice(&row.address)),
+                    H256::from_slice(&row.key),
+                );
+                StorageLog::new_write_log(key, H256::from_slice(&row.value))
+            })
+            .collect())
+    }
+
+    /// Removes all storage logs for the specified `hashed_keys`. Used when applying incremental snapshots
+    /// to only retain the latest log for each key.
+    pub async fn delete_storage_logs_for_keys(&mut self, hashed_keys: &[H256]) -> DalResult<()> {
+        let hashed_keys: Vec<_> = hashed_keys.iter().map(H256::as_bytes).collect();
+        sqlx::query!(
+            r#"
+            DELETE FROM storage_logs
+            WHERE
+                hashed_key = ANY($1)
+            "#,
+            &hashed_keys as &[&[u8]]
+        )
+        .instrument("delete_storage_logs_for_keys")
+        .with_arg("hashed_keys.len", &hashed_keys.len())
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     /// Removes all storage logs with a L2 block number strictly greater than the specified `block_number`.
     pub async fn roll_back_storage_logs(&mut self, block_number: L2BlockNumber) -> DalResult<()> {
         sqlx::query!(
@@ -938,6 +995,13 @@ mod tests {
             H256::repeat_byte(2)
         );
 
+        let block_logs = conn
+            .storage_logs_dal()
+            .get_storage_logs_for_l2_block(L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(block_logs, [log, other_log, third_log]);
+
         test_revert(&mut conn, first_key, second_key).await;
     }
 
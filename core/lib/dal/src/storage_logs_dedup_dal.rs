@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use sqlx::types::chrono::Utc;
 use zksync_db_connection::{
@@ -270,6 +270,34 @@ impl StorageLogsDedupDal<'_, '_> {
         .map(|row| row.index as u64))
     }
 
+    /// Returns hashed keys for the specified enumeration indices. Indices missing from the `initial_writes` table
+    /// are omitted from the returned map.
+    pub async fn get_hashed_keys_by_enumeration_indices(
+        &mut self,
+        indices: &[u64],
+    ) -> DalResult<HashMap<u64, H256>> {
+        let indices: Vec<_> = indices.iter().map(|&index| index as i64).collect();
+        Ok(sqlx::query!(
+            r#"
+            SELECT
+                index,
+                hashed_key
+            FROM
+                initial_writes
+            WHERE
+                index = ANY($1)
+            "#,
+            &indices
+        )
+        .instrument("get_hashed_keys_by_enumeration_indices")
+        .with_arg("indices.len", &indices.len())
+        .fetch_all(self.storage)
+        .await?
+        .into_iter()
+        .map(|row| (row.index as u64, H256::from_slice(&row.hashed_key)))
+        .collect())
+    }
+
     /// Returns `hashed_keys` that are both present in the input and in `initial_writes` table.
     pub async fn filter_written_slots(&mut self, hashed_keys: &[H256]) -> DalResult<HashSet<H256>> {
         let hashed_keys: Vec<_> = hashed_keys.iter().map(H256::as_bytes).collect();
@@ -76,26 +76,40 @@ impl TokensDal<'_, '_> {
             .collect())
     }
 
-    /// Removes token records that were deployed after `block_number`.
-    pub async fn roll_back_tokens(&mut self, block_number: L2BlockNumber) -> DalResult<()> {
+    /// Returns L2 addresses of tokens that were deployed after `block_number` and would be removed
+    /// by [`Self::roll_back_tokens()`].
+    pub async fn get_token_addresses_to_roll_back(
+        &mut self,
+        block_number: L2BlockNumber,
+    ) -> DalResult<Vec<Address>> {
         let all_token_addresses = self.get_all_l2_token_addresses().await?;
         let token_deployment_data = self
             .storage
             .storage_logs_dal()
             .filter_deployed_contracts(all_token_addresses.iter().copied(), None)
             .await?;
-        let token_addresses_to_be_removed: Vec<_> = all_token_addresses
+        Ok(all_token_addresses
             .into_iter()
-            .filter_map(|address| {
+            .filter(|address| {
                 if address.is_zero() {
-                    None
-                } else if let Some((deployed_at, _)) = token_deployment_data.get(&address) {
-                    (deployed_at > &block_number).then_some(address.0)
+                    false
+                } else if let Some((deployed_at, _)) = token_deployment_data.get(address) {
+                    deployed_at > &block_number
                 } else {
                     // Token belongs to a "pending" L2 block that's not yet fully inserted to the database.
-                    Some(address.0)
+                    true
                 }
             })
+            .collect())
+    }
+
+    /// Removes token records that were deployed after `block_number`.
+    pub async fn roll_back_tokens(&mut self, block_number: L2BlockNumber) -> DalResult<()> {
+        let token_addresses_to_be_removed: Vec<_> = self
+            .get_token_addresses_to_roll_back(block_number)
+            .await?
+            .into_iter()
+            .map(|address| address.0)
             .collect();
         sqlx::query!(
             r#"
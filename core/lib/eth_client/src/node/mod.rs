@@ -7,7 +7,8 @@ pub use self::{
     bridge_addresses::BridgeAddressesUpdaterLayer,
     pk_signing_eth_client::PKSigningEthClientLayer,
     resources::{
-        BoundEthInterfaceForBlobsResource, BoundEthInterfaceForL2Resource, SenderConfigResource,
+        AdditionalOperatorsResource, BoundEthInterfaceForBlobsResource,
+        BoundEthInterfaceForL2Resource, SenderConfigResource,
     },
 };
 
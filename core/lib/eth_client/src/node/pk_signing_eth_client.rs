@@ -1,4 +1,5 @@
 use zksync_config::{configs::wallets, GasAdjusterConfig};
+use zksync_crypto_primitives::K256PrivateKey;
 use zksync_node_framework::{
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
@@ -7,12 +8,15 @@ use zksync_operator_signer::OperatorSigner;
 use zksync_shared_resources::contracts::{
     L1ChainContractsResource, SettlementLayerContractsResource,
 };
+use zksync_types::Address;
 use zksync_web3_decl::{
     client::{DynClient, L1},
     node::SettlementLayerClient,
 };
 
-use super::resources::{BoundEthInterfaceForBlobsResource, BoundEthInterfaceForL2Resource};
+use super::resources::{
+    AdditionalOperatorsResource, BoundEthInterfaceForBlobsResource, BoundEthInterfaceForL2Resource,
+};
 use crate::{clients::SigningClient, BoundEthInterface, EthInterface};
 
 /// Wiring layer for creating signing Ethereum clients.
@@ -23,6 +27,8 @@ pub struct PKSigningEthClientLayer {
     gas_adjuster_config: GasAdjusterConfig,
     operator: wallets::Wallet,
     blob_operator: Option<wallets::Wallet>,
+    additional_operators: Vec<OperatorSigner>,
+    additional_blob_operators: Vec<OperatorSigner>,
 }
 
 #[derive(Debug, FromContext)]
@@ -39,6 +45,8 @@ pub struct Output {
     /// Only provided if the blob operator key is provided to the layer.
     signing_client_for_blobs: Option<BoundEthInterfaceForBlobsResource>,
     signing_client_for_gateway: Option<BoundEthInterfaceForL2Resource>,
+    /// Only provided if additional operators are provided to the layer and the chain settles on L1.
+    additional_operators: Option<AdditionalOperatorsResource>,
 }
 
 impl PKSigningEthClientLayer {
@@ -51,8 +59,51 @@ impl PKSigningEthClientLayer {
             gas_adjuster_config,
             operator,
             blob_operator,
+            additional_operators: vec![],
+            additional_blob_operators: vec![],
         }
     }
+
+    /// Adds accounts sharing the load of the operator and the blob operator respectively.
+    pub fn with_additional_operators(
+        mut self,
+        operators: Vec<K256PrivateKey>,
+        blob_operators: Vec<K256PrivateKey>,
+    ) -> Self {
+        self.additional_operators
+            .extend(operators.into_iter().map(OperatorSigner::local));
+        self.additional_blob_operators
+            .extend(blob_operators.into_iter().map(OperatorSigner::local));
+        self
+    }
+
+    /// Adds GCP KMS-backed accounts sharing the load of the operator and the blob operator respectively.
+    pub fn with_additional_gcp_kms_operators(
+        mut self,
+        operators: Vec<String>,
+        blob_operators: Vec<String>,
+    ) -> Self {
+        self.additional_operators
+            .extend(operators.into_iter().map(OperatorSigner::gcp_kms));
+        self.additional_blob_operators
+            .extend(blob_operators.into_iter().map(OperatorSigner::gcp_kms));
+        self
+    }
+}
+
+/// Resolves addresses of the provided signers; for GCP KMS signers, this fetches public keys from KMS.
+async fn resolve_addresses(
+    signers: Vec<OperatorSigner>,
+) -> Result<Vec<(OperatorSigner, Address)>, WiringError> {
+    let mut resolved = Vec::with_capacity(signers.len());
+    for signer in signers {
+        let address = signer
+            .address()
+            .await
+            .map_err(|e| WiringError::Internal(e.into()))?;
+        resolved.push((signer, address));
+    }
+    Ok(resolved)
 }
 
 #[async_trait::async_trait]
@@ -121,6 +172,59 @@ impl WiringLayer for PKSigningEthClientLayer {
             None => None,
         };
 
+        let create_additional_client = |(signer, address): (OperatorSigner, Address)| {
+            let client = SigningClient::new(
+                query_client.clone(),
+                zksync_contracts::hyperchain_contract(),
+                address,
+                signer,
+                l1_diamond_proxy_addr,
+                gas_adjuster_config.default_priority_fee_per_gas.into(),
+                l1_chain_id,
+            );
+            Box::new(client) as Box<dyn BoundEthInterface>
+        };
+        let additional_operators: Vec<_> = resolve_addresses(self.additional_operators)
+            .await?
+            .into_iter()
+            .map(create_additional_client)
+            .collect();
+        let additional_blob_operators: Vec<_> = if signing_client_for_blobs.is_some() {
+            resolve_addresses(self.additional_blob_operators)
+                .await?
+                .into_iter()
+                .map(create_additional_client)
+                .collect()
+        } else {
+            if !self.additional_blob_operators.is_empty() {
+                tracing::warn!(
+                    "Additional blob operators are ignored since blob operator is not set"
+                );
+            }
+            vec![]
+        };
+        for client in additional_operators
+            .iter()
+            .chain(&additional_blob_operators)
+        {
+            tracing::info!("Additional operator address: {:?}", client.sender_account());
+        }
+
+        let is_gateway = matches!(input.gateway_client, SettlementLayerClient::Gateway(_));
+        let has_additional_operators =
+            !additional_operators.is_empty() || !additional_blob_operators.is_empty();
+        let additional_operators = if is_gateway || !has_additional_operators {
+            if is_gateway && has_additional_operators {
+                tracing::warn!("Additional operators are ignored when settling on Gateway");
+            }
+            None
+        } else {
+            Some(AdditionalOperatorsResource {
+                operators: additional_operators,
+                blob_operators: additional_blob_operators,
+            })
+        };
+
         let signing_client_for_gateway = match input.gateway_client {
             SettlementLayerClient::Gateway(gateway_client) => {
                 let l2_chain_id = gateway_client
@@ -147,6 +251,7 @@ impl WiringLayer for PKSigningEthClientLayer {
             signing_client,
             signing_client_for_blobs,
             signing_client_for_gateway,
+            additional_operators,
         })
     }
 }
@@ -39,6 +39,22 @@ impl Resource for BoundEthInterfaceForL2Resource {
     }
 }
 
+/// Signing clients for additional operator accounts sharing the load of the main operators
+/// when settling on L1. Each account has an independent nonce sequence.
+#[derive(Debug, Clone, Default)]
+pub struct AdditionalOperatorsResource {
+    /// Accounts sharing the load of the main operator.
+    pub operators: Vec<Box<dyn BoundEthInterface>>,
+    /// Accounts sharing the load of the blob operator (i.e., sending commit transactions).
+    pub blob_operators: Vec<Box<dyn BoundEthInterface>>,
+}
+
+impl Resource for AdditionalOperatorsResource {
+    fn name() -> String {
+        "common/additional_operators".into()
+    }
+}
+
 impl From<SettlementLayerClient> for Box<dyn EthInterface> {
     fn from(client: SettlementLayerClient) -> Self {
         match client {
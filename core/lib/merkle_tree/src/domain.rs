@@ -1,5 +1,8 @@
 //! Tying the Merkle tree implementation to the problem domain.
 
+use std::path::Path;
+
+use anyhow::Context as _;
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
 use zksync_prover_interface::inputs::{StorageLogMetadata, WitnessInputMerklePaths};
@@ -10,7 +13,7 @@ use crate::{
     storage::{PatchSet, Patched, RocksDBWrapper},
     types::{
         Key, NodeKey, RawNode, Root, TreeEntry, TreeEntryWithProof, TreeInstruction, TreeLogEntry,
-        ValueHash, TREE_DEPTH,
+        TreeMultiProof, TreeRangeAbsenceProof, ValueHash, TREE_DEPTH,
     },
     BlockOutput, HashTree, MerkleTree, MerkleTreePruner, MerkleTreePrunerHandle, NoVersionError,
     PruneDatabase,
@@ -382,6 +385,27 @@ impl ZkSyncTree {
     pub fn reset(&mut self) {
         self.tree.db.reset();
     }
+
+    /// Creates a RocksDB checkpoint of this tree at the specified `path`. The tree must not have unsaved changes,
+    /// so that the checkpoint corresponds to a consistent L1 batch. Returns the next L1 batch number
+    /// for the checkpointed tree.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the tree has unsaved changes. Proxies database I/O errors.
+    pub fn create_checkpoint(&self, path: &Path) -> anyhow::Result<L1BatchNumber> {
+        let patched_versions = self.tree.db.patched_versions();
+        anyhow::ensure!(
+            patched_versions.is_empty(),
+            "cannot create checkpoint for tree with unsaved versions {patched_versions:?}"
+        );
+        self.tree
+            .db
+            .inner()
+            .create_checkpoint(path)
+            .with_context(|| format!("failed creating tree checkpoint at `{}`", path.display()))?;
+        Ok(self.next_l1_batch_number())
+    }
 }
 
 /// Readonly handle to a [`ZkSyncTree`].
@@ -438,6 +462,21 @@ impl ZkSyncTreeReader {
         })
     }
 
+    /// Reads entries with the specified keys from the tree. The entries are returned in the same order
+    /// as requested. Missing keys correspond to [empty](TreeEntry::is_empty()) entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    pub fn entries(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        keys: &[Key],
+    ) -> Result<Vec<TreeEntry>, NoVersionError> {
+        let version = u64::from(l1_batch_number.0);
+        self.0.entries(version, keys)
+    }
+
     /// Reads entries together with Merkle proofs with the specified keys from the tree. The entries are returned
     /// in the same order as requested.
     ///
@@ -453,6 +492,44 @@ impl ZkSyncTreeReader {
         self.0.entries_with_proofs(version, keys)
     }
 
+    /// Reads entries with the specified keys from the tree together with a compact multi-proof for all of them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keys` are empty.
+    pub fn entries_with_multi_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        keys: &[Key],
+    ) -> Result<TreeMultiProof, NoVersionError> {
+        let version = u64::from(l1_batch_number.0);
+        self.0.entries_with_multi_proof(version, keys)
+    }
+
+    /// Creates a proof that the tree contains no entries in the `start_key..=end_key` range. Returns `Ok(None)`
+    /// if the range is not empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start_key > end_key`.
+    pub fn range_absence_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        start_key: Key,
+        end_key: Key,
+    ) -> Result<Option<TreeRangeAbsenceProof>, NoVersionError> {
+        let version = u64::from(l1_batch_number.0);
+        self.0.range_absence_proof(version, start_key, end_key)
+    }
+
     /// Returns raw nodes for the specified `keys`.
     pub fn raw_nodes(&self, keys: &[NodeKey]) -> Vec<Option<RawNode>> {
         let raw_nodes = self.0.db.raw_nodes(keys).into_iter();
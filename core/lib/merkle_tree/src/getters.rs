@@ -4,8 +4,11 @@ use crate::{
     hasher::HasherWithStats,
     recovery::MerkleTreeRecovery,
     storage::{LoadAncestorsResult, SortedKeys, WorkingPatchSet},
-    types::{Nibbles, Node, ProfiledTreeOperation, TreeEntry, TreeEntryWithProof},
-    Database, HashTree, Key, MerkleTree, NoVersionError, PruneDatabase, ValueHash,
+    types::{
+        Nibbles, Node, ProfiledTreeOperation, TreeEntry, TreeEntryWithProof, TreeMultiProof,
+        TreeRangeAbsenceProof,
+    },
+    Database, HashTree, Key, MerkleTree, NoVersionError, PruneDatabase, TreeRangeDigest, ValueHash,
 };
 
 impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
@@ -59,6 +62,62 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
             },
         )
     }
+
+    /// Reads entries with the specified keys from the tree together with a compact [`TreeMultiProof`]
+    /// for all of them. Entries in the returned proof are ordered by key and deduplicated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `leaf_keys` are empty.
+    pub fn entries_with_multi_proof(
+        &self,
+        version: u64,
+        leaf_keys: &[Key],
+    ) -> Result<TreeMultiProof, NoVersionError> {
+        assert!(!leaf_keys.is_empty(), "No keys to prove");
+        let proofs = self.entries_with_proofs(version, leaf_keys)?;
+        Ok(TreeMultiProof::new(proofs))
+    }
+
+    /// Creates a proof that the tree contains no entries with keys in the `start_key..=end_key` range.
+    /// Returns `Ok(None)` if the range is not empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start_key > end_key`.
+    pub fn range_absence_proof(
+        &self,
+        version: u64,
+        start_key: Key,
+        end_key: Key,
+    ) -> Result<Option<TreeRangeAbsenceProof>, NoVersionError> {
+        assert!(start_key <= end_key, "Invalid key range");
+        let mut proofs = self.entries_with_proofs(version, &[start_key, end_key])?;
+        let end = proofs.pop().unwrap();
+        let start = proofs.pop().unwrap();
+        // ^ `unwrap()`s are safe by construction
+        if !start.base.is_empty() || !end.base.is_empty() {
+            return Ok(None);
+        }
+        if start_key < end_key {
+            // The range digest restores the tree root hash iff there are no entries between the boundaries.
+            let root_hash = self.root_hash(version).unwrap();
+            // ^ `unwrap()` is safe since the version is checked when loading entries
+            let range_hash = TreeRangeDigest::new(&self.hasher, start_key, &start).finalize(&end);
+            if range_hash != root_hash {
+                return Ok(None);
+            }
+        }
+        Ok(Some(TreeRangeAbsenceProof { start, end }))
+    }
 }
 
 fn load_and_transform_entries<T>(
@@ -157,4 +216,45 @@ mod tests {
         assert!(entries[1].base.is_empty());
         entries[1].verify(&tree.hasher, output.root_hash).unwrap();
     }
+
+    #[test]
+    fn multi_proof_for_single_node_tree() {
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        let key = Key::from(987_654);
+        let output = tree
+            .extend(vec![TreeEntry::new(key, 1, ValueHash::repeat_byte(1))])
+            .unwrap();
+        let missing_key = Key::from(123);
+
+        let proof = tree
+            .entries_with_multi_proof(0, &[key, missing_key, key])
+            .unwrap();
+        assert_eq!(proof.entries.len(), 2);
+        assert!(proof.entries[0].is_empty());
+        assert_eq!(proof.entries[1].key, key);
+        proof.verify(&tree.hasher, output.root_hash).unwrap();
+    }
+
+    #[test]
+    fn range_absence_proofs_in_single_node_tree() {
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        let key = Key::from(987_654);
+        let output = tree
+            .extend(vec![TreeEntry::new(key, 1, ValueHash::repeat_byte(1))])
+            .unwrap();
+
+        for (start, end) in [(0, 100), (5, 5), (987_655, 1_000_000)] {
+            let proof = tree
+                .range_absence_proof(0, Key::from(start), Key::from(end))
+                .unwrap()
+                .expect("range is empty");
+            proof.verify(&tree.hasher, output.root_hash).unwrap();
+        }
+        for (start, end) in [(0, 987_654), (987_654, 987_654), (100, 1_000_000)] {
+            let proof = tree
+                .range_absence_proof(0, Key::from(start), Key::from(end))
+                .unwrap();
+            assert!(proof.is_none(), "{start}..={end}");
+        }
+    }
 }
@@ -2,13 +2,13 @@
 
 use std::mem;
 
-use anyhow::ensure;
+use anyhow::{ensure, Context as _};
 
 use crate::{
     hasher::{HashTree, HasherWithStats},
     types::{
         BlockOutputWithProofs, Key, LeafNode, TreeEntry, TreeEntryWithProof, TreeInstruction,
-        TreeLogEntry, ValueHash, TREE_DEPTH,
+        TreeLogEntry, TreeMultiProof, TreeRangeAbsenceProof, ValueHash, TREE_DEPTH,
     },
     utils,
 };
@@ -104,6 +104,202 @@ impl TreeEntryWithProof {
     }
 }
 
+/// Node in a partially restored tree processed during multi-proof creation or verification.
+#[derive(Debug)]
+struct MultiProofNode<T> {
+    /// Key prefix of the node; i.e., the node key shifted right by the node depth.
+    prefix: Key,
+    /// Depth at which the truncated Merkle path for this node starts.
+    path_start: usize,
+    payload: T,
+}
+
+impl<T> MultiProofNode<T> {
+    fn new(key: Key, path_len: usize, payload: T) -> Self {
+        Self {
+            prefix: key,
+            path_start: TREE_DEPTH - path_len,
+            payload,
+        }
+    }
+
+    fn has_sibling(&self, next: &Self) -> bool {
+        self.prefix >> 1 == next.prefix >> 1
+    }
+}
+
+impl TreeMultiProof {
+    /// Creates a multi-proof from proofs for individual entries. Proofs can be provided in any order
+    /// and may contain duplicate keys; the duplicates are removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `proofs` are empty, or if any of the Merkle paths is longer than the tree depth.
+    pub fn new(mut proofs: Vec<TreeEntryWithProof>) -> Self {
+        assert!(
+            !proofs.is_empty(),
+            "Cannot create a multi-proof for no entries"
+        );
+        proofs.sort_unstable_by_key(|proof| proof.base.key);
+        proofs.dedup_by_key(|proof| proof.base.key);
+
+        let mut nodes: Vec<_> = proofs
+            .iter()
+            .enumerate()
+            .map(|(i, proof)| {
+                assert!(
+                    proof.merkle_path.len() <= TREE_DEPTH,
+                    "Merkle path is too long"
+                );
+                MultiProofNode::new(proof.base.key, proof.merkle_path.len(), i)
+            })
+            .collect();
+        let mut hashes = vec![];
+        for depth in 0..TREE_DEPTH {
+            let mut parents = Vec::with_capacity(nodes.len());
+            let mut nodes_iter = nodes.into_iter().peekable();
+            while let Some(mut node) = nodes_iter.next() {
+                if let Some(sibling) = nodes_iter.next_if(|next| node.has_sibling(next)) {
+                    // The adjacent hash is computable from the proven entries. Paths for both nodes coincide
+                    // above this level, but one of the paths may be truncated; keep the longer one.
+                    if sibling.path_start < node.path_start {
+                        node.path_start = sibling.path_start;
+                        node.payload = sibling.payload;
+                    }
+                } else if depth >= node.path_start {
+                    hashes.push(proofs[node.payload].merkle_path[depth - node.path_start]);
+                }
+                node.prefix >>= 1;
+                parents.push(node);
+            }
+            nodes = parents;
+        }
+
+        Self {
+            path_lengths: proofs.iter().map(|proof| proof.merkle_path.len()).collect(),
+            entries: proofs.into_iter().map(|proof| proof.base).collect(),
+            hashes,
+        }
+    }
+
+    /// Verifies this proof.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error <=> proof is invalid.
+    pub fn verify(
+        &self,
+        hasher: &dyn HashTree,
+        trusted_root_hash: ValueHash,
+    ) -> anyhow::Result<()> {
+        ensure!(!self.entries.is_empty(), "Multi-proof contains no entries");
+        ensure!(
+            self.entries.len() == self.path_lengths.len(),
+            "Mismatch between number of entries ({}) and path lengths ({})",
+            self.entries.len(),
+            self.path_lengths.len()
+        );
+        for window in self.entries.windows(2) {
+            ensure!(
+                window[0].key < window[1].key,
+                "Entries are not ordered by key or contain duplicates"
+            );
+        }
+
+        let mut nodes = Vec::with_capacity(self.entries.len());
+        for (entry, &path_len) in self.entries.iter().zip(&self.path_lengths) {
+            if entry.leaf_index == 0 {
+                ensure!(
+                    entry.value.is_zero(),
+                    "Invalid missing value specification for key {:0>64x}: leaf index is zero, but value is non-default",
+                    entry.key
+                );
+            }
+            ensure!(
+                path_len <= TREE_DEPTH,
+                "Merkle path for key {:0>64x} is too long: {path_len}",
+                entry.key
+            );
+            let leaf_hash = hasher.hash_leaf(&entry.value, entry.leaf_index);
+            nodes.push(MultiProofNode::new(entry.key, path_len, leaf_hash));
+        }
+
+        let mut hashes = self.hashes.iter();
+        for depth in 0..TREE_DEPTH {
+            let mut parents = Vec::with_capacity(nodes.len());
+            let mut nodes_iter = nodes.into_iter().peekable();
+            while let Some(mut node) = nodes_iter.next() {
+                if let Some(sibling) = nodes_iter.next_if(|next| node.has_sibling(next)) {
+                    node.path_start = node.path_start.min(sibling.path_start);
+                    node.payload = hasher.hash_branch(&node.payload, &sibling.payload);
+                } else {
+                    let adjacent_hash = if depth >= node.path_start {
+                        *hashes
+                            .next()
+                            .context("Multi-proof contains not enough hashes")?
+                    } else {
+                        hasher.empty_subtree_hash(depth)
+                    };
+                    node.payload = if node.prefix.bit(0) {
+                        hasher.hash_branch(&adjacent_hash, &node.payload)
+                    } else {
+                        hasher.hash_branch(&node.payload, &adjacent_hash)
+                    };
+                }
+                node.prefix >>= 1;
+                parents.push(node);
+            }
+            nodes = parents;
+        }
+
+        ensure!(hashes.next().is_none(), "Multi-proof contains extra hashes");
+        // By construction, all nodes are merged into a single root node.
+        let root_hash = nodes[0].payload;
+        ensure!(
+            root_hash == trusted_root_hash,
+            "Root hash mismatch: got {root_hash}, want {trusted_root_hash}"
+        );
+        Ok(())
+    }
+}
+
+impl TreeRangeAbsenceProof {
+    /// Verifies this proof.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error <=> proof is invalid.
+    pub fn verify(
+        &self,
+        hasher: &dyn HashTree,
+        trusted_root_hash: ValueHash,
+    ) -> anyhow::Result<()> {
+        let (start, end) = (&self.start.base, &self.end.base);
+        ensure!(start.is_empty(), "Range start entry is not empty");
+        ensure!(end.is_empty(), "Range end entry is not empty");
+        ensure!(
+            self.start.merkle_path.len() <= TREE_DEPTH && self.end.merkle_path.len() <= TREE_DEPTH,
+            "Merkle path for a range boundary is too long"
+        );
+        ensure!(
+            start.key <= end.key,
+            "Range start {:0>64x} is greater than its end {:0>64x}",
+            start.key,
+            end.key
+        );
+
+        if start.key == end.key {
+            return self.start.verify(hasher, trusted_root_hash);
+        }
+        let root_hash = TreeRangeDigest::new(hasher, start.key, &self.start).finalize(&self.end);
+        ensure!(
+            root_hash == trusted_root_hash,
+            "Root hash mismatch: got {root_hash}, want {trusted_root_hash}"
+        );
+        Ok(())
+    }
+}
+
 /// Range digest in a Merkle tree allowing to compute its root hash based on the provided entries.
 ///
 /// - The entries must be ordered by key. I.e., the first entry must have the numerically smallest key,
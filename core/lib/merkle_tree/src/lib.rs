@@ -58,7 +58,7 @@ pub use crate::{
     },
     types::{
         BlockOutput, BlockOutputWithProofs, Key, TreeEntry, TreeEntryWithProof, TreeInstruction,
-        TreeLogEntry, TreeLogEntryWithProof, ValueHash,
+        TreeLogEntry, TreeLogEntryWithProof, TreeMultiProof, TreeRangeAbsenceProof, ValueHash,
     },
 };
 use crate::{storage::Storage, types::Root};
@@ -270,6 +270,15 @@ impl RocksDBWrapper {
         StaleKeysRepairData::deserialize(&raw_value).map(Some)
     }
 
+    /// Creates a RocksDB checkpoint at the specified `path`. See [`RocksDB::create_checkpoint()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB I/O errors.
+    pub fn create_checkpoint(&self, path: &Path) -> Result<(), rocksdb::Error> {
+        self.db.create_checkpoint(path)
+    }
+
     /// Returns the wrapped RocksDB instance.
     pub fn into_inner(self) -> RocksDB<MerkleTreeColumnFamily> {
         self.db
@@ -116,6 +116,38 @@ pub struct TreeEntryWithProof {
     pub merkle_path: Vec<ValueHash>,
 }
 
+/// Compact proof for multiple entries in a Merkle tree.
+///
+/// Unlike a collection of [`TreeEntryWithProof`]s, a multi-proof doesn't contain hashes that can be computed
+/// from the proven entries themselves, and each of remaining hashes is only included once. Thus, the proof size
+/// is significantly smaller if proven keys share many tree ancestors (e.g., if they are storage slots of the same contract,
+/// or if there are hundreds of proven keys).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeMultiProof {
+    /// Proven entries ordered by key. Keys are unique.
+    pub entries: Vec<TreeEntry>,
+    /// Lengths of truncated Merkle paths for each of `entries`, in the same sense as in [`TreeEntryWithProof`].
+    /// Adjacent hashes for the tree levels below the path start correspond to empty subtrees and are not included
+    /// into the proof.
+    pub path_lengths: Vec<usize>,
+    /// Hashes not computable from `entries` in the order they are consumed during verification: level by level
+    /// starting from the bottom-most level of the tree, and by key within each level.
+    pub hashes: Vec<ValueHash>,
+}
+
+/// Proof that the tree contains no entries with keys in a certain inclusive range.
+///
+/// The proof consists of proofs for the range boundaries, which must be [empty](TreeEntry::is_empty()).
+/// Since there are no entries between the boundaries, the root hash can be restored from these proofs
+/// using [`TreeRangeDigest`](crate::TreeRangeDigest).
+#[derive(Debug, Clone)]
+pub struct TreeRangeAbsenceProof {
+    /// Proof for the start of the range.
+    pub start: TreeEntryWithProof,
+    /// Proof for the end of the range (inclusive). Can coincide with `start` if the range consists of a single key.
+    pub end: TreeEntryWithProof,
+}
+
 /// Output of inserting a block of entries into a Merkle tree.
 #[derive(Debug, PartialEq, Eq)]
 pub struct BlockOutput {
@@ -314,6 +314,32 @@ fn reset_tree() {
     });
 }
 
+#[test]
+fn creating_checkpoint() {
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let storage = RocksDB::new(&temp_dir.path().join("tree")).unwrap();
+    let logs = gen_storage_logs();
+    let mut tree = ZkSyncTree::new_lightweight(storage.into()).unwrap();
+    tree.process_l1_batch(&logs[..50]).unwrap();
+    let checkpoint_path = temp_dir.path().join("checkpoint");
+    let err = tree.create_checkpoint(&checkpoint_path).unwrap_err();
+    assert!(err.to_string().contains("unsaved"), "{err:#}");
+
+    tree.save().unwrap();
+    let root_hash = tree.root_hash();
+    let next_l1_batch = tree.create_checkpoint(&checkpoint_path).unwrap();
+    assert_eq!(next_l1_batch, L1BatchNumber(1));
+    // Changes after the checkpoint must not influence it.
+    tree.process_l1_batch(&logs[50..]).unwrap();
+    tree.save().unwrap();
+    drop(tree);
+
+    let storage = RocksDB::new(&checkpoint_path).unwrap();
+    let tree = ZkSyncTree::new_lightweight(storage.into()).unwrap();
+    assert_eq!(tree.next_l1_batch_number(), next_l1_batch);
+    assert_eq!(tree.root_hash(), root_hash);
+}
+
 #[test]
 fn read_logs() {
     let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
@@ -7,7 +7,7 @@ use test_casing::test_casing;
 use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
 use zksync_merkle_tree::{
     Database, HashTree, MerkleTree, PatchSet, Patched, PruneDatabase, TreeEntry, TreeInstruction,
-    TreeLogEntry, TreeRangeDigest,
+    TreeLogEntry, TreeMultiProof, TreeRangeDigest,
 };
 use zksync_types::{AccountTreeId, Address, StorageKey, H256, U256};
 
@@ -543,6 +543,139 @@ fn range_proofs_with_random_ranges() {
     }
 }
 
+#[test_casing(7, [1, 2, 3, 5, 10, 42, 100])]
+fn multi_proofs_with_existing_and_missing_keys(key_count: usize) {
+    const RNG_SEED: u64 = 123;
+
+    let mut rng = StdRng::seed_from_u64(RNG_SEED);
+    let (kvs, expected_hash) = &*ENTRIES_AND_HASH;
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    tree.extend(kvs.clone()).unwrap();
+
+    let existing_keys = kvs
+        .choose_multiple(&mut rng, key_count)
+        .map(|entry| entry.key);
+    let missing_keys = (0..key_count).map(|_| U256([rng.gen(), rng.gen(), rng.gen(), rng.gen()]));
+    let mut keys: Vec<_> = existing_keys.chain(missing_keys).collect();
+    keys.shuffle(&mut rng);
+
+    let mut proofs = tree.entries_with_proofs(0, &keys).unwrap();
+    let separate_hash_count: usize = proofs.iter().map(|proof| proof.merkle_path.len()).sum();
+    let multi_proof = tree.entries_with_multi_proof(0, &keys).unwrap();
+    assert_eq!(multi_proof, TreeMultiProof::new(proofs.clone()));
+    assert!(multi_proof.hashes.len() <= separate_hash_count);
+
+    proofs.sort_unstable_by_key(|proof| proof.base.key);
+    let expected_entries: Vec<_> = proofs.iter().map(|proof| proof.base).collect();
+    assert_eq!(multi_proof.entries, expected_entries);
+    multi_proof.verify(&Blake2Hasher, *expected_hash).unwrap();
+}
+
+#[test]
+fn multi_proof_is_more_compact_for_many_keys() {
+    let (kvs, expected_hash) = &*ENTRIES_AND_HASH;
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    tree.extend(kvs.clone()).unwrap();
+
+    let keys: Vec<_> = kvs.iter().map(|entry| entry.key).collect();
+    let proofs = tree.entries_with_proofs(0, &keys).unwrap();
+    let separate_hash_count: usize = proofs.iter().map(|proof| proof.merkle_path.len()).sum();
+    let multi_proof = TreeMultiProof::new(proofs);
+    // All entries in the tree are proven, so all hashes can be computed from the entries.
+    assert!(multi_proof.hashes.is_empty(), "{multi_proof:?}");
+    assert!(separate_hash_count > 0);
+    multi_proof.verify(&Blake2Hasher, *expected_hash).unwrap();
+}
+
+#[test]
+fn tampered_multi_proofs_are_rejected() {
+    const RNG_SEED: u64 = 456;
+
+    let mut rng = StdRng::seed_from_u64(RNG_SEED);
+    let (kvs, expected_hash) = &*ENTRIES_AND_HASH;
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    tree.extend(kvs.clone()).unwrap();
+
+    let keys: Vec<_> = kvs
+        .choose_multiple(&mut rng, 10)
+        .map(|entry| entry.key)
+        .collect();
+    let multi_proof = tree.entries_with_multi_proof(0, &keys).unwrap();
+    multi_proof.verify(&Blake2Hasher, *expected_hash).unwrap();
+    assert!(!multi_proof.hashes.is_empty());
+
+    let mut tampered = multi_proof.clone();
+    tampered.entries[3].value = H256::repeat_byte(0xfe);
+    tampered.verify(&Blake2Hasher, *expected_hash).unwrap_err();
+
+    let mut tampered = multi_proof.clone();
+    tampered.hashes[0] = H256::repeat_byte(0xfe);
+    tampered.verify(&Blake2Hasher, *expected_hash).unwrap_err();
+
+    let mut tampered = multi_proof.clone();
+    tampered.hashes.pop();
+    tampered.verify(&Blake2Hasher, *expected_hash).unwrap_err();
+
+    let mut tampered = multi_proof.clone();
+    tampered.hashes.push(H256::zero());
+    tampered.verify(&Blake2Hasher, *expected_hash).unwrap_err();
+
+    let mut tampered = multi_proof.clone();
+    tampered.entries.swap(0, 1);
+    tampered.verify(&Blake2Hasher, *expected_hash).unwrap_err();
+
+    let mut tampered = multi_proof;
+    tampered.entries.pop();
+    tampered.verify(&Blake2Hasher, *expected_hash).unwrap_err();
+}
+
+#[test]
+fn range_absence_proofs_with_random_ranges() {
+    const ITER_COUNT: usize = 100;
+    const RNG_SEED: u64 = 789;
+
+    let mut rng = StdRng::seed_from_u64(RNG_SEED);
+    let (kvs, expected_hash) = &*ENTRIES_AND_HASH;
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    tree.extend(kvs.clone()).unwrap();
+    let mut sorted_keys: Vec<_> = kvs.iter().map(|entry| entry.key).collect();
+    sorted_keys.sort_unstable();
+
+    for _ in 0..ITER_COUNT {
+        // Choose a range between two adjacent keys, which is guaranteed to be empty.
+        let idx = rng.gen_range(0..sorted_keys.len() - 1);
+        let (lower, upper) = (sorted_keys[idx], sorted_keys[idx + 1]);
+        let start_key = lower + 1 + (upper - lower - 1) / 3;
+        let end_key = upper - 1;
+
+        let proof = tree
+            .range_absence_proof(0, start_key, end_key)
+            .unwrap()
+            .expect("range is empty");
+        proof.verify(&Blake2Hasher, *expected_hash).unwrap();
+
+        // Extending the range to include an existing key must fail.
+        let proof = tree.range_absence_proof(0, start_key, upper).unwrap();
+        assert!(proof.is_none());
+        let proof = tree.range_absence_proof(0, lower - 1, end_key).unwrap();
+        assert!(proof.is_none());
+    }
+
+    // A range proof with a boundary substituted must not verify.
+    let (lower, upper) = (sorted_keys[0], sorted_keys[1]);
+    let mut proof = tree
+        .range_absence_proof(0, lower + 1, upper - 1)
+        .unwrap()
+        .unwrap();
+    let [other_end] = tree
+        .entries_with_proofs(0, &[sorted_keys[2] - 1])
+        .unwrap()
+        .try_into()
+        .unwrap();
+    proof.end = other_end;
+    proof.verify(&Blake2Hasher, *expected_hash).unwrap_err();
+}
+
 /// RocksDB-specific tests.
 mod rocksdb {
     use std::collections::BTreeMap;
@@ -0,0 +1,94 @@
+use anyhow::Context as _;
+use zksync_types::{
+    writes::{decompress_state_diffs, CompressedStateDiff},
+    Address, U256,
+};
+
+use crate::interface::pubdata::L1MessengerL2ToL1Log;
+
+/// Size of a packed user L2->L1 log in pubdata.
+const PACKED_L2_TO_L1_LOG_SIZE: usize = 88;
+
+/// Pubdata published on the settlement layer, decoded from the output of [`FullPubdataBuilder`](super::FullPubdataBuilder).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedPubdata {
+    pub user_logs: Vec<L1MessengerL2ToL1Log>,
+    pub l2_to_l1_messages: Vec<Vec<u8>>,
+    pub published_bytecodes: Vec<Vec<u8>>,
+    pub state_diffs: Vec<CompressedStateDiff>,
+}
+
+/// Cursor over the pubdata bytes.
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn read(&mut self, len: usize, what: &str) -> anyhow::Result<&'a [u8]> {
+        let bytes = self
+            .data
+            .get(self.offset..self.offset + len)
+            .with_context(|| format!("{what} is truncated at offset {}", self.offset))?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn read_u32(&mut self, what: &str) -> anyhow::Result<usize> {
+        let bytes = self.read(4, what)?;
+        Ok(u32::from_be_bytes(bytes.try_into().unwrap()) as usize)
+    }
+
+    fn read_byte_arrays(&mut self, what: &str) -> anyhow::Result<Vec<Vec<u8>>> {
+        let count = self.read_u32(what)?;
+        (0..count)
+            .map(|_| {
+                let len = self.read_u32(what)?;
+                Ok(self.read(len, what)?.to_vec())
+            })
+            .collect()
+    }
+}
+
+fn decode_user_log(bytes: &[u8]) -> L1MessengerL2ToL1Log {
+    L1MessengerL2ToL1Log {
+        l2_shard_id: bytes[0],
+        is_service: bytes[1] != 0,
+        tx_number_in_block: u16::from_be_bytes([bytes[2], bytes[3]]),
+        sender: Address::from_slice(&bytes[4..24]),
+        key: U256::from_big_endian(&bytes[24..56]),
+        value: U256::from_big_endian(&bytes[56..88]),
+    }
+}
+
+/// Decodes pubdata published on the settlement layer by post-gateway protocol versions
+/// (i.e., [`PubdataBuilder::settlement_layer_pubdata()`](crate::interface::pubdata::PubdataBuilder::settlement_layer_pubdata())
+/// for the full pubdata builder). Returns the decoded pubdata and its length; trailing bytes
+/// (e.g., the zero padding of blobs) are ignored.
+pub fn decode_settlement_layer_pubdata(pubdata: &[u8]) -> anyhow::Result<(DecodedPubdata, usize)> {
+    let mut reader = Reader {
+        data: pubdata,
+        offset: 0,
+    };
+
+    let user_logs_count = reader.read_u32("user logs")?;
+    let user_logs = (0..user_logs_count)
+        .map(|_| {
+            Ok(decode_user_log(
+                reader.read(PACKED_L2_TO_L1_LOG_SIZE, "user log")?,
+            ))
+        })
+        .collect::<anyhow::Result<_>>()?;
+    let l2_to_l1_messages = reader.read_byte_arrays("L2->L1 messages")?;
+    let published_bytecodes = reader.read_byte_arrays("published bytecodes")?;
+    let (state_diffs, state_diffs_len) = decompress_state_diffs(&pubdata[reader.offset..])
+        .context("failed decoding compressed state diffs")?;
+
+    let pubdata = DecodedPubdata {
+        user_logs,
+        l2_to_l1_messages,
+        published_bytecodes,
+        state_diffs,
+    };
+    Ok((pubdata, reader.offset + state_diffs_len))
+}
@@ -1,5 +1,6 @@
 use std::rc::Rc;
 
+pub use decoder::{decode_settlement_layer_pubdata, DecodedPubdata};
 pub use full_builder::FullPubdataBuilder;
 pub use hashed_builder::HashedPubdataBuilder;
 use zksync_types::{
@@ -9,6 +10,7 @@ use zksync_types::{
 
 use crate::interface::pubdata::PubdataBuilder;
 
+mod decoder;
 mod full_builder;
 mod hashed_builder;
 #[cfg(test)]
@@ -1,11 +1,14 @@
 use zksync_types::{
     commitment::{L2DACommitmentScheme, L2PubdataValidator},
     u256_to_h256,
-    writes::StateDiffRecord,
+    writes::{CompressedStateDiffKey, StateDiffRecord},
     Address, ProtocolVersionId, ACCOUNT_CODE_STORAGE_ADDRESS, BOOTLOADER_ADDRESS,
 };
 
-use super::{full_builder::FullPubdataBuilder, hashed_builder::HashedPubdataBuilder};
+use super::{
+    decode_settlement_layer_pubdata, full_builder::FullPubdataBuilder,
+    hashed_builder::HashedPubdataBuilder,
+};
 use crate::interface::pubdata::{L1MessengerL2ToL1Log, PubdataBuilder, PubdataInput};
 
 fn mock_input() -> PubdataInput {
@@ -112,6 +115,37 @@ fn test_full_pubdata_building() {
     );
 }
 
+#[test]
+fn decoding_settlement_layer_pubdata() {
+    let input = mock_input();
+    let full_pubdata_builder = FullPubdataBuilder::new(L2PubdataValidator::CommitmentScheme(
+        L2DACommitmentScheme::BlobsAndPubdataKeccak256,
+    ));
+    let mut pubdata =
+        full_pubdata_builder.settlement_layer_pubdata(&input, ProtocolVersionId::Version31);
+    let pubdata_len = pubdata.len();
+    pubdata.resize(pubdata_len + 100, 0); // emulate blob padding
+
+    let (decoded, len) = decode_settlement_layer_pubdata(&pubdata).unwrap();
+    assert_eq!(len, pubdata_len);
+    assert_eq!(decoded.user_logs, input.user_logs);
+    assert_eq!(decoded.l2_to_l1_messages, input.l2_to_l1_messages);
+    assert_eq!(decoded.published_bytecodes, input.published_bytecodes);
+
+    let keys: Vec<_> = decoded.state_diffs.iter().map(|diff| diff.key).collect();
+    assert_eq!(
+        keys,
+        [
+            CompressedStateDiffKey::Initial(u256_to_h256(126.into())),
+            CompressedStateDiffKey::Repeated(12),
+        ]
+    );
+    assert_eq!(decoded.state_diffs[0].value.apply(0.into()), 14.into());
+    assert_eq!(decoded.state_diffs[1].value.apply(11.into()), 12.into());
+
+    decode_settlement_layer_pubdata(&pubdata[..pubdata_len - 1]).unwrap_err();
+}
+
 #[test]
 fn test_hashed_pubdata_building() {
     let input = mock_input();
@@ -0,0 +1,435 @@
+//! Azure Blob Storage-based [`ObjectStore`] implementation.
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use azure_core::{error::ErrorKind, RetryOptions, StatusCode};
+use azure_storage::{CloudLocation, StorageCredentials};
+use azure_storage_blobs::prelude::{
+    BlobBlockType, BlockId, BlockList, ClientBuilder, ContainerClient,
+};
+use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
+
+use crate::raw::{Bucket, ObjectStore, ObjectStoreError, ObjectStream};
+
+/// Size of a block in streamed uploads.
+const BLOCK_SIZE: usize = 8 << 20;
+/// Size of a chunk requested from Azure in streamed downloads.
+const STREAM_CHUNK_SIZE: u64 = 4 << 20;
+
+/// [`ObjectStore`] implementation based on Azure Blob Storage.
+pub struct AzureBlobStore {
+    endpoint: String,
+    container: String,
+    client: ContainerClient,
+}
+
+impl fmt::Debug for AzureBlobStore {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("AzureBlobStore")
+            .field("endpoint", &self.endpoint)
+            .field("container", &self.container)
+            // Skip `client` as its representation may contain sensitive info
+            .finish_non_exhaustive()
+    }
+}
+
+/// Authentication mode for [`AzureBlobStore`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum AzureBlobStoreAuthMode {
+    /// Authentication via a storage account access key read from the file at the specified path.
+    SharedKeyFile(PathBuf),
+    /// Authentication via a shared access signature (SAS) token read from the file at the specified path.
+    SasTokenFile(PathBuf),
+    /// Ambient authentication via the default Azure credential chain (works via managed identity
+    /// if the binary runs on Azure).
+    ManagedIdentity,
+}
+
+impl AzureBlobStore {
+    /// Creates a new Azure Blob store.
+    ///
+    /// If `endpoint` is not specified, the public Azure Blob Storage endpoint for the account is used.
+    /// Specifying the endpoint allows using a local Azurite emulator, e.g. `http://127.0.0.1:10000/devstoreaccount1`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if credentials cannot be loaded.
+    pub async fn new(
+        auth_mode: AzureBlobStoreAuthMode,
+        account: String,
+        container: String,
+        endpoint: Option<String>,
+    ) -> Result<Self, ObjectStoreError> {
+        let credentials = Self::get_credentials(auth_mode, &account).await?;
+        let (location, endpoint) = if let Some(endpoint) = endpoint {
+            tracing::info!(%endpoint, "using Azure Blob endpoint defined in storage config");
+            let location = CloudLocation::Custom {
+                account,
+                uri: endpoint.clone(),
+            };
+            (location, endpoint)
+        } else {
+            let endpoint = format!("https://{account}.blob.core.windows.net");
+            (CloudLocation::Public { account }, endpoint)
+        };
+
+        // Retries are handled by `StoreWithRetries`.
+        let client = ClientBuilder::with_location(location, credentials)
+            .retry(RetryOptions::none())
+            .container_client(container.clone());
+        Ok(Self {
+            endpoint,
+            container,
+            client,
+        })
+    }
+
+    async fn get_credentials(
+        auth_mode: AzureBlobStoreAuthMode,
+        account: &str,
+    ) -> Result<StorageCredentials, ObjectStoreError> {
+        Ok(match auth_mode {
+            AzureBlobStoreAuthMode::SharedKeyFile(path) => {
+                let key = Self::read_secret(&path).await?;
+                StorageCredentials::access_key(account.to_owned(), key)
+            }
+            AzureBlobStoreAuthMode::SasTokenFile(path) => {
+                let token = Self::read_secret(&path).await?;
+                StorageCredentials::sas_token(token).context("invalid SAS token")?
+            }
+            AzureBlobStoreAuthMode::ManagedIdentity => {
+                let credential = azure_identity::create_credential()
+                    .context("failed creating Azure credential")?;
+                StorageCredentials::token_credential(credential)
+            }
+        })
+    }
+
+    async fn read_secret(path: &Path) -> anyhow::Result<String> {
+        let secret = tokio::fs::read_to_string(path)
+            .await
+            .with_context(|| format!("failed reading `{}`", path.display()))?;
+        Ok(secret.trim().to_owned())
+    }
+
+    fn filename(bucket: &str, filename: &str) -> String {
+        format!("{bucket}/{filename}")
+    }
+
+    /// Reads the next block of a streamed upload. Returns the block data and a flag whether the stream has ended.
+    async fn read_block(value: &mut ObjectStream) -> Result<(Vec<u8>, bool), ObjectStoreError> {
+        let mut block = Vec::with_capacity(BLOCK_SIZE);
+        while block.len() < BLOCK_SIZE {
+            let Some(chunk) = value.try_next().await? else {
+                return Ok((block, true));
+            };
+            block.extend_from_slice(&chunk);
+        }
+        Ok((block, false))
+    }
+}
+
+impl From<azure_core::Error> for ObjectStoreError {
+    fn from(err: azure_core::Error) -> Self {
+        match err.kind().clone() {
+            ErrorKind::HttpResponse {
+                status: StatusCode::NotFound,
+                ..
+            } => ObjectStoreError::KeyNotFound(err.into()),
+            ErrorKind::HttpResponse {
+                status: StatusCode::Unauthorized | StatusCode::Forbidden,
+                ..
+            } => ObjectStoreError::Initialization {
+                source: err.into(),
+                is_retriable: false,
+            },
+            // Credentials are usually obtained via HTTP requests (e.g., to the instance metadata service),
+            // which may fail transiently.
+            ErrorKind::Credential => ObjectStoreError::Initialization {
+                source: err.into(),
+                is_retriable: true,
+            },
+            kind => {
+                let is_retriable = match kind {
+                    ErrorKind::HttpResponse { status, .. } => matches!(
+                        status,
+                        StatusCode::RequestTimeout
+                            | StatusCode::TooManyRequests
+                            | StatusCode::InternalServerError
+                            | StatusCode::BadGateway
+                            | StatusCode::ServiceUnavailable
+                            | StatusCode::GatewayTimeout
+                    ),
+                    // We treat any I/O errors as retriable, same as for other stores.
+                    ErrorKind::Io => true,
+                    _ => false,
+                };
+                ObjectStoreError::Other {
+                    source: err.into(),
+                    is_retriable,
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for AzureBlobStore {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let filename = Self::filename(bucket.as_str(), key);
+        tracing::trace!(
+            "Fetching data from Azure for key {filename} from container {}",
+            self.container
+        );
+        let content = self.client.blob_client(filename).get_content().await?;
+        Ok(content)
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        let filename = Self::filename(bucket.as_str(), key);
+        tracing::trace!(
+            "Storing data to Azure for key {filename} from container {}",
+            self.container
+        );
+        self.client
+            .blob_client(filename)
+            .put_block_blob(Bytes::from(value))
+            .await?;
+        Ok(())
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        let filename = Self::filename(bucket.as_str(), key);
+        tracing::trace!(
+            "Removing data from Azure for key {filename} from container {}",
+            self.container
+        );
+        self.client.blob_client(filename).delete().await?;
+        Ok(())
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        let bucket_prefix = Self::filename(bucket.as_str(), "");
+        tracing::trace!(
+            "Listing Azure blobs with prefix {bucket_prefix}{prefix} in container {}",
+            self.container
+        );
+
+        let mut keys = vec![];
+        let mut pages = self
+            .client
+            .list_blobs()
+            .prefix(format!("{bucket_prefix}{prefix}"))
+            .into_stream();
+        while let Some(page) = pages.try_next().await? {
+            keys.extend(
+                page.blobs
+                    .blobs()
+                    .filter_map(|blob| blob.name.strip_prefix(&bucket_prefix).map(str::to_owned)),
+            );
+        }
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
+    async fn get_stream_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+    ) -> Result<ObjectStream, ObjectStoreError> {
+        let filename = Self::filename(bucket.as_str(), key);
+        tracing::trace!(
+            "Streaming data from Azure for key {filename} from container {}",
+            self.container
+        );
+
+        let mut responses = self
+            .client
+            .blob_client(filename)
+            .get()
+            .chunk_size(STREAM_CHUNK_SIZE)
+            .into_stream();
+        // Await the first response eagerly, so that a missing object is reported by this method
+        // rather than by the returned stream.
+        let first_response = responses.try_next().await?;
+        let responses = futures::stream::iter(first_response.map(Ok)).chain(responses);
+        let chunks = responses
+            .map_err(ObjectStoreError::from)
+            .and_then(|response| async move { Ok(response.data.collect().await?) });
+        Ok(chunks.boxed())
+    }
+
+    async fn put_stream_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        mut value: ObjectStream,
+    ) -> Result<(), ObjectStoreError> {
+        let (mut block, mut is_last) = Self::read_block(&mut value).await?;
+        if is_last {
+            // The object is small enough to be uploaded in a single request.
+            return self.put_raw(bucket, key, block).await;
+        }
+
+        let filename = Self::filename(bucket.as_str(), key);
+        tracing::trace!(
+            "Streaming data to Azure for key {filename} from container {}",
+            self.container
+        );
+        let blob_client = self.client.blob_client(filename);
+        let mut block_list = BlockList { blocks: vec![] };
+        let mut block_index = 0_u64;
+        while !block.is_empty() {
+            // All block IDs within a blob must have the same length.
+            let block_id = BlockId::new(format!("{block_index:016x}"));
+            blob_client
+                .put_block(block_id.clone(), Bytes::from(block))
+                .await?;
+            block_list
+                .blocks
+                .push(BlobBlockType::new_uncommitted(block_id));
+            block_index += 1;
+
+            if is_last {
+                break;
+            }
+            (block, is_last) = Self::read_block(&mut value).await?;
+        }
+        // Uncommitted blocks are garbage-collected by Azure if the upload fails, so there's no need to abort it explicitly.
+        blob_client.put_block_list(block_list).await?;
+        Ok(())
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        format!("{}/{}/{}", self.endpoint, self.container, bucket.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    fn http_error(status: StatusCode) -> azure_core::Error {
+        azure_core::Error::message(
+            ErrorKind::HttpResponse {
+                status,
+                error_code: None,
+            },
+            "test error",
+        )
+    }
+
+    #[test]
+    fn converting_errors() {
+        let err = ObjectStoreError::from(http_error(StatusCode::NotFound));
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+
+        let err = ObjectStoreError::from(http_error(StatusCode::Forbidden));
+        assert_matches!(
+            err,
+            ObjectStoreError::Initialization {
+                is_retriable: false,
+                ..
+            }
+        );
+
+        let err = ObjectStoreError::from(http_error(StatusCode::ServiceUnavailable));
+        assert!(err.is_retriable());
+        let err = ObjectStoreError::from(http_error(StatusCode::BadRequest));
+        assert!(!err.is_retriable());
+
+        let err = ObjectStoreError::from(azure_core::Error::message(ErrorKind::Io, "reset"));
+        assert!(err.is_retriable());
+    }
+
+    /// Well-known Azurite development key for the `devstoreaccount1` account.
+    const AZURITE_ACCOUNT: &str = "devstoreaccount1";
+    const AZURITE_KEY: &str =
+        "Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFTBwkJ9Ubo7Q27eIAxA==";
+    const AZURITE_CONTAINER: &str = "zksync-object-store-tests";
+
+    async fn azurite_store() -> AzureBlobStore {
+        let endpoint = std::env::var("AZURITE_ENDPOINT")
+            .unwrap_or_else(|_| format!("http://127.0.0.1:10000/{AZURITE_ACCOUNT}"));
+        let credentials = StorageCredentials::access_key(AZURITE_ACCOUNT, AZURITE_KEY.to_owned());
+        let location = CloudLocation::Custom {
+            account: AZURITE_ACCOUNT.to_owned(),
+            uri: endpoint.clone(),
+        };
+        let container =
+            ClientBuilder::with_location(location, credentials).container_client(AZURITE_CONTAINER);
+        if let Err(err) = container.create().await {
+            // The container may be created by a previous test run.
+            assert_matches!(
+                err.kind(),
+                ErrorKind::HttpResponse {
+                    status: StatusCode::Conflict,
+                    ..
+                },
+                "{err}"
+            );
+        }
+
+        let key_file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(key_file.path(), AZURITE_KEY).unwrap();
+        AzureBlobStore::new(
+            AzureBlobStoreAuthMode::SharedKeyFile(key_file.path().to_owned()),
+            AZURITE_ACCOUNT.to_owned(),
+            AZURITE_CONTAINER.to_owned(),
+            Some(endpoint),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running Azurite instance; see README"]
+    async fn azurite_roundtrip() {
+        let store = azurite_store().await;
+        let bucket = Bucket::StorageSnapshot;
+        let prefix = format!("azurite_roundtrip_{}/", rand::random::<u64>());
+        let keys = [format!("{prefix}1.bin"), format!("{prefix}2.bin")];
+
+        for (i, key) in keys.iter().enumerate() {
+            store
+                .put_raw(bucket, key, vec![i as u8; 100])
+                .await
+                .unwrap();
+        }
+        for (i, key) in keys.iter().enumerate() {
+            let value = store.get_raw(bucket, key).await.unwrap();
+            assert_eq!(value, [i as u8; 100]);
+        }
+        let listed = store.list_raw(bucket, &prefix).await.unwrap();
+        assert_eq!(listed, keys);
+
+        store.remove_raw(bucket, &keys[0]).await.unwrap();
+        let err = store.get_raw(bucket, &keys[0]).await.unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+        let listed = store.list_raw(bucket, &prefix).await.unwrap();
+        assert_eq!(listed, keys[1..]);
+
+        store.remove_raw(bucket, &keys[1]).await.unwrap();
+        let listed = store.list_raw(bucket, &prefix).await.unwrap();
+        assert!(listed.is_empty(), "{listed:?}");
+    }
+}
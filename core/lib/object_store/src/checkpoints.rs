@@ -0,0 +1,610 @@
+//! Backup and restore of RocksDB checkpoints using an object store.
+
+use std::{
+    fmt,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+
+use anyhow::Context as _;
+use bytes::Bytes;
+use futures::{stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    fs,
+    io::{AsyncReadExt, AsyncWriteExt},
+};
+use zksync_types::L1BatchNumber;
+
+use crate::{
+    objects::StoredObject,
+    raw::{BoxedError, Bucket, ObjectStore, ObjectStoreError, ObjectStream},
+};
+
+/// Size of chunks in which checkpoint files are read when uploading.
+const UPLOAD_CHUNK_SIZE: usize = 1 << 20;
+
+/// Kind of a RocksDB instance that checkpoints are created for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RocksdbCheckpointKind {
+    /// Merkle tree RocksDB.
+    MerkleTree,
+    /// State keeper cache RocksDB.
+    StateKeeperCache,
+}
+
+impl RocksdbCheckpointKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::MerkleTree => "merkle_tree",
+            Self::StateKeeperCache => "state_keeper_cache",
+        }
+    }
+
+    fn key_prefix(self) -> String {
+        format!("{}_checkpoint_l1_batch_", self.as_str())
+    }
+
+    fn checkpoint_prefix(self, next_l1_batch_number: L1BatchNumber) -> String {
+        format!("{}{:010}", self.key_prefix(), next_l1_batch_number.0)
+    }
+}
+
+impl fmt::Display for RocksdbCheckpointKind {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
+/// Information about a single file in a [checkpoint](RocksdbCheckpointManifest).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RocksdbCheckpointFile {
+    /// File name relative to the checkpoint directory.
+    pub name: String,
+    /// File size in bytes.
+    pub size: u64,
+}
+
+/// Manifest of a RocksDB checkpoint stored in the object store. The manifest is uploaded after all checkpoint files,
+/// so a checkpoint is considered complete iff its manifest is present.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RocksdbCheckpointManifest {
+    /// Number of the next L1 batch to be processed by the RocksDB instance; i.e., the checkpoint contains data
+    /// for all L1 batches preceding this one.
+    pub next_l1_batch_number: L1BatchNumber,
+    /// Files constituting the checkpoint.
+    pub files: Vec<RocksdbCheckpointFile>,
+}
+
+impl StoredObject for RocksdbCheckpointManifest {
+    const BUCKET: Bucket = Bucket::StorageCheckpoints;
+    type Key<'a> = (RocksdbCheckpointKind, L1BatchNumber);
+
+    fn encode_key((kind, next_l1_batch_number): Self::Key<'_>) -> String {
+        format!("{}.json", kind.checkpoint_prefix(next_l1_batch_number))
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, BoxedError> {
+        serde_json::to_vec(self).map_err(From::from)
+    }
+
+    fn deserialize(bytes: Vec<u8>) -> Result<Self, BoxedError> {
+        serde_json::from_slice(&bytes).map_err(From::from)
+    }
+}
+
+/// Store of RocksDB checkpoints of a certain [kind](RocksdbCheckpointKind) on top of an [`ObjectStore`].
+/// Checkpoints are identified by the next L1 batch number of the checkpointed RocksDB instance.
+#[derive(Debug, Clone)]
+pub struct RocksdbCheckpointStore {
+    store: Arc<dyn ObjectStore>,
+    kind: RocksdbCheckpointKind,
+}
+
+impl RocksdbCheckpointStore {
+    /// Creates a new checkpoint store.
+    pub fn new(store: Arc<dyn ObjectStore>, kind: RocksdbCheckpointKind) -> Self {
+        Self { store, kind }
+    }
+
+    /// Returns the kind of checkpoints in this store.
+    pub fn kind(&self) -> RocksdbCheckpointKind {
+        self.kind
+    }
+
+    fn file_key(&self, next_l1_batch_number: L1BatchNumber, file_name: &str) -> String {
+        format!(
+            "{}_file_{file_name}",
+            self.kind.checkpoint_prefix(next_l1_batch_number)
+        )
+    }
+
+    /// Parses the L1 batch number from a key in the store and returns whether the key corresponds to a manifest.
+    fn parse_key(&self, key: &str) -> Option<(L1BatchNumber, bool)> {
+        let suffix = key.strip_prefix(&self.kind.key_prefix())?;
+        let (number, rest) = suffix.split_at_checked(10)?;
+        let number = number.parse().ok()?;
+        let is_manifest = rest == ".json";
+        (is_manifest || rest.starts_with("_file_")).then_some((L1BatchNumber(number), is_manifest))
+    }
+
+    /// Lists next L1 batch numbers of all complete checkpoints in the store in the ascending order.
+    ///
+    /// # Errors
+    ///
+    /// Propagates object store errors.
+    pub async fn list(&self) -> anyhow::Result<Vec<L1BatchNumber>> {
+        let keys = self
+            .store
+            .list_raw(Bucket::StorageCheckpoints, &self.kind.key_prefix())
+            .await
+            .with_context(|| format!("failed listing {} checkpoints", self.kind))?;
+        let mut numbers: Vec<_> = keys
+            .iter()
+            .filter_map(|key| {
+                let (number, is_manifest) = self.parse_key(key)?;
+                is_manifest.then_some(number)
+            })
+            .collect();
+        numbers.sort_unstable();
+        Ok(numbers)
+    }
+
+    /// Returns the manifest of the checkpoint with the greatest next L1 batch number, or `None` if the store
+    /// has no complete checkpoints.
+    ///
+    /// # Errors
+    ///
+    /// Propagates object store errors.
+    pub async fn latest(&self) -> anyhow::Result<Option<RocksdbCheckpointManifest>> {
+        let Some(&latest) = self.list().await?.last() else {
+            return Ok(None);
+        };
+        self.manifest(latest).await.map(Some)
+    }
+
+    /// Returns the greatest next L1 batch number of a complete checkpoint within the specified range, or `None`
+    /// if there are no such checkpoints.
+    ///
+    /// # Errors
+    ///
+    /// Propagates object store errors.
+    pub async fn latest_in_range(
+        &self,
+        next_l1_batch_numbers: RangeInclusive<L1BatchNumber>,
+    ) -> anyhow::Result<Option<L1BatchNumber>> {
+        let checkpoints = self.list().await?;
+        Ok(checkpoints
+            .into_iter()
+            .rev()
+            .find(|number| next_l1_batch_numbers.contains(number)))
+    }
+
+    /// Loads the manifest of the checkpoint with the specified next L1 batch number.
+    ///
+    /// # Errors
+    ///
+    /// Propagates object store errors, including if the checkpoint doesn't exist.
+    pub async fn manifest(
+        &self,
+        next_l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<RocksdbCheckpointManifest> {
+        let manifest: RocksdbCheckpointManifest = self
+            .store
+            .get((self.kind, next_l1_batch_number))
+            .await
+            .with_context(|| {
+                format!(
+                    "failed getting manifest for {} checkpoint at L1 batch #{next_l1_batch_number}",
+                    self.kind
+                )
+            })?;
+        anyhow::ensure!(
+            manifest.next_l1_batch_number == next_l1_batch_number,
+            "manifest for {} checkpoint at L1 batch #{next_l1_batch_number} has unexpected L1 batch #{}",
+            self.kind,
+            manifest.next_l1_batch_number
+        );
+        Ok(manifest)
+    }
+
+    /// Uploads a checkpoint from the specified local directory. The directory must be flat, which is the case
+    /// for checkpoints created by RocksDB. The manifest is uploaded last, so that a partially uploaded checkpoint
+    /// is never observable.
+    ///
+    /// # Errors
+    ///
+    /// Propagates I/O and object store errors.
+    pub async fn upload(
+        &self,
+        next_l1_batch_number: L1BatchNumber,
+        checkpoint_dir: &Path,
+    ) -> anyhow::Result<RocksdbCheckpointManifest> {
+        let started_at = Instant::now();
+        let files = list_checkpoint_files(checkpoint_dir).await?;
+        for file in &files {
+            let path = checkpoint_dir.join(&file.name);
+            let file_handle = fs::File::open(&path)
+                .await
+                .with_context(|| format!("failed opening checkpoint file `{}`", path.display()))?;
+            let key = self.file_key(next_l1_batch_number, &file.name);
+            self.store
+                .put_stream_raw(Bucket::StorageCheckpoints, &key, file_stream(file_handle))
+                .await
+                .with_context(|| format!("failed uploading checkpoint file `{key}`"))?;
+        }
+
+        let manifest = RocksdbCheckpointManifest {
+            next_l1_batch_number,
+            files,
+        };
+        self.store
+            .put((self.kind, next_l1_batch_number), &manifest)
+            .await
+            .context("failed uploading checkpoint manifest")?;
+        let total_size: u64 = manifest.files.iter().map(|file| file.size).sum();
+        tracing::info!(
+            "Uploaded {} checkpoint at L1 batch #{next_l1_batch_number} ({} files, {total_size} bytes) in {:?}",
+            self.kind,
+            manifest.files.len(),
+            started_at.elapsed()
+        );
+        Ok(manifest)
+    }
+
+    /// Downloads the checkpoint with the specified next L1 batch number to the `target_dir`, which must not exist.
+    /// Files are downloaded to a temporary sibling directory, which is renamed to `target_dir` once all files
+    /// are downloaded and their sizes are checked against the manifest.
+    ///
+    /// # Errors
+    ///
+    /// Propagates I/O and object store errors. Errors if the downloaded files don't match the manifest.
+    pub async fn download(
+        &self,
+        next_l1_batch_number: L1BatchNumber,
+        target_dir: &Path,
+    ) -> anyhow::Result<RocksdbCheckpointManifest> {
+        anyhow::ensure!(
+            !fs::try_exists(target_dir).await?,
+            "target directory `{}` for checkpoint already exists",
+            target_dir.display()
+        );
+        let started_at = Instant::now();
+        let manifest = self.manifest(next_l1_batch_number).await?;
+
+        let mut download_dir = target_dir.as_os_str().to_owned();
+        download_dir.push(".download");
+        let download_dir = PathBuf::from(download_dir);
+        if fs::try_exists(&download_dir).await? {
+            tracing::info!(
+                "Removing leftover checkpoint download directory `{}`",
+                download_dir.display()
+            );
+            fs::remove_dir_all(&download_dir).await?;
+        }
+        fs::create_dir_all(&download_dir).await.with_context(|| {
+            format!(
+                "failed creating checkpoint download directory `{}`",
+                download_dir.display()
+            )
+        })?;
+
+        for file in &manifest.files {
+            self.download_file(next_l1_batch_number, file, &download_dir)
+                .await?;
+        }
+        fs::rename(&download_dir, target_dir)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed moving downloaded checkpoint to `{}`",
+                    target_dir.display()
+                )
+            })?;
+
+        let total_size: u64 = manifest.files.iter().map(|file| file.size).sum();
+        tracing::info!(
+            "Downloaded {} checkpoint at L1 batch #{next_l1_batch_number} ({} files, {total_size} bytes) to `{}` in {:?}",
+            self.kind,
+            manifest.files.len(),
+            target_dir.display(),
+            started_at.elapsed()
+        );
+        Ok(manifest)
+    }
+
+    async fn download_file(
+        &self,
+        next_l1_batch_number: L1BatchNumber,
+        file: &RocksdbCheckpointFile,
+        download_dir: &Path,
+    ) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            !file.name.contains(['/', '\\']) && file.name != "..",
+            "invalid checkpoint file name: {:?}",
+            file.name
+        );
+        let key = self.file_key(next_l1_batch_number, &file.name);
+        let mut chunks = self
+            .store
+            .get_stream_raw(Bucket::StorageCheckpoints, &key)
+            .await
+            .with_context(|| format!("failed getting checkpoint file `{key}`"))?;
+
+        let path = download_dir.join(&file.name);
+        let mut local_file = fs::File::create(&path)
+            .await
+            .with_context(|| format!("failed creating file `{}`", path.display()))?;
+        let mut size = 0_u64;
+        while let Some(chunk) = chunks
+            .try_next()
+            .await
+            .with_context(|| format!("failed downloading checkpoint file `{key}`"))?
+        {
+            size += chunk.len() as u64;
+            local_file.write_all(&chunk).await?;
+        }
+        local_file.sync_all().await?;
+        anyhow::ensure!(
+            size == file.size,
+            "size of checkpoint file `{key}` ({size} bytes) differs from the manifest ({} bytes)",
+            file.size
+        );
+        Ok(())
+    }
+
+    /// Removes all checkpoints except for `retained_count` most recent ones. Incomplete checkpoints older
+    /// than the retained ones are removed as well. Returns the number of removed checkpoints.
+    ///
+    /// # Errors
+    ///
+    /// Propagates object store errors.
+    pub async fn remove_old(&self, retained_count: usize) -> anyhow::Result<usize> {
+        let checkpoints = self.list().await?;
+        let Some(removed_count) = checkpoints.len().checked_sub(retained_count) else {
+            return Ok(0);
+        };
+        let Some(&oldest_retained) = checkpoints.get(removed_count) else {
+            // `retained_count` is zero; we don't want to remove all checkpoints.
+            return Ok(0);
+        };
+
+        let keys = self
+            .store
+            .list_raw(Bucket::StorageCheckpoints, &self.kind.key_prefix())
+            .await
+            .with_context(|| format!("failed listing {} checkpoints", self.kind))?;
+        let mut removed_keys: Vec<_> = keys
+            .into_iter()
+            .filter_map(|key| {
+                let (number, is_manifest) = self.parse_key(&key)?;
+                (number < oldest_retained).then_some((is_manifest, key))
+            })
+            .collect();
+        // Remove manifests first, so that checkpoints are never observable in a partially removed state.
+        removed_keys.sort_unstable_by_key(|(is_manifest, _)| !*is_manifest);
+        for (_, key) in &removed_keys {
+            self.store
+                .remove_raw(Bucket::StorageCheckpoints, key)
+                .await
+                .with_context(|| format!("failed removing `{key}`"))?;
+        }
+
+        if removed_count > 0 {
+            tracing::info!(
+                "Removed {removed_count} {} checkpoints older than L1 batch #{oldest_retained}",
+                self.kind
+            );
+        }
+        Ok(removed_count)
+    }
+}
+
+async fn list_checkpoint_files(
+    checkpoint_dir: &Path,
+) -> anyhow::Result<Vec<RocksdbCheckpointFile>> {
+    let mut entries = fs::read_dir(checkpoint_dir).await.with_context(|| {
+        format!(
+            "failed reading checkpoint directory `{}`",
+            checkpoint_dir.display()
+        )
+    })?;
+    let mut files = vec![];
+    while let Some(entry) = entries.next_entry().await? {
+        let metadata = entry.metadata().await?;
+        let name = entry.file_name().into_string().map_err(|name| {
+            anyhow::anyhow!("checkpoint contains file with non-UTF8 name {name:?}")
+        })?;
+        anyhow::ensure!(
+            metadata.is_file(),
+            "checkpoint contains non-file entry `{name}`"
+        );
+        files.push(RocksdbCheckpointFile {
+            name,
+            size: metadata.len(),
+        });
+    }
+    anyhow::ensure!(
+        !files.is_empty(),
+        "checkpoint directory `{}` is empty",
+        checkpoint_dir.display()
+    );
+    files.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+    Ok(files)
+}
+
+fn file_stream(file: fs::File) -> ObjectStream {
+    let chunks = stream::try_unfold(file, |mut file| async move {
+        let mut chunk = vec![0; UPLOAD_CHUNK_SIZE];
+        let chunk_len = file.read(&mut chunk).await?;
+        if chunk_len == 0 {
+            return Ok::<_, ObjectStoreError>(None);
+        }
+        chunk.truncate(chunk_len);
+        Ok(Some((Bytes::from(chunk), file)))
+    });
+    chunks.boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::MockObjectStore;
+
+    async fn create_checkpoint_dir(dir: &Path, seed: u8) {
+        fs::create_dir(dir).await.unwrap();
+        fs::write(dir.join("CURRENT"), b"MANIFEST-000001\n")
+            .await
+            .unwrap();
+        fs::write(
+            dir.join("000001.sst"),
+            vec![seed; 3 * UPLOAD_CHUNK_SIZE / 2],
+        )
+        .await
+        .unwrap();
+        fs::write(dir.join("MANIFEST-000001"), [seed; 32])
+            .await
+            .unwrap();
+    }
+
+    async fn assert_dirs_eq(expected: &Path, actual: &Path) {
+        let expected_files = list_checkpoint_files(expected).await.unwrap();
+        let actual_files = list_checkpoint_files(actual).await.unwrap();
+        assert_eq!(expected_files, actual_files);
+        for file in &expected_files {
+            let expected = fs::read(expected.join(&file.name)).await.unwrap();
+            let actual = fs::read(actual.join(&file.name)).await.unwrap();
+            assert_eq!(expected, actual, "{file:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn uploading_and_downloading_checkpoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let checkpoint_dir = temp_dir.path().join("checkpoint");
+        create_checkpoint_dir(&checkpoint_dir, 1).await;
+
+        let store =
+            RocksdbCheckpointStore::new(MockObjectStore::arc(), RocksdbCheckpointKind::MerkleTree);
+        assert_eq!(store.latest().await.unwrap(), None);
+        let manifest = store
+            .upload(L1BatchNumber(42), &checkpoint_dir)
+            .await
+            .unwrap();
+        assert_eq!(manifest.next_l1_batch_number, L1BatchNumber(42));
+        assert_eq!(manifest.files.len(), 3);
+        assert_eq!(store.list().await.unwrap(), [L1BatchNumber(42)]);
+        assert_eq!(store.latest().await.unwrap(), Some(manifest));
+
+        // Checkpoints of other kinds are not visible.
+        let other_store = RocksdbCheckpointStore::new(
+            store.store.clone(),
+            RocksdbCheckpointKind::StateKeeperCache,
+        );
+        assert_eq!(other_store.list().await.unwrap(), []);
+
+        let target_dir = temp_dir.path().join("restored");
+        store
+            .download(L1BatchNumber(42), &target_dir)
+            .await
+            .unwrap();
+        assert_dirs_eq(&checkpoint_dir, &target_dir).await;
+
+        let err = store
+            .download(L1BatchNumber(42), &target_dir)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{err:#}");
+    }
+
+    #[tokio::test]
+    async fn incomplete_checkpoints_are_ignored() {
+        let temp_dir = TempDir::new().unwrap();
+        let checkpoint_dir = temp_dir.path().join("checkpoint");
+        create_checkpoint_dir(&checkpoint_dir, 1).await;
+
+        let object_store = MockObjectStore::arc();
+        let store =
+            RocksdbCheckpointStore::new(object_store.clone(), RocksdbCheckpointKind::MerkleTree);
+        store
+            .upload(L1BatchNumber(1), &checkpoint_dir)
+            .await
+            .unwrap();
+        // Emulate an interrupted upload.
+        let key = store.file_key(L1BatchNumber(2), "CURRENT");
+        object_store
+            .put_raw(Bucket::StorageCheckpoints, &key, vec![1])
+            .await
+            .unwrap();
+        assert_eq!(store.list().await.unwrap(), [L1BatchNumber(1)]);
+
+        // Emulate a corrupted file.
+        let key = store.file_key(L1BatchNumber(1), "CURRENT");
+        object_store
+            .put_raw(Bucket::StorageCheckpoints, &key, vec![1])
+            .await
+            .unwrap();
+        let target_dir = temp_dir.path().join("restored");
+        let err = store
+            .download(L1BatchNumber(1), &target_dir)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("differs from the manifest"),
+            "{err:#}"
+        );
+        assert!(!target_dir.exists());
+    }
+
+    #[tokio::test]
+    async fn removing_old_checkpoints() {
+        let temp_dir = TempDir::new().unwrap();
+        let object_store = MockObjectStore::arc();
+        let store = RocksdbCheckpointStore::new(
+            object_store.clone(),
+            RocksdbCheckpointKind::StateKeeperCache,
+        );
+        for number in [10, 20, 30, 40] {
+            let checkpoint_dir = temp_dir.path().join(number.to_string());
+            create_checkpoint_dir(&checkpoint_dir, number as u8).await;
+            store
+                .upload(L1BatchNumber(number), &checkpoint_dir)
+                .await
+                .unwrap();
+        }
+        // Incomplete checkpoints older than retained ones should be removed as well.
+        let key = store.file_key(L1BatchNumber(15), "CURRENT");
+        object_store
+            .put_raw(Bucket::StorageCheckpoints, &key, vec![1])
+            .await
+            .unwrap();
+
+        assert_eq!(store.remove_old(5).await.unwrap(), 0);
+        assert_eq!(store.remove_old(2).await.unwrap(), 2);
+        assert_eq!(
+            store.list().await.unwrap(),
+            [L1BatchNumber(30), L1BatchNumber(40)]
+        );
+        let latest = store
+            .latest_in_range(L1BatchNumber(1)..=L1BatchNumber(35))
+            .await
+            .unwrap();
+        assert_eq!(latest, Some(L1BatchNumber(30)));
+        let latest = store
+            .latest_in_range(L1BatchNumber(1)..=L1BatchNumber(25))
+            .await
+            .unwrap();
+        assert_eq!(latest, None);
+        let keys = object_store
+            .list_raw(Bucket::StorageCheckpoints, "")
+            .await
+            .unwrap();
+        assert_eq!(keys.len(), 8, "{keys:?}"); // 2 checkpoints * (3 files + manifest)
+        assert_eq!(store.remove_old(0).await.unwrap(), 0);
+        assert_eq!(store.list().await.unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,436 @@
+//! Object store transparently compressing and encrypting objects on the client side.
+
+use std::{collections::HashMap, fmt, path::Path, sync::Arc};
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use async_trait::async_trait;
+use rand::Rng;
+use zksync_config::configs::object_store::ObjectStoreEncryptionConfig;
+
+use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+
+/// Magic bytes prepended to all objects written by [`EncryptingObjectStore`]. Objects without this prefix
+/// are treated as legacy plaintext objects and are returned as is.
+const MAGIC: &[u8; 4] = b"ZKOE";
+/// Envelope version: zstd compression followed by AES-256-GCM encryption.
+const VERSION_ZSTD_AES256_GCM: u8 = 1;
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+/// Set of keys used by [`EncryptingObjectStore`].
+struct KeyRing {
+    active_key_id: String,
+    ciphers: HashMap<String, Aes256Gcm>,
+    compression_level: i32,
+}
+
+impl fmt::Debug for KeyRing {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Intentionally doesn't output keys.
+        let mut key_ids: Vec<_> = self.ciphers.keys().collect();
+        key_ids.sort_unstable();
+        formatter
+            .debug_struct("KeyRing")
+            .field("active_key_id", &self.active_key_id)
+            .field("key_ids", &key_ids)
+            .field("compression_level", &self.compression_level)
+            .finish()
+    }
+}
+
+impl KeyRing {
+    fn new(
+        keys: HashMap<String, String>,
+        active_key_id: String,
+        compression_level: i32,
+    ) -> anyhow::Result<Self> {
+        let ciphers = keys
+            .into_iter()
+            .map(|(key_id, hex_key)| {
+                anyhow::ensure!(
+                    key_id.len() <= usize::from(u8::MAX),
+                    "key ID `{key_id}` is too long"
+                );
+                let hex_key = hex_key.strip_prefix("0x").unwrap_or(&hex_key);
+                let key = hex::decode(hex_key)
+                    .map_err(|err| anyhow::anyhow!("key `{key_id}` is not a hex string: {err}"))?;
+                anyhow::ensure!(
+                    key.len() == KEY_LEN,
+                    "key `{key_id}` has invalid length {}, expected {KEY_LEN} bytes",
+                    key.len()
+                );
+                let cipher = Aes256Gcm::new_from_slice(&key)
+                    .map_err(|_| anyhow::anyhow!("invalid key `{key_id}`"))?;
+                Ok((key_id, cipher))
+            })
+            .collect::<anyhow::Result<HashMap<_, _>>>()?;
+
+        anyhow::ensure!(
+            ciphers.contains_key(&active_key_id),
+            "active key `{active_key_id}` is not present in the key set"
+        );
+        Ok(Self {
+            active_key_id,
+            ciphers,
+            compression_level,
+        })
+    }
+
+    /// Envelope layout: magic (4 bytes), version (1 byte), key ID length (1 byte), key ID, nonce (12 bytes),
+    /// followed by the ciphertext with the authentication tag. The header is authenticated as associated data.
+    fn seal(&self, value: &[u8]) -> Result<Vec<u8>, ObjectStoreError> {
+        let compressed = zstd::encode_all(value, self.compression_level)
+            .map_err(|err| ObjectStoreError::Serialization(err.into()))?;
+
+        let key_id = self.active_key_id.as_bytes();
+        let nonce: [u8; NONCE_LEN] = rand::thread_rng().gen();
+        let mut envelope =
+            Vec::with_capacity(MAGIC.len() + 2 + key_id.len() + NONCE_LEN + compressed.len() + 16);
+        envelope.extend_from_slice(MAGIC);
+        envelope.push(VERSION_ZSTD_AES256_GCM);
+        envelope
+            .push(u8::try_from(key_id.len()).expect("key ID length is checked when loading keys"));
+        envelope.extend_from_slice(key_id);
+        envelope.extend_from_slice(&nonce);
+
+        let cipher = &self.ciphers[&self.active_key_id];
+        let payload = Payload {
+            msg: &compressed,
+            aad: &envelope,
+        };
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), payload)
+            .map_err(|_| ObjectStoreError::Serialization("failed encrypting object".into()))?;
+        envelope.extend_from_slice(&ciphertext);
+        Ok(envelope)
+    }
+
+    /// Returns `None` if the object is not encrypted.
+    fn open(&self, envelope: &[u8]) -> Option<Result<Vec<u8>, ObjectStoreError>> {
+        let rest = envelope.strip_prefix(MAGIC)?;
+        Some(self.open_inner(envelope, rest))
+    }
+
+    fn open_inner(&self, envelope: &[u8], rest: &[u8]) -> Result<Vec<u8>, ObjectStoreError> {
+        let (&version, rest) = rest
+            .split_first()
+            .ok_or_else(|| ObjectStoreError::Serialization("truncated object header".into()))?;
+        if version != VERSION_ZSTD_AES256_GCM {
+            let err = format!("unsupported encrypted object version {version}");
+            return Err(ObjectStoreError::Serialization(err.into()));
+        }
+        let (&key_id_len, rest) = rest
+            .split_first()
+            .ok_or_else(|| ObjectStoreError::Serialization("truncated object header".into()))?;
+        let key_id_len = usize::from(key_id_len);
+        if rest.len() < key_id_len + NONCE_LEN {
+            return Err(ObjectStoreError::Serialization(
+                "truncated object header".into(),
+            ));
+        }
+        let (key_id, rest) = rest.split_at(key_id_len);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let header = &envelope[..envelope.len() - ciphertext.len()];
+
+        let key_id = String::from_utf8_lossy(key_id);
+        let cipher = self.ciphers.get(key_id.as_ref()).ok_or_else(|| {
+            let err = format!("object is encrypted with unknown key `{key_id}`");
+            ObjectStoreError::Serialization(err.into())
+        })?;
+        let payload = Payload {
+            msg: ciphertext,
+            aad: header,
+        };
+        let compressed = cipher
+            .decrypt(Nonce::from_slice(nonce), payload)
+            .map_err(|_| {
+                let err = format!("failed decrypting object with key `{key_id}`");
+                ObjectStoreError::Serialization(err.into())
+            })?;
+        zstd::decode_all(compressed.as_slice())
+            .map_err(|err| ObjectStoreError::Serialization(err.into()))
+    }
+}
+
+/// Object store wrapper that compresses objects using zstd and encrypts them using AES-256-GCM before
+/// passing them to the underlying store. Each object references the ID of the key it was encrypted with,
+/// so keys can be rotated by adding a new key and making it active; old keys must be retained as long as
+/// objects encrypted with them exist.
+///
+/// Objects not written by this store (e.g., written before encryption was enabled) are returned as is, unless
+/// plaintext objects are disallowed in the config.
+#[derive(Debug)]
+pub(crate) struct EncryptingObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    keys: Arc<KeyRing>,
+    allow_plaintext_objects: bool,
+}
+
+impl EncryptingObjectStore {
+    pub async fn new(
+        inner: Arc<dyn ObjectStore>,
+        config: &ObjectStoreEncryptionConfig,
+    ) -> Result<Self, ObjectStoreError> {
+        let keys = Self::load_keys(&config.keys_path).await.map_err(|err| {
+            ObjectStoreError::Initialization {
+                source: err.into(),
+                is_retriable: false,
+            }
+        })?;
+        let keys =
+            KeyRing::new(keys, config.key_id.clone(), config.compression_level).map_err(|err| {
+                ObjectStoreError::Initialization {
+                    source: err.into(),
+                    is_retriable: false,
+                }
+            })?;
+        tracing::info!(
+            "Initializing encryption for store {inner:?} with {keys:?}; allow_plaintext_objects={}",
+            config.allow_plaintext_objects
+        );
+        Ok(Self {
+            inner,
+            keys: Arc::new(keys),
+            allow_plaintext_objects: config.allow_plaintext_objects,
+        })
+    }
+
+    async fn load_keys(path: &Path) -> anyhow::Result<HashMap<String, String>> {
+        let raw = tokio::fs::read(path).await.map_err(|err| {
+            anyhow::anyhow!("failed reading keys file `{}`: {err}", path.display())
+        })?;
+        serde_json::from_slice(&raw)
+            .map_err(|err| anyhow::anyhow!("failed parsing keys file `{}`: {err}", path.display()))
+    }
+}
+
+#[async_trait]
+impl ObjectStore for EncryptingObjectStore {
+    #[tracing::instrument(name = "EncryptingObjectStore::get_raw", skip(self))]
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let object = self.inner.get_raw(bucket, key).await?;
+        if !object.starts_with(MAGIC) {
+            if !self.allow_plaintext_objects {
+                return Err(ObjectStoreError::Serialization(
+                    "object is not encrypted, and plaintext objects are not allowed".into(),
+                ));
+            }
+            tracing::trace!("object is not encrypted; returning it as is");
+            return Ok(object);
+        }
+        let keys = self.keys.clone();
+        tokio::task::spawn_blocking(move || keys.open(&object).unwrap_or(Ok(object)))
+            .await
+            .map_err(|err| ObjectStoreError::Other {
+                source: err.into(),
+                is_retriable: false,
+            })?
+    }
+
+    #[tracing::instrument(
+        name = "EncryptingObjectStore::put_raw",
+        skip(self, value),
+        fields(value.len = value.len())
+    )]
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        let keys = self.keys.clone();
+        let envelope = tokio::task::spawn_blocking(move || keys.seal(&value))
+            .await
+            .map_err(|err| ObjectStoreError::Other {
+                source: err.into(),
+                is_retriable: false,
+            })??;
+        self.inner.put_raw(bucket, key, envelope).await
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        self.inner.remove_raw(bucket, key).await
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        self.inner.list_raw(bucket, prefix).await
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        self.inner.storage_prefix_raw(bucket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::MockObjectStore;
+
+    const OLD_KEY: &str = "0101010101010101010101010101010101010101010101010101010101010101";
+    const NEW_KEY: &str = "0x0202020202020202020202020202020202020202020202020202020202020202";
+
+    fn create_store(
+        inner: &Arc<dyn ObjectStore>,
+        keys: &[(&str, &str)],
+        active_key_id: &str,
+    ) -> EncryptingObjectStore {
+        let keys = keys
+            .iter()
+            .map(|&(id, key)| (id.to_owned(), key.to_owned()))
+            .collect();
+        let keys = KeyRing::new(keys, active_key_id.to_owned(), 3).unwrap();
+        EncryptingObjectStore {
+            inner: inner.clone(),
+            keys: Arc::new(keys),
+            allow_plaintext_objects: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn roundtrip() {
+        let inner = MockObjectStore::arc();
+        let store = create_store(&inner, &[("old", OLD_KEY)], "old");
+        let value = b"test".repeat(1_000);
+        store
+            .put_raw(Bucket::StorageSnapshot, "test-key", value.clone())
+            .await
+            .unwrap();
+
+        let stored = inner
+            .get_raw(Bucket::StorageSnapshot, "test-key")
+            .await
+            .unwrap();
+        assert!(stored.starts_with(MAGIC));
+        assert!(stored.len() < value.len()); // the value should be compressed
+        assert!(!stored.windows(4).any(|window| window == b"test"));
+
+        let retrieved = store
+            .get_raw(Bucket::StorageSnapshot, "test-key")
+            .await
+            .unwrap();
+        assert_eq!(retrieved, value);
+    }
+
+    #[tokio::test]
+    async fn key_rotation() {
+        let inner = MockObjectStore::arc();
+        let old_store = create_store(&inner, &[("old", OLD_KEY)], "old");
+        old_store
+            .put_raw(Bucket::StorageSnapshot, "old", b"old".to_vec())
+            .await
+            .unwrap();
+
+        let new_store = create_store(&inner, &[("old", OLD_KEY), ("new", NEW_KEY)], "new");
+        new_store
+            .put_raw(Bucket::StorageSnapshot, "new", b"new".to_vec())
+            .await
+            .unwrap();
+        let value = new_store
+            .get_raw(Bucket::StorageSnapshot, "old")
+            .await
+            .unwrap();
+        assert_eq!(value, b"old");
+        let value = new_store
+            .get_raw(Bucket::StorageSnapshot, "new")
+            .await
+            .unwrap();
+        assert_eq!(value, b"new");
+
+        let err = old_store
+            .get_raw(Bucket::StorageSnapshot, "new")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::Serialization(_));
+        assert!(err.to_string().contains("unknown key `new`"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn plaintext_objects_are_returned_as_is() {
+        let inner = MockObjectStore::arc();
+        inner
+            .put_raw(Bucket::StorageSnapshot, "legacy", b"legacy".to_vec())
+            .await
+            .unwrap();
+        let store = create_store(&inner, &[("old", OLD_KEY)], "old");
+        let value = store
+            .get_raw(Bucket::StorageSnapshot, "legacy")
+            .await
+            .unwrap();
+        assert_eq!(value, b"legacy");
+    }
+
+    #[tokio::test]
+    async fn plaintext_objects_are_rejected_if_disallowed() {
+        let inner = MockObjectStore::arc();
+        inner
+            .put_raw(Bucket::StorageSnapshot, "legacy", b"legacy".to_vec())
+            .await
+            .unwrap();
+        let store = EncryptingObjectStore {
+            allow_plaintext_objects: false,
+            ..create_store(&inner, &[("old", OLD_KEY)], "old")
+        };
+        let err = store
+            .get_raw(Bucket::StorageSnapshot, "legacy")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::Serialization(_));
+        assert!(err.to_string().contains("not encrypted"), "{err}");
+
+        // Encrypted objects are still readable.
+        store
+            .put_raw(Bucket::StorageSnapshot, "test-key", b"test".to_vec())
+            .await
+            .unwrap();
+        let value = store
+            .get_raw(Bucket::StorageSnapshot, "test-key")
+            .await
+            .unwrap();
+        assert_eq!(value, b"test");
+    }
+
+    #[tokio::test]
+    async fn tampered_objects_are_rejected() {
+        let inner = MockObjectStore::arc();
+        let store = create_store(&inner, &[("old", OLD_KEY)], "old");
+        store
+            .put_raw(Bucket::StorageSnapshot, "test-key", b"test".to_vec())
+            .await
+            .unwrap();
+        let mut stored = inner
+            .get_raw(Bucket::StorageSnapshot, "test-key")
+            .await
+            .unwrap();
+        *stored.last_mut().unwrap() ^= 1;
+        inner
+            .put_raw(Bucket::StorageSnapshot, "test-key", stored)
+            .await
+            .unwrap();
+
+        let err = store
+            .get_raw(Bucket::StorageSnapshot, "test-key")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::Serialization(_));
+    }
+
+    #[test]
+    fn invalid_key_sets_are_rejected() {
+        let keys = HashMap::from([("old".to_owned(), OLD_KEY.to_owned())]);
+        let err = KeyRing::new(keys, "new".to_owned(), 3).unwrap_err();
+        assert!(err.to_string().contains("not present"), "{err}");
+
+        let keys = HashMap::from([("short".to_owned(), "0102".to_owned())]);
+        let err = KeyRing::new(keys, "short".to_owned(), 3).unwrap_err();
+        assert!(err.to_string().contains("invalid length"), "{err}");
+    }
+}
@@ -4,7 +4,10 @@ use anyhow::Context as _;
 use tokio::sync::OnceCell;
 use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
 
+#[cfg(feature = "azure")]
+use crate::azure::{AzureBlobStore, AzureBlobStoreAuthMode};
 use crate::{
+    encryption::EncryptingObjectStore,
     file::FileBackedObjectStore,
     gcs::{GoogleCloudStore, GoogleCloudStoreAuthMode},
     mirror::MirroringObjectStore,
@@ -62,7 +65,7 @@ impl ObjectStoreFactory {
         config: &ObjectStoreConfig,
     ) -> Result<Arc<dyn ObjectStore>, ObjectStoreError> {
         tracing::trace!("Initializing object store with configuration {config:?}");
-        match &config.mode {
+        let store: Arc<dyn ObjectStore> = match &config.mode {
             ObjectStoreMode::GCS { bucket_base_url } => {
                 let store = StoreWithRetries::try_new(config.max_retries, || {
                     GoogleCloudStore::new(
@@ -71,7 +74,7 @@ impl ObjectStoreFactory {
                     )
                 })
                 .await?;
-                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await
+                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await?
             }
             ObjectStoreMode::GCSWithCredentialFile {
                 bucket_base_url,
@@ -86,7 +89,7 @@ impl ObjectStoreFactory {
                     )
                 })
                 .await?;
-                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await
+                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await?
             }
             ObjectStoreMode::GCSAnonymousReadOnly { bucket_base_url } => {
                 let store = StoreWithRetries::try_new(config.max_retries, || {
@@ -96,7 +99,7 @@ impl ObjectStoreFactory {
                     )
                 })
                 .await?;
-                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await
+                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await?
             }
 
             ObjectStoreMode::S3WithCredentialFile {
@@ -116,7 +119,7 @@ impl ObjectStoreFactory {
                     )
                 })
                 .await?;
-                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await
+                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await?
             }
             ObjectStoreMode::S3AnonymousReadOnly {
                 bucket_base_url,
@@ -132,7 +135,71 @@ impl ObjectStoreFactory {
                     )
                 })
                 .await?;
-                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await
+                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await?
+            }
+
+            #[cfg(feature = "azure")]
+            ObjectStoreMode::AzureWithSharedKey {
+                bucket_base_url,
+                azure_account_name,
+                azure_account_key_file_path,
+                endpoint,
+            } => {
+                let store = StoreWithRetries::try_new(config.max_retries, || {
+                    AzureBlobStore::new(
+                        AzureBlobStoreAuthMode::SharedKeyFile(azure_account_key_file_path.clone()),
+                        azure_account_name.clone(),
+                        bucket_base_url.clone(),
+                        endpoint.clone(),
+                    )
+                })
+                .await?;
+                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await?
+            }
+            #[cfg(feature = "azure")]
+            ObjectStoreMode::AzureWithSasToken {
+                bucket_base_url,
+                azure_account_name,
+                azure_sas_token_file_path,
+                endpoint,
+            } => {
+                let store = StoreWithRetries::try_new(config.max_retries, || {
+                    AzureBlobStore::new(
+                        AzureBlobStoreAuthMode::SasTokenFile(azure_sas_token_file_path.clone()),
+                        azure_account_name.clone(),
+                        bucket_base_url.clone(),
+                        endpoint.clone(),
+                    )
+                })
+                .await?;
+                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await?
+            }
+            #[cfg(feature = "azure")]
+            ObjectStoreMode::AzureWithManagedIdentity {
+                bucket_base_url,
+                azure_account_name,
+                endpoint,
+            } => {
+                let store = StoreWithRetries::try_new(config.max_retries, || {
+                    AzureBlobStore::new(
+                        AzureBlobStoreAuthMode::ManagedIdentity,
+                        azure_account_name.clone(),
+                        bucket_base_url.clone(),
+                        endpoint.clone(),
+                    )
+                })
+                .await?;
+                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await?
+            }
+
+            #[cfg(not(feature = "azure"))]
+            ObjectStoreMode::AzureWithSharedKey { .. }
+            | ObjectStoreMode::AzureWithSasToken { .. }
+            | ObjectStoreMode::AzureWithManagedIdentity { .. } => {
+                return Err(ObjectStoreError::Initialization {
+                    source: "Azure Blob Storage support is not enabled; build with the `azure` feature of `zksync_object_store`".into(),
+                    is_retriable: false,
+                });
             }
 
             ObjectStoreMode::FileBacked {
@@ -149,9 +216,16 @@ impl ObjectStoreFactory {
                         mirror_path.display()
                     );
                 }
-                Ok(Arc::new(store))
+                Arc::new(store)
             }
-        }
+        };
+
+        // Encryption is the outermost layer, so that mirrored objects are encrypted as well.
+        Ok(if let Some(encryption) = &config.encryption {
+            Arc::new(EncryptingObjectStore::new(store, encryption).await?)
+        } else {
+            store
+        })
     }
 
     async fn wrap_mirroring(
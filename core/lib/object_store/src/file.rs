@@ -1,9 +1,24 @@
-use std::{fmt::Debug, path::PathBuf};
+use std::{
+    fmt::Debug,
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 use async_trait::async_trait;
-use tokio::{fs, io};
+use bytes::Bytes;
+use futures::{stream, StreamExt, TryStreamExt};
+use tokio::{
+    fs, io,
+    io::{AsyncReadExt, AsyncWriteExt},
+};
 
-use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+use crate::raw::{Bucket, ObjectStore, ObjectStoreError, ObjectStream};
+
+/// Size of chunks in which objects are read when streaming.
+const STREAM_CHUNK_SIZE: usize = 1 << 20;
+/// Directory (relative to the base dir) for objects being written. It's placed outside bucket directories
+/// so that partially written objects are never listed.
+const TMP_DIR: &str = ".tmp";
 
 impl From<io::Error> for ObjectStoreError {
     fn from(err: io::Error) -> Self {
@@ -21,6 +36,7 @@ impl From<io::Error> for ObjectStoreError {
 #[derive(Debug)]
 pub struct FileBackedObjectStore {
     base_dir: PathBuf,
+    next_tmp_file_id: AtomicU64,
 }
 
 impl FileBackedObjectStore {
@@ -43,16 +59,57 @@ impl FileBackedObjectStore {
             Bucket::ProofsFri,
             Bucket::StorageSnapshot,
             Bucket::VmDumps,
+            Bucket::StorageCheckpoints,
         ] {
             let bucket_path = base_dir.join(bucket.to_string());
             fs::create_dir_all(&bucket_path).await?;
         }
-        Ok(FileBackedObjectStore { base_dir })
+        fs::create_dir_all(base_dir.join(TMP_DIR)).await?;
+        Ok(FileBackedObjectStore {
+            base_dir,
+            next_tmp_file_id: AtomicU64::new(0),
+        })
     }
 
     fn filename(&self, bucket: Bucket, key: &str) -> PathBuf {
         self.base_dir.join(format!("{bucket}/{key}"))
     }
+
+    /// Returns a unique path for a temporary file. Paths are unique across concurrent writers,
+    /// including ones in other processes sharing the base dir.
+    fn tmp_filename(&self) -> PathBuf {
+        let id = self.next_tmp_file_id.fetch_add(1, Ordering::Relaxed);
+        let name = format!("{}-{id}", std::process::id());
+        self.base_dir.join(TMP_DIR).join(name)
+    }
+
+    /// Lists keys of all files in the bucket directory, including files in nested directories.
+    async fn list_bucket(&self, bucket: Bucket) -> Result<Vec<String>, ObjectStoreError> {
+        let bucket_path = self.base_dir.join(bucket.to_string());
+        let mut keys = vec![];
+        let mut pending_dirs = vec![(bucket_path, String::new())];
+        while let Some((dir, key_prefix)) = pending_dirs.pop() {
+            let mut entries = match fs::read_dir(&dir).await {
+                Ok(entries) => entries,
+                // The bucket directory is only created for some buckets.
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let Ok(name) = entry.file_name().into_string() else {
+                    tracing::warn!("Skipping non-UTF8 path {:?}", entry.path());
+                    continue;
+                };
+                let key = format!("{key_prefix}{name}");
+                if entry.file_type().await?.is_dir() {
+                    pending_dirs.push((entry.path(), format!("{key}/")));
+                } else {
+                    keys.push(key);
+                }
+            }
+        }
+        Ok(keys)
+    }
 }
 
 #[async_trait]
@@ -77,6 +134,61 @@ impl ObjectStore for FileBackedObjectStore {
         fs::remove_file(filename).await.map_err(From::from)
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        let mut keys = self.list_bucket(bucket).await?;
+        keys.retain(|key| key.starts_with(prefix));
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
+    async fn get_stream_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+    ) -> Result<ObjectStream, ObjectStoreError> {
+        let file = fs::File::open(self.filename(bucket, key)).await?;
+        let chunks = stream::try_unfold(file, |mut file| async move {
+            let mut chunk = vec![0; STREAM_CHUNK_SIZE];
+            let chunk_len = file.read(&mut chunk).await?;
+            if chunk_len == 0 {
+                return Ok(None);
+            }
+            chunk.truncate(chunk_len);
+            Ok(Some((Bytes::from(chunk), file)))
+        });
+        Ok(chunks.boxed())
+    }
+
+    async fn put_stream_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        mut value: ObjectStream,
+    ) -> Result<(), ObjectStoreError> {
+        // Write to a temporary file first so that a partially written object is never observable.
+        let filename = self.filename(bucket, key);
+        let tmp_filename = self.tmp_filename();
+
+        let write_result = async {
+            let mut file = fs::File::create(&tmp_filename).await?;
+            while let Some(chunk) = value.try_next().await? {
+                file.write_all(&chunk).await?;
+            }
+            file.flush().await?;
+            Ok::<_, ObjectStoreError>(())
+        }
+        .await;
+        if let Err(err) = write_result {
+            fs::remove_file(&tmp_filename).await.ok();
+            return Err(err);
+        }
+        fs::rename(tmp_filename, filename).await.map_err(From::from)
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         self.base_dir
             .join(bucket.to_string())
@@ -135,4 +247,121 @@ mod test {
             .await
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn test_list() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().to_owned();
+        let object_store = FileBackedObjectStore::new(path).await.unwrap();
+        for key in ["proof_2.bin", "proof_1.bin", "witness_1.bin"] {
+            object_store
+                .put_raw(Bucket::ProverJobs, key, vec![0, 1])
+                .await
+                .unwrap();
+        }
+
+        let keys = object_store
+            .list_raw(Bucket::ProverJobs, "proof_")
+            .await
+            .unwrap();
+        assert_eq!(keys, ["proof_1.bin", "proof_2.bin"]);
+        let keys = object_store.list_raw(Bucket::ProverJobs, "").await.unwrap();
+        assert_eq!(keys, ["proof_1.bin", "proof_2.bin", "witness_1.bin"]);
+        // Listing a bucket without a directory should succeed.
+        let keys = object_store
+            .list_raw(Bucket::DataAvailability, "")
+            .await
+            .unwrap();
+        assert!(keys.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_streaming() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().to_owned();
+        let object_store = FileBackedObjectStore::new(path).await.unwrap();
+        let expected: Vec<u8> = (0..=u8::MAX)
+            .cycle()
+            .take(STREAM_CHUNK_SIZE * 2 + 5)
+            .collect();
+        let chunks: Vec<_> = expected
+            .chunks(1_000)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect();
+        object_store
+            .put_stream_raw(
+                Bucket::ProverJobs,
+                "test-key.bin",
+                stream::iter(chunks).boxed(),
+            )
+            .await
+            .unwrap();
+
+        let chunks: Vec<_> = object_store
+            .get_stream_raw(Bucket::ProverJobs, "test-key.bin")
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks.concat(), expected);
+        let keys = object_store.list_raw(Bucket::ProverJobs, "").await.unwrap();
+        assert_eq!(keys, ["test-key.bin"]);
+    }
+
+    #[tokio::test]
+    async fn failed_streaming_leaves_no_files() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().to_owned();
+        let object_store = FileBackedObjectStore::new(path.clone()).await.unwrap();
+        let chunks = vec![
+            Ok(Bytes::from_static(b"test")),
+            Err(ObjectStoreError::Other {
+                is_retriable: true,
+                source: "stream interrupted".into(),
+            }),
+        ];
+        object_store
+            .put_stream_raw(
+                Bucket::ProverJobs,
+                "test-key.bin",
+                stream::iter(chunks).boxed(),
+            )
+            .await
+            .unwrap_err();
+
+        let keys = object_store.list_raw(Bucket::ProverJobs, "").await.unwrap();
+        assert!(keys.is_empty(), "{keys:?}");
+        let mut tmp_entries = fs::read_dir(path.join(TMP_DIR)).await.unwrap();
+        assert!(tmp_entries.next_entry().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn concurrent_streaming_to_same_key() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().to_owned();
+        let object_store = FileBackedObjectStore::new(path).await.unwrap();
+        let values = [vec![1_u8; 10_000], vec![2_u8; 10_000]];
+        let writes = values.iter().map(|value| {
+            let chunks: Vec<_> = value
+                .chunks(100)
+                .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+                .collect();
+            object_store.put_stream_raw(
+                Bucket::ProverJobs,
+                "test-key.bin",
+                stream::iter(chunks).boxed(),
+            )
+        });
+        futures::future::try_join_all(writes).await.unwrap();
+
+        let keys = object_store.list_raw(Bucket::ProverJobs, "").await.unwrap();
+        assert_eq!(keys, ["test-key.bin"]);
+        let value = object_store
+            .get_raw(Bucket::ProverJobs, "test-key.bin")
+            .await
+            .unwrap();
+        assert!(values.contains(&value));
+    }
 }
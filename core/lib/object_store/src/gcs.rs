@@ -1,8 +1,17 @@
 //! GCS-based [`ObjectStore`] implementation.
 
-use std::{error::Error as StdError, fmt, io, path::PathBuf};
+use std::{
+    error::Error as StdError,
+    fmt, io,
+    path::PathBuf,
+    pin::Pin,
+    sync::{Arc, Mutex, PoisonError},
+    task,
+};
 
 use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{Stream, StreamExt, TryStreamExt};
 use google_cloud_auth::{credentials::CredentialsFile, error::Error as AuthError};
 use google_cloud_storage::{
     client::{Client, ClientConfig},
@@ -11,15 +20,16 @@ use google_cloud_storage::{
             delete::DeleteObjectRequest,
             download::Range,
             get::GetObjectRequest,
+            list::ListObjectsRequest,
             upload::{Media, UploadObjectRequest, UploadType},
         },
         Error as HttpError,
     },
 };
 use http::StatusCode;
-use tokio::sync::{AcquireError, Semaphore};
+use tokio::sync::{AcquireError, OwnedSemaphorePermit, Semaphore};
 
-use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+use crate::raw::{Bucket, ObjectStore, ObjectStoreError, ObjectStream};
 
 /// Default maximum number of concurrent requests to GCS.
 /// Consider this a throttle to prevent overwhelming GCS or network card.
@@ -32,7 +42,7 @@ pub struct GoogleCloudStore {
     bucket_prefix: String,
     client: Client,
     // used to limit the number of concurrent requests to GCS.
-    semaphore: Semaphore,
+    semaphore: Arc<Semaphore>,
 }
 
 impl fmt::Debug for GoogleCloudStore {
@@ -68,7 +78,7 @@ impl GoogleCloudStore {
         bucket_prefix: String,
     ) -> Result<Self, ObjectStoreError> {
         let client_config = Self::get_client_config(auth_mode.clone()).await?;
-        let semaphore = Semaphore::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
+        let semaphore = Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_REQUESTS));
         Ok(Self {
             client: Client::new(client_config),
             bucket_prefix,
@@ -80,7 +90,7 @@ impl GoogleCloudStore {
     /// NOTE: Big numbers can saturate the network and/or cause the GCS to be unavailable.
     #[must_use]
     pub fn with_request_limit(mut self, request_limit: usize) -> Self {
-        self.semaphore = Semaphore::new(request_limit);
+        self.semaphore = Arc::new(Semaphore::new(request_limit));
         self
     }
 
@@ -104,6 +114,36 @@ impl GoogleCloudStore {
     }
 }
 
+/// Wrapper making [`ObjectStream`] `Sync`, which is required by the GCS client for streamed uploads.
+/// The stream is only accessed via an exclusive reference, so the mutex is never locked.
+///
+/// The wrapper also holds the request permit until the upload has started (i.e., the first chunk is consumed
+/// by the client); otherwise, a slow producer could hold the permit indefinitely.
+struct SyncObjectStream {
+    stream: Mutex<ObjectStream>,
+    permit: Option<OwnedSemaphorePermit>,
+}
+
+impl Stream for SyncObjectStream {
+    type Item = Result<Bytes, ObjectStoreError>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let stream = this
+            .stream
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        let poll = stream.poll_next_unpin(cx);
+        if poll.is_ready() {
+            this.permit = None;
+        }
+        poll
+    }
+}
+
 impl From<AuthError> for ObjectStoreError {
     fn from(err: AuthError) -> Self {
         let is_retriable = matches!(
@@ -250,6 +290,97 @@ impl ObjectStore for GoogleCloudStore {
         Ok(())
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        let _permit = self.semaphore.acquire().await?;
+        let bucket_prefix = Self::filename(bucket.as_str(), "");
+        tracing::trace!(
+            "Listing GCS objects with prefix {bucket_prefix}{prefix} in bucket {}",
+            self.bucket_prefix
+        );
+
+        let mut keys = vec![];
+        let mut page_token = None;
+        loop {
+            let request = ListObjectsRequest {
+                bucket: self.bucket_prefix.clone(),
+                prefix: Some(format!("{bucket_prefix}{prefix}")),
+                page_token,
+                ..ListObjectsRequest::default()
+            };
+            let response = self.client.list_objects(&request).await?;
+            let objects = response.items.unwrap_or_default();
+            keys.extend(
+                objects.into_iter().filter_map(|object| {
+                    object.name.strip_prefix(&bucket_prefix).map(str::to_owned)
+                }),
+            );
+            page_token = response.next_page_token;
+            if page_token.is_none() {
+                break;
+            }
+        }
+        // GCS lists objects in the lexicographic order, but we sort keys for extra safety.
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
+    async fn get_stream_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+    ) -> Result<ObjectStream, ObjectStoreError> {
+        // The permit only covers initiating the download; otherwise, a slow consumer could hold it indefinitely.
+        let _permit = self.semaphore.acquire().await?;
+        let filename = Self::filename(bucket.as_str(), key);
+        tracing::trace!(
+            "Streaming data from GCS for key {filename} from bucket {}",
+            self.bucket_prefix
+        );
+
+        let request = GetObjectRequest {
+            bucket: self.bucket_prefix.clone(),
+            object: filename,
+            ..GetObjectRequest::default()
+        };
+        let stream = self
+            .client
+            .download_streamed_object(&request, &Range::default())
+            .await?;
+        Ok(stream.map_err(ObjectStoreError::from).boxed())
+    }
+
+    async fn put_stream_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: ObjectStream,
+    ) -> Result<(), ObjectStoreError> {
+        let permit = self.semaphore.clone().acquire_owned().await?;
+        let filename = Self::filename(bucket.as_str(), key);
+        tracing::trace!(
+            "Streaming data to GCS for key {filename} from bucket {}",
+            self.bucket_prefix
+        );
+
+        let upload_type = UploadType::Simple(Media::new(filename));
+        let request = UploadObjectRequest {
+            bucket: self.bucket_prefix.clone(),
+            ..Default::default()
+        };
+        let value = SyncObjectStream {
+            stream: Mutex::new(value),
+            permit: Some(permit),
+        };
+        self.client
+            .upload_streamed_object(&request, value, &upload_type)
+            .await?;
+        Ok(())
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         format!(
             "https://storage.googleapis.com/{}/{}",
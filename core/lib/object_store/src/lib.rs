@@ -1,18 +1,24 @@
 //! This crate provides the [object storage abstraction](ObjectStore) that allows to get,
-//! put and remove binary blobs. The following implementations are available:
+//! put, remove and list binary blobs, and to read and write them as [streams](ObjectStream).
+//! The following implementations are available:
 //!
 //! - [File-backed store](FileBackedObjectStore) saving blobs as separate files in the local filesystem
 //! - [GCS-based store](GoogleCloudStore)
+//! - [Azure Blob Storage-based store](AzureBlobStore) (requires the `azure` feature)
 //! - [Mock in-memory store](MockObjectStore)
 //!
 //! Normally, these implementations are not used directly. Instead, a store trait object (`Arc<dyn ObjectStore>`)
 //! can be constructed using an [`ObjectStoreFactory`] based on the configuration.
-//! This trait object is what should be used for dependency injection.
+//! This trait object is what should be used for dependency injection. Depending on the configuration,
+//! the store may be wrapped to retry failed requests, mirror objects in the local filesystem, and transparently
+//! compress and encrypt objects on the client side.
 //!
 //! Besides the lower-level storage abstraction, the crate provides high-level
 //! typesafe `<dyn ObjectStore>::get()` and `<dyn ObjectStore>::put()` methods
 //! to store [(de)serializable objects](StoredObject). Prefer using these methods
-//! whenever possible.
+//! whenever possible. [`RocksdbCheckpointStore`] builds on top of the streaming methods to back up and restore
+//! RocksDB checkpoints. [`SnapshotArchive`] and [`SnapshotArchiveWriter`] pack application-level snapshots
+//! into self-describing single-file archives, which allow transferring snapshots without an object store.
 
 // Linter settings.
 #![warn(missing_debug_implementations, bare_trait_objects)]
@@ -23,6 +29,10 @@
     clippy::doc_markdown
 )]
 
+#[cfg(feature = "azure")]
+mod azure;
+mod checkpoints;
+mod encryption;
 mod factory;
 mod file;
 mod gcs;
@@ -35,6 +45,7 @@ mod objects;
 mod raw;
 mod retries;
 mod s3;
+mod snapshot_archive;
 
 // Re-export `bincode` crate so that client binaries can conveniently use it.
 pub use bincode;
@@ -44,11 +55,21 @@ pub mod _reexports {
     pub use crate::raw::BoxedError;
 }
 
+#[cfg(feature = "azure")]
+pub use self::azure::{AzureBlobStore, AzureBlobStoreAuthMode};
 pub use self::{
+    checkpoints::{
+        RocksdbCheckpointFile, RocksdbCheckpointKind, RocksdbCheckpointManifest,
+        RocksdbCheckpointStore,
+    },
     factory::ObjectStoreFactory,
     file::FileBackedObjectStore,
     gcs::{GoogleCloudStore, GoogleCloudStoreAuthMode},
     mock::MockObjectStore,
     objects::StoredObject,
-    raw::{Bucket, ObjectStore, ObjectStoreError},
+    raw::{Bucket, ObjectStore, ObjectStoreError, ObjectStream},
+    snapshot_archive::{
+        SnapshotArchive, SnapshotArchiveEntry, SnapshotArchiveManifest, SnapshotArchiveMetadata,
+        SnapshotArchiveWriter,
+    },
 };
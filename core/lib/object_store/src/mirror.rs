@@ -4,7 +4,11 @@ use std::path::PathBuf;
 
 use async_trait::async_trait;
 
-use crate::{file::FileBackedObjectStore, raw::ObjectStore, Bucket, ObjectStoreError};
+use crate::{
+    file::FileBackedObjectStore,
+    raw::{ObjectStore, ObjectStream},
+    Bucket, ObjectStoreError,
+};
 
 #[derive(Debug)]
 pub(crate) struct MirroringObjectStore<S> {
@@ -90,6 +94,60 @@ impl<S: ObjectStore> ObjectStore for MirroringObjectStore<S> {
         Ok(())
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        self.inner.list_raw(bucket, prefix).await
+    }
+
+    /// Streams the object from the mirror if it's present there. Otherwise, streams it from the underlying store
+    /// without mirroring (mirroring would require buffering the object).
+    #[tracing::instrument(name = "MirroringObjectStore::get_stream_raw", skip(self))]
+    async fn get_stream_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+    ) -> Result<ObjectStream, ObjectStoreError> {
+        match self.mirror_store.get_stream_raw(bucket, key).await {
+            Ok(stream) => {
+                tracing::trace!("streaming object from mirror");
+                Ok(stream)
+            }
+            Err(err) => {
+                if !matches!(err, ObjectStoreError::KeyNotFound(_)) {
+                    tracing::warn!(
+                        "unexpected error calling local mirror store: {:#}",
+                        anyhow::Error::from(err)
+                    );
+                }
+                self.inner.get_stream_raw(bucket, key).await
+            }
+        }
+    }
+
+    #[tracing::instrument(name = "MirroringObjectStore::put_stream_raw", skip(self, value))]
+    async fn put_stream_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: ObjectStream,
+    ) -> Result<(), ObjectStoreError> {
+        self.inner.put_stream_raw(bucket, key, value).await?;
+        // The streamed value is not mirrored, so we need to remove a potentially stale mirrored value.
+        match self.mirror_store.remove_raw(bucket, key).await {
+            Ok(()) | Err(ObjectStoreError::KeyNotFound(_)) => {}
+            Err(err) => {
+                tracing::warn!(
+                    "failed removing object from mirror: {:#}",
+                    anyhow::Error::from(err)
+                );
+            }
+        }
+        Ok(())
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         self.inner.storage_prefix_raw(bucket)
     }
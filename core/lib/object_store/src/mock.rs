@@ -54,6 +54,24 @@ impl ObjectStore for MockObjectStore {
         Ok(())
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        let lock = self.inner.lock().await;
+        let Some(bucket_map) = lock.get(&bucket) else {
+            return Ok(vec![]);
+        };
+        let mut keys: Vec<_> = bucket_map
+            .keys()
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect();
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         bucket.to_string()
     }
@@ -1,6 +1,8 @@
 use std::{error, fmt};
 
 use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{future, stream, stream::BoxStream, StreamExt, TryStreamExt};
 
 /// Bucket for [`ObjectStore`] in which objects can be placed.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -21,6 +23,7 @@ pub enum Bucket {
     DataAvailability,
     VmDumps,
     PublicWitnessInputs,
+    StorageCheckpoints,
 }
 
 impl Bucket {
@@ -41,6 +44,7 @@ impl Bucket {
             Self::DataAvailability => "data_availability",
             Self::VmDumps => "vm_dumps",
             Self::PublicWitnessInputs => "public_witness_inputs",
+            Self::StorageCheckpoints => "storage_checkpoints",
         }
     }
 }
@@ -51,6 +55,10 @@ impl fmt::Display for Bucket {
     }
 }
 
+/// Stream of data chunks of an object returned by [`ObjectStore::get_stream_raw()`] and accepted
+/// by [`ObjectStore::put_stream_raw()`].
+pub type ObjectStream = BoxStream<'static, Result<Bytes, ObjectStoreError>>;
+
 /// Thread-safe boxed error.
 pub type BoxedError = Box<dyn error::Error + Send + Sync>;
 
@@ -159,5 +167,51 @@ pub trait ObjectStore: 'static + fmt::Debug + Send + Sync {
     /// Returns an error if removal fails.
     async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError>;
 
+    /// Lists keys of objects in the given bucket that start with the specified `prefix`. Keys are returned
+    /// in the same form as accepted by other methods (i.e., relative to the bucket) in the lexicographic order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if listing fails.
+    async fn list_raw(&self, bucket: Bucket, prefix: &str)
+        -> Result<Vec<String>, ObjectStoreError>;
+
+    /// Fetches the value for the given key from the given bucket as a stream of chunks. The default implementation
+    /// fetches the entire value using [`Self::get_raw()`]; stores should override it to avoid buffering large objects
+    /// in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an object with the `key` does not exist or cannot be accessed. Errors occurring
+    /// after the stream is returned are yielded by the stream.
+    async fn get_stream_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+    ) -> Result<ObjectStream, ObjectStoreError> {
+        let value = self.get_raw(bucket, key).await?;
+        Ok(stream::once(future::ready(Ok(Bytes::from(value)))).boxed())
+    }
+
+    /// Stores the value supplied as a stream of chunks associating it with the key into the given bucket.
+    /// If the key already exists, the value is replaced. The default implementation collects the entire value
+    /// and stores it using [`Self::put_raw()`]; stores should override it to avoid buffering large objects in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream yields an error, or the insertion / replacement operation fails.
+    async fn put_stream_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        mut value: ObjectStream,
+    ) -> Result<(), ObjectStoreError> {
+        let mut buffer = vec![];
+        while let Some(chunk) = value.try_next().await? {
+            buffer.extend_from_slice(&chunk);
+        }
+        self.put_raw(bucket, key, buffer).await
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String;
 }
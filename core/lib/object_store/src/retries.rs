@@ -5,7 +5,7 @@ use rand::Rng;
 
 use crate::{
     metrics::OBJECT_STORE_METRICS,
-    raw::{Bucket, ObjectStore, ObjectStoreError},
+    raw::{Bucket, ObjectStore, ObjectStoreError, ObjectStream},
 };
 
 /// Timeout for a single object store operation attempt. If a single get/put/remove call
@@ -21,6 +21,8 @@ enum Request<'a> {
     Get(Bucket, &'a str),
     Put(Bucket, &'a str),
     Remove(Bucket, &'a str),
+    List(Bucket, &'a str),
+    GetStream(Bucket, &'a str),
 }
 
 impl Request<'_> {
@@ -141,6 +143,44 @@ impl<S: ObjectStore> ObjectStore for StoreWithRetries<S> {
             .await
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        Request::List(bucket, prefix)
+            .retry(&self.inner, self.max_retries, || {
+                self.inner.list_raw(bucket, prefix)
+            })
+            .await
+    }
+
+    /// Only initiating the stream is retried; errors yielded by the stream are propagated as-is.
+    async fn get_stream_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+    ) -> Result<ObjectStream, ObjectStoreError> {
+        Request::GetStream(bucket, key)
+            .retry(&self.inner, self.max_retries, || {
+                self.inner.get_stream_raw(bucket, key)
+            })
+            .await
+    }
+
+    /// Streamed uploads are not retried since the stream cannot be replayed.
+    async fn put_stream_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: ObjectStream,
+    ) -> Result<(), ObjectStoreError> {
+        let latency = OBJECT_STORE_METRICS.start_store(bucket);
+        let result = self.inner.put_stream_raw(bucket, key, value).await;
+        latency.observe();
+        result
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         self.inner.storage_prefix_raw(bucket)
     }
@@ -6,10 +6,20 @@ use anyhow::Context;
 use async_trait::async_trait;
 use aws_config::{meta::region::RegionProviderChain, BehaviorVersion, ConfigLoader, Region};
 use aws_runtime::env_config::file::{EnvConfigFileKind, EnvConfigFiles};
-use aws_sdk_s3::{error::SdkError, primitives::ByteStreamError, Client};
+use aws_sdk_s3::{
+    error::SdkError,
+    primitives::{ByteStream, ByteStreamError},
+    types::{CompletedMultipartUpload, CompletedPart},
+    Client,
+};
+use futures::{stream, StreamExt, TryStreamExt};
 use http::StatusCode;
 
-use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+use crate::raw::{Bucket, ObjectStore, ObjectStoreError, ObjectStream};
+
+/// Minimum size of a part in streamed (multipart) uploads. S3 requires all parts except for the last one
+/// to be at least 5 MiB.
+const MULTIPART_PART_SIZE: usize = 8 << 20;
 
 /// [`ObjectStore`] implementation based on AWS S3.
 pub struct S3Store {
@@ -82,6 +92,67 @@ impl S3Store {
     fn filename(bucket: &str, filename: &str) -> String {
         format!("{bucket}/{filename}")
     }
+
+    /// Reads the next part of a multipart upload from the stream. Returns the part data and a flag whether
+    /// the stream has ended.
+    async fn read_part(value: &mut ObjectStream) -> Result<(Vec<u8>, bool), ObjectStoreError> {
+        let mut part = Vec::with_capacity(MULTIPART_PART_SIZE);
+        while part.len() < MULTIPART_PART_SIZE {
+            let Some(chunk) = value.try_next().await? else {
+                return Ok((part, true));
+            };
+            part.extend_from_slice(&chunk);
+        }
+        Ok((part, false))
+    }
+
+    async fn upload_parts(
+        &self,
+        filename: &str,
+        upload_id: &str,
+        mut part: Vec<u8>,
+        mut value: ObjectStream,
+    ) -> Result<(), ObjectStoreError> {
+        let mut completed_parts = vec![];
+        let mut part_number = 1;
+        loop {
+            let output = self
+                .client
+                .upload_part()
+                .bucket(self.bucket_prefix.clone())
+                .key(filename)
+                .upload_id(upload_id)
+                .part_number(part_number)
+                .body(ByteStream::from(part))
+                .send()
+                .await?;
+            let completed_part = CompletedPart::builder()
+                .set_e_tag(output.e_tag().map(str::to_owned))
+                .part_number(part_number)
+                .build();
+            completed_parts.push(completed_part);
+
+            let (next_part, is_last) = Self::read_part(&mut value).await?;
+            if next_part.is_empty() && is_last {
+                break;
+            }
+            part = next_part;
+            part_number += 1;
+        }
+
+        let completed_upload = CompletedMultipartUpload::builder()
+            .set_parts(Some(completed_parts))
+            .build();
+        self.client
+            .complete_multipart_upload()
+            .bucket(self.bucket_prefix.clone())
+            .key(filename)
+            .upload_id(upload_id)
+            .multipart_upload(completed_upload)
+            .send()
+            .await?;
+        Ok(())
+    }
 }
 
 impl From<ByteStreamError> for ObjectStoreError {
@@ -215,6 +286,115 @@ impl ObjectStore for S3Store {
         Ok(())
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        let bucket_prefix = Self::filename(bucket.as_str(), "");
+        tracing::trace!(
+            "Listing S3 objects with prefix {bucket_prefix}{prefix} in bucket {}",
+            self.bucket_prefix
+        );
+
+        let mut keys = vec![];
+        let mut continuation_token = None;
+        loop {
+            let output = self
+                .client
+                .list_objects_v2()
+                .bucket(self.bucket_prefix.clone())
+                .prefix(format!("{bucket_prefix}{prefix}"))
+                .set_continuation_token(continuation_token)
+                .send()
+                .await?;
+            keys.extend(output.contents().iter().filter_map(|object| {
+                object
+                    .key()?
+                    .strip_prefix(&bucket_prefix)
+                    .map(str::to_owned)
+            }));
+            continuation_token = output.next_continuation_token().map(str::to_owned);
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
+    async fn get_stream_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+    ) -> Result<ObjectStream, ObjectStoreError> {
+        let filename = Self::filename(bucket.as_str(), key);
+        tracing::trace!(
+            "Streaming data from S3 for key {filename} from bucket {}",
+            self.bucket_prefix
+        );
+
+        let get_object_output = self
+            .client
+            .get_object()
+            .bucket(self.bucket_prefix.clone())
+            .key(filename)
+            .send()
+            .await?;
+        let chunks = stream::try_unfold(get_object_output.body, |mut body| async move {
+            let chunk = body.try_next().await?;
+            Ok(chunk.map(|chunk| (chunk, body)))
+        });
+        Ok(chunks.boxed())
+    }
+
+    async fn put_stream_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        mut value: ObjectStream,
+    ) -> Result<(), ObjectStoreError> {
+        let (first_part, is_last) = Self::read_part(&mut value).await?;
+        if is_last {
+            // The object is small enough to be uploaded in a single request.
+            return self.put_raw(bucket, key, first_part).await;
+        }
+
+        let filename = Self::filename(bucket.as_str(), key);
+        tracing::trace!(
+            "Streaming data to S3 for key {filename} from bucket {}",
+            self.bucket_prefix
+        );
+        let upload = self
+            .client
+            .create_multipart_upload()
+            .bucket(self.bucket_prefix.clone())
+            .key(&filename)
+            .send()
+            .await?;
+        let upload_id = upload
+            .upload_id()
+            .context("S3 did not return multipart upload ID")?;
+
+        let result = self
+            .upload_parts(&filename, upload_id, first_part, value)
+            .await;
+        if result.is_err() {
+            let abort_result = self
+                .client
+                .abort_multipart_upload()
+                .bucket(self.bucket_prefix.clone())
+                .key(&filename)
+                .upload_id(upload_id)
+                .send()
+                .await;
+            if let Err(err) = abort_result {
+                tracing::warn!("Failed aborting multipart upload for key {filename}: {err}");
+            }
+        }
+        result
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         format!(
             "{}/{}/{}",
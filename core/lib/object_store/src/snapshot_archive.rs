@@ -0,0 +1,509 @@
+//! Self-describing single-file archives of application-level snapshots.
+//!
+//! An archive is laid out as follows:
+//!
+//! ```text
+//! [snapshot objects][manifest JSON][manifest length: u64 LE][magic: 8 bytes]
+//! ```
+//!
+//! Snapshot objects are copied verbatim from [`Bucket::StorageSnapshot`], so an archive can be read
+//! as a read-only [`ObjectStore`]. The manifest lists offsets, sizes and SHA-256 checksums of all objects,
+//! together with the snapshot header and other data necessary to recover from the snapshot without querying the main node.
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::SeekFrom,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::{
+    fs,
+    io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
+};
+use zksync_types::{snapshots::SnapshotHeader, tokens::TokenInfo, ProtocolVersionId, H256};
+
+use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+
+/// Magic bytes at the end of a snapshot archive.
+const MAGIC: &[u8; 8] = b"ZKSNAP01";
+/// Size of the archive trailer: the manifest length followed by magic bytes.
+const TRAILER_SIZE: usize = 16;
+/// Supported version of the archive format.
+const FORMAT_VERSION: u32 = 1;
+
+/// Information about a single snapshot object in a [snapshot archive](SnapshotArchiveManifest).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotArchiveEntry {
+    /// Key of the object in [`Bucket::StorageSnapshot`].
+    pub key: String,
+    /// Offset of the object from the start of the archive.
+    pub offset: u64,
+    /// Object size in bytes.
+    pub size: u64,
+    /// SHA-256 digest of the object.
+    pub sha256: H256,
+}
+
+/// Snapshot metadata stored in a [snapshot archive](SnapshotArchiveManifest). Contains everything that is normally
+/// fetched from the main node during snapshot recovery.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotArchiveMetadata {
+    /// Snapshot header. Object paths in the header are keys of the archived objects.
+    pub header: SnapshotHeader,
+    /// Timestamp of the snapshot L1 batch.
+    pub l1_batch_timestamp: u64,
+    /// Root hash of the snapshot L1 batch.
+    pub l1_batch_root_hash: H256,
+    /// Timestamp of the last L2 block in the snapshot L1 batch.
+    pub l2_block_timestamp: u64,
+    /// Hash of the last L2 block in the snapshot L1 batch.
+    pub l2_block_hash: H256,
+    /// Protocol version of the last L2 block in the snapshot L1 batch.
+    pub protocol_version: ProtocolVersionId,
+    /// Tokens as of the last L2 block in the snapshot L1 batch.
+    pub tokens: Vec<TokenInfo>,
+}
+
+/// Manifest of a snapshot archive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotArchiveManifest {
+    /// Version of the archive format.
+    pub format_version: u32,
+    /// Snapshot metadata.
+    pub snapshot: SnapshotArchiveMetadata,
+    /// Archived objects ordered by their offset.
+    pub entries: Vec<SnapshotArchiveEntry>,
+}
+
+/// Writer of snapshot archives. Objects are written to a temporary file, which is moved to the target path
+/// once the manifest is written, so that an incomplete archive is never observable at the target path.
+#[derive(Debug)]
+pub struct SnapshotArchiveWriter {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    file: fs::File,
+    offset: u64,
+    entries: Vec<SnapshotArchiveEntry>,
+}
+
+impl SnapshotArchiveWriter {
+    /// Creates a writer for a new archive at the specified path, which must not exist.
+    ///
+    /// # Errors
+    ///
+    /// Propagates I/O errors. Errors if the target path exists.
+    pub async fn create(path: &Path) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            !fs::try_exists(path).await?,
+            "snapshot archive `{}` already exists",
+            path.display()
+        );
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+        let file = fs::File::create(&tmp_path)
+            .await
+            .with_context(|| format!("failed creating file `{}`", tmp_path.display()))?;
+        Ok(Self {
+            path: path.to_owned(),
+            tmp_path,
+            file,
+            offset: 0,
+            entries: vec![],
+        })
+    }
+
+    /// Appends an object with the specified key to the archive.
+    ///
+    /// # Errors
+    ///
+    /// Propagates I/O errors. Errors if an object with the same key was already added.
+    pub async fn add(&mut self, key: &str, data: &[u8]) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.entries.iter().all(|entry| entry.key != key),
+            "object `{key}` is already added to the snapshot archive"
+        );
+        self.file
+            .write_all(data)
+            .await
+            .with_context(|| format!("failed writing object `{key}` to snapshot archive"))?;
+        let size = data.len() as u64;
+        self.entries.push(SnapshotArchiveEntry {
+            key: key.to_owned(),
+            offset: self.offset,
+            size,
+            sha256: H256(Sha256::digest(data).into()),
+        });
+        self.offset += size;
+        Ok(())
+    }
+
+    /// Writes the manifest and moves the archive to the target path.
+    ///
+    /// # Errors
+    ///
+    /// Propagates I/O errors. Errors if the objects referenced by the snapshot header are not in the archive.
+    pub async fn finish(
+        mut self,
+        snapshot: SnapshotArchiveMetadata,
+    ) -> anyhow::Result<SnapshotArchiveManifest> {
+        let manifest = SnapshotArchiveManifest {
+            format_version: FORMAT_VERSION,
+            snapshot,
+            entries: self.entries,
+        };
+        manifest.check_header_objects()?;
+
+        let manifest_bytes = serde_json::to_vec(&manifest)
+            .context("failed serializing snapshot archive manifest")?;
+        self.file.write_all(&manifest_bytes).await?;
+        self.file
+            .write_all(&(manifest_bytes.len() as u64).to_le_bytes())
+            .await?;
+        self.file.write_all(MAGIC).await?;
+        self.file.sync_all().await?;
+        drop(self.file);
+
+        fs::rename(&self.tmp_path, &self.path)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed moving snapshot archive to `{}`",
+                    self.path.display()
+                )
+            })?;
+        tracing::info!(
+            "Written snapshot archive for L1 batch #{} with {} objects ({} bytes) to `{}`",
+            manifest.snapshot.header.l1_batch_number,
+            manifest.entries.len(),
+            self.offset,
+            self.path.display()
+        );
+        Ok(manifest)
+    }
+}
+
+impl SnapshotArchiveManifest {
+    /// Checks that this manifest is well-formed: it has a supported format version, object keys are unique,
+    /// and all objects referenced by the snapshot header are present. Object offsets are not checked since they
+    /// depend on the archive size.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the manifest is malformed.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.format_version == FORMAT_VERSION,
+            "unsupported snapshot archive format version {}; expected {FORMAT_VERSION}",
+            self.format_version
+        );
+        let mut keys = HashSet::with_capacity(self.entries.len());
+        for entry in &self.entries {
+            anyhow::ensure!(
+                keys.insert(entry.key.as_str()),
+                "object `{}` is duplicated in snapshot archive",
+                entry.key
+            );
+        }
+        self.check_header_objects()
+    }
+
+    fn check_header_objects(&self) -> anyhow::Result<()> {
+        let header = &self.snapshot.header;
+        let referenced_keys = header
+            .storage_logs_chunks
+            .iter()
+            .map(|chunk| &chunk.filepath)
+            .chain([&header.factory_deps_filepath]);
+        for key in referenced_keys {
+            anyhow::ensure!(
+                self.entries.iter().any(|entry| entry.key == *key),
+                "object `{key}` referenced by the snapshot header is missing in the snapshot archive"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Snapshot archive opened for reading. Exposes archived objects as a read-only [`ObjectStore`];
+/// checksums of the objects are verified on each read.
+#[derive(Debug)]
+pub struct SnapshotArchive {
+    path: PathBuf,
+    manifest: SnapshotArchiveManifest,
+    entries: HashMap<String, SnapshotArchiveEntry>,
+}
+
+impl SnapshotArchive {
+    /// Opens an archive at the specified path and reads its manifest.
+    ///
+    /// # Errors
+    ///
+    /// Propagates I/O errors. Errors if the archive is malformed.
+    pub async fn open(path: &Path) -> anyhow::Result<Self> {
+        let mut file = fs::File::open(path)
+            .await
+            .with_context(|| format!("failed opening snapshot archive `{}`", path.display()))?;
+        let file_size = file.metadata().await?.len();
+        anyhow::ensure!(
+            file_size >= TRAILER_SIZE as u64,
+            "snapshot archive `{}` is too small",
+            path.display()
+        );
+        let trailer_offset = file_size - TRAILER_SIZE as u64;
+        file.seek(SeekFrom::Start(trailer_offset)).await?;
+        let mut trailer = [0_u8; TRAILER_SIZE];
+        file.read_exact(&mut trailer).await?;
+        let (manifest_size, magic) = trailer.split_at(8);
+        anyhow::ensure!(
+            magic == MAGIC,
+            "`{}` is not a snapshot archive",
+            path.display()
+        );
+        let manifest_size = u64::from_le_bytes(manifest_size.try_into().unwrap());
+        let manifest_offset = trailer_offset
+            .checked_sub(manifest_size)
+            .context("invalid manifest size in snapshot archive")?;
+
+        file.seek(SeekFrom::Start(manifest_offset)).await?;
+        let mut manifest_bytes = vec![0_u8; usize::try_from(manifest_size)?];
+        file.read_exact(&mut manifest_bytes).await?;
+        let manifest: SnapshotArchiveManifest = serde_json::from_slice(&manifest_bytes)
+            .context("failed deserializing snapshot archive manifest")?;
+        manifest.validate()?;
+
+        let mut entries = HashMap::with_capacity(manifest.entries.len());
+        for entry in &manifest.entries {
+            let end = entry.offset.checked_add(entry.size);
+            anyhow::ensure!(
+                end.is_some_and(|end| end <= manifest_offset),
+                "object `{}` is out of bounds of snapshot archive",
+                entry.key
+            );
+            entries.insert(entry.key.clone(), entry.clone());
+        }
+
+        Ok(Self {
+            path: path.to_owned(),
+            manifest,
+            entries,
+        })
+    }
+
+    /// Returns the manifest of this archive.
+    pub fn manifest(&self) -> &SnapshotArchiveManifest {
+        &self.manifest
+    }
+
+    async fn read_entry(&self, entry: &SnapshotArchiveEntry) -> Result<Vec<u8>, ObjectStoreError> {
+        let mut file = fs::File::open(&self.path).await?;
+        file.seek(SeekFrom::Start(entry.offset)).await?;
+        let size = usize::try_from(entry.size).map_err(|err| ObjectStoreError::Other {
+            is_retriable: false,
+            source: err.into(),
+        })?;
+        let mut data = vec![0_u8; size];
+        file.read_exact(&mut data).await?;
+
+        let sha256 = H256(Sha256::digest(&data).into());
+        if sha256 != entry.sha256 {
+            let err = anyhow::anyhow!(
+                "checksum mismatch for object `{}` in snapshot archive: expected {:?}, got {sha256:?}",
+                entry.key,
+                entry.sha256
+            );
+            return Err(ObjectStoreError::Other {
+                is_retriable: false,
+                source: err.into(),
+            });
+        }
+        Ok(data)
+    }
+
+    fn read_only_error(&self) -> ObjectStoreError {
+        let err = anyhow::anyhow!("snapshot archive `{}` is read-only", self.path.display());
+        ObjectStoreError::Other {
+            is_retriable: false,
+            source: err.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for SnapshotArchive {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let entry = (bucket == Bucket::StorageSnapshot)
+            .then(|| self.entries.get(key))
+            .flatten();
+        let Some(entry) = entry else {
+            let err = anyhow::anyhow!("object `{bucket}/{key}` is not in snapshot archive");
+            return Err(ObjectStoreError::KeyNotFound(err.into()));
+        };
+        self.read_entry(entry).await
+    }
+
+    async fn put_raw(
+        &self,
+        _bucket: Bucket,
+        _key: &str,
+        _value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        Err(self.read_only_error())
+    }
+
+    async fn remove_raw(&self, _bucket: Bucket, _key: &str) -> Result<(), ObjectStoreError> {
+        Err(self.read_only_error())
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        if bucket != Bucket::StorageSnapshot {
+            return Ok(vec![]);
+        }
+        let mut keys: Vec<_> = self
+            .entries
+            .keys()
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect();
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        format!("{}/{bucket}", self.path.display())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use tempfile::TempDir;
+    use zksync_types::{snapshots::SnapshotStorageLogsChunkMetadata, L1BatchNumber, L2BlockNumber};
+
+    use super::*;
+
+    fn test_metadata() -> SnapshotArchiveMetadata {
+        SnapshotArchiveMetadata {
+            header: SnapshotHeader {
+                version: 1,
+                l1_batch_number: L1BatchNumber(5),
+                l2_block_number: L2BlockNumber(10),
+                storage_logs_chunks: vec![SnapshotStorageLogsChunkMetadata {
+                    chunk_id: 0,
+                    filepath: "logs_0".to_owned(),
+                }],
+                factory_deps_filepath: "deps".to_owned(),
+                base_l1_batch_number: None,
+            },
+            l1_batch_timestamp: 100,
+            l1_batch_root_hash: H256::repeat_byte(1),
+            l2_block_timestamp: 101,
+            l2_block_hash: H256::repeat_byte(2),
+            protocol_version: ProtocolVersionId::latest(),
+            tokens: vec![],
+        }
+    }
+
+    async fn write_archive(path: &Path) -> SnapshotArchiveManifest {
+        let mut writer = SnapshotArchiveWriter::create(path).await.unwrap();
+        writer.add("deps", b"factory deps").await.unwrap();
+        writer.add("logs_0", &[42; 1_000]).await.unwrap();
+        writer.finish(test_metadata()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn writing_and_reading_archive() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("snapshot.bin");
+        let manifest = write_archive(&path).await;
+        assert_eq!(manifest.entries.len(), 2);
+        assert!(!fs::try_exists(path.with_extension("bin.tmp"))
+            .await
+            .unwrap());
+
+        let archive = SnapshotArchive::open(&path).await.unwrap();
+        assert_eq!(*archive.manifest(), manifest);
+        let deps = archive
+            .get_raw(Bucket::StorageSnapshot, "deps")
+            .await
+            .unwrap();
+        assert_eq!(deps, b"factory deps");
+        let logs = archive
+            .get_raw(Bucket::StorageSnapshot, "logs_0")
+            .await
+            .unwrap();
+        assert_eq!(logs, vec![42_u8; 1_000]);
+
+        let keys = archive.list_raw(Bucket::StorageSnapshot, "").await.unwrap();
+        assert_eq!(keys, ["deps", "logs_0"]);
+        let err = archive
+            .get_raw(Bucket::StorageSnapshot, "missing")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+        let err = archive
+            .get_raw(Bucket::ProofsFri, "deps")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+        let err = archive
+            .put_raw(Bucket::StorageSnapshot, "deps", vec![])
+            .await
+            .unwrap_err();
+        assert!(!err.is_retriable());
+    }
+
+    #[tokio::test]
+    async fn corrupted_object_is_detected() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("snapshot.bin");
+        let manifest = write_archive(&path).await;
+
+        let mut bytes = fs::read(&path).await.unwrap();
+        let logs_offset = usize::try_from(manifest.entries[1].offset).unwrap();
+        bytes[logs_offset + 10] ^= 1;
+        fs::write(&path, bytes).await.unwrap();
+
+        let archive = SnapshotArchive::open(&path).await.unwrap();
+        archive
+            .get_raw(Bucket::StorageSnapshot, "deps")
+            .await
+            .unwrap();
+        let err = archive
+            .get_raw(Bucket::StorageSnapshot, "logs_0")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn archive_with_missing_objects_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("snapshot.bin");
+        let mut writer = SnapshotArchiveWriter::create(&path).await.unwrap();
+        writer.add("deps", b"factory deps").await.unwrap();
+        let err = writer.finish(test_metadata()).await.unwrap_err();
+        assert!(err.to_string().contains("logs_0"), "{err:#}");
+        assert!(!fs::try_exists(&path).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn non_archive_file_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("snapshot.bin");
+        fs::write(&path, [0_u8; 64]).await.unwrap();
+        let err = SnapshotArchive::open(&path).await.unwrap_err();
+        assert!(
+            err.to_string().contains("not a snapshot archive"),
+            "{err:#}"
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! Data availability backpressure shared between the state keeper and the API server.
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_config::configs::chain::DaBackpressureConfig;
+use zksync_health_check::{CheckHealth, Health, HealthStatus};
+use zksync_node_framework::Resource;
+use zksync_types::pubdata_da::DataAvailabilityBacklog;
+
+/// Reason for applying backpressure.
+#[derive(Debug, Clone, PartialEq, Serialize, thiserror::Error)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum DaBackpressureReason {
+    #[error("{pending} L1 batches await DA inclusion, while the limit is {limit}")]
+    TooManyPendingL1Batches { pending: u32, limit: u32 },
+    #[error("oldest L1 batch awaiting DA inclusion was sealed {age:?} ago, while the limit is {limit:?}")]
+    PendingL1BatchTooOld { age: Duration, limit: Duration },
+}
+
+/// State of the data availability backlog as observed by the state keeper.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DaBackpressureState {
+    /// Number of sealed L1 batches awaiting DA inclusion.
+    pub pending_l1_batches: u32,
+    /// Age of the oldest sealed L1 batch awaiting DA inclusion.
+    pub oldest_pending_l1_batch_age: Option<Duration>,
+    /// Reason for applying backpressure. `None` if backpressure is not applied.
+    pub reason: Option<DaBackpressureReason>,
+}
+
+impl DaBackpressureState {
+    /// Computes the backpressure state for the DA `backlog` observed at `now_millis` (milliseconds since UNIX epoch).
+    pub fn new(
+        config: &DaBackpressureConfig,
+        backlog: &DataAvailabilityBacklog,
+        now_millis: u64,
+    ) -> Self {
+        let oldest_pending_l1_batch_age = backlog.oldest_pending_sealed_at.map(|sealed_at| {
+            let sealed_at_millis = u64::try_from(sealed_at.timestamp_millis()).unwrap_or(0);
+            Duration::from_millis(now_millis.saturating_sub(sealed_at_millis))
+        });
+
+        let reason = if let Some(limit) = config
+            .max_pending_l1_batches
+            .filter(|limit| backlog.pending_l1_batches > limit.get())
+        {
+            Some(DaBackpressureReason::TooManyPendingL1Batches {
+                pending: backlog.pending_l1_batches,
+                limit: limit.get(),
+            })
+        } else {
+            match (oldest_pending_l1_batch_age, config.max_pending_l1_batch_age) {
+                (Some(age), Some(limit)) if age > limit => {
+                    Some(DaBackpressureReason::PendingL1BatchTooOld { age, limit })
+                }
+                _ => None,
+            }
+        };
+
+        Self {
+            pending_l1_batches: backlog.pending_l1_batches,
+            oldest_pending_l1_batch_age,
+            reason,
+        }
+    }
+}
+
+/// Shared handle for data availability backpressure. Backpressure is applied by the state keeper (which stops
+/// including new transactions) and the API server (which rejects submitted transactions).
+///
+/// The state keeper updates the handle while checking the DA backlog. If the API server runs in a separate process,
+/// this resource is not available to it, so the API server maintains its own handle by polling the DA backlog
+/// from Postgres.
+#[derive(Debug, Clone)]
+pub struct DaBackpressure(Arc<watch::Sender<DaBackpressureState>>);
+
+impl Default for DaBackpressure {
+    fn default() -> Self {
+        Self(Arc::new(watch::channel(DaBackpressureState::default()).0))
+    }
+}
+
+impl Resource for DaBackpressure {
+    fn name() -> String {
+        "common/da_backpressure".into()
+    }
+}
+
+impl DaBackpressure {
+    pub fn update(&self, state: DaBackpressureState) {
+        self.0.send_replace(state);
+    }
+
+    /// Returns the reason for applying backpressure, or `None` if backpressure is not applied.
+    pub fn reason(&self) -> Option<DaBackpressureReason> {
+        self.0.borrow().reason.clone()
+    }
+}
+
+#[async_trait]
+impl CheckHealth for DaBackpressure {
+    fn name(&self) -> &'static str {
+        "da_backpressure"
+    }
+
+    async fn check_health(&self) -> Health {
+        Health::from(&*self.0.borrow())
+    }
+}
+
+impl From<&DaBackpressureState> for Health {
+    fn from(state: &DaBackpressureState) -> Self {
+        #[derive(Debug, Serialize)]
+        struct DaBackpressureHealthDetails<'a> {
+            pending_l1_batches: u32,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            oldest_pending_l1_batch_age_secs: Option<u64>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            reason: Option<&'a DaBackpressureReason>,
+        }
+
+        let status = if state.reason.is_some() {
+            HealthStatus::Affected
+        } else {
+            HealthStatus::Ready
+        };
+        Health::from(status).with_details(DaBackpressureHealthDetails {
+            pending_l1_batches: state.pending_l1_batches,
+            oldest_pending_l1_batch_age_secs: state
+                .oldest_pending_l1_batch_age
+                .map(|age| age.as_secs()),
+            reason: state.reason.as_ref(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use assert_matches::assert_matches;
+    use chrono::DateTime;
+
+    use super::*;
+
+    #[test]
+    fn computing_backpressure_state() {
+        let config = DaBackpressureConfig {
+            max_pending_l1_batches: NonZeroU32::new(2),
+            max_pending_l1_batch_age: Some(Duration::from_secs(600)),
+            check_interval: Duration::ZERO,
+        };
+        let now_millis = 1_700_000_000_000;
+
+        let state =
+            DaBackpressureState::new(&config, &DataAvailabilityBacklog::default(), now_millis);
+        assert_eq!(state.reason, None);
+
+        let backlog = DataAvailabilityBacklog {
+            pending_l1_batches: 3,
+            oldest_pending_sealed_at: None,
+        };
+        let state = DaBackpressureState::new(&config, &backlog, now_millis);
+        assert_matches!(
+            state.reason,
+            Some(DaBackpressureReason::TooManyPendingL1Batches {
+                pending: 3,
+                limit: 2
+            })
+        );
+
+        let backlog = DataAvailabilityBacklog {
+            pending_l1_batches: 1,
+            oldest_pending_sealed_at: DateTime::from_timestamp_millis(1_700_000_000_000 - 700_000),
+        };
+        let state = DaBackpressureState::new(&config, &backlog, now_millis);
+        assert_eq!(
+            state.oldest_pending_l1_batch_age,
+            Some(Duration::from_secs(700))
+        );
+        assert_matches!(
+            state.reason,
+            Some(DaBackpressureReason::PendingL1BatchTooOld { age, .. })
+                if age == Duration::from_secs(700)
+        );
+
+        let backlog = DataAvailabilityBacklog {
+            pending_l1_batches: 1,
+            oldest_pending_sealed_at: DateTime::from_timestamp_millis(1_700_000_000_000 - 60_000),
+        };
+        let state = DaBackpressureState::new(&config, &backlog, now_millis);
+        assert_eq!(state.reason, None);
+    }
+
+    #[tokio::test]
+    async fn da_backpressure_health() {
+        let backpressure = DaBackpressure::default();
+        let health = backpressure.check_health().await;
+        assert_matches!(health.status(), HealthStatus::Ready);
+
+        backpressure.update(DaBackpressureState {
+            pending_l1_batches: 11,
+            oldest_pending_l1_batch_age: Some(Duration::from_secs(60)),
+            reason: Some(DaBackpressureReason::TooManyPendingL1Batches {
+                pending: 11,
+                limit: 10,
+            }),
+        });
+        assert!(backpressure.reason().is_some());
+        let health = backpressure.check_health().await;
+        assert_matches!(health.status(), HealthStatus::Affected);
+        let details = health.details().unwrap();
+        assert_eq!(details["reason"]["kind"], "too_many_pending_l1_batches");
+
+        backpressure.update(DaBackpressureState::default());
+        assert_eq!(backpressure.reason(), None);
+        let health = backpressure.check_health().await;
+        assert_matches!(health.status(), HealthStatus::Ready);
+    }
+}
@@ -5,6 +5,7 @@ use zksync_types::{commitment::L1BatchCommitmentMode, pubdata_da::PubdataSending
 
 pub mod api;
 pub mod contracts;
+pub mod da_backpressure;
 pub mod tree;
 
 #[derive(Debug, Clone, Copy)]
@@ -33,6 +33,38 @@ impl TreeEntryWithProof {
     }
 }
 
+/// Entry in a [`TreeMultiProof`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TreeMultiProofEntry {
+    /// Hashed key of the entry.
+    pub key: U256,
+    #[serde(default, skip_serializing_if = "H256::is_zero")]
+    pub value: H256,
+    #[serde(default, skip_serializing_if = "TreeEntryWithProof::is_zero")]
+    pub index: u64,
+    /// Length of the Merkle path for the entry with the leading hashes for empty subtrees skipped.
+    pub path_length: usize,
+}
+
+/// Compact proof for multiple entries in the Merkle tree. Unlike separate proofs for each entry, the multi-proof
+/// doesn't contain hashes computable from the entries, and contains each remaining hash only once.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TreeMultiProof {
+    /// Entries ordered by the hashed key. Keys are unique.
+    pub entries: Vec<TreeMultiProofEntry>,
+    /// Hashes in the order they are consumed during verification: level by level starting from leaves,
+    /// and by the key within each level.
+    pub hashes: Vec<H256>,
+}
+
+/// Proof that the Merkle tree contains no entries in a certain inclusive range of hashed keys. Consists of proofs
+/// for the range boundaries; both must be missing from the tree.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TreeRangeAbsenceProof {
+    pub start: TreeEntryWithProof,
+    pub end: TreeEntryWithProof,
+}
+
 /// Client-side tree API error used by [`TreeApiClient`].
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
@@ -65,6 +97,22 @@ pub trait TreeApiClient: 'static + Send + Sync + fmt::Debug {
         l1_batch_number: L1BatchNumber,
         hashed_keys: Vec<U256>,
     ) -> Result<Vec<TreeEntryWithProof>, TreeApiError>;
+
+    /// Obtains a compact multi-proof for the specified `hashed_keys` at the specified tree version.
+    async fn get_multi_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeMultiProof, TreeApiError>;
+
+    /// Obtains a proof that there are no entries with hashed keys in the `start_key..=end_key` range
+    /// at the specified tree version. Returns `Ok(None)` if the range is not empty.
+    async fn get_range_absence_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        start_key: U256,
+        end_key: U256,
+    ) -> Result<Option<TreeRangeAbsenceProof>, TreeApiError>;
 }
 
 impl Resource<resource::Shared> for dyn TreeApiClient {
@@ -0,0 +1,161 @@
+//! Recovery from local snapshot archives.
+
+use std::{path::Path, sync::Arc};
+
+use async_trait::async_trait;
+use zksync_object_store::{ObjectStore, SnapshotArchive, SnapshotArchiveMetadata};
+use zksync_types::{
+    api, snapshots::SnapshotHeader, tokens::TokenInfo, Address, L1BatchNumber, L2BlockNumber, H256,
+};
+use zksync_web3_decl::error::EnrichedClientResult;
+
+use crate::SnapshotsApplierMainNodeClient;
+
+/// Source of snapshot data backed by a local [`SnapshotArchive`]. Emulates the main node API using the archive manifest,
+/// so that snapshot recovery requires neither the main node nor an object store.
+///
+/// Only the L1 batch root hash is verified against a trusted value (and storage logs are verified against it during
+/// recovery). All other metadata, such as the L2 block hash, L1 batch / L2 block timestamps, the protocol version
+/// and the token list, is taken from the archive manifest as is. Thus, the archive itself must come from a trusted
+/// source.
+#[derive(Debug, Clone)]
+pub struct SnapshotArchiveSource {
+    archive: Arc<SnapshotArchive>,
+}
+
+impl SnapshotArchiveSource {
+    /// Opens the archive at the specified path and checks that it corresponds to the trusted L1 batch root hash.
+    /// This only checks the archive manifest; the snapshot data is verified against the root hash during recovery.
+    pub async fn open(path: &Path, expected_root_hash: H256) -> anyhow::Result<Self> {
+        let archive = SnapshotArchive::open(path).await?;
+        let metadata = &archive.manifest().snapshot;
+        let l1_batch_number = metadata.header.l1_batch_number;
+        anyhow::ensure!(
+            metadata.l1_batch_root_hash == expected_root_hash,
+            "snapshot archive `{}` for L1 batch #{l1_batch_number} has unexpected root hash: expected {expected_root_hash:?}, \
+             got {:?}",
+            path.display(),
+            metadata.l1_batch_root_hash
+        );
+        anyhow::ensure!(
+            metadata.header.base_l1_batch_number.is_none(),
+            "snapshot archive `{}` contains an incremental snapshot, which cannot be recovered from",
+            path.display()
+        );
+        tracing::info!(
+            "Opened snapshot archive `{}` for L1 batch #{l1_batch_number}, L2 block #{}",
+            path.display(),
+            metadata.header.l2_block_number
+        );
+        Ok(Self {
+            archive: Arc::new(archive),
+        })
+    }
+
+    fn metadata(&self) -> &SnapshotArchiveMetadata {
+        &self.archive.manifest().snapshot
+    }
+
+    pub(crate) fn l1_batch_number(&self) -> L1BatchNumber {
+        self.metadata().header.l1_batch_number
+    }
+
+    pub(crate) fn object_store(&self) -> Arc<dyn ObjectStore> {
+        self.archive.clone()
+    }
+}
+
+fn block_details_base(timestamp: u64, root_hash: H256) -> api::BlockDetailsBase {
+    api::BlockDetailsBase {
+        timestamp,
+        l1_tx_count: 0,
+        l2_tx_count: 0,
+        root_hash: Some(root_hash),
+        status: api::BlockStatus::Verified,
+        commit_tx_hash: None,
+        committed_at: None,
+        commit_tx_finality: None,
+        commit_chain_id: None,
+        prove_tx_hash: None,
+        prove_tx_finality: None,
+        proven_at: None,
+        prove_chain_id: None,
+        execute_tx_hash: None,
+        execute_tx_finality: None,
+        executed_at: None,
+        execute_chain_id: None,
+        precommit_tx_hash: None,
+        precommit_tx_finality: None,
+        precommitted_at: None,
+        precommit_chain_id: None,
+        l1_gas_price: 0,
+        l2_fair_gas_price: 0,
+        fair_pubdata_price: None,
+        base_system_contracts_hashes: Default::default(),
+    }
+}
+
+/// Only the snapshot L1 batch and L2 block are known; other blocks are reported as missing.
+#[async_trait]
+impl SnapshotsApplierMainNodeClient for SnapshotArchiveSource {
+    async fn fetch_l1_batch_details(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<api::L1BatchDetails>> {
+        let metadata = self.metadata();
+        if number != metadata.header.l1_batch_number {
+            return Ok(None);
+        }
+        Ok(Some(api::L1BatchDetails {
+            number,
+            commitment: None,
+            base: block_details_base(metadata.l1_batch_timestamp, metadata.l1_batch_root_hash),
+        }))
+    }
+
+    async fn fetch_l2_block_details(
+        &self,
+        number: L2BlockNumber,
+    ) -> EnrichedClientResult<Option<api::BlockDetails>> {
+        let metadata = self.metadata();
+        if number != metadata.header.l2_block_number {
+            return Ok(None);
+        }
+        Ok(Some(api::BlockDetails {
+            number,
+            l1_batch_number: metadata.header.l1_batch_number,
+            base: block_details_base(metadata.l2_block_timestamp, metadata.l2_block_hash),
+            operator_address: Address::zero(),
+            protocol_version: Some(metadata.protocol_version),
+        }))
+    }
+
+    async fn fetch_newest_snapshot_l1_batch_number(
+        &self,
+    ) -> EnrichedClientResult<Option<L1BatchNumber>> {
+        Ok(Some(self.l1_batch_number()))
+    }
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<SnapshotHeader>> {
+        let header = &self.metadata().header;
+        Ok((header.l1_batch_number == l1_batch_number).then(|| header.clone()))
+    }
+
+    async fn fetch_tokens(
+        &self,
+        at_l2_block: L2BlockNumber,
+    ) -> EnrichedClientResult<Vec<TokenInfo>> {
+        let metadata = self.metadata();
+        // Tokens are only stored as of the snapshot L2 block, which is the only block queried during recovery.
+        if at_l2_block != metadata.header.l2_block_number {
+            tracing::warn!(
+                "Requested tokens at L2 block #{at_l2_block}, but snapshot archive only contains tokens at L2 block #{}",
+                metadata.header.l2_block_number
+            );
+        }
+        Ok(metadata.tokens.clone())
+    }
+}
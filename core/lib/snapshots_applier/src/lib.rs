@@ -1,7 +1,13 @@
 //! Logic for applying application-level snapshots to Postgres storage.
 
 use std::{
-    cmp::Ordering, collections::HashMap, fmt, mem, num::NonZeroUsize, sync::Arc, time::Duration,
+    cmp::Ordering,
+    collections::HashMap,
+    fmt, mem,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
 };
 
 use anyhow::Context as _;
@@ -28,11 +34,21 @@ use zksync_web3_decl::{
     namespaces::{EnNamespaceClient, SnapshotsNamespaceClient, ZksNamespaceClient},
 };
 
-use self::metrics::{InitialStage, StorageLogsChunksStage, METRICS};
+pub use self::{
+    archive::SnapshotArchiveSource,
+    peers::{PeerSnapshotSource, SnapshotArchiveServer, SnapshotPeers},
+};
+use self::{
+    metrics::{InitialStage, StorageLogsChunksStage, METRICS},
+    verification::VerificationTree,
+};
 
+mod archive;
 mod metrics;
+mod peers;
 #[cfg(test)]
 mod tests;
+mod verification;
 
 #[derive(Debug, Serialize)]
 struct SnapshotsApplierHealthDetails {
@@ -253,6 +269,8 @@ pub struct SnapshotApplierTaskStats {
 pub struct SnapshotsApplierTask {
     snapshot_l1_batch: Option<L1BatchNumber>,
     drop_storage_key_preimages: bool,
+    tree_verification_path: Option<PathBuf>,
+    require_tree_verification: bool,
     config: SnapshotsApplierConfig,
     health_updater: HealthUpdater,
     connection_pool: ConnectionPool<Core>,
@@ -270,6 +288,8 @@ impl SnapshotsApplierTask {
         Self {
             snapshot_l1_batch: None,
             drop_storage_key_preimages: false,
+            tree_verification_path: None,
+            require_tree_verification: false,
             config,
             health_updater: ReactiveHealthCheck::new("snapshot_recovery").1,
             connection_pool,
@@ -278,6 +298,40 @@ impl SnapshotsApplierTask {
         }
     }
 
+    /// Creates a task recovering from a local snapshot archive instead of the main node and object store.
+    /// Since the archive is untrusted, recovered storage logs are verified against the L1 batch root hash
+    /// supplied to [`SnapshotArchiveSource::open()`] using a scratch Merkle tree; thus, recovery fails
+    /// unless [`Self::set_tree_verification_path()`] is called.
+    pub fn from_archive(
+        config: SnapshotsApplierConfig,
+        connection_pool: ConnectionPool<Core>,
+        source: SnapshotArchiveSource,
+    ) -> Self {
+        let blob_store = source.object_store();
+        let snapshot_l1_batch = source.l1_batch_number();
+        let mut this = Self::new(config, connection_pool, Box::new(source), blob_store);
+        this.snapshot_l1_batch = Some(snapshot_l1_batch);
+        this.require_tree_verification = true;
+        this
+    }
+
+    /// Creates a task recovering from a snapshot archive served by peer nodes instead of the object store.
+    /// Objects fetched from peers are checked against the archive manifest, and recovered storage logs are verified
+    /// against the L1 batch root hash fetched from the main node using a scratch Merkle tree; thus, recovery fails
+    /// unless [`Self::set_tree_verification_path()`] is called.
+    pub fn from_peers(
+        config: SnapshotsApplierConfig,
+        connection_pool: ConnectionPool<Core>,
+        source: PeerSnapshotSource,
+    ) -> Self {
+        let blob_store = source.object_store();
+        let snapshot_l1_batch = source.l1_batch_number();
+        let mut this = Self::new(config, connection_pool, Box::new(source), blob_store);
+        this.snapshot_l1_batch = Some(snapshot_l1_batch);
+        this.require_tree_verification = true;
+        this
+    }
+
     /// Checks whether the snapshot recovery is already completed.
     ///
     /// Returns `None` if no snapshot recovery information is detected in the DB.
@@ -328,6 +382,13 @@ impl SnapshotsApplierTask {
         self.drop_storage_key_preimages = true;
     }
 
+    /// Sets the directory for a scratch Merkle tree used to verify root hashes when recovering from incremental snapshots
+    /// or [snapshot archives](Self::from_archive()). Recovery from such snapshots fails if this path is not set. Each snapshot
+    /// is verified before its storage logs are recovered. The directory is removed once recovery is completed.
+    pub fn set_tree_verification_path(&mut self, path: PathBuf) {
+        self.tree_verification_path = Some(path);
+    }
+
     /// Returns the health check for snapshot recovery.
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
@@ -397,6 +458,29 @@ impl SnapshotsApplierTask {
     }
 }
 
+/// Chain of snapshots to recover from: a full snapshot optionally followed by incremental snapshots,
+/// each of which is based on the previous snapshot in the chain.
+#[derive(Debug, Default)]
+struct SnapshotChain {
+    headers: Vec<SnapshotHeader>,
+    /// Index of the snapshot currently being applied.
+    current: usize,
+}
+
+impl SnapshotChain {
+    fn current_header(&self) -> &SnapshotHeader {
+        &self.headers[self.current]
+    }
+
+    fn has_incremental_snapshots(&self) -> bool {
+        self.headers.len() > 1
+    }
+
+    fn next_header(&self) -> Option<&SnapshotHeader> {
+        self.headers.get(self.current + 1)
+    }
+}
+
 /// Strategy determining how snapshot recovery should proceed.
 #[derive(Debug, Clone, Copy)]
 enum SnapshotRecoveryStrategy {
@@ -413,7 +497,7 @@ impl SnapshotRecoveryStrategy {
         storage: &mut Connection<'_, Core>,
         main_node_client: &dyn SnapshotsApplierMainNodeClient,
         snapshot_l1_batch: Option<L1BatchNumber>,
-    ) -> Result<(Self, SnapshotRecoveryStatus), SnapshotsApplierError> {
+    ) -> Result<(Self, SnapshotRecoveryStatus, SnapshotChain), SnapshotsApplierError> {
         let latency =
             METRICS.initial_stage_duration[&InitialStage::FetchMetadataFromMainNode].start();
         let applied_snapshot_status = storage
@@ -424,7 +508,11 @@ impl SnapshotRecoveryStrategy {
         if let Some(applied_snapshot_status) = applied_snapshot_status {
             let sealed_l2_block_number = storage.blocks_dal().get_sealed_l2_block_number().await?;
             if sealed_l2_block_number.is_some() {
-                return Ok((Self::Completed, applied_snapshot_status));
+                return Ok((
+                    Self::Completed,
+                    applied_snapshot_status,
+                    SnapshotChain::default(),
+                ));
             }
 
             let l1_batch_number = applied_snapshot_status.l1_batch_number;
@@ -437,10 +525,17 @@ impl SnapshotRecoveryStrategy {
             // Old snapshots can theoretically be removed by the node, but in this case the snapshot data may be removed as well,
             // so returning an error looks appropriate here.
             let snapshot_version = Self::check_snapshot_version(snapshot_header.version)?;
+            let chain =
+                Self::resumed_snapshot_chain(main_node_client, snapshot_l1_batch, l1_batch_number)
+                    .await?;
 
             let latency = latency.observe();
             tracing::info!("Re-initialized snapshots applier after reset/failure in {latency:?}");
-            Ok((Self::Resumed(snapshot_version), applied_snapshot_status))
+            Ok((
+                Self::Resumed(snapshot_version),
+                applied_snapshot_status,
+                chain,
+            ))
         } else {
             let is_genesis_needed = storage.blocks_dal().is_genesis_needed().await?;
             if !is_genesis_needed {
@@ -450,7 +545,7 @@ impl SnapshotRecoveryStrategy {
                 return Err(SnapshotsApplierError::Fatal(err));
             }
 
-            let (recovery_status, snapshot_version) =
+            let (recovery_status, snapshot_version, chain) =
                 Self::create_fresh_recovery_status(main_node_client, snapshot_l1_batch).await?;
 
             let storage_logs_count = storage
@@ -468,14 +563,15 @@ impl SnapshotRecoveryStrategy {
 
             let latency = latency.observe();
             tracing::info!("Initialized fresh snapshots applier in {latency:?}");
-            Ok((Self::New(snapshot_version), recovery_status))
+            Ok((Self::New(snapshot_version), recovery_status, chain))
         }
     }
 
     async fn create_fresh_recovery_status(
         main_node_client: &dyn SnapshotsApplierMainNodeClient,
         snapshot_l1_batch: Option<L1BatchNumber>,
-    ) -> Result<(SnapshotRecoveryStatus, SnapshotVersion), SnapshotsApplierError> {
+    ) -> Result<(SnapshotRecoveryStatus, SnapshotVersion, SnapshotChain), SnapshotsApplierError>
+    {
         let l1_batch_number = match snapshot_l1_batch {
             Some(num) => num,
             None => main_node_client
@@ -483,11 +579,99 @@ impl SnapshotRecoveryStrategy {
                 .await?
                 .context("no snapshots on main node; snapshot recovery is impossible")?,
         };
-        let snapshot_response = main_node_client.fetch_snapshot(l1_batch_number).await?;
+        let headers = Self::fetch_snapshot_chain(main_node_client, l1_batch_number).await?;
+        if headers.len() > 1 {
+            tracing::info!(
+                "Snapshot for L1 batch #{l1_batch_number} is incremental; recovering from the full snapshot for L1 batch #{} \
+                 and {} incremental snapshot(s)",
+                headers[0].l1_batch_number,
+                headers.len() - 1
+            );
+        }
+        let (status, snapshot_version) =
+            Self::recovery_status_for_snapshot(main_node_client, &headers[0]).await?;
+        let chain = SnapshotChain {
+            headers,
+            current: 0,
+        };
+        Ok((status, snapshot_version, chain))
+    }
 
-        let snapshot = snapshot_response.with_context(|| {
-            format!("snapshot for L1 batch #{l1_batch_number} is not present on main node")
-        })?;
+    /// Determines the chain of snapshots when resuming recovery. Remaining incremental snapshots are taken
+    /// from the chain ending at the requested snapshot (or the newest one); if the snapshot being recovered
+    /// is not in this chain, recovery will finish with this snapshot.
+    async fn resumed_snapshot_chain(
+        main_node_client: &dyn SnapshotsApplierMainNodeClient,
+        snapshot_l1_batch: Option<L1BatchNumber>,
+        current_l1_batch: L1BatchNumber,
+    ) -> Result<SnapshotChain, SnapshotsApplierError> {
+        let target_l1_batch = match snapshot_l1_batch {
+            Some(num) => Some(num),
+            None => {
+                main_node_client
+                    .fetch_newest_snapshot_l1_batch_number()
+                    .await?
+            }
+        };
+        if let Some(target_l1_batch) = target_l1_batch.filter(|&num| num > current_l1_batch) {
+            let headers = Self::fetch_snapshot_chain(main_node_client, target_l1_batch).await?;
+            let position = headers
+                .iter()
+                .position(|header| header.l1_batch_number == current_l1_batch);
+            if let Some(current) = position {
+                return Ok(SnapshotChain { headers, current });
+            }
+            tracing::info!(
+                "Snapshot for L1 batch #{current_l1_batch} is not a part of the snapshot chain ending at L1 batch #{target_l1_batch}; \
+                 recovery will finish at L1 batch #{current_l1_batch}"
+            );
+        }
+
+        let headers = Self::fetch_snapshot_chain(main_node_client, current_l1_batch).await?;
+        let current = headers.len() - 1;
+        Ok(SnapshotChain { headers, current })
+    }
+
+    /// Fetches headers for the snapshot at `l1_batch_number` and all its base snapshots, starting from the full snapshot.
+    async fn fetch_snapshot_chain(
+        main_node_client: &dyn SnapshotsApplierMainNodeClient,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Vec<SnapshotHeader>, SnapshotsApplierError> {
+        let mut headers = vec![];
+        let mut next_l1_batch = Some(l1_batch_number);
+        while let Some(l1_batch_number) = next_l1_batch {
+            let snapshot = main_node_client
+                .fetch_snapshot(l1_batch_number)
+                .await?
+                .with_context(|| {
+                    format!("snapshot for L1 batch #{l1_batch_number} is not present on main node")
+                })?;
+            let snapshot_version = Self::check_snapshot_version(snapshot.version)?;
+            next_l1_batch = if snapshot_version.is_incremental() {
+                let base = snapshot.base_l1_batch_number.with_context(|| {
+                    format!("incremental snapshot for L1 batch #{l1_batch_number} doesn't specify its base snapshot")
+                })?;
+                if base >= l1_batch_number {
+                    let err = anyhow::anyhow!(
+                        "incremental snapshot for L1 batch #{l1_batch_number} has invalid base L1 batch #{base}"
+                    );
+                    return Err(err.into());
+                }
+                Some(base)
+            } else {
+                None
+            };
+            headers.push(snapshot);
+        }
+        headers.reverse();
+        Ok(headers)
+    }
+
+    async fn recovery_status_for_snapshot(
+        main_node_client: &dyn SnapshotsApplierMainNodeClient,
+        snapshot: &SnapshotHeader,
+    ) -> Result<(SnapshotRecoveryStatus, SnapshotVersion), SnapshotsApplierError> {
+        let l1_batch_number = snapshot.l1_batch_number;
         let l2_block_number = snapshot.l2_block_number;
         tracing::info!(
             "Found snapshot with data up to L1 batch #{l1_batch_number}, L2 block #{l2_block_number}, \
@@ -543,9 +727,16 @@ impl SnapshotRecoveryStrategy {
             )
         })?;
         anyhow::ensure!(
-            matches!(version, SnapshotVersion::Version0 | SnapshotVersion::Version1),
+            matches!(
+                version,
+                SnapshotVersion::Version0 | SnapshotVersion::Version1 | SnapshotVersion::Version2
+            ),
             "Cannot recover from a snapshot with version {version:?}; the only supported versions are {:?}",
-            [SnapshotVersion::Version0, SnapshotVersion::Version1]
+            [
+                SnapshotVersion::Version0,
+                SnapshotVersion::Version1,
+                SnapshotVersion::Version2
+            ]
         );
         Ok(version)
     }
@@ -569,7 +760,8 @@ impl StorageLogs {
                 let logs: SnapshotStorageLogsChunk<StorageKey> = blob_store.get(key).await?;
                 Ok(Self::V0(logs.storage_logs))
             }
-            SnapshotVersion::Version1 => {
+            // Incremental snapshots use the same format for storage logs.
+            SnapshotVersion::Version1 | SnapshotVersion::Version2 => {
                 let logs: SnapshotStorageLogsChunk = blob_store.get(key).await?;
                 Ok(Self::V1(logs.storage_logs))
             }
@@ -642,6 +834,9 @@ struct SnapshotsApplier<'a> {
     applied_snapshot_status: SnapshotRecoveryStatus,
     health_updater: &'a HealthUpdater,
     snapshot_version: SnapshotVersion,
+    snapshot_chain: SnapshotChain,
+    tree_verification_path: Option<&'a Path>,
+    require_tree_verification: bool,
     max_concurrency: usize,
     drop_storage_key_preimages: bool,
     factory_deps_recovered: bool,
@@ -666,15 +861,31 @@ impl<'a> SnapshotsApplierOrStatus<'a> {
                 return Ok((SnapshotRecoveryStrategy::Completed, status))
             }
         };
-        applier.recover_storage_logs(stop_receiver).await?;
-        for is_chunk_processed in &mut applier
-            .applied_snapshot_status
-            .storage_logs_chunks_processed
-        {
-            *is_chunk_processed = true;
+        let mut verification_tree = applier.open_verification_tree().await?;
+        loop {
+            // Verify the snapshot before recovering its storage logs, so that logs from a corrupted snapshot
+            // are never committed to Postgres.
+            if let Some(tree) = verification_tree.take() {
+                verification_tree = Some(applier.verify_root_hash(tree, stop_receiver).await?);
+            }
+            applier.recover_storage_logs(stop_receiver).await?;
+            for is_chunk_processed in &mut applier
+                .applied_snapshot_status
+                .storage_logs_chunks_processed
+            {
+                *is_chunk_processed = true;
+            }
+
+            if applier.snapshot_chain.next_header().is_none() {
+                break;
+            }
+            applier.start_incremental_snapshot().await?;
         }
 
         applier.recover_tokens().await?;
+        if let Some(tree) = verification_tree {
+            tree.remove().await.map_err(SnapshotsApplierError::Fatal)?;
+        }
         applier.tokens_recovered = true;
         applier.update_health();
         Ok((strategy, applier.applied_snapshot_status))
@@ -694,7 +905,7 @@ impl<'a> SnapshotsApplierOrStatus<'a> {
             .await?;
         let mut storage_transaction = storage.start_transaction().await?;
 
-        let (strategy, applied_snapshot_status) = SnapshotRecoveryStrategy::new(
+        let (strategy, applied_snapshot_status, snapshot_chain) = SnapshotRecoveryStrategy::new(
             &mut storage_transaction,
             main_node_client,
             task.snapshot_l1_batch,
@@ -716,6 +927,9 @@ impl<'a> SnapshotsApplierOrStatus<'a> {
             applied_snapshot_status,
             health_updater,
             snapshot_version,
+            snapshot_chain,
+            tree_verification_path: task.tree_verification_path.as_deref(),
+            require_tree_verification: task.require_tree_verification,
             max_concurrency: task.config.max_concurrency.get(),
             drop_storage_key_preimages: task.drop_storage_key_preimages,
             factory_deps_recovered: !created_from_scratch,
@@ -743,23 +957,8 @@ impl<'a> SnapshotsApplierOrStatus<'a> {
                 .snapshot_recovery_dal()
                 .insert_initial_recovery_status(&applier.applied_snapshot_status)
                 .await?;
-
-            // Insert artificial entries into the pruning log so that it's guaranteed to match the snapshot recovery metadata.
-            // This allows to not deal with the corner cases when a node was recovered from a snapshot, but its pruning log is empty.
-            storage_transaction
-                .pruning_dal()
-                .insert_soft_pruning_log(
-                    applier.applied_snapshot_status.l1_batch_number,
-                    applier.applied_snapshot_status.l2_block_number,
-                )
-                .await?;
-            storage_transaction
-                .pruning_dal()
-                .insert_hard_pruning_log(
-                    applier.applied_snapshot_status.l1_batch_number,
-                    applier.applied_snapshot_status.l2_block_number,
-                    applier.applied_snapshot_status.l1_batch_root_hash,
-                )
+            applier
+                .insert_pruning_logs(&mut storage_transaction)
                 .await?;
         }
         storage_transaction.commit().await?;
@@ -793,6 +992,213 @@ impl<'a> SnapshotsApplier<'a> {
             .update(Health::from(status).with_details(details));
     }
 
+    /// Inserts artificial entries into the pruning log so that it's guaranteed to match the snapshot recovery metadata.
+    /// This allows to not deal with the corner cases when a node was recovered from a snapshot, but its pruning log is empty.
+    async fn insert_pruning_logs(
+        &self,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<(), SnapshotsApplierError> {
+        let status = &self.applied_snapshot_status;
+        storage
+            .pruning_dal()
+            .insert_soft_pruning_log(status.l1_batch_number, status.l2_block_number)
+            .await?;
+        storage
+            .pruning_dal()
+            .insert_hard_pruning_log(
+                status.l1_batch_number,
+                status.l2_block_number,
+                status.l1_batch_root_hash,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Switches recovery to the next incremental snapshot in the chain. This updates the recovery status
+    /// and recovers factory deps introduced by the snapshot.
+    async fn start_incremental_snapshot(&mut self) -> Result<(), SnapshotsApplierError> {
+        // `unwrap()` is safe: the method is only called if there is the next snapshot
+        let header = self.snapshot_chain.next_header().unwrap();
+        tracing::info!(
+            "Starting recovery from incremental snapshot for L1 batch #{} based on L1 batch #{}",
+            header.l1_batch_number,
+            self.applied_snapshot_status.l1_batch_number
+        );
+        let (status, snapshot_version) =
+            SnapshotRecoveryStrategy::recovery_status_for_snapshot(self.main_node_client, header)
+                .await?;
+        self.applied_snapshot_status = status;
+        self.snapshot_version = snapshot_version;
+        self.snapshot_chain.current += 1;
+        self.factory_deps_recovered = false;
+        METRICS.storage_logs_chunks_count.set(
+            self.applied_snapshot_status
+                .storage_logs_chunks_processed
+                .len(),
+        );
+        METRICS.storage_logs_chunks_left_to_process.set(
+            self.applied_snapshot_status
+                .storage_logs_chunks_left_to_process(),
+        );
+        self.update_health();
+
+        let mut storage = self
+            .connection_pool
+            .connection_tagged("snapshots_applier")
+            .await?;
+        let mut storage_transaction = storage.start_transaction().await?;
+        storage_transaction
+            .snapshot_recovery_dal()
+            .update_recovery_status(&self.applied_snapshot_status)
+            .await?;
+        self.recover_factory_deps(&mut storage_transaction).await?;
+        self.insert_pruning_logs(&mut storage_transaction).await?;
+        storage_transaction.commit().await?;
+
+        self.factory_deps_recovered = true;
+        self.update_health();
+        Ok(())
+    }
+
+    async fn open_verification_tree(
+        &self,
+    ) -> Result<Option<VerificationTree>, SnapshotsApplierError> {
+        if !self.require_tree_verification && !self.snapshot_chain.has_incremental_snapshots() {
+            return Ok(None);
+        }
+        let path = self.tree_verification_path.context(
+            "recovering from incremental snapshots, snapshot archives or snapshot peers requires a path for the verification Merkle tree",
+        )?;
+        Ok(Some(VerificationTree::open(path).await?))
+    }
+
+    /// Ensures that the verification tree corresponds to the current snapshot and that its root hash matches
+    /// the root hash of the snapshot L1 batch. If the tree is behind (e.g., it corresponds to the previous snapshot,
+    /// possibly with some chunks of the current snapshot applied), it is updated; otherwise, it's rebuilt from scratch.
+    async fn verify_root_hash(
+        &self,
+        tree: VerificationTree,
+        stop_receiver: &mut watch::Receiver<bool>,
+    ) -> StopResult<VerificationTree> {
+        tokio::select! {
+            res = self.verify_root_hash_inner(tree) => Ok(res?),
+            _ = stop_receiver.changed() => Err(OrStopped::Stopped),
+        }
+    }
+
+    async fn verify_root_hash_inner(
+        &self,
+        mut tree: VerificationTree,
+    ) -> Result<VerificationTree, SnapshotsApplierError> {
+        let current = self.snapshot_chain.current;
+        let first_step = match tree.latest_state().await? {
+            Some((_, root_hash)) if root_hash == self.expected_root_hash(current).await? => {
+                tracing::info!(
+                    "Verification tree is already at L1 batch #{}",
+                    self.applied_snapshot_status.l1_batch_number
+                );
+                return Ok(tree);
+            }
+            Some(_) if current > 0 => {
+                let base_root_hash = self.expected_root_hash(current - 1).await?;
+                if let Some(version) = tree.find_version(base_root_hash).await? {
+                    tracing::info!(
+                        "Resuming verification tree from version {version} corresponding to L1 batch #{}",
+                        self.snapshot_chain.headers[current - 1].l1_batch_number
+                    );
+                    tree.truncate(version).await?;
+                    Some(current)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        let first_step = match first_step {
+            Some(step) => step,
+            None => {
+                tracing::info!("Rebuilding verification tree from scratch");
+                tree = tree.reset().await?;
+                0
+            }
+        };
+
+        for step in first_step..=current {
+            let header = &self.snapshot_chain.headers[step];
+            let latency = METRICS.initial_stage_duration[&InitialStage::VerifyTree].start();
+            let root_hash = if step == 0 {
+                for chunk in &header.storage_logs_chunks {
+                    let logs = self
+                        .load_storage_logs_for_verification(header, chunk.chunk_id)
+                        .await?;
+                    tree.extend_recovery(logs).await?;
+                }
+                tree.finalize_recovery().await?
+            } else {
+                let mut root_hash = self.expected_root_hash(step - 1).await?;
+                for chunk in &header.storage_logs_chunks {
+                    let logs = self
+                        .load_storage_logs_for_verification(header, chunk.chunk_id)
+                        .await?;
+                    root_hash = tree.extend(logs).await?;
+                }
+                root_hash
+            };
+
+            let expected_root_hash = self.expected_root_hash(step).await?;
+            if root_hash != expected_root_hash {
+                let err = anyhow::anyhow!(
+                    "root hash mismatch after applying snapshot for L1 batch #{}: expected {expected_root_hash:?}, \
+                     got {root_hash:?}; the snapshot may be corrupted",
+                    header.l1_batch_number
+                );
+                return Err(err.into());
+            }
+            let latency = latency.observe();
+            tracing::info!(
+                "Verified root hash for L1 batch #{} in {latency:?}",
+                header.l1_batch_number
+            );
+        }
+        Ok(tree)
+    }
+
+    async fn expected_root_hash(&self, step: usize) -> Result<H256, SnapshotsApplierError> {
+        if step == self.snapshot_chain.current {
+            return Ok(self.applied_snapshot_status.l1_batch_root_hash);
+        }
+        let l1_batch_number = self.snapshot_chain.headers[step].l1_batch_number;
+        let l1_batch = self
+            .main_node_client
+            .fetch_l1_batch_details(l1_batch_number)
+            .await?
+            .with_context(|| format!("L1 batch #{l1_batch_number} is missing on main node"))?;
+        let root_hash = l1_batch.base.root_hash.with_context(|| {
+            format!("L1 batch #{l1_batch_number} fetched from main node doesn't have root hash set")
+        })?;
+        Ok(root_hash)
+    }
+
+    async fn load_storage_logs_for_verification(
+        &self,
+        header: &SnapshotHeader,
+        chunk_id: u64,
+    ) -> Result<Vec<SnapshotStorageLog>, SnapshotsApplierError> {
+        let storage_key = SnapshotStorageLogsStorageKey {
+            chunk_id,
+            l1_batch_number: header.l1_batch_number,
+        };
+        let snapshot_version = SnapshotRecoveryStrategy::check_snapshot_version(header.version)?;
+        let storage_logs = StorageLogs::load(self.blob_store, storage_key, snapshot_version)
+            .await
+            .map_err(|err| {
+                let context =
+                    format!("cannot fetch storage logs {storage_key:?} from object store");
+                SnapshotsApplierError::object_store(err, context)
+            })?;
+        Ok(storage_logs.without_preimages())
+    }
+
     async fn recover_factory_deps(
         &mut self,
         storage: &mut Connection<'_, Core>,
@@ -868,15 +1274,33 @@ impl<'a> SnapshotsApplier<'a> {
         Ok(())
     }
 
+    /// Returns the base L1 batch if the current snapshot is incremental.
+    fn base_l1_batch_number(&self) -> Option<L1BatchNumber> {
+        self.snapshot_chain.current_header().base_l1_batch_number
+    }
+
     async fn insert_initial_writes_chunk(
         &self,
         storage_logs: &[SnapshotStorageLog],
         storage: &mut Connection<'_, Core>,
     ) -> Result<(), SnapshotsApplierError> {
-        storage
-            .storage_logs_dedup_dal()
-            .insert_initial_writes_from_snapshot(storage_logs)
-            .await?;
+        if let Some(base_l1_batch_number) = self.base_l1_batch_number() {
+            // Initial writes for keys updated by an incremental snapshot are already recovered from previous snapshots.
+            let new_logs: Vec<_> = storage_logs
+                .iter()
+                .filter(|log| log.l1_batch_number_of_initial_write > base_l1_batch_number)
+                .cloned()
+                .collect();
+            storage
+                .storage_logs_dedup_dal()
+                .insert_initial_writes_from_snapshot(&new_logs)
+                .await?;
+        } else {
+            storage
+                .storage_logs_dedup_dal()
+                .insert_initial_writes_from_snapshot(storage_logs)
+                .await?;
+        }
         Ok(())
     }
 
@@ -885,24 +1309,27 @@ impl<'a> SnapshotsApplier<'a> {
         storage_logs: &StorageLogs,
         storage: &mut Connection<'_, Core>,
     ) -> Result<(), SnapshotsApplierError> {
+        let l2_block_number = self.applied_snapshot_status.l2_block_number;
         match storage_logs {
             StorageLogs::V0(logs) => {
                 #[allow(deprecated)]
                 storage
                     .storage_logs_dal()
-                    .insert_storage_logs_with_preimages_from_snapshot(
-                        self.applied_snapshot_status.l2_block_number,
-                        logs,
-                    )
+                    .insert_storage_logs_with_preimages_from_snapshot(l2_block_number, logs)
                     .await?;
             }
             StorageLogs::V1(logs) => {
+                if self.base_l1_batch_number().is_some() {
+                    // Remove logs for the updated keys written by previous snapshots, so that there's a single log per key.
+                    let hashed_keys: Vec<_> = logs.iter().map(|log| log.key).collect();
+                    storage
+                        .storage_logs_dal()
+                        .delete_storage_logs_for_keys(&hashed_keys)
+                        .await?;
+                }
                 storage
                     .storage_logs_dal()
-                    .insert_storage_logs_from_snapshot(
-                        self.applied_snapshot_status.l2_block_number,
-                        logs,
-                    )
+                    .insert_storage_logs_from_snapshot(l2_block_number, logs)
                     .await?;
             }
         }
@@ -16,6 +16,7 @@ pub(crate) enum StorageLogsChunksStage {
 pub(crate) enum InitialStage {
     FetchMetadataFromMainNode,
     ApplyFactoryDeps,
+    VerifyTree,
 }
 
 #[derive(Debug, Metrics)]
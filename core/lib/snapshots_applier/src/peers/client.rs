@@ -0,0 +1,270 @@
+//! Client side of snapshot distribution among peers.
+
+use std::{collections::HashMap, fmt, time::Duration};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use zksync_object_store::{
+    Bucket, ObjectStore, ObjectStoreError, SnapshotArchiveEntry, SnapshotArchiveManifest,
+};
+use zksync_types::{L1BatchNumber, H256};
+
+/// Read-only [`ObjectStore`] fetching archived snapshot objects from peers running
+/// [`SnapshotArchiveServer`](super::SnapshotArchiveServer).
+///
+/// Requests for different objects are spread among peers, so that concurrent requests (e.g., for storage log chunks
+/// during snapshot recovery) are served by several peers in parallel. If a peer fails to return an object or returns
+/// an object not matching its checksum, the object is requested from the next peer.
+pub struct SnapshotPeers {
+    client: reqwest::Client,
+    /// Base URLs of peers serving `manifest`.
+    urls: Vec<String>,
+    manifest: SnapshotArchiveManifest,
+    /// Archived objects together with their indices in the manifest.
+    entries: HashMap<String, (usize, SnapshotArchiveEntry)>,
+}
+
+impl fmt::Debug for SnapshotPeers {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("SnapshotPeers")
+            .field("urls", &self.urls)
+            .field("l1_batch_number", &self.l1_batch_number())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SnapshotPeers {
+    /// Fetches manifests from the specified peers and groups peers by the served manifest. Groups are ordered
+    /// by preference: newer snapshots go first; among manifests for the same L1 batch, manifests served by more peers
+    /// go first. Peers that are unreachable or serve a malformed manifest are not used.
+    ///
+    /// Since peers are untrusted, the caller should check manifests against the main node before using them
+    /// (see [`PeerSnapshotSource::new()`](super::PeerSnapshotSource::new())).
+    ///
+    /// # Errors
+    ///
+    /// Errors if no peers serve a valid manifest.
+    pub async fn discover(urls: &[String], request_timeout: Duration) -> anyhow::Result<Vec<Self>> {
+        anyhow::ensure!(!urls.is_empty(), "no snapshot peers specified");
+        let client = reqwest::Client::builder()
+            .timeout(request_timeout)
+            .build()
+            .context("failed building HTTP client")?;
+
+        let manifests = futures::future::join_all(urls.iter().map(|url| {
+            let client = &client;
+            async move {
+                let manifest = Self::fetch_manifest(client, url).await;
+                (url.trim_end_matches('/').to_owned(), manifest)
+            }
+        }))
+        .await;
+
+        let mut candidates: Vec<(SnapshotArchiveManifest, Vec<String>)> = vec![];
+        for (url, manifest) in manifests {
+            let manifest = match manifest {
+                Ok(manifest) => manifest,
+                Err(err) => {
+                    tracing::warn!("Failed getting snapshot manifest from peer {url}: {err:#}");
+                    continue;
+                }
+            };
+            tracing::info!(
+                "Peer {url} serves snapshot for L1 batch #{} with {} objects",
+                manifest.snapshot.header.l1_batch_number,
+                manifest.entries.len()
+            );
+            if let Some((_, urls)) = candidates.iter_mut().find(|(m, _)| *m == manifest) {
+                urls.push(url);
+            } else {
+                candidates.push((manifest, vec![url]));
+            }
+        }
+        anyhow::ensure!(
+            !candidates.is_empty(),
+            "none of snapshot peers serves a valid snapshot manifest"
+        );
+
+        // The sort is stable, so manifests with equal keys retain the order of the first peer serving them.
+        candidates.sort_by_key(|(manifest, urls)| {
+            let l1_batch_number = manifest.snapshot.header.l1_batch_number;
+            (
+                std::cmp::Reverse(l1_batch_number),
+                std::cmp::Reverse(urls.len()),
+            )
+        });
+        let candidates = candidates.into_iter().map(|(manifest, urls)| {
+            let entries = manifest
+                .entries
+                .iter()
+                .enumerate()
+                .map(|(i, entry)| (entry.key.clone(), (i, entry.clone())))
+                .collect();
+            Self {
+                client: client.clone(),
+                urls,
+                manifest,
+                entries,
+            }
+        });
+        Ok(candidates.collect())
+    }
+
+    async fn fetch_manifest(
+        client: &reqwest::Client,
+        url: &str,
+    ) -> anyhow::Result<SnapshotArchiveManifest> {
+        let url = format!("{}/snapshot/manifest", url.trim_end_matches('/'));
+        let response = client.get(&url).send().await?.error_for_status()?;
+        let bytes = response.bytes().await?;
+        let manifest: SnapshotArchiveManifest =
+            serde_json::from_slice(&bytes).context("failed deserializing manifest")?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    /// Returns the manifest served by these peers.
+    pub fn manifest(&self) -> &SnapshotArchiveManifest {
+        &self.manifest
+    }
+
+    pub(crate) fn l1_batch_number(&self) -> L1BatchNumber {
+        self.manifest.snapshot.header.l1_batch_number
+    }
+
+    /// Returns base URLs of the peers.
+    pub fn urls(&self) -> &[String] {
+        &self.urls
+    }
+
+    async fn fetch_object(
+        &self,
+        url: &str,
+        entry: &SnapshotArchiveEntry,
+    ) -> Result<Vec<u8>, ObjectStoreError> {
+        let url = format!("{url}/snapshot/objects/{}", entry.key);
+        let response =
+            self.client
+                .get(&url)
+                .send()
+                .await
+                .map_err(|err| ObjectStoreError::Other {
+                    is_retriable: true,
+                    source: err.into(),
+                })?;
+        let response = response
+            .error_for_status()
+            .map_err(|err| ObjectStoreError::Other {
+                // Peers may have a transient issue with reading the archive.
+                is_retriable: err.status().is_some_and(|status| status.is_server_error()),
+                source: err.into(),
+            })?;
+        let data = response
+            .bytes()
+            .await
+            .map_err(|err| ObjectStoreError::Other {
+                is_retriable: true,
+                source: err.into(),
+            })?;
+
+        let sha256 = H256(Sha256::digest(&data).into());
+        if data.len() as u64 != entry.size || sha256 != entry.sha256 {
+            let err = anyhow::anyhow!(
+                "object `{}` returned by peer {url} doesn't match snapshot manifest: expected {} bytes with checksum {:?}, \
+                 got {} bytes with checksum {sha256:?}",
+                entry.key,
+                entry.size,
+                entry.sha256,
+                data.len()
+            );
+            return Err(ObjectStoreError::Other {
+                is_retriable: false,
+                source: err.into(),
+            });
+        }
+        Ok(data.into())
+    }
+
+    fn read_only_error() -> ObjectStoreError {
+        let err = anyhow::anyhow!("snapshot peers are read-only");
+        ObjectStoreError::Other {
+            is_retriable: false,
+            source: err.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for SnapshotPeers {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let entry = (bucket == Bucket::StorageSnapshot)
+            .then(|| self.entries.get(key))
+            .flatten();
+        let Some((index, entry)) = entry else {
+            let err = anyhow::anyhow!("object `{bucket}/{key}` is not in snapshot manifest");
+            return Err(ObjectStoreError::KeyNotFound(err.into()));
+        };
+
+        let mut is_retriable = false;
+        let mut last_error = None;
+        for i in 0..self.urls.len() {
+            let url = &self.urls[(index + i) % self.urls.len()];
+            match self.fetch_object(url, entry).await {
+                Ok(data) => return Ok(data),
+                Err(err) => {
+                    tracing::warn!(
+                        "Failed fetching object `{key}` from snapshot peer {url}: {err}"
+                    );
+                    is_retriable |= err.is_retriable();
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        // `unwrap()` is safe: there is at least one peer
+        let err = anyhow::Error::from(last_error.unwrap()).context(format!(
+            "all snapshot peers failed to return object `{key}`"
+        ));
+        Err(ObjectStoreError::Other {
+            is_retriable,
+            source: err.into(),
+        })
+    }
+
+    async fn put_raw(
+        &self,
+        _bucket: Bucket,
+        _key: &str,
+        _value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        Err(Self::read_only_error())
+    }
+
+    async fn remove_raw(&self, _bucket: Bucket, _key: &str) -> Result<(), ObjectStoreError> {
+        Err(Self::read_only_error())
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        if bucket != Bucket::StorageSnapshot {
+            return Ok(vec![]);
+        }
+        let mut keys: Vec<_> = self
+            .entries
+            .keys()
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect();
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        format!("snapshot_peers/{bucket}")
+    }
+}
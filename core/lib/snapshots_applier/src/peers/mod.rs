@@ -0,0 +1,167 @@
+//! Recovery from snapshot archives served by peer nodes.
+//!
+//! A node having a snapshot archive can serve it to peers using [`SnapshotArchiveServer`]. [`SnapshotPeers`]
+//! is the client side: it fetches archive manifests from several peers and then exposes archived objects as
+//! a read-only object store, distributing requests among the peers serving the same manifest. Since peers are untrusted,
+//! each fetched object is checked against the SHA-256 checksum in the manifest, and [`PeerSnapshotSource`] only accepts
+//! manifests consistent with the main node.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use zksync_object_store::ObjectStore;
+use zksync_types::{
+    api, snapshots::SnapshotHeader, tokens::TokenInfo, L1BatchNumber, L2BlockNumber,
+};
+use zksync_web3_decl::error::EnrichedClientResult;
+
+pub use self::{client::SnapshotPeers, server::SnapshotArchiveServer};
+use crate::SnapshotsApplierMainNodeClient;
+
+mod client;
+mod server;
+
+/// Source of snapshot data fetched from [peers](SnapshotPeers) serving a snapshot archive. The snapshot header
+/// and objects are taken from peers, while L1 batch and L2 block metadata (most importantly, the L1 batch root hash
+/// that recovered storage logs are verified against) and tokens are fetched from the main node.
+#[derive(Debug)]
+pub struct PeerSnapshotSource {
+    peers: Arc<SnapshotPeers>,
+    main_node_client: Box<dyn SnapshotsApplierMainNodeClient>,
+}
+
+impl PeerSnapshotSource {
+    /// Selects the first of the [discovered](SnapshotPeers::discover()) `candidates` confirmed by the main node.
+    /// A snapshot is confirmed if the main node knows its L1 batch and L2 block, and their hashes match the manifest.
+    /// Incremental snapshots cannot be recovered from and are skipped as well.
+    ///
+    /// # Errors
+    ///
+    /// Propagates main node errors. Errors if none of the candidates is confirmed.
+    pub async fn new(
+        candidates: Vec<SnapshotPeers>,
+        main_node_client: Box<dyn SnapshotsApplierMainNodeClient>,
+    ) -> anyhow::Result<Self> {
+        for peers in candidates {
+            let l1_batch_number = peers.l1_batch_number();
+            if let Err(err) = Self::confirm_snapshot(&peers, main_node_client.as_ref()).await? {
+                tracing::warn!(
+                    "Skipping snapshot for L1 batch #{l1_batch_number} served by peer(s) {:?}: {err:#}",
+                    peers.urls()
+                );
+                continue;
+            }
+
+            tracing::info!(
+                "Selected snapshot for L1 batch #{l1_batch_number} served by {} peer(s): {:?}",
+                peers.urls().len(),
+                peers.urls()
+            );
+            return Ok(Self {
+                peers: Arc::new(peers),
+                main_node_client,
+            });
+        }
+        anyhow::bail!("none of snapshot peers serves a snapshot confirmed by the main node")
+    }
+
+    /// Checks the snapshot served by `peers` against the main node. Returns `Ok(Err(_))` if the snapshot is not confirmed.
+    async fn confirm_snapshot(
+        peers: &SnapshotPeers,
+        main_node_client: &dyn SnapshotsApplierMainNodeClient,
+    ) -> EnrichedClientResult<anyhow::Result<()>> {
+        let snapshot = &peers.manifest().snapshot;
+        let header = &snapshot.header;
+        if let Some(base_l1_batch_number) = header.base_l1_batch_number {
+            return Ok(Err(anyhow::anyhow!(
+                "snapshot is incremental (base L1 batch #{base_l1_batch_number}), which cannot be recovered from"
+            )));
+        }
+
+        let Some(l1_batch) = main_node_client
+            .fetch_l1_batch_details(header.l1_batch_number)
+            .await?
+        else {
+            return Ok(Err(anyhow::anyhow!("L1 batch is unknown to the main node")));
+        };
+        if l1_batch.base.root_hash != Some(snapshot.l1_batch_root_hash) {
+            return Ok(Err(anyhow::anyhow!(
+                "L1 batch root hash {:?} doesn't match the main node ({:?})",
+                snapshot.l1_batch_root_hash,
+                l1_batch.base.root_hash
+            )));
+        }
+
+        let Some(l2_block) = main_node_client
+            .fetch_l2_block_details(header.l2_block_number)
+            .await?
+        else {
+            return Ok(Err(anyhow::anyhow!(
+                "L2 block #{} is unknown to the main node",
+                header.l2_block_number
+            )));
+        };
+        if l2_block.l1_batch_number != header.l1_batch_number
+            || l2_block.base.root_hash != Some(snapshot.l2_block_hash)
+        {
+            return Ok(Err(anyhow::anyhow!(
+                "L2 block #{} with hash {:?} doesn't match the main node (L1 batch #{}, hash {:?})",
+                header.l2_block_number,
+                snapshot.l2_block_hash,
+                l2_block.l1_batch_number,
+                l2_block.base.root_hash
+            )));
+        }
+        Ok(Ok(()))
+    }
+
+    fn header(&self) -> &SnapshotHeader {
+        &self.peers.manifest().snapshot.header
+    }
+
+    pub(crate) fn l1_batch_number(&self) -> L1BatchNumber {
+        self.header().l1_batch_number
+    }
+
+    pub(crate) fn object_store(&self) -> Arc<dyn ObjectStore> {
+        self.peers.clone()
+    }
+}
+
+#[async_trait]
+impl SnapshotsApplierMainNodeClient for PeerSnapshotSource {
+    async fn fetch_l1_batch_details(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<api::L1BatchDetails>> {
+        self.main_node_client.fetch_l1_batch_details(number).await
+    }
+
+    async fn fetch_l2_block_details(
+        &self,
+        number: L2BlockNumber,
+    ) -> EnrichedClientResult<Option<api::BlockDetails>> {
+        self.main_node_client.fetch_l2_block_details(number).await
+    }
+
+    async fn fetch_newest_snapshot_l1_batch_number(
+        &self,
+    ) -> EnrichedClientResult<Option<L1BatchNumber>> {
+        Ok(Some(self.l1_batch_number()))
+    }
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<SnapshotHeader>> {
+        let header = self.header();
+        Ok((header.l1_batch_number == l1_batch_number).then(|| header.clone()))
+    }
+
+    async fn fetch_tokens(
+        &self,
+        at_l2_block: L2BlockNumber,
+    ) -> EnrichedClientResult<Vec<TokenInfo>> {
+        self.main_node_client.fetch_tokens(at_l2_block).await
+    }
+}
@@ -0,0 +1,86 @@
+//! HTTP server exposing a snapshot archive to peers.
+
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing, Json, Router,
+};
+use tokio::{net::TcpListener, sync::watch};
+use zksync_object_store::{
+    Bucket, ObjectStore, ObjectStoreError, SnapshotArchive, SnapshotArchiveManifest,
+};
+
+/// HTTP server exposing a [`SnapshotArchive`] to peers. The server has two endpoints:
+///
+/// - `GET /snapshot/manifest` returns the archive manifest as JSON
+/// - `GET /snapshot/objects/{key}` returns a raw archived object
+#[derive(Debug)]
+pub struct SnapshotArchiveServer {
+    archive: Arc<SnapshotArchive>,
+}
+
+impl SnapshotArchiveServer {
+    pub fn new(archive: Arc<SnapshotArchive>) -> Self {
+        Self { archive }
+    }
+
+    async fn manifest_handler(
+        State(archive): State<Arc<SnapshotArchive>>,
+    ) -> Json<SnapshotArchiveManifest> {
+        Json(archive.manifest().clone())
+    }
+
+    async fn object_handler(
+        State(archive): State<Arc<SnapshotArchive>>,
+        Path(key): Path<String>,
+    ) -> Response {
+        match archive.get_raw(Bucket::StorageSnapshot, &key).await {
+            Ok(data) => {
+                ([(header::CONTENT_TYPE, "application/octet-stream")], data).into_response()
+            }
+            Err(ObjectStoreError::KeyNotFound(_)) => StatusCode::NOT_FOUND.into_response(),
+            Err(err) => {
+                tracing::warn!("Failed reading object `{key}` from snapshot archive: {err}");
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        }
+    }
+
+    /// Runs the server on the provided listener until a stop request is received.
+    pub async fn run(
+        self,
+        listener: TcpListener,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        let local_addr = listener.local_addr()?;
+        tracing::info!(
+            "Serving snapshot archive for L1 batch #{} on {local_addr}",
+            self.archive.manifest().snapshot.header.l1_batch_number
+        );
+
+        let app = Router::new()
+            .route("/snapshot/manifest", routing::get(Self::manifest_handler))
+            .route(
+                "/snapshot/objects/{key}",
+                routing::get(Self::object_handler),
+            )
+            .with_state(self.archive);
+        axum::serve(listener, app)
+            .with_graceful_shutdown(async move {
+                if stop_receiver.changed().await.is_err() {
+                    tracing::warn!(
+                        "Stop request sender for snapshot archive server was dropped without sending a signal"
+                    );
+                }
+                tracing::info!("Stop request received, snapshot archive server is shutting down");
+            })
+            .await
+            .context("snapshot archive server failed")?;
+        tracing::info!("Snapshot archive server shut down");
+        Ok(())
+    }
+}
@@ -9,27 +9,32 @@ use assert_matches::assert_matches;
 use test_casing::test_casing;
 use tokio::sync::Barrier;
 use zksync_health_check::CheckHealth;
-use zksync_object_store::MockObjectStore;
+use zksync_merkle_tree::{MerkleTree, PatchSet, TreeEntry};
+use zksync_object_store::{
+    Bucket, MockObjectStore, SnapshotArchive, SnapshotArchiveMetadata, SnapshotArchiveWriter,
+    StoredObject,
+};
 use zksync_types::{
     api::{BlockDetails, L1BatchDetails},
     block::L1BatchHeader,
     get_code_key,
     settlement::SettlementLayer,
-    L1BatchNumber, ProtocolVersion, ProtocolVersionId,
+    L1BatchNumber, ProtocolVersion, ProtocolVersionId, U256,
 };
 
 use self::utils::{
     mock_l2_block_header, mock_recovery_status, mock_snapshot_header, mock_tokens, prepare_clients,
-    random_storage_logs, MockMainNodeClient, ObjectStoreWithErrors,
+    put_storage_logs, random_storage_logs, MockMainNodeClient, ObjectStoreWithErrors,
 };
 use super::*;
 use crate::tests::utils::{mock_factory_deps, HangingObjectStore};
 
+mod peers;
 mod utils;
 
 async fn is_recovery_completed(
     pool: &ConnectionPool<Core>,
-    client: &MockMainNodeClient,
+    client: &dyn SnapshotsApplierMainNodeClient,
 ) -> RecoveryCompletionStatus {
     let mut connection = pool.connection().await.unwrap();
     SnapshotsApplierTask::is_recovery_completed(&mut connection, client)
@@ -773,3 +778,540 @@ async fn snapshot_applier_can_be_canceled() {
         .unwrap_err();
     assert_matches!(err, OrStopped::Stopped);
 }
+
+fn compute_root_hash(tree: &mut MerkleTree<PatchSet>, logs: &[SnapshotStorageLog]) -> H256 {
+    let entries = logs
+        .iter()
+        .map(|log| {
+            let key = U256::from_little_endian(log.key.as_bytes());
+            TreeEntry::new(key, log.enumeration_index, log.value)
+        })
+        .collect();
+    tree.extend(entries).unwrap().root_hash
+}
+
+/// Prepares a full snapshot followed by an incremental snapshot, which updates some of the existing keys
+/// and inserts new ones. Returns expected storage logs together with the L2 block they should be recovered at.
+async fn prepare_incremental_snapshots() -> (
+    Arc<dyn ObjectStore>,
+    MockMainNodeClient,
+    SnapshotRecoveryStatus,
+    HashMap<H256, (SnapshotStorageLog, L2BlockNumber)>,
+) {
+    let mut base_status = mock_recovery_status();
+    let base_logs = random_storage_logs::<H256>(base_status.l1_batch_number, 200);
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    base_status.l1_batch_root_hash = compute_root_hash(&mut tree, &base_logs);
+
+    let mut status = SnapshotRecoveryStatus {
+        l1_batch_number: base_status.l1_batch_number + 5,
+        l2_block_number: base_status.l2_block_number + 20,
+        l2_block_hash: H256::random(),
+        storage_logs_chunks_processed: vec![false; 3],
+        ..base_status.clone()
+    };
+    let updated_logs = base_logs[..50].iter().map(|log| SnapshotStorageLog {
+        value: H256::random(),
+        ..log.clone()
+    });
+    let new_logs = random_storage_logs::<H256>(status.l1_batch_number, 30)
+        .into_iter()
+        .map(|log| SnapshotStorageLog {
+            enumeration_index: log.enumeration_index + 200,
+            ..log
+        });
+    let incremental_logs: Vec<_> = updated_logs.chain(new_logs).collect();
+    status.l1_batch_root_hash = compute_root_hash(&mut tree, &incremental_logs);
+
+    let factory_deps = mock_factory_deps(None);
+    let (object_store, mut client) = prepare_clients(&base_status, &factory_deps, &base_logs).await;
+    object_store
+        .put(
+            status.l1_batch_number,
+            &mock_factory_deps(Some(BytecodeMarker::EraVm)),
+        )
+        .await
+        .unwrap();
+    put_storage_logs(&*object_store, &status, &incremental_logs).await;
+
+    let base_header = client.fetch_newest_snapshot_response.take().unwrap();
+    let mut header = mock_snapshot_header(SnapshotVersion::Version2.into(), &status);
+    header.base_l1_batch_number = Some(base_status.l1_batch_number);
+    client
+        .fetch_snapshot_responses
+        .insert(base_status.l1_batch_number, base_header);
+    client.fetch_newest_snapshot_response = Some(header);
+    client.add_block_details(&status);
+
+    let base_logs = base_logs
+        .into_iter()
+        .map(|log| (log.key, (log, base_status.l2_block_number)));
+    let mut expected_logs: HashMap<_, _> = base_logs.collect();
+    let incremental_logs = incremental_logs
+        .into_iter()
+        .map(|log| (log.key, (log, status.l2_block_number)));
+    expected_logs.extend(incremental_logs);
+    status.storage_logs_chunks_processed = vec![true; 3];
+    (object_store, client, status, expected_logs)
+}
+
+#[tokio::test]
+async fn applier_recovers_incremental_snapshot() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let (object_store, client, expected_status, expected_logs) =
+        prepare_incremental_snapshots().await;
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let tree_path = temp_dir.path().join("tree");
+
+    let mut task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client.clone()),
+        object_store,
+    );
+    task.set_tree_verification_path(tree_path.clone());
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let stats = task.run(stop_receiver).await.unwrap();
+    assert!(stats.done_work);
+    assert!(!tree_path.exists());
+    assert_eq!(
+        is_recovery_completed(&pool, &client).await,
+        RecoveryCompletionStatus::Completed
+    );
+
+    let mut storage = pool.connection().await.unwrap();
+    let status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap();
+    assert_eq!(status.unwrap(), expected_status);
+
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), expected_logs.len());
+    for db_log in all_storage_logs {
+        let (expected_log, expected_l2_block) = &expected_logs[&db_log.hashed_key];
+        assert_eq!(db_log.value, expected_log.value);
+        assert_eq!(db_log.l2_block_number, *expected_l2_block);
+    }
+
+    let all_initial_writes = storage
+        .storage_logs_dedup_dal()
+        .dump_all_initial_writes_for_tests()
+        .await;
+    assert_eq!(all_initial_writes.len(), expected_logs.len());
+    for initial_write in all_initial_writes {
+        let (log, _) = &expected_logs[&initial_write.hashed_key];
+        assert_eq!(
+            initial_write.l1_batch_number,
+            log.l1_batch_number_of_initial_write
+        );
+        assert_eq!(initial_write.index, log.enumeration_index);
+    }
+
+    let pruning_info = storage.pruning_dal().get_pruning_info().await.unwrap();
+    let last_hard_pruned = pruning_info.last_hard_pruned.unwrap();
+    assert_eq!(last_hard_pruned.l1_batch, expected_status.l1_batch_number);
+    assert_eq!(last_hard_pruned.l2_block, expected_status.l2_block_number);
+}
+
+#[tokio::test]
+async fn applier_errors_for_incremental_snapshot_without_tree_path() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let (object_store, client, ..) = prepare_incremental_snapshots().await;
+
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool,
+        Box::new(client),
+        object_store,
+    );
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let err = task.run(stop_receiver).await.unwrap_err();
+    let OrStopped::Internal(err) = err else {
+        panic!("Unexpected error: {err:?}");
+    };
+    assert!(
+        format!("{err:#}").contains("verification Merkle tree"),
+        "{err:#}"
+    );
+}
+
+#[tokio::test]
+async fn applier_errors_on_incremental_snapshot_root_hash_mismatch() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let (object_store, mut client, expected_status, _) = prepare_incremental_snapshots().await;
+    let bogus_status = SnapshotRecoveryStatus {
+        l1_batch_root_hash: H256::repeat_byte(0xff),
+        ..expected_status
+    };
+    client.add_block_details(&bogus_status);
+    let temp_dir = tempfile::TempDir::new().unwrap();
+
+    let mut task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client),
+        object_store,
+    );
+    task.set_tree_verification_path(temp_dir.path().join("tree"));
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let err = task.run(stop_receiver).await.unwrap_err();
+    let OrStopped::Internal(err) = err else {
+        panic!("Unexpected error: {err:?}");
+    };
+    assert!(format!("{err:#}").contains("root hash mismatch"), "{err:#}");
+
+    // Storage logs from the incremental snapshot must not be recovered.
+    let mut storage = pool.connection().await.unwrap();
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), 200);
+    assert!(all_storage_logs
+        .iter()
+        .all(|log| log.l2_block_number != expected_status.l2_block_number));
+}
+
+#[tokio::test]
+async fn applier_resumes_incremental_snapshot_verification() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let (object_store, client, expected_status, expected_logs) =
+        prepare_incremental_snapshots().await;
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let tree_path = temp_dir.path().join("tree");
+
+    // Fail fetching the last chunk of the incremental snapshot, so that only some of its chunks are applied
+    // to the verification tree.
+    let failing_key = format!(
+        "snapshot_l1_batch_{}_storage_logs_part_0002",
+        expected_status.l1_batch_number
+    );
+    let failing_object_store = ObjectStoreWithErrors::new(object_store.clone(), move |key| {
+        if key.starts_with(&failing_key) {
+            Err(ObjectStoreError::KeyNotFound("not found".into()))
+        } else {
+            Ok(())
+        }
+    });
+    let mut task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client.clone()),
+        Arc::new(failing_object_store),
+    );
+    task.set_tree_verification_path(tree_path.clone());
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    task.run(stop_receiver.clone()).await.unwrap_err();
+
+    let mut storage = pool.connection().await.unwrap();
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert!(all_storage_logs
+        .iter()
+        .all(|log| log.l2_block_number != expected_status.l2_block_number));
+    drop(storage);
+
+    // Add an unrelated version to the tree to check that the tree is truncated when resuming verification.
+    let tree = VerificationTree::open(&tree_path).await.unwrap();
+    let (version, _) = tree.latest_state().await.unwrap().unwrap();
+    assert_eq!(version, 2); // the full snapshot + 2 chunks of the incremental snapshot
+    let bogus_logs = random_storage_logs::<H256>(expected_status.l1_batch_number, 1)
+        .into_iter()
+        .map(|log| SnapshotStorageLog {
+            enumeration_index: 1_000,
+            ..log
+        })
+        .collect();
+    tree.extend(bogus_logs).await.unwrap();
+    drop(tree);
+
+    let mut task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client.clone()),
+        object_store,
+    );
+    task.set_tree_verification_path(tree_path.clone());
+    let stats = task.run(stop_receiver).await.unwrap();
+    assert!(stats.done_work);
+    assert!(!tree_path.exists());
+    assert_eq!(
+        is_recovery_completed(&pool, &client).await,
+        RecoveryCompletionStatus::Completed
+    );
+
+    let mut storage = pool.connection().await.unwrap();
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), expected_logs.len());
+    for db_log in all_storage_logs {
+        let (expected_log, _) = &expected_logs[&db_log.hashed_key];
+        assert_eq!(db_log.value, expected_log.value);
+    }
+}
+
+/// Writes a snapshot archive with the specified storage logs. The root hash in the archive manifest is computed
+/// based on `root_hash_logs`.
+async fn prepare_snapshot_archive(
+    path: &Path,
+    logs: &[SnapshotStorageLog],
+    root_hash_logs: &[SnapshotStorageLog],
+) -> SnapshotRecoveryStatus {
+    let mut status = mock_recovery_status();
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    status.l1_batch_root_hash = compute_root_hash(&mut tree, root_hash_logs);
+    let (object_store, client) = prepare_clients(&status, &mock_factory_deps(None), logs).await;
+
+    let mut writer = SnapshotArchiveWriter::create(path).await.unwrap();
+    let keys = object_store
+        .list_raw(Bucket::StorageSnapshot, "")
+        .await
+        .unwrap();
+    for key in &keys {
+        let data = object_store
+            .get_raw(Bucket::StorageSnapshot, key)
+            .await
+            .unwrap();
+        writer.add(key, &data).await.unwrap();
+    }
+
+    let mut header = client.fetch_newest_snapshot_response.unwrap();
+    for chunk in &mut header.storage_logs_chunks {
+        chunk.filepath =
+            SnapshotStorageLogsChunk::<H256>::encode_key(SnapshotStorageLogsStorageKey {
+                l1_batch_number: status.l1_batch_number,
+                chunk_id: chunk.chunk_id,
+            });
+    }
+    header.factory_deps_filepath = SnapshotFactoryDependencies::encode_key(status.l1_batch_number);
+    let metadata = SnapshotArchiveMetadata {
+        header,
+        l1_batch_timestamp: status.l1_batch_timestamp,
+        l1_batch_root_hash: status.l1_batch_root_hash,
+        l2_block_timestamp: status.l2_block_timestamp,
+        l2_block_hash: status.l2_block_hash,
+        protocol_version: status.protocol_version,
+        tokens: mock_tokens(),
+    };
+    writer.finish(metadata).await.unwrap();
+    status
+}
+
+#[tokio::test]
+async fn applier_recovers_from_snapshot_archive() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let archive_path = temp_dir.path().join("snapshot.bin");
+    let tree_path = temp_dir.path().join("tree");
+    let storage_logs = random_storage_logs::<H256>(L1BatchNumber(1), 200);
+    let expected_status =
+        prepare_snapshot_archive(&archive_path, &storage_logs, &storage_logs).await;
+
+    let source = SnapshotArchiveSource::open(&archive_path, expected_status.l1_batch_root_hash)
+        .await
+        .unwrap();
+    let mut task = SnapshotsApplierTask::from_archive(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        source.clone(),
+    );
+    task.set_tree_verification_path(tree_path.clone());
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let stats = task.run(stop_receiver).await.unwrap();
+    assert!(stats.done_work);
+    assert!(!tree_path.exists());
+    assert_eq!(
+        is_recovery_completed(&pool, &source).await,
+        RecoveryCompletionStatus::Completed
+    );
+
+    let mut storage = pool.connection().await.unwrap();
+    let status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap();
+    assert_eq!(status.unwrap(), expected_status);
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), storage_logs.len());
+    let tokens = storage
+        .tokens_web3_dal()
+        .get_all_tokens(Some(expected_status.l2_block_number))
+        .await
+        .unwrap();
+    assert_eq!(tokens.len(), mock_tokens().len());
+}
+
+#[tokio::test]
+async fn snapshot_archive_with_unexpected_root_hash_is_rejected() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let archive_path = temp_dir.path().join("snapshot.bin");
+    let storage_logs = random_storage_logs::<H256>(L1BatchNumber(1), 200);
+    prepare_snapshot_archive(&archive_path, &storage_logs, &storage_logs).await;
+
+    let err = SnapshotArchiveSource::open(&archive_path, H256::repeat_byte(0xff))
+        .await
+        .unwrap_err();
+    assert!(
+        format!("{err:#}").contains("unexpected root hash"),
+        "{err:#}"
+    );
+}
+
+#[tokio::test]
+async fn applier_errors_on_snapshot_archive_with_tampered_logs() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let archive_path = temp_dir.path().join("snapshot.bin");
+    let storage_logs = random_storage_logs::<H256>(L1BatchNumber(1), 200);
+    let tampered_logs: Vec<_> = storage_logs
+        .iter()
+        .map(|log| SnapshotStorageLog {
+            value: H256::random(),
+            ..log.clone()
+        })
+        .collect();
+    let status = prepare_snapshot_archive(&archive_path, &tampered_logs, &storage_logs).await;
+
+    let source = SnapshotArchiveSource::open(&archive_path, status.l1_batch_root_hash)
+        .await
+        .unwrap();
+    let mut task = SnapshotsApplierTask::from_archive(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        source,
+    );
+    task.set_tree_verification_path(temp_dir.path().join("tree"));
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let err = task.run(stop_receiver).await.unwrap_err();
+    let OrStopped::Internal(err) = err else {
+        panic!("Unexpected error: {err:?}");
+    };
+    assert!(format!("{err:#}").contains("root hash mismatch"), "{err:#}");
+
+    // Tampered logs must not be recovered.
+    let mut storage = pool.connection().await.unwrap();
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert!(all_storage_logs.is_empty(), "{all_storage_logs:?}");
+}
+
+#[tokio::test]
+async fn applier_errors_for_snapshot_archive_without_tree_path() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let archive_path = temp_dir.path().join("snapshot.bin");
+    let storage_logs = random_storage_logs::<H256>(L1BatchNumber(1), 200);
+    let status = prepare_snapshot_archive(&archive_path, &storage_logs, &storage_logs).await;
+
+    let source = SnapshotArchiveSource::open(&archive_path, status.l1_batch_root_hash)
+        .await
+        .unwrap();
+    let task =
+        SnapshotsApplierTask::from_archive(SnapshotsApplierConfig::for_tests(), pool, source);
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let err = task.run(stop_receiver).await.unwrap_err();
+    let OrStopped::Internal(err) = err else {
+        panic!("Unexpected error: {err:?}");
+    };
+    assert!(
+        format!("{err:#}").contains("verification Merkle tree"),
+        "{err:#}"
+    );
+}
+
+async fn serve_snapshot_archive(path: &Path, stop_receiver: watch::Receiver<bool>) -> String {
+    let archive = SnapshotArchive::open(path).await.unwrap();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let server = SnapshotArchiveServer::new(Arc::new(archive));
+    tokio::spawn(server.run(listener, stop_receiver));
+    url
+}
+
+#[test_casing(2, [false, true])]
+#[tokio::test]
+async fn applier_recovers_from_snapshot_peers(tamper_with_logs: bool) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let archive_path = temp_dir.path().join("snapshot.bin");
+    let tree_path = temp_dir.path().join("tree");
+    let storage_logs = random_storage_logs::<H256>(L1BatchNumber(1), 200);
+    let archived_logs = if tamper_with_logs {
+        storage_logs
+            .iter()
+            .map(|log| SnapshotStorageLog {
+                value: H256::random(),
+                ..log.clone()
+            })
+            .collect()
+    } else {
+        storage_logs.clone()
+    };
+    let expected_status =
+        prepare_snapshot_archive(&archive_path, &archived_logs, &storage_logs).await;
+    // The main node is the source of truth for the snapshot L1 batch root hash.
+    let (_, client) =
+        prepare_clients(&expected_status, &mock_factory_deps(None), &storage_logs).await;
+
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let urls = [
+        serve_snapshot_archive(&archive_path, stop_receiver.clone()).await,
+        serve_snapshot_archive(&archive_path, stop_receiver.clone()).await,
+    ];
+    let candidates = SnapshotPeers::discover(&urls, Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].urls().len(), 2);
+    let source = PeerSnapshotSource::new(candidates, Box::new(client.clone()))
+        .await
+        .unwrap();
+    let mut task =
+        SnapshotsApplierTask::from_peers(SnapshotsApplierConfig::for_tests(), pool.clone(), source);
+    task.set_tree_verification_path(tree_path.clone());
+
+    if tamper_with_logs {
+        let err = task.run(stop_receiver).await.unwrap_err();
+        let OrStopped::Internal(err) = err else {
+            panic!("Unexpected error: {err:?}");
+        };
+        assert!(format!("{err:#}").contains("root hash mismatch"), "{err:#}");
+        return;
+    }
+
+    let stats = task.run(stop_receiver).await.unwrap();
+    assert!(stats.done_work);
+    assert!(!tree_path.exists());
+    assert_eq!(
+        is_recovery_completed(&pool, &client).await,
+        RecoveryCompletionStatus::Completed
+    );
+
+    let mut storage = pool.connection().await.unwrap();
+    let status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap();
+    assert_eq!(status.unwrap(), expected_status);
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), storage_logs.len());
+}
@@ -0,0 +1,290 @@
+//! Tests for fetching snapshots from peers.
+
+use std::path::Path;
+
+use axum::{routing, Json, Router};
+use tempfile::TempDir;
+use tokio::{fs, net::TcpListener};
+use zksync_object_store::SnapshotArchiveManifest;
+use zksync_types::snapshots::SnapshotStorageLogsChunkMetadata;
+
+use super::{
+    utils::{l1_batch_details, l2_block_details},
+    *,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn test_metadata() -> SnapshotArchiveMetadata {
+    SnapshotArchiveMetadata {
+        header: SnapshotHeader {
+            version: 1,
+            l1_batch_number: L1BatchNumber(5),
+            l2_block_number: L2BlockNumber(10),
+            storage_logs_chunks: vec![SnapshotStorageLogsChunkMetadata {
+                chunk_id: 0,
+                filepath: "logs_0".to_owned(),
+            }],
+            factory_deps_filepath: "deps".to_owned(),
+            base_l1_batch_number: None,
+        },
+        l1_batch_timestamp: 100,
+        l1_batch_root_hash: H256::repeat_byte(1),
+        l2_block_timestamp: 101,
+        l2_block_hash: H256::repeat_byte(2),
+        protocol_version: ProtocolVersionId::latest(),
+        tokens: vec![],
+    }
+}
+
+async fn write_archive(path: &Path) -> SnapshotArchiveManifest {
+    let mut writer = SnapshotArchiveWriter::create(path).await.unwrap();
+    writer.add("deps", b"factory deps").await.unwrap();
+    writer.add("logs_0", &[42; 1_000]).await.unwrap();
+    writer.finish(test_metadata()).await.unwrap()
+}
+
+/// Writes an archive for a newer snapshot (L1 batch #7) than [`write_archive()`].
+async fn write_newer_archive(path: &Path) -> SnapshotArchiveManifest {
+    let mut writer = SnapshotArchiveWriter::create(path).await.unwrap();
+    writer.add("deps", b"factory deps").await.unwrap();
+    writer.add("logs_0", &[23; 100]).await.unwrap();
+    let mut metadata = test_metadata();
+    metadata.header.l1_batch_number = L1BatchNumber(7);
+    metadata.header.l2_block_number = L2BlockNumber(14);
+    metadata.l1_batch_root_hash = H256::repeat_byte(3);
+    metadata.l2_block_hash = H256::repeat_byte(4);
+    writer.finish(metadata).await.unwrap()
+}
+
+async fn serve(archive_path: &Path, stop_receiver: watch::Receiver<bool>) -> String {
+    let archive = SnapshotArchive::open(archive_path).await.unwrap();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    let server = SnapshotArchiveServer::new(Arc::new(archive));
+    tokio::spawn(server.run(listener, stop_receiver));
+    url
+}
+
+async fn discover_single(urls: &[String]) -> SnapshotPeers {
+    let mut candidates = SnapshotPeers::discover(urls, TIMEOUT).await.unwrap();
+    assert_eq!(candidates.len(), 1, "{candidates:?}");
+    candidates.pop().unwrap()
+}
+
+/// Creates a main node client knowing about the snapshot from [`write_archive()`].
+fn main_node_client() -> MockMainNodeClient {
+    let metadata = test_metadata();
+    let header = &metadata.header;
+    let mut client = MockMainNodeClient::default();
+    client.fetch_l1_batch_responses.insert(
+        header.l1_batch_number,
+        l1_batch_details(header.l1_batch_number, metadata.l1_batch_root_hash),
+    );
+    client.fetch_l2_block_responses.insert(
+        header.l2_block_number,
+        l2_block_details(
+            header.l2_block_number,
+            header.l1_batch_number,
+            metadata.l2_block_hash,
+        ),
+    );
+    client
+}
+
+#[tokio::test]
+async fn fetching_objects_from_peers() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("snapshot.bin");
+    let manifest = write_archive(&path).await;
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let first_url = serve(&path, stop_receiver.clone()).await;
+    let second_url = serve(&path, stop_receiver).await;
+    // Use a port that was just freed, so that there's no server listening on it.
+    let missing_url = {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    };
+
+    let peers = discover_single(&[first_url, missing_url, second_url]).await;
+    assert_eq!(*peers.manifest(), manifest);
+    assert_eq!(peers.urls().len(), 2);
+
+    let deps = peers
+        .get_raw(Bucket::StorageSnapshot, "deps")
+        .await
+        .unwrap();
+    assert_eq!(deps, b"factory deps");
+    let logs = peers
+        .get_raw(Bucket::StorageSnapshot, "logs_0")
+        .await
+        .unwrap();
+    assert_eq!(logs, vec![42_u8; 1_000]);
+    let keys = peers.list_raw(Bucket::StorageSnapshot, "").await.unwrap();
+    assert_eq!(keys, ["deps", "logs_0"]);
+
+    let err = peers
+        .get_raw(Bucket::StorageSnapshot, "missing")
+        .await
+        .unwrap_err();
+    assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+    let err = peers
+        .put_raw(Bucket::StorageSnapshot, "deps", vec![])
+        .await
+        .unwrap_err();
+    assert!(!err.is_retriable());
+}
+
+#[tokio::test]
+async fn corrupted_peer_is_bypassed() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("snapshot.bin");
+    let manifest = write_archive(&path).await;
+    let corrupted_path = temp_dir.path().join("corrupted.bin");
+    let mut bytes = fs::read(&path).await.unwrap();
+    let logs_offset = usize::try_from(manifest.entries[1].offset).unwrap();
+    bytes[logs_offset + 10] ^= 1;
+    fs::write(&corrupted_path, bytes).await.unwrap();
+
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let corrupted_url = serve(&corrupted_path, stop_receiver.clone()).await;
+    let peers = discover_single(&[corrupted_url.clone()]).await;
+    let err = peers
+        .get_raw(Bucket::StorageSnapshot, "logs_0")
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("all snapshot peers failed"),
+        "{err}"
+    );
+
+    // Logs are requested from the corrupted peer first since they have index 1 in the manifest.
+    let url = serve(&path, stop_receiver).await;
+    let peers = discover_single(&[url, corrupted_url]).await;
+    assert_eq!(peers.urls().len(), 2);
+    let logs = peers
+        .get_raw(Bucket::StorageSnapshot, "logs_0")
+        .await
+        .unwrap();
+    assert_eq!(logs, vec![42_u8; 1_000]);
+}
+
+#[tokio::test]
+async fn mismatched_objects_are_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("snapshot.bin");
+    let manifest = write_archive(&path).await;
+
+    // Peer serving a valid manifest, but bogus objects.
+    let app = Router::new()
+        .route(
+            "/snapshot/manifest",
+            routing::get(move || {
+                let manifest = manifest.clone();
+                async move { Json(manifest) }
+            }),
+        )
+        .route(
+            "/snapshot/objects/{key}",
+            routing::get(|| async { b"bogus".to_vec() }),
+        );
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let peers = discover_single(&[url]).await;
+    let err = peers
+        .get_raw(Bucket::StorageSnapshot, "deps")
+        .await
+        .unwrap_err();
+    assert!(!err.is_retriable());
+    let err = format!("{:#}", anyhow::Error::from(err));
+    assert!(err.contains("doesn't match snapshot manifest"), "{err}");
+}
+
+#[tokio::test]
+async fn discovered_snapshots_are_ordered_by_preference() {
+    let temp_dir = TempDir::new().unwrap();
+    let old_path = temp_dir.path().join("old.bin");
+    write_archive(&old_path).await;
+    let new_path = temp_dir.path().join("new.bin");
+    write_newer_archive(&new_path).await;
+
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let old_url = serve(&old_path, stop_receiver.clone()).await;
+    let new_url = serve(&new_path, stop_receiver).await;
+    let candidates = SnapshotPeers::discover(&[old_url.clone(), new_url, old_url], TIMEOUT)
+        .await
+        .unwrap();
+    let candidates: Vec<_> = candidates
+        .iter()
+        .map(|peers| (peers.l1_batch_number(), peers.urls().len()))
+        .collect();
+    assert_eq!(candidates, [(L1BatchNumber(7), 1), (L1BatchNumber(5), 2)]);
+}
+
+#[test_casing(2, [false, true])]
+#[tokio::test]
+async fn unconfirmed_snapshot_is_skipped(known_to_main_node: bool) {
+    let temp_dir = TempDir::new().unwrap();
+    let old_path = temp_dir.path().join("old.bin");
+    write_archive(&old_path).await;
+    let new_path = temp_dir.path().join("new.bin");
+    let new_manifest = write_newer_archive(&new_path).await;
+
+    let mut client = main_node_client();
+    if known_to_main_node {
+        // The main node has the newer L1 batch, but with a different root hash.
+        let header = &new_manifest.snapshot.header;
+        client.fetch_l1_batch_responses.insert(
+            header.l1_batch_number,
+            l1_batch_details(header.l1_batch_number, H256::zero()),
+        );
+    }
+
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let urls = [
+        serve(&new_path, stop_receiver.clone()).await,
+        serve(&new_path, stop_receiver.clone()).await,
+        serve(&old_path, stop_receiver).await,
+    ];
+    let candidates = SnapshotPeers::discover(&urls, TIMEOUT).await.unwrap();
+    assert_eq!(candidates.len(), 2);
+    let source = PeerSnapshotSource::new(candidates, Box::new(client))
+        .await
+        .unwrap();
+    assert_eq!(source.l1_batch_number(), L1BatchNumber(5));
+    let logs = source
+        .object_store()
+        .get_raw(Bucket::StorageSnapshot, "logs_0")
+        .await
+        .unwrap();
+    assert_eq!(logs, vec![42_u8; 1_000]);
+}
+
+#[tokio::test]
+async fn snapshot_source_errors_if_no_snapshots_are_confirmed() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("snapshot.bin");
+    write_archive(&path).await;
+
+    let mut client = main_node_client();
+    let l2_block_number = test_metadata().header.l2_block_number;
+    // L2 block hash doesn't match the manifest.
+    client.fetch_l2_block_responses.insert(
+        l2_block_number,
+        l2_block_details(l2_block_number, L1BatchNumber(5), H256::zero()),
+    );
+
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let url = serve(&path, stop_receiver).await;
+    let candidates = SnapshotPeers::discover(&[url], TIMEOUT).await.unwrap();
+    let err = PeerSnapshotSource::new(candidates, Box::new(client))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("none of snapshot peers serves a snapshot confirmed by the main node"),
+        "{err:#}"
+    );
+}
@@ -55,6 +55,8 @@ pub(super) struct MockMainNodeClient {
     pub fetch_l1_batch_responses: HashMap<L1BatchNumber, api::L1BatchDetails>,
     pub fetch_l2_block_responses: HashMap<L2BlockNumber, api::BlockDetails>,
     pub fetch_newest_snapshot_response: Option<SnapshotHeader>,
+    /// Responses for older snapshots (i.e., ones other than `fetch_newest_snapshot_response`).
+    pub fetch_snapshot_responses: HashMap<L1BatchNumber, SnapshotHeader>,
     pub tokens_response: Vec<TokenInfo>,
     pub tokens_response_error: Arc<RwLock<Option<EnrichedClientError>>>,
 }
@@ -69,6 +71,22 @@ impl MockMainNodeClient {
     fn take_token_response_error(&self) -> Option<EnrichedClientError> {
         self.tokens_response_error.write().unwrap().take()
     }
+
+    /// Adds L1 batch and L2 block details corresponding to the snapshot recovery `status`.
+    pub(super) fn add_block_details(&mut self, status: &SnapshotRecoveryStatus) {
+        self.fetch_l1_batch_responses.insert(
+            status.l1_batch_number,
+            l1_batch_details(status.l1_batch_number, status.l1_batch_root_hash),
+        );
+        self.fetch_l2_block_responses.insert(
+            status.l2_block_number,
+            l2_block_details(
+                status.l2_block_number,
+                status.l1_batch_number,
+                status.l2_block_hash,
+            ),
+        );
+    }
 }
 
 #[async_trait]
@@ -100,10 +118,14 @@ impl SnapshotsApplierMainNodeClient for MockMainNodeClient {
         &self,
         l1_batch_number: L1BatchNumber,
     ) -> EnrichedClientResult<Option<SnapshotHeader>> {
-        Ok(self
+        let newest_snapshot = self
             .fetch_newest_snapshot_response
             .clone()
-            .filter(|response| response.l1_batch_number == l1_batch_number))
+            .filter(|response| response.l1_batch_number == l1_batch_number);
+        Ok(
+            newest_snapshot
+                .or_else(|| self.fetch_snapshot_responses.get(&l1_batch_number).cloned()),
+        )
     }
 
     async fn fetch_tokens(
@@ -163,6 +185,14 @@ impl ObjectStore for ObjectStoreWithErrors {
         unreachable!("Should not be used in snapshot applier")
     }
 
+    async fn list_raw(
+        &self,
+        _bucket: Bucket,
+        _prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        unreachable!("Should not be used in snapshot applier")
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         self.inner.storage_prefix_raw(bucket)
     }
@@ -219,7 +249,7 @@ fn block_details_base(hash: H256) -> api::BlockDetailsBase {
     }
 }
 
-fn l2_block_details(
+pub(super) fn l2_block_details(
     number: L2BlockNumber,
     l1_batch_number: L1BatchNumber,
     hash: H256,
@@ -233,7 +263,7 @@ fn l2_block_details(
     }
 }
 
-fn l1_batch_details(number: L1BatchNumber, root_hash: H256) -> api::L1BatchDetails {
+pub(super) fn l1_batch_details(number: L1BatchNumber, root_hash: H256) -> api::L1BatchDetails {
     api::L1BatchDetails {
         number,
         commitment: Some(H256::repeat_byte(number.0 as u8)),
@@ -324,6 +354,7 @@ pub(super) fn mock_snapshot_header(
             })
             .collect(),
         factory_deps_filepath: "some_filepath".to_string(),
+        base_l1_batch_number: None,
     }
 }
 
@@ -342,7 +373,22 @@ where
         .put(status.l1_batch_number, factory_deps)
         .await
         .unwrap();
+    put_storage_logs(&*object_store, status, logs).await;
 
+    client.fetch_newest_snapshot_response = Some(mock_snapshot_header(K::VERSION.into(), status));
+    client.add_block_details(status);
+    (object_store, client)
+}
+
+/// Persists snapshot storage logs into the object store, splitting them into the number of chunks specified in `status`.
+pub(super) async fn put_storage_logs<K>(
+    object_store: &dyn ObjectStore,
+    status: &SnapshotRecoveryStatus,
+    logs: &[SnapshotStorageLog<K>],
+) where
+    K: SnapshotLogKey,
+    for<'a> SnapshotStorageLogsChunk<K>: StoredObject<Key<'a> = SnapshotStorageLogsStorageKey>,
+{
     let chunk_size = logs
         .len()
         .div_ceil(status.storage_logs_chunks_processed.len());
@@ -361,21 +407,6 @@ where
             .await
             .unwrap();
     }
-
-    client.fetch_newest_snapshot_response = Some(mock_snapshot_header(K::VERSION.into(), status));
-    client.fetch_l1_batch_responses.insert(
-        status.l1_batch_number,
-        l1_batch_details(status.l1_batch_number, status.l1_batch_root_hash),
-    );
-    client.fetch_l2_block_responses.insert(
-        status.l2_block_number,
-        l2_block_details(
-            status.l2_block_number,
-            status.l1_batch_number,
-            status.l2_block_hash,
-        ),
-    );
-    (object_store, client)
 }
 
 /// Object store wrapper that hangs up after processing the specified number of requests.
@@ -434,6 +465,14 @@ impl ObjectStore for HangingObjectStore {
         unreachable!("Should not be used in snapshot applier")
     }
 
+    async fn list_raw(
+        &self,
+        _bucket: Bucket,
+        _prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        unreachable!("Should not be used in snapshot applier")
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         self.inner.storage_prefix_raw(bucket)
     }
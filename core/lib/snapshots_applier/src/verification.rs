@@ -0,0 +1,139 @@
+//! Merkle tree used to verify recovery from a chain of incremental snapshots.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use tokio::fs;
+use zksync_merkle_tree::{MerkleTree, MerkleTreeRecovery, RocksDBWrapper, TreeEntry};
+use zksync_types::{snapshots::SnapshotStorageLog, H256, U256};
+
+/// Scratch Merkle tree persisted in RocksDB. Tree version 0 corresponds to the full snapshot; each storage logs chunk
+/// of an incremental snapshot is applied as a separate version, so that chunks don't need to be loaded into memory at once.
+/// Since keys in an incremental snapshot are unique, the state after applying all its chunks doesn't depend on chunking.
+/// The version at which the tree state corresponds to a certain snapshot is found by the root hash, which allows
+/// to resume verification after a restart.
+#[derive(Debug)]
+pub(crate) struct VerificationTree {
+    path: PathBuf,
+    db: RocksDBWrapper,
+}
+
+impl VerificationTree {
+    pub async fn open(path: &Path) -> anyhow::Result<Self> {
+        let path = path.to_owned();
+        tokio::task::spawn_blocking(move || {
+            let db = RocksDBWrapper::new(&path).with_context(|| {
+                format!(
+                    "failed initializing RocksDB for verification tree at `{}`",
+                    path.display()
+                )
+            })?;
+            Ok(Self { path, db })
+        })
+        .await
+        .context("opening verification tree panicked")?
+    }
+
+    /// Returns the latest version of the tree together with its root hash, or `None` if the tree
+    /// is empty or cannot be loaded (e.g., if its recovery wasn't finalized).
+    pub async fn latest_state(&self) -> anyhow::Result<Option<(u64, H256)>> {
+        let db = self.db.clone();
+        tokio::task::spawn_blocking(move || match MerkleTree::new(db) {
+            Ok(tree) => Ok(tree
+                .latest_version()
+                .map(|version| (version, tree.latest_root_hash()))),
+            Err(err) => {
+                tracing::info!("Cannot load verification tree: {err:#}");
+                Ok(None)
+            }
+        })
+        .await
+        .context("loading verification tree panicked")?
+    }
+
+    /// Returns the latest version of the tree with the specified root hash.
+    pub async fn find_version(&self, root_hash: H256) -> anyhow::Result<Option<u64>> {
+        let db = self.db.clone();
+        tokio::task::spawn_blocking(move || {
+            let tree = MerkleTree::new(db)?;
+            let Some(latest_version) = tree.latest_version() else {
+                return Ok(None);
+            };
+            Ok((0..=latest_version)
+                .rev()
+                .find(|&version| tree.root_hash(version) == Some(root_hash)))
+        })
+        .await
+        .context("searching verification tree panicked")?
+    }
+
+    /// Removes all versions after the specified one.
+    pub async fn truncate(&self, last_retained_version: u64) -> anyhow::Result<()> {
+        let db = self.db.clone();
+        tokio::task::spawn_blocking(move || {
+            MerkleTree::new(db)?.truncate_recent_versions(last_retained_version + 1)
+        })
+        .await
+        .context("truncating verification tree panicked")?
+    }
+
+    /// Removes all data from the tree.
+    pub async fn reset(self) -> anyhow::Result<Self> {
+        let path = self.remove().await?;
+        Self::open(&path).await
+    }
+
+    /// Removes the tree directory. Returns the path to the removed directory.
+    pub async fn remove(self) -> anyhow::Result<PathBuf> {
+        let Self { path, db } = self;
+        drop(db);
+        if fs::try_exists(&path).await? {
+            fs::remove_dir_all(&path).await.with_context(|| {
+                format!("failed removing verification tree at `{}`", path.display())
+            })?;
+        }
+        Ok(path)
+    }
+
+    /// Adds entries from the full snapshot. Entries can be supplied in any order and split into any number of calls.
+    pub async fn extend_recovery(&self, logs: Vec<SnapshotStorageLog>) -> anyhow::Result<()> {
+        let db = self.db.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut recovery = MerkleTreeRecovery::new(db, 0)?;
+            recovery.extend_random(tree_entries(&logs))
+        })
+        .await
+        .context("extending verification tree panicked")?
+    }
+
+    /// Finalizes recovery from the full snapshot, returning the root hash of the tree.
+    pub async fn finalize_recovery(&self) -> anyhow::Result<H256> {
+        let db = self.db.clone();
+        tokio::task::spawn_blocking(move || {
+            let db = MerkleTreeRecovery::new(db, 0)?.finalize()?;
+            Ok(MerkleTree::new(db)?.latest_root_hash())
+        })
+        .await
+        .context("finalizing verification tree panicked")?
+    }
+
+    /// Applies logs from an incremental snapshot chunk as a new tree version, returning the updated root hash.
+    pub async fn extend(&self, logs: Vec<SnapshotStorageLog>) -> anyhow::Result<H256> {
+        let db = self.db.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut tree = MerkleTree::new(db)?;
+            Ok(tree.extend(tree_entries(&logs))?.root_hash)
+        })
+        .await
+        .context("extending verification tree panicked")?
+    }
+}
+
+fn tree_entries(logs: &[SnapshotStorageLog]) -> Vec<TreeEntry> {
+    logs.iter()
+        .map(|log| {
+            let key = U256::from_little_endian(log.key.as_bytes());
+            TreeEntry::new(key, log.enumeration_index, log.value)
+        })
+        .collect()
+}
@@ -1,11 +1,14 @@
 use std::{
+    ffi::OsString,
+    num::NonZeroU32,
     path::PathBuf,
     time::{Duration, Instant},
 };
 
 use anyhow::Context;
-use tokio::sync::watch;
-use zksync_dal::{ConnectionPool, Core};
+use tokio::{fs, sync::watch, task::JoinHandle};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_object_store::RocksdbCheckpointStore;
 use zksync_shared_metrics::{SnapshotRecoveryStage, APP_METRICS};
 use zksync_types::{try_stoppable, L1BatchNumber, OrStopped, StopContext};
 
@@ -35,6 +38,7 @@ type AsyncOnceCell<T> = watch::Receiver<Option<T>>;
 pub struct RocksdbCell {
     initial_state: AsyncOnceCell<InitialRocksdbState>,
     db: AsyncOnceCell<RocksdbStorage>,
+    db_path: PathBuf,
 }
 
 impl RocksdbCell {
@@ -84,6 +88,28 @@ impl RocksdbCell {
             pool,
         }
     }
+
+    /// Creates a task that will periodically create checkpoints of the storage once it has caught up,
+    /// and upload them to the `store`. A checkpoint is created every `interval` L1 batches; only `retained_count`
+    /// most recent checkpoints are kept in the store.
+    pub fn checkpoint_task(
+        &self,
+        store: RocksdbCheckpointStore,
+        interval: NonZeroU32,
+        retained_count: usize,
+    ) -> CheckpointTask {
+        let mut checkpoint_path = OsString::from(self.db_path.as_os_str());
+        checkpoint_path.push(".checkpoint");
+        CheckpointTask {
+            db: self.db.clone(),
+            checkpoint_path: checkpoint_path.into(),
+            store,
+            interval,
+            retained_count,
+            next_checkpoint: None,
+            upload_task: None,
+        }
+    }
 }
 
 /// A runnable task that blocks until the provided RocksDB cache instance is caught up with
@@ -99,6 +125,7 @@ pub struct AsyncCatchupTask {
     initial_state_sender: watch::Sender<Option<InitialRocksdbState>>,
     db_sender: watch::Sender<Option<RocksdbStorage>>,
     to_l1_batch_number: Option<L1BatchNumber>,
+    checkpoint_store: Option<RocksdbCheckpointStore>,
 }
 
 impl AsyncCatchupTask {
@@ -107,6 +134,11 @@ impl AsyncCatchupTask {
     pub fn new(pool: ConnectionPool<Core>, state_keeper_db_path: PathBuf) -> (Self, RocksdbCell) {
         let (initial_state_sender, initial_state) = watch::channel(None);
         let (db_sender, db) = watch::channel(None);
+        let cell = RocksdbCell {
+            initial_state,
+            db,
+            db_path: state_keeper_db_path.clone(),
+        };
         let this = Self {
             recovery_pool: pool.clone(),
             pool,
@@ -115,8 +147,9 @@ impl AsyncCatchupTask {
             initial_state_sender,
             db_sender,
             to_l1_batch_number: None,
+            checkpoint_store: None,
         };
-        (this, RocksdbCell { initial_state, db })
+        (this, cell)
     }
 
     /// Sets RocksDB options.
@@ -141,16 +174,95 @@ impl AsyncCatchupTask {
         self
     }
 
+    /// Sets up a store of state keeper cache checkpoints. If RocksDB is absent, it will be restored
+    /// from the latest checkpoint compatible with Postgres data (if any) before catching up.
+    #[must_use]
+    pub fn with_checkpoint_store(mut self, store: RocksdbCheckpointStore) -> Self {
+        self.checkpoint_store = Some(store);
+        self
+    }
+
+    async fn restore_from_checkpoint(
+        &self,
+        store: &RocksdbCheckpointStore,
+        stop_receiver: &mut watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        let db_path = &self.state_keeper_db_path;
+        if fs::try_exists(db_path).await?
+            && fs::read_dir(db_path).await?.next_entry().await?.is_some()
+        {
+            return Ok(());
+        }
+
+        let mut storage = self.pool.connection_tagged("state_keeper").await?;
+        let Some(sealed_l1_batch) = storage.blocks_dal().get_sealed_l1_batch_number().await? else {
+            return Ok(());
+        };
+        let last_l1_batch = self
+            .to_l1_batch_number
+            .map_or(sealed_l1_batch, |number| number.min(sealed_l1_batch));
+        // Postgres doesn't contain storage logs necessary to catch up from L1 batches preceding snapshot recovery or pruning.
+        let snapshot_recovery = storage
+            .snapshot_recovery_dal()
+            .get_applied_snapshot_status()
+            .await?;
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        drop(storage);
+        let first_l1_batch = [
+            snapshot_recovery.map(|status| status.l1_batch_number),
+            pruning_info.last_soft_pruned.map(|info| info.l1_batch),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+        .unwrap_or_default();
+
+        let Some(next_l1_batch_number) = store
+            .latest_in_range((first_l1_batch + 1)..=(last_l1_batch + 1))
+            .await?
+        else {
+            tracing::info!(
+                "No state keeper cache checkpoints suitable for L1 batches #{first_l1_batch}..=#{last_l1_batch}"
+            );
+            return Ok(());
+        };
+
+        tracing::info!(
+            "Restoring state keeper cache from checkpoint at L1 batch #{next_l1_batch_number}"
+        );
+        if fs::try_exists(db_path).await? {
+            fs::remove_dir(db_path).await?;
+        }
+        tokio::select! {
+            res = store.download(next_l1_batch_number, db_path) => {
+                res?;
+            }
+            _ = stop_receiver.changed() => {
+                tracing::info!("Stop request received, interrupting state keeper cache restoration");
+            }
+        }
+        Ok(())
+    }
+
     /// Block until RocksDB cache instance is caught up with Postgres.
     ///
     /// # Errors
     ///
     /// Propagates RocksDB and Postgres errors.
     #[tracing::instrument(name = "catch_up", skip_all, fields(target_l1_batch = ?self.to_l1_batch_number))]
-    pub async fn run(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let started_at = Instant::now();
         tracing::info!("Catching up RocksDB asynchronously");
 
+        if let Some(store) = &self.checkpoint_store {
+            self.restore_from_checkpoint(store, &mut stop_receiver)
+                .await
+                .context("failed restoring state keeper cache from checkpoint")?;
+            if *stop_receiver.borrow() {
+                return Ok(());
+            }
+        }
+
         let rocksdb_builder = RocksdbStorage::builder_with_options(
             self.state_keeper_db_path.as_ref(),
             self.state_keeper_db_options,
@@ -223,11 +335,112 @@ impl KeepUpdatedTask {
     }
 }
 
+/// A runnable task that periodically creates checkpoints of the RocksDB cache once it has caught up,
+/// and uploads them to the object store in the background. Created using [`RocksdbCell::checkpoint_task()`].
+#[derive(Debug)]
+pub struct CheckpointTask {
+    db: AsyncOnceCell<RocksdbStorage>,
+    checkpoint_path: PathBuf,
+    store: RocksdbCheckpointStore,
+    interval: NonZeroU32,
+    retained_count: usize,
+    /// Next L1 batch number of the storage, starting from which a new checkpoint should be created.
+    /// Lazily initialized from the latest checkpoint in the store.
+    next_checkpoint: Option<L1BatchNumber>,
+    upload_task: Option<JoinHandle<anyhow::Result<()>>>,
+}
+
+impl CheckpointTask {
+    const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let storage: RocksdbStorage = tokio::select! {
+            res = self.db.wait_for(Option::is_some) => if let Ok(db) = res {
+                // `unwrap()` is safe by construction
+                db.clone().unwrap()
+            } else {
+                tracing::info!("Stop request received, shutting down RocksDB checkpoint task");
+                return Ok(());
+            },
+            _ = stop_receiver.changed() => {
+                tracing::info!("Stop request received, shutting down RocksDB checkpoint task");
+                return Ok(());
+            }
+        };
+
+        while !*stop_receiver.borrow() {
+            // Checkpoints are auxiliary, so errors are logged rather than propagated.
+            if let Err(err) = self.maybe_create_checkpoint(&storage).await {
+                tracing::warn!("Failed creating RocksDB cache checkpoint: {err:#}");
+            }
+            tokio::time::timeout(Self::POLL_INTERVAL, stop_receiver.changed())
+                .await
+                .ok();
+        }
+
+        if let Some(upload_task) = self.upload_task.take() {
+            tracing::info!("Aborting RocksDB cache checkpoint upload");
+            upload_task.abort();
+        }
+        tracing::info!("Stop request received, shutting down RocksDB checkpoint task");
+        Ok(())
+    }
+
+    async fn maybe_create_checkpoint(&mut self, storage: &RocksdbStorage) -> anyhow::Result<()> {
+        if let Some(upload_task) = &self.upload_task {
+            if !upload_task.is_finished() {
+                return Ok(());
+            }
+            // `unwrap()` is safe by construction
+            let upload_task = self.upload_task.take().unwrap();
+            upload_task
+                .await
+                .context("checkpoint upload panicked")?
+                .context("failed uploading checkpoint")?;
+        }
+
+        let next_checkpoint = if let Some(number) = self.next_checkpoint {
+            number
+        } else {
+            let latest = self.store.list().await?.last().copied();
+            let number = latest.map_or(L1BatchNumber(0), |number| number + self.interval.get());
+            *self.next_checkpoint.insert(number)
+        };
+        if storage.next_l1_batch_number().await < next_checkpoint {
+            return Ok(());
+        }
+
+        let checkpoint_path = &self.checkpoint_path;
+        if fs::try_exists(checkpoint_path).await? {
+            tracing::info!(
+                "Removing leftover RocksDB cache checkpoint at `{}`",
+                checkpoint_path.display()
+            );
+            fs::remove_dir_all(checkpoint_path).await?;
+        }
+        let next_l1_batch_number = storage.create_checkpoint(checkpoint_path).await?;
+        self.next_checkpoint = Some(next_l1_batch_number + self.interval.get());
+
+        let store = self.store.clone();
+        let checkpoint_path = checkpoint_path.clone();
+        let retained_count = self.retained_count;
+        self.upload_task = Some(tokio::spawn(async move {
+            store.upload(next_l1_batch_number, &checkpoint_path).await?;
+            fs::remove_dir_all(&checkpoint_path).await?;
+            store.remove_old(retained_count).await?;
+            Ok(())
+        }));
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use tempfile::TempDir;
     use test_casing::test_casing;
+    use zksync_object_store::{MockObjectStore, RocksdbCheckpointKind};
     use zksync_types::L2BlockNumber;
+    use zksync_vm_interface::storage::ReadStorage;
 
     use super::*;
     use crate::test_utils::{create_l1_batch, create_l2_block, gen_storage_logs, prepare_postgres};
@@ -317,6 +530,88 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn restoring_from_checkpoint() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        prepare_postgres(&mut conn).await;
+        let storage_logs = gen_storage_logs(20..40);
+        create_l2_block(&mut conn, L2BlockNumber(1), &storage_logs).await;
+        create_l1_batch(&mut conn, L1BatchNumber(1), &storage_logs).await;
+        drop(conn);
+
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let (task, rocksdb_cell) = AsyncCatchupTask::new(pool.clone(), db_path.clone());
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        task.run(stop_receiver.clone()).await.unwrap();
+        let db = rocksdb_cell.get().unwrap();
+        let checkpoint_path = temp_dir.path().join("checkpoint");
+        let next_l1_batch_number = db.create_checkpoint(&checkpoint_path).await.unwrap();
+        assert_eq!(next_l1_batch_number, L1BatchNumber(2));
+
+        let store = RocksdbCheckpointStore::new(
+            MockObjectStore::arc(),
+            RocksdbCheckpointKind::StateKeeperCache,
+        );
+        store
+            .upload(next_l1_batch_number, &checkpoint_path)
+            .await
+            .unwrap();
+
+        let restored_db_path = temp_dir.path().join("restored");
+        let (task, rocksdb_cell) = AsyncCatchupTask::new(pool.clone(), restored_db_path.clone());
+        let task_handle = tokio::spawn(task.with_checkpoint_store(store).run(stop_receiver));
+        let initial_state = rocksdb_cell.ensure_initialized().await.unwrap();
+        assert_eq!(initial_state.next_l1_batch_number, Some(L1BatchNumber(2)));
+        task_handle.await.unwrap().unwrap();
+
+        let mut restored_db = rocksdb_cell.get().unwrap();
+        for log in &storage_logs {
+            assert_eq!(restored_db.read_value(&log.key), log.value);
+        }
+    }
+
+    #[tokio::test]
+    async fn creating_checkpoints() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        prepare_postgres(&mut conn).await;
+        let storage_logs = gen_storage_logs(20..40);
+        create_l2_block(&mut conn, L2BlockNumber(1), &storage_logs).await;
+        create_l1_batch(&mut conn, L1BatchNumber(1), &storage_logs).await;
+        drop(conn);
+
+        let temp_dir = TempDir::new().unwrap();
+        let (task, rocksdb_cell) = AsyncCatchupTask::new(pool.clone(), temp_dir.path().join("db"));
+        let store = RocksdbCheckpointStore::new(
+            MockObjectStore::arc(),
+            RocksdbCheckpointKind::StateKeeperCache,
+        );
+        let checkpoint_task =
+            rocksdb_cell.checkpoint_task(store.clone(), NonZeroU32::new(1).unwrap(), 1);
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let checkpoint_task_handle = tokio::spawn(checkpoint_task.run(stop_receiver.clone()));
+        task.run(stop_receiver).await.unwrap();
+
+        let started_at = Instant::now();
+        let checkpoints = loop {
+            assert!(
+                started_at.elapsed() < Duration::from_secs(10),
+                "Timeout waiting for checkpoint"
+            );
+            let checkpoints = store.list().await.unwrap();
+            if !checkpoints.is_empty() {
+                break checkpoints;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+        assert_eq!(checkpoints, [L1BatchNumber(2)]);
+
+        stop_sender.send_replace(true);
+        checkpoint_task_handle.await.unwrap().unwrap();
+    }
+
     #[derive(Debug)]
     enum CancellationScenario {
         DropTask,
@@ -494,6 +494,41 @@ impl<'a> PostgresStorage<'a> {
         })
     }
 
+    /// Creates a storage for an L2 block that is retained as an archive checkpoint after its L1 batch was pruned
+    /// (see [`PruningDal::get_archive_checkpoint()`]). The storage reflects the state after the entire checkpoint L1 batch.
+    /// Returns `Ok(None)` if the L2 block is not an archive checkpoint.
+    ///
+    /// # Errors
+    ///
+    /// Propagates Postgres errors.
+    ///
+    /// [`PruningDal::get_archive_checkpoint()`]: zksync_dal::pruning_dal::PruningDal::get_archive_checkpoint()
+    pub async fn archived(
+        rt_handle: Handle,
+        mut connection: Connection<'a, Core>,
+        block_number: L2BlockNumber,
+    ) -> anyhow::Result<Option<Self>> {
+        let Some(checkpoint) = connection
+            .pruning_dal()
+            .get_archive_checkpoint(block_number)
+            .await?
+        else {
+            return Ok(None);
+        };
+        // `miniblocks` rows for the checkpoint L1 batch are pruned, so the L1 batch cannot be resolved from them.
+        // The checkpoint is the last L2 block in its L1 batch, thus the following L1 batch would be pending for it.
+        Ok(Some(Self {
+            rt_handle,
+            connection,
+            l2_block_number: block_number,
+            l1_batch_number_for_l2_block: checkpoint.l1_batch,
+            pending_l1_batch_number: checkpoint.l1_batch + 1,
+            // The checkpoint L2 block is the last block in its L1 batch, so all writes in the batch should be considered.
+            consider_new_l1_batch: true,
+            caches: None,
+        }))
+    }
+
     /// Sets the caches to use with the storage.
     #[must_use]
     pub fn with_caches(self, caches: PostgresStorageCaches) -> Self {
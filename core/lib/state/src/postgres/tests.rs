@@ -1,6 +1,6 @@
 //! Tests for `PostgresStorage`.
 
-use std::{collections::HashMap, mem, time::Duration};
+use std::{collections::HashMap, mem, num::NonZeroU32, time::Duration};
 
 use rand::{
     rngs::StdRng,
@@ -202,6 +202,101 @@ async fn postgres_storage_after_sealing_l2_block(consider_new_batch: bool) {
     .unwrap();
 }
 
+fn test_archived_postgres_storage(pool: &ConnectionPool<Core>, rt_handle: Handle) {
+    let mut connection = rt_handle.block_on(pool.connection()).unwrap();
+    rt_handle.block_on(prepare_postgres(&mut connection));
+    let existing_logs = gen_storage_logs(0..20);
+    let updated_logs: Vec<_> = existing_logs[..10]
+        .iter()
+        .map(|log| StorageLog::new_write_log(log.key, H256::repeat_byte(0xff)))
+        .collect();
+    let new_logs = gen_storage_logs(20..30);
+    let l2_block_logs: Vec<_> = updated_logs.iter().chain(&new_logs).copied().collect();
+    rt_handle.block_on(create_l2_block(
+        &mut connection,
+        L2BlockNumber(1),
+        &l2_block_logs,
+    ));
+    rt_handle.block_on(create_l1_batch(
+        &mut connection,
+        L1BatchNumber(1),
+        &new_logs,
+    ));
+    rt_handle.block_on(create_l2_block(&mut connection, L2BlockNumber(2), &[]));
+    rt_handle.block_on(create_l1_batch(&mut connection, L1BatchNumber(2), &[]));
+
+    // Without archive checkpoints, pruning would remove overwritten genesis logs.
+    let stats = rt_handle
+        .block_on(
+            connection
+                .pruning_dal()
+                .hard_prune_batches_range_with_archive(
+                    L1BatchNumber(1),
+                    L2BlockNumber(1),
+                    NonZeroU32::new(1),
+                ),
+        )
+        .unwrap();
+    assert_eq!(stats.deleted_storage_logs, 0);
+
+    let mut storage = rt_handle
+        .block_on(PostgresStorage::archived(
+            rt_handle.clone(),
+            connection,
+            L2BlockNumber(0),
+        ))
+        .unwrap()
+        .expect("genesis L2 block is not archived");
+    assert_eq!(storage.l1_batch_number_for_l2_block, L1BatchNumber(0));
+    for log in &existing_logs {
+        assert!(!storage.is_write_initial(&log.key));
+        assert_eq!(storage.read_value(&log.key), log.value);
+    }
+    for log in &new_logs {
+        assert!(storage.is_write_initial(&log.key));
+        assert_eq!(storage.read_value(&log.key), H256::zero());
+    }
+
+    let connection = storage.into_inner();
+    let mut storage = rt_handle
+        .block_on(PostgresStorage::archived(
+            rt_handle.clone(),
+            connection,
+            L2BlockNumber(1),
+        ))
+        .unwrap()
+        .expect("L2 block #1 is not archived");
+    assert_eq!(storage.l1_batch_number_for_l2_block, L1BatchNumber(1));
+    assert_eq!(storage.pending_l1_batch_number, L1BatchNumber(2));
+    for log in updated_logs
+        .iter()
+        .chain(&existing_logs[10..])
+        .chain(&new_logs)
+    {
+        assert!(!storage.is_write_initial(&log.key));
+        assert_eq!(storage.read_value(&log.key), log.value);
+    }
+
+    let connection = storage.into_inner();
+    let storage = rt_handle
+        .block_on(PostgresStorage::archived(
+            rt_handle.clone(),
+            connection,
+            L2BlockNumber(2),
+        ))
+        .unwrap();
+    assert!(storage.is_none());
+}
+
+#[tokio::test]
+async fn archived_postgres_storage() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let handle = Handle::current();
+    tokio::task::spawn_blocking(move || test_archived_postgres_storage(&pool, handle))
+        .await
+        .unwrap();
+}
+
 fn test_factory_deps_cache(pool: &ConnectionPool<Core>, rt_handle: Handle) {
     let mut connection = rt_handle.block_on(pool.connection()).unwrap();
     rt_handle.block_on(prepare_postgres(&mut connection));
@@ -636,6 +636,44 @@ impl RocksdbStorage {
             .estimated_number_of_entries(StateKeeperColumnFamily::State)
     }
 
+    /// Creates a RocksDB checkpoint of this storage at the specified `path` and returns the next L1 batch number
+    /// for the checkpointed storage. The checkpoint is consistent since the L1 batch number is persisted atomically
+    /// with the corresponding state changes; the number is read from the checkpoint itself, so this method can be called
+    /// concurrently with methods modifying the storage (e.g., [`Self::synchronize()`]) on its clones.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the storage has unsaved changes or the checkpointed storage is not initialized.
+    /// Propagates RocksDB I/O errors.
+    pub async fn create_checkpoint(&self, path: &Path) -> anyhow::Result<L1BatchNumber> {
+        anyhow::ensure!(
+            self.pending_patch.state.is_empty() && self.pending_patch.factory_deps.is_empty(),
+            "cannot create checkpoint for storage with unsaved changes"
+        );
+
+        let db = self.db.clone();
+        let path = path.to_owned();
+        let number_bytes = tokio::task::spawn_blocking(move || {
+            db.create_checkpoint(&path).with_context(|| {
+                format!(
+                    "failed creating state keeper cache checkpoint at `{}`",
+                    path.display()
+                )
+            })?;
+            let checkpoint = RocksDB::<StateKeeperColumnFamily>::new(&path)
+                .context("failed opening state keeper cache checkpoint")?;
+            checkpoint
+                .get_cf(StateKeeperColumnFamily::State, Self::L1_BATCH_NUMBER_KEY)
+                .context("failed getting L1 batch number from checkpoint")
+        })
+        .await
+        .context("panicked creating state keeper cache checkpoint")??;
+
+        let number_bytes =
+            number_bytes.context("cannot create checkpoint for uninitialized storage")?;
+        try_deserialize_l1_batch_number(&number_bytes).map(L1BatchNumber)
+    }
+
     /// Converts self into the underlying RocksDB primitive
     pub fn into_rocksdb(self) -> RocksDB<StateKeeperColumnFamily> {
         self.db
@@ -172,6 +172,33 @@ async fn rocksdb_storage_syncing_with_postgres() {
     }
 }
 
+#[tokio::test]
+async fn creating_checkpoint() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres(&mut conn).await;
+    let storage_logs = gen_storage_logs(20..40);
+    create_l2_block(&mut conn, L2BlockNumber(1), &storage_logs).await;
+    create_l1_batch(&mut conn, L1BatchNumber(1), &storage_logs).await;
+    drop(conn);
+
+    let dir = TempDir::new().expect("cannot create temporary dir for state keeper");
+    let storage = sync_test_storage(&dir, &pool).await;
+    let checkpoint_path = dir.path().join("checkpoint");
+    let next_l1_batch_number = storage.create_checkpoint(&checkpoint_path).await.unwrap();
+    assert_eq!(next_l1_batch_number, L1BatchNumber(2));
+    drop(storage);
+
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let builder = RocksdbStorage::builder(&checkpoint_path).await.unwrap();
+    assert_eq!(builder.next_l1_batch_number().await, Some(L1BatchNumber(2)));
+    let (mut storage, init_strategy) = builder.ensure_ready(&pool, &stop_receiver).await.unwrap();
+    assert_matches!(init_strategy, InitStrategy::Complete);
+    for log in &storage_logs {
+        assert_eq!(storage.read_value(&log.key), log.value);
+    }
+}
+
 #[tokio::test]
 async fn rocksdb_storage_syncing_fault_tolerance() {
     let pool = ConnectionPool::<Core>::test_pool().await;
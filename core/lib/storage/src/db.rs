@@ -16,8 +16,9 @@ use std::{
 };
 
 use rocksdb::{
-    perf, properties, BlockBasedOptions, Cache, ColumnFamily, ColumnFamilyDescriptor,
-    DBPinnableSlice, Direction, IteratorMode, Options, PrefixRange, ReadOptions, WriteOptions, DB,
+    checkpoint::Checkpoint, perf, properties, BlockBasedOptions, Cache, ColumnFamily,
+    ColumnFamilyDescriptor, DBPinnableSlice, Direction, IteratorMode, Options, PrefixRange,
+    ReadOptions, WriteOptions, DB,
 };
 use thread_local::ThreadLocal;
 use vise::MetricsFamily;
@@ -377,12 +378,36 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
     }
 
     pub fn with_options(path: &Path, options: RocksDBOptions) -> Result<Self, rocksdb::Error> {
+        Self::open(path, options, None)
+    }
+
+    /// Opens a secondary instance of the DB stored at `primary_path`. A secondary instance is readonly and can be opened
+    /// concurrently with the primary instance (e.g., in another process). It only observes the primary state as of its opening
+    /// and needs to [catch up](Self::try_catch_up_with_primary()) with the primary instance to observe subsequent changes.
+    ///
+    /// `secondary_path` is used to store info logs of the secondary instance; it must differ from `primary_path`.
+    /// `options.max_open_files` is ignored since secondary instances always keep all files open.
+    pub fn secondary_with_options(
+        primary_path: &Path,
+        secondary_path: &Path,
+        options: RocksDBOptions,
+    ) -> Result<Self, rocksdb::Error> {
+        Self::open(primary_path, options, Some(secondary_path))
+    }
+
+    fn open(
+        path: &Path,
+        options: RocksDBOptions,
+        secondary_path: Option<&Path>,
+    ) -> Result<Self, rocksdb::Error> {
         let caches = RocksDBCaches::new(options.block_cache_capacity);
         let mut db_options = Self::rocksdb_options(None, None);
-        let max_open_files = if let Some(non_zero) = options.max_open_files {
-            i32::try_from(non_zero.get()).unwrap_or(i32::MAX)
-        } else {
-            -1
+        let max_open_files = match options.max_open_files {
+            // Secondary instances require all files to be open.
+            Some(non_zero) if secondary_path.is_none() => {
+                i32::try_from(non_zero.get()).unwrap_or(i32::MAX)
+            }
+            _ => -1,
         };
         db_options.set_max_open_files(max_open_files);
         let existing_cfs = DB::list_cf(&db_options, path).unwrap_or_else(|err| {
@@ -439,7 +464,11 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
             ColumnFamilyDescriptor::new(cf_name, cf_options)
         });
 
-        let db = DB::open_cf_descriptors(&db_options, path, cfs)?;
+        let db = if let Some(secondary_path) = secondary_path {
+            DB::open_cf_descriptors_as_secondary(&db_options, path, secondary_path, cfs)?
+        } else {
+            DB::open_cf_descriptors(&db_options, path, cfs)?
+        };
         let inner = Arc::new(RocksDBInner {
             db,
             db_name: CF::DB_NAME,
@@ -449,13 +478,21 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
         });
         RocksdbSizeMetrics::register(CF::DB_NAME, Arc::downgrade(&inner));
 
-        tracing::info!(
-            "Initialized RocksDB `{}` at `{}` with {options:?}",
-            CF::DB_NAME,
-            path.display()
-        );
-
-        inner.wait_for_writes_to_resume(&options.stalled_writes_retries);
+        if let Some(secondary_path) = secondary_path {
+            tracing::info!(
+                "Initialized secondary RocksDB `{}` at `{}` (secondary path: `{}`) with {options:?}",
+                CF::DB_NAME,
+                path.display(),
+                secondary_path.display()
+            );
+        } else {
+            tracing::info!(
+                "Initialized RocksDB `{}` at `{}` with {options:?}",
+                CF::DB_NAME,
+                path.display()
+            );
+            inner.wait_for_writes_to_resume(&options.stalled_writes_retries);
+        }
         Ok(Self {
             inner,
             sync_writes: false,
@@ -464,6 +501,12 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
         })
     }
 
+    /// Makes a secondary instance observe the latest changes in the primary instance. Has no effect
+    /// for primary instances.
+    pub fn try_catch_up_with_primary(&self) -> Result<(), rocksdb::Error> {
+        self.inner.db.try_catch_up_with_primary()
+    }
+
     /// Switches on sync writes in [`Self::write()`] and [`Self::put()`]. This has a performance
     /// penalty and is mostly useful for tests.
     #[must_use]
@@ -673,6 +716,21 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
         self.inner.db.raw_iterator_cf_opt(cf, options)
     }
 
+    /// Creates a checkpoint of this DB at the specified `path`, which must not exist. SST files are hard-linked
+    /// if `path` resides on the same filesystem as the DB, so creating a checkpoint is cheap. The checkpoint
+    /// is consistent, i.e., it contains all writes completed before the call and no partial writes.
+    pub fn create_checkpoint(&self, path: &Path) -> Result<(), rocksdb::Error> {
+        let started_at = Instant::now();
+        Checkpoint::new(&self.inner.db)?.create_checkpoint(path)?;
+        tracing::info!(
+            "Created checkpoint of RocksDB `{}` at `{}` in {:?}",
+            CF::DB_NAME,
+            path.display(),
+            started_at.elapsed()
+        );
+        Ok(())
+    }
+
     /// Creates a new profiled operation.
     pub fn new_profiled_operation(&self, name: &'static str) -> ProfiledOperation {
         ProfiledOperation {
@@ -1076,6 +1134,78 @@ mod tests {
         assert_eq!(value.unwrap(), b"value");
     }
 
+    #[test]
+    fn creating_checkpoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let db = RocksDB::<NewColumnFamilies>::new(&db_path)
+            .unwrap()
+            .with_sync_writes();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Other, b"test", b"value");
+        db.write(batch).unwrap();
+
+        let checkpoint_path = temp_dir.path().join("checkpoint");
+        db.create_checkpoint(&checkpoint_path).unwrap();
+        // Writes after the checkpoint must not be visible in it.
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Other, b"test2", b"value2");
+        db.write(batch).unwrap();
+        // The checkpoint path must not exist.
+        db.create_checkpoint(&checkpoint_path).unwrap_err();
+        drop(db);
+
+        let checkpoint = RocksDB::<NewColumnFamilies>::new(&checkpoint_path).unwrap();
+        let value = checkpoint
+            .get_cf(NewColumnFamilies::Other, b"test")
+            .unwrap();
+        assert_eq!(value.unwrap(), b"value");
+        let value = checkpoint
+            .get_cf(NewColumnFamilies::Other, b"test2")
+            .unwrap();
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn secondary_instance_basics() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let db = RocksDB::<NewColumnFamilies>::new(&db_path)
+            .unwrap()
+            .with_sync_writes();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Other, b"test", b"value");
+        db.write(batch).unwrap();
+
+        let secondary = RocksDB::<NewColumnFamilies>::secondary_with_options(
+            &db_path,
+            &temp_dir.path().join("secondary"),
+            RocksDBOptions::default(),
+        )
+        .unwrap();
+        let value = secondary.get_cf(NewColumnFamilies::Other, b"test").unwrap();
+        assert_eq!(value.unwrap(), b"value");
+
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Other, b"test2", b"value2");
+        db.write(batch).unwrap();
+        let value = secondary
+            .get_cf(NewColumnFamilies::Other, b"test2")
+            .unwrap();
+        assert_eq!(value, None);
+
+        secondary.try_catch_up_with_primary().unwrap();
+        let value = secondary
+            .get_cf(NewColumnFamilies::Other, b"test2")
+            .unwrap();
+        assert_eq!(value.unwrap(), b"value2");
+
+        // Secondary instances are readonly.
+        let mut batch = secondary.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Other, b"test3", b"value3");
+        secondary.write(batch).unwrap_err();
+    }
+
     #[test]
     fn write_batch_can_be_restored_from_bytes() {
         let temp_dir = TempDir::new().unwrap();
@@ -2,12 +2,13 @@
 
 use serde::{Deserialize, Serialize};
 use zksync_basic_types::{
-    commitment::PubdataParams, settlement::SettlementLayer, Address, L1BatchNumber, L2BlockNumber,
-    H256, U256,
+    commitment::PubdataParams, settlement::SettlementLayer, web3::Bytes, Address, L1BatchNumber,
+    L2BlockNumber, H256, U256,
 };
 use zksync_contracts::BaseSystemContractsHashes;
 
-use crate::{InteropRoot, ProtocolVersionId};
+use super::{Log, TransactionReceipt};
+use crate::{block::L1BatchHeader, InteropRoot, ProtocolVersionId};
 
 /// Representation of the L2 block, as needed for the EN synchronization.
 ///
@@ -69,3 +70,64 @@ pub struct ConsensusGlobalConfig(pub serde_json::Value);
 /// The wrapped JSON value corresponds to `zksync_dal::consensus::BlockMetadata`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockMetadata(pub serde_json::Value);
+
+/// Storage write persisted for an L2 block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageWrite {
+    pub address: Address,
+    pub key: H256,
+    pub value: H256,
+}
+
+/// Factory dependency (i.e., contract bytecode) persisted for an L2 block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FactoryDep {
+    pub bytecode_hash: H256,
+    pub bytecode: Bytes,
+}
+
+/// L1 batch data necessary to seal the batch without re-executing it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchSealData {
+    pub header: L1BatchHeader,
+    /// Initial bootloader heap of the batch; used to generate batch commitments.
+    pub initial_bootloader_contents: Vec<(usize, U256)>,
+    /// Hashed keys of the initial storage writes in the batch together with their enumeration indices,
+    /// ordered by the index.
+    pub initial_writes: Vec<(H256, u64)>,
+    pub storage_refunds: Vec<u32>,
+    pub pubdata_costs: Vec<i32>,
+}
+
+/// Outputs of executing an L2 block on the main node. Used by the external node to apply the block
+/// directly instead of re-executing its transactions; block metadata and transactions are provided
+/// by [`SyncBlock`].
+///
+/// Call traces and revert reasons of transactions are not included.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L2BlockStateDiff {
+    /// Number of the L2 block.
+    pub number: L2BlockNumber,
+    /// Base fee per gas in the L2 block.
+    pub base_fee_per_gas: u64,
+    /// Gas per pubdata limit in the L2 block.
+    pub gas_per_pubdata_limit: u64,
+    /// Gas limit of the L2 block.
+    pub gas_limit: u64,
+    /// Rolling hash of transactions in the L2 block.
+    pub rolling_txs_hash: Option<H256>,
+    /// Deduplicated storage writes in the order they were persisted.
+    pub storage_writes: Vec<StorageWrite>,
+    /// Factory dependencies published in the L2 block.
+    pub factory_deps: Vec<FactoryDep>,
+    /// Events emitted in the L2 block, ordered by their index in the block.
+    pub events: Vec<Log>,
+    /// Receipts of all transactions in the L2 block, ordered by the transaction index.
+    pub receipts: Vec<TransactionReceipt>,
+    /// Data for sealing the L1 batch. Only present for the last L2 block in a sealed batch.
+    pub l1_batch: Option<L1BatchSealData>,
+}
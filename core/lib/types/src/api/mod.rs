@@ -1026,11 +1026,25 @@ pub struct StorageProof {
     pub index: u64,
 }
 
+/// Compact multi-proof for storage slots returned by `zks_getProof` in the compact mode. In this mode, storage proofs
+/// are ordered by the hashed key and have empty `proof`s; instead, all Merkle tree hashes are provided here.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageMultiProof {
+    /// Lengths of Merkle paths for each storage proof with the leading hashes for empty subtrees skipped.
+    pub path_lengths: Vec<usize>,
+    /// Hashes not computable from the proven entries, in the leaf-to-root order by tree levels,
+    /// and by the hashed key within each level.
+    pub hashes: Vec<H256>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Proof {
     pub address: Address,
     pub storage_proof: Vec<StorageProof>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multi_proof: Option<StorageMultiProof>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
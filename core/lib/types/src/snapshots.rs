@@ -25,6 +25,16 @@ pub enum SnapshotVersion {
     /// Snapshot version made compatible with L1 recovery. Differs from `Version0` by including
     /// hashed keys in storage logs instead of `(address, key)` pairs.
     Version1 = 1,
+    /// Incremental snapshot. Uses the same data format as `Version1`, but only contains storage logs
+    /// and factory dependencies changed since the base snapshot (which can be incremental itself).
+    Version2 = 2,
+}
+
+impl SnapshotVersion {
+    /// Checks whether this version corresponds to an incremental snapshot.
+    pub fn is_incremental(self) -> bool {
+        matches!(self, Self::Version2)
+    }
 }
 
 /// Storage snapshot metadata. Used in DAL to fetch certain snapshot data.
@@ -33,6 +43,8 @@ pub struct SnapshotMetadata {
     pub version: SnapshotVersion,
     /// L1 batch for the snapshot. The data in the snapshot captures node storage at the end of this batch.
     pub l1_batch_number: L1BatchNumber,
+    /// L1 batch of the base snapshot. Only set for incremental snapshots.
+    pub base_l1_batch_number: Option<L1BatchNumber>,
     /// Path to the factory dependencies blob.
     pub factory_deps_filepath: String,
     /// Paths to the storage log blobs. Ordered by the chunk ID. If a certain chunk is not produced yet,
@@ -49,7 +61,7 @@ impl SnapshotMetadata {
 
 /// Snapshot data returned by using JSON-RPC API.
 /// Contains all data not contained in `factory_deps` / `storage_logs` files to perform restore process.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SnapshotHeader {
     // Not a `SnapshotVersion` to have controllable error handling in case of deserializing a header on an outdated node.
@@ -61,6 +73,9 @@ pub struct SnapshotHeader {
     /// Ordered by chunk IDs.
     pub storage_logs_chunks: Vec<SnapshotStorageLogsChunkMetadata>,
     pub factory_deps_filepath: String,
+    /// L1 batch of the base snapshot that should be applied before this one. Only set for incremental snapshots.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_l1_batch_number: Option<L1BatchNumber>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
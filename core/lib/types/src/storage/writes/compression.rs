@@ -187,6 +187,50 @@ pub fn compress_with_best_strategy(prev_value: U256, new_value: U256) -> Vec<u8>
         })
 }
 
+/// Storage value compressed by [`compress_with_best_strategy()`] and decoded from its extended encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressedValue {
+    operation_id: u8,
+    value: U256,
+}
+
+impl CompressedValue {
+    /// Decodes a compressed value (the metadata byte followed by the compressed value) from the start of `bytes`.
+    /// Returns the decoded value and the number of consumed bytes, or `None` if `bytes` are malformed.
+    pub fn decode(bytes: &[u8]) -> Option<(Self, usize)> {
+        let metadata = *bytes.first()?;
+        let operation_id = metadata & 7;
+        let len = if operation_id == 0 {
+            // `CompressionByteNone` has the zero metadata byte.
+            if metadata != 0 {
+                return None;
+            }
+            32
+        } else {
+            usize::from(metadata >> 3)
+        };
+        if operation_id > 3 || len > 32 {
+            return None;
+        }
+        let value = bytes.get(1..=len)?;
+        let value = Self {
+            operation_id,
+            value: U256::from_big_endian(value),
+        };
+        Some((value, len + 1))
+    }
+
+    /// Returns the new value given the previous value of the storage slot.
+    pub fn apply(&self, prev_value: U256) -> U256 {
+        match self.operation_id {
+            1 => prev_value.overflowing_add(self.value).0,
+            2 => prev_value.overflowing_sub(self.value).0,
+            // Transform and no compression both contain the new value
+            _ => self.value,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::{Add, BitAnd, Shr, Sub};
@@ -352,6 +396,29 @@ mod tests {
         assert!((((compressed_val.bits() as f64) / 8f64).ceil() as usize) == 1);
     }
 
+    #[test]
+    fn decompressing_values() {
+        let values = [
+            (U256::from(255438218), U256::from(255438638)),
+            (U256::from(580481589), U256::from(229496100)),
+            (U256::from(580481589), U256::from(1337)),
+            (U256::from(580481589), U256::zero()),
+            (U256::MAX, U256::from(1)),
+            (U256::zero(), U256::MAX),
+            (U256::zero(), U256::MAX / 3),
+        ];
+        for (initial_val, final_val) in values {
+            let compressed = compress_with_best_strategy(initial_val, final_val);
+            let (value, len) = CompressedValue::decode(&compressed).unwrap();
+            assert_eq!(len, compressed.len());
+            assert_eq!(value.apply(initial_val), final_val);
+        }
+
+        assert!(CompressedValue::decode(&[]).is_none());
+        assert!(CompressedValue::decode(&[17, 1]).is_none());
+        assert!(CompressedValue::decode(&[4]).is_none());
+    }
+
     fn verify_add_is_none(initial_val: U256, final_val: U256) {
         let compression_add_strategy = CompressionByteAdd {
             prev_value: initial_val,
@@ -1,8 +1,10 @@
 use std::{convert::TryInto, fmt};
 
+use anyhow::Context as _;
 use serde::{de, ser::SerializeTuple, Deserialize, Deserializer, Serialize, Serializer};
 use zksync_basic_types::{Address, U256};
 
+pub use self::compression::CompressedValue;
 pub(crate) use self::compression::{compress_with_best_strategy, COMPRESSION_VERSION_NUMBER};
 use crate::H256;
 
@@ -190,6 +192,78 @@ fn prepend_header(compressed_state_diffs: Vec<u8>) -> Vec<u8> {
     res.to_vec()
 }
 
+/// Key of a state diff decoded from compressed state diffs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressedStateDiffKey {
+    /// Initial write identified by the derived (i.e., hashed) key of the storage slot.
+    Initial(H256),
+    /// Repeated write identified by the enumeration index of the storage slot.
+    Repeated(u64),
+}
+
+/// State diff decoded from compressed state diffs produced by [`compress_state_diffs()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompressedStateDiff {
+    pub key: CompressedStateDiffKey,
+    /// Compressed value; the previous value of the slot is required to get the new value.
+    /// The previous value of initial writes is zero.
+    pub value: CompressedValue,
+}
+
+/// Decodes compressed state diffs produced by [`compress_state_diffs()`] (including the header) from the start of `data`.
+/// Returns state diffs in the order they were compressed (i.e., initial writes first) and the number of consumed bytes.
+pub fn decompress_state_diffs(data: &[u8]) -> anyhow::Result<(Vec<CompressedStateDiff>, usize)> {
+    anyhow::ensure!(
+        data.len() >= 5,
+        "compressed state diffs header is truncated"
+    );
+    anyhow::ensure!(
+        data[0] == COMPRESSION_VERSION_NUMBER,
+        "unsupported state diffs compression version: {}",
+        data[0]
+    );
+    anyhow::ensure!(
+        data[4] == BYTES_PER_ENUMERATION_INDEX,
+        "unsupported enumeration index size: {}",
+        data[4]
+    );
+    let len = u32::from_be_bytes([0, data[1], data[2], data[3]]) as usize;
+    let body = data
+        .get(5..5 + len)
+        .context("compressed state diffs are truncated")?;
+    anyhow::ensure!(body.len() >= 2, "number of initial writes is truncated");
+    let initial_writes_count = u16::from_be_bytes([body[0], body[1]]);
+
+    let mut state_diffs = vec![];
+    let mut offset = 2;
+    while offset < body.len() {
+        let key = if state_diffs.len() < usize::from(initial_writes_count) {
+            let derived_key = body
+                .get(offset..offset + usize::from(BYTES_PER_DERIVED_KEY))
+                .context("derived key is truncated")?;
+            offset += derived_key.len();
+            CompressedStateDiffKey::Initial(H256::from_slice(derived_key))
+        } else {
+            let index = body
+                .get(offset..offset + usize::from(BYTES_PER_ENUMERATION_INDEX))
+                .context("enumeration index is truncated")?;
+            offset += index.len();
+            let index = u32::from_be_bytes(index.try_into().unwrap());
+            CompressedStateDiffKey::Repeated(index.into())
+        };
+        let (value, value_len) = CompressedValue::decode(&body[offset..])
+            .with_context(|| format!("malformed compressed value at offset {offset}"))?;
+        offset += value_len;
+        state_diffs.push(CompressedStateDiff { key, value });
+    }
+    anyhow::ensure!(
+        state_diffs.len() >= usize::from(initial_writes_count),
+        "expected {initial_writes_count} initial writes, got {}",
+        state_diffs.len()
+    );
+    Ok((state_diffs, 5 + len))
+}
+
 /// Struct for storing tree writes in DB.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct TreeWrite {
@@ -314,6 +388,56 @@ mod tests {
         assert_eq!(expected_bytes, bytes);
     }
 
+    #[test]
+    fn decompressing_state_diffs() {
+        let state_diffs = vec![
+            StateDiffRecord {
+                address: Address::repeat_byte(2),
+                key: U256::from(1u8),
+                derived_key: [1u8; 32],
+                enumeration_index: 0,
+                initial_value: U256::zero(),
+                final_value: U256::MAX,
+            },
+            StateDiffRecord {
+                address: Address::repeat_byte(1),
+                key: U256::from(2u8),
+                derived_key: [2u8; 32],
+                enumeration_index: 3,
+                initial_value: U256::from(64u8),
+                final_value: U256::from(20u8),
+            },
+            StateDiffRecord {
+                address: Address::repeat_byte(1),
+                key: U256::from(1u8),
+                derived_key: [3u8; 32],
+                enumeration_index: 0,
+                initial_value: U256::zero(),
+                final_value: U256::from(1337u16),
+            },
+        ];
+        let mut compressed = compress_state_diffs(state_diffs);
+        let compressed_len = compressed.len();
+        compressed.extend([0xff; 3]); // trailing data must be ignored
+
+        let (decoded, len) = decompress_state_diffs(&compressed).unwrap();
+        assert_eq!(len, compressed_len);
+        let keys: Vec<_> = decoded.iter().map(|diff| diff.key).collect();
+        assert_eq!(
+            keys,
+            [
+                CompressedStateDiffKey::Initial(H256([3; 32])),
+                CompressedStateDiffKey::Initial(H256([1; 32])),
+                CompressedStateDiffKey::Repeated(3),
+            ]
+        );
+        assert_eq!(decoded[0].value.apply(U256::zero()), U256::from(1337u16));
+        assert_eq!(decoded[1].value.apply(U256::zero()), U256::MAX);
+        assert_eq!(decoded[2].value.apply(U256::from(64u8)), U256::from(20u8));
+
+        decompress_state_diffs(&compressed[..compressed_len - 1]).unwrap_err();
+    }
+
     #[test]
     fn test_compression() {
         let initial_add = StateDiffRecord {
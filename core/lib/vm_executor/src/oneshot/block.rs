@@ -1,7 +1,7 @@
 use std::time::SystemTime;
 
 use anyhow::Context;
-use zksync_dal::{Connection, Core, CoreDal, DalError};
+use zksync_dal::{pruning_dal::ArchiveCheckpointMetadata, Connection, Core, CoreDal, DalError};
 use zksync_multivm::{
     interface::{L1BatchEnv, L2BlockEnv, OneshotEnv, StoredL2BlockEnv, SystemEnv, TxExecutionMode},
     vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
@@ -26,6 +26,15 @@ pub struct BlockInfo {
     resolved_block_number: L2BlockNumber,
     l1_batch_timestamp_s: Option<u64>,
     settlement_layer: SettlementLayer,
+    archived: Option<ArchivedBlockInfo>,
+}
+
+/// Information about an L2 block retained as an archive checkpoint after its L1 batch was pruned. Since the block header
+/// is pruned, all block data is taken from the checkpoint metadata.
+#[derive(Debug, Clone, Copy)]
+struct ArchivedBlockInfo {
+    l1_batch: L1BatchNumber,
+    metadata: ArchiveCheckpointMetadata,
 }
 
 impl BlockInfo {
@@ -44,6 +53,7 @@ impl BlockInfo {
             resolved_block_number,
             l1_batch_timestamp_s: None,
             settlement_layer,
+            archived: None,
         })
     }
 
@@ -71,9 +81,41 @@ impl BlockInfo {
             resolved_block_number: number,
             l1_batch_timestamp_s: Some(l1_batch_timestamp),
             settlement_layer,
+            archived: None,
         })
     }
 
+    /// Fetches information for a pruned block retained as an archive checkpoint. Returns `None` if the block is not
+    /// an archive checkpoint, or if the checkpoint lacks information about the previous block necessary to execute calls.
+    pub async fn for_archived_block(
+        connection: &mut Connection<'_, Core>,
+        number: L2BlockNumber,
+    ) -> anyhow::Result<Option<Self>> {
+        let checkpoint = connection
+            .pruning_dal()
+            .get_archive_checkpoint(number)
+            .await
+            .with_context(|| format!("failed loading archive checkpoint for L2 block #{number}"))?;
+        let Some(checkpoint) = checkpoint else {
+            return Ok(None);
+        };
+        let metadata = checkpoint.metadata;
+        if number > L2BlockNumber(0) && metadata.prev_l2_block.is_none() {
+            // We cannot reset the L2 block context to the checkpoint block without the previous block info.
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            resolved_block_number: number,
+            l1_batch_timestamp_s: Some(metadata.l1_batch_timestamp),
+            settlement_layer: metadata.settlement_layer,
+            archived: Some(ArchivedBlockInfo {
+                l1_batch: checkpoint.l1_batch,
+                metadata,
+            }),
+        }))
+    }
+
     /// Returns L2 block number.
     pub fn block_number(&self) -> L2BlockNumber {
         self.resolved_block_number
@@ -93,6 +135,9 @@ impl BlockInfo {
         &self,
         connection: &mut Connection<'_, Core>,
     ) -> anyhow::Result<BatchFeeInput> {
+        if let Some(archived) = &self.archived {
+            return Ok(archived.metadata.batch_fee_input);
+        }
         let header = connection
             .blocks_dal()
             .get_l2_block_header(self.resolved_block_number)
@@ -107,8 +152,16 @@ impl BlockInfo {
         connection: &mut Connection<'_, Core>,
     ) -> anyhow::Result<ResolvedBlockInfo> {
         let (state_l2_block_number, vm_l1_batch_number, l1_batch_timestamp);
+        let (state_l2_block_hash, protocol_version);
 
-        let l2_block_header = if let Some(l1_batch_timestamp_s) = self.l1_batch_timestamp_s {
+        if let Some(archived) = &self.archived {
+            // The block header and L1 batch are pruned; use the archive checkpoint metadata instead.
+            vm_l1_batch_number = archived.l1_batch;
+            l1_batch_timestamp = archived.metadata.l1_batch_timestamp;
+            state_l2_block_number = self.resolved_block_number;
+            state_l2_block_hash = archived.metadata.l2_block_hash;
+            protocol_version = archived.metadata.protocol_version;
+        } else if let Some(l1_batch_timestamp_s) = self.l1_batch_timestamp_s {
             vm_l1_batch_number = connection
                 .storage_web3_dal()
                 .resolve_l1_batch_number_of_l2_block(self.resolved_block_number)
@@ -118,11 +171,13 @@ impl BlockInfo {
             l1_batch_timestamp = l1_batch_timestamp_s;
             state_l2_block_number = self.resolved_block_number;
 
-            connection
+            let l2_block_header = connection
                 .blocks_dal()
                 .get_l2_block_header(self.resolved_block_number)
                 .await?
-                .context("resolved L2 block disappeared from storage")?
+                .context("resolved L2 block disappeared from storage")?;
+            state_l2_block_hash = l2_block_header.hash;
+            protocol_version = l2_block_header.protocol_version;
         } else {
             vm_l1_batch_number = connection
                 .blocks_dal()
@@ -142,26 +197,27 @@ impl BlockInfo {
                 .context("incorrect system time")?
                 .as_secs();
             l1_batch_timestamp = current_timestamp.max(sealed_l2_block_header.timestamp + 1);
-            sealed_l2_block_header
-        };
+            state_l2_block_hash = sealed_l2_block_header.hash;
+            protocol_version = sealed_l2_block_header.protocol_version;
+        }
 
         // Blocks without version specified are considered to be of `Version9`.
         // TODO: remove `unwrap_or` when protocol version ID will be assigned for each block.
-        let protocol_version = l2_block_header
-            .protocol_version
-            .unwrap_or(ProtocolVersionId::last_potentially_undefined());
+        let protocol_version =
+            protocol_version.unwrap_or(ProtocolVersionId::last_potentially_undefined());
 
         let use_evm_emulator =
             Self::is_evm_emulation_enabled(connection, state_l2_block_number).await?;
         Ok(ResolvedBlockInfo {
             state_l2_block_number,
-            state_l2_block_hash: l2_block_header.hash,
+            state_l2_block_hash,
             vm_l1_batch_number,
             l1_batch_timestamp,
             protocol_version,
             use_evm_emulator,
             is_pending: self.is_pending_l2_block(),
             settlement_layer: self.settlement_layer,
+            archived: self.archived,
         })
     }
 
@@ -203,6 +259,7 @@ pub struct ResolvedBlockInfo {
     use_evm_emulator: bool,
     is_pending: bool,
     settlement_layer: SettlementLayer,
+    archived: Option<ArchivedBlockInfo>,
 }
 
 impl ResolvedBlockInfo {
@@ -218,6 +275,12 @@ impl ResolvedBlockInfo {
     pub fn use_evm_emulator(&self) -> bool {
         self.use_evm_emulator
     }
+
+    /// Whether the block is a pruned block retained as an archive checkpoint. Storage for such blocks must be created
+    /// using `PostgresStorage::archived()`.
+    pub fn is_archived(&self) -> bool {
+        self.archived.is_some()
+    }
 }
 
 impl<C: ContractsKind> OneshotEnvParameters<C> {
@@ -247,6 +310,8 @@ impl<C: ContractsKind> OneshotEnvParameters<C> {
                 .with_context(|| "failed loading interop fee for unsealed L1 batch".to_string())?
                 .map(|unsealed_batch| unsealed_batch.interop_fee)
                 .unwrap_or(interop_fee_fallback)
+        } else if let Some(archived) = &resolved_block_info.archived {
+            archived.metadata.interop_fee
         } else {
             let batch_number = resolved_block_info.vm_l1_batch_number;
             connection
@@ -357,6 +422,26 @@ async fn load_l2_block_info(
         // We need to reset L2 block info in storage to process transaction in the current block context.
         // Actual resetting will be done after `storage_view` is created.
         let prev_block_number = resolved_block_info.state_l2_block_number - 1;
+        if let Some(prev_block) = resolved_block_info
+            .archived
+            .and_then(|archived| archived.metadata.prev_l2_block)
+        {
+            // Storage logs for the previous block may be pruned; use the data copied to the archive checkpoint.
+            current_block = Some(StoredL2BlockEnv {
+                number: prev_block_number.0,
+                timestamp: prev_block.timestamp,
+                txs_rolling_hash: prev_block.txs_rolling_hash,
+            });
+            let next_block = L2BlockEnv {
+                number: next_block.number,
+                timestamp: next_block.timestamp,
+                prev_block_hash: prev_block.hash,
+                max_virtual_blocks_to_create: 1,
+                interop_roots: vec![],
+            };
+            return Ok((next_block, current_block));
+        }
+
         let prev_l2_block = read_stored_l2_block(connection, prev_block_number)
             .await
             .context("failed reading previous L2 block info")?;
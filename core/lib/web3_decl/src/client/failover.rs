@@ -0,0 +1,550 @@
+//! Client with failover among several L2 upstreams.
+
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+};
+
+use async_trait::async_trait;
+use jsonrpsee::{
+    core::{
+        client::{BatchResponse, ClientT, Error},
+        params::BatchRequestBuilder,
+        traits::ToRpcParams,
+    },
+    types::error::ErrorCode,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{boxed::RawParams, DynClient, ForWeb3Network, Network, TaggedClient};
+use crate::error::PrunedErrorData;
+
+/// Status of an upstream tracked by [`FailoverClient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum UpstreamStatus {
+    /// Upstream isn't validated yet, or its status is unknown.
+    Unvalidated,
+    /// Upstream is validated and responds to requests.
+    Healthy {
+        /// Latest L2 block number reported by the upstream.
+        block_number: u64,
+    },
+    /// Upstream has recently failed a request; it won't be used until its status is updated.
+    Failing,
+    /// Upstream has failed validation (e.g., it belongs to another chain) and must never be used.
+    Invalid,
+}
+
+#[derive(Debug)]
+struct FailoverState {
+    statuses: Vec<UpstreamStatus>,
+    active: usize,
+}
+
+impl FailoverState {
+    /// Returns healthy additional upstreams (in the specified order) that lag behind the most advanced healthy upstream
+    /// by at most `max_lag` blocks.
+    fn eligible_upstreams(&self, max_lag: u64) -> Vec<usize> {
+        let best_block_number = self
+            .statuses
+            .iter()
+            .filter_map(|status| match status {
+                UpstreamStatus::Healthy { block_number } => Some(*block_number),
+                _ => None,
+            })
+            .max();
+        let Some(best_block_number) = best_block_number else {
+            return vec![];
+        };
+
+        (1..self.statuses.len())
+            .filter(|&i| match self.statuses[i] {
+                UpstreamStatus::Healthy { block_number } => {
+                    best_block_number - block_number <= max_lag
+                }
+                _ => false,
+            })
+            .collect()
+    }
+
+    /// Selects the first eligible upstream in the priority order (additional upstreams in the specified order,
+    /// then the primary one). If there are no eligible upstreams, falls back to the primary upstream.
+    fn select(&mut self, max_lag: u64) -> usize {
+        self.active = self
+            .eligible_upstreams(max_lag)
+            .first()
+            .copied()
+            .unwrap_or(0);
+        self.active
+    }
+}
+
+/// Reason to retry a request with another upstream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FailoverReason {
+    /// Upstream is unreachable or overloaded; it should not be used until its status is updated.
+    UpstreamFailure,
+    /// Upstream is healthy, but cannot serve this specific call (e.g., the requested block is pruned on it).
+    CallUnavailable,
+}
+
+/// Returns a reason to retry a request with another upstream after getting the specified error, if any.
+fn failover_reason(err: &Error) -> Option<FailoverReason> {
+    /// Code of [`Web3Error::TreeApiUnavailable`](crate::error::Web3Error::TreeApiUnavailable) returned by the API server.
+    const TREE_API_UNAVAILABLE_CODE: i32 = 6;
+
+    match err {
+        Error::Transport(_) | Error::RequestTimeout | Error::RestartNeeded(_) => {
+            Some(FailoverReason::UpstreamFailure)
+        }
+        Error::Call(err) if err.code() == ErrorCode::ServerIsBusy.code() => {
+            Some(FailoverReason::UpstreamFailure)
+        }
+        Error::Call(err) if err.code() == TREE_API_UNAVAILABLE_CODE => {
+            Some(FailoverReason::CallUnavailable)
+        }
+        Error::Call(err) if PrunedErrorData::from_error(err).is_some() => {
+            Some(FailoverReason::CallUnavailable)
+        }
+        _ => None,
+    }
+}
+
+/// L2 client sending requests to one of several upstreams (the main node or other nodes serving the same API),
+/// with lag-based upstream selection and failover on transport errors.
+///
+/// Requests are rotated among eligible additional upstreams (i.e., healthy ones not lagging behind too much).
+/// If an upstream fails, or cannot serve a specific call (e.g., because the requested block is pruned on it),
+/// the request is retried with the next eligible upstream, and ultimately with the primary one.
+///
+/// The first upstream is the primary one (i.e., the main node); it's trusted and is used as a fallback
+/// if no other upstreams are healthy. Other upstreams are used only after their status is set to
+/// [`UpstreamStatus::Healthy`] via [`Self::update_status()`], which is supposed to be done by a component
+/// validating upstreams and tracking their latest blocks.
+#[derive(Clone)]
+pub struct FailoverClient<Net: Network> {
+    upstreams: Arc<[Box<DynClient<Net>>]>,
+    state: Arc<RwLock<FailoverState>>,
+    /// Counter used to rotate requests among eligible upstreams.
+    rotation: Arc<AtomicUsize>,
+    max_lag: u64,
+    component_name: &'static str,
+    network: Net,
+}
+
+impl<Net: Network> fmt::Debug for FailoverClient<Net> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("FailoverClient")
+            .field("upstreams", &self.upstreams)
+            .field("state", &self.state)
+            .field("rotation", &self.rotation)
+            .field("max_lag", &self.max_lag)
+            .field("component_name", &self.component_name)
+            .field("network", &self.network)
+            .finish()
+    }
+}
+
+impl<Net: Network> FailoverClient<Net> {
+    /// Creates a client with the specified upstreams; the first upstream is the primary one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `upstreams` is empty.
+    pub fn new(upstreams: Vec<Box<DynClient<Net>>>, max_lag: u64) -> Self {
+        assert!(!upstreams.is_empty(), "no upstreams provided");
+        let network = upstreams[0].network();
+        let statuses = (0..upstreams.len())
+            .map(|i| {
+                if i == 0 {
+                    UpstreamStatus::Healthy { block_number: 0 }
+                } else {
+                    UpstreamStatus::Unvalidated
+                }
+            })
+            .collect();
+        Self {
+            upstreams: upstreams.into(),
+            state: Arc::new(RwLock::new(FailoverState {
+                statuses,
+                active: 0,
+            })),
+            rotation: Arc::default(),
+            max_lag,
+            component_name: "",
+            network,
+        }
+    }
+
+    /// Returns all upstreams in the order they were supplied to the constructor.
+    pub fn upstreams(&self) -> &[Box<DynClient<Net>>] {
+        &self.upstreams
+    }
+
+    /// Returns the index of the upstream with the highest priority among eligible ones. Note that requests are rotated
+    /// among all eligible upstreams.
+    pub fn active_upstream(&self) -> usize {
+        self.state
+            .read()
+            .expect("failover state is poisoned")
+            .active
+    }
+
+    /// Returns statuses of all upstreams.
+    pub fn statuses(&self) -> Vec<UpstreamStatus> {
+        let state = self.state.read().expect("failover state is poisoned");
+        state.statuses.clone()
+    }
+
+    /// Updates the status of the upstream with the specified index and reselects the active upstream.
+    /// Returns the index of the active upstream after the update.
+    pub fn update_status(&self, index: usize, status: UpstreamStatus) -> usize {
+        let mut state = self.state.write().expect("failover state is poisoned");
+        if state.statuses[index] == UpstreamStatus::Invalid {
+            return state.active; // invalid upstreams can never be restored
+        }
+        state.statuses[index] = status;
+        let prev_active = state.active;
+        let active = state.select(self.max_lag);
+        if active != prev_active {
+            tracing::info!(
+                "Switched active upstream from #{prev_active} to #{active}; upstream statuses: {:?}",
+                state.statuses
+            );
+        }
+        active
+    }
+
+    /// Returns upstreams to send the next request to, in the order they should be tried. Eligible additional upstreams
+    /// are rotated so that requests are spread among them; the primary upstream is always the last resort.
+    fn request_order(&self) -> Vec<usize> {
+        let mut order = {
+            let state = self.state.read().expect("failover state is poisoned");
+            state.eligible_upstreams(self.max_lag)
+        };
+        if !order.is_empty() {
+            let start = self.rotation.fetch_add(1, Ordering::Relaxed) % order.len();
+            order.rotate_left(start);
+        }
+        order.push(0);
+        order
+    }
+
+    /// Checks whether a request should be retried with another upstream after the specified error.
+    /// If the upstream has failed, marks it as failing.
+    fn should_fail_over(&self, index: usize, err: &Error) -> bool {
+        match failover_reason(err) {
+            Some(FailoverReason::UpstreamFailure) => {
+                tracing::warn!(
+                    component = self.component_name,
+                    "Request to upstream #{index} failed, marking it as failing: {err}"
+                );
+                self.update_status(index, UpstreamStatus::Failing);
+                true
+            }
+            Some(FailoverReason::CallUnavailable) => {
+                tracing::debug!(
+                    component = self.component_name,
+                    "Upstream #{index} cannot serve request, trying the next upstream: {err}"
+                );
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<Net: Network> ForWeb3Network for FailoverClient<Net> {
+    type Net = Net;
+
+    fn network(&self) -> Self::Net {
+        self.network
+    }
+
+    fn component(&self) -> &'static str {
+        self.component_name
+    }
+}
+
+impl<Net: Network> TaggedClient for FailoverClient<Net> {
+    fn set_component(&mut self, component_name: &'static str) {
+        self.component_name = component_name;
+        self.upstreams = self
+            .upstreams
+            .iter()
+            .map(|client| client.clone().for_component(component_name))
+            .collect();
+    }
+}
+
+#[async_trait]
+impl<Net: Network> ClientT for FailoverClient<Net> {
+    async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), Error>
+    where
+        Params: ToRpcParams + Send,
+    {
+        let params = RawParams::new(params)?;
+        let mut last_err = None;
+        for index in self.request_order() {
+            match self.upstreams[index]
+                .generic_notification(method, params.clone())
+                .await
+            {
+                Err(err) if self.should_fail_over(index, &err) => last_err = Some(err),
+                result => return result,
+            }
+        }
+        Err(last_err.unwrap_or_else(|| Error::Custom("all upstreams have failed".into())))
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, Error>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        let params = RawParams::new(params)?;
+        let mut last_err = None;
+        for index in self.request_order() {
+            match self.upstreams[index]
+                .generic_request(method, params.clone())
+                .await
+            {
+                Err(err) if self.should_fail_over(index, &err) => last_err = Some(err),
+                result => return serde_json::from_value(result?).map_err(Error::ParseError),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| Error::Custom("all upstreams have failed".into())))
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, Error>
+    where
+        R: DeserializeOwned + fmt::Debug + 'a,
+    {
+        let mut last_err = None;
+        for index in self.request_order() {
+            let upstream: &DynClient<Net> = self.upstreams[index].as_ref();
+            match ClientT::batch_request(&upstream, batch.clone()).await {
+                Err(err) if self.should_fail_over(index, &err) => last_err = Some(err),
+                result => return result,
+            }
+        }
+        Err(last_err.unwrap_or_else(|| Error::Custom("all upstreams have failed".into())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use jsonrpsee::types::ErrorObjectOwned;
+    use zksync_types::{L1BatchNumber, L2BlockNumber, U64};
+
+    use super::*;
+    use crate::{
+        client::{MockClient, L2},
+        error::Web3Error,
+        namespaces::EthNamespaceClient,
+    };
+
+    fn mock_upstream(block_number: u64) -> Box<DynClient<L2>> {
+        let client = MockClient::builder(L2::default())
+            .method("eth_blockNumber", move || Ok(U64::from(block_number)))
+            .build();
+        Box::new(client)
+    }
+
+    fn failing_upstream(call_count: Arc<AtomicUsize>) -> Box<DynClient<L2>> {
+        let client = MockClient::builder(L2::default())
+            .method("eth_blockNumber", move || {
+                call_count.fetch_add(1, Ordering::Relaxed);
+                Err::<U64, _>(Error::RequestTimeout)
+            })
+            .build();
+        Box::new(client)
+    }
+
+    #[tokio::test]
+    async fn selecting_upstream_based_on_lag() {
+        let upstreams = vec![mock_upstream(100), mock_upstream(1), mock_upstream(2)];
+        let client = FailoverClient::new(upstreams, 5);
+        assert_eq!(client.active_upstream(), 0);
+        assert_eq!(client.get_block_number().await.unwrap(), 100.into());
+
+        // Unvalidated upstreams must not be used.
+        let active = client.update_status(0, UpstreamStatus::Healthy { block_number: 100 });
+        assert_eq!(active, 0);
+
+        // Upstream #1 is lagging too much.
+        let active = client.update_status(1, UpstreamStatus::Healthy { block_number: 90 });
+        assert_eq!(active, 0);
+        let active = client.update_status(2, UpstreamStatus::Healthy { block_number: 96 });
+        assert_eq!(active, 2);
+        assert_eq!(client.get_block_number().await.unwrap(), 2.into());
+
+        let active = client.update_status(1, UpstreamStatus::Healthy { block_number: 95 });
+        assert_eq!(active, 1);
+        // Requests are rotated among both eligible upstreams.
+        let mut block_numbers = vec![];
+        for _ in 0..2 {
+            block_numbers.push(client.get_block_number().await.unwrap().as_u64());
+        }
+        block_numbers.sort_unstable();
+        assert_eq!(block_numbers, [1, 2]);
+
+        // Invalid upstreams must never be restored.
+        let active = client.update_status(1, UpstreamStatus::Invalid);
+        assert_eq!(active, 2);
+        let active = client.update_status(1, UpstreamStatus::Healthy { block_number: 100 });
+        assert_eq!(active, 2);
+        assert_eq!(client.statuses()[1], UpstreamStatus::Invalid);
+    }
+
+    #[tokio::test]
+    async fn failing_over_on_transport_errors() {
+        let call_count = Arc::new(AtomicUsize::new(0));
+        let upstreams = vec![mock_upstream(100), failing_upstream(call_count.clone())];
+        let client = FailoverClient::new(upstreams, 5);
+        client.update_status(1, UpstreamStatus::Healthy { block_number: 100 });
+        assert_eq!(client.active_upstream(), 1);
+
+        let client = Box::new(client.clone()) as Box<DynClient<L2>>;
+        let client = client.for_component("test");
+        assert_eq!(client.get_block_number().await.unwrap(), 100.into());
+        assert_eq!(call_count.load(Ordering::Relaxed), 1);
+        // The failing upstream should not be queried until its status is updated.
+        assert_eq!(client.get_block_number().await.unwrap(), 100.into());
+        assert_eq!(call_count.load(Ordering::Relaxed), 1);
+    }
+
+    fn pruned_upstream(call_count: Arc<AtomicUsize>) -> Box<DynClient<L2>> {
+        let client = MockClient::builder(L2::default())
+            .method("eth_blockNumber", move || {
+                call_count.fetch_add(1, Ordering::Relaxed);
+                let err = Web3Error::PrunedBlock(L2BlockNumber(50));
+                let data = err.pruned_data();
+                let err =
+                    ErrorObjectOwned::owned(ErrorCode::InvalidParams.code(), err.to_string(), data);
+                Err::<U64, _>(Error::Call(err))
+            })
+            .build();
+        Box::new(client)
+    }
+
+    #[test]
+    fn detecting_pruned_data_errors() {
+        let pruned_errors = [
+            Web3Error::PrunedBlock(L2BlockNumber(50)),
+            Web3Error::PrunedL1Batch(L1BatchNumber(5)),
+            Web3Error::PrunedBlockData("call traces", L2BlockNumber(50)),
+        ];
+        for err in pruned_errors {
+            let data = err.pruned_data();
+            assert!(data.is_some(), "{err:?}");
+            // The message is irrelevant; only the code and structured data are checked.
+            let err = ErrorObjectOwned::owned(ErrorCode::InvalidParams.code(), "pruned", data);
+            assert_eq!(
+                failover_reason(&Error::Call(err)),
+                Some(FailoverReason::CallUnavailable)
+            );
+        }
+
+        let message = Web3Error::PrunedBlock(L2BlockNumber(50)).to_string();
+        let err = ErrorObjectOwned::owned(ErrorCode::InvalidParams.code(), message, None::<()>);
+        assert_eq!(failover_reason(&Error::Call(err)), None);
+        let err = ErrorObjectOwned::owned(ErrorCode::InvalidParams.code(), "invalid", Some("0x"));
+        assert_eq!(failover_reason(&Error::Call(err)), None);
+    }
+
+    #[tokio::test]
+    async fn rotating_requests_among_healthy_upstreams() {
+        let upstreams = vec![mock_upstream(100), mock_upstream(1), mock_upstream(2)];
+        let client = FailoverClient::new(upstreams, 5);
+        client.update_status(1, UpstreamStatus::Healthy { block_number: 100 });
+        client.update_status(2, UpstreamStatus::Healthy { block_number: 98 });
+
+        let mut block_numbers = vec![];
+        for _ in 0..4 {
+            block_numbers.push(client.get_block_number().await.unwrap().as_u64());
+        }
+        assert_eq!(block_numbers, [1, 2, 1, 2]);
+
+        // Lagging upstreams must be excluded from rotation.
+        client.update_status(2, UpstreamStatus::Healthy { block_number: 90 });
+        for _ in 0..3 {
+            assert_eq!(client.get_block_number().await.unwrap(), 1.into());
+        }
+    }
+
+    #[tokio::test]
+    async fn failing_over_on_pruned_block_errors() {
+        let call_count = Arc::new(AtomicUsize::new(0));
+        let upstreams = vec![
+            mock_upstream(100),
+            pruned_upstream(call_count.clone()),
+            mock_upstream(2),
+        ];
+        let client = FailoverClient::new(upstreams, 5);
+        client.update_status(1, UpstreamStatus::Healthy { block_number: 100 });
+
+        assert_eq!(client.get_block_number().await.unwrap(), 100.into());
+        assert_eq!(call_count.load(Ordering::Relaxed), 1);
+        // The upstream is healthy, it just cannot serve the call; it must not be marked as failing.
+        assert_eq!(
+            client.statuses()[1],
+            UpstreamStatus::Healthy { block_number: 100 }
+        );
+
+        client.update_status(2, UpstreamStatus::Healthy { block_number: 100 });
+        let mut block_numbers = vec![];
+        for _ in 0..2 {
+            block_numbers.push(client.get_block_number().await.unwrap().as_u64());
+        }
+        block_numbers.sort_unstable();
+        // One of the requests is routed to upstream #1 first and then falls over to upstream #2.
+        assert_eq!(block_numbers, [2, 2]);
+        assert_eq!(call_count.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn returning_pruned_block_error_if_no_upstream_can_serve_call() {
+        let call_count = Arc::new(AtomicUsize::new(0));
+        let upstreams = vec![
+            pruned_upstream(call_count.clone()),
+            pruned_upstream(call_count.clone()),
+        ];
+        let client = FailoverClient::new(upstreams, 5);
+        client.update_status(1, UpstreamStatus::Healthy { block_number: 100 });
+
+        let err = client.get_block_number().await.unwrap_err();
+        assert!(
+            matches!(&err, Error::Call(err) if err.message().contains("pruned")),
+            "{err:?}"
+        );
+        assert_eq!(call_count.load(Ordering::Relaxed), 2);
+        assert_eq!(
+            client.statuses()[0],
+            UpstreamStatus::Healthy { block_number: 0 }
+        );
+    }
+
+    #[tokio::test]
+    async fn primary_upstream_is_used_as_last_resort() {
+        let call_count = Arc::new(AtomicUsize::new(0));
+        let upstreams = vec![failing_upstream(call_count.clone()), mock_upstream(100)];
+        let client = FailoverClient::new(upstreams, 5);
+
+        let err = client.get_block_number().await.unwrap_err();
+        assert!(matches!(err, Error::RequestTimeout), "{err:?}");
+        assert_eq!(call_count.load(Ordering::Relaxed), 1);
+        assert_eq!(client.statuses()[0], UpstreamStatus::Failing);
+        assert_eq!(client.active_upstream(), 0);
+    }
+}
@@ -10,6 +10,8 @@
 //!   where it's possible.
 //! - [`BoxedL2Client`] is a generic client (essentially, a wrapper around a trait object). Use it for dependency injection
 //!   instead of `L2Client`. Both `L2Client` and `MockL2Client` are convertible to `BoxedL2Client`.
+//! - [`FailoverClient`] distributes requests among several upstreams with failover. It's used by external nodes
+//!   syncing from multiple L2 nodes.
 
 use std::{
     any,
@@ -40,6 +42,7 @@ use zksync_types::url::SensitiveUrl;
 use self::metrics::{L2ClientMetrics, METRICS};
 pub use self::{
     boxed::{DynClient, ObjectSafeClient},
+    failover::{FailoverClient, UpstreamStatus},
     mock::{MockClient, MockClientBuilder},
     network::{ForWeb3Network, Network, TaggedClient, L1, L2},
     shared::Shared,
@@ -48,6 +51,7 @@ use crate::client::metrics::{ClientLabels, INFO_METRICS};
 
 mod boxed;
 mod decompression;
+mod failover;
 mod metrics;
 mod mock;
 mod network;
@@ -11,8 +11,12 @@ use std::{
     task::{Context, Poll},
 };
 
-use jsonrpsee::{core::ClientError, types::error::ErrorCode};
+use jsonrpsee::{
+    core::ClientError,
+    types::{error::ErrorCode, ErrorObject},
+};
 use pin_project_lite::pin_project;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use zksync_types::{api::SerializationTransactionError, L1BatchNumber, L2BlockNumber};
 
@@ -25,6 +29,10 @@ pub enum Web3Error {
     PrunedBlock(L2BlockNumber),
     #[error("L1 batch with such an ID is pruned; the first retained L1 batch is {0}")]
     PrunedL1Batch(L1BatchNumber),
+    /// Block is not pruned, but a certain kind of data for it (e.g., call traces) is. The first arg is the human-readable
+    /// data description.
+    #[error("Block data ({0}) is pruned; the first block with retained {0} is {1}")]
+    PrunedBlockData(&'static str, L2BlockNumber),
     #[error("{}", _0.as_ref())]
     ProxyError(#[from] EnrichedClientError),
     #[error("{0}")]
@@ -60,6 +68,54 @@ pub enum Web3Error {
     InvalidTransactionRequest(String),
 }
 
+impl Web3Error {
+    /// Returns structured data for errors caused by pruning. This data is returned to clients as the error `data`.
+    pub fn pruned_data(&self) -> Option<PrunedErrorData> {
+        let (pruned, first_retained) = match self {
+            Self::PrunedBlock(number) => (PrunedDataKind::Block, number.0),
+            Self::PrunedL1Batch(number) => (PrunedDataKind::L1Batch, number.0),
+            Self::PrunedBlockData(_, number) => (PrunedDataKind::BlockData, number.0),
+            _ => return None,
+        };
+        Some(PrunedErrorData {
+            pruned,
+            first_retained,
+        })
+    }
+}
+
+/// Kind of pruned data referenced by [`PrunedErrorData`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PrunedDataKind {
+    /// L2 block ([`Web3Error::PrunedBlock`]).
+    Block,
+    /// L1 batch ([`Web3Error::PrunedL1Batch`]).
+    L1Batch,
+    /// Certain kind of data for an L2 block ([`Web3Error::PrunedBlockData`]).
+    BlockData,
+}
+
+/// Structured `data` of errors caused by pruning, which allows clients to detect such errors without parsing
+/// error messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrunedErrorData {
+    pub pruned: PrunedDataKind,
+    /// Number of the first retained L2 block or L1 batch, depending on `pruned`.
+    pub first_retained: u32,
+}
+
+impl PrunedErrorData {
+    /// Extracts pruning data from a JSON-RPC error. Returns `None` if the error is not caused by pruning.
+    pub fn from_error(err: &ErrorObject<'_>) -> Option<Self> {
+        if err.code() != ErrorCode::InvalidParams.code() {
+            return None;
+        }
+        serde_json::from_str(err.data()?.get()).ok()
+    }
+}
+
 /// Client RPC error with additional details: the method name and arguments of the called method.
 ///
 /// The wrapped error can be accessed using [`AsRef`].
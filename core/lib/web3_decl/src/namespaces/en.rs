@@ -28,6 +28,15 @@ pub trait EnNamespace {
         include_transactions: bool,
     ) -> RpcResult<Option<en::SyncBlock>>;
 
+    /// Returns the outputs of executing the specified L2 block, such as storage writes, events and transaction receipts.
+    ///
+    /// This method is used by EN in the fast sync mode, in which blocks are applied without re-executing transactions.
+    #[method(name = "syncL2BlockStateDiff")]
+    async fn sync_l2_block_state_diff(
+        &self,
+        block_number: L2BlockNumber,
+    ) -> RpcResult<Option<en::L2BlockStateDiff>>;
+
     #[method(name = "consensusGlobalConfig")]
     async fn consensus_global_config(&self) -> RpcResult<Option<en::ConsensusGlobalConfig>>;
 
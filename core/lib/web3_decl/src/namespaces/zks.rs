@@ -117,6 +117,7 @@ pub trait ZksNamespace {
         address: Address,
         keys: Vec<H256>,
         l1_batch_number: L1BatchNumber,
+        compact: Option<bool>,
     ) -> RpcResult<Option<Proof>>;
 
     #[method(name = "getBatchFeeInput")]
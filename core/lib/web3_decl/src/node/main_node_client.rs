@@ -10,7 +10,7 @@ use zksync_node_framework::{
 use zksync_types::{url::SensitiveUrl, L2ChainId};
 
 use crate::{
-    client::{Client, DynClient, L2},
+    client::{Client, DynClient, FailoverClient, L2},
     namespaces::EthNamespaceClient,
 };
 
@@ -20,6 +20,8 @@ pub struct MainNodeClientLayer {
     url: SensitiveUrl,
     rate_limit_rps: NonZeroUsize,
     l2_chain_id: L2ChainId,
+    upstream_urls: Vec<SensitiveUrl>,
+    upstream_max_lag: u32,
 }
 
 #[derive(Debug, FromContext)]
@@ -31,6 +33,7 @@ pub struct Input {
 #[derive(Debug, IntoContext)]
 pub struct Output {
     main_node_client: Box<DynClient<L2>>,
+    failover_client: Option<FailoverClient<L2>>,
 }
 
 impl MainNodeClientLayer {
@@ -39,8 +42,27 @@ impl MainNodeClientLayer {
             url,
             rate_limit_rps,
             l2_chain_id,
+            upstream_urls: vec![],
+            upstream_max_lag: 0,
         }
     }
+
+    /// Adds upstreams (e.g., other external nodes) to sync from in addition to the main node. Upstreams are used
+    /// if they lag behind the most advanced upstream by at most `max_lag` L2 blocks; the main node is used as a fallback.
+    /// Upstream statuses must be updated via the [`FailoverClient`] resource.
+    pub fn with_upstreams(mut self, urls: Vec<SensitiveUrl>, max_lag: u32) -> Self {
+        self.upstream_urls = urls;
+        self.upstream_max_lag = max_lag;
+        self
+    }
+
+    fn build_client(&self, url: SensitiveUrl) -> anyhow::Result<Box<DynClient<L2>>> {
+        let client = Client::http(url)?
+            .for_network(self.l2_chain_id.into())
+            .with_allowed_requests_per_second(self.rate_limit_rps)
+            .build();
+        Ok(Box::new(client))
+    }
 }
 
 #[async_trait::async_trait]
@@ -53,13 +75,27 @@ impl WiringLayer for MainNodeClientLayer {
     }
 
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
-        let main_node_client = Client::http(self.url)
-            .context("failed creating JSON-RPC client for main node")?
-            .for_network(self.l2_chain_id.into())
-            .with_allowed_requests_per_second(self.rate_limit_rps)
-            .build();
-
-        let client = Box::new(main_node_client) as Box<DynClient<L2>>;
+        let main_node_client = self
+            .build_client(self.url.clone())
+            .context("failed creating JSON-RPC client for main node")?;
+        let (client, failover_client) = if self.upstream_urls.is_empty() {
+            (main_node_client, None)
+        } else {
+            let mut upstreams = vec![main_node_client];
+            for url in &self.upstream_urls {
+                upstreams.push(
+                    self.build_client(url.clone())
+                        .context("failed creating JSON-RPC client for upstream")?,
+                );
+            }
+            tracing::info!(
+                "Syncing from {} upstreams in addition to the main node",
+                self.upstream_urls.len()
+            );
+            let failover_client = FailoverClient::new(upstreams, self.upstream_max_lag.into());
+            let client = Box::new(failover_client.clone()) as Box<DynClient<L2>>;
+            (client, Some(failover_client))
+        };
 
         // Insert healthcheck
         input
@@ -69,6 +105,7 @@ impl WiringLayer for MainNodeClientLayer {
 
         Ok(Output {
             main_node_client: client,
+            failover_client,
         })
     }
 }
@@ -1,7 +1,7 @@
 use zksync_node_framework::resource::{self, Resource};
 use zksync_types::settlement::{SettlementLayer, WorkingSettlementLayer};
 
-use crate::client::{DynClient, L1, L2};
+use crate::client::{DynClient, FailoverClient, L1, L2};
 
 #[derive(Debug, Clone)]
 pub struct SettlementModeResource(pub WorkingSettlementLayer);
@@ -66,3 +66,11 @@ impl Resource for GatewayClientResource {
         "common/gateway_client".into()
     }
 }
+
+/// Failover client wrapped by the main node client if multiple upstreams are configured. Allows to update
+/// statuses of upstreams.
+impl Resource for FailoverClient<L2> {
+    fn name() -> String {
+        "external_node/main_node_failover_client".into()
+    }
+}
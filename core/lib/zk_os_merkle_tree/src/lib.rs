@@ -196,6 +196,30 @@ impl<DB: Database, P: TreeParams> MerkleTree<DB, P> {
         Ok(patch.create_batch_proof(&self.hasher, vec![], update.take_read_operations()))
     }
 
+    /// Returns leaf indices and values for the specified `keys` at the specified tree version. Entries are returned
+    /// in the same order as `keys`; missing keys correspond to `None`.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an error if the version doesn't exist.
+    /// - Proxies database errors.
+    pub fn entries(&self, version: u64, keys: &[H256]) -> anyhow::Result<Vec<Option<(u64, H256)>>> {
+        let (patch, mut update) = self.create_patch::<TreeEntry>(version, &[], keys)?;
+        let entries = update
+            .take_read_operations()
+            .into_iter()
+            .map(|op| match op {
+                TreeOperation::Hit { index } => {
+                    let leaf = patch
+                        .leaf(index)
+                        .expect("leaf for a read hit is not loaded");
+                    Some((index, leaf.value))
+                }
+                TreeOperation::Miss { .. } => None,
+            });
+        Ok(entries.collect())
+    }
+
     /// Extends this tree by creating its new version.
     ///
     /// All keys in the provided entries must be distinct.
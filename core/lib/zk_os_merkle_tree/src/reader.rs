@@ -75,6 +75,12 @@ impl<DB: Database, P: TreeParams> MerkleTreeReader<DB, P> {
     pub fn prove(&self, version: u64, keys: &[H256]) -> anyhow::Result<BatchTreeProof> {
         self.0.prove(version, keys)
     }
+
+    /// Returns leaf indices and values for the specified `keys` at the specified tree version.
+    /// See [`MerkleTree::entries()`] for details.
+    pub fn entries(&self, version: u64, keys: &[H256]) -> anyhow::Result<Vec<Option<(u64, H256)>>> {
+        self.0.entries(version, keys)
+    }
 }
 
 impl<P: TreeParams> MerkleTreeReader<RocksDBWrapper, P> {
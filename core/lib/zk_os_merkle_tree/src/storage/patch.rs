@@ -281,6 +281,10 @@ impl<P: TreeParams> WorkingPatchSet<P> {
         Ok(())
     }
 
+    pub(crate) fn leaf(&self, index: u64) -> Option<&Leaf> {
+        self.inner.leaves.get(&index)
+    }
+
     pub(crate) fn loaded_leaves_count(&self) -> usize {
         self.inner.leaves.len()
     }
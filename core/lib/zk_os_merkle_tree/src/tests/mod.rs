@@ -526,6 +526,39 @@ fn read_proofs() {
     test_read_proofs(PatchSet::default());
 }
 
+#[test]
+fn getting_entries() {
+    const RNG_SEED: u64 = 123;
+
+    let mut rng = StdRng::seed_from_u64(RNG_SEED);
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    tree.extend(&[]).unwrap();
+    let inserts: Vec<_> = (0..100)
+        .map(|_| TreeEntry {
+            key: H256(rng.gen()),
+            value: H256(rng.gen()),
+        })
+        .collect();
+    tree.extend(&inserts).unwrap();
+
+    let mut keys: Vec<_> = inserts.iter().map(|entry| entry.key).collect();
+    let missing_key = H256(rng.gen());
+    keys.push(missing_key);
+
+    let entries = tree.entries(0, &keys).unwrap();
+    assert!(entries.iter().all(Option::is_none));
+
+    let entries = tree.entries(1, &keys).unwrap();
+    assert_eq!(entries.len(), keys.len());
+    for (i, (entry, insert)) in entries.iter().zip(&inserts).enumerate() {
+        // Leaves are numbered after the 2 guards.
+        assert_eq!(*entry, Some((i as u64 + 2, insert.value)));
+    }
+    assert_eq!(entries.last().unwrap(), &None);
+
+    tree.entries(2, &keys).unwrap_err();
+}
+
 mod rocksdb {
     use serde::{Deserialize, Serialize};
     use serde_with::{hex::Hex, serde_as};
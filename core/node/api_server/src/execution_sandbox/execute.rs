@@ -398,14 +398,19 @@ impl SandboxExecutor {
             }
         }
 
-        let mut storage = PostgresStorage::new_async(
-            Handle::current(),
-            connection,
-            resolved_block_info.state_l2_block_number(),
-            false,
-        )
-        .await
-        .context("cannot create `PostgresStorage`")?;
+        let state_l2_block_number = resolved_block_info.state_l2_block_number();
+        let mut storage = if resolved_block_info.is_archived() {
+            PostgresStorage::archived(Handle::current(), connection, state_l2_block_number)
+                .await
+                .context("cannot create `PostgresStorage` for archive checkpoint")?
+                .with_context(|| {
+                    format!("L2 block #{state_l2_block_number} is no longer an archive checkpoint")
+                })?
+        } else {
+            PostgresStorage::new_async(Handle::current(), connection, state_l2_block_number, false)
+                .await
+                .context("cannot create `PostgresStorage`")?
+        };
 
         if let Some(caches) = &self.storage_caches {
             storage = storage.with_caches(caches.clone());
@@ -3,13 +3,15 @@ use std::{
     time::{Duration, Instant},
 };
 
-use anyhow::Context as _;
 use rand::{thread_rng, Rng};
-use zksync_dal::{pruning_dal::PruningInfo, Connection, Core, CoreDal, DalError};
+use zksync_dal::{
+    pruning_dal::{CategoryPruningInfo, PrunedDataCategory, PruningInfo},
+    Connection, Core, CoreDal, DalError,
+};
 use zksync_multivm::utils::get_eth_call_gas_limit;
 use zksync_types::{
     api, fee_model::BatchFeeInput, settlement::SettlementLayer, L1BatchNumber, L2BlockNumber,
-    ProtocolVersionId, U256,
+    ProtocolVersionId, U256, U64,
 };
 use zksync_vm_executor::oneshot::{BlockInfo, ResolvedBlockInfo};
 
@@ -140,6 +142,7 @@ impl VmConcurrencyLimiter {
 #[derive(Debug, Clone, Copy)]
 struct BlockStartInfoInner {
     info: PruningInfo,
+    category_info: CategoryPruningInfo,
     cached_at: Instant,
 }
 
@@ -173,9 +176,11 @@ impl BlockStartInfo {
         max_cache_age: Duration,
     ) -> anyhow::Result<Self> {
         let info = storage.pruning_dal().get_pruning_info().await?;
+        let category_info = storage.pruning_dal().get_category_pruning_info().await?;
         Ok(Self {
             cached_pruning_info: Arc::new(RwLock::new(BlockStartInfoInner {
                 info,
+                category_info,
                 cached_at: Instant::now(),
             })),
             max_cache_age,
@@ -193,39 +198,46 @@ impl BlockStartInfo {
         &self,
         storage: &mut Connection<'_, Core>,
         now: Instant,
-    ) -> anyhow::Result<PruningInfo> {
+    ) -> anyhow::Result<BlockStartInfoInner> {
         let info = storage.pruning_dal().get_pruning_info().await?;
+        let category_info = storage.pruning_dal().get_category_pruning_info().await?;
 
         let mut new_cached_pruning_info = self
             .cached_pruning_info
             .write()
             .map_err(|_| anyhow::anyhow!("BlockStartInfo is poisoned"))?;
-        Ok(if new_cached_pruning_info.cached_at < now {
+        // If we've got a newer cache already, there's no need to update it again.
+        if new_cached_pruning_info.cached_at < now {
             *new_cached_pruning_info = BlockStartInfoInner {
                 info,
+                category_info,
                 cached_at: now,
             };
-            info
-        } else {
-            // Got a newer cache already; no need to update it again.
-            new_cached_pruning_info.info
-        })
+        }
+        Ok(*new_cached_pruning_info)
     }
 
-    async fn get_pruning_info(
+    async fn get_inner(
         &self,
         storage: &mut Connection<'_, Core>,
-    ) -> anyhow::Result<PruningInfo> {
+    ) -> anyhow::Result<BlockStartInfoInner> {
         let inner = self.copy_inner();
         let now = Instant::now();
         if inner.is_expired(now, self.max_cache_age) {
             // Multiple threads may execute this query if we're very unlucky
             self.update_cache(storage, now).await
         } else {
-            Ok(inner.info)
+            Ok(inner)
         }
     }
 
+    async fn get_pruning_info(
+        &self,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<PruningInfo> {
+        Ok(self.get_inner(storage).await?.info)
+    }
+
     pub async fn first_l2_block(
         &self,
         storage: &mut Connection<'_, Core>,
@@ -248,6 +260,25 @@ impl BlockStartInfo {
         Ok(L1BatchNumber(0))
     }
 
+    /// Returns the first L2 block for which data of the specified category is retained. This block is always greater than or equal
+    /// to [`Self::first_l2_block()`].
+    pub async fn first_l2_block_with_data(
+        &self,
+        category: PrunedDataCategory,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L2BlockNumber> {
+        let inner = self.get_inner(storage).await?;
+        let last_pruned_l2_block = [
+            inner.info.last_soft_pruned,
+            inner.category_info.get(category).last_soft_pruned,
+        ]
+        .into_iter()
+        .flatten()
+        .map(|info| info.l2_block)
+        .max();
+        Ok(last_pruned_l2_block.map_or(L2BlockNumber(0), |block| block + 1))
+    }
+
     /// Checks whether a block with the specified ID is pruned and returns an error if it is.
     /// The `Err` variant wraps the first non-pruned L2 block.
     pub async fn ensure_not_pruned_block(
@@ -324,10 +355,23 @@ impl BlockArgs {
     ) -> Result<Self, BlockArgsError> {
         // We need to check that `block_id` is present in Postgres or can be present in the future
         // (i.e., it does not refer to a pruned block). If called for a pruned block, the returned value
-        // (specifically, `l1_batch_timestamp_s`) will be nonsensical.
-        start_info
+        // (specifically, `l1_batch_timestamp_s`) will be nonsensical. The only exception are pruned blocks
+        // retained as archive checkpoints; for them, block info is loaded from the checkpoint metadata.
+        if let Err(err) = start_info
             .ensure_not_pruned_block(block_id, connection)
-            .await?;
+            .await
+        {
+            if let (
+                BlockArgsError::Pruned(_),
+                api::BlockId::Number(api::BlockNumber::Number(number)),
+            ) = (&err, block_id)
+            {
+                if let Some(archived) = Self::archived(connection, number, block_id).await? {
+                    return Ok(archived);
+                }
+            }
+            return Err(err);
+        }
 
         if block_id == api::BlockId::Number(api::BlockNumber::Pending) {
             return Ok(Self::pending(connection, settlement_layer).await?);
@@ -350,6 +394,30 @@ impl BlockArgs {
         })
     }
 
+    async fn archived(
+        connection: &mut Connection<'_, Core>,
+        number: U64,
+        block_id: api::BlockId,
+    ) -> anyhow::Result<Option<Self>> {
+        let Ok(number) = u32::try_from(number) else {
+            return Ok(None);
+        };
+        let Some(inner) = BlockInfo::for_archived_block(connection, L2BlockNumber(number)).await?
+        else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            inner,
+            resolved: inner.resolve(connection).await?,
+            block_id,
+        }))
+    }
+
+    /// Returns `true` if the block is a pruned block retained as an archive checkpoint.
+    pub fn is_archived(&self) -> bool {
+        self.resolved.is_archived()
+    }
+
     pub fn resolved_block_number(&self) -> L2BlockNumber {
         self.inner.block_number()
     }
@@ -401,14 +469,9 @@ impl BlockArgs {
         let protocol_version = if self.is_pending() {
             connection.blocks_dal().pending_protocol_version().await?
         } else {
-            let block_number = self.inner.block_number();
-            connection
-                .blocks_dal()
-                .get_l2_block_header(block_number)
-                .await?
-                .with_context(|| format!("missing header for resolved block #{block_number}"))?
-                .protocol_version
-                .unwrap_or_else(ProtocolVersionId::last_potentially_undefined)
+            // For non-pending blocks, the resolved protocol version is taken from the block header
+            // (or from the archive checkpoint metadata if the header is pruned).
+            self.resolved.protocol_version()
         };
 
         let effective_gas_limit = Self::calculate_effective_gas_limit(protocol_version, gas_cap);
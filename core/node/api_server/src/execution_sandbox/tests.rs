@@ -105,6 +105,46 @@ async fn creating_block_args() {
     assert_matches!(err, BlockArgsError::Missing);
 }
 
+#[tokio::test]
+async fn first_l2_block_with_pruned_data_category() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParamsInitials::mock())
+        .await
+        .unwrap();
+    storage
+        .pruning_dal()
+        .insert_category_soft_pruning_log(
+            PrunedDataCategory::CallTraces,
+            L1BatchNumber(0),
+            L2BlockNumber(2),
+        )
+        .await
+        .unwrap();
+
+    let start_info = BlockStartInfo::new(&mut storage, Duration::MAX)
+        .await
+        .unwrap();
+    assert_eq!(
+        start_info.first_l2_block(&mut storage).await.unwrap(),
+        L2BlockNumber(0)
+    );
+    assert_eq!(
+        start_info
+            .first_l2_block_with_data(PrunedDataCategory::CallTraces, &mut storage)
+            .await
+            .unwrap(),
+        L2BlockNumber(3)
+    );
+    assert_eq!(
+        start_info
+            .first_l2_block_with_data(PrunedDataCategory::StorageLogs, &mut storage)
+            .await
+            .unwrap(),
+        L2BlockNumber(0)
+    );
+}
+
 #[tokio::test]
 async fn creating_block_args_after_snapshot_recovery() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -1,8 +1,14 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use tokio::sync::RwLock;
-use zksync_config::configs::chain::TimestampAsserterConfig;
-use zksync_dal::node::{PoolResource, ReplicaPool};
+use zksync_config::configs::chain::{DaBackpressureConfig, TimestampAsserterConfig};
+use zksync_dal::{
+    node::{PoolResource, ReplicaPool},
+    ConnectionPool, Core, CoreDal,
+};
 use zksync_health_check::AppHealthCheck;
 use zksync_node_fee_model::node::ApiFeeInputResource;
 use zksync_node_framework::{
@@ -12,7 +18,10 @@ use zksync_node_framework::{
     FromContext, IntoContext,
 };
 use zksync_object_store::ObjectStore;
-use zksync_shared_resources::contracts::L2ContractsResource;
+use zksync_shared_resources::{
+    contracts::L2ContractsResource,
+    da_backpressure::{DaBackpressure, DaBackpressureState},
+};
 use zksync_state::{PostgresStorageCaches, PostgresStorageCachesTask};
 use zksync_types::{vm::FastVmMode, AccountTreeId, Address};
 use zksync_vm_executor::node::ApiTransactionFilter;
@@ -46,6 +55,7 @@ pub struct PostgresStorageCachesConfig {
 /// - `TxSinkResource`
 /// - `PoolResource<ReplicaPool>`
 /// - `ConditionalSealerResource` (optional)
+/// - `DaBackpressure` (optional)
 /// - `FeeInputResource`
 ///
 /// ## Adds resources
@@ -57,6 +67,8 @@ pub struct PostgresStorageCachesConfig {
 /// - `PostgresStorageCachesTask`
 /// - `VmConcurrencyBarrierTask`
 /// - `WhitelistedTokensForAaUpdateTask` (optional)
+/// - `DaBackpressureUpdateTask` (optional; only if DA backpressure is enabled, but `DaBackpressure` is not provided,
+///   e.g. if the API server runs in a separate process from the state keeper)
 #[derive(Debug)]
 pub struct TxSenderLayer {
     postgres_storage_caches_config: PostgresStorageCachesConfig,
@@ -65,6 +77,7 @@ pub struct TxSenderLayer {
     vm_mode: FastVmMode,
     timestamp_asserter_config: TimestampAsserterConfig,
     tx_sender_config: TxSenderConfig,
+    da_backpressure_config: DaBackpressureConfig,
 }
 
 #[derive(Debug, FromContext)]
@@ -75,6 +88,7 @@ pub struct Input {
     fee_input: ApiFeeInputResource,
     main_node_client: Option<Box<DynClient<L2>>>,
     transaction_filter: Option<ApiTransactionFilter>,
+    da_backpressure: Option<DaBackpressure>,
     l2_contracts: L2ContractsResource,
     core_object_store: Option<Arc<dyn ObjectStore>>,
 }
@@ -88,6 +102,8 @@ pub struct Output {
     postgres_storage_caches_task: Option<PostgresStorageCachesTaskWrapper>,
     #[context(task)]
     whitelisted_tokens_for_aa_update_task: Option<WhitelistedTokensForAaUpdateTask>,
+    #[context(task)]
+    da_backpressure_update_task: Option<DaBackpressureUpdateTask>,
 }
 
 impl TxSenderLayer {
@@ -104,6 +120,7 @@ impl TxSenderLayer {
             vm_mode: FastVmMode::Old,
             timestamp_asserter_config,
             tx_sender_config,
+            da_backpressure_config: DaBackpressureConfig::default(),
         }
     }
 
@@ -116,6 +133,13 @@ impl TxSenderLayer {
         self
     }
 
+    /// Sets the DA backpressure config. If backpressure is enabled and the `DaBackpressure` resource is not provided
+    /// by the state keeper, the DA backlog is polled from Postgres. Disabled by default.
+    pub fn with_da_backpressure(mut self, config: DaBackpressureConfig) -> Self {
+        self.da_backpressure_config = config;
+        self
+    }
+
     /// Sets the fast VM modes used for all supported operations.
     pub fn with_vm_mode(mut self, mode: FastVmMode) -> Self {
         self.vm_mode = mode;
@@ -191,11 +215,34 @@ impl WiringLayer for TxSenderLayer {
             executor_options.set_vm_dump_object_store(store);
         }
 
+        // The state keeper doesn't run in the same process; poll the DA backlog from Postgres instead.
+        let mut da_backpressure_update_task = None;
+        let da_backpressure = match input.da_backpressure {
+            Some(da_backpressure) => Some(da_backpressure),
+            None if self.da_backpressure_config.is_enabled() => {
+                let da_backpressure = DaBackpressure::default();
+                input
+                    .app_health
+                    .insert_custom_component(Arc::new(da_backpressure.clone()))
+                    .map_err(WiringError::internal)?;
+                da_backpressure_update_task = Some(DaBackpressureUpdateTask {
+                    config: self.da_backpressure_config,
+                    pool: replica_pool.clone(),
+                    backpressure: da_backpressure.clone(),
+                });
+                Some(da_backpressure)
+            }
+            None => None,
+        };
+
         // Build `TxSender`.
         let mut tx_sender = TxSenderBuilder::new(config, replica_pool, tx_sink);
         if let Some(transaction_filter) = transaction_filter {
             tx_sender = tx_sender.with_transaction_filter(transaction_filter);
         }
+        if let Some(da_backpressure) = da_backpressure {
+            tx_sender = tx_sender.with_da_backpressure(da_backpressure);
+        }
 
         // Add the task for updating the whitelisted tokens for the AA cache.
         let whitelisted_tokens_for_aa_update_task = if self.whitelisted_tokens_for_aa_cache {
@@ -230,6 +277,7 @@ impl WiringLayer for TxSenderLayer {
             postgres_storage_caches_task,
             vm_concurrency_barrier,
             whitelisted_tokens_for_aa_update_task,
+            da_backpressure_update_task,
         })
     }
 }
@@ -304,3 +352,56 @@ impl Task for WhitelistedTokensForAaUpdateTask {
         Ok(())
     }
 }
+
+/// Task updating DA backpressure based on the DA backlog in Postgres. Used if the state keeper (which normally updates
+/// backpressure) runs in a separate process.
+#[derive(Debug)]
+pub struct DaBackpressureUpdateTask {
+    config: DaBackpressureConfig,
+    pool: ConnectionPool<Core>,
+    backpressure: DaBackpressure,
+}
+
+impl DaBackpressureUpdateTask {
+    async fn update(&self) -> anyhow::Result<()> {
+        let mut conn = self.pool.connection_tagged("api").await?;
+        let backlog = conn.data_availability_dal().get_da_backlog().await?;
+        drop(conn);
+
+        let now_millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Incorrect system time")
+            .as_millis() as u64;
+        let state = DaBackpressureState::new(&self.config, &backlog, now_millis);
+        if state.reason.is_some() != self.backpressure.reason().is_some() {
+            if let Some(reason) = &state.reason {
+                tracing::warn!("Engaging DA backpressure for submitted transactions: {reason}");
+            } else {
+                tracing::info!("Releasing DA backpressure for submitted transactions; {backlog:?}");
+            }
+        }
+        self.backpressure.update(state);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for DaBackpressureUpdateTask {
+    fn id(&self) -> TaskId {
+        "da_backpressure_update_task".into()
+    }
+
+    async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        while !*stop_receiver.0.borrow_and_update() {
+            if let Err(err) = self.update().await {
+                tracing::warn!("Failed updating DA backpressure: {err:#}");
+            }
+
+            // Error here corresponds to a timeout w/o `stop_receiver` changed; we're OK with this.
+            tokio::time::timeout(self.config.check_interval, stop_receiver.0.changed())
+                .await
+                .ok();
+        }
+        Ok(())
+    }
+}
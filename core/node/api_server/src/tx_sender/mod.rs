@@ -30,6 +30,7 @@ use zksync_node_fee_model::{
     ApiFeeInputProvider, BatchFeeModelInputProvider, MockBatchFeeParamsProvider,
 };
 use zksync_object_store::ObjectStore;
+use zksync_shared_resources::da_backpressure::DaBackpressure;
 use zksync_state::PostgresStorageCaches;
 use zksync_types::{
     api::state_override::StateOverride,
@@ -196,6 +197,8 @@ pub struct TxSenderBuilder {
     transaction_filter: Option<Arc<dyn TransactionFilter>>,
     /// Cache for tokens that are white-listed for AA.
     whitelisted_tokens_for_aa_cache: Option<Arc<RwLock<Vec<Address>>>>,
+    /// DA backpressure state; if backpressure is applied, submitted transactions are rejected.
+    da_backpressure: Option<DaBackpressure>,
 }
 
 impl TxSenderBuilder {
@@ -210,6 +213,7 @@ impl TxSenderBuilder {
             tx_sink,
             transaction_filter: None,
             whitelisted_tokens_for_aa_cache: None,
+            da_backpressure: None,
         }
     }
 
@@ -223,6 +227,11 @@ impl TxSenderBuilder {
         self
     }
 
+    pub fn with_da_backpressure(mut self, backpressure: DaBackpressure) -> Self {
+        self.da_backpressure = Some(backpressure);
+        self
+    }
+
     pub fn build(
         self,
         batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
@@ -260,6 +269,7 @@ impl TxSenderBuilder {
             vm_concurrency_limiter,
             whitelisted_tokens_for_aa_cache,
             transaction_filter,
+            da_backpressure: self.da_backpressure,
             executor,
         }))
     }
@@ -332,6 +342,8 @@ pub struct TxSenderInner {
     pub(super) whitelisted_tokens_for_aa_cache: Arc<RwLock<Vec<Address>>>,
     /// Batch sealer used to check whether transaction can be executed by the sequencer.
     pub(super) transaction_filter: Arc<dyn TransactionFilter>,
+    /// DA backpressure state shared with the state keeper.
+    pub(super) da_backpressure: Option<DaBackpressure>,
     pub(super) executor: SandboxExecutor,
 }
 
@@ -426,6 +438,16 @@ impl TxSender {
         block_args: BlockArgs,
     ) -> Result<SandboxExecutionOutput, SubmitTxError> {
         let tx_hash = tx.hash();
+        // Transactions won't be included by the state keeper while DA backpressure is applied, so we reject them early.
+        if let Some(reason) = self
+            .0
+            .da_backpressure
+            .as_ref()
+            .and_then(DaBackpressure::reason)
+        {
+            return Err(SubmitTxError::DaBackpressure(reason));
+        }
+
         let stage_latency = SANDBOX_METRICS.start_tx_submit_stage(tx_hash, SubmitTxStage::Validate);
         self.validate_tx(&tx, block_args.protocol_version()).await?;
         stage_latency.observe();
@@ -1,5 +1,6 @@
 use thiserror::Error;
 use zksync_multivm::interface::ExecutionResult;
+use zksync_shared_resources::da_backpressure::DaBackpressureReason;
 use zksync_types::{l2::error::TxCheckError, Address, U256};
 use zksync_web3_decl::error::EnrichedClientError;
 
@@ -71,6 +72,9 @@ pub enum SubmitTxError {
     Internal(#[from] anyhow::Error),
     #[error("contract deployer address {0} is not in the allow list")]
     DeployerNotInAllowList(Address),
+    /// Transactions are temporarily not accepted because L1 batches are not included in the DA layer in time.
+    #[error("transactions are temporarily not accepted: {0}")]
+    DaBackpressure(DaBackpressureReason),
 }
 
 impl SubmitTxError {
@@ -102,6 +106,7 @@ impl SubmitTxError {
             Self::ProxyError(_) => "proxy-error",
             Self::Internal(_) => "internal",
             Self::DeployerNotInAllowList(_) => "deployer-not-in-allow-list",
+            Self::DaBackpressure(_) => "da-backpressure",
         }
     }
 
@@ -8,6 +8,7 @@ use test_casing::test_casing;
 use zksync_multivm::interface::{tracer::ValidationTraces, ExecutionResult};
 use zksync_node_fee_model::{BatchFeeModelInputProvider, MockBatchFeeParamsProvider};
 use zksync_node_test_utils::create_l2_transaction;
+use zksync_shared_resources::da_backpressure::{DaBackpressureReason, DaBackpressureState};
 use zksync_test_contracts::Account;
 
 use super::*;
@@ -239,6 +240,44 @@ async fn sending_transfer_with_incorrect_signature() {
     assert_matches!(err, SubmitTxError::ValidationFailed(_));
 }
 
+#[tokio::test]
+async fn sending_transfer_under_da_backpressure() {
+    let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let mut tx_sender = create_real_tx_sender(pool).await;
+    let da_backpressure = DaBackpressure::default();
+    Arc::get_mut(&mut tx_sender.0).unwrap().da_backpressure = Some(da_backpressure.clone());
+    let block_args = pending_block_args(&tx_sender).await;
+    let mut alice = Account::random();
+
+    let storage = tx_sender.acquire_replica_connection().await.unwrap();
+    StateBuilder::default()
+        .with_balance(alice.address(), u64::MAX.into())
+        .apply(storage)
+        .await;
+
+    da_backpressure.update(DaBackpressureState {
+        pending_l1_batches: 11,
+        oldest_pending_l1_batch_age: None,
+        reason: Some(DaBackpressureReason::TooManyPendingL1Batches {
+            pending: 11,
+            limit: 10,
+        }),
+    });
+    let transfer = alice.create_transfer(1_000_000_000.into());
+    let err = tx_sender
+        .submit_tx(transfer.clone(), block_args.clone())
+        .await
+        .unwrap_err();
+    assert_matches!(
+        err,
+        SubmitTxError::DaBackpressure(DaBackpressureReason::TooManyPendingL1Batches { .. })
+    );
+
+    da_backpressure.update(DaBackpressureState::default());
+    let vm_result = tx_sender.submit_tx(transfer, block_args).await.unwrap();
+    assert!(!vm_result.result.is_failed(), "{vm_result:?}");
+}
+
 #[test_casing(5, LOAD_TEST_CASES)]
 #[tokio::test]
 async fn sending_load_test_transaction(tx_params: LoadnextContractExecutionParams) {
@@ -26,10 +26,14 @@ impl MethodTracer {
     pub(crate) fn map_err(&self, err: Web3Error) -> ErrorObjectOwned {
         self.observe_error(&err);
 
-        let data = match &err {
-            Web3Error::SubmitTransactionError(_, data) => Some(format!("0x{}", hex::encode(data))),
-            Web3Error::ProxyError(_) => Some("0x".to_owned()),
-            _ => None,
+        let data: Option<serde_json::Value> = match &err {
+            Web3Error::SubmitTransactionError(_, data) => {
+                Some(format!("0x{}", hex::encode(data)).into())
+            }
+            Web3Error::ProxyError(_) => Some("0x".into()),
+            _ => err
+                .pruned_data()
+                .map(|data| serde_json::to_value(data).expect("failed serializing pruned data")),
         };
         let code = match err {
             Web3Error::MethodNotImplemented => ErrorCode::MethodNotFound.code(),
@@ -37,6 +41,7 @@ impl MethodTracer {
             Web3Error::NoBlock
             | Web3Error::PrunedBlock(_)
             | Web3Error::PrunedL1Batch(_)
+            | Web3Error::PrunedBlockData(..)
             | Web3Error::TooManyTopics
             | Web3Error::FilterNotFound
             | Web3Error::InvalidFilterBlockHash
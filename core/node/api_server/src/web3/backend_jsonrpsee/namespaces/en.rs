@@ -23,6 +23,15 @@ impl EnNamespaceServer for EnNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn sync_l2_block_state_diff(
+        &self,
+        block_number: L2BlockNumber,
+    ) -> RpcResult<Option<en::L2BlockStateDiff>> {
+        self.sync_l2_block_state_diff_impl(block_number)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn consensus_global_config(&self) -> RpcResult<Option<en::ConsensusGlobalConfig>> {
         self.consensus_global_config_impl()
             .await
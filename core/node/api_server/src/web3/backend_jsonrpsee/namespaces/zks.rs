@@ -158,8 +158,9 @@ impl ZksNamespaceServer for ZksNamespace {
         address: Address,
         keys: Vec<H256>,
         l1_batch_number: L1BatchNumber,
+        compact: Option<bool>,
     ) -> RpcResult<Option<Proof>> {
-        self.get_proofs_impl(address, keys, l1_batch_number)
+        self.get_proofs_impl(address, keys, l1_batch_number, compact.unwrap_or(false))
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
@@ -1,6 +1,6 @@
 use anyhow::Context as _;
 use tokio::runtime::Handle;
-use zksync_dal::{CoreDal, DalError};
+use zksync_dal::{pruning_dal::PrunedDataCategory, CoreDal, DalError};
 use zksync_multivm::interface::{
     BatchTransactionExecutionResult, Call, CallType, ExecutionResult, L2BlockEnv,
     OneshotTracingParams,
@@ -206,6 +206,14 @@ impl DebugNamespace {
             .await?;
 
         let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        self.state
+            .start_info
+            .ensure_data_not_pruned(
+                PrunedDataCategory::CallTraces,
+                block_number,
+                &mut connection,
+            )
+            .await?;
         self.current_method()
             .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
 
@@ -303,6 +311,15 @@ impl DebugNamespace {
             // Transaction doesn't exist or hasn't been sealed in a batch yet.
             return Ok(None);
         };
+        // Do not replay the L1 batch if call traces for it were intentionally pruned.
+        self.state
+            .start_info
+            .ensure_data_not_pruned(
+                PrunedDataCategory::CallTraces,
+                miniblock_number,
+                &mut connection,
+            )
+            .await?;
         drop(connection);
 
         // Replay the L1 batch up to this transaction to generate the missing trace.
@@ -566,6 +583,14 @@ impl DebugNamespace {
             .state
             .resolve_block_args(&mut connection, block_id)
             .await?;
+        self.state
+            .start_info
+            .ensure_data_not_pruned(
+                PrunedDataCategory::StorageLogs,
+                block_args.resolved_block_number(),
+                &mut connection,
+            )
+            .await?;
         self.current_method().set_block_diff(
             self.state
                 .last_sealed_l2_block
@@ -1,11 +1,11 @@
 use anyhow::Context as _;
 use zksync_consensus_roles::validator;
-use zksync_dal::{CoreDal, DalError};
+use zksync_dal::{pruning_dal::PrunedDataCategory, Connection, Core, CoreDal, DalError};
 use zksync_types::{
-    api::{en, ProtocolVersionInfo},
+    api::{en, GetLogsFilter, ProtocolVersionInfo},
     protocol_version::ProtocolSemanticVersion,
     tokens::TokenInfo,
-    Address, L1BatchNumber, L2BlockNumber, U256,
+    Address, L1BatchNumber, L2BlockNumber, Transaction, U256,
 };
 use zksync_web3_decl::{
     error::Web3Error,
@@ -92,6 +92,169 @@ impl EnNamespace {
             .map_err(DalError::generalize)?)
     }
 
+    pub async fn sync_l2_block_state_diff_impl(
+        &self,
+        block_number: L2BlockNumber,
+    ) -> Result<Option<en::L2BlockStateDiff>, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        self.state
+            .start_info
+            .ensure_not_pruned(block_number, &mut connection)
+            .await?;
+        self.state
+            .start_info
+            .ensure_data_not_pruned(
+                PrunedDataCategory::StorageLogs,
+                block_number,
+                &mut connection,
+            )
+            .await?;
+        self.current_method()
+            .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
+
+        // Use a readonly transaction to get a consistent view of the block data.
+        let mut storage = connection
+            .transaction_builder()
+            .unwrap()
+            .set_readonly()
+            .build()
+            .await
+            .context("TransactionBuilder::build()")?;
+
+        let Some(header) = storage
+            .blocks_dal()
+            .get_l2_block_header(block_number)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+
+        let storage_writes = storage
+            .storage_logs_dal()
+            .get_storage_logs_for_l2_block(block_number)
+            .await
+            .map_err(DalError::generalize)?
+            .into_iter()
+            .map(|log| en::StorageWrite {
+                address: *log.key.address(),
+                key: *log.key.key(),
+                value: log.value,
+            })
+            .collect();
+        let factory_deps = storage
+            .factory_deps_dal()
+            .get_factory_deps_for_l2_block(block_number)
+            .await
+            .map_err(DalError::generalize)?
+            .into_iter()
+            .map(|(bytecode_hash, bytecode)| en::FactoryDep {
+                bytecode_hash,
+                bytecode: bytecode.into(),
+            })
+            .collect();
+
+        let filter = GetLogsFilter {
+            from_block: block_number,
+            to_block: block_number,
+            addresses: vec![],
+            topics: vec![],
+        };
+        let events = storage
+            .events_web3_dal()
+            .get_logs(filter, i32::MAX as usize)
+            .await
+            .map_err(DalError::generalize)?;
+
+        let tx_hashes: Vec<_> = storage
+            .transactions_web3_dal()
+            .get_raw_l2_block_transactions(block_number)
+            .await
+            .map_err(DalError::generalize)?
+            .iter()
+            .map(Transaction::hash)
+            .collect();
+        let mut receipts: Vec<_> = storage
+            .transactions_web3_dal()
+            .get_transaction_receipts(&tx_hashes)
+            .await
+            .map_err(DalError::generalize)?
+            .into_iter()
+            .map(|receipt| receipt.inner)
+            .collect();
+        receipts.sort_unstable_by_key(|receipt| receipt.transaction_index);
+
+        let l1_batch_number = storage
+            .blocks_web3_dal()
+            .get_l1_batch_number_of_l2_block(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        let l1_batch = if let Some(l1_batch_number) = l1_batch_number {
+            let (_, last_l2_block) = storage
+                .blocks_web3_dal()
+                .get_l2_block_range_of_l1_batch(l1_batch_number)
+                .await
+                .map_err(DalError::generalize)?
+                .with_context(|| format!("L1 batch #{l1_batch_number} has no L2 blocks"))?;
+            if last_l2_block == block_number {
+                Self::l1_batch_seal_data(&mut storage, l1_batch_number).await?
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        Ok(Some(en::L2BlockStateDiff {
+            number: block_number,
+            base_fee_per_gas: header.base_fee_per_gas,
+            gas_per_pubdata_limit: header.gas_per_pubdata_limit,
+            gas_limit: header.gas_limit,
+            rolling_txs_hash: header.rolling_txs_hash,
+            storage_writes,
+            factory_deps,
+            events,
+            receipts,
+            l1_batch,
+        }))
+    }
+
+    async fn l1_batch_seal_data(
+        storage: &mut Connection<'_, Core>,
+        number: L1BatchNumber,
+    ) -> Result<Option<en::L1BatchSealData>, Web3Error> {
+        let Some(header) = storage
+            .blocks_dal()
+            .get_l1_batch_header(number)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        let initial_bootloader_contents = storage
+            .blocks_dal()
+            .get_initial_bootloader_heap(number)
+            .await?
+            .unwrap_or_default();
+        let storage_oracle_info = storage.blocks_dal().get_storage_oracle_info(number).await?;
+        let (storage_refunds, pubdata_costs) = storage_oracle_info
+            .map(|info| (info.storage_refunds, info.pubdata_costs.unwrap_or_default()))
+            .unwrap_or_default();
+        let initial_writes = storage
+            .storage_logs_dedup_dal()
+            .initial_writes_for_batch(number)
+            .await
+            .map_err(DalError::generalize)?;
+
+        Ok(Some(en::L1BatchSealData {
+            header,
+            initial_bootloader_contents,
+            initial_writes,
+            storage_refunds,
+            pubdata_costs,
+        }))
+    }
+
     pub async fn sync_tokens_impl(
         &self,
         block_number: Option<L2BlockNumber>,
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use anyhow::Context as _;
-use zksync_dal::{CoreDal, DalError};
+use zksync_dal::{pruning_dal::PrunedDataCategory, CoreDal, DalError};
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
@@ -82,6 +82,17 @@ impl EthNamespace {
             .state
             .resolve_block_args(&mut connection, block_id)
             .await?;
+        // Storage logs necessary to execute calls are retained for archive checkpoints.
+        if !block_args.is_archived() {
+            self.state
+                .start_info
+                .ensure_data_not_pruned(
+                    PrunedDataCategory::StorageLogs,
+                    block_args.resolved_block_number(),
+                    &mut connection,
+                )
+                .await?;
+        }
         self.current_method().set_block_diff(
             self.state
                 .last_sealed_l2_block
@@ -472,7 +483,10 @@ impl EthNamespace {
         self.current_method().set_block_id(block_id);
 
         let mut connection = self.state.acquire_connection().await?;
-        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        let block_number = self
+            .state
+            .resolve_block_with_archive(&mut connection, block_id)
+            .await?;
 
         let balance = connection
             .storage_web3_dal()
@@ -692,7 +706,10 @@ impl EthNamespace {
         self.current_method().set_block_id(block_id);
 
         let mut connection = self.state.acquire_connection().await?;
-        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        let block_number = self
+            .state
+            .resolve_block_with_archive(&mut connection, block_id)
+            .await?;
         self.set_block_diff(block_number);
 
         let contract_code = connection
@@ -743,7 +760,10 @@ impl EthNamespace {
 
         let storage_key = StorageKey::new(AccountTreeId::new(address), u256_to_h256(idx));
         let mut connection = self.state.acquire_connection().await?;
-        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        let block_number = self
+            .state
+            .resolve_block_with_archive(&mut connection, block_id)
+            .await?;
         self.set_block_diff(block_number);
         let value = connection
             .storage_web3_dal()
@@ -763,8 +783,10 @@ impl EthNamespace {
         self.current_method().set_block_id(block_id);
 
         let mut connection = self.state.acquire_connection().await?;
-
-        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        let block_number = self
+            .state
+            .resolve_block_with_archive(&mut connection, block_id)
+            .await?;
         self.set_block_diff(block_number);
         let (account_type, mut nonce) = self
             .state
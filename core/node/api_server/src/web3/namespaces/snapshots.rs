@@ -76,6 +76,7 @@ impl SnapshotsNamespace {
             l2_block_number,
             storage_logs_chunks: chunks,
             factory_deps_filepath: snapshot_metadata.factory_deps_filepath,
+            base_l1_batch_number: snapshot_metadata.base_l1_batch_number,
         }))
     }
 }
@@ -1,5 +1,4 @@
-use anyhow::Context as _;
-use zksync_dal::{Connection, Core, CoreDal, DalError};
+use zksync_dal::{Connection, Core, CoreDal};
 use zksync_types::{
     api::{BlockId, BlockNumber},
     transaction_request::CallRequest,
@@ -46,16 +45,7 @@ pub async fn get_protocol_version_for_block(
     if is_pending {
         Ok(connection.blocks_dal().pending_protocol_version().await?)
     } else {
-        let block_number = block_args.resolved_block_number();
-        let header = connection
-            .blocks_dal()
-            .get_l2_block_header(block_number)
-            .await
-            .map_err(DalError::generalize)?
-            .with_context(|| format!("missing header for resolved block #{block_number}"))?;
-
-        Ok(header
-            .protocol_version
-            .unwrap_or_else(zksync_types::ProtocolVersionId::last_potentially_undefined))
+        // Resolved from the block header, or from the archive checkpoint metadata if the header is pruned.
+        Ok(block_args.protocol_version())
     }
 }
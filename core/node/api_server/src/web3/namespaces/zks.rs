@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+
+use anyhow::Context as _;
 use zksync_crypto_primitives::hasher::{keccak::KeccakHasher, Hasher};
 use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_mini_merkle_tree::MiniMerkleTree;
@@ -6,7 +9,8 @@ use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
         state_override::StateOverride, BlockDetails, BridgeAddresses, InteropMode, L1BatchDetails,
-        L2ToL1LogProof, Proof, ProtocolVersion, StorageProof, TransactionDetails,
+        L2ToL1LogProof, Proof, ProtocolVersion, StorageMultiProof, StorageProof,
+        TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -496,13 +500,14 @@ impl ZksNamespace {
         address: Address,
         keys: Vec<H256>,
         l1_batch_number: L1BatchNumber,
+        compact: bool,
     ) -> Result<Option<Proof>, Web3Error> {
         let mut storage = self.state.acquire_connection().await?;
         self.state
             .start_info
             .ensure_not_pruned(l1_batch_number, &mut storage)
             .await?;
-        let hashed_keys = keys
+        let hashed_keys: Vec<_> = keys
             .iter()
             .map(|key| StorageKey::new(AccountTreeId::new(address), *key).hashed_key_u256())
             .collect();
@@ -511,31 +516,44 @@ impl ZksNamespace {
             .tree_api
             .as_deref()
             .ok_or(Web3Error::MethodNotImplemented)?;
-        let proofs_result = tree_api.get_proofs(l1_batch_number, hashed_keys).await;
-        let proofs = match proofs_result {
-            Ok(proofs) => proofs,
-            Err(TreeApiError::NotReady(_)) => return Err(Web3Error::TreeApiUnavailable),
-            Err(TreeApiError::NoVersion {
-                missing_version,
-                version_count,
-            }) => {
-                return if missing_version > version_count {
-                    Ok(None)
-                } else {
-                    Err(Web3Error::InternalError(anyhow::anyhow!(
-                        "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
-                    )))
-                };
-            }
-            Err(TreeApiError::Internal(err)) => return Err(Web3Error::InternalError(err)),
-            Err(_) => {
-                // This branch is not expected to be executed, but has to be provided since the error is non-exhaustive.
-                return Err(Web3Error::InternalError(anyhow::anyhow!(
-                    "Unspecified tree API error"
-                )));
+
+        if compact {
+            let proof_result = tree_api
+                .get_multi_proof(l1_batch_number, hashed_keys.clone())
+                .await;
+            let Some(multi_proof) = Self::map_tree_api_result(proof_result, l1_batch_number)?
+            else {
+                return Ok(None);
+            };
+            let keys_by_hash: HashMap<_, _> = hashed_keys.into_iter().zip(keys).collect();
+            let mut path_lengths = Vec::with_capacity(multi_proof.entries.len());
+            let mut storage_proof = Vec::with_capacity(multi_proof.entries.len());
+            for entry in multi_proof.entries {
+                let key = *keys_by_hash.get(&entry.key).with_context(|| {
+                    format!("tree API returned unexpected key {:#x}", entry.key)
+                })?;
+                path_lengths.push(entry.path_length);
+                storage_proof.push(StorageProof {
+                    key,
+                    proof: vec![],
+                    value: entry.value,
+                    index: entry.index,
+                });
             }
-        };
+            return Ok(Some(Proof {
+                address,
+                storage_proof,
+                multi_proof: Some(StorageMultiProof {
+                    path_lengths,
+                    hashes: multi_proof.hashes,
+                }),
+            }));
+        }
 
+        let proofs_result = tree_api.get_proofs(l1_batch_number, hashed_keys).await;
+        let Some(proofs) = Self::map_tree_api_result(proofs_result, l1_batch_number)? else {
+            return Ok(None);
+        };
         let storage_proof = proofs
             .into_iter()
             .zip(keys)
@@ -550,9 +568,40 @@ impl ZksNamespace {
         Ok(Some(Proof {
             address,
             storage_proof,
+            multi_proof: None,
         }))
     }
 
+    /// Maps a tree API response, returning `Ok(None)` if the requested L1 batch is not processed by the tree yet.
+    fn map_tree_api_result<T>(
+        result: Result<T, TreeApiError>,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Option<T>, Web3Error> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(TreeApiError::NotReady(_)) => Err(Web3Error::TreeApiUnavailable),
+            Err(TreeApiError::NoVersion {
+                missing_version,
+                version_count,
+            }) => {
+                if missing_version > version_count {
+                    Ok(None)
+                } else {
+                    Err(Web3Error::InternalError(anyhow::anyhow!(
+                        "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
+                    )))
+                }
+            }
+            Err(TreeApiError::Internal(err)) => Err(Web3Error::InternalError(err)),
+            Err(_) => {
+                // This branch is not expected to be executed, but has to be provided since the error is non-exhaustive.
+                Err(Web3Error::InternalError(anyhow::anyhow!(
+                    "Unspecified tree API error"
+                )))
+            }
+        }
+    }
+
     pub fn get_base_token_l1_address_impl(&self) -> Result<Address, Web3Error> {
         self.state
             .api_config
@@ -24,7 +24,9 @@ use zksync_config::{
     },
     GenesisConfig,
 };
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
+use zksync_dal::{
+    pruning_dal::PrunedDataCategory, Connection, ConnectionPool, Core, CoreDal, DalError,
+};
 use zksync_object_store::ObjectStore;
 use zksync_shared_resources::{
     api::{BridgeAddressesHandle, SyncState},
@@ -105,6 +107,25 @@ impl BlockStartInfo {
             }
         }
     }
+
+    /// Checks that data of the specified category is not pruned for the specified L2 block. This check is only
+    /// meaningful if the block itself is not pruned.
+    pub(super) async fn ensure_data_not_pruned(
+        &self,
+        category: PrunedDataCategory,
+        block_number: L2BlockNumber,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<(), Web3Error> {
+        let first_l2_block = self.first_l2_block_with_data(category, storage).await?;
+        if block_number < first_l2_block {
+            let description = match category {
+                PrunedDataCategory::CallTraces => "call traces",
+                PrunedDataCategory::StorageLogs => "historical storage state",
+            };
+            return Err(Web3Error::PrunedBlockData(description, first_l2_block));
+        }
+        Ok(())
+    }
 }
 
 /// Builder for Configuration values for the API.
@@ -388,6 +409,47 @@ impl RpcState {
             .ok_or(Web3Error::NoBlock)
     }
 
+    /// Same as [`Self::resolve_block()`], but additionally checks that storage logs are not pruned for the block,
+    /// and allows pruned L2 blocks retained as archive checkpoints by the DB pruner. Storage logs are retained for such blocks,
+    /// so the returned block number can only be used for historical storage reads (balances, storage slots etc.).
+    pub(crate) async fn resolve_block_with_archive(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        block: api::BlockId,
+    ) -> Result<L2BlockNumber, Web3Error> {
+        let result = match self.resolve_block(connection, block).await {
+            Ok(number) => self
+                .start_info
+                .ensure_data_not_pruned(PrunedDataCategory::StorageLogs, number, connection)
+                .await
+                .map(|()| number),
+            Err(err) => Err(err),
+        };
+
+        match result {
+            Err(err @ (Web3Error::PrunedBlock(_) | Web3Error::PrunedBlockData(..))) => {
+                let api::BlockId::Number(api::BlockNumber::Number(number)) = block else {
+                    return Err(err);
+                };
+                let Ok(number) = u32::try_from(number) else {
+                    return Err(err);
+                };
+                let number = L2BlockNumber(number);
+                let checkpoint = connection
+                    .pruning_dal()
+                    .get_archive_checkpoint(number)
+                    .await
+                    .map_err(DalError::generalize)?;
+                if checkpoint.is_some() {
+                    Ok(number)
+                } else {
+                    Err(err)
+                }
+            }
+            result => result,
+        }
+    }
+
     /// Resolves the specified block ID to a block number, which is **not** guaranteed to be present in the node storage.
     /// Returns `None` if the block is known to not be present in the storage (e.g., it's a "finalized" block ID and no blocks
     /// were finalized yet).
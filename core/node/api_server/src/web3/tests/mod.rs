@@ -1,6 +1,7 @@
 use std::{
     collections::{HashMap, HashSet},
     net::Ipv4Addr,
+    num::NonZeroU32,
 };
 
 use assert_matches::assert_matches;
@@ -46,6 +47,7 @@ use zksync_types::{
 use zksync_vm_executor::oneshot::MockOneshotExecutor;
 use zksync_web3_decl::{
     client::{Client, DynClient, L2},
+    error::{PrunedDataKind, PrunedErrorData},
     jsonrpsee::{
         core::{client::ClientT, params::BatchRequestBuilder, ClientError},
         http_client::HttpClient,
@@ -144,6 +146,12 @@ trait HttpTest: Send + Sync {
         StorageInitialization::genesis()
     }
 
+    /// Modifies the storage after [`Self::storage_initialization()`], but before the server is started
+    /// (e.g., to prune data, which is cached by the server on start).
+    async fn prepare_storage(&self, _storage: &mut Connection<'_, Core>) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     fn transaction_executor(&self) -> MockOneshotExecutor {
         MockOneshotExecutor::default()
     }
@@ -274,6 +282,9 @@ async fn test_http_server(test: impl HttpTest) {
         .prepare_storage(&mut storage)
         .await
         .expect("Failed preparing storage for test");
+    test.prepare_storage(&mut storage)
+        .await
+        .expect("Failed preparing storage for test");
     drop(storage);
 
     let (stop_sender, stop_receiver) = watch::channel(false);
@@ -546,7 +557,14 @@ fn assert_pruned_block_error(error: &ClientError, first_retained_block: L2BlockN
                 .contains(&format!("first retained block is {first_retained_block}")),
             "{error:?}"
         );
-        assert!(error.data().is_none(), "{error:?}");
+        let data = PrunedErrorData::from_error(error).unwrap();
+        assert_eq!(
+            data,
+            PrunedErrorData {
+                pruned: PrunedDataKind::Block,
+                first_retained: first_retained_block.0,
+            }
+        );
     } else {
         panic!("Unexpected error: {error:?}");
     }
@@ -628,7 +646,14 @@ fn assert_pruned_l1_batch_error(error: &ClientError, first_retained_l1_batch: L1
             )),
             "{error:?}"
         );
-        assert!(error.data().is_none(), "{error:?}");
+        let data = PrunedErrorData::from_error(error).unwrap();
+        assert_eq!(
+            data,
+            PrunedErrorData {
+                pruned: PrunedDataKind::L1Batch,
+                first_retained: first_retained_l1_batch.0,
+            }
+        );
     } else {
         panic!("Unexpected error: {error:?}");
     }
@@ -870,6 +895,98 @@ async fn getting_transaction_count_for_account_after_snapshot_recovery() {
     test_http_server(TransactionCountAfterSnapshotRecoveryTest).await;
 }
 
+/// Tests `eth_getTransactionCount` at a pruned L2 block retained as an archive checkpoint.
+#[derive(Debug)]
+struct TransactionCountAtArchiveCheckpointTest;
+
+impl TransactionCountAtArchiveCheckpointTest {
+    const ARCHIVE_INTERVAL: NonZeroU32 = NonZeroU32::new(2).unwrap();
+    const LAST_PRUNED_L1_BATCH: L1BatchNumber = L1BatchNumber(2);
+    /// Each L1 batch contains a single L2 block with the same number.
+    const LAST_PRUNED_L2_BLOCK: L2BlockNumber = L2BlockNumber(2);
+}
+
+#[async_trait]
+impl HttpTest for TransactionCountAtArchiveCheckpointTest {
+    async fn prepare_storage(&self, storage: &mut Connection<'_, Core>) -> anyhow::Result<()> {
+        let test_address = Address::repeat_byte(11);
+        for number in 1..=3 {
+            let l2_block_number = L2BlockNumber(number);
+            store_l2_block(storage, l2_block_number, &[]).await?;
+            // Each block increments the account nonce.
+            let nonce_log = StorageLog::new_write_log(
+                get_nonce_key(&test_address),
+                H256::from_low_u64_be(number.into()),
+            );
+            storage
+                .storage_logs_dal()
+                .insert_storage_logs(l2_block_number, &[nonce_log])
+                .await?;
+            seal_l1_batch(storage, L1BatchNumber(number)).await?;
+        }
+
+        storage
+            .pruning_dal()
+            .insert_soft_pruning_log(Self::LAST_PRUNED_L1_BATCH, Self::LAST_PRUNED_L2_BLOCK)
+            .await?;
+        storage
+            .pruning_dal()
+            .hard_prune_batches_range_with_archive(
+                Self::LAST_PRUNED_L1_BATCH,
+                Self::LAST_PRUNED_L2_BLOCK,
+                Some(Self::ARCHIVE_INTERVAL),
+            )
+            .await?;
+        storage
+            .pruning_dal()
+            .insert_hard_pruning_log(
+                Self::LAST_PRUNED_L1_BATCH,
+                Self::LAST_PRUNED_L2_BLOCK,
+                H256::zero(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let test_address = Address::repeat_byte(11);
+        let first_retained_block = Self::LAST_PRUNED_L2_BLOCK + 1;
+        // L2 block #1 is pruned and isn't an archive checkpoint.
+        let number = api::BlockIdVariant::BlockNumber(1.into());
+        let error = client
+            .get_transaction_count(test_address, Some(number))
+            .await
+            .unwrap_err();
+        assert_pruned_block_error(&error, first_retained_block);
+
+        // L2 block #2 is the archive checkpoint for L1 batch #2.
+        let number = api::BlockIdVariant::BlockNumber(Self::LAST_PRUNED_L2_BLOCK.0.into());
+        let archived_count = client
+            .get_transaction_count(test_address, Some(number))
+            .await?;
+        assert_eq!(archived_count, 2.into());
+
+        let latest_block_numbers = [api::BlockNumber::Latest, first_retained_block.0.into()];
+        for number in latest_block_numbers {
+            let number = api::BlockIdVariant::BlockNumber(number);
+            let latest_count = client
+                .get_transaction_count(test_address, Some(number))
+                .await?;
+            assert_eq!(latest_count, 3.into());
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_transaction_count_at_archive_checkpoint() {
+    test_http_server(TransactionCountAtArchiveCheckpointTest).await;
+}
+
 #[derive(Debug)]
 struct TransactionReceiptsTest;
 
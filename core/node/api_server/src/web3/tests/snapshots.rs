@@ -48,6 +48,7 @@ impl HttpTest for SnapshotBasicsTest {
                 L1BatchNumber(1),
                 Self::CHUNK_COUNT,
                 "file:///factory_deps",
+                None,
             )
             .await?;
 
@@ -1,6 +1,7 @@
 //! Tests for the VM-instantiating methods (e.g., `eth_call`).
 
 use std::{
+    num::NonZeroU32,
     str,
     sync::{
         atomic::{AtomicU32, Ordering},
@@ -363,6 +364,98 @@ async fn call_method_after_snapshot_recovery() {
     test_http_server(CallTestAfterSnapshotRecovery::default()).await;
 }
 
+/// Tests `eth_call` at a pruned L2 block retained as an archive checkpoint.
+#[derive(Debug, Default)]
+struct CallTestAtArchiveCheckpoint {
+    fee_input: ExpectedFeeInput,
+}
+
+impl CallTestAtArchiveCheckpoint {
+    const ARCHIVE_INTERVAL: NonZeroU32 = NonZeroU32::new(2).unwrap();
+    const LAST_PRUNED_L1_BATCH: L1BatchNumber = L1BatchNumber(2);
+    /// Each L1 batch contains a single L2 block with the same number.
+    const LAST_PRUNED_L2_BLOCK: L2BlockNumber = L2BlockNumber(2);
+}
+
+#[async_trait]
+impl HttpTest for CallTestAtArchiveCheckpoint {
+    async fn prepare_storage(&self, storage: &mut Connection<'_, Core>) -> anyhow::Result<()> {
+        for number in 1..=3 {
+            store_l2_block(storage, L2BlockNumber(number), &[]).await?;
+            seal_l1_batch(storage, L1BatchNumber(number)).await?;
+        }
+
+        storage
+            .pruning_dal()
+            .insert_soft_pruning_log(Self::LAST_PRUNED_L1_BATCH, Self::LAST_PRUNED_L2_BLOCK)
+            .await?;
+        storage
+            .pruning_dal()
+            .hard_prune_batches_range_with_archive(
+                Self::LAST_PRUNED_L1_BATCH,
+                Self::LAST_PRUNED_L2_BLOCK,
+                Some(Self::ARCHIVE_INTERVAL),
+            )
+            .await?;
+        storage
+            .pruning_dal()
+            .insert_hard_pruning_log(
+                Self::LAST_PRUNED_L1_BATCH,
+                Self::LAST_PRUNED_L2_BLOCK,
+                H256::zero(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    fn transaction_executor(&self) -> MockOneshotExecutor {
+        CallTest::create_executor(L2BlockNumber(3), self.fee_input.clone())
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let first_retained_block = Self::LAST_PRUNED_L2_BLOCK + 1;
+        // L2 block #1 is pruned and isn't an archive checkpoint.
+        let error = client
+            .call(
+                CallTest::call_request(b"pruned"),
+                Some(api::BlockIdVariant::BlockNumber(1.into())),
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert_pruned_block_error(&error, first_retained_block);
+
+        // L2 block #2 is the archive checkpoint for L1 batch #2. Its header and L1 batch are pruned,
+        // so the block info must be taken from the checkpoint.
+        let archived_block = api::BlockNumber::from(Self::LAST_PRUNED_L2_BLOCK.0);
+        self.fee_input.expect_for_block(archived_block, 1.0);
+        let call_result = client
+            .call(
+                CallTest::call_request(b"block=2"),
+                Some(api::BlockIdVariant::BlockNumber(archived_block)),
+                None,
+            )
+            .await?;
+        assert_eq!(call_result.0, b"output");
+
+        self.fee_input.expect_default(1.0);
+        let call_result = client
+            .call(CallTest::call_request(b"pending"), None, None)
+            .await?;
+        assert_eq!(call_result.0, b"output");
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn call_method_at_archive_checkpoint() {
+    test_http_server(CallTestAtArchiveCheckpoint::default()).await;
+}
+
 #[derive(Debug)]
 struct CallTestWithSlowVm;
 
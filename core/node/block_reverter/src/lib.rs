@@ -9,14 +9,14 @@ use serde::Serialize;
 use tokio::{fs, sync::Semaphore};
 use zksync_config::EthConfig;
 use zksync_contracts::hyperchain_contract;
-use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_dal::{blocks_dal::RollbackRowCounts, Connection, ConnectionPool, Core, CoreDal};
 // Public re-export to simplify the API use.
 pub use zksync_eth_client as eth_client;
 use zksync_eth_client::{BoundEthInterface, CallFunctionArgs, EthInterface, Options};
-use zksync_merkle_tree::domain::ZkSyncTree;
+use zksync_merkle_tree::domain::{ZkSyncTree, ZkSyncTreeReader};
 use zksync_object_store::{ObjectStore, ObjectStoreError};
-use zksync_state::RocksdbStorage;
-use zksync_storage::RocksDB;
+use zksync_state::{RocksdbStorage, RocksdbStorageBuilder, StateKeeperColumnFamily};
+use zksync_storage::{db::NamedColumnFamily, RocksDB, RocksDBOptions};
 use zksync_types::{
     aggregated_operations::L1BatchAggregatedActionType,
     ethabi::Token,
@@ -26,7 +26,7 @@ use zksync_types::{
         SnapshotStorageLogsStorageKey,
     },
     web3::BlockNumber,
-    Address, L1BatchNumber, H160, H256, U256,
+    Address, L1BatchNumber, L2BlockNumber, H160, H256, U256,
 };
 
 pub mod node;
@@ -71,6 +71,106 @@ pub enum NodeRole {
     External,
 }
 
+/// Rollback plan returned by [`BlockReverter::plan()`] and [`BlockReverter::plan_l2_blocks_rollback()`].
+/// Describes what would be deleted or reset in each of the storages enabled in the reverter.
+#[derive(Debug, Serialize)]
+pub struct RollbackPlan {
+    pub last_l1_batch_to_keep: L1BatchNumber,
+    pub last_l2_block_to_keep: L2BlockNumber,
+    /// `None` if rolling back Postgres is not enabled.
+    pub postgres: Option<PostgresRollbackPlan>,
+    /// `None` if rolling back the Merkle tree is not enabled, or if it's not affected by the rollback.
+    pub merkle_tree: Option<RocksdbRollbackPlan>,
+    pub storage_caches: Vec<RocksdbRollbackPlan>,
+    /// Snapshots that would be removed. Snapshot metadata is removed together with Postgres data,
+    /// and snapshot files are removed only if rolling back snapshot objects is enabled.
+    pub snapshots: Vec<SnapshotRollbackPlan>,
+}
+
+/// Changes in Postgres made by a rollback.
+#[derive(Debug, Serialize)]
+pub struct PostgresRollbackPlan {
+    /// Numbers of deleted (or, for transactions, reset) rows per table.
+    pub rows: RollbackRowCounts,
+    pub tokens: usize,
+    /// Whether the unsealed L1 batch would be removed. Only relevant for L2 block rollbacks.
+    pub removes_unsealed_l1_batch: bool,
+    /// Whether the consistency checker index would be reset (only on external nodes).
+    pub resets_consistency_checker: bool,
+    /// Whether a consensus hard fork would be performed (only on the main node).
+    pub forks_consensus: bool,
+}
+
+/// Changes in a RocksDB instance (the Merkle tree or a storage cache) made by a rollback.
+#[derive(Debug, Serialize)]
+pub struct RocksdbRollbackPlan {
+    pub path: PathBuf,
+    #[serde(flatten)]
+    pub state: RocksdbRollbackState,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum RocksdbRollbackState {
+    /// DB doesn't exist or isn't initialized; it will be skipped.
+    Missing,
+    /// DB doesn't contain data after the target L1 batch; it will be left as is.
+    UpToDate { next_l1_batch: L1BatchNumber },
+    /// DB will be rolled back; data for L1 batches `new_next_l1_batch..next_l1_batch` will be removed.
+    RolledBack {
+        next_l1_batch: L1BatchNumber,
+        new_next_l1_batch: L1BatchNumber,
+    },
+}
+
+impl RocksdbRollbackState {
+    fn new(next_l1_batch: L1BatchNumber, last_l1_batch_to_keep: L1BatchNumber) -> Self {
+        if next_l1_batch > last_l1_batch_to_keep + 1 {
+            Self::RolledBack {
+                next_l1_batch,
+                new_next_l1_batch: last_l1_batch_to_keep + 1,
+            }
+        } else {
+            Self::UpToDate { next_l1_batch }
+        }
+    }
+}
+
+/// Opens a secondary RocksDB instance at `path`, so that planning a rollback never writes to the DB (e.g., by opening
+/// a non-existing DB or applying WAL on open). Returns the instance together with the temporary directory for secondary
+/// instance logs, which must outlive the instance.
+fn open_secondary_db<CF: NamedColumnFamily>(
+    path: &Path,
+) -> anyhow::Result<(RocksDB<CF>, tempfile::TempDir)> {
+    let secondary_dir = tempfile::tempdir().context("failed creating dir for secondary RocksDB")?;
+    let db = RocksDB::secondary_with_options(path, secondary_dir.path(), RocksDBOptions::default())
+        .with_context(|| format!("failed opening secondary RocksDB at `{}`", path.display()))?;
+    Ok((db, secondary_dir))
+}
+
+/// Snapshot removed by a rollback.
+#[derive(Debug, Serialize)]
+pub struct SnapshotRollbackPlan {
+    pub l1_batch_number: L1BatchNumber,
+    /// Whether snapshot files would be removed from the object store.
+    pub removes_files: bool,
+    pub files: Vec<String>,
+}
+
+impl SnapshotRollbackPlan {
+    fn new(snapshot: SnapshotMetadata, removes_files: bool) -> Self {
+        let storage_logs_files = snapshot.storage_logs_filepaths.into_iter().flatten();
+        Self {
+            l1_batch_number: snapshot.l1_batch_number,
+            removes_files,
+            files: [snapshot.factory_deps_filepath]
+                .into_iter()
+                .chain(storage_logs_files)
+                .collect(),
+        }
+    }
+}
+
 /// This struct is used to roll back node state and revert batches committed (but generally not finalized) on L1.
 ///
 /// Reversion is a rare event of manual intervention, when the node operator
@@ -90,6 +190,10 @@ pub enum NodeRole {
 /// - Object store for protocol snapshots
 ///
 /// In addition, it can revert the state of the Ethereum contract (if the reverted L1 batches were committed).
+///
+/// Besides rolling back whole L1 batches, `BlockReverter` can roll back L2 blocks in the last open L1 batch
+/// (e.g., to recover from bad sequencer output), and it can report what a rollback would change without
+/// changing anything (see [`Self::plan()`]).
 #[derive(Debug)]
 pub struct BlockReverter {
     /// Role affects the interactions with the consensus state.
@@ -152,20 +256,7 @@ impl BlockReverter {
     /// Rolls back previously enabled DBs (Postgres + RocksDB) and the snapshot object store to a previous state.
     #[tracing::instrument(skip(self), err)]
     pub async fn roll_back(&self, last_l1_batch_to_keep: L1BatchNumber) -> anyhow::Result<()> {
-        if !self.allow_rolling_back_executed_batches {
-            let mut storage = self
-                .connection_pool
-                .connection_tagged("block_reverter")
-                .await?;
-            let last_executed_l1_batch = storage
-                .blocks_dal()
-                .get_number_of_last_l1_batch_executed_on_eth()
-                .await?;
-            anyhow::ensure!(
-                Some(last_l1_batch_to_keep) >= last_executed_l1_batch,
-                "Attempt to roll back already executed L1 batches; the last executed batch is: {last_executed_l1_batch:?}"
-            );
-        }
+        self.check_executed_batches(last_l1_batch_to_keep).await?;
 
         // Tree needs to be rolled back first to keep the state recoverable
         self.roll_back_rocksdb_instances(last_l1_batch_to_keep)
@@ -188,32 +279,207 @@ impl BlockReverter {
         Ok(())
     }
 
-    async fn roll_back_rocksdb_instances(
+    async fn check_executed_batches(
         &self,
         last_l1_batch_to_keep: L1BatchNumber,
     ) -> anyhow::Result<()> {
-        if let Some(merkle_tree_path) = &self.merkle_tree_path {
-            let mut connection = self
+        if !self.allow_rolling_back_executed_batches {
+            let mut storage = self
                 .connection_pool
                 .connection_tagged("block_reverter")
                 .await?;
-            let storage_root_hash = connection
+            let last_executed_l1_batch = storage
                 .blocks_dal()
-                .get_l1_batch_state_root(last_l1_batch_to_keep)
+                .get_number_of_last_l1_batch_executed_on_eth()
                 .await?;
-            let Some(storage_root_hash) = storage_root_hash else {
-                let latest_l1_batch = connection.blocks_dal().get_sealed_l1_batch_number().await?;
-                let earliest_l1_batch = connection
-                    .blocks_dal()
-                    .get_earliest_l1_batch_number()
-                    .await?;
-                anyhow::bail!(
-                    "no state root hash for target L1 batch #{last_l1_batch_to_keep}; \
-                     Postgres contains batches from {earliest_l1_batch:?} to {latest_l1_batch:?} (both inclusive)"
-                );
+            anyhow::ensure!(
+                Some(last_l1_batch_to_keep) >= last_executed_l1_batch,
+                "Attempt to roll back already executed L1 batches; the last executed batch is: {last_executed_l1_batch:?}"
+            );
+        }
+        Ok(())
+    }
+
+    /// Reports what [`Self::roll_back()`] would delete or reset in each of the enabled storages
+    /// without changing anything.
+    #[tracing::instrument(skip(self), err)]
+    pub async fn plan(&self, last_l1_batch_to_keep: L1BatchNumber) -> anyhow::Result<RollbackPlan> {
+        self.check_executed_batches(last_l1_batch_to_keep).await?;
+
+        let mut storage = self
+            .connection_pool
+            .connection_tagged("block_reverter")
+            .await?;
+        let (_, last_l2_block_to_keep) = storage
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(last_l1_batch_to_keep)
+            .await?
+            .with_context(|| {
+                format!("L1 batch #{last_l1_batch_to_keep} doesn't contain L2 blocks")
+            })?;
+        let (postgres, snapshots) = if self.should_roll_back_postgres {
+            let postgres = self
+                .plan_postgres(&mut storage, last_l2_block_to_keep, last_l1_batch_to_keep)
+                .await?;
+            let removes_files = self.snapshots_object_store.is_some();
+            let snapshots = storage
+                .snapshots_dal()
+                .get_snapshots_after(last_l1_batch_to_keep)
+                .await?
+                .into_iter()
+                .map(|snapshot| SnapshotRollbackPlan::new(snapshot, removes_files))
+                .collect();
+            (Some(postgres), snapshots)
+        } else {
+            (None, vec![])
+        };
+        drop(storage);
+
+        let merkle_tree = if let Some(merkle_tree_path) = &self.merkle_tree_path {
+            // Check that the tree can be rolled back.
+            self.get_state_root_hash(last_l1_batch_to_keep).await?;
+            Some(Self::plan_tree(merkle_tree_path, last_l1_batch_to_keep).await?)
+        } else {
+            None
+        };
+
+        let mut storage_caches = Vec::with_capacity(self.storage_cache_paths.len());
+        for storage_cache_path in &self.storage_cache_paths {
+            storage_caches
+                .push(Self::plan_storage_cache(storage_cache_path, last_l1_batch_to_keep).await?);
+        }
+
+        Ok(RollbackPlan {
+            last_l1_batch_to_keep,
+            last_l2_block_to_keep,
+            postgres,
+            merkle_tree,
+            storage_caches,
+            snapshots,
+        })
+    }
+
+    async fn plan_postgres(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        last_l2_block_to_keep: L2BlockNumber,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> anyhow::Result<PostgresRollbackPlan> {
+        let rows = storage
+            .blocks_dal()
+            .count_rows_to_roll_back(last_l2_block_to_keep, last_l1_batch_to_keep)
+            .await?;
+        let tokens = storage
+            .tokens_dal()
+            .get_token_addresses_to_roll_back(last_l2_block_to_keep)
+            .await?
+            .len();
+        Ok(PostgresRollbackPlan {
+            rows,
+            tokens,
+            removes_unsealed_l1_batch: false,
+            resets_consistency_checker: self.node_role == NodeRole::External,
+            forks_consensus: self.node_role == NodeRole::Main,
+        })
+    }
+
+    async fn plan_tree(
+        merkle_tree_path: &Path,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> anyhow::Result<RocksdbRollbackPlan> {
+        let merkle_tree_exists = fs::try_exists(merkle_tree_path).await.with_context(|| {
+            format!(
+                "cannot check whether Merkle tree path `{}` exists",
+                merkle_tree_path.display()
+            )
+        })?;
+        let state = if merkle_tree_exists {
+            let path = merkle_tree_path.to_path_buf();
+            let next_l1_batch = tokio::task::spawn_blocking(move || {
+                let (db, _secondary_dir) = open_secondary_db(&path)
+                    .context("failed initializing RocksDB for Merkle tree")?;
+                let tree =
+                    ZkSyncTreeReader::new(db.into()).context("failed initializing Merkle tree")?;
+                anyhow::Ok(tree.next_l1_batch_number())
+            })
+            .await
+            .context("reading Merkle tree panicked")??;
+            RocksdbRollbackState::new(next_l1_batch, last_l1_batch_to_keep)
+        } else {
+            RocksdbRollbackState::Missing
+        };
+        Ok(RocksdbRollbackPlan {
+            path: merkle_tree_path.to_path_buf(),
+            state,
+        })
+    }
+
+    async fn plan_storage_cache(
+        storage_cache_path: &Path,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> anyhow::Result<RocksdbRollbackPlan> {
+        let sk_cache_exists = fs::try_exists(storage_cache_path).await.with_context(|| {
+            format!("cannot check whether storage cache path `{storage_cache_path:?}` exists")
+        })?;
+        let state = if sk_cache_exists {
+            let path = storage_cache_path.to_path_buf();
+            let (db, secondary_dir) = tokio::task::spawn_blocking(move || {
+                open_secondary_db::<StateKeeperColumnFamily>(&path)
+            })
+            .await
+            .context("opening storage cache panicked")?
+            .context("failed initializing storage cache")?;
+            let next_l1_batch = match RocksdbStorageBuilder::from_rocksdb(db).get().await {
+                Some(sk_cache) => Some(sk_cache.next_l1_batch_number().await),
+                None => None,
             };
-            drop(connection);
+            drop(secondary_dir);
+            next_l1_batch.map_or(RocksdbRollbackState::Missing, |next_l1_batch| {
+                RocksdbRollbackState::new(next_l1_batch, last_l1_batch_to_keep)
+            })
+        } else {
+            RocksdbRollbackState::Missing
+        };
+        Ok(RocksdbRollbackPlan {
+            path: storage_cache_path.to_path_buf(),
+            state,
+        })
+    }
+
+    /// Returns the state root hash for the target L1 batch, which the Merkle tree is checked against after rollback.
+    async fn get_state_root_hash(
+        &self,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> anyhow::Result<H256> {
+        let mut connection = self
+            .connection_pool
+            .connection_tagged("block_reverter")
+            .await?;
+        let storage_root_hash = connection
+            .blocks_dal()
+            .get_l1_batch_state_root(last_l1_batch_to_keep)
+            .await?;
+        if let Some(storage_root_hash) = storage_root_hash {
+            return Ok(storage_root_hash);
+        }
+
+        let latest_l1_batch = connection.blocks_dal().get_sealed_l1_batch_number().await?;
+        let earliest_l1_batch = connection
+            .blocks_dal()
+            .get_earliest_l1_batch_number()
+            .await?;
+        anyhow::bail!(
+            "no state root hash for target L1 batch #{last_l1_batch_to_keep}; \
+             Postgres contains batches from {earliest_l1_batch:?} to {latest_l1_batch:?} (both inclusive)"
+        );
+    }
 
+    async fn roll_back_rocksdb_instances(
+        &self,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        if let Some(merkle_tree_path) = &self.merkle_tree_path {
+            let storage_root_hash = self.get_state_root_hash(last_l1_batch_to_keep).await?;
             let merkle_tree_path = Path::new(merkle_tree_path);
             let merkle_tree_exists = fs::try_exists(merkle_tree_path).await.with_context(|| {
                 format!(
@@ -339,36 +605,7 @@ impl BlockReverter {
                 format!("L1 batch #{last_l1_batch_to_keep} doesn't contain L2 blocks")
             })?;
 
-        tracing::info!("Rolling back transactions state");
-        transaction
-            .transactions_dal()
-            .reset_transactions_state(last_l2_block_to_keep)
-            .await?;
-        tracing::info!("Rolling back events");
-        transaction
-            .events_dal()
-            .roll_back_events(last_l2_block_to_keep)
-            .await?;
-        tracing::info!("Rolling back L2-to-L1 logs");
-        transaction
-            .events_dal()
-            .roll_back_l2_to_l1_logs(last_l2_block_to_keep)
-            .await?;
-        tracing::info!("Rolling back created tokens");
-        transaction
-            .tokens_dal()
-            .roll_back_tokens(last_l2_block_to_keep)
-            .await?;
-        tracing::info!("Rolling back factory deps");
-        transaction
-            .factory_deps_dal()
-            .roll_back_factory_deps(last_l2_block_to_keep)
-            .await?;
-        tracing::info!("Rolling back storage logs");
-        transaction
-            .storage_logs_dal()
-            .roll_back_storage_logs(last_l2_block_to_keep)
-            .await?;
+        Self::roll_back_l2_block_data(&mut transaction, last_l2_block_to_keep).await?;
         tracing::info!("Rolling back Ethereum transactions");
         transaction
             .eth_sender_dal()
@@ -414,6 +651,11 @@ impl BlockReverter {
                 .blocks_dal()
                 .set_consistency_checker_last_processed_l1_batch(last_l1_batch_to_keep)
                 .await?;
+            tracing::info!("Rolling back fast sync verification cursor");
+            transaction
+                .blocks_dal()
+                .roll_back_fast_sync_next_l1_batch_to_verify(last_l1_batch_to_keep)
+                .await?;
         }
 
         if self.node_role == NodeRole::Main {
@@ -425,6 +667,175 @@ impl BlockReverter {
         Ok(deleted_snapshots)
     }
 
+    /// Rolls back data associated with L2 blocks (transactions, events, logs etc.), except for the L2 blocks themselves.
+    async fn roll_back_l2_block_data(
+        transaction: &mut Connection<'_, Core>,
+        last_l2_block_to_keep: L2BlockNumber,
+    ) -> anyhow::Result<()> {
+        tracing::info!("Rolling back transactions state");
+        transaction
+            .transactions_dal()
+            .reset_transactions_state(last_l2_block_to_keep)
+            .await?;
+        tracing::info!("Rolling back events");
+        transaction
+            .events_dal()
+            .roll_back_events(last_l2_block_to_keep)
+            .await?;
+        tracing::info!("Rolling back L2-to-L1 logs");
+        transaction
+            .events_dal()
+            .roll_back_l2_to_l1_logs(last_l2_block_to_keep)
+            .await?;
+        tracing::info!("Rolling back created tokens");
+        transaction
+            .tokens_dal()
+            .roll_back_tokens(last_l2_block_to_keep)
+            .await?;
+        tracing::info!("Rolling back factory deps");
+        transaction
+            .factory_deps_dal()
+            .roll_back_factory_deps(last_l2_block_to_keep)
+            .await?;
+        tracing::info!("Rolling back storage logs");
+        transaction
+            .storage_logs_dal()
+            .roll_back_storage_logs(last_l2_block_to_keep)
+            .await?;
+        Ok(())
+    }
+
+    /// Checks that `last_l2_block_to_keep` is not sealed in an L1 batch and returns the last L1 batch
+    /// that would be retained after rolling back L2 blocks, i.e. either the last sealed batch (if all L2 blocks
+    /// in the open batch are rolled back), or the open batch.
+    async fn check_l2_blocks_rollback(
+        storage: &mut Connection<'_, Core>,
+        last_l2_block_to_keep: L2BlockNumber,
+    ) -> anyhow::Result<L1BatchNumber> {
+        let last_sealed_l1_batch = storage.blocks_dal().get_sealed_l1_batch_number().await?;
+        let (last_sealed_l1_batch, last_sealed_l2_block) =
+            if let Some(number) = last_sealed_l1_batch {
+                let (_, last_l2_block) = storage
+                    .blocks_dal()
+                    .get_l2_block_range_of_l1_batch(number)
+                    .await?
+                    .with_context(|| format!("L1 batch #{number} doesn't contain L2 blocks"))?;
+                (number, last_l2_block)
+            } else {
+                let snapshot_recovery = storage
+                    .snapshot_recovery_dal()
+                    .get_applied_snapshot_status()
+                    .await?
+                    .context(
+                        "Postgres contains neither sealed L1 batches nor snapshot recovery info",
+                    )?;
+                (
+                    snapshot_recovery.l1_batch_number,
+                    snapshot_recovery.l2_block_number,
+                )
+            };
+        anyhow::ensure!(
+            last_l2_block_to_keep >= last_sealed_l2_block,
+            "Attempt to roll back L2 blocks sealed in L1 batch #{last_sealed_l1_batch} (last L2 block: \
+             #{last_sealed_l2_block}); roll back L1 batches instead"
+        );
+
+        let last_l2_block = storage
+            .blocks_dal()
+            .get_sealed_l2_block_number()
+            .await?
+            .unwrap_or(last_sealed_l2_block);
+        anyhow::ensure!(
+            last_l2_block_to_keep <= last_l2_block,
+            "Attempt to roll back to L2 block #{last_l2_block_to_keep}, which is newer than the latest L2 block #{last_l2_block}"
+        );
+
+        Ok(if last_l2_block_to_keep == last_sealed_l2_block {
+            last_sealed_l1_batch
+        } else {
+            last_sealed_l1_batch + 1
+        })
+    }
+
+    /// Reports what [`Self::roll_back_l2_blocks()`] would delete or reset without changing anything.
+    #[tracing::instrument(skip(self), err)]
+    pub async fn plan_l2_blocks_rollback(
+        &self,
+        last_l2_block_to_keep: L2BlockNumber,
+    ) -> anyhow::Result<RollbackPlan> {
+        let mut storage = self
+            .connection_pool
+            .connection_tagged("block_reverter")
+            .await?;
+        let last_l1_batch_to_keep =
+            Self::check_l2_blocks_rollback(&mut storage, last_l2_block_to_keep).await?;
+        let postgres = if self.should_roll_back_postgres {
+            let mut plan = self
+                .plan_postgres(&mut storage, last_l2_block_to_keep, last_l1_batch_to_keep)
+                .await?;
+            plan.removes_unsealed_l1_batch = plan.rows.l1_batches > 0;
+            plan.resets_consistency_checker = false;
+            Some(plan)
+        } else {
+            None
+        };
+
+        Ok(RollbackPlan {
+            last_l1_batch_to_keep,
+            last_l2_block_to_keep,
+            postgres,
+            merkle_tree: None,
+            storage_caches: vec![],
+            snapshots: vec![],
+        })
+    }
+
+    /// Rolls back L2 blocks in the last open (i.e., not sealed) L1 batch, so that `last_l2_block_to_keep`
+    /// is the last retained L2 block. This can be used to recover from bad sequencer output.
+    ///
+    /// Only Postgres is affected since the Merkle tree, storage caches and snapshots are only updated
+    /// for sealed L1 batches. If all L2 blocks in the open batch are rolled back, the unsealed batch
+    /// is removed as well.
+    #[tracing::instrument(skip(self), err)]
+    pub async fn roll_back_l2_blocks(
+        &self,
+        last_l2_block_to_keep: L2BlockNumber,
+    ) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.should_roll_back_postgres,
+            "Rolling back L2 blocks requires rolling back Postgres to be enabled"
+        );
+
+        tracing::info!("Rolling back Postgres data");
+        let mut storage = self
+            .connection_pool
+            .connection_tagged("block_reverter")
+            .await?;
+        let mut transaction = storage.start_transaction().await?;
+        let last_l1_batch_to_keep =
+            Self::check_l2_blocks_rollback(&mut transaction, last_l2_block_to_keep).await?;
+
+        Self::roll_back_l2_block_data(&mut transaction, last_l2_block_to_keep).await?;
+        tracing::info!("Rolling back L2 blocks");
+        transaction
+            .blocks_dal()
+            .delete_l2_blocks(last_l2_block_to_keep)
+            .await?;
+        tracing::info!("Removing unsealed L1 batch if it has no L2 blocks");
+        transaction
+            .blocks_dal()
+            .delete_unsealed_l1_batch(last_l1_batch_to_keep)
+            .await?;
+
+        if self.node_role == NodeRole::Main {
+            tracing::info!("Performing consensus hard fork");
+            transaction.consensus_dal().fork().await?;
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
     async fn delete_snapshot_files(
         object_store: &dyn ObjectStore,
         deleted_snapshots: &[SnapshotMetadata],
@@ -47,6 +47,50 @@ fn initialize_merkle_tree(path: &Path, storage_logs: &[StorageLog]) -> Vec<H256>
     hashes.collect()
 }
 
+fn create_l2_block_header(number: u32) -> L2BlockHeader {
+    L2BlockHeader {
+        number: L2BlockNumber(number),
+        timestamp: number.into(),
+        hash: H256::from_low_u64_be(number.into()),
+        l1_tx_count: 0,
+        l2_tx_count: 0,
+        fee_account_address: Address::default(),
+        base_fee_per_gas: 0,
+        batch_fee_input: BatchFeeInput::pubdata_independent(0, 0, 0),
+        gas_per_pubdata_limit: 0,
+        base_system_contracts_hashes: Default::default(),
+        protocol_version: Some(ProtocolVersionId::latest()),
+        virtual_blocks: 1,
+        gas_limit: 0,
+        logs_bloom: Default::default(),
+        pubdata_params: PubdataParams::genesis(),
+        rolling_txs_hash: Some(H256::zero()),
+    }
+}
+
+fn create_l1_batch_header(number: u32) -> L1BatchHeader {
+    L1BatchHeader {
+        number: L1BatchNumber(number),
+        timestamp: number.into(),
+        l1_tx_count: 0,
+        l2_tx_count: 0,
+        priority_ops_onchain_data: vec![],
+        l2_to_l1_logs: vec![],
+        l2_to_l1_messages: vec![],
+        bloom: Default::default(),
+        used_contract_hashes: vec![],
+        base_system_contracts_hashes: Default::default(),
+        system_logs: vec![],
+        protocol_version: Some(ProtocolVersionId::latest()),
+        pubdata_input: None,
+        fee_address: Default::default(),
+        batch_fee_input: BatchFeeInput::pubdata_independent(0, 0, 0),
+        interop_fee: U256::zero(),
+        pubdata_limit: Some(100_000),
+        settlement_layer: SettlementLayer::for_tests(),
+    }
+}
+
 async fn setup_storage(storage: &mut Connection<'_, Core>, storage_logs: &[StorageLog]) {
     storage
         .protocol_versions_dal()
@@ -55,49 +99,13 @@ async fn setup_storage(storage: &mut Connection<'_, Core>, storage_logs: &[Stora
         .unwrap();
 
     for (number, storage_log) in (0..).zip(storage_logs) {
-        let l2_block_header = L2BlockHeader {
-            number: L2BlockNumber(number),
-            timestamp: number.into(),
-            hash: H256::from_low_u64_be(number.into()),
-            l1_tx_count: 0,
-            l2_tx_count: 0,
-            fee_account_address: Address::default(),
-            base_fee_per_gas: 0,
-            batch_fee_input: BatchFeeInput::pubdata_independent(0, 0, 0),
-            gas_per_pubdata_limit: 0,
-            base_system_contracts_hashes: Default::default(),
-            protocol_version: Some(ProtocolVersionId::latest()),
-            virtual_blocks: 1,
-            gas_limit: 0,
-            logs_bloom: Default::default(),
-            pubdata_params: PubdataParams::genesis(),
-            rolling_txs_hash: Some(H256::zero()),
-        };
+        let l2_block_header = create_l2_block_header(number);
         storage
             .blocks_dal()
             .insert_l2_block(&l2_block_header)
             .await
             .unwrap();
-        let l1_batch_header = L1BatchHeader {
-            number: L1BatchNumber(number),
-            timestamp: number.into(),
-            l1_tx_count: 0,
-            l2_tx_count: 0,
-            priority_ops_onchain_data: vec![],
-            l2_to_l1_logs: vec![],
-            l2_to_l1_messages: vec![],
-            bloom: Default::default(),
-            used_contract_hashes: vec![],
-            base_system_contracts_hashes: Default::default(),
-            system_logs: vec![],
-            protocol_version: Some(ProtocolVersionId::latest()),
-            pubdata_input: None,
-            fee_address: Default::default(),
-            batch_fee_input: BatchFeeInput::pubdata_independent(0, 0, 0),
-            interop_fee: U256::zero(),
-            pubdata_limit: Some(100_000),
-            settlement_layer: SettlementLayer::for_tests(),
-        };
+        let l1_batch_header = create_l1_batch_header(number);
         storage
             .blocks_dal()
             .insert_mock_l1_batch(&l1_batch_header)
@@ -213,6 +221,134 @@ async fn block_reverter_basics(sync_merkle_tree: bool) {
     }
 }
 
+#[tokio::test]
+async fn planning_rollback() {
+    let storage_logs = gen_storage_logs();
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &storage_logs).await;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let merkle_tree_path = temp_dir.path().join("tree");
+    let l1_batch_hashes = initialize_merkle_tree(&merkle_tree_path, &storage_logs);
+    for (number, hash) in (0..).zip(l1_batch_hashes) {
+        storage
+            .blocks_dal()
+            .set_l1_batch_hash(L1BatchNumber(number), hash)
+            .await
+            .unwrap();
+    }
+    let object_store = MockObjectStore::arc();
+    create_mock_snapshot(&mut storage, &*object_store, L1BatchNumber(7), 0..5).await;
+
+    let plan = BlockReverter::new(NodeRole::External, pool.clone())
+        .enable_rolling_back_postgres()
+        .enable_rolling_back_merkle_tree(merkle_tree_path.clone())
+        .add_rocksdb_storage_path_to_rollback(temp_dir.path().join("sk_cache"))
+        .enable_rolling_back_snapshot_objects(object_store.clone())
+        .plan(L1BatchNumber(5))
+        .await
+        .unwrap();
+
+    assert_eq!(plan.last_l1_batch_to_keep, L1BatchNumber(5));
+    assert_eq!(plan.last_l2_block_to_keep, L2BlockNumber(5));
+    let postgres = plan.postgres.unwrap();
+    assert_eq!(
+        postgres.rows,
+        RollbackRowCounts {
+            storage_logs: 4,
+            l2_blocks: 4,
+            snapshots: 1,
+            l1_batches: 4,
+            initial_writes: 4,
+            ..RollbackRowCounts::default()
+        }
+    );
+    assert_eq!(postgres.tokens, 0);
+    assert!(postgres.resets_consistency_checker);
+    assert!(!postgres.forks_consensus);
+    assert!(!postgres.removes_unsealed_l1_batch);
+
+    assert_matches!(
+        plan.merkle_tree.unwrap().state,
+        RocksdbRollbackState::RolledBack {
+            next_l1_batch: L1BatchNumber(10),
+            new_next_l1_batch: L1BatchNumber(6),
+        }
+    );
+    assert_matches!(
+        plan.storage_caches.as_slice(),
+        [RocksdbRollbackPlan {
+            state: RocksdbRollbackState::Missing,
+            ..
+        }]
+    );
+    assert_matches!(
+        plan.snapshots.as_slice(),
+        [SnapshotRollbackPlan {
+            l1_batch_number: L1BatchNumber(7),
+            removes_files: true,
+            files,
+        }] if files.len() == 6
+    );
+
+    // Check that nothing has changed.
+    let last_l1_batch_number = storage
+        .blocks_dal()
+        .get_sealed_l1_batch_number()
+        .await
+        .unwrap();
+    assert_eq!(last_l1_batch_number, Some(L1BatchNumber(9)));
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), 10);
+    let all_snapshots = storage
+        .snapshots_dal()
+        .get_all_complete_snapshots()
+        .await
+        .unwrap();
+    assert_eq!(all_snapshots.snapshots_l1_batch_numbers, [L1BatchNumber(7)]);
+    object_store
+        .get::<SnapshotFactoryDependencies>(L1BatchNumber(7))
+        .await
+        .unwrap();
+
+    let db = RocksDB::new(&merkle_tree_path).unwrap();
+    let tree = ZkSyncTree::new(db.into()).unwrap();
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(10));
+}
+
+#[tokio::test]
+async fn planning_rollback_while_merkle_tree_is_open() {
+    let storage_logs = gen_storage_logs();
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &storage_logs).await;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let merkle_tree_path = temp_dir.path().join("tree");
+    initialize_merkle_tree(&merkle_tree_path, &storage_logs);
+    // Emulate a running node holding the primary RocksDB instance (and thus its lock).
+    let db = RocksDB::new(&merkle_tree_path).unwrap();
+    let tree = ZkSyncTree::new(db.into()).unwrap();
+
+    let plan = BlockReverter::new(NodeRole::External, pool.clone())
+        .enable_rolling_back_merkle_tree(merkle_tree_path.clone())
+        .plan(L1BatchNumber(5))
+        .await
+        .unwrap();
+    assert_matches!(
+        plan.merkle_tree.unwrap().state,
+        RocksdbRollbackState::RolledBack {
+            next_l1_batch: L1BatchNumber(10),
+            new_next_l1_batch: L1BatchNumber(6),
+        }
+    );
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(10));
+}
+
 async fn create_mock_snapshot(
     storage: &mut Connection<'_, Core>,
     object_store: &dyn ObjectStore,
@@ -241,6 +377,7 @@ async fn create_mock_snapshot(
             l1_batch_number,
             storage_logs_chunk_count,
             &factory_deps_key,
+            None,
         )
         .await
         .unwrap();
@@ -399,6 +536,14 @@ impl ObjectStore for ErroneousStore {
         })
     }
 
+    async fn list_raw(
+        &self,
+        _bucket: Bucket,
+        _prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        unreachable!("not called by reverter")
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         bucket.to_string()
     }
@@ -482,3 +627,177 @@ async fn reverter_handles_incomplete_snapshot() {
         assert_matches!(chunk_result.unwrap_err(), ObjectStoreError::KeyNotFound(_));
     }
 }
+
+/// Inserts an unsealed L1 batch with L2 blocks following the last sealed L2 block, one block per storage log.
+async fn setup_pending_l2_blocks(storage: &mut Connection<'_, Core>, storage_logs: &[StorageLog]) {
+    let last_sealed_l1_batch = storage
+        .blocks_dal()
+        .get_sealed_l1_batch_number()
+        .await
+        .unwrap()
+        .unwrap();
+    let l1_batch_header = create_l1_batch_header(last_sealed_l1_batch.0 + 1);
+    storage
+        .blocks_dal()
+        .insert_l1_batch(l1_batch_header.to_unsealed_header())
+        .await
+        .unwrap();
+
+    let last_l2_block = storage
+        .blocks_dal()
+        .get_sealed_l2_block_number()
+        .await
+        .unwrap()
+        .unwrap();
+    for (number, storage_log) in (last_l2_block.0 + 1..).zip(storage_logs) {
+        storage
+            .blocks_dal()
+            .insert_l2_block(&create_l2_block_header(number))
+            .await
+            .unwrap();
+        storage
+            .storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(number), &[*storage_log])
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn rolling_back_l2_blocks() {
+    let storage_logs = gen_storage_logs();
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &storage_logs).await;
+    let pending_storage_logs: Vec<_> = storage_logs[..4]
+        .iter()
+        .map(|log| StorageLog::new_write_log(log.key, H256::repeat_byte(0xee)))
+        .collect();
+    setup_pending_l2_blocks(&mut storage, &pending_storage_logs).await;
+
+    let mut block_reverter = BlockReverter::new(NodeRole::External, pool.clone());
+    block_reverter.enable_rolling_back_postgres();
+    let plan = block_reverter
+        .plan_l2_blocks_rollback(L2BlockNumber(11))
+        .await
+        .unwrap();
+    assert_eq!(plan.last_l1_batch_to_keep, L1BatchNumber(10));
+    let postgres = plan.postgres.unwrap();
+    assert_eq!(
+        postgres.rows,
+        RollbackRowCounts {
+            storage_logs: 2,
+            l2_blocks: 2,
+            ..RollbackRowCounts::default()
+        }
+    );
+    assert!(!postgres.removes_unsealed_l1_batch);
+    assert!(!postgres.resets_consistency_checker);
+
+    block_reverter
+        .roll_back_l2_blocks(L2BlockNumber(11))
+        .await
+        .unwrap();
+    let last_l2_block_number = storage
+        .blocks_dal()
+        .get_sealed_l2_block_number()
+        .await
+        .unwrap();
+    assert_eq!(last_l2_block_number, Some(L2BlockNumber(11)));
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), 12);
+    let unsealed_batch = storage
+        .blocks_dal()
+        .get_unsealed_l1_batch()
+        .await
+        .unwrap()
+        .expect("unsealed batch should be retained");
+    assert_eq!(unsealed_batch.number, L1BatchNumber(10));
+
+    // Roll back all L2 blocks in the open batch.
+    let plan = block_reverter
+        .plan_l2_blocks_rollback(L2BlockNumber(9))
+        .await
+        .unwrap();
+    assert_eq!(plan.last_l1_batch_to_keep, L1BatchNumber(9));
+    let postgres = plan.postgres.unwrap();
+    assert_eq!(
+        postgres.rows,
+        RollbackRowCounts {
+            storage_logs: 2,
+            l2_blocks: 2,
+            l1_batches: 1,
+            ..RollbackRowCounts::default()
+        }
+    );
+    assert!(postgres.removes_unsealed_l1_batch);
+
+    block_reverter
+        .roll_back_l2_blocks(L2BlockNumber(9))
+        .await
+        .unwrap();
+    let last_l2_block_number = storage
+        .blocks_dal()
+        .get_sealed_l2_block_number()
+        .await
+        .unwrap();
+    assert_eq!(last_l2_block_number, Some(L2BlockNumber(9)));
+    let unsealed_batch = storage.blocks_dal().get_unsealed_l1_batch().await.unwrap();
+    assert!(unsealed_batch.is_none(), "{unsealed_batch:?}");
+    let last_l1_batch_number = storage
+        .blocks_dal()
+        .get_sealed_l1_batch_number()
+        .await
+        .unwrap();
+    assert_eq!(last_l1_batch_number, Some(L1BatchNumber(9)));
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), 10);
+}
+
+#[tokio::test]
+async fn rolling_back_sealed_l2_blocks_is_rejected() {
+    let storage_logs = gen_storage_logs();
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &storage_logs).await;
+    setup_pending_l2_blocks(&mut storage, &storage_logs[..2]).await;
+
+    let mut block_reverter = BlockReverter::new(NodeRole::External, pool.clone());
+    let err = block_reverter
+        .roll_back_l2_blocks(L2BlockNumber(10))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("requires rolling back Postgres"),
+        "{err}"
+    );
+
+    block_reverter.enable_rolling_back_postgres();
+    let err = block_reverter
+        .roll_back_l2_blocks(L2BlockNumber(8))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("sealed in L1 batch #9"), "{err}");
+    let err = block_reverter
+        .plan_l2_blocks_rollback(L2BlockNumber(20))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("newer than the latest L2 block"),
+        "{err}"
+    );
+
+    // Check that nothing has changed.
+    let last_l2_block_number = storage
+        .blocks_dal()
+        .get_sealed_l2_block_number()
+        .await
+        .unwrap();
+    assert_eq!(last_l2_block_number, Some(L2BlockNumber(11)));
+}
@@ -11,7 +11,9 @@ use zksync_node_framework::{
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
 };
-use zksync_node_sync::{node::ActionQueueSenderResource, ActionQueueSender};
+use zksync_node_sync::{
+    fast_sync::FastSyncCompletion, node::ActionQueueSenderResource, ActionQueueSender,
+};
 use zksync_shared_resources::api::SyncState;
 use zksync_web3_decl::client::{DynClient, L2};
 
@@ -29,6 +31,7 @@ pub struct Input {
     main_node_client: Box<DynClient<L2>>,
     sync_state: SyncState,
     action_queue_sender: ActionQueueSenderResource,
+    fast_sync: Option<FastSyncCompletion>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -78,6 +81,7 @@ impl WiringLayer for ExternalNodeConsensusLayer {
             main_node_client,
             sync_state,
             action_queue_sender,
+            fast_sync: input.fast_sync,
         };
         Ok(Output { consensus_task })
     }
@@ -91,6 +95,7 @@ pub struct ExternalNodeTask {
     main_node_client: Box<DynClient<L2>>,
     sync_state: SyncState,
     action_queue_sender: ActionQueueSender,
+    fast_sync: Option<FastSyncCompletion>,
 }
 
 #[async_trait::async_trait]
@@ -107,15 +112,27 @@ impl Task for ExternalNodeTask {
         // not the consensus task itself. There may have been any number of tasks running in the root context,
         // but we only need to wait for a stop request once, and it will be propagated to all child contexts.
         scope::run!(&ctx::root(), |ctx, s| async {
-            s.spawn_bg(crate::run_external_node(
-                ctx,
-                self.config,
-                self.pool,
-                self.sync_state,
-                self.main_node_client,
-                self.action_queue_sender,
-                self.build_version,
-            ));
+            s.spawn_bg(async move {
+                // Fast sync writes L2 blocks directly to Postgres, so the fetcher can only start after it has finished.
+                if let Some(mut fast_sync) = self.fast_sync {
+                    tracing::info!(
+                        "Waiting for fast sync to complete before starting consensus fetcher"
+                    );
+                    if ctx.wait(fast_sync.wait()).await.is_err() {
+                        return Ok(());
+                    }
+                }
+                crate::run_external_node(
+                    ctx,
+                    self.config,
+                    self.pool,
+                    self.sync_state,
+                    self.main_node_client,
+                    self.action_queue_sender,
+                    self.build_version,
+                )
+                .await
+            });
             // `run_external_node` might return an error or panic,
             // in which case we need to return immediately,
             // rather than wait for the `stop_receiver`.
@@ -10,7 +10,10 @@ use secrecy::ExposeSecret;
 use serde::{Deserialize, Serialize};
 use zksync_config::configs::da_client::avail::{AvailClientConfig, AvailConfig, AvailSecrets};
 use zksync_da_client::{
-    types::{ClientType, DAError, DispatchResponse, FinalityResponse, InclusionData},
+    types::{
+        ClientType, DAError, DispatchResponse, FinalityResponse, InclusionData,
+        InclusionVerification,
+    },
     DataAvailabilityClient,
 };
 use zksync_types::{
@@ -20,7 +23,10 @@ use zksync_types::{
 };
 
 use crate::{
-    avail::sdk::{GasRelayClient, RawAvailClient},
+    avail::{
+        sdk::{GasRelayClient, RawAvailClient},
+        verification::{self, AvailAttestationProvider},
+    },
     utils::{to_non_retriable_da_error, to_retriable_da_error},
 };
 
@@ -37,6 +43,7 @@ pub struct AvailClient {
     sdk_client: Arc<AvailClientMode>,
     api_client: Arc<reqwest::Client>, // bridge API reqwest client
     sl_chain_id: SLChainId,
+    attestation_provider: Option<Arc<dyn AvailAttestationProvider>>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -77,25 +84,25 @@ where
     }
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
-struct MerkleProofInput {
+pub(super) struct MerkleProofInput {
     // proof of inclusion for the data root
-    data_root_proof: Vec<H256>,
+    pub(super) data_root_proof: Vec<H256>,
     // proof of inclusion of leaf within blob/bridge root
-    leaf_proof: Vec<H256>,
+    pub(super) leaf_proof: Vec<H256>,
     // abi.encodePacked(startBlock, endBlock) of header range commitment on vectorx
-    range_hash: H256,
+    pub(super) range_hash: H256,
     // index of the data root in the commitment tree
-    data_root_index: U256,
+    pub(super) data_root_index: U256,
     // blob root to check proof against, or reconstruct the data root
-    blob_root: H256,
+    pub(super) blob_root: H256,
     // bridge root to check proof against, or reconstruct the data root
-    bridge_root: H256,
+    pub(super) bridge_root: H256,
     // leaf being proven
-    leaf: H256,
+    pub(super) leaf: H256,
     // index of the leaf in the blob/bridge root tree
-    leaf_index: U256,
+    pub(super) leaf_index: U256,
 }
 
 impl Tokenize for MerkleProofInput {
@@ -170,8 +177,19 @@ impl AvailClient {
             sdk_client,
             api_client,
             sl_chain_id,
+            attestation_provider: None,
         })
     }
+
+    /// Enables verification of inclusion data against data root commitments returned by the provided provider.
+    #[must_use]
+    pub fn with_attestation_provider(
+        mut self,
+        provider: Arc<dyn AvailAttestationProvider>,
+    ) -> Self {
+        self.attestation_provider = Some(provider);
+        self
+    }
 }
 
 #[async_trait]
@@ -365,6 +383,24 @@ impl DataAvailabilityClient for AvailClient {
         }
     }
 
+    async fn verify_inclusion_data(
+        &self,
+        blob_id: &str,
+        inclusion_data: &InclusionData,
+    ) -> Result<InclusionVerification, DAError> {
+        let provider = self.attestation_provider.as_deref().ok_or_else(|| DAError {
+            error: anyhow!("cannot verify inclusion data for blob {blob_id}: VectorX contract is not configured"),
+            is_retriable: false,
+        })?;
+        verification::verify_inclusion_data(provider, &inclusion_data.data)
+            .await
+            .map_err(to_retriable_da_error)
+    }
+
+    fn supports_inclusion_verification(&self) -> bool {
+        self.attestation_provider.is_some()
+    }
+
     fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
         Box::new(self.clone())
     }
@@ -1,4 +1,8 @@
 mod client;
 mod sdk;
+mod verification;
 
-pub use self::client::AvailClient;
+pub use self::{
+    client::AvailClient,
+    verification::{AvailAttestationProvider, VectorXAttestationProvider},
+};
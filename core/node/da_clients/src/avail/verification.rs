@@ -0,0 +1,268 @@
+//! Verification of Avail inclusion data against data root commitments attested by the VectorX contract.
+
+use std::fmt;
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use zksync_da_client::types::InclusionVerification;
+use zksync_eth_client::EthInterface;
+use zksync_types::{
+    ethabi::{self, ParamType, Token},
+    web3::{keccak256, keccak256_concat},
+    Address, H256, U256,
+};
+
+use super::client::MerkleProofInput;
+use crate::utils::call_view_function;
+
+/// Provider of Avail data root commitments attested on the settlement layer.
+#[async_trait]
+pub trait AvailAttestationProvider: fmt::Debug + Send + Sync {
+    /// Returns the data root commitment for the specified Avail block range hash, or `None` if the range
+    /// is not attested yet.
+    async fn data_root_commitment(&self, range_hash: H256) -> anyhow::Result<Option<H256>>;
+}
+
+/// [`AvailAttestationProvider`] reading commitments from the VectorX contract.
+#[derive(Debug)]
+pub struct VectorXAttestationProvider {
+    client: Box<dyn EthInterface>,
+    contract_address: Address,
+}
+
+impl VectorXAttestationProvider {
+    pub fn new(client: Box<dyn EthInterface>, contract_address: Address) -> Self {
+        Self {
+            client,
+            contract_address,
+        }
+    }
+}
+
+#[async_trait]
+impl AvailAttestationProvider for VectorXAttestationProvider {
+    async fn data_root_commitment(&self, range_hash: H256) -> anyhow::Result<Option<H256>> {
+        let output = call_view_function(
+            self.client.as_ref(),
+            self.contract_address,
+            ("dataRootCommitments", &[ParamType::FixedBytes(32)]),
+            &[Token::FixedBytes(range_hash.as_bytes().to_vec())],
+            &[ParamType::FixedBytes(32)],
+        )
+        .await?;
+        let [Token::FixedBytes(commitment)] = output.as_slice() else {
+            anyhow::bail!("unexpected output of `dataRootCommitments`: {output:?}");
+        };
+        let commitment = H256::from_slice(commitment);
+        Ok((!commitment.is_zero()).then_some(commitment))
+    }
+}
+
+impl MerkleProofInput {
+    fn param_type() -> ParamType {
+        let hash = || ParamType::FixedBytes(32);
+        ParamType::Tuple(vec![
+            ParamType::Array(Box::new(hash())),
+            ParamType::Array(Box::new(hash())),
+            hash(),
+            ParamType::Uint(256),
+            hash(),
+            hash(),
+            hash(),
+            ParamType::Uint(256),
+        ])
+    }
+
+    /// Decodes the input from the ABI-encoded inclusion data produced by the client.
+    fn decode(data: &[u8]) -> anyhow::Result<Self> {
+        let tokens = ethabi::decode(&[Self::param_type()], data)?;
+        let Some(Token::Tuple(fields)) = tokens.into_iter().next() else {
+            anyhow::bail!("inclusion data is not a tuple");
+        };
+        let mut fields = fields.into_iter();
+        let mut next = |name: &str| fields.next().with_context(|| format!("missing `{name}`"));
+        let hashes = |token: Token| -> anyhow::Result<Vec<H256>> {
+            token
+                .into_array()
+                .context("not an array")?
+                .into_iter()
+                .map(|token| {
+                    token
+                        .into_fixed_bytes()
+                        .map(|bytes| H256::from_slice(&bytes))
+                })
+                .collect::<Option<_>>()
+                .context("not an array of hashes")
+        };
+        let hash = |token: Token| -> anyhow::Result<H256> {
+            let bytes = token.into_fixed_bytes().context("not a hash")?;
+            Ok(H256::from_slice(&bytes))
+        };
+        let uint = |token: Token| token.into_uint().context("not an integer");
+
+        Ok(Self {
+            data_root_proof: hashes(next("data_root_proof")?)?,
+            leaf_proof: hashes(next("leaf_proof")?)?,
+            range_hash: hash(next("range_hash")?)?,
+            data_root_index: uint(next("data_root_index")?)?,
+            blob_root: hash(next("blob_root")?)?,
+            bridge_root: hash(next("bridge_root")?)?,
+            leaf: hash(next("leaf")?)?,
+            leaf_index: uint(next("leaf_index")?)?,
+        })
+    }
+}
+
+/// Computes the Merkle root from a leaf and its proof the same way as the Avail bridge contracts:
+/// the index bits determine whether the current node is the left or the right child.
+fn merkle_root(leaf: H256, proof: &[H256], mut index: U256) -> H256 {
+    proof.iter().fold(leaf, |node, &sibling| {
+        let parent = if index.bit(0) {
+            keccak256_concat(sibling, node)
+        } else {
+            keccak256_concat(node, sibling)
+        };
+        index >>= 1;
+        parent
+    })
+}
+
+/// Verifies Avail inclusion data the same way as the Avail bridge contract on the settlement layer:
+/// the blob leaf must belong to the blob root, and the data root (reconstructed from the blob and bridge roots)
+/// must belong to the data root commitment attested by VectorX.
+pub(super) async fn verify_inclusion_data(
+    provider: &dyn AvailAttestationProvider,
+    inclusion_data: &[u8],
+) -> anyhow::Result<InclusionVerification> {
+    let input = match MerkleProofInput::decode(inclusion_data) {
+        Ok(input) => input,
+        Err(err) => {
+            return Ok(InclusionVerification::Invalid(format!(
+                "cannot decode Merkle proof input: {err:#}"
+            )));
+        }
+    };
+    if input.blob_root.is_zero() {
+        return Ok(InclusionVerification::Invalid("blob root is empty".into()));
+    }
+
+    let leaf_hash = H256(keccak256(input.leaf.as_bytes()));
+    if merkle_root(leaf_hash, &input.leaf_proof, input.leaf_index) != input.blob_root {
+        return Ok(InclusionVerification::Invalid(format!(
+            "leaf proof does not match blob root {:?}",
+            input.blob_root
+        )));
+    }
+
+    let Some(commitment) = provider.data_root_commitment(input.range_hash).await? else {
+        return Ok(InclusionVerification::Pending);
+    };
+    let data_root = keccak256_concat(input.blob_root, input.bridge_root);
+    if merkle_root(data_root, &input.data_root_proof, input.data_root_index) != commitment {
+        return Ok(InclusionVerification::Invalid(format!(
+            "data root proof does not match commitment {commitment:?} attested for range {:?}",
+            input.range_hash
+        )));
+    }
+    Ok(InclusionVerification::Verified)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use zksync_types::web3::contract::Tokenize;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct MockAttestationProvider(HashMap<H256, H256>);
+
+    #[async_trait]
+    impl AvailAttestationProvider for MockAttestationProvider {
+        async fn data_root_commitment(&self, range_hash: H256) -> anyhow::Result<Option<H256>> {
+            Ok(self.0.get(&range_hash).copied())
+        }
+    }
+
+    /// Creates valid inclusion data with the leaf at index 1 of the blob root tree and the data root
+    /// at index 2 of the commitment tree. Returns the proof input and the data root commitment.
+    fn create_inclusion_data() -> (MerkleProofInput, H256) {
+        let leaf = H256::repeat_byte(1);
+        let leaf_sibling = H256::repeat_byte(2);
+        let blob_root = keccak256_concat(leaf_sibling, H256(keccak256(leaf.as_bytes())));
+        let bridge_root = H256::repeat_byte(3);
+
+        let data_root = keccak256_concat(blob_root, bridge_root);
+        let data_root_proof = vec![H256::repeat_byte(4), H256::repeat_byte(5)];
+        let commitment = keccak256_concat(
+            data_root_proof[1],
+            keccak256_concat(data_root, data_root_proof[0]),
+        );
+        let input = MerkleProofInput {
+            data_root_proof,
+            leaf_proof: vec![leaf_sibling],
+            range_hash: H256::repeat_byte(0xff),
+            data_root_index: 2.into(),
+            blob_root,
+            bridge_root,
+            leaf,
+            leaf_index: 1.into(),
+        };
+        (input, commitment)
+    }
+
+    fn encode(input: MerkleProofInput) -> Vec<u8> {
+        ethabi::encode(&input.into_tokens())
+    }
+
+    #[test]
+    fn decoding_merkle_proof_input() {
+        let (input, _) = create_inclusion_data();
+        let encoded = encode(input.clone());
+        assert_eq!(MerkleProofInput::decode(&encoded).unwrap(), input);
+    }
+
+    #[tokio::test]
+    async fn verifying_inclusion_data() {
+        let (input, commitment) = create_inclusion_data();
+        let range_hash = input.range_hash;
+        let data = encode(input);
+
+        let mut provider = MockAttestationProvider::default();
+        let verification = verify_inclusion_data(&provider, &data).await.unwrap();
+        assert_eq!(verification, InclusionVerification::Pending);
+
+        provider.0.insert(range_hash, commitment);
+        let verification = verify_inclusion_data(&provider, &data).await.unwrap();
+        assert_eq!(verification, InclusionVerification::Verified);
+
+        provider.0.insert(range_hash, H256::repeat_byte(0xaa));
+        let verification = verify_inclusion_data(&provider, &data).await.unwrap();
+        assert!(
+            matches!(&verification, InclusionVerification::Invalid(reason) if reason.contains("data root proof")),
+            "{verification:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn verifying_invalid_inclusion_data() {
+        let (mut input, commitment) = create_inclusion_data();
+        let provider = MockAttestationProvider([(input.range_hash, commitment)].into());
+
+        input.leaf_index = 0.into();
+        let verification = verify_inclusion_data(&provider, &encode(input))
+            .await
+            .unwrap();
+        assert!(
+            matches!(&verification, InclusionVerification::Invalid(reason) if reason.contains("leaf proof")),
+            "{verification:?}"
+        );
+
+        let verification = verify_inclusion_data(&provider, b"garbage").await.unwrap();
+        assert!(
+            matches!(&verification, InclusionVerification::Invalid(reason) if reason.contains("decode")),
+            "{verification:?}"
+        );
+    }
+}
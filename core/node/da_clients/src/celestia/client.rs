@@ -12,13 +12,19 @@ use serde::{Deserialize, Serialize};
 use tonic::transport::Endpoint;
 use zksync_config::configs::da_client::celestia::{CelestiaConfig, CelestiaSecrets};
 use zksync_da_client::{
-    types::{ClientType, DAError, DispatchResponse, FinalityResponse, InclusionData},
+    types::{
+        ClientType, DAError, DispatchResponse, FinalityResponse, InclusionData,
+        InclusionVerification,
+    },
     DataAvailabilityClient,
 };
 
 use crate::{
-    celestia::sdk::{BlobTxHash, RawCelestiaClient},
-    utils::to_non_retriable_da_error,
+    celestia::{
+        sdk::{BlobTxHash, RawCelestiaClient},
+        verification::{self, BlobstreamAttestationProvider, CelestiaProofProvider},
+    },
+    utils::{to_non_retriable_da_error, to_retriable_da_error},
 };
 
 /// An implementation of the `DataAvailabilityClient` trait that interacts with the Avail network.
@@ -26,6 +32,14 @@ use crate::{
 pub struct CelestiaClient {
     config: CelestiaConfig,
     client: Arc<RawCelestiaClient>,
+    inclusion_verifier: Option<InclusionVerifier>,
+}
+
+/// Providers used to verify blob inclusion.
+#[derive(Debug, Clone)]
+struct InclusionVerifier {
+    attestations: Arc<dyn BlobstreamAttestationProvider>,
+    proofs: Arc<dyn CelestiaProofProvider>,
 }
 
 impl CelestiaClient {
@@ -42,9 +56,31 @@ impl CelestiaClient {
         Ok(Self {
             config,
             client: Arc::new(client),
+            inclusion_verifier: None,
         })
     }
+
+    /// Enables verification of dispatched blobs against Blobstream data commitments returned by `attestations`.
+    /// Blob inclusion proofs are fetched from `proofs`.
+    #[must_use]
+    pub fn with_inclusion_verification(
+        mut self,
+        attestations: Arc<dyn BlobstreamAttestationProvider>,
+        proofs: Arc<dyn CelestiaProofProvider>,
+    ) -> Self {
+        self.inclusion_verifier = Some(InclusionVerifier {
+            attestations,
+            proofs,
+        });
+        self
+    }
+
+    fn namespace(&self) -> anyhow::Result<Namespace> {
+        let namespace_bytes = hex::decode(&self.config.namespace)?;
+        Ok(Namespace::new_v0(namespace_bytes.as_slice())?)
+    }
 }
+
 #[derive(Serialize, Deserialize)]
 pub struct BlobId {
     pub commitment: Commitment,
@@ -58,10 +94,7 @@ impl DataAvailabilityClient for CelestiaClient {
         _: u32, // batch number
         data: Vec<u8>,
     ) -> Result<DispatchResponse, DAError> {
-        let namespace_bytes =
-            hex::decode(&self.config.namespace).map_err(to_non_retriable_da_error)?;
-        let namespace =
-            Namespace::new_v0(namespace_bytes.as_slice()).map_err(to_non_retriable_da_error)?;
+        let namespace = self.namespace().map_err(to_non_retriable_da_error)?;
         let blob = Blob::new(namespace, data).map_err(to_non_retriable_da_error)?;
 
         let commitment = blob.commitment;
@@ -101,6 +134,32 @@ impl DataAvailabilityClient for CelestiaClient {
         Ok(Some(InclusionData { data: vec![] }))
     }
 
+    async fn verify_inclusion_data(
+        &self,
+        blob_id: &str,
+        _: &InclusionData,
+    ) -> Result<InclusionVerification, DAError> {
+        let verifier = self.inclusion_verifier.as_ref().ok_or_else(|| DAError {
+            error: anyhow::anyhow!(
+                "cannot verify inclusion of blob {blob_id}: Blobstream contract is not configured"
+            ),
+            is_retriable: false,
+        })?;
+        let namespace = self.namespace().map_err(to_non_retriable_da_error)?;
+        verification::verify_inclusion(
+            verifier.attestations.as_ref(),
+            verifier.proofs.as_ref(),
+            namespace,
+            blob_id,
+        )
+        .await
+        .map_err(to_retriable_da_error)
+    }
+
+    fn supports_inclusion_verification(&self) -> bool {
+        self.inclusion_verifier.is_some()
+    }
+
     fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
         Box::new(self.clone())
     }
@@ -126,6 +185,7 @@ impl Debug for CelestiaClient {
         f.debug_struct("CelestiaClient")
             .field("config.api_node_url", &self.config.api_node_url)
             .field("config.namespace", &self.config.namespace)
+            .field("inclusion_verifier", &self.inclusion_verifier)
             .finish()
     }
 }
@@ -1,7 +1,14 @@
 mod client;
 mod sdk;
+mod verification;
 
-pub use self::client::CelestiaClient;
+pub use self::{
+    client::CelestiaClient,
+    verification::{
+        BlobstreamAttestationProvider, BlobstreamContractProvider, CelestiaNodeProofProvider,
+        CelestiaProofProvider, DataCommitment,
+    },
+};
 
 pub mod celestia_proto {
     include!("generated/celestia.blob.v1.rs");
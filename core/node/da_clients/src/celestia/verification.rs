@@ -0,0 +1,870 @@
+//! Verification of Celestia blobs against data commitments relayed by the Blobstream contract.
+//!
+//! A blob is verified the same way as by Blobstream-based DA validators on the settlement layer:
+//!
+//! 1. The `(height, data_root)` tuple of the Celestia block containing the blob must be included
+//!    into the data commitment stored in the Blobstream contract.
+//! 2. The blob shares must be included into the data root of the block.
+//!
+//! Proofs are fetched from a Celestia node; the only trusted input is the data commitment read from the settlement layer.
+
+use std::fmt;
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use celestia_types::{blob::Commitment, hash::Hash, nmt::Namespace, Blob, ShareProof};
+use secrecy::{ExposeSecret, SecretString};
+use serde::{de::DeserializeOwned, Deserialize};
+use sha2::{Digest, Sha256};
+use zksync_da_client::types::InclusionVerification;
+use zksync_eth_client::EthInterface;
+use zksync_types::{
+    ethabi::{self, ParamType, Token},
+    web3::{BlockNumber, FilterBuilder, Log},
+    Address, H256, U256,
+};
+
+use super::client::BlobId;
+use crate::utils::call_view_function;
+
+/// Number of settlement layer blocks queried for `DataCommitmentStored` events at once.
+const LOGS_PAGE_SIZE: u64 = 5_000;
+/// Maximum number of settlement layer blocks to look back for a data commitment covering a Celestia block.
+const MAX_LOGS_LOOKBACK: u64 = 100_000;
+
+/// Blobstream data commitment to the data roots of a range of Celestia blocks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DataCommitment {
+    pub proof_nonce: U256,
+    /// First Celestia block covered by the commitment (inclusive).
+    pub start_block: u64,
+    /// Last Celestia block covered by the commitment (exclusive).
+    pub end_block: u64,
+    /// Merkle root of the data root tuples for the covered blocks.
+    pub data_root_tuple_root: H256,
+}
+
+impl DataCommitment {
+    fn covers(&self, height: u64) -> bool {
+        (self.start_block..self.end_block).contains(&height)
+    }
+}
+
+/// Provider of Celestia block data commitments attested on the settlement layer.
+#[async_trait]
+pub trait BlobstreamAttestationProvider: fmt::Debug + Send + Sync {
+    /// Returns the latest Celestia block height covered by a data commitment.
+    async fn latest_attested_height(&self) -> anyhow::Result<u64>;
+
+    /// Returns the data commitment covering the specified Celestia block, or `None` if it cannot be found.
+    async fn data_commitment(&self, height: u64) -> anyhow::Result<Option<DataCommitment>>;
+}
+
+/// [`BlobstreamAttestationProvider`] reading data commitments from the Blobstream contract.
+#[derive(Debug)]
+pub struct BlobstreamContractProvider {
+    client: Box<dyn EthInterface>,
+    contract_address: Address,
+}
+
+impl BlobstreamContractProvider {
+    pub fn new(client: Box<dyn EthInterface>, contract_address: Address) -> Self {
+        Self {
+            client,
+            contract_address,
+        }
+    }
+
+    fn data_commitment_stored_topic() -> H256 {
+        ethabi::long_signature(
+            "DataCommitmentStored",
+            &[
+                ParamType::Uint(256),
+                ParamType::Uint(64),
+                ParamType::Uint(64),
+                ParamType::FixedBytes(32),
+            ],
+        )
+    }
+
+    /// Parses a `DataCommitmentStored(uint256 proofNonce, uint64 indexed startBlock, uint64 indexed endBlock,
+    /// bytes32 indexed dataCommitment)` event.
+    fn parse_data_commitment(log: &Log) -> anyhow::Result<DataCommitment> {
+        let [_, start_block, end_block, data_root_tuple_root] = log.topics.as_slice() else {
+            anyhow::bail!("unexpected topics in `DataCommitmentStored` event: {log:?}");
+        };
+        let tokens = ethabi::decode(&[ParamType::Uint(256)], &log.data.0)
+            .context("failed decoding `DataCommitmentStored` event data")?;
+        let Some(Token::Uint(proof_nonce)) = tokens.into_iter().next() else {
+            anyhow::bail!("unexpected data in `DataCommitmentStored` event: {log:?}");
+        };
+        let block = |topic: &H256| {
+            let block = U256::from_big_endian(topic.as_bytes());
+            anyhow::ensure!(block <= u64::MAX.into(), "block overflow: {block}");
+            Ok(block.as_u64())
+        };
+        Ok(DataCommitment {
+            proof_nonce,
+            start_block: block(start_block)?,
+            end_block: block(end_block)?,
+            data_root_tuple_root: *data_root_tuple_root,
+        })
+    }
+
+    async fn stored_data_commitment(&self, proof_nonce: U256) -> anyhow::Result<H256> {
+        let output = call_view_function(
+            self.client.as_ref(),
+            self.contract_address,
+            ("state_dataCommitments", &[ParamType::Uint(256)]),
+            &[Token::Uint(proof_nonce)],
+            &[ParamType::FixedBytes(32)],
+        )
+        .await?;
+        let [Token::FixedBytes(commitment)] = output.as_slice() else {
+            anyhow::bail!("unexpected output of `state_dataCommitments`: {output:?}");
+        };
+        Ok(H256::from_slice(commitment))
+    }
+}
+
+#[async_trait]
+impl BlobstreamAttestationProvider for BlobstreamContractProvider {
+    async fn latest_attested_height(&self) -> anyhow::Result<u64> {
+        let output = call_view_function(
+            self.client.as_ref(),
+            self.contract_address,
+            ("latestBlock", &[]),
+            &[],
+            &[ParamType::Uint(64)],
+        )
+        .await?;
+        let [token] = output.as_slice() else {
+            anyhow::bail!("unexpected output of `latestBlock`: {output:?}");
+        };
+        let height = token
+            .clone()
+            .into_uint()
+            .filter(|height| *height <= u64::MAX.into())
+            .ok_or_else(|| anyhow::anyhow!("unexpected output of `latestBlock`: {output:?}"))?;
+        Ok(height.as_u64())
+    }
+
+    async fn data_commitment(&self, height: u64) -> anyhow::Result<Option<DataCommitment>> {
+        let latest_block = self
+            .client
+            .block_number()
+            .await
+            .context("failed getting latest settlement layer block")?
+            .as_u64();
+        let first_block = latest_block.saturating_sub(MAX_LOGS_LOOKBACK);
+        let topic = Self::data_commitment_stored_topic();
+
+        // Commitments are stored in the order of Celestia blocks, so we scan events from the newest to the oldest
+        // and stop once we reach a commitment preceding the requested block.
+        let mut to_block = latest_block;
+        loop {
+            let from_block = to_block.saturating_sub(LOGS_PAGE_SIZE - 1).max(first_block);
+            let filter = FilterBuilder::default()
+                .address(vec![self.contract_address])
+                .from_block(BlockNumber::Number(from_block.into()))
+                .to_block(BlockNumber::Number(to_block.into()))
+                .topics(Some(vec![topic]), None, None, None)
+                .build();
+            let logs = self
+                .client
+                .logs(&filter)
+                .await
+                .context("failed getting `DataCommitmentStored` events")?;
+
+            for log in logs.iter().rev() {
+                let commitment = Self::parse_data_commitment(log)?;
+                if commitment.covers(height) {
+                    // Double-check the commitment against the contract storage in case the events are reorged.
+                    let stored = self.stored_data_commitment(commitment.proof_nonce).await?;
+                    anyhow::ensure!(
+                        stored == commitment.data_root_tuple_root,
+                        "data commitment for nonce {} in contract storage ({stored:?}) differs from the one in event ({:?})",
+                        commitment.proof_nonce,
+                        commitment.data_root_tuple_root
+                    );
+                    return Ok(Some(commitment));
+                } else if commitment.end_block <= height {
+                    return Ok(None);
+                }
+            }
+
+            if from_block == first_block {
+                return Ok(None);
+            }
+            to_block = from_block - 1;
+        }
+    }
+}
+
+/// Provider of Celestia blobs and their inclusion proofs.
+#[async_trait]
+pub trait CelestiaProofProvider: fmt::Debug + Send + Sync {
+    /// Returns the blob with the specified commitment included into the specified block, or `None`
+    /// if the blob is not found.
+    async fn get_blob(
+        &self,
+        height: u64,
+        namespace: Namespace,
+        commitment: Commitment,
+    ) -> anyhow::Result<Option<Blob>>;
+
+    /// Returns the data root of the specified block.
+    async fn data_root(&self, height: u64) -> anyhow::Result<H256>;
+
+    /// Returns the Merkle proof (i.e., side nodes from the leaf to the root) of inclusion of the data root tuple
+    /// for the specified block into the data commitment for the `start..end` block range.
+    async fn data_root_tuple_proof(
+        &self,
+        height: u64,
+        start_block: u64,
+        end_block: u64,
+    ) -> anyhow::Result<Vec<H256>>;
+
+    /// Returns the proof of inclusion of the `start..end` shares of the original data square into the data root
+    /// of the specified block.
+    async fn share_proof(&self, height: u64, start: u64, end: u64) -> anyhow::Result<ShareProof>;
+}
+
+/// Error returned by a Celestia node JSON-RPC API.
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+impl fmt::Display for JsonRpcError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "JSON-RPC error {}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for JsonRpcError {}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse<T> {
+    result: Option<T>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtendedHeader {
+    header: RawHeader,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawHeader {
+    data_hash: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawMerkleProof {
+    #[serde(default)]
+    aunts: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetRangeResult {
+    #[serde(rename = "Proof")]
+    proof: ShareProof,
+}
+
+/// [`CelestiaProofProvider`] using the JSON-RPC API of a Celestia data availability node (bridge, full or light).
+#[derive(Debug)]
+pub struct CelestiaNodeProofProvider {
+    client: reqwest::Client,
+    url: String,
+    auth_token: Option<SecretString>,
+}
+
+impl CelestiaNodeProofProvider {
+    pub fn new(client: reqwest::Client, url: String, auth_token: Option<SecretString>) -> Self {
+        Self {
+            client,
+            url,
+            auth_token,
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> anyhow::Result<T> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let mut request = self.client.post(&self.url).json(&request);
+        if let Some(token) = &self.auth_token {
+            request = request.bearer_auth(token.expose_secret());
+        }
+        let response: JsonRpcResponse<T> = request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .with_context(|| format!("failed calling `{method}`"))?
+            .json()
+            .await
+            .with_context(|| format!("failed decoding response of `{method}`"))?;
+
+        if let Some(err) = response.error {
+            return Err(anyhow::Error::new(err).context(format!("`{method}` failed")));
+        }
+        response
+            .result
+            .with_context(|| format!("`{method}` returned no result"))
+    }
+}
+
+#[async_trait]
+impl CelestiaProofProvider for CelestiaNodeProofProvider {
+    async fn get_blob(
+        &self,
+        height: u64,
+        namespace: Namespace,
+        commitment: Commitment,
+    ) -> anyhow::Result<Option<Blob>> {
+        let params = serde_json::json!([height, namespace, commitment]);
+        match self.call("blob.Get", params).await {
+            Ok(blob) => Ok(Some(blob)),
+            Err(err)
+                if err
+                    .downcast_ref::<JsonRpcError>()
+                    .is_some_and(|err| err.message.contains("blob: not found")) =>
+            {
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn data_root(&self, height: u64) -> anyhow::Result<H256> {
+        let header: ExtendedHeader = self
+            .call("header.GetByHeight", serde_json::json!([height]))
+            .await?;
+        let data_root = hex::decode(&header.header.data_hash).context("invalid data root")?;
+        anyhow::ensure!(
+            data_root.len() == 32,
+            "unexpected data root length: {}",
+            data_root.len()
+        );
+        Ok(H256::from_slice(&data_root))
+    }
+
+    async fn data_root_tuple_proof(
+        &self,
+        height: u64,
+        start_block: u64,
+        end_block: u64,
+    ) -> anyhow::Result<Vec<H256>> {
+        let params = serde_json::json!([height, start_block, end_block]);
+        let proof: RawMerkleProof = self
+            .call("blobstream.GetDataRootTupleInclusionProof", params)
+            .await?;
+        proof
+            .aunts
+            .unwrap_or_default()
+            .iter()
+            .map(|aunt| {
+                let aunt = BASE64.decode(aunt).context("invalid side node")?;
+                anyhow::ensure!(aunt.len() == 32, "unexpected side node length");
+                Ok(H256::from_slice(&aunt))
+            })
+            .collect()
+    }
+
+    async fn share_proof(&self, height: u64, start: u64, end: u64) -> anyhow::Result<ShareProof> {
+        let result: GetRangeResult = self
+            .call("share.GetRange", serde_json::json!([height, start, end]))
+            .await?;
+        Ok(result.proof)
+    }
+}
+
+fn leaf_hash(data: &[u8]) -> H256 {
+    H256(
+        Sha256::new()
+            .chain_update([0])
+            .chain_update(data)
+            .finalize()
+            .into(),
+    )
+}
+
+fn inner_hash(left: H256, right: H256) -> H256 {
+    H256(
+        Sha256::new()
+            .chain_update([1])
+            .chain_update(left)
+            .chain_update(right)
+            .finalize()
+            .into(),
+    )
+}
+
+/// Computes the root of an RFC 6962 Merkle tree (as used by Celestia and Blobstream) with `total` leaves
+/// from the leaf with the specified index and its proof. The proof lists side nodes from the leaf to the root.
+/// Returns `None` if the proof has an unexpected length.
+fn merkle_root(index: u64, total: u64, leaf_hash: H256, side_nodes: &[H256]) -> Option<H256> {
+    if index >= total {
+        return None;
+    }
+    if total == 1 {
+        return side_nodes.is_empty().then_some(leaf_hash);
+    }
+    let (&sibling, side_nodes) = side_nodes.split_last()?;
+    // Number of leaves in the left subtree: the largest power of 2 less than `total`.
+    let split_point = 1_u64 << (63 - (total - 1).leading_zeros());
+    Some(if index < split_point {
+        let left = merkle_root(index, split_point, leaf_hash, side_nodes)?;
+        inner_hash(left, sibling)
+    } else {
+        let right = merkle_root(
+            index - split_point,
+            total - split_point,
+            leaf_hash,
+            side_nodes,
+        )?;
+        inner_hash(sibling, right)
+    })
+}
+
+/// Checks that the data root tuple for the specified block is included into the data commitment,
+/// the same way as `Blobstream.verifyAttestation()`.
+fn verify_data_root_tuple(
+    commitment: &DataCommitment,
+    height: u64,
+    data_root: H256,
+    side_nodes: &[H256],
+) -> bool {
+    if !commitment.covers(height) {
+        return false;
+    }
+    // `abi.encode(DataRootTuple { height, dataRoot })`
+    let tuple = ethabi::encode(&[
+        Token::Uint(height.into()),
+        Token::FixedBytes(data_root.as_bytes().to_vec()),
+    ]);
+    let index = height - commitment.start_block;
+    let total = commitment.end_block - commitment.start_block;
+    merkle_root(index, total, leaf_hash(&tuple), side_nodes)
+        == Some(commitment.data_root_tuple_root)
+}
+
+/// Verifies that the blob is included into a Celestia block covered by a Blobstream data commitment.
+pub(super) async fn verify_inclusion(
+    attestations: &dyn BlobstreamAttestationProvider,
+    proofs: &dyn CelestiaProofProvider,
+    namespace: Namespace,
+    blob_id: &str,
+) -> anyhow::Result<InclusionVerification> {
+    let blob_id = match decode_blob_id(blob_id) {
+        Ok(blob_id) => blob_id,
+        Err(err) => {
+            return Ok(InclusionVerification::Invalid(format!(
+                "cannot decode blob ID: {err:#}"
+            )));
+        }
+    };
+    let height = blob_id.height;
+    if verify_height(attestations, height).await? == InclusionVerification::Pending {
+        return Ok(InclusionVerification::Pending);
+    }
+
+    let commitment = attestations
+        .data_commitment(height)
+        .await?
+        .with_context(|| {
+            format!("cannot find Blobstream data commitment covering Celestia block #{height}")
+        })?;
+    let data_root = proofs.data_root(height).await?;
+    let side_nodes = proofs
+        .data_root_tuple_proof(height, commitment.start_block, commitment.end_block)
+        .await?;
+    if !verify_data_root_tuple(&commitment, height, data_root, &side_nodes) {
+        return Ok(InclusionVerification::Invalid(format!(
+            "data root {data_root:?} of Celestia block #{height} is not included into Blobstream data commitment \
+             with nonce {}",
+            commitment.proof_nonce
+        )));
+    }
+
+    let Some(blob) = proofs
+        .get_blob(height, namespace, blob_id.commitment)
+        .await?
+    else {
+        return Ok(InclusionVerification::Invalid(format!(
+            "blob is not found in Celestia block #{height}"
+        )));
+    };
+    let start = blob
+        .index
+        .with_context(|| format!("Celestia node returned no index for blob in block #{height}"))?;
+    // Recompute the blob from its data, so that we don't trust the commitment and shares returned by the node.
+    let blob = Blob::new(namespace, blob.data).context("cannot recreate blob")?;
+    if blob.commitment != blob_id.commitment {
+        return Ok(InclusionVerification::Invalid(format!(
+            "commitment of the blob in Celestia block #{height} doesn't match the blob ID"
+        )));
+    }
+    let shares = blob.to_shares().context("cannot split blob into shares")?;
+
+    let share_proof = proofs
+        .share_proof(height, start, start + shares.len() as u64)
+        .await?;
+    if let Err(err) = share_proof.verify(Hash::Sha256(data_root.0)) {
+        return Ok(InclusionVerification::Invalid(format!(
+            "share inclusion proof for blob in Celestia block #{height} is invalid: {err}"
+        )));
+    }
+    let proven_shares = share_proof.shares();
+    let shares_match = proven_shares.len() == shares.len()
+        && proven_shares
+            .iter()
+            .zip(&shares)
+            .all(|(proven, share)| proven.as_slice() == share.as_ref());
+    if !shares_match {
+        return Ok(InclusionVerification::Invalid(format!(
+            "shares proven for blob in Celestia block #{height} don't match the blob"
+        )));
+    }
+    Ok(InclusionVerification::Verified)
+}
+
+fn decode_blob_id(blob_id: &str) -> anyhow::Result<BlobId> {
+    let bytes = hex::decode(blob_id)?;
+    let blob_id: BlobId = bincode::deserialize(&bytes)?;
+    anyhow::ensure!(blob_id.height > 0, "blob height is zero");
+    Ok(blob_id)
+}
+
+async fn verify_height(
+    provider: &dyn BlobstreamAttestationProvider,
+    height: u64,
+) -> anyhow::Result<InclusionVerification> {
+    let latest_attested_height = provider.latest_attested_height().await?;
+    Ok(if height <= latest_attested_height {
+        InclusionVerification::Verified
+    } else {
+        tracing::debug!(
+            "Celestia block #{height} is not covered by Blobstream yet; latest attested block is #{latest_attested_height}"
+        );
+        InclusionVerification::Pending
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAMESPACE: [u8; 10] = [0; 10];
+
+    #[derive(Debug)]
+    struct MockAttestationProvider {
+        latest_attested_height: u64,
+        commitments: Vec<DataCommitment>,
+    }
+
+    impl MockAttestationProvider {
+        fn new(latest_attested_height: u64) -> Self {
+            Self {
+                latest_attested_height,
+                commitments: vec![],
+            }
+        }
+    }
+
+    #[async_trait]
+    impl BlobstreamAttestationProvider for MockAttestationProvider {
+        async fn latest_attested_height(&self) -> anyhow::Result<u64> {
+            Ok(self.latest_attested_height)
+        }
+
+        async fn data_commitment(&self, height: u64) -> anyhow::Result<Option<DataCommitment>> {
+            Ok(self
+                .commitments
+                .iter()
+                .find(|commitment| commitment.covers(height))
+                .copied())
+        }
+    }
+
+    /// Celestia node storing a single block with the specified data root; blobs are stored without proofs.
+    #[derive(Debug)]
+    struct MockProofProvider {
+        data_root: H256,
+        side_nodes: Vec<H256>,
+        blob: Option<Blob>,
+    }
+
+    #[async_trait]
+    impl CelestiaProofProvider for MockProofProvider {
+        async fn get_blob(
+            &self,
+            _height: u64,
+            _namespace: Namespace,
+            _commitment: Commitment,
+        ) -> anyhow::Result<Option<Blob>> {
+            Ok(self.blob.clone())
+        }
+
+        async fn data_root(&self, _height: u64) -> anyhow::Result<H256> {
+            Ok(self.data_root)
+        }
+
+        async fn data_root_tuple_proof(
+            &self,
+            _height: u64,
+            _start_block: u64,
+            _end_block: u64,
+        ) -> anyhow::Result<Vec<H256>> {
+            Ok(self.side_nodes.clone())
+        }
+
+        async fn share_proof(
+            &self,
+            _height: u64,
+            _start: u64,
+            _end: u64,
+        ) -> anyhow::Result<ShareProof> {
+            anyhow::bail!("share proofs are not supported")
+        }
+    }
+
+    fn reference_merkle_root(leaves: &[H256]) -> H256 {
+        match leaves {
+            [leaf] => *leaf,
+            _ => {
+                let split_point = leaves.len().next_power_of_two() / 2;
+                let (left, right) = leaves.split_at(split_point);
+                inner_hash(reference_merkle_root(left), reference_merkle_root(right))
+            }
+        }
+    }
+
+    fn reference_merkle_proof(leaves: &[H256], index: usize) -> Vec<H256> {
+        if leaves.len() == 1 {
+            return vec![];
+        }
+        let split_point = leaves.len().next_power_of_two() / 2;
+        let (left, right) = leaves.split_at(split_point);
+        let (mut proof, sibling) = if index < split_point {
+            (
+                reference_merkle_proof(left, index),
+                reference_merkle_root(right),
+            )
+        } else {
+            (
+                reference_merkle_proof(right, index - split_point),
+                reference_merkle_root(left),
+            )
+        };
+        proof.push(sibling);
+        proof
+    }
+
+    fn tuple_leaf(height: u64, data_root: H256) -> H256 {
+        let tuple = ethabi::encode(&[
+            Token::Uint(height.into()),
+            Token::FixedBytes(data_root.as_bytes().to_vec()),
+        ]);
+        leaf_hash(&tuple)
+    }
+
+    /// Creates a data commitment for blocks `start_block..end_block` together with the data roots of these blocks.
+    fn mock_commitment(start_block: u64, end_block: u64) -> (DataCommitment, Vec<H256>) {
+        let data_roots: Vec<_> = (start_block..end_block)
+            .map(H256::from_low_u64_be)
+            .collect();
+        let leaves: Vec<_> = (start_block..)
+            .zip(&data_roots)
+            .map(|(height, &data_root)| tuple_leaf(height, data_root))
+            .collect();
+        let commitment = DataCommitment {
+            proof_nonce: 1.into(),
+            start_block,
+            end_block,
+            data_root_tuple_root: reference_merkle_root(&leaves),
+        };
+        (commitment, data_roots)
+    }
+
+    fn blob_id(blob: &Blob, height: u64) -> String {
+        let blob_id = BlobId {
+            commitment: blob.commitment,
+            height,
+        };
+        hex::encode(bincode::serialize(&blob_id).unwrap())
+    }
+
+    #[test]
+    fn computing_merkle_root() {
+        for total in 1..=11 {
+            let leaves: Vec<_> = (0..total).map(H256::from_low_u64_be).collect();
+            let root = reference_merkle_root(&leaves);
+            for index in 0..total {
+                let proof = reference_merkle_proof(&leaves, index);
+                let (index, total) = (index as u64, total as u64);
+                assert_eq!(
+                    merkle_root(index, total, leaves[index as usize], &proof),
+                    Some(root),
+                    "total={total}, index={index}"
+                );
+                if total > 1 {
+                    assert_eq!(merkle_root(index, total, leaves[index as usize], &[]), None);
+                    let wrong_index = (index + 1) % total;
+                    assert_ne!(
+                        merkle_root(wrong_index, total, leaves[index as usize], &proof),
+                        Some(root)
+                    );
+                }
+                assert_eq!(
+                    merkle_root(total, total, leaves[index as usize], &proof),
+                    None
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn verifying_data_root_tuple() {
+        let (commitment, data_roots) = mock_commitment(100, 107);
+        let leaves: Vec<_> = (100..)
+            .zip(&data_roots)
+            .map(|(height, &data_root)| tuple_leaf(height, data_root))
+            .collect();
+        for (i, &data_root) in data_roots.iter().enumerate() {
+            let height = 100 + i as u64;
+            let proof = reference_merkle_proof(&leaves, i);
+            assert!(verify_data_root_tuple(
+                &commitment,
+                height,
+                data_root,
+                &proof
+            ));
+            assert!(!verify_data_root_tuple(
+                &commitment,
+                height,
+                H256::repeat_byte(0xff),
+                &proof
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn verifying_blob_height() {
+        let provider = MockAttestationProvider::new(100);
+        let verification = verify_height(&provider, 99).await.unwrap();
+        assert_eq!(verification, InclusionVerification::Verified);
+        let verification = verify_height(&provider, 100).await.unwrap();
+        assert_eq!(verification, InclusionVerification::Verified);
+        let verification = verify_height(&provider, 101).await.unwrap();
+        assert_eq!(verification, InclusionVerification::Pending);
+    }
+
+    #[tokio::test]
+    async fn verifying_invalid_blob_id() {
+        let attestations = MockAttestationProvider::new(100);
+        let proofs = MockProofProvider {
+            data_root: H256::zero(),
+            side_nodes: vec![],
+            blob: None,
+        };
+        let namespace = Namespace::new_v0(&NAMESPACE).unwrap();
+        for blob_id in ["not hex", "0123"] {
+            let verification = verify_inclusion(&attestations, &proofs, namespace, blob_id)
+                .await
+                .unwrap();
+            assert!(
+                matches!(&verification, InclusionVerification::Invalid(reason) if reason.contains("blob ID")),
+                "{verification:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn verifying_blob_against_data_commitment() {
+        let namespace = Namespace::new_v0(&NAMESPACE).unwrap();
+        let mut blob = Blob::new(namespace, vec![1; 1_000]).unwrap();
+        blob.index = Some(0);
+        let (commitment, data_roots) = mock_commitment(100, 105);
+        let leaves: Vec<_> = (100..)
+            .zip(&data_roots)
+            .map(|(height, &data_root)| tuple_leaf(height, data_root))
+            .collect();
+        let height = 102;
+        let blob_id = blob_id(&blob, height);
+
+        let mut attestations = MockAttestationProvider::new(101);
+        let mut proofs = MockProofProvider {
+            data_root: data_roots[2],
+            side_nodes: reference_merkle_proof(&leaves, 2),
+            blob: Some(blob.clone()),
+        };
+        let verification = verify_inclusion(&attestations, &proofs, namespace, &blob_id)
+            .await
+            .unwrap();
+        assert_eq!(verification, InclusionVerification::Pending);
+
+        attestations.latest_attested_height = 104;
+        let err = verify_inclusion(&attestations, &proofs, namespace, &blob_id)
+            .await
+            .unwrap_err();
+        assert!(
+            format!("{err:#}").contains("cannot find Blobstream data commitment"),
+            "{err:#}"
+        );
+
+        attestations.commitments.push(commitment);
+        // The tuple proof is verified, so the verifier proceeds to checking shares, which aren't supported by the mock.
+        let err = verify_inclusion(&attestations, &proofs, namespace, &blob_id)
+            .await
+            .unwrap_err();
+        assert!(
+            format!("{err:#}").contains("share proofs are not supported"),
+            "{err:#}"
+        );
+
+        // Data root returned by the node is not attested.
+        proofs.data_root = H256::repeat_byte(0xff);
+        let verification = verify_inclusion(&attestations, &proofs, namespace, &blob_id)
+            .await
+            .unwrap();
+        assert!(
+            matches!(&verification, InclusionVerification::Invalid(reason) if reason.contains("data commitment")),
+            "{verification:?}"
+        );
+
+        // Blob is missing.
+        proofs.data_root = data_roots[2];
+        proofs.blob = None;
+        let verification = verify_inclusion(&attestations, &proofs, namespace, &blob_id)
+            .await
+            .unwrap();
+        assert!(
+            matches!(&verification, InclusionVerification::Invalid(reason) if reason.contains("not found")),
+            "{verification:?}"
+        );
+
+        // Blob data doesn't match the commitment.
+        let mut other_blob = Blob::new(namespace, vec![2; 1_000]).unwrap();
+        other_blob.index = Some(0);
+        other_blob.commitment = blob.commitment;
+        proofs.blob = Some(other_blob);
+        let verification = verify_inclusion(&attestations, &proofs, namespace, &blob_id)
+            .await
+            .unwrap();
+        assert!(
+            matches!(&verification, InclusionVerification::Invalid(reason) if reason.contains("doesn't match")),
+            "{verification:?}"
+        );
+    }
+}
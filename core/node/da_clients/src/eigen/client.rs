@@ -1,5 +1,6 @@
-use std::str::FromStr;
+use std::{str::FromStr, sync::Arc};
 
+use anyhow::Context as _;
 use chrono::{DateTime, Utc};
 use rust_eigenda_signers::signers::private_key::Signer;
 use rust_eigenda_v2_client::{
@@ -12,26 +13,44 @@ use subxt_signer::ExposeSecret;
 use url::Url;
 use zksync_config::{configs::da_client::eigen::EigenSecrets, EigenConfig};
 use zksync_da_client::{
-    types::{ClientType, DAError, DispatchResponse, FinalityResponse, InclusionData},
+    types::{
+        ClientType, DAError, DispatchResponse, FinalityResponse, InclusionData,
+        InclusionVerification,
+    },
     DataAvailabilityClient,
 };
+use zksync_eth_client::web3_decl::client::{Client, DynClient, L1};
+use zksync_types::Address;
 
+use super::verification::{self, CertVerifierRouter, EigenCertVerifier};
 use crate::utils::{to_non_retriable_da_error, to_retriable_da_error};
 
 // We can't implement DataAvailabilityClient for an outside struct, so it is needed to defined this intermediate struct
 #[derive(Debug, Clone)]
 pub struct EigenDAClient {
     client: PayloadDisperser,
+    cert_verifier: Arc<dyn EigenCertVerifier>,
 }
 
 impl EigenDAClient {
     pub async fn new(config: EigenConfig, secrets: EigenSecrets) -> anyhow::Result<Self> {
-        let url = Url::from_str(
-            config
-                .eigenda_eth_rpc
-                .ok_or(anyhow::anyhow!("Eigenda eth rpc url is not set"))?
-                .expose_str(),
-        )?;
+        let eth_rpc = config
+            .eigenda_eth_rpc
+            .ok_or(anyhow::anyhow!("Eigenda eth rpc url is not set"))?;
+        let url = Url::from_str(eth_rpc.expose_str())?;
+
+        let cert_verifier_router_addr: Address = config
+            .cert_verifier_router_addr
+            .parse()
+            .context("invalid cert verifier router address")?;
+        let l1_client = Client::<L1>::http(eth_rpc)
+            .context("failed creating L1 client for cert verification")?
+            .build();
+        let l1_client: Box<DynClient<L1>> = Box::new(l1_client);
+        let cert_verifier = CertVerifierRouter::new(
+            Box::new(l1_client.for_component("eigenda_cert_verifier")),
+            cert_verifier_router_addr,
+        );
 
         let private_key = secrets.private_key.0.expose_secret();
 
@@ -50,7 +69,17 @@ impl EigenDAClient {
         let signer = Signer::new(private_key);
         let client = PayloadDisperser::new(payload_disperser_config, signer).await?;
 
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            cert_verifier: Arc::new(cert_verifier),
+        })
+    }
+
+    /// Overrides the verifier used to check EigenDA certificates.
+    #[must_use]
+    pub fn with_cert_verifier(mut self, verifier: Arc<dyn EigenCertVerifier>) -> Self {
+        self.cert_verifier = verifier;
+        self
     }
 }
 
@@ -133,6 +162,20 @@ impl DataAvailabilityClient for EigenDAClient {
         }
     }
 
+    async fn verify_inclusion_data(
+        &self,
+        _: &str,
+        inclusion_data: &InclusionData,
+    ) -> Result<InclusionVerification, DAError> {
+        verification::verify_cert(self.cert_verifier.as_ref(), &inclusion_data.data)
+            .await
+            .map_err(to_retriable_da_error)
+    }
+
+    fn supports_inclusion_verification(&self) -> bool {
+        true
+    }
+
     fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
         Box::new(self.clone())
     }
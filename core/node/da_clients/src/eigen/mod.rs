@@ -1,3 +1,7 @@
 mod client;
+mod verification;
 
-pub use self::client::EigenDAClient;
+pub use self::{
+    client::EigenDAClient,
+    verification::{CertVerifierRouter, EigenCertVerifier},
+};
@@ -0,0 +1,107 @@
+//! Verification of EigenDA certificates against the cert verifier router contract.
+
+use std::fmt;
+
+use async_trait::async_trait;
+use zksync_da_client::types::InclusionVerification;
+use zksync_eth_client::EthInterface;
+use zksync_types::{
+    ethabi::{ParamType, Token},
+    Address,
+};
+
+use crate::utils::call_view_function;
+
+/// Status returned by the cert verifier for valid certificates.
+const CERT_STATUS_SUCCESS: u8 = 1;
+
+/// Verifier of EigenDA certificates.
+#[async_trait]
+pub trait EigenCertVerifier: fmt::Debug + Send + Sync {
+    /// Checks an ABI-encoded EigenDA certificate and returns the verification status code.
+    async fn check_da_cert(&self, cert: &[u8]) -> anyhow::Result<u8>;
+}
+
+/// [`EigenCertVerifier`] calling the cert verifier router contract on L1.
+#[derive(Debug)]
+pub struct CertVerifierRouter {
+    client: Box<dyn EthInterface>,
+    contract_address: Address,
+}
+
+impl CertVerifierRouter {
+    pub fn new(client: Box<dyn EthInterface>, contract_address: Address) -> Self {
+        Self {
+            client,
+            contract_address,
+        }
+    }
+}
+
+#[async_trait]
+impl EigenCertVerifier for CertVerifierRouter {
+    async fn check_da_cert(&self, cert: &[u8]) -> anyhow::Result<u8> {
+        let output = call_view_function(
+            self.client.as_ref(),
+            self.contract_address,
+            ("checkDACert", &[ParamType::Bytes]),
+            &[Token::Bytes(cert.to_vec())],
+            &[ParamType::Uint(8)],
+        )
+        .await?;
+        let [Token::Uint(status)] = output.as_slice() else {
+            anyhow::bail!("unexpected output of `checkDACert`: {output:?}");
+        };
+        Ok(status.low_u32() as u8)
+    }
+}
+
+pub(super) async fn verify_cert(
+    verifier: &dyn EigenCertVerifier,
+    cert: &[u8],
+) -> anyhow::Result<InclusionVerification> {
+    if cert.is_empty() {
+        return Ok(InclusionVerification::Invalid(
+            "certificate is empty".into(),
+        ));
+    }
+    let status = verifier.check_da_cert(cert).await?;
+    Ok(if status == CERT_STATUS_SUCCESS {
+        InclusionVerification::Verified
+    } else {
+        InclusionVerification::Invalid(format!(
+            "certificate was rejected by the verifier with status {status}"
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct MockCertVerifier;
+
+    #[async_trait]
+    impl EigenCertVerifier for MockCertVerifier {
+        async fn check_da_cert(&self, cert: &[u8]) -> anyhow::Result<u8> {
+            Ok(cert[0])
+        }
+    }
+
+    #[tokio::test]
+    async fn verifying_certificates() {
+        let verification = verify_cert(&MockCertVerifier, &[CERT_STATUS_SUCCESS, 0])
+            .await
+            .unwrap();
+        assert_eq!(verification, InclusionVerification::Verified);
+
+        for cert in [&[][..], &[2, 0]] {
+            let verification = verify_cert(&MockCertVerifier, cert).await.unwrap();
+            assert!(
+                matches!(verification, InclusionVerification::Invalid(_)),
+                "{verification:?}"
+            );
+        }
+    }
+}
@@ -4,4 +4,5 @@ pub mod eigen;
 pub mod no_da;
 pub mod node;
 pub mod object_store;
+pub mod redundant;
 mod utils;
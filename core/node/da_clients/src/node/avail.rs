@@ -1,12 +1,16 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
 use zksync_config::{configs::da_client::avail::AvailSecrets, AvailConfig};
 use zksync_da_client::DataAvailabilityClient;
-use zksync_eth_client::web3_decl::node::SettlementModeResource;
+use zksync_eth_client::web3_decl::node::{SettlementLayerClient, SettlementModeResource};
 use zksync_node_framework::{
     wiring_layer::{WiringError, WiringLayer},
     FromContext,
 };
+use zksync_types::SLChainId;
 
-use crate::avail::AvailClient;
+use crate::avail::{AvailClient, VectorXAttestationProvider};
 
 #[derive(Debug)]
 pub struct AvailWiringLayer {
@@ -17,6 +21,8 @@ pub struct AvailWiringLayer {
 #[derive(Debug, FromContext)]
 pub struct Input {
     settlement_mode: SettlementModeResource,
+    /// Only required if inclusion data verification is enabled.
+    sl_client: Option<SettlementLayerClient>,
 }
 
 impl AvailWiringLayer {
@@ -36,7 +42,25 @@ impl WiringLayer for AvailWiringLayer {
 
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
         let sl_chain_id = input.settlement_mode.settlement_layer().chain_id();
-        let client = AvailClient::new(self.config, self.secrets, sl_chain_id).await?;
+        let client = create_client(self.config, self.secrets, sl_chain_id, input.sl_client).await?;
         Ok(Box::new(client))
     }
 }
+
+/// Creates an Avail client with inclusion data verification enabled if the VectorX contract is configured.
+pub(super) async fn create_client(
+    config: AvailConfig,
+    secrets: AvailSecrets,
+    sl_chain_id: SLChainId,
+    sl_client: Option<SettlementLayerClient>,
+) -> anyhow::Result<AvailClient> {
+    let vectorx_contract_address = config.vectorx_contract_address;
+    let mut client = AvailClient::new(config, secrets, sl_chain_id).await?;
+    if let Some(address) = vectorx_contract_address {
+        let sl_client = sl_client
+            .context("settlement layer client is required to verify Avail inclusion data")?;
+        let provider = VectorXAttestationProvider::new(sl_client.into(), address);
+        client = client.with_attestation_provider(Arc::new(provider));
+    }
+    Ok(client)
+}
@@ -1,8 +1,15 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
 use zksync_config::{configs::da_client::celestia::CelestiaSecrets, CelestiaConfig};
 use zksync_da_client::DataAvailabilityClient;
-use zksync_node_framework::wiring_layer::{WiringError, WiringLayer};
+use zksync_eth_client::web3_decl::client::{DynClient, L1};
+use zksync_node_framework::{
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext,
+};
 
-use crate::celestia::CelestiaClient;
+use crate::celestia::{BlobstreamContractProvider, CelestiaClient, CelestiaNodeProofProvider};
 
 #[derive(Debug)]
 pub struct CelestiaWiringLayer {
@@ -10,6 +17,12 @@ pub struct CelestiaWiringLayer {
     secrets: CelestiaSecrets,
 }
 
+#[derive(Debug, FromContext)]
+pub struct Input {
+    /// Only required if blob inclusion verification is enabled.
+    l1_client: Option<Box<DynClient<L1>>>,
+}
+
 impl CelestiaWiringLayer {
     pub fn new(config: CelestiaConfig, secrets: CelestiaSecrets) -> Self {
         Self { config, secrets }
@@ -18,15 +31,49 @@ impl CelestiaWiringLayer {
 
 #[async_trait::async_trait]
 impl WiringLayer for CelestiaWiringLayer {
-    type Input = ();
+    type Input = Input;
     type Output = Box<dyn DataAvailabilityClient>;
 
     fn layer_name(&self) -> &'static str {
         "celestia_client_layer"
     }
 
-    async fn wire(self, (): Self::Input) -> Result<Self::Output, WiringError> {
-        let client = CelestiaClient::new(self.config, self.secrets).await?;
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let client = create_client(self.config, self.secrets, input.l1_client).await?;
         Ok(Box::new(client))
     }
 }
+
+/// Creates a Celestia client with blob inclusion verification enabled if the Blobstream contract is configured.
+pub(super) async fn create_client(
+    config: CelestiaConfig,
+    secrets: CelestiaSecrets,
+    l1_client: Option<Box<DynClient<L1>>>,
+) -> anyhow::Result<CelestiaClient> {
+    let blobstream_contract_address = config.blobstream_contract_address;
+    let node_rpc_url = config.node_rpc_url.clone();
+    let timeout = config.timeout;
+    let node_rpc_auth_token = secrets.node_rpc_auth_token.clone();
+    let mut client = CelestiaClient::new(config, secrets).await?;
+    if let Some(address) = blobstream_contract_address {
+        let l1_client =
+            l1_client.context("L1 client is required to verify Celestia blob inclusion")?;
+        let node_rpc_url = node_rpc_url
+            .context("Celestia node RPC URL is required to verify Celestia blob inclusion")?;
+        let attestations = BlobstreamContractProvider::new(
+            Box::new(l1_client.for_component("celestia_blobstream")),
+            address,
+        );
+        let http_client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .context("failed building HTTP client")?;
+        let proofs = CelestiaNodeProofProvider::new(
+            http_client,
+            node_rpc_url,
+            node_rpc_auth_token.map(|token| token.0),
+        );
+        client = client.with_inclusion_verification(Arc::new(attestations), Arc::new(proofs));
+    }
+    Ok(client)
+}
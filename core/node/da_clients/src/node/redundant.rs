@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use zksync_config::configs::da_client::redundant::{
+    RedundantDABackend, RedundantDAConfig, RedundantDASecrets,
+};
+use zksync_da_client::DataAvailabilityClient;
+use zksync_dal::node::{MasterPool, PoolResource};
+use zksync_eth_client::web3_decl::{
+    client::{DynClient, L1},
+    node::{SettlementLayerClient, SettlementModeResource},
+};
+use zksync_health_check::AppHealthCheck;
+use zksync_node_framework::{
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext,
+};
+
+use super::{avail, celestia};
+use crate::{
+    eigen::EigenDAClient, object_store::ObjectStoreDAClient, redundant::RedundantDAClient,
+};
+
+/// Wiring layer for [`RedundantDAClient`] dispatching blobs to several DA layers.
+#[derive(Debug)]
+pub struct RedundantDAClientWiringLayer {
+    config: RedundantDAConfig,
+    secrets: Option<RedundantDASecrets>,
+}
+
+#[derive(Debug, FromContext)]
+pub struct Input {
+    master_pool: PoolResource<MasterPool>,
+    settlement_mode: SettlementModeResource,
+    /// Only required if Avail inclusion data verification is enabled.
+    sl_client: Option<SettlementLayerClient>,
+    /// Only required if Celestia blob inclusion verification is enabled.
+    l1_client: Option<Box<DynClient<L1>>>,
+    #[context(default)]
+    app_health: Arc<AppHealthCheck>,
+}
+
+impl RedundantDAClientWiringLayer {
+    pub fn new(config: RedundantDAConfig, secrets: Option<RedundantDASecrets>) -> Self {
+        Self { config, secrets }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for RedundantDAClientWiringLayer {
+    type Input = Input;
+    type Output = Box<dyn DataAvailabilityClient>;
+
+    fn layer_name(&self) -> &'static str {
+        "redundant_da_client_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let primary = self.config.primary;
+        let required_backends = self.config.required_backends();
+        let secrets = self.secrets.as_ref();
+        let mut backends: Vec<(_, Box<dyn DataAvailabilityClient>)> = vec![];
+
+        if let Some(config) = self.config.avail {
+            let secrets = secrets
+                .and_then(|secrets| secrets.avail.clone())
+                .context("Avail secrets are missing")?;
+            let sl_chain_id = input.settlement_mode.settlement_layer().chain_id();
+            let client =
+                avail::create_client(config, secrets, sl_chain_id, input.sl_client).await?;
+            backends.push((RedundantDABackend::Avail, Box::new(client)));
+        }
+        if let Some(config) = self.config.celestia {
+            let secrets = secrets
+                .and_then(|secrets| secrets.celestia.clone())
+                .context("Celestia secrets are missing")?;
+            let client = celestia::create_client(config, secrets, input.l1_client).await?;
+            backends.push((RedundantDABackend::Celestia, Box::new(client)));
+        }
+        if let Some(config) = self.config.eigen {
+            let secrets = secrets
+                .and_then(|secrets| secrets.eigen.clone())
+                .context("EigenDA secrets are missing")?;
+            let client = EigenDAClient::new(config, secrets).await?;
+            backends.push((RedundantDABackend::Eigen, Box::new(client)));
+        }
+        if let Some(config) = self.config.object_store {
+            let client = ObjectStoreDAClient::new(config).await?;
+            backends.push((RedundantDABackend::ObjectStore, Box::new(client)));
+        }
+
+        let pool = input.master_pool.get().await?;
+        let client = RedundantDAClient::new(backends, primary, required_backends, pool)?;
+        input
+            .app_health
+            .insert_component(client.health_check())
+            .map_err(WiringError::internal)?;
+        Ok(Box::new(client))
+    }
+}
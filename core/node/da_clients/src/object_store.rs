@@ -91,6 +91,26 @@ impl DataAvailabilityClient for ObjectStoreDAClient {
         return Ok(Some(InclusionData::default()));
     }
 
+    async fn get_blob_data(&self, key: &str) -> Result<Option<Vec<u8>>, DAError> {
+        let key_u32 = key.parse::<u32>().map_err(|err| DAError {
+            error: anyhow::Error::from(err).context(format!("Failed to parse blob key: {}", key)),
+            is_retriable: false,
+        })?;
+
+        match self
+            .object_store
+            .get::<StorablePubdata>(L1BatchNumber(key_u32))
+            .await
+        {
+            Ok(pubdata) => Ok(Some(pubdata.data)),
+            Err(zksync_object_store::ObjectStoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(DAError {
+                is_retriable: err.is_retriable(),
+                error: anyhow::Error::from(err),
+            }),
+        }
+    }
+
     fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
         Box::new(self.clone())
     }
@@ -99,6 +119,11 @@ impl DataAvailabilityClient for ObjectStoreDAClient {
         None
     }
 
+    /// Pubdata is stored by the L1 batch number.
+    fn supports_chunked_dispatch(&self) -> bool {
+        false
+    }
+
     fn client_type(&self) -> ClientType {
         ClientType::ObjectStore
     }
@@ -0,0 +1,748 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use zksync_config::configs::da_client::redundant::RedundantDABackend;
+use zksync_da_client::{
+    types::{
+        ClientType, DAError, DispatchResponse, FinalityResponse, InclusionData,
+        InclusionVerification,
+    },
+    DataAvailabilityClient,
+};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_types::L1BatchNumber;
+
+use crate::utils::{to_non_retriable_da_error, to_retriable_da_error};
+
+/// Returns the name of the backend used in the DB and in health details.
+fn backend_name(backend: RedundantDABackend) -> &'static str {
+    match backend {
+        RedundantDABackend::Avail => "avail",
+        RedundantDABackend::Celestia => "celestia",
+        RedundantDABackend::Eigen => "eigen",
+        RedundantDABackend::ObjectStore => "object_store",
+    }
+}
+
+/// Checks whether the redundancy policy (any `required_backends` of the configured backends, including
+/// the primary one if it's set) is met given the backends that have succeeded at a certain stage
+/// (dispatch, finality or inclusion).
+fn is_policy_met<'a>(
+    primary: Option<RedundantDABackend>,
+    required_backends: usize,
+    succeeded: impl IntoIterator<Item = &'a str>,
+) -> bool {
+    let succeeded: HashSet<_> = succeeded.into_iter().collect();
+    let has_primary = primary.is_none_or(|primary| succeeded.contains(backend_name(primary)));
+    has_primary && succeeded.len() >= required_backends
+}
+
+/// Blob ID returned by [`RedundantDAClient`]. The per-backend blob IDs are persisted in Postgres, so the ID only
+/// needs to reference the L1 batch.
+#[derive(Debug, Serialize, Deserialize)]
+struct RedundantBlobId {
+    l1_batch_number: L1BatchNumber,
+    /// Blob ID in the primary backend, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    primary: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct BackendHealthDetails {
+    last_l1_batch: Option<L1BatchNumber>,
+    last_error: Option<String>,
+}
+
+#[derive(Debug)]
+struct RedundantDAHealth {
+    updater: HealthUpdater,
+    backends: Mutex<HashMap<&'static str, BackendHealthDetails>>,
+}
+
+impl RedundantDAHealth {
+    fn update(&self, backend: RedundantDABackend, l1_batch: L1BatchNumber, error: Option<String>) {
+        let mut backends = self.backends.lock().expect("health details are poisoned");
+        let details = backends.entry(backend_name(backend)).or_default();
+        details.last_l1_batch = Some(l1_batch);
+        details.last_error = error;
+
+        let status = if backends
+            .values()
+            .all(|details| details.last_error.is_none())
+        {
+            HealthStatus::Ready
+        } else {
+            HealthStatus::Affected
+        };
+        self.updater
+            .update(Health::from(status).with_details(&*backends));
+    }
+}
+
+/// An implementation of the `DataAvailabilityClient` trait that dispatches each blob to several DA layers.
+///
+/// A blob is considered available once it is dispatched to, finalized and included in at least the configured
+/// number of backends, which must include the primary backend if it's set. Only the inclusion data of the primary
+/// backend is returned (and thus verified on the settlement layer); without a primary backend, the returned
+/// inclusion data is empty. The per-backend state is persisted in the `data_availability_backends` table.
+#[derive(Debug, Clone)]
+pub struct RedundantDAClient {
+    backends: Arc<[(RedundantDABackend, Box<dyn DataAvailabilityClient>)]>,
+    primary: Option<RedundantDABackend>,
+    required_backends: usize,
+    pool: ConnectionPool<Core>,
+    health: Arc<RedundantDAHealth>,
+}
+
+impl RedundantDAClient {
+    pub fn new(
+        backends: Vec<(RedundantDABackend, Box<dyn DataAvailabilityClient>)>,
+        primary: Option<RedundantDABackend>,
+        required_backends: usize,
+        pool: ConnectionPool<Core>,
+    ) -> anyhow::Result<Self> {
+        if let Some(primary) = primary {
+            anyhow::ensure!(
+                backends.iter().any(|(backend, _)| *backend == primary),
+                "primary DA backend {primary:?} is not configured"
+            );
+        }
+        anyhow::ensure!(
+            (1..=backends.len()).contains(&required_backends),
+            "required number of DA backends ({required_backends}) must be in 1..={}",
+            backends.len()
+        );
+
+        let (_, updater) = ReactiveHealthCheck::new("da_client");
+        updater.update(HealthStatus::Ready.into());
+        Ok(Self {
+            backends: backends.into(),
+            primary,
+            required_backends,
+            pool,
+            health: Arc::new(RedundantDAHealth {
+                updater,
+                backends: Mutex::default(),
+            }),
+        })
+    }
+
+    /// Returns the health check reporting the state of each backend.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health.updater.subscribe()
+    }
+
+    fn primary_client(&self) -> Option<&dyn DataAvailabilityClient> {
+        let primary = self.primary?;
+        let client = self
+            .backends
+            .iter()
+            .find_map(|(backend, client)| (*backend == primary).then_some(client.as_ref()));
+        Some(client.unwrap()) // checked in the constructor
+    }
+
+    fn client(&self, name: &str) -> Option<(RedundantDABackend, &dyn DataAvailabilityClient)> {
+        self.backends.iter().find_map(|(backend, client)| {
+            (backend_name(*backend) == name).then_some((*backend, client.as_ref()))
+        })
+    }
+
+    fn policy_error(&self, stage: &str, succeeded: usize) -> DAError {
+        let required = self.required_backends;
+        let err = match self.primary {
+            Some(primary) => anyhow::anyhow!(
+                "redundancy policy is not met for {stage}: {succeeded} backends succeeded, \
+                 {required} (including primary {primary:?}) required"
+            ),
+            None => anyhow::anyhow!(
+                "redundancy policy is not met for {stage}: {succeeded} backends succeeded, {required} required"
+            ),
+        };
+        to_retriable_da_error(err)
+    }
+}
+
+#[async_trait]
+impl DataAvailabilityClient for RedundantDAClient {
+    async fn dispatch_blob(
+        &self,
+        batch_number: u32,
+        data: Vec<u8>,
+    ) -> Result<DispatchResponse, DAError> {
+        let l1_batch = L1BatchNumber(batch_number);
+        let mut storage = self
+            .pool
+            .connection_tagged("da_client")
+            .await
+            .map_err(to_retriable_da_error)?;
+        let existing = storage
+            .data_availability_dal()
+            .get_backend_blobs(l1_batch)
+            .await
+            .map_err(to_retriable_da_error)?;
+        drop(storage);
+
+        // Backends that have already accepted the blob (e.g., before a failed attempt) are not dispatched to again.
+        let mut dispatched: HashSet<_> = existing
+            .iter()
+            .filter(|blob| blob.dispatch_request_id.is_some())
+            .map(|blob| blob.backend.clone())
+            .collect();
+        let pending = self
+            .backends
+            .iter()
+            .filter(|(backend, _)| !dispatched.contains(backend_name(*backend)));
+        let results = futures::future::join_all(pending.map(|(backend, client)| {
+            let data = data.clone();
+            async move { (*backend, client.dispatch_blob(batch_number, data).await) }
+        }))
+        .await;
+
+        let sent_at = Utc::now().naive_utc();
+        let mut storage = self
+            .pool
+            .connection_tagged("da_client")
+            .await
+            .map_err(to_retriable_da_error)?;
+        for (backend, result) in results {
+            let name = backend_name(backend);
+            let (request_id, error) = match &result {
+                Ok(response) => (Some(response.request_id.as_str()), None),
+                Err(err) => {
+                    tracing::warn!(
+                        "Failed dispatching L1 batch #{l1_batch} to DA backend `{name}`: {err}"
+                    );
+                    (None, Some(err.to_string()))
+                }
+            };
+            storage
+                .data_availability_dal()
+                .save_backend_dispatch(l1_batch, name, request_id, error.as_deref(), sent_at)
+                .await
+                .map_err(to_retriable_da_error)?;
+            self.health.update(backend, l1_batch, error);
+            if result.is_ok() {
+                dispatched.insert(name.to_owned());
+            }
+        }
+
+        if !is_policy_met(
+            self.primary,
+            self.required_backends,
+            dispatched.iter().map(String::as_str),
+        ) {
+            return Err(self.policy_error("dispatch", dispatched.len()));
+        }
+        Ok(DispatchResponse {
+            request_id: batch_number.to_string(),
+        })
+    }
+
+    async fn ensure_finality(
+        &self,
+        dispatch_request_id: String,
+        dispatched_at: DateTime<Utc>,
+    ) -> Result<Option<FinalityResponse>, DAError> {
+        let l1_batch = dispatch_request_id
+            .parse::<u32>()
+            .map(L1BatchNumber)
+            .with_context(|| format!("invalid dispatch request ID: {dispatch_request_id}"))
+            .map_err(to_non_retriable_da_error)?;
+        let mut storage = self
+            .pool
+            .connection_tagged("da_client")
+            .await
+            .map_err(to_retriable_da_error)?;
+        let blobs = storage
+            .data_availability_dal()
+            .get_backend_blobs(l1_batch)
+            .await
+            .map_err(to_retriable_da_error)?;
+
+        let mut finalized = HashMap::new();
+        for blob in blobs {
+            if let Some(blob_id) = blob.blob_id {
+                finalized.insert(blob.backend, blob_id);
+                continue;
+            }
+            let (Some(request_id), Some((backend, client))) =
+                (blob.dispatch_request_id, self.client(&blob.backend))
+            else {
+                continue;
+            };
+
+            let sent_at = blob.sent_at.unwrap_or(dispatched_at);
+            match client.ensure_finality(request_id, sent_at).await {
+                Ok(Some(response)) => {
+                    storage
+                        .data_availability_dal()
+                        .set_backend_blob_id(l1_batch, &blob.backend, &response.blob_id)
+                        .await
+                        .map_err(to_retriable_da_error)?;
+                    self.health.update(backend, l1_batch, None);
+                    finalized.insert(blob.backend, response.blob_id);
+                }
+                Ok(None) => { /* not finalized yet */ }
+                Err(err) => {
+                    tracing::warn!(
+                        "Failed checking finality of L1 batch #{l1_batch} in DA backend `{}`: {err}",
+                        blob.backend
+                    );
+                    self.health.update(backend, l1_batch, Some(err.to_string()));
+                }
+            }
+        }
+
+        if !is_policy_met(
+            self.primary,
+            self.required_backends,
+            finalized.keys().map(String::as_str),
+        ) {
+            return Ok(None);
+        }
+        let blob_id = RedundantBlobId {
+            l1_batch_number: l1_batch,
+            primary: self
+                .primary
+                .map(|primary| finalized[backend_name(primary)].clone()),
+        };
+        Ok(Some(FinalityResponse {
+            blob_id: serde_json::to_string(&blob_id).map_err(to_non_retriable_da_error)?,
+        }))
+    }
+
+    async fn get_inclusion_data(&self, blob_id: &str) -> Result<Option<InclusionData>, DAError> {
+        let blob_id: RedundantBlobId = serde_json::from_str(blob_id)
+            .with_context(|| format!("invalid blob ID: {blob_id}"))
+            .map_err(to_non_retriable_da_error)?;
+        let l1_batch = blob_id.l1_batch_number;
+        let mut storage = self
+            .pool
+            .connection_tagged("da_client")
+            .await
+            .map_err(to_retriable_da_error)?;
+        let blobs = storage
+            .data_availability_dal()
+            .get_backend_blobs(l1_batch)
+            .await
+            .map_err(to_retriable_da_error)?;
+
+        let mut included = HashMap::new();
+        for blob in blobs {
+            if let Some(inclusion_data) = blob.inclusion_data {
+                included.insert(blob.backend, inclusion_data);
+                continue;
+            }
+            let (Some(backend_blob_id), Some((backend, client))) =
+                (blob.blob_id, self.client(&blob.backend))
+            else {
+                continue;
+            };
+
+            match client.get_inclusion_data(&backend_blob_id).await {
+                Ok(Some(inclusion_data)) => {
+                    storage
+                        .data_availability_dal()
+                        .save_backend_inclusion_data(l1_batch, &blob.backend, &inclusion_data.data)
+                        .await
+                        .map_err(to_retriable_da_error)?;
+                    self.health.update(backend, l1_batch, None);
+                    included.insert(blob.backend, inclusion_data.data);
+                }
+                Ok(None) => { /* not included yet */ }
+                Err(err) => {
+                    tracing::warn!(
+                        "Failed getting inclusion data for L1 batch #{l1_batch} from DA backend `{}`: {err}",
+                        blob.backend
+                    );
+                    self.health.update(backend, l1_batch, Some(err.to_string()));
+                }
+            }
+        }
+
+        if !is_policy_met(
+            self.primary,
+            self.required_backends,
+            included.keys().map(String::as_str),
+        ) {
+            return Ok(None);
+        }
+        let data = match self.primary {
+            Some(primary) => included.remove(backend_name(primary)).unwrap(), // checked by the policy
+            // Inclusion data isn't verified on the settlement layer.
+            None => vec![],
+        };
+        Ok(Some(InclusionData { data }))
+    }
+
+    /// Verifies inclusion data of the primary backend. If the data is invalid, the primary backend blob is removed,
+    /// so that it's re-dispatched together with the L1 batch.
+    async fn verify_inclusion_data(
+        &self,
+        blob_id: &str,
+        inclusion_data: &InclusionData,
+    ) -> Result<InclusionVerification, DAError> {
+        let blob_id: RedundantBlobId = serde_json::from_str(blob_id)
+            .with_context(|| format!("invalid blob ID: {blob_id}"))
+            .map_err(to_non_retriable_da_error)?;
+        let (Some(primary), Some(client)) = (self.primary, self.primary_client()) else {
+            return Ok(InclusionVerification::Verified);
+        };
+        let primary_blob_id = blob_id
+            .primary
+            .context("blob ID doesn't contain primary backend blob ID")
+            .map_err(to_non_retriable_da_error)?;
+        let verification = client
+            .verify_inclusion_data(&primary_blob_id, inclusion_data)
+            .await?;
+
+        if matches!(verification, InclusionVerification::Invalid(_)) {
+            let l1_batch = blob_id.l1_batch_number;
+            let mut storage = self
+                .pool
+                .connection_tagged("da_client")
+                .await
+                .map_err(to_retriable_da_error)?;
+            storage
+                .data_availability_dal()
+                .remove_backend_blob(l1_batch, backend_name(primary))
+                .await
+                .map_err(to_retriable_da_error)?;
+        }
+        Ok(verification)
+    }
+
+    fn supports_inclusion_verification(&self) -> bool {
+        self.primary_client()
+            .is_some_and(|client| client.supports_inclusion_verification())
+    }
+
+    fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
+        Box::new(self.clone())
+    }
+
+    fn blob_size_limit(&self) -> Option<usize> {
+        self.backends
+            .iter()
+            .filter_map(|(_, client)| client.blob_size_limit())
+            .min()
+    }
+
+    /// Per-backend state is tracked per L1 batch, so a batch cannot be split into several blobs.
+    fn supports_chunked_dispatch(&self) -> bool {
+        false
+    }
+
+    fn client_type(&self) -> ClientType {
+        self.primary_client()
+            .map_or(ClientType::NoDA, |client| client.client_type())
+    }
+
+    /// Returns the balance of the primary backend or, if there's no primary backend, of the first configured one.
+    async fn balance(&self) -> Result<u64, DAError> {
+        let client = self
+            .primary_client()
+            .unwrap_or_else(|| self.backends[0].1.as_ref());
+        client.balance().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    use zksync_node_test_utils::create_l1_batch;
+    use zksync_types::ProtocolVersion;
+
+    use super::*;
+
+    /// Mock DA backend that can be switched to return errors from all its methods.
+    #[derive(Debug, Clone)]
+    struct MockBackend {
+        name: &'static str,
+        is_failing: Arc<AtomicBool>,
+        dispatch_count: Arc<AtomicUsize>,
+    }
+
+    impl MockBackend {
+        fn new(backend: RedundantDABackend) -> Self {
+            Self {
+                name: backend_name(backend),
+                is_failing: Arc::default(),
+                dispatch_count: Arc::default(),
+            }
+        }
+
+        fn set_failing(&self, is_failing: bool) {
+            self.is_failing.store(is_failing, Ordering::Relaxed);
+        }
+
+        fn check(&self) -> Result<(), DAError> {
+            if self.is_failing.load(Ordering::Relaxed) {
+                Err(to_retriable_da_error(anyhow::anyhow!(
+                    "{} is unavailable",
+                    self.name
+                )))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DataAvailabilityClient for MockBackend {
+        async fn dispatch_blob(
+            &self,
+            batch_number: u32,
+            _data: Vec<u8>,
+        ) -> Result<DispatchResponse, DAError> {
+            self.dispatch_count.fetch_add(1, Ordering::Relaxed);
+            self.check()?;
+            Ok(DispatchResponse {
+                request_id: format!("{}-{batch_number}", self.name),
+            })
+        }
+
+        async fn ensure_finality(
+            &self,
+            dispatch_request_id: String,
+            _dispatched_at: DateTime<Utc>,
+        ) -> Result<Option<FinalityResponse>, DAError> {
+            self.check()?;
+            Ok(Some(FinalityResponse {
+                blob_id: format!("{dispatch_request_id}-blob"),
+            }))
+        }
+
+        async fn get_inclusion_data(
+            &self,
+            _blob_id: &str,
+        ) -> Result<Option<InclusionData>, DAError> {
+            self.check()?;
+            Ok(Some(InclusionData {
+                data: self.name.as_bytes().to_vec(),
+            }))
+        }
+
+        async fn verify_inclusion_data(
+            &self,
+            _blob_id: &str,
+            inclusion_data: &InclusionData,
+        ) -> Result<InclusionVerification, DAError> {
+            self.check()?;
+            Ok(if inclusion_data.data == self.name.as_bytes() {
+                InclusionVerification::Verified
+            } else {
+                InclusionVerification::Invalid("unexpected inclusion data".into())
+            })
+        }
+
+        fn supports_inclusion_verification(&self) -> bool {
+            true
+        }
+
+        fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
+            Box::new(self.clone())
+        }
+
+        fn blob_size_limit(&self) -> Option<usize> {
+            None
+        }
+
+        fn client_type(&self) -> ClientType {
+            ClientType::ObjectStore
+        }
+
+        async fn balance(&self) -> Result<u64, DAError> {
+            Ok(0)
+        }
+    }
+
+    async fn setup(
+        backends: &[RedundantDABackend],
+        primary: Option<RedundantDABackend>,
+        required_backends: usize,
+    ) -> (RedundantDAClient, Vec<MockBackend>, ConnectionPool<Core>) {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch(1))
+            .await
+            .unwrap();
+
+        let mocks: Vec<_> = backends.iter().copied().map(MockBackend::new).collect();
+        let clients = backends
+            .iter()
+            .zip(&mocks)
+            .map(|(&backend, mock)| (backend, mock.clone_boxed()))
+            .collect();
+        let client =
+            RedundantDAClient::new(clients, primary, required_backends, pool.clone()).unwrap();
+        (client, mocks, pool)
+    }
+
+    async fn backend_errors(pool: &ConnectionPool<Core>) -> HashMap<String, Option<String>> {
+        let mut storage = pool.connection().await.unwrap();
+        let blobs = storage
+            .data_availability_dal()
+            .get_backend_blobs(L1BatchNumber(1))
+            .await
+            .unwrap();
+        blobs
+            .into_iter()
+            .map(|blob| (blob.backend, blob.error))
+            .collect()
+    }
+
+    #[test]
+    fn checking_redundancy_policy() {
+        let primary = Some(RedundantDABackend::Celestia);
+        assert!(is_policy_met(primary, 1, ["celestia"]));
+        assert!(!is_policy_met(primary, 1, ["avail"]));
+        assert!(!is_policy_met(primary, 2, ["celestia"]));
+        assert!(is_policy_met(primary, 2, ["avail", "celestia"]));
+        assert!(!is_policy_met(primary, 2, ["avail", "object_store"]));
+        assert!(is_policy_met(
+            primary,
+            2,
+            ["avail", "celestia", "object_store"]
+        ));
+
+        assert!(is_policy_met(None, 1, ["avail"]));
+        assert!(!is_policy_met(None, 2, ["avail"]));
+        assert!(is_policy_met(None, 2, ["avail", "object_store"]));
+    }
+
+    #[tokio::test]
+    async fn any_backends_satisfy_policy_without_primary() {
+        let backends = [
+            RedundantDABackend::Avail,
+            RedundantDABackend::Celestia,
+            RedundantDABackend::ObjectStore,
+        ];
+        let (client, mocks, pool) = setup(&backends, None, 2).await;
+        assert!(matches!(client.client_type(), ClientType::NoDA));
+        assert!(!client.supports_inclusion_verification());
+
+        mocks[0].set_failing(true);
+        let response = client.dispatch_blob(1, vec![1; 32]).await.unwrap();
+        let errors = backend_errors(&pool).await;
+        assert!(errors["avail"].is_some(), "{errors:?}");
+        assert_eq!(errors["celestia"], None);
+        assert_eq!(errors["object_store"], None);
+
+        // Only a single backend is finalized, which isn't enough.
+        mocks[2].set_failing(true);
+        let finality = client
+            .ensure_finality(response.request_id.clone(), Utc::now())
+            .await
+            .unwrap();
+        assert!(finality.is_none());
+
+        mocks[2].set_failing(false);
+        let finality = client
+            .ensure_finality(response.request_id, Utc::now())
+            .await
+            .unwrap()
+            .expect("blob is not finalized");
+        let blob_id: RedundantBlobId = serde_json::from_str(&finality.blob_id).unwrap();
+        assert_eq!(blob_id.l1_batch_number, L1BatchNumber(1));
+        assert_eq!(blob_id.primary, None);
+
+        let inclusion_data = client
+            .get_inclusion_data(&finality.blob_id)
+            .await
+            .unwrap()
+            .expect("no inclusion data");
+        assert!(inclusion_data.data.is_empty());
+        let verification = client
+            .verify_inclusion_data(&finality.blob_id, &inclusion_data)
+            .await
+            .unwrap();
+        assert_eq!(verification, InclusionVerification::Verified);
+    }
+
+    #[tokio::test]
+    async fn primary_failure_blocks_dispatch() {
+        let backends = [RedundantDABackend::Avail, RedundantDABackend::Celestia];
+        let primary = RedundantDABackend::Celestia;
+        let (client, mocks, pool) = setup(&backends, Some(primary), 1).await;
+
+        mocks[1].set_failing(true);
+        let Err(err) = client.dispatch_blob(1, vec![1; 32]).await else {
+            panic!("dispatch must fail if the primary backend fails");
+        };
+        assert!(err.is_retriable, "{err:?}");
+        let errors = backend_errors(&pool).await;
+        assert_eq!(errors["avail"], None);
+        assert!(errors["celestia"].is_some(), "{errors:?}");
+
+        // The backend that has accepted the blob must not be dispatched to again.
+        mocks[1].set_failing(false);
+        let response = client.dispatch_blob(1, vec![1; 32]).await.unwrap();
+        assert_eq!(mocks[0].dispatch_count.load(Ordering::Relaxed), 1);
+        assert_eq!(mocks[1].dispatch_count.load(Ordering::Relaxed), 2);
+
+        let finality = client
+            .ensure_finality(response.request_id, Utc::now())
+            .await
+            .unwrap()
+            .expect("blob is not finalized");
+        let blob_id: RedundantBlobId = serde_json::from_str(&finality.blob_id).unwrap();
+        assert_eq!(blob_id.primary.as_deref(), Some("celestia-1-blob"));
+
+        // Inclusion data must be returned for the primary backend only.
+        mocks[1].set_failing(true);
+        let inclusion_data = client.get_inclusion_data(&finality.blob_id).await.unwrap();
+        assert!(inclusion_data.is_none());
+        mocks[1].set_failing(false);
+        let inclusion_data = client
+            .get_inclusion_data(&finality.blob_id)
+            .await
+            .unwrap()
+            .expect("no inclusion data");
+        assert_eq!(inclusion_data.data, b"celestia");
+    }
+
+    #[tokio::test]
+    async fn invalid_primary_inclusion_data_removes_primary_blob() {
+        let backends = [RedundantDABackend::Avail, RedundantDABackend::Celestia];
+        let primary = RedundantDABackend::Avail;
+        let (client, _mocks, pool) = setup(&backends, Some(primary), 2).await;
+
+        let response = client.dispatch_blob(1, vec![1; 32]).await.unwrap();
+        let finality = client
+            .ensure_finality(response.request_id, Utc::now())
+            .await
+            .unwrap()
+            .expect("blob is not finalized");
+        let invalid_data = InclusionData {
+            data: b"celestia".to_vec(),
+        };
+        let verification = client
+            .verify_inclusion_data(&finality.blob_id, &invalid_data)
+            .await
+            .unwrap();
+        assert!(
+            matches!(verification, InclusionVerification::Invalid(_)),
+            "{verification:?}"
+        );
+
+        let errors = backend_errors(&pool).await;
+        assert!(!errors.contains_key("avail"), "{errors:?}");
+        assert!(errors.contains_key("celestia"), "{errors:?}");
+    }
+}
@@ -1,4 +1,11 @@
+use anyhow::Context as _;
 use zksync_da_client::types::DAError;
+use zksync_eth_client::EthInterface;
+use zksync_types::{
+    ethabi::{self, ParamType, Token},
+    web3::CallRequest,
+    Address,
+};
 
 pub fn to_non_retriable_da_error(error: impl Into<anyhow::Error>) -> DAError {
     DAError {
@@ -13,3 +20,27 @@ pub fn to_retriable_da_error(error: impl Into<anyhow::Error>) -> DAError {
         is_retriable: true,
     }
 }
+
+/// Calls a view function of a contract via `eth_call` on the latest block and decodes its output.
+/// Used to read DA layer attestations from the settlement layer without loading full contract ABIs.
+pub(crate) async fn call_view_function(
+    client: &dyn EthInterface,
+    contract_address: Address,
+    (function_name, params): (&str, &[ParamType]),
+    args: &[Token],
+    outputs: &[ParamType],
+) -> anyhow::Result<Vec<Token>> {
+    let mut data = ethabi::short_signature(function_name, params).to_vec();
+    data.extend(ethabi::encode(args));
+    let request = CallRequest {
+        to: Some(contract_address),
+        data: Some(data.into()),
+        ..CallRequest::default()
+    };
+    let output = client
+        .call_contract_function(request, None)
+        .await
+        .with_context(|| format!("failed calling `{function_name}` on {contract_address:?}"))?;
+    ethabi::decode(outputs, &output.0)
+        .with_context(|| format!("failed decoding output of `{function_name}`"))
+}
@@ -6,13 +6,16 @@ use rand::Rng;
 use tokio::sync::watch::Receiver;
 use zksync_config::{configs::contracts::chain::L2Contracts, DADispatcherConfig};
 use zksync_da_client::{
-    types::{DAError, InclusionData},
+    chunking::{combine_inclusion_data, decode_chunked_id, encode_chunked_id, split_blob},
+    types::{DAError, InclusionData, InclusionVerification},
     DataAvailabilityClient,
 };
 use zksync_dal::{ConnectionPool, Core, CoreDal};
-use zksync_types::{l2_to_l1_log::L2ToL1Log, Address, L1BatchNumber, H256};
+use zksync_types::{
+    l2_to_l1_log::L2ToL1Log, pubdata_da::DataAvailabilityBlob, Address, L1BatchNumber, H256,
+};
 
-use crate::metrics::METRICS;
+use crate::metrics::{InclusionVerificationResult, METRICS};
 
 #[derive(Debug, Clone)]
 pub struct DataAvailabilityDispatcher {
@@ -136,23 +139,29 @@ impl DataAvailabilityDispatcher {
 
         for batch in &batches {
             let dispatch_latency = METRICS.blob_dispatch_latency.start();
-            let dispatch_response = retry(
-                self.config.max_retries,
-                batch.l1_batch_number,
-                "DA dispatch",
-                || {
-                    self.client
-                        .dispatch_blob(batch.l1_batch_number.0, batch.pubdata.clone())
-                },
-            )
-            .await
-            .with_context(|| {
-                format!(
-                    "failed to dispatch a blob with batch_number: {}, pubdata_len: {}",
+            let dispatch_request_id = if let Some(limit) = self.chunk_size(batch.pubdata.len()) {
+                self.dispatch_chunks(batch.l1_batch_number, &batch.pubdata, limit)
+                    .await?
+            } else {
+                retry(
+                    self.config.max_retries,
                     batch.l1_batch_number,
-                    batch.pubdata.len()
+                    "DA dispatch",
+                    || {
+                        self.client
+                            .dispatch_blob(batch.l1_batch_number.0, batch.pubdata.clone())
+                    },
                 )
-            })?;
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed to dispatch a blob with batch_number: {}, pubdata_len: {}",
+                        batch.l1_batch_number,
+                        batch.pubdata.len()
+                    )
+                })?
+                .request_id
+            };
             let dispatch_latency_duration = dispatch_latency.observe();
 
             let sent_at = Utc::now();
@@ -161,7 +170,7 @@ impl DataAvailabilityDispatcher {
             conn.data_availability_dal()
                 .insert_l1_batch_da_request_id(
                     batch.l1_batch_number,
-                    dispatch_response.request_id.as_str(),
+                    dispatch_request_id.as_str(),
                     sent_at.naive_utc(),
                     self.client.client_type().into_pubdata_type(),
                     Some(find_l2_da_validator_address(batch.system_logs.as_slice())?),
@@ -210,6 +219,88 @@ impl DataAvailabilityDispatcher {
         Ok(())
     }
 
+    /// Returns the size of chunks if the pubdata of the specified length should be split into several blobs.
+    fn chunk_size(&self, pubdata_len: usize) -> Option<usize> {
+        if !self.config.split_oversized_blobs {
+            return None;
+        }
+        self.client
+            .blob_size_limit()
+            .filter(|&limit| pubdata_len > limit)
+    }
+
+    /// Dispatches chunks of the pubdata exceeding the blob size limit, skipping chunks dispatched previously
+    /// (e.g., before a restart). Returns the combined dispatch request ID.
+    async fn dispatch_chunks(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        pubdata: &[u8],
+        chunk_size: usize,
+    ) -> anyhow::Result<String> {
+        let chunks = split_blob(pubdata, chunk_size);
+        let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
+        let dispatched_chunks = conn
+            .data_availability_dal()
+            .get_l1_batch_da_chunks(l1_batch_number)
+            .await?;
+        drop(conn);
+
+        let mut request_ids = vec![None; chunks.len()];
+        for chunk in dispatched_chunks {
+            let request_id = request_ids.get_mut(chunk.chunk_index).with_context(|| {
+                format!(
+                    "unexpected DA chunk #{} for batch_number: {}",
+                    chunk.chunk_index, l1_batch_number
+                )
+            })?;
+            *request_id = Some(chunk.dispatch_request_id);
+        }
+
+        let chunk_count = chunks.len();
+        for (chunk_index, chunk) in chunks.into_iter().enumerate() {
+            if request_ids[chunk_index].is_some() {
+                continue;
+            }
+
+            let dispatch_response = retry(
+                self.config.max_retries,
+                l1_batch_number,
+                "DA chunk dispatch",
+                || {
+                    self.client
+                        .dispatch_blob(l1_batch_number.0, chunk.clone())
+                },
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to dispatch chunk {chunk_index}/{chunk_count} with batch_number: {}, chunk_len: {}",
+                    l1_batch_number,
+                    chunk.len()
+                )
+            })?;
+
+            let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
+            conn.data_availability_dal()
+                .insert_l1_batch_da_chunk_request_id(
+                    l1_batch_number,
+                    chunk_index,
+                    &dispatch_response.request_id,
+                    Utc::now().naive_utc(),
+                )
+                .await?;
+            drop(conn);
+            request_ids[chunk_index] = Some(dispatch_response.request_id);
+        }
+
+        tracing::info!(
+            "Dispatched {chunk_count} chunks for batch_number: {}, chunk_size: {chunk_size}",
+            l1_batch_number
+        );
+        let request_ids: Vec<_> = request_ids.into_iter().flatten().collect();
+        Ok(encode_chunked_id(&request_ids))
+    }
+
     async fn ensure_finality(&self) -> anyhow::Result<()> {
         let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
         let blob = conn
@@ -222,6 +313,10 @@ impl DataAvailabilityDispatcher {
             return Ok(());
         };
 
+        if decode_chunked_id(&blob.dispatch_request_id)?.is_some() {
+            return self.ensure_chunks_finality(&blob).await;
+        }
+
         // TODO: add metrics for finality latency
         let finality_response = self
             .client
@@ -261,6 +356,83 @@ impl DataAvailabilityDispatcher {
         Ok(())
     }
 
+    /// Checks finality of all chunks of a split blob. The blob is considered final once all its chunks are final.
+    async fn ensure_chunks_finality(&self, blob: &DataAvailabilityBlob) -> anyhow::Result<()> {
+        let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
+        let chunks = conn
+            .data_availability_dal()
+            .get_l1_batch_da_chunks(blob.l1_batch_number)
+            .await?;
+        drop(conn);
+
+        let mut blob_ids = Vec::with_capacity(chunks.len());
+        for chunk in chunks {
+            if let Some(blob_id) = chunk.blob_id {
+                blob_ids.push(blob_id);
+                continue;
+            }
+
+            let finality_response = self
+                .client
+                .ensure_finality(chunk.dispatch_request_id, chunk.sent_at)
+                .await;
+            match finality_response {
+                Ok(None) => {
+                    // Not final yet, do nothing
+                }
+                Ok(Some(finality_response)) => {
+                    let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
+                    conn.data_availability_dal()
+                        .set_chunk_blob_id(
+                            blob.l1_batch_number,
+                            chunk.chunk_index,
+                            &finality_response.blob_id,
+                        )
+                        .await?;
+                    blob_ids.push(finality_response.blob_id);
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        "Finality check for chunk {} of a batch_number: {} failed with an error: {}",
+                        chunk.chunk_index,
+                        blob.l1_batch_number,
+                        err.error
+                    );
+
+                    // remove the chunk and the combined entry from the database to resend the chunk again
+                    let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
+                    let mut transaction = conn.start_transaction().await?;
+                    transaction
+                        .data_availability_dal()
+                        .remove_data_availability_chunk(blob.l1_batch_number, chunk.chunk_index)
+                        .await?;
+                    transaction
+                        .data_availability_dal()
+                        .remove_data_availability_entry(blob.l1_batch_number)
+                        .await?;
+                    transaction.commit().await?;
+                    return Ok(());
+                }
+            }
+        }
+
+        let chunk_request_ids = decode_chunked_id(&blob.dispatch_request_id)?.unwrap_or_default();
+        if blob_ids.len() < chunk_request_ids.len() {
+            return Ok(());
+        }
+
+        let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
+        conn.data_availability_dal()
+            .set_blob_id(blob.l1_batch_number, &encode_chunked_id(&blob_ids))
+            .await?;
+        tracing::info!(
+            "Finality check for all {} chunks of a batch_number: {} is successful",
+            blob_ids.len(),
+            blob.l1_batch_number
+        );
+        Ok(())
+    }
+
     /// Polls the data availability layer for inclusion data, and saves it in the database.
     async fn poll_for_inclusion(&self) -> anyhow::Result<()> {
         if self.config.inclusion_verification_transition_enabled {
@@ -302,15 +474,33 @@ impl DataAvailabilityDispatcher {
                 );
             };
 
-            self.client
-                .get_inclusion_data(blob_id.as_str())
-                .await
-                .with_context(|| {
-                    format!(
-                        "failed to get inclusion data for blob_id: {}, batch_number: {}",
-                        blob_id, blob_info.l1_batch_number
-                    )
-                })?
+            if decode_chunked_id(&blob_id)?.is_some() {
+                self.get_chunks_inclusion_data(blob_info.l1_batch_number)
+                    .await?
+            } else {
+                let inclusion_data = self
+                    .client
+                    .get_inclusion_data(blob_id.as_str())
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "failed to get inclusion data for blob_id: {}, batch_number: {}",
+                            blob_id, blob_info.l1_batch_number
+                        )
+                    })?;
+                match inclusion_data {
+                    Some(inclusion_data) if self.config.verify_inclusion_data => {
+                        self.verify_inclusion_data(
+                            blob_info.l1_batch_number,
+                            None,
+                            &blob_id,
+                            inclusion_data,
+                        )
+                        .await?
+                    }
+                    inclusion_data => inclusion_data,
+                }
+            }
         };
 
         let Some(inclusion_data) = inclusion_data else {
@@ -343,7 +533,140 @@ impl DataAvailabilityDispatcher {
         Ok(())
     }
 
+    /// Gets inclusion data for all chunks of a split blob, and combines it once it's available for all chunks.
+    async fn get_chunks_inclusion_data(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<InclusionData>> {
+        let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
+        let chunks = conn
+            .data_availability_dal()
+            .get_l1_batch_da_chunks(l1_batch_number)
+            .await?;
+        drop(conn);
+
+        let mut chunks_inclusion_data = Vec::with_capacity(chunks.len());
+        for chunk in chunks {
+            if let Some(inclusion_data) = chunk.inclusion_data {
+                chunks_inclusion_data.push(inclusion_data);
+                continue;
+            }
+            let blob_id = chunk.blob_id.with_context(|| {
+                format!(
+                    "Blob ID is not set for chunk {} of batch_number: {l1_batch_number}",
+                    chunk.chunk_index
+                )
+            })?;
+
+            let inclusion_data = self
+                .client
+                .get_inclusion_data(&blob_id)
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed to get inclusion data for blob_id: {blob_id}, chunk: {}, batch_number: {l1_batch_number}",
+                        chunk.chunk_index
+                    )
+                })?;
+            let Some(mut inclusion_data) = inclusion_data else {
+                return Ok(None);
+            };
+            if self.config.verify_inclusion_data {
+                let verified = self
+                    .verify_inclusion_data(
+                        l1_batch_number,
+                        Some(chunk.chunk_index),
+                        &blob_id,
+                        inclusion_data,
+                    )
+                    .await?;
+                let Some(verified) = verified else {
+                    return Ok(None);
+                };
+                inclusion_data = verified;
+            }
+
+            let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
+            conn.data_availability_dal()
+                .save_chunk_inclusion_data(l1_batch_number, chunk.chunk_index, &inclusion_data.data)
+                .await?;
+            drop(conn);
+            chunks_inclusion_data.push(inclusion_data.data);
+        }
+
+        Ok(Some(InclusionData {
+            data: combine_inclusion_data(chunks_inclusion_data),
+        }))
+    }
+
+    /// Verifies inclusion data for a blob (or a chunk of a split blob) against the DA layer attestations.
+    /// Returns the inclusion data if it's verified. If the inclusion data is invalid, the blob (or the chunk)
+    /// is removed from the database, so that it's dispatched again.
+    async fn verify_inclusion_data(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        chunk_index: Option<usize>,
+        blob_id: &str,
+        inclusion_data: InclusionData,
+    ) -> anyhow::Result<Option<InclusionData>> {
+        let latency = METRICS.inclusion_verification_latency.start();
+        let verification = self
+            .client
+            .verify_inclusion_data(blob_id, &inclusion_data)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to verify inclusion data for blob_id: {blob_id}, batch_number: {l1_batch_number}"
+                )
+            })?;
+        latency.observe();
+
+        match verification {
+            InclusionVerification::Verified => {
+                METRICS.inclusion_verifications[&InclusionVerificationResult::Verified].inc();
+                Ok(Some(inclusion_data))
+            }
+            InclusionVerification::Pending => {
+                METRICS.inclusion_verifications[&InclusionVerificationResult::Pending].inc();
+                tracing::info!(
+                    "Inclusion data for blob_id: {blob_id}, batch_number: {l1_batch_number} is not attested yet"
+                );
+                Ok(None)
+            }
+            InclusionVerification::Invalid(reason) => {
+                METRICS.inclusion_verifications[&InclusionVerificationResult::Invalid].inc();
+                tracing::warn!(
+                    "Inclusion data for blob_id: {blob_id}, batch_number: {l1_batch_number} is invalid: {reason}; \
+                     the blob will be dispatched again"
+                );
+
+                let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
+                let mut transaction = conn.start_transaction().await?;
+                if let Some(chunk_index) = chunk_index {
+                    transaction
+                        .data_availability_dal()
+                        .remove_data_availability_chunk(l1_batch_number, chunk_index)
+                        .await?;
+                }
+                transaction
+                    .data_availability_dal()
+                    .remove_data_availability_entry(l1_batch_number)
+                    .await?;
+                transaction.commit().await?;
+                Ok(None)
+            }
+        }
+    }
+
     async fn check_for_misconfiguration(&mut self) -> anyhow::Result<()> {
+        if self.config.verify_inclusion_data
+            && !self.config.use_dummy_inclusion_data
+            && !self.client.supports_inclusion_verification()
+        {
+            anyhow::bail!(
+                "verifying inclusion data is enabled, but it is not supported by the DA client"
+            );
+        }
         if self.config.inclusion_verification_transition_enabled {
             self.transitional_l2_da_validator_address = Some(
                 self.l2_contracts
@@ -408,3 +731,538 @@ pub fn find_l2_da_validator_address(system_logs: &[L2ToL1Log]) -> anyhow::Result
         .value
         .into())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashSet,
+        sync::{Arc, Mutex},
+    };
+
+    use async_trait::async_trait;
+    use chrono::DateTime;
+    use test_casing::test_casing;
+    use zksync_da_client::{
+        chunking::split_inclusion_data,
+        types::{ClientType, DispatchResponse, FinalityResponse},
+    };
+    use zksync_node_test_utils::create_l1_batch;
+    use zksync_types::{
+        l2_to_l1_log::SystemL2ToL1Log, pubdata_da::DataAvailabilityDetails, ProtocolVersion,
+    };
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct MockDAState {
+        dispatched_blobs: Vec<Vec<u8>>,
+        invalid_blob_ids: HashSet<String>,
+        /// IDs of blobs that are neither final nor included yet.
+        pending_blob_ids: HashSet<String>,
+    }
+
+    /// DA client that uses dispatch request IDs as blob IDs and blob IDs as inclusion data.
+    #[derive(Debug, Clone, Default)]
+    struct MockDAClient {
+        state: Arc<Mutex<MockDAState>>,
+        blob_size_limit: Option<usize>,
+    }
+
+    impl MockDAClient {
+        fn mark_invalid(&self, blob_id: &str) {
+            let mut state = self.state.lock().unwrap();
+            state.invalid_blob_ids.insert(blob_id.to_owned());
+        }
+
+        fn set_pending(&self, blob_ids: &[&str]) {
+            let mut state = self.state.lock().unwrap();
+            state.pending_blob_ids = blob_ids.iter().map(|&id| id.to_owned()).collect();
+        }
+
+        fn dispatched_blobs(&self) -> Vec<Vec<u8>> {
+            self.state.lock().unwrap().dispatched_blobs.clone()
+        }
+    }
+
+    #[async_trait]
+    impl DataAvailabilityClient for MockDAClient {
+        async fn dispatch_blob(
+            &self,
+            _batch_number: u32,
+            data: Vec<u8>,
+        ) -> Result<DispatchResponse, DAError> {
+            let mut state = self.state.lock().unwrap();
+            let request_id = format!("blob{}", state.dispatched_blobs.len());
+            state.dispatched_blobs.push(data);
+            Ok(DispatchResponse { request_id })
+        }
+
+        async fn ensure_finality(
+            &self,
+            dispatch_request_id: String,
+            _dispatched_at: DateTime<Utc>,
+        ) -> Result<Option<FinalityResponse>, DAError> {
+            let state = self.state.lock().unwrap();
+            if state.pending_blob_ids.contains(&dispatch_request_id) {
+                return Ok(None);
+            }
+            Ok(Some(FinalityResponse {
+                blob_id: dispatch_request_id,
+            }))
+        }
+
+        async fn get_inclusion_data(
+            &self,
+            blob_id: &str,
+        ) -> Result<Option<InclusionData>, DAError> {
+            let state = self.state.lock().unwrap();
+            if state.pending_blob_ids.contains(blob_id) {
+                return Ok(None);
+            }
+            Ok(Some(InclusionData {
+                data: blob_id.as_bytes().to_vec(),
+            }))
+        }
+
+        async fn verify_inclusion_data(
+            &self,
+            blob_id: &str,
+            inclusion_data: &InclusionData,
+        ) -> Result<InclusionVerification, DAError> {
+            assert_eq!(inclusion_data.data, blob_id.as_bytes());
+            let state = self.state.lock().unwrap();
+            Ok(if state.invalid_blob_ids.contains(blob_id) {
+                InclusionVerification::Invalid("test".to_owned())
+            } else {
+                InclusionVerification::Verified
+            })
+        }
+
+        fn supports_inclusion_verification(&self) -> bool {
+            true
+        }
+
+        fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
+            Box::new(self.clone())
+        }
+
+        fn blob_size_limit(&self) -> Option<usize> {
+            self.blob_size_limit
+        }
+
+        fn client_type(&self) -> ClientType {
+            ClientType::ObjectStore
+        }
+
+        async fn balance(&self) -> Result<u64, DAError> {
+            Ok(0)
+        }
+    }
+
+    fn mock_l2_contracts() -> L2Contracts {
+        L2Contracts {
+            erc20_default_bridge: Address::repeat_byte(1),
+            shared_bridge_addr: Address::repeat_byte(2),
+            legacy_shared_bridge_addr: None,
+            timestamp_asserter_addr: None,
+            da_validator_addr: None,
+            testnet_paymaster_addr: None,
+            multicall3: None,
+        }
+    }
+
+    async fn insert_l1_batch_with_pubdata(pool: &ConnectionPool<Core>, pubdata: Vec<u8>) {
+        let mut storage = pool.connection().await.unwrap();
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        let mut header = create_l1_batch(1);
+        header.pubdata_input = Some(pubdata);
+        header.system_logs.push(SystemL2ToL1Log(L2ToL1Log {
+            shard_id: 0,
+            is_service: true,
+            tx_number_in_block: 0,
+            sender: Address::zero(),
+            key: H256::from_low_u64_be(u64::from(
+                zksync_system_constants::L2_DA_VALIDATOR_OUTPUT_HASH_KEY,
+            )),
+            value: H256::from(Address::repeat_byte(0x23)),
+        }));
+        storage
+            .blocks_dal()
+            .insert_mock_l1_batch(&header)
+            .await
+            .unwrap();
+    }
+
+    async fn run_iteration(dispatcher: &DataAvailabilityDispatcher) {
+        dispatcher.dispatch().await.unwrap();
+        dispatcher.ensure_finality().await.unwrap();
+        dispatcher.poll_for_inclusion().await.unwrap();
+    }
+
+    async fn get_da_details(pool: &ConnectionPool<Core>) -> DataAvailabilityDetails {
+        let mut storage = pool.connection().await.unwrap();
+        storage
+            .data_availability_dal()
+            .get_da_details_by_batch_number(L1BatchNumber(1))
+            .await
+            .unwrap()
+            .expect("no DA details")
+    }
+
+    fn chunked_id(ids: &[&str]) -> String {
+        let ids: Vec<_> = ids.iter().map(|&id| id.to_owned()).collect();
+        encode_chunked_id(&ids)
+    }
+
+    fn combined_inclusion_data(blob_ids: &[&str]) -> Vec<u8> {
+        let chunks_inclusion_data = blob_ids.iter().map(|id| id.as_bytes().to_vec()).collect();
+        combine_inclusion_data(chunks_inclusion_data)
+    }
+
+    async fn ready_for_dispatch(pool: &ConnectionPool<Core>) -> Vec<L1BatchNumber> {
+        let mut storage = pool.connection().await.unwrap();
+        let batches = storage
+            .data_availability_dal()
+            .get_ready_for_da_dispatch_l1_batches(10)
+            .await
+            .unwrap();
+        batches.iter().map(|batch| batch.l1_batch_number).collect()
+    }
+
+    #[tokio::test]
+    async fn invalid_inclusion_data_leads_to_redispatch() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let pubdata = vec![1; 100];
+        insert_l1_batch_with_pubdata(&pool, pubdata.clone()).await;
+
+        let client = MockDAClient::default();
+        client.mark_invalid("blob0");
+        let config = DADispatcherConfig {
+            verify_inclusion_data: true,
+            ..DADispatcherConfig::default()
+        };
+        let dispatcher = DataAvailabilityDispatcher::new(
+            pool.clone(),
+            config,
+            Box::new(client.clone()),
+            mock_l2_contracts(),
+        );
+
+        run_iteration(&dispatcher).await;
+        assert_eq!(client.dispatched_blobs(), [pubdata.clone()]);
+        // The DA entry must be removed, so that the batch is dispatched again.
+        assert_eq!(ready_for_dispatch(&pool).await, [L1BatchNumber(1)]);
+        let mut storage = pool.connection().await.unwrap();
+        let details = storage
+            .data_availability_dal()
+            .get_da_details_by_batch_number(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert!(details.is_none(), "{details:?}");
+        drop(storage);
+
+        run_iteration(&dispatcher).await;
+        assert_eq!(client.dispatched_blobs(), [pubdata.clone(), pubdata]);
+        assert!(ready_for_dispatch(&pool).await.is_empty());
+        let mut storage = pool.connection().await.unwrap();
+        let details = storage
+            .data_availability_dal()
+            .get_da_details_by_batch_number(L1BatchNumber(1))
+            .await
+            .unwrap()
+            .expect("no DA details");
+        assert_eq!(details.blob_id, "blob1");
+        assert_eq!(details.inclusion_data.unwrap(), b"blob1");
+    }
+
+    #[tokio::test]
+    async fn invalid_chunk_inclusion_data_leads_to_chunk_redispatch() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let pubdata: Vec<_> = (0..100).collect();
+        insert_l1_batch_with_pubdata(&pool, pubdata.clone()).await;
+
+        let client = MockDAClient {
+            blob_size_limit: Some(60),
+            ..MockDAClient::default()
+        };
+        client.mark_invalid("blob1");
+        let config = DADispatcherConfig {
+            verify_inclusion_data: true,
+            split_oversized_blobs: true,
+            ..DADispatcherConfig::default()
+        };
+        let dispatcher = DataAvailabilityDispatcher::new(
+            pool.clone(),
+            config,
+            Box::new(client.clone()),
+            mock_l2_contracts(),
+        );
+
+        run_iteration(&dispatcher).await;
+        let chunks = split_blob(&pubdata, 60);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(client.dispatched_blobs(), chunks);
+        assert_eq!(ready_for_dispatch(&pool).await, [L1BatchNumber(1)]);
+        // Only the invalid chunk must be removed.
+        let mut storage = pool.connection().await.unwrap();
+        let stored_chunks = storage
+            .data_availability_dal()
+            .get_l1_batch_da_chunks(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(stored_chunks.len(), 1);
+        assert_eq!(stored_chunks[0].chunk_index, 0);
+        assert_eq!(
+            stored_chunks[0].inclusion_data.as_deref(),
+            Some(&b"blob0"[..])
+        );
+        drop(storage);
+
+        run_iteration(&dispatcher).await;
+        let dispatched_blobs = client.dispatched_blobs();
+        assert_eq!(dispatched_blobs.len(), 3);
+        assert_eq!(dispatched_blobs[2], chunks[1]);
+        assert!(ready_for_dispatch(&pool).await.is_empty());
+        let mut storage = pool.connection().await.unwrap();
+        let details = storage
+            .data_availability_dal()
+            .get_da_details_by_batch_number(L1BatchNumber(1))
+            .await
+            .unwrap()
+            .expect("no DA details");
+        assert_eq!(
+            details.blob_id,
+            encode_chunked_id(&["blob0".to_owned(), "blob2".to_owned()])
+        );
+        let expected_inclusion_data =
+            combine_inclusion_data(vec![b"blob0".to_vec(), b"blob2".to_vec()]);
+        assert_eq!(details.inclusion_data.unwrap(), expected_inclusion_data);
+    }
+
+    #[tokio::test]
+    async fn oversized_blob_is_split_into_chunks() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let pubdata: Vec<_> = (0..=255).collect();
+        insert_l1_batch_with_pubdata(&pool, pubdata.clone()).await;
+
+        let client = MockDAClient {
+            blob_size_limit: Some(60),
+            ..MockDAClient::default()
+        };
+        let config = DADispatcherConfig {
+            verify_inclusion_data: true,
+            split_oversized_blobs: true,
+            ..DADispatcherConfig::default()
+        };
+        let dispatcher = DataAvailabilityDispatcher::new(
+            pool.clone(),
+            config,
+            Box::new(client.clone()),
+            mock_l2_contracts(),
+        );
+
+        dispatcher.dispatch().await.unwrap();
+        let dispatched_blobs = client.dispatched_blobs();
+        assert_eq!(dispatched_blobs.len(), 5);
+        assert!(dispatched_blobs.iter().all(|blob| blob.len() <= 60));
+        assert_eq!(dispatched_blobs.concat(), pubdata);
+        assert!(ready_for_dispatch(&pool).await.is_empty());
+
+        let mut storage = pool.connection().await.unwrap();
+        let stored_chunks = storage
+            .data_availability_dal()
+            .get_l1_batch_da_chunks(L1BatchNumber(1))
+            .await
+            .unwrap();
+        drop(storage);
+        let request_ids: Vec<_> = stored_chunks
+            .iter()
+            .map(|chunk| (chunk.chunk_index, chunk.dispatch_request_id.as_str()))
+            .collect();
+        assert_eq!(
+            request_ids,
+            [
+                (0, "blob0"),
+                (1, "blob1"),
+                (2, "blob2"),
+                (3, "blob3"),
+                (4, "blob4")
+            ]
+        );
+
+        dispatcher.ensure_finality().await.unwrap();
+        dispatcher.poll_for_inclusion().await.unwrap();
+        let blob_ids = ["blob0", "blob1", "blob2", "blob3", "blob4"];
+        let details = get_da_details(&pool).await;
+        assert_eq!(details.blob_id, chunked_id(&blob_ids));
+        assert_eq!(
+            details.inclusion_data.unwrap(),
+            combined_inclusion_data(&blob_ids)
+        );
+    }
+
+    #[test_casing(2, [false, true])]
+    #[tokio::test]
+    async fn blob_is_not_split_if_not_oversized_or_disabled(split_oversized_blobs: bool) {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let pubdata = vec![1; 100];
+        insert_l1_batch_with_pubdata(&pool, pubdata.clone()).await;
+
+        let client = MockDAClient {
+            // If splitting is enabled, the blob size limit is not exceeded.
+            blob_size_limit: Some(if split_oversized_blobs { 100 } else { 60 }),
+            ..MockDAClient::default()
+        };
+        let config = DADispatcherConfig {
+            split_oversized_blobs,
+            ..DADispatcherConfig::default()
+        };
+        let dispatcher = DataAvailabilityDispatcher::new(
+            pool.clone(),
+            config,
+            Box::new(client.clone()),
+            mock_l2_contracts(),
+        );
+
+        run_iteration(&dispatcher).await;
+        assert_eq!(client.dispatched_blobs(), [pubdata]);
+        let mut storage = pool.connection().await.unwrap();
+        let stored_chunks = storage
+            .data_availability_dal()
+            .get_l1_batch_da_chunks(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert!(stored_chunks.is_empty());
+        drop(storage);
+        let details = get_da_details(&pool).await;
+        assert_eq!(details.blob_id, "blob0");
+        assert_eq!(details.inclusion_data.unwrap(), b"blob0");
+    }
+
+    #[tokio::test]
+    async fn partially_dispatched_chunks_are_resumed_after_restart() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let pubdata: Vec<_> = (0..=255).collect();
+        insert_l1_batch_with_pubdata(&pool, pubdata.clone()).await;
+
+        // Emulate a dispatcher that has dispatched the first 2 chunks before a restart.
+        let mut storage = pool.connection().await.unwrap();
+        let sent_at = Utc::now().naive_utc();
+        for (chunk_index, request_id) in ["old0", "old1"].into_iter().enumerate() {
+            storage
+                .data_availability_dal()
+                .insert_l1_batch_da_chunk_request_id(
+                    L1BatchNumber(1),
+                    chunk_index,
+                    request_id,
+                    sent_at,
+                )
+                .await
+                .unwrap();
+        }
+        drop(storage);
+        assert_eq!(ready_for_dispatch(&pool).await, [L1BatchNumber(1)]);
+
+        let client = MockDAClient {
+            blob_size_limit: Some(60),
+            ..MockDAClient::default()
+        };
+        let config = DADispatcherConfig {
+            verify_inclusion_data: true,
+            split_oversized_blobs: true,
+            ..DADispatcherConfig::default()
+        };
+        let dispatcher = DataAvailabilityDispatcher::new(
+            pool.clone(),
+            config,
+            Box::new(client.clone()),
+            mock_l2_contracts(),
+        );
+
+        run_iteration(&dispatcher).await;
+        // Only the remaining chunks must be dispatched.
+        let chunks = split_blob(&pubdata, 60);
+        assert_eq!(client.dispatched_blobs(), chunks[2..]);
+        assert!(ready_for_dispatch(&pool).await.is_empty());
+
+        let blob_ids = ["old0", "old1", "blob0", "blob1", "blob2"];
+        let details = get_da_details(&pool).await;
+        assert_eq!(details.blob_id, chunked_id(&blob_ids));
+        assert_eq!(
+            details.inclusion_data.unwrap(),
+            combined_inclusion_data(&blob_ids)
+        );
+    }
+
+    #[tokio::test]
+    async fn chunk_inclusion_data_is_combined_in_chunk_order() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let pubdata: Vec<_> = (0..150).collect();
+        insert_l1_batch_with_pubdata(&pool, pubdata).await;
+
+        let client = MockDAClient {
+            blob_size_limit: Some(60),
+            ..MockDAClient::default()
+        };
+        let config = DADispatcherConfig {
+            verify_inclusion_data: true,
+            split_oversized_blobs: true,
+            ..DADispatcherConfig::default()
+        };
+        let dispatcher = DataAvailabilityDispatcher::new(
+            pool.clone(),
+            config,
+            Box::new(client.clone()),
+            mock_l2_contracts(),
+        );
+
+        // The first chunk becomes final after the other chunks.
+        client.set_pending(&["blob0"]);
+        run_iteration(&dispatcher).await;
+        let mut storage = pool.connection().await.unwrap();
+        let stored_chunks = storage
+            .data_availability_dal()
+            .get_l1_batch_da_chunks(L1BatchNumber(1))
+            .await
+            .unwrap();
+        let blob_ids: Vec<_> = stored_chunks
+            .iter()
+            .map(|chunk| chunk.blob_id.as_deref())
+            .collect();
+        assert_eq!(blob_ids, [None, Some("blob1"), Some("blob2")]);
+        // The blob isn't final until all its chunks are final.
+        let details = storage
+            .data_availability_dal()
+            .get_da_details_by_batch_number(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert!(details.is_none(), "{details:?}");
+        drop(storage);
+
+        // The middle chunk is included after the other chunks.
+        client.set_pending(&["blob1"]);
+        run_iteration(&dispatcher).await;
+        let details = get_da_details(&pool).await;
+        assert_eq!(details.blob_id, chunked_id(&["blob0", "blob1", "blob2"]));
+        assert!(details.inclusion_data.is_none());
+
+        client.set_pending(&[]);
+        run_iteration(&dispatcher).await;
+        let details = get_da_details(&pool).await;
+        let inclusion_data = details.inclusion_data.unwrap();
+        assert_eq!(
+            inclusion_data,
+            combined_inclusion_data(&["blob0", "blob1", "blob2"])
+        );
+        let chunks_inclusion_data = split_inclusion_data(&inclusion_data).unwrap();
+        assert_eq!(
+            chunks_inclusion_data,
+            [b"blob0".to_vec(), b"blob1".to_vec(), b"blob2".to_vec()]
+        );
+        assert_eq!(client.dispatched_blobs().len(), 3);
+    }
+}
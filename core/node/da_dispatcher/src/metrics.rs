@@ -1,11 +1,22 @@
 use std::time::Duration;
 
-use vise::{Buckets, Gauge, Histogram, Metrics, Unit};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit,
+};
 
 /// Buckets for `blob_dispatch_latency` (from 0.1 to 120 seconds).
 const DISPATCH_LATENCIES: Buckets =
     Buckets::values(&[0.1, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0, 60.0, 120.0, 240.0]);
 
+/// Outcome of verifying inclusion data against the DA layer attestations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "result", rename_all = "snake_case")]
+pub(super) enum InclusionVerificationResult {
+    Verified,
+    Pending,
+    Invalid,
+}
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "server_da_dispatcher")]
 pub(super) struct DataAvailabilityDispatcherMetrics {
@@ -32,6 +43,11 @@ pub(super) struct DataAvailabilityDispatcherMetrics {
     pub sealed_to_dispatched_lag: Histogram<Duration>,
     /// The balance of the operator wallet on DA network.
     pub operator_balance: Gauge<u64>,
+    /// Latency of verifying inclusion data against the DA layer attestations.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub inclusion_verification_latency: Histogram<Duration>,
+    /// Number of inclusion data verifications grouped by the outcome.
+    pub inclusion_verifications: Family<InclusionVerificationResult, Counter>,
 }
 
 #[vise::register]
@@ -52,17 +52,24 @@ impl WiringLayer for DataAvailabilityDispatcherLayer {
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
         let da_client = input.da_client;
         if let Some(limit) = da_client.blob_size_limit() {
-            if self
-                .state_keeper_config
-                .seal_criteria
-                .max_pubdata_per_batch
-                .0
-                > limit as u64
-            {
-                return Err(WiringError::Configuration(format!(
-                    "Max pubdata per batch is greater than the blob size limit: {} > {} B",
-                    self.state_keeper_config.seal_criteria.max_pubdata_per_batch, limit
-                )));
+            let max_pubdata_per_batch =
+                self.state_keeper_config.seal_criteria.max_pubdata_per_batch;
+            if max_pubdata_per_batch.0 > limit as u64 {
+                if !self.da_config.split_oversized_blobs {
+                    return Err(WiringError::Configuration(format!(
+                        "Max pubdata per batch is greater than the blob size limit: {max_pubdata_per_batch} > {limit} B; \
+                         consider enabling `split_oversized_blobs`"
+                    )));
+                }
+                if !da_client.supports_chunked_dispatch() {
+                    return Err(WiringError::Configuration(format!(
+                        "Max pubdata per batch is greater than the blob size limit: {max_pubdata_per_batch} > {limit} B, \
+                         and the DA client doesn't support splitting blobs"
+                    )));
+                }
+                tracing::info!(
+                    "Pubdata exceeding the blob size limit ({limit} B) will be split into several blobs"
+                );
             }
         }
 
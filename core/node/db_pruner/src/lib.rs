@@ -1,6 +1,8 @@
 //! Postgres pruning component.
 
 use std::{
+    collections::HashMap,
+    num::NonZeroU32,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -9,7 +11,7 @@ use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
 use tokio::sync::watch;
 use zksync_dal::{
-    pruning_dal::{HardPruningInfo, PruningInfo, SoftPruningInfo},
+    pruning_dal::{HardPruningInfo, PrunedDataCategory, PruningInfo, SoftPruningInfo},
     Connection, ConnectionPool, Core, CoreDal,
 };
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
@@ -40,6 +42,12 @@ pub struct DbPrunerConfig {
     /// Minimum age of an L1 batch in order for it to be eligible for pruning. Setting this to zero
     /// will effectively disable this pruning criterion.
     pub minimum_l1_batch_age: Duration,
+    /// If set, storage logs are retained for every L1 batch with number divisible by this value, so that storage state
+    /// can be read as of such batches after they are pruned.
+    pub archive_interval: Option<NonZeroU32>,
+    /// Minimum age of an L1 batch in order for data of a certain category to be pruned, potentially ahead of other data
+    /// in the batch. Categories not mentioned here are pruned together with other data.
+    pub category_retention: HashMap<PrunedDataCategory, Duration>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -81,11 +89,38 @@ pub struct DbPruner {
     connection_pool: ConnectionPool<Core>,
     health_updater: HealthUpdater,
     prune_conditions: Vec<Arc<dyn PruneCondition>>,
+    category_prune_conditions: HashMap<PrunedDataCategory, Vec<Arc<dyn PruneCondition>>>,
 }
 
 impl DbPruner {
     pub fn new(config: DbPrunerConfig, connection_pool: ConnectionPool<Core>) -> Self {
-        let mut conditions: Vec<Arc<dyn PruneCondition>> = vec![
+        let mut conditions = Self::base_conditions(&connection_pool);
+        if config.minimum_l1_batch_age > Duration::ZERO {
+            // Do not add a condition if it's trivial in order to not clutter logs.
+            conditions.push(Arc::new(L1BatchOlderThanPruneCondition {
+                minimum_age: config.minimum_l1_batch_age,
+                pool: connection_pool.clone(),
+            }));
+        }
+
+        let category_retention = config.category_retention.clone();
+        let mut pruner = Self::with_conditions(config, connection_pool.clone(), conditions);
+        for (category, retention) in category_retention {
+            let mut conditions = Self::base_conditions(&connection_pool);
+            if retention > Duration::ZERO {
+                conditions.push(Arc::new(L1BatchOlderThanPruneCondition {
+                    minimum_age: retention,
+                    pool: connection_pool.clone(),
+                }));
+            }
+            pruner = pruner.with_category_conditions(category, conditions);
+        }
+        pruner
+    }
+
+    /// Conditions ensuring that pruning doesn't interfere with other node components.
+    fn base_conditions(connection_pool: &ConnectionPool<Core>) -> Vec<Arc<dyn PruneCondition>> {
+        vec![
             Arc::new(L1BatchExistsCondition {
                 pool: connection_pool.clone(),
             }),
@@ -98,16 +133,7 @@ impl DbPruner {
             Arc::new(ConsistencyCheckerProcessedBatch {
                 pool: connection_pool.clone(),
             }),
-        ];
-        if config.minimum_l1_batch_age > Duration::ZERO {
-            // Do not add a condition if it's trivial in order to not clutter logs.
-            conditions.push(Arc::new(L1BatchOlderThanPruneCondition {
-                minimum_age: config.minimum_l1_batch_age,
-                pool: connection_pool.clone(),
-            }));
-        }
-
-        Self::with_conditions(config, connection_pool, conditions)
+        ]
     }
 
     fn with_conditions(
@@ -120,19 +146,37 @@ impl DbPruner {
             connection_pool,
             health_updater: ReactiveHealthCheck::new("db_pruner").1,
             prune_conditions,
+            category_prune_conditions: HashMap::new(),
         }
     }
 
+    fn with_category_conditions(
+        mut self,
+        category: PrunedDataCategory,
+        prune_conditions: Vec<Arc<dyn PruneCondition>>,
+    ) -> Self {
+        self.category_prune_conditions
+            .insert(category, prune_conditions);
+        self
+    }
+
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
     }
 
     async fn is_l1_batch_prunable(&self, l1_batch_number: L1BatchNumber) -> bool {
+        Self::are_conditions_met(&self.prune_conditions, l1_batch_number).await
+    }
+
+    async fn are_conditions_met(
+        prune_conditions: &[Arc<dyn PruneCondition>],
+        l1_batch_number: L1BatchNumber,
+    ) -> bool {
         let mut successful_conditions = vec![];
         let mut failed_conditions = vec![];
         let mut errored_conditions = vec![];
 
-        for condition in &self.prune_conditions {
+        for condition in prune_conditions {
             let outcome = match condition.is_batch_prunable(l1_batch_number).await {
                 Ok(true) => {
                     successful_conditions.push(condition.to_string());
@@ -251,9 +295,10 @@ impl DbPruner {
 
         let mut dal = transaction.pruning_dal();
         let stats = tokio::select! {
-            result = dal.hard_prune_batches_range(
+            result = dal.hard_prune_batches_range_with_archive(
                 soft_pruned.l1_batch,
                 soft_pruned.l2_block,
+                self.config.archive_interval,
             ) => result?,
 
             _ = stop_receiver.changed() => {
@@ -286,6 +331,107 @@ impl DbPruner {
         Ok(PruningIterationOutcome::Pruned)
     }
 
+    /// Soft-prunes data of the specified category. Returns `true` if there is soft-pruned data for the category
+    /// that should be hard-pruned.
+    async fn soft_prune_category(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        category: PrunedDataCategory,
+        prune_conditions: &[Arc<dyn PruneCondition>],
+    ) -> anyhow::Result<bool> {
+        let mut transaction = storage.start_transaction().await?;
+        let current_pruning_info = transaction.pruning_dal().get_pruning_info().await?;
+        let category_info = *transaction
+            .pruning_dal()
+            .get_category_pruning_info()
+            .await?
+            .get(category);
+        if !category_info.is_caught_up() {
+            // The node has restarted after soft pruning the category.
+            return Ok(true);
+        }
+
+        // Data pruned by the main pruning doesn't need to be pruned again.
+        let last_soft_pruned_l1_batch = [
+            current_pruning_info.last_soft_pruned,
+            category_info.last_soft_pruned,
+        ]
+        .into_iter()
+        .flatten()
+        .map(|info| info.l1_batch)
+        .max();
+        let next_l1_batch_to_prune = last_soft_pruned_l1_batch.unwrap_or(L1BatchNumber(0))
+            + self.config.pruned_batch_chunk_size;
+        if !Self::are_conditions_met(prune_conditions, next_l1_batch_to_prune).await {
+            return Ok(false);
+        }
+
+        let (_, next_l2_block_to_prune) = transaction
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(next_l1_batch_to_prune)
+            .await?
+            .with_context(|| format!("L1 batch #{next_l1_batch_to_prune} is ready to be pruned, but has no L2 blocks"))?;
+        transaction
+            .pruning_dal()
+            .insert_category_soft_pruning_log(
+                category,
+                next_l1_batch_to_prune,
+                next_l2_block_to_prune,
+            )
+            .await?;
+        transaction.commit().await?;
+
+        tracing::info!(
+            "Soft pruned {category} for L1 batches up to {next_l1_batch_to_prune} and L2 blocks up to {next_l2_block_to_prune}"
+        );
+        Ok(true)
+    }
+
+    async fn hard_prune_category(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        category: PrunedDataCategory,
+        stop_receiver: &mut watch::Receiver<bool>,
+    ) -> Result<(), OrStopped> {
+        let latency = METRICS.category_pruning_chunk_duration[&category.into()].start();
+        let mut transaction = storage.start_transaction().await?;
+
+        let category_info = *transaction
+            .pruning_dal()
+            .get_category_pruning_info()
+            .await?
+            .get(category);
+        let soft_pruned = category_info.last_soft_pruned.with_context(|| {
+            format!("bogus {category} pruning info {category_info:?}: trying to hard-prune data, but there is no soft-pruned data")
+        })?;
+
+        let mut dal = transaction.pruning_dal();
+        let deleted_count = tokio::select! {
+            result = dal.hard_prune_category(
+                category,
+                soft_pruned.l1_batch,
+                soft_pruned.l2_block,
+                self.config.archive_interval,
+            ) => result?,
+
+            _ = stop_receiver.changed() => {
+                tracing::info!("Hard pruning of {category} interrupted; rolling back pruning transaction");
+                transaction.rollback().await?;
+                return Err(OrStopped::Stopped);
+            }
+        };
+        dal.insert_category_hard_pruning_log(category, soft_pruned.l1_batch, soft_pruned.l2_block)
+            .await?;
+        transaction.commit().await?;
+
+        METRICS.observe_category_pruning(category, deleted_count);
+        let latency = latency.observe();
+        tracing::info!(
+            "Hard pruned {deleted_count} entities of {category} up to {soft_pruned:?}, operation took {latency:?}"
+        );
+        Ok(())
+    }
+
     async fn run_single_iteration(
         &self,
         stop_receiver: &mut watch::Receiver<bool>,
@@ -295,12 +441,22 @@ impl DbPruner {
         self.update_health(current_pruning_info);
 
         // If this `if` is not entered, it means that the node has restarted after soft pruning
+        let mut should_hard_prune = true;
         if current_pruning_info.is_caught_up() {
-            let pruning_done = self.soft_prune(&mut storage).await?;
-            if !pruning_done {
-                return Ok(PruningIterationOutcome::NoOp);
+            should_hard_prune = self.soft_prune(&mut storage).await?;
+        }
+        let mut categories_to_hard_prune = vec![];
+        for (&category, prune_conditions) in &self.category_prune_conditions {
+            if self
+                .soft_prune_category(&mut storage, category, prune_conditions)
+                .await?
+            {
+                categories_to_hard_prune.push(category);
             }
         }
+        if !should_hard_prune && categories_to_hard_prune.is_empty() {
+            return Ok(PruningIterationOutcome::NoOp);
+        }
         drop(storage); // Don't hold a connection across a timeout
 
         if tokio::time::timeout(self.config.removal_delay, stop_receiver.changed())
@@ -311,7 +467,15 @@ impl DbPruner {
         }
 
         let mut storage = self.connection_pool.connection_tagged("db_pruner").await?;
-        self.hard_prune(&mut storage, stop_receiver).await
+        for category in categories_to_hard_prune {
+            self.hard_prune_category(&mut storage, category, stop_receiver)
+                .await?;
+        }
+        if should_hard_prune {
+            self.hard_prune(&mut storage, stop_receiver).await
+        } else {
+            Ok(PruningIterationOutcome::Pruned)
+        }
     }
 
     pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
@@ -3,7 +3,7 @@ use std::time::Duration;
 use vise::{
     Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit,
 };
-use zksync_dal::pruning_dal::HardPruningStats;
+use zksync_dal::pruning_dal::{HardPruningStats, PrunedDataCategory};
 
 use crate::prune_conditions::PruneCondition;
 
@@ -15,6 +15,22 @@ pub(super) enum PruneType {
     Hard,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "category", rename_all = "snake_case")]
+pub(super) enum PrunedCategory {
+    CallTraces,
+    StorageLogs,
+}
+
+impl From<PrunedDataCategory> for PrunedCategory {
+    fn from(category: PrunedDataCategory) -> Self {
+        match category {
+            PrunedDataCategory::CallTraces => Self::CallTraces,
+            PrunedDataCategory::StorageLogs => Self::StorageLogs,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "type", rename_all = "snake_case")]
 enum PrunedEntityType {
@@ -51,6 +67,9 @@ pub(super) struct DbPrunerMetrics {
     /// Total latency of pruning chunk of L1 batches.
     #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
     pub pruning_chunk_duration: Family<PruneType, Histogram<Duration>>,
+    /// Latency of hard-pruning a data category ahead of other data.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub category_pruning_chunk_duration: Family<PrunedCategory, Histogram<Duration>>,
     /// Number of not-pruned L1 batches.
     pub not_pruned_l1_batches_count: Gauge<u64>,
     /// Number of entities deleted during a single hard pruning iteration, grouped by entity type.
@@ -84,6 +103,14 @@ impl DbPrunerMetrics {
         self.deleted_entities[&PrunedEntityType::CallTrace].observe(deleted_call_traces);
     }
 
+    pub fn observe_category_pruning(&self, category: PrunedDataCategory, deleted_count: u64) {
+        let entity_type = match category {
+            PrunedDataCategory::CallTraces => PrunedEntityType::CallTrace,
+            PrunedDataCategory::StorageLogs => PrunedEntityType::StorageLog,
+        };
+        self.deleted_entities[&entity_type].observe(deleted_count);
+    }
+
     pub fn observe_condition(&self, condition: &dyn PruneCondition, outcome: ConditionOutcome) {
         let labels = ConditionOutcomeLabels {
             condition: condition.metric_label(),
@@ -1,6 +1,9 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, num::NonZeroU32, sync::Arc, time::Duration};
 
-use zksync_dal::node::{MasterPool, PoolResource};
+use zksync_dal::{
+    node::{MasterPool, PoolResource},
+    pruning_dal::PrunedDataCategory,
+};
 use zksync_health_check::AppHealthCheck;
 use zksync_node_framework::{
     service::StopReceiver,
@@ -17,6 +20,8 @@ pub struct PruningLayer {
     pruning_removal_delay: Duration,
     pruning_chunk_size: u32,
     minimum_l1_batch_age: Duration,
+    archive_interval: Option<NonZeroU32>,
+    category_retention: HashMap<PrunedDataCategory, Duration>,
 }
 
 #[derive(Debug, FromContext)]
@@ -42,7 +47,30 @@ impl PruningLayer {
             pruning_removal_delay,
             pruning_chunk_size,
             minimum_l1_batch_age,
+            archive_interval: None,
+            category_retention: HashMap::new(),
+        }
+    }
+
+    /// Retains storage logs for every L1 batch with number divisible by `interval`.
+    pub fn with_archive_interval(mut self, interval: Option<NonZeroU32>) -> Self {
+        self.archive_interval = interval;
+        self
+    }
+
+    /// Prunes data of the specified category once its L1 batch is `retention` old, potentially ahead of other data.
+    /// If `retention` is `None`, the category is pruned together with other data.
+    pub fn with_category_retention(
+        mut self,
+        category: PrunedDataCategory,
+        retention: Option<Duration>,
+    ) -> Self {
+        if let Some(retention) = retention {
+            self.category_retention.insert(category, retention);
+        } else {
+            self.category_retention.remove(&category);
         }
+        self
     }
 }
 
@@ -63,6 +91,8 @@ impl WiringLayer for PruningLayer {
                 removal_delay: self.pruning_removal_delay,
                 pruned_batch_chunk_size: self.pruning_chunk_size,
                 minimum_l1_batch_age: self.minimum_l1_batch_age,
+                archive_interval: self.archive_interval,
+                category_retention: self.category_retention,
             },
             main_pool,
         );
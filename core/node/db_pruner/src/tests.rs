@@ -79,6 +79,8 @@ async fn is_l1_batch_prunable_works() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 1,
             minimum_l1_batch_age: Duration::ZERO,
+            archive_interval: None,
+            category_retention: HashMap::new(),
         },
         ConnectionPool::test_pool().await,
         vec![failing_check, other_failing_check],
@@ -149,6 +151,8 @@ async fn hard_pruning_ignores_conditions_checks() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 5,
             minimum_l1_batch_age: Duration::ZERO,
+            archive_interval: None,
+            category_retention: HashMap::new(),
         },
         pool.clone(),
         vec![nothing_prunable_check],
@@ -198,6 +202,8 @@ async fn pruner_catches_up_with_hard_pruning_up_to_soft_pruning_boundary_ignorin
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 5,
             minimum_l1_batch_age: Duration::ZERO,
+            archive_interval: None,
+            category_retention: HashMap::new(),
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -236,6 +242,8 @@ async fn unconstrained_pruner_with_fresh_database() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            archive_interval: None,
+            category_retention: HashMap::new(),
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -262,6 +270,54 @@ async fn unconstrained_pruner_with_fresh_database() {
     );
 }
 
+#[test(tokio::test)]
+async fn category_is_pruned_ahead_of_other_data() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_l2_blocks(&mut conn, 10, 2).await;
+
+    let nothing_prunable_check = Arc::new(ConditionMock::name("nothing prunable"));
+    let pruner = DbPruner::with_conditions(
+        DbPrunerConfig {
+            removal_delay: Duration::ZERO,
+            pruned_batch_chunk_size: 3,
+            minimum_l1_batch_age: Duration::ZERO,
+            archive_interval: None,
+            category_retention: HashMap::new(),
+        },
+        pool.clone(),
+        vec![nothing_prunable_check],
+    )
+    .with_category_conditions(PrunedDataCategory::CallTraces, vec![]);
+
+    let (_stop_sender, mut stop_receiver) = watch::channel(false);
+    for expected_info in [(3, 7), (6, 13)] {
+        let outcome = pruner
+            .run_single_iteration(&mut stop_receiver)
+            .await
+            .unwrap();
+        assert_matches!(outcome, PruningIterationOutcome::Pruned);
+
+        assert_eq!(
+            conn.pruning_dal().get_pruning_info().await.unwrap(),
+            PruningInfo::default()
+        );
+        let category_info = conn
+            .pruning_dal()
+            .get_category_pruning_info()
+            .await
+            .unwrap();
+        let mut expected_info = test_pruning_info(expected_info.0, expected_info.1);
+        expected_info
+            .last_hard_pruned
+            .as_mut()
+            .unwrap()
+            .l1_batch_root_hash = None;
+        assert_eq!(category_info.call_traces, expected_info);
+        assert_eq!(category_info.storage_logs, PruningInfo::default());
+    }
+}
+
 #[test(tokio::test)]
 async fn pruning_blocked_after_first_chunk() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -276,6 +332,8 @@ async fn pruning_blocked_after_first_chunk() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            archive_interval: None,
+            category_retention: HashMap::new(),
         },
         pool.clone(),
         vec![first_chunk_prunable_check],
@@ -316,6 +374,8 @@ async fn pruner_is_resistant_to_errors() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            archive_interval: None,
+            category_retention: HashMap::new(),
         },
         pool.clone(),
         vec![erroneous_condition],
@@ -492,6 +552,8 @@ async fn pruner_with_real_conditions() {
         removal_delay: Duration::from_millis(10), // non-zero to not have a tight loop in `DbPruner::run()`
         pruned_batch_chunk_size: 1,
         minimum_l1_batch_age: Duration::ZERO,
+        archive_interval: None,
+        category_retention: HashMap::new(),
     };
     let pruner = DbPruner::new(config, pool.clone());
     let mut health_check = pruner.health_check();
@@ -548,6 +610,8 @@ async fn pruning_iteration_timely_shuts_down() {
             removal_delay: Duration::MAX, // intentionally chosen so that pruning iterations stuck
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            archive_interval: None,
+            category_retention: HashMap::new(),
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -577,6 +641,8 @@ async fn pruner_timely_shuts_down() {
             removal_delay: Duration::MAX, // intentionally chosen so that pruning iterations stuck
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            archive_interval: None,
+            category_retention: HashMap::new(),
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -68,6 +68,7 @@ impl TestContext {
                 },
                 max_retries: 10,
                 local_mirror_path: None,
+                encryption: None,
             },
             event_poll_interval: Duration::from_secs(1),
             request_sending_interval: Duration::from_secs(1),
@@ -64,10 +64,15 @@ pub(super) trait AbstractL1Interface: 'static + Sync + Send + fmt::Debug {
 
     fn get_operator_account(&self, operator_type: OperatorType) -> Address;
 
+    /// Returns all accounts of the specified operator type, starting from the main one.
+    /// Returns an empty list if the operator type is not configured.
+    fn get_operator_accounts(&self, operator_type: OperatorType) -> Vec<Address>;
+
     async fn get_operator_nonce(
         &self,
         block_numbers: L1BlockNumbers,
         operator_type: OperatorType,
+        operator_address: Address,
     ) -> Result<Option<OperatorNonce>, EthSenderError>;
 
     #[allow(clippy::too_many_arguments)]
@@ -93,6 +98,10 @@ pub(super) struct RealL1Interface {
     pub ethereum_client: Option<Box<dyn BoundEthInterface>>,
     pub ethereum_client_blobs: Option<Box<dyn BoundEthInterface>>,
     pub sl_client: Option<Box<dyn BoundEthInterface>>,
+    /// Additional accounts sharing the load of `ethereum_client`.
+    pub additional_ethereum_clients: Vec<Box<dyn BoundEthInterface>>,
+    /// Additional accounts sharing the load of `ethereum_client_blobs`.
+    pub additional_ethereum_clients_blobs: Vec<Box<dyn BoundEthInterface>>,
     pub wait_confirmations: Option<u64>,
 }
 
@@ -112,6 +121,37 @@ impl RealL1Interface {
             OperatorType::Gateway => self.sl_client.as_deref().unwrap(),
         }
     }
+
+    fn additional_clients(&self, operator_type: OperatorType) -> &[Box<dyn BoundEthInterface>] {
+        match operator_type {
+            OperatorType::NonBlob => &self.additional_ethereum_clients,
+            OperatorType::Blob => &self.additional_ethereum_clients_blobs,
+            OperatorType::Gateway => &[],
+        }
+    }
+
+    fn main_client(&self, operator_type: OperatorType) -> Option<&dyn BoundEthInterface> {
+        match operator_type {
+            OperatorType::NonBlob => self.ethereum_client.as_deref(),
+            OperatorType::Blob => self.ethereum_client_blobs.as_deref(),
+            OperatorType::Gateway => self.sl_client.as_deref(),
+        }
+    }
+
+    fn bound_client_for_account(
+        &self,
+        operator_type: OperatorType,
+        operator_address: Address,
+    ) -> Option<&dyn BoundEthInterface> {
+        let main_client = self.main_client(operator_type)?;
+        std::iter::once(main_client)
+            .chain(
+                self.additional_clients(operator_type)
+                    .iter()
+                    .map(|client| client.as_ref()),
+            )
+            .find(|client| client.sender_account() == operator_address)
+    }
 }
 
 #[async_trait]
@@ -176,28 +216,45 @@ impl AbstractL1Interface for RealL1Interface {
         self.bound_query_client(operator_type).sender_account()
     }
 
+    fn get_operator_accounts(&self, operator_type: OperatorType) -> Vec<Address> {
+        let Some(main_client) = self.main_client(operator_type) else {
+            return vec![];
+        };
+        let additional_accounts = self
+            .additional_clients(operator_type)
+            .iter()
+            .map(|client| client.sender_account());
+        std::iter::once(main_client.sender_account())
+            .chain(additional_accounts)
+            .collect()
+    }
+
     async fn get_operator_nonce(
         &self,
         block_numbers: L1BlockNumbers,
         operator_type: OperatorType,
+        operator_address: Address,
     ) -> Result<Option<OperatorNonce>, EthSenderError> {
-        let finalized = self
-            .bound_query_client(operator_type)
-            .nonce_at(block_numbers.finalized.0.into())
+        // Accounts removed from the configuration don't have a bound client, but their nonces
+        // can still be queried to track their transactions.
+        let client = match self.bound_client_for_account(operator_type, operator_address) {
+            Some(client) => client.as_ref(),
+            None => self.query_client(operator_type),
+        };
+        let finalized = client
+            .nonce_at_for_account(operator_address, block_numbers.finalized.0.into())
             .await?
             .as_u32()
             .into();
 
-        let latest = self
-            .bound_query_client(operator_type)
-            .nonce_at(block_numbers.latest.0.into())
+        let latest = client
+            .nonce_at_for_account(operator_address, block_numbers.latest.0.into())
             .await?
             .as_u32()
             .into();
 
-        let fast_finality = self
-            .bound_query_client(operator_type)
-            .nonce_at(block_numbers.fast_finality.0.into())
+        let fast_finality = client
+            .nonce_at_for_account(operator_address, block_numbers.fast_finality.0.into())
             .await?
             .as_u32()
             .into();
@@ -219,7 +276,17 @@ impl AbstractL1Interface for RealL1Interface {
         operator_type: OperatorType,
         max_gas_per_pubdata: Option<U256>,
     ) -> SignedCallResult {
-        self.bound_query_client(operator_type)
+        // Transactions without a sender are signed by the main account of the operator type.
+        // Transactions of unknown accounts are never signed, since they would get a wrong nonce.
+        let client = match tx.from_addr {
+            Some(address) => self
+                .bound_client_for_account(operator_type, address)
+                .unwrap_or_else(|| {
+                    panic!("Unknown {operator_type:?} operator account {address:?}")
+                }),
+            None => self.bound_query_client(operator_type),
+        };
+        client
             .sign_prepared_tx_for_addr(
                 tx.raw_tx.clone(),
                 tx.contract_address,
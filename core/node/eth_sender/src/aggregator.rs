@@ -1,6 +1,5 @@
 use std::{sync::Arc, time::Duration};
 
-use chrono::Utc;
 use zksync_airbender_prover_interface::outputs::L1BatchAirbenderSnarkProofForL1;
 use zksync_config::configs::eth_sender::{
     PrecommitParams, ProofSendingMode, ProverType, SenderConfig,
@@ -15,7 +14,6 @@ use zksync_types::{
     aggregated_operations::L1BatchAggregatedActionType,
     commitment::{L1BatchCommitmentMode, L1BatchWithMetadata, PriorityOpsMerkleProof},
     hasher::keccak::KeccakHasher,
-    helpers::unix_timestamp_ms,
     l1::L1Tx,
     protocol_version::{L1VerifierConfig, ProtocolSemanticVersion},
     pubdata_da::PubdataSendingMode,
@@ -26,6 +24,8 @@ use zksync_types::{
 
 use super::{
     aggregated_operations::AggregatedOperation,
+    env::AggregatorEnv,
+    metrics::EthSenderMetrics,
     publish_criterion::{
         GasCriterionKind, L1BatchPublishCriterion, L1GasCriterion, NumberCriterion,
         TimestampDeadlineCriterion,
@@ -54,6 +54,7 @@ pub struct Aggregator {
     commitment_mode: L1BatchCommitmentMode,
     priority_merkle_tree: Option<MiniMerkleTree<L1Tx>>,
     settlement_layer: SettlementLayer,
+    env: AggregatorEnv,
 }
 
 /// Denotes whether there are any restrictions on sending either
@@ -138,6 +139,27 @@ impl Aggregator {
         commitment_mode: L1BatchCommitmentMode,
         pool: ConnectionPool<Core>,
         settlement_layer: SettlementLayer,
+    ) -> anyhow::Result<Self> {
+        Self::with_env(
+            config,
+            blob_store,
+            custom_commit_sender_addr,
+            commitment_mode,
+            pool,
+            settlement_layer,
+            AggregatorEnv::Real,
+        )
+        .await
+    }
+
+    pub(crate) async fn with_env(
+        config: SenderConfig,
+        blob_store: Arc<dyn ObjectStore>,
+        custom_commit_sender_addr: bool,
+        commitment_mode: L1BatchCommitmentMode,
+        pool: ConnectionPool<Core>,
+        settlement_layer: SettlementLayer,
+        env: AggregatorEnv,
     ) -> anyhow::Result<Self> {
         let operate_4844_mode: bool = custom_commit_sender_addr && !settlement_layer.is_gateway();
 
@@ -167,6 +189,7 @@ impl Aggregator {
                     op: L1BatchAggregatedActionType::Execute,
                     deadline: config.aggregated_block_execute_deadline,
                     max_allowed_lag: Some(config.timestamp_criteria_max_allowed_lag),
+                    env: env.clone(),
                 }),
                 Box::from(L1GasCriterion::new(
                     config.max_aggregated_tx_gas,
@@ -188,6 +211,7 @@ impl Aggregator {
                         op: L1BatchAggregatedActionType::Commit,
                         deadline: config.aggregated_block_commit_deadline,
                         max_allowed_lag: Some(config.timestamp_criteria_max_allowed_lag),
+                        env: env.clone(),
                     }),
                     Box::from(L1GasCriterion::new(
                         config.max_aggregated_tx_gas,
@@ -223,9 +247,39 @@ impl Aggregator {
             priority_merkle_tree: None,
             pool,
             settlement_layer,
+            env,
         })
     }
 
+    /// Selects L1 batches to be published in a single operation of the specified type from `unpublished_l1_batches`
+    /// using the configured publish criteria. Returns `None` if no batches should be published yet.
+    ///
+    /// Unlike [`Self::get_next_ready_operation()`], this doesn't load batches from Postgres, which allows simulating
+    /// aggregation decisions without changing the eth_sender state.
+    #[cfg(any(test, feature = "replay"))]
+    pub(crate) async fn select_l1_batches(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        op: L1BatchAggregatedActionType,
+        unpublished_l1_batches: Vec<L1BatchWithMetadata>,
+        last_sealed_l1_batch: L1BatchNumber,
+    ) -> Option<Vec<L1BatchWithMetadata>> {
+        let criteria = match op {
+            L1BatchAggregatedActionType::Commit => &mut self.commit_criteria,
+            L1BatchAggregatedActionType::PublishProofOnchain => &mut self.proof_criteria,
+            L1BatchAggregatedActionType::Execute => &mut self.execute_criteria,
+        };
+        extract_ready_subrange(
+            storage,
+            criteria,
+            unpublished_l1_batches,
+            last_sealed_l1_batch,
+            self.settlement_layer.is_gateway(),
+            self.env.metrics(),
+        )
+        .await
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub(crate) async fn get_next_ready_operation(
         &mut self,
@@ -383,7 +437,7 @@ impl Aggregator {
                 &first_tx,
                 last_tx,
                 precommit_params,
-                Utc::now().timestamp(),
+                self.env.now_secs(),
             )
         {
             return Ok(None);
@@ -421,8 +475,11 @@ impl Aggregator {
         is_gateway: bool,
         execution_delay: Duration,
     ) -> Result<Option<ExecuteBatches>, EthSenderError> {
-        let mut max_l1_batch_timestamp_millis =
-            Some(unix_timestamp_ms() - execution_delay.as_millis() as u64);
+        let mut max_l1_batch_timestamp_millis = Some(
+            self.env
+                .now_ms()
+                .saturating_sub(execution_delay.as_millis() as u64),
+        );
 
         // Add safety margin for L1 block inclusion delays
         // On L1 time is discrete and in worst case if you send time at X,
@@ -449,6 +506,7 @@ impl Aggregator {
             ready_for_execute_batches,
             last_sealed_l1_batch,
             is_gateway,
+            self.env.metrics(),
         )
         .await
         else {
@@ -634,6 +692,7 @@ impl Aggregator {
             ready_for_commit_l1_batches,
             last_sealed_batch,
             self.settlement_layer.is_gateway(),
+            self.env.metrics(),
         )
         .await;
 
@@ -652,7 +711,7 @@ impl Aggregator {
         ))
     }
 
-    async fn get_commitment_modes(
+    pub(crate) async fn get_commitment_modes(
         &self,
         batch: &L1BatchWithMetadata,
         storage: &mut Connection<'_, Core>,
@@ -883,6 +942,7 @@ impl Aggregator {
             ready_for_proof_l1_batches,
             last_sealed_l1_batch,
             self.settlement_layer.is_gateway(),
+            self.env.metrics(),
         )
         .await?;
 
@@ -970,6 +1030,7 @@ async fn extract_ready_subrange(
     unpublished_l1_batches: Vec<L1BatchWithMetadata>,
     last_sealed_l1_batch: L1BatchNumber,
     is_gateway: bool,
+    metrics: &EthSenderMetrics,
 ) -> Option<Vec<L1BatchWithMetadata>> {
     let mut last_l1_batch: Option<L1BatchNumber> = None;
     for criterion in publish_criteria {
@@ -982,6 +1043,7 @@ async fn extract_ready_subrange(
             )
             .await;
         if let Some(l1_batch) = l1_batch_by_criterion {
+            metrics.block_aggregation_reason[&criterion.aggregation_reason().into()].inc();
             last_l1_batch = Some(last_l1_batch.map_or(l1_batch, |number| number.min(l1_batch)));
         }
     }
@@ -1064,6 +1126,7 @@ async fn load_airbender_snark_proof(
 
 #[cfg(test)]
 mod tests {
+    use chrono::Utc;
     use zksync_types::L2BlockNumber;
 
     use super::*;
@@ -0,0 +1,66 @@
+#[cfg(any(test, feature = "replay"))]
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use chrono::Utc;
+use zksync_types::helpers::unix_timestamp_ms;
+
+use crate::metrics::{EthSenderMetrics, METRICS};
+
+/// Environment observed by [`Aggregator`](crate::Aggregator): the current time and the metrics sink.
+///
+/// In production, the time is taken from the system clock. Replays substitute it with the simulated time, so that
+/// publish criteria observe historical batches at their original timestamps, and report to detached metrics.
+#[derive(Debug, Clone, Default)]
+pub(crate) enum AggregatorEnv {
+    #[default]
+    Real,
+    #[cfg(any(test, feature = "replay"))]
+    Simulated(Arc<SimulatedEnv>),
+}
+
+impl AggregatorEnv {
+    pub fn now_ms(&self) -> u64 {
+        match self {
+            Self::Real => unix_timestamp_ms(),
+            #[cfg(any(test, feature = "replay"))]
+            Self::Simulated(env) => env.now_ms.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn now_secs(&self) -> i64 {
+        match self {
+            Self::Real => Utc::now().timestamp(),
+            #[cfg(any(test, feature = "replay"))]
+            Self::Simulated(env) => (env.now_ms.load(Ordering::Relaxed) / 1_000) as i64,
+        }
+    }
+
+    pub fn metrics(&self) -> &'static EthSenderMetrics {
+        match self {
+            Self::Real => &METRICS,
+            #[cfg(any(test, feature = "replay"))]
+            Self::Simulated(_) => &crate::metrics::REPLAY_METRICS,
+        }
+    }
+}
+
+/// Simulated time shared between a replay and the aggregator it drives.
+#[cfg(any(test, feature = "replay"))]
+#[derive(Debug, Default)]
+pub(crate) struct SimulatedEnv {
+    now_ms: AtomicU64,
+}
+
+#[cfg(any(test, feature = "replay"))]
+impl SimulatedEnv {
+    pub fn now_ms(&self) -> u64 {
+        self.now_ms.load(Ordering::Relaxed)
+    }
+
+    pub fn set_now_ms(&self, now_ms: u64) {
+        self.now_ms.store(now_ms, Ordering::Relaxed);
+    }
+}
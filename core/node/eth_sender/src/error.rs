@@ -1,6 +1,6 @@
 use zksync_dal::DalError;
 use zksync_eth_client::{ContractCallError, EnrichedClientError};
-use zksync_types::{web3::contract, L1BatchNumber};
+use zksync_types::{web3::contract, Address, L1BatchNumber};
 
 #[derive(Debug, thiserror::Error)]
 pub enum EthSenderError {
@@ -16,6 +16,8 @@ pub enum EthSenderError {
     Dal(#[from] DalError),
     #[error("Missing aggregation root for L1 batch #{0}")]
     MissingAggregationRoot(L1BatchNumber),
+    #[error("Operator account {0:?} is not configured")]
+    UnknownOperatorAccount(Address),
 }
 
 impl EthSenderError {
@@ -41,6 +41,7 @@ use crate::{
     aggregator::OperationSkippingRestrictions,
     health::{EthTxAggregatorHealthDetails, EthTxDetails},
     metrics::{PubdataKind, METRICS},
+    operator_pool::{OperatorPool, UnconfirmedDependency},
     publish_criterion::L1GasCriterion,
     zksync_functions::ZkSyncFunctions,
     Aggregator, EthSenderError,
@@ -93,10 +94,13 @@ pub struct EthTxAggregator {
     state_transition_manager_address: Address,
     functions: ZkSyncFunctions,
     rollup_chain_id: L2ChainId,
+    /// Accounts used to send all transactions except for commit transactions in the 4844 mode.
+    /// The first account corresponds to `eth_client`.
+    operator_pool: OperatorPool,
     /// If set to `Some` node is operating in the 4844 mode with two operator
-    /// addresses at play: the main one and the custom address for sending commit
-    /// transactions. The `Some` then contains client for this custom operator address.
-    eth_client_blobs: Option<Box<dyn BoundEthInterface>>,
+    /// roles at play: the main one and the custom one for sending commit
+    /// transactions. The `Some` then contains the accounts for this custom operator role.
+    blob_operator_pool: Option<OperatorPool>,
     pool: ConnectionPool<Core>,
     sl_chain_id: SLChainId,
     health_updater: HealthUpdater,
@@ -121,6 +125,8 @@ impl EthTxAggregator {
         aggregator: Aggregator,
         eth_client: Box<dyn BoundEthInterface>,
         eth_client_blobs: Option<Box<dyn BoundEthInterface>>,
+        additional_eth_clients: Vec<Box<dyn BoundEthInterface>>,
+        additional_eth_clients_blobs: Vec<Box<dyn BoundEthInterface>>,
         config_timelock_contract_address: Address,
         state_transition_manager_address: Address,
         l1_multicall3_address: Address,
@@ -134,13 +140,33 @@ impl EthTxAggregator {
         let functions = ZkSyncFunctions::default();
 
         let mut initial_pending_nonces = HashMap::new();
-        for client in eth_client_blobs.iter().chain(std::iter::once(&eth_client)) {
+        let all_clients = eth_client_blobs
+            .iter()
+            .chain(&additional_eth_clients_blobs)
+            .chain(std::iter::once(&eth_client))
+            .chain(&additional_eth_clients);
+        for client in all_clients {
             let address = client.sender_account();
             let nonce = client.pending_nonce().await.unwrap().as_u64();
 
             initial_pending_nonces.insert(address, nonce);
         }
 
+        let operator_pool = OperatorPool::new(
+            std::iter::once(&eth_client)
+                .chain(&additional_eth_clients)
+                .map(|client| client.sender_account())
+                .collect(),
+        );
+        let blob_operator_pool = eth_client_blobs.as_ref().map(|client| {
+            OperatorPool::new(
+                std::iter::once(client)
+                    .chain(&additional_eth_clients_blobs)
+                    .map(|client| client.sender_account())
+                    .collect(),
+            )
+        });
+
         let sl_chain_id = (*eth_client).as_ref().fetch_chain_id().await.unwrap();
 
         Self {
@@ -153,7 +179,8 @@ impl EthTxAggregator {
             state_transition_chain_contract,
             functions,
             rollup_chain_id,
-            eth_client_blobs,
+            operator_pool,
+            blob_operator_pool,
             pool,
             sl_chain_id,
             health_updater: ReactiveHealthCheck::new("eth_tx_aggregator").1,
@@ -894,7 +921,7 @@ impl EthTxAggregator {
             )
             .await?
         {
-            let tx = self
+            let saved_tx = self
                 .save_eth_tx(
                     storage,
                     &agg_op,
@@ -908,14 +935,16 @@ impl EthTxAggregator {
                     use_fusaka_blob_format,
                 )
                 .await?;
-            Self::report_eth_tx_saving(storage, &agg_op, &tx).await;
+            if let Some(tx) = saved_tx {
+                Self::report_eth_tx_saving(storage, &agg_op, &tx).await;
 
-            self.health_updater.update(
-                EthTxAggregatorHealthDetails {
-                    last_saved_tx: EthTxDetails::new(&tx, None),
-                }
-                .into(),
-            );
+                self.health_updater.update(
+                    EthTxAggregatorHealthDetails {
+                        last_saved_tx: EthTxDetails::new(&tx, None),
+                    }
+                    .into(),
+                );
+            }
         }
 
         if precommit_params.is_some() {
@@ -1236,19 +1265,27 @@ impl EthTxAggregator {
         chain_protocol_version_id: ProtocolVersionId,
         is_gateway: bool,
         use_fusaka_blob_format: bool,
-    ) -> Result<EthTx, EthSenderError> {
+    ) -> Result<Option<EthTx>, EthSenderError> {
         let mut transaction = storage.start_transaction().await.unwrap();
         let op_type = aggregated_op.get_action_type();
         // We may be using a custom sender for commit transactions, so use this
-        // var whatever it actually is: a `None` for single-addr operator or `Some`
-        // for multi-addr operator in 4844 mode.
-        let sender_addr = match (op_type, is_gateway) {
+        // var whatever it actually is: a `None` for single-role operator or `Some`
+        // for multi-role operator in 4844 mode.
+        let operator_pool = match (op_type, is_gateway) {
             (AggregatedActionType::L1Batch(L1BatchAggregatedActionType::Commit), false) => self
-                .eth_client_blobs
+                .blob_operator_pool
                 .as_ref()
-                .map(|c| (c.sender_account()))
-                .unwrap_or_else(|| self.eth_client.sender_account()),
-            (_, _) => self.eth_client.sender_account(),
+                .unwrap_or(&self.operator_pool),
+            (_, _) => &self.operator_pool,
+        };
+        let Some(sender_addr) = self
+            .select_sender(&mut transaction, operator_pool, aggregated_op, is_gateway)
+            .await
+        else {
+            tracing::debug!(
+                "Postponing {op_type} operation until the operations it depends on are confirmed"
+            );
+            return Ok(None);
         };
         let nonce = self
             .get_next_nonce(&mut transaction, sender_addr, is_gateway)
@@ -1337,7 +1374,7 @@ impl EthTxAggregator {
             }
         }
         transaction.commit().await.unwrap();
-        Ok(eth_tx)
+        Ok(Some(eth_tx))
     }
 
     async fn is_gateway_for_sending_txs(
@@ -1358,6 +1395,65 @@ impl EthTxAggregator {
             .unwrap_or(false))
     }
 
+    async fn select_sender(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        operator_pool: &OperatorPool,
+        aggregated_op: &AggregatedOperation,
+        is_gateway: bool,
+    ) -> Option<Address> {
+        if let [address] = operator_pool.addresses() {
+            return Some(*address);
+        }
+
+        let op_type = aggregated_op.get_action_type();
+        let mut dependencies = vec![];
+        let latest_same_type_tx = storage
+            .eth_sender_dal()
+            .get_latest_unconfirmed_tx_sender(op_type, is_gateway)
+            .await
+            .unwrap();
+        dependencies.extend(latest_same_type_tx);
+
+        if let AggregatedOperation::L1Batch(op) = aggregated_op {
+            let previous_op_type = match op.get_action_type() {
+                L1BatchAggregatedActionType::Commit => None,
+                L1BatchAggregatedActionType::PublishProofOnchain => {
+                    Some(L1BatchAggregatedActionType::Commit)
+                }
+                L1BatchAggregatedActionType::Execute => {
+                    Some(L1BatchAggregatedActionType::PublishProofOnchain)
+                }
+            };
+            if let Some(previous_op_type) = previous_op_type {
+                let previous_txs = storage
+                    .eth_sender_dal()
+                    .get_unconfirmed_tx_senders_for_l1_batches(
+                        op.l1_batch_range(),
+                        previous_op_type.into(),
+                    )
+                    .await
+                    .unwrap();
+                dependencies.extend(previous_txs);
+            }
+        }
+        let dependencies: Vec<_> = dependencies
+            .into_iter()
+            .map(|(sender, nonce)| UnconfirmedDependency { sender, nonce })
+            .collect();
+
+        let unconfirmed_txs = storage
+            .eth_sender_dal()
+            .get_unconfirmed_txs_by_sender(is_gateway)
+            .await
+            .unwrap();
+        let sender = operator_pool.select_sender(&dependencies, &unconfirmed_txs);
+        tracing::debug!(
+            "Selected sender {sender:?} for {op_type} operation with dependencies {dependencies:?}"
+        );
+        sender
+    }
+
     async fn get_next_nonce(
         &self,
         storage: &mut Connection<'_, Core>,
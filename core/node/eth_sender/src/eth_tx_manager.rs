@@ -43,6 +43,7 @@ pub struct EthTxManager {
 }
 
 impl EthTxManager {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         pool: ConnectionPool<Core>,
         config: SenderConfig,
@@ -50,10 +51,20 @@ impl EthTxManager {
         ethereum_client: Option<Box<dyn BoundEthInterface>>,
         ethereum_client_blobs: Option<Box<dyn BoundEthInterface>>,
         l2_client: Option<Box<dyn BoundEthInterface>>,
+        additional_ethereum_clients: Vec<Box<dyn BoundEthInterface>>,
+        additional_ethereum_clients_blobs: Vec<Box<dyn BoundEthInterface>>,
     ) -> Self {
         let ethereum_client = ethereum_client.map(|eth| eth.for_component("eth_tx_manager"));
         let ethereum_client_blobs =
             ethereum_client_blobs.map(|eth| eth.for_component("eth_tx_manager"));
+        let additional_ethereum_clients = additional_ethereum_clients
+            .into_iter()
+            .map(|eth| eth.for_component("eth_tx_manager"))
+            .collect();
+        let additional_ethereum_clients_blobs = additional_ethereum_clients_blobs
+            .into_iter()
+            .map(|eth| eth.for_component("eth_tx_manager"))
+            .collect();
         // If `time_in_mempool_multiplier_cap` is set in config then we use it to derive cap for `l1_blocks_cap`.
         // Otherwise we use `time_in_mempool_in_l1_blocks_cap`.
         let time_in_mempool_in_l1_blocks_cap =
@@ -72,6 +83,8 @@ impl EthTxManager {
             ethereum_client,
             ethereum_client_blobs,
             sl_client: l2_client,
+            additional_ethereum_clients,
+            additional_ethereum_clients_blobs,
             wait_confirmations: config.wait_confirmations,
         });
         tracing::info!(
@@ -140,6 +153,14 @@ impl EthTxManager {
             .await
             .unwrap();
 
+        if self.is_retired_operator(tx) {
+            // There's no signer for the account anymore; signing the transaction with another account
+            // would use a wrong nonce.
+            return Err(EthSenderError::UnknownOperatorAccount(
+                tx.from_addr.unwrap_or_default(),
+            ));
+        }
+
         let operator_type = self.operator_type(tx);
         let EthFees {
             base_fee_per_gas,
@@ -385,19 +406,17 @@ impl EthTxManager {
         storage: &mut Connection<'_, Core>,
         l1_block_numbers: L1BlockNumbers,
         operator_type: OperatorType,
+        operator_address: Address,
     ) -> Result<Option<(EthTx, u32)>, EthSenderError> {
         let operator_nonce = self
             .l1_interface
-            .get_operator_nonce(l1_block_numbers, operator_type)
+            .get_operator_nonce(l1_block_numbers, operator_type, operator_address)
             .await?;
 
         if let Some(operator_nonce) = operator_nonce {
             let non_final_txs = storage
                 .eth_sender_dal()
-                .get_non_final_txs(
-                    self.operator_address(operator_type),
-                    operator_type == OperatorType::Gateway,
-                )
+                .get_non_final_txs(operator_address, operator_type == OperatorType::Gateway)
                 .await
                 .unwrap();
 
@@ -414,7 +433,7 @@ impl EthTxManager {
                 storage
                     .eth_sender_dal()
                     .unfinalize_txs(
-                        self.operator_address(operator_type),
+                        operator_address,
                         operator_type == OperatorType::Gateway,
                         eth_tx.id,
                     )
@@ -424,13 +443,12 @@ impl EthTxManager {
 
             let inflight_txs = storage
                 .eth_sender_dal()
-                .get_inflight_txs(
-                    self.operator_address(operator_type),
-                    operator_type == OperatorType::Gateway,
-                )
+                .get_inflight_txs(operator_address, operator_type == OperatorType::Gateway)
                 .await
                 .unwrap();
-            METRICS.number_of_inflight_txs[&operator_type].set(inflight_txs.len());
+            if operator_address == self.operator_address(operator_type) {
+                METRICS.number_of_inflight_txs[&operator_type].set(inflight_txs.len());
+            }
             Ok(self
                 .apply_inflight_txs_statuses_and_get_first_to_resend(
                     storage,
@@ -590,16 +608,60 @@ impl EthTxManager {
         }
     }
 
+    /// Returns `true` if the transaction was assigned to an L1 operator account that is no longer configured.
+    fn is_retired_operator(&self, tx: &EthTx) -> bool {
+        if tx.is_gateway {
+            return false;
+        }
+        tx.from_addr.is_some_and(|address| {
+            [OperatorType::NonBlob, OperatorType::Blob]
+                .into_iter()
+                .all(|ty| {
+                    !self
+                        .l1_interface
+                        .get_operator_accounts(ty)
+                        .contains(&address)
+                })
+        })
+    }
+
+    /// Returns the operator type used to query L1 on behalf of retired operator accounts.
+    fn retired_operators_type(&self) -> OperatorType {
+        if self
+            .l1_interface
+            .supported_operator_types()
+            .contains(&OperatorType::NonBlob)
+        {
+            OperatorType::NonBlob
+        } else {
+            OperatorType::Blob
+        }
+    }
+
     fn operator_type(&self, tx: &EthTx) -> OperatorType {
         if tx.is_gateway {
             OperatorType::Gateway
         } else {
             match tx.from_addr {
-                Some(a) if a == self.operator_address(OperatorType::NonBlob) => {
+                Some(a)
+                    if self
+                        .l1_interface
+                        .get_operator_accounts(OperatorType::NonBlob)
+                        .contains(&a) =>
+                {
                     OperatorType::NonBlob
                 }
-                Some(a) if a == self.operator_address(OperatorType::Blob) => OperatorType::Blob,
-                Some(a) => panic!("Cannot infer operator type for {a:#?}"),
+                Some(a)
+                    if self
+                        .l1_interface
+                        .get_operator_accounts(OperatorType::Blob)
+                        .contains(&a) =>
+                {
+                    OperatorType::Blob
+                }
+                // The account was removed from the configuration. Its transactions are still tracked
+                // (but never resent), which only requires querying L1, so any L1 client works.
+                Some(_) => self.retired_operators_type(),
                 None => OperatorType::NonBlob,
             }
         }
@@ -751,13 +813,11 @@ impl EthTxManager {
         storage: &mut Connection<'_, Core>,
         current_block: L1BlockNumber,
         operator_type: OperatorType,
+        operator_address: Address,
     ) {
         let number_inflight_txs = storage
             .eth_sender_dal()
-            .get_inflight_txs(
-                self.operator_address(operator_type),
-                operator_type == OperatorType::Gateway,
-            )
+            .get_inflight_txs(operator_address, operator_type == OperatorType::Gateway)
             .await
             .unwrap()
             .len();
@@ -772,7 +832,7 @@ impl EthTxManager {
                 .eth_sender_dal()
                 .get_new_eth_txs(
                     number_of_available_slots_for_eth_txs,
-                    self.operator_address(operator_type),
+                    operator_address,
                     operator_type == OperatorType::Gateway,
                 )
                 .await
@@ -780,11 +840,13 @@ impl EthTxManager {
 
             if !new_eth_tx.is_empty() {
                 tracing::info!(
-                    "Sending {} {operator_type:?} new transactions",
+                    "Sending {} {operator_type:?} new transactions from {operator_address:?}",
                     new_eth_tx.len()
                 );
             } else {
-                tracing::debug!("No new {operator_type:?} transactions to send");
+                tracing::debug!(
+                    "No new {operator_type:?} transactions to send from {operator_address:?}"
+                );
             }
             for tx in new_eth_tx {
                 let result = self.send_eth_tx(storage, &tx, 0, current_block).await;
@@ -805,9 +867,15 @@ impl EthTxManager {
         storage: &mut Connection<'_, Core>,
         l1_block_numbers: L1BlockNumbers,
         operator_type: OperatorType,
+        operator_address: Address,
     ) -> Result<(), EthSenderError> {
         if let Some((tx, sent_at_block)) = self
-            .monitor_inflight_transactions_single_operator(storage, l1_block_numbers, operator_type)
+            .monitor_inflight_transactions_single_operator(
+                storage,
+                l1_block_numbers,
+                operator_type,
+                operator_address,
+            )
             .await?
         {
             // New gas price depends on the time this tx spent in mempool.
@@ -826,35 +894,112 @@ impl EthTxManager {
 
     #[tracing::instrument(skip_all, name = "EthTxManager::loop_iteration")]
     pub async fn loop_iteration(&mut self, storage: &mut Connection<'_, Core>) {
-        // We can treat blob and non-blob operators independently as they have different nonces and
-        // aggregator makes sure that corresponding Commit transaction is confirmed before creating
-        // a PublishProof transaction
+        // We can treat operator accounts independently as they have different nonces and
+        // aggregator makes sure that the dependency of an operation (e.g., Commit transaction for
+        // a PublishProof transaction) is confirmed before creating it if they may be sent from different accounts.
         for operator_type in self.l1_interface.supported_operator_types() {
             let l1_block_numbers = self
                 .l1_interface
                 .get_l1_block_numbers(operator_type)
                 .await
                 .unwrap();
+            for operator_address in self.l1_interface.get_operator_accounts(operator_type) {
+                tracing::debug!(
+                    "Loop iteration at block {} for {operator_type:?} operator {operator_address:?}",
+                    l1_block_numbers.latest
+                );
+                self.send_new_eth_txs(
+                    storage,
+                    l1_block_numbers.latest,
+                    operator_type,
+                    operator_address,
+                )
+                .await;
+                let result = self
+                    .update_statuses_and_resend_if_needed(
+                        storage,
+                        l1_block_numbers,
+                        operator_type,
+                        operator_address,
+                    )
+                    .await;
+
+                // We don't want an error in sending transactions from one account to interrupt sending txs from other accounts
+                if let Err(error) = result {
+                    // Web3 API request failures can cause this,
+                    // and anything more important is already properly reported.
+                    tracing::warn!("eth_sender error {:?}", error);
+                    if error.is_retriable() {
+                        METRICS.l1_transient_errors.inc();
+                    }
+                }
+            }
+        }
+
+        if let Err(error) = self.track_retired_operators(storage).await {
+            tracing::warn!("eth_sender error {:?}", error);
+            if error.is_retriable() {
+                METRICS.l1_transient_errors.inc();
+            }
+        }
+    }
+
+    /// Tracks transactions of L1 operator accounts that were removed from the configuration while they
+    /// still had unconfirmed or non-finalized transactions. Statuses of such transactions are updated as usual,
+    /// but they are never (re)sent since there's no signer for them anymore.
+    async fn track_retired_operators(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<(), EthSenderError> {
+        let operator_types = self.l1_interface.supported_operator_types();
+        if operator_types.contains(&OperatorType::Gateway) {
+            return Ok(());
+        }
+        let known_accounts: Vec<_> = operator_types
+            .iter()
+            .flat_map(|&operator_type| self.l1_interface.get_operator_accounts(operator_type))
+            .collect();
+        let retired_accounts = storage
+            .eth_sender_dal()
+            .get_unfinalized_tx_senders(false)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|address| !known_accounts.contains(address))
+            .collect::<Vec<_>>();
+        if retired_accounts.is_empty() {
+            return Ok(());
+        }
+
+        let operator_type = self.retired_operators_type();
+        let l1_block_numbers = self
+            .l1_interface
+            .get_l1_block_numbers(operator_type)
+            .await?;
+        for operator_address in retired_accounts {
             tracing::debug!(
-                "Loop iteration at block {} for {operator_type:?} operator",
+                "Loop iteration at block {} for retired operator {operator_address:?}",
                 l1_block_numbers.latest
             );
-            self.send_new_eth_txs(storage, l1_block_numbers.latest, operator_type)
-                .await;
-            let result = self
-                .update_statuses_and_resend_if_needed(storage, l1_block_numbers, operator_type)
-                .await;
-
-            //We don't want an error in sending non-blob transactions interrupt sending blob txs
-            if let Err(error) = result {
-                // Web3 API request failures can cause this,
-                // and anything more important is already properly reported.
-                tracing::warn!("eth_sender error {:?}", error);
-                if error.is_retriable() {
-                    METRICS.l1_transient_errors.inc();
-                }
+            let tx_to_resend = self
+                .monitor_inflight_transactions_single_operator(
+                    storage,
+                    l1_block_numbers,
+                    operator_type,
+                    operator_address,
+                )
+                .await?;
+            if let Some((tx, _)) = tx_to_resend {
+                tracing::error!(
+                    "eth_tx {} (nonce {}) is assigned to operator account {operator_address:?} that is not \
+                     configured and cannot be sent; add the account back to the configuration until all its \
+                     transactions are finalized",
+                    tx.id,
+                    tx.nonce
+                );
             }
         }
+        Ok(())
     }
 
     /// Returns the health check for eth tx manager.
@@ -863,7 +1008,7 @@ impl EthTxManager {
     }
 }
 
-fn derive_l1_block_cap(multiplier_cap: u32, b: f64) -> u32 {
+pub(crate) fn derive_l1_block_cap(multiplier_cap: u32, b: f64) -> u32 {
     (multiplier_cap as f64).log(b).ceil() as u32
 }
 
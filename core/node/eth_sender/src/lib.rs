@@ -1,12 +1,16 @@
 mod aggregated_operations;
 mod aggregator;
+mod env;
 mod error;
 mod eth_tx_aggregator;
 mod eth_tx_manager;
 mod health;
 mod metrics;
 pub mod node;
+mod operator_pool;
 mod publish_criterion;
+#[cfg(any(test, feature = "replay"))]
+pub mod replay;
 mod zksync_functions;
 
 mod abstract_l1_interface;
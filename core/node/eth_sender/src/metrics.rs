@@ -247,3 +247,7 @@ impl EthSenderMetrics {
 
 #[vise::register]
 pub(super) static METRICS: vise::Global<EthSenderMetrics> = vise::Global::new();
+
+/// Metrics reported by replays. Not registered, so that simulated decisions are never exported.
+#[cfg(any(test, feature = "replay"))]
+pub(super) static REPLAY_METRICS: vise::Global<EthSenderMetrics> = vise::Global::new();
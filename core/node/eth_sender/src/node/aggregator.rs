@@ -5,8 +5,8 @@ use zksync_circuit_breaker::{l1_txs::FailedL1TransactionChecker, CircuitBreakers
 use zksync_dal::node::{MasterPool, PoolResource, ReplicaPool};
 use zksync_eth_client::{
     node::{
-        contracts::SettlementLayerContractsResource, BoundEthInterfaceForBlobsResource,
-        BoundEthInterfaceForL2Resource, SenderConfigResource,
+        contracts::SettlementLayerContractsResource, AdditionalOperatorsResource,
+        BoundEthInterfaceForBlobsResource, BoundEthInterfaceForL2Resource, SenderConfigResource,
     },
     web3_decl::node::SettlementModeResource,
     BoundEthInterface,
@@ -35,6 +35,7 @@ use crate::{Aggregator, EthTxAggregator};
 /// - `PoolResource<ReplicaPool>`
 /// - `BoundEthInterfaceResource`
 /// - `BoundEthInterfaceForBlobsResource` (optional)
+/// - `AdditionalOperatorsResource` (optional)
 /// - `ObjectStoreResource`
 /// - `CircuitBreakersResource` (adds a circuit breaker)
 ///
@@ -54,6 +55,7 @@ pub struct Input {
     eth_client: Option<Box<dyn BoundEthInterface>>,
     eth_client_blobs: Option<BoundEthInterfaceForBlobsResource>,
     eth_client_gateway: Option<BoundEthInterfaceForL2Resource>,
+    additional_operators: Option<AdditionalOperatorsResource>,
     object_store: Arc<dyn ObjectStore>,
     settlement_mode: SettlementModeResource,
     sender_config: SenderConfigResource,
@@ -115,14 +117,18 @@ impl WiringLayer for EthTxAggregatorLayer {
             .chain_contracts_config
             .diamond_proxy_addr;
 
-        let eth_client = if input.settlement_mode.settlement_layer().is_gateway() {
-            input
-                .eth_client_gateway
-                .context("eth_client_gateway missing")?
-                .0
-        } else {
-            input.eth_client.context("eth_client missing")?
-        };
+        let (eth_client, additional_operators) =
+            if input.settlement_mode.settlement_layer().is_gateway() {
+                let client = input
+                    .eth_client_gateway
+                    .context("eth_client_gateway missing")?
+                    .0;
+                // Additional operators are only supported when settling on L1.
+                (client, AdditionalOperatorsResource::default())
+            } else {
+                let client = input.eth_client.context("eth_client missing")?;
+                (client, input.additional_operators.unwrap_or_default())
+            };
 
         let master_pool = input.master_pool.get().await?;
         let replica_pool = input.replica_pool.get().await?;
@@ -149,6 +155,8 @@ impl WiringLayer for EthTxAggregatorLayer {
             aggregator,
             eth_client,
             eth_client_blobs,
+            additional_operators.operators,
+            additional_operators.blob_operators,
             validator_timelock_addr,
             state_transition_manager_address,
             multicall3_addr,
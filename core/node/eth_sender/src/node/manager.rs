@@ -4,7 +4,8 @@ use zksync_circuit_breaker::{l1_txs::FailedL1TransactionChecker, CircuitBreakers
 use zksync_dal::node::{MasterPool, PoolResource, ReplicaPool};
 use zksync_eth_client::{
     node::{
-        BoundEthInterfaceForBlobsResource, BoundEthInterfaceForL2Resource, SenderConfigResource,
+        AdditionalOperatorsResource, BoundEthInterfaceForBlobsResource,
+        BoundEthInterfaceForL2Resource, SenderConfigResource,
     },
     BoundEthInterface,
 };
@@ -30,6 +31,7 @@ use crate::EthTxManager;
 /// - `PoolResource<ReplicaPool>`
 /// - `BoundEthInterfaceResource`
 /// - `BoundEthInterfaceForBlobsResource` (optional)
+/// - `AdditionalOperatorsResource` (optional)
 /// - `TxParamsResource`
 /// - `CircuitBreakersResource` (adds a circuit breaker)
 ///
@@ -46,6 +48,7 @@ pub struct Input {
     eth_client: Box<dyn BoundEthInterface>,
     eth_client_blobs: Option<BoundEthInterfaceForBlobsResource>,
     eth_client_gateway: Option<BoundEthInterfaceForL2Resource>,
+    additional_operators: Option<AdditionalOperatorsResource>,
     gas_adjuster: Arc<GasAdjuster>,
     sender_config: SenderConfigResource,
     #[context(default)]
@@ -77,6 +80,7 @@ impl WiringLayer for EthTxManagerLayer {
         let eth_client = input.eth_client;
         let eth_client_blobs = input.eth_client_blobs.map(|c| c.0);
         let l2_client = input.eth_client_gateway.map(|c| c.0);
+        let additional_operators = input.additional_operators.unwrap_or_default();
 
         let eth_tx_manager = EthTxManager::new(
             master_pool,
@@ -85,6 +89,8 @@ impl WiringLayer for EthTxManagerLayer {
             Some(eth_client),
             eth_client_blobs,
             l2_client,
+            additional_operators.operators,
+            additional_operators.blob_operators,
         );
 
         // Insert circuit breaker.
@@ -0,0 +1,195 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use zksync_types::Address;
+
+/// Unconfirmed transaction that a new operation must be included after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct UnconfirmedDependency {
+    pub sender: Address,
+    pub nonce: u64,
+}
+
+/// Pool of operator accounts taking turns in sending L1 transactions of a certain role
+/// (e.g., commit transactions for the blob operator pool).
+///
+/// Each account has its own nonce sequence, so the pool doesn't by itself guarantee any ordering
+/// of transactions sent from different accounts. A new operation depends on the latest unconfirmed operation
+/// of the same type and, for prove and execute operations, on unconfirmed commit / prove operations for the same L1 batches.
+/// If there are no such dependencies, accounts are selected round-robin, preferring accounts with fewer unconfirmed
+/// transactions (so that an account with a stuck nonce doesn't receive new operations). Otherwise, the operation
+/// is appended to the account sending the dependencies, but only if they are the latest transactions of this account;
+/// if the account has other transactions queued after the dependencies (which may be stuck), the operation
+/// is postponed until the dependencies are confirmed.
+#[derive(Debug)]
+pub(crate) struct OperatorPool {
+    addresses: Vec<Address>,
+    next_index: AtomicUsize,
+}
+
+impl OperatorPool {
+    pub fn new(addresses: Vec<Address>) -> Self {
+        assert!(!addresses.is_empty(), "operator pool cannot be empty");
+        Self {
+            addresses,
+            next_index: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn addresses(&self) -> &[Address] {
+        &self.addresses
+    }
+
+    pub fn contains(&self, address: Address) -> bool {
+        self.addresses.contains(&address)
+    }
+
+    /// Selects an account to send a new operation from. Returns `None` if the operation should be postponed
+    /// until its dependencies are confirmed.
+    ///
+    /// - `dependencies` are unconfirmed transactions the operation must be included after.
+    /// - `unconfirmed_txs` contains the number of unconfirmed transactions and the greatest nonce among them
+    ///   for each sender.
+    pub fn select_sender(
+        &self,
+        dependencies: &[UnconfirmedDependency],
+        unconfirmed_txs: &HashMap<Address, (usize, u64)>,
+    ) -> Option<Address> {
+        if let [address] = self.addresses.as_slice() {
+            // Nonces of a single account guarantee the ordering of all operations.
+            return Some(*address);
+        }
+
+        if let Some(first_dependency) = dependencies.first() {
+            let sender = first_dependency.sender;
+            if !self.contains(sender) || dependencies.iter().any(|dep| dep.sender != sender) {
+                // Dependencies cannot be ordered by nonces of a single account from the pool.
+                return None;
+            }
+            let last_dependency_nonce = dependencies.iter().map(|dep| dep.nonce).max().unwrap();
+            let is_latest_on_sender = unconfirmed_txs
+                .get(&sender)
+                .is_none_or(|&(_, latest_nonce)| latest_nonce <= last_dependency_nonce);
+            return is_latest_on_sender.then_some(sender);
+        }
+
+        let start = self.next_index.load(Ordering::Relaxed);
+        let len = self.addresses.len();
+        let (selected_index, _) = (start..start + len)
+            .map(|i| i % len)
+            .map(|i| {
+                let count = unconfirmed_txs
+                    .get(&self.addresses[i])
+                    .map_or(0, |&(count, _)| count);
+                (i, count)
+            })
+            .min_by_key(|&(_, count)| count)
+            .unwrap(); // `addresses` is non-empty
+        self.next_index
+            .store((selected_index + 1) % len, Ordering::Relaxed);
+        Some(self.addresses[selected_index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESSES: [Address; 3] = [
+        Address::repeat_byte(1),
+        Address::repeat_byte(2),
+        Address::repeat_byte(3),
+    ];
+
+    fn dependency(sender: Address, nonce: u64) -> UnconfirmedDependency {
+        UnconfirmedDependency { sender, nonce }
+    }
+
+    #[test]
+    fn selecting_sender_round_robin() {
+        let pool = OperatorPool::new(ADDRESSES.to_vec());
+        let txs = HashMap::new();
+        let selected: Vec<_> = (0..4)
+            .map(|_| pool.select_sender(&[], &txs).unwrap())
+            .collect();
+        assert_eq!(
+            selected,
+            [ADDRESSES[0], ADDRESSES[1], ADDRESSES[2], ADDRESSES[0]]
+        );
+    }
+
+    #[test]
+    fn selecting_sender_with_unconfirmed_dependency() {
+        let pool = OperatorPool::new(ADDRESSES.to_vec());
+        let txs = HashMap::from([(ADDRESSES[2], (2, 5))]);
+        for _ in 0..3 {
+            assert_eq!(
+                pool.select_sender(&[dependency(ADDRESSES[2], 5)], &txs),
+                Some(ADDRESSES[2])
+            );
+        }
+        // Several dependencies on the same account
+        let deps = [dependency(ADDRESSES[2], 4), dependency(ADDRESSES[2], 5)];
+        assert_eq!(pool.select_sender(&deps, &txs), Some(ADDRESSES[2]));
+
+        // Dependencies on different accounts cannot be ordered.
+        let deps = [dependency(ADDRESSES[0], 1), dependency(ADDRESSES[2], 5)];
+        assert_eq!(pool.select_sender(&deps, &txs), None);
+        // Senders outside the pool (e.g., after a key rotation) cannot be used.
+        let unknown_sender = Address::repeat_byte(0xff);
+        assert_eq!(
+            pool.select_sender(&[dependency(unknown_sender, 0)], &txs),
+            None
+        );
+    }
+
+    #[test]
+    fn stuck_account_only_receives_dependent_operations() {
+        let pool = OperatorPool::new(ADDRESSES.to_vec());
+        // The first account has a stuck transaction with nonce 3 and transactions queued after it.
+        let txs = HashMap::from([(ADDRESSES[0], (5, 7)), (ADDRESSES[1], (1, 0))]);
+
+        // Independent operations avoid the stuck account.
+        assert_eq!(pool.select_sender(&[], &txs), Some(ADDRESSES[2]));
+        // An operation depending on a transaction queued in the middle of the stuck account is postponed,
+        // rather than being queued after unrelated transactions.
+        assert_eq!(
+            pool.select_sender(&[dependency(ADDRESSES[0], 5)], &txs),
+            None
+        );
+        // An operation depending on the latest transaction of the account cannot be sent from elsewhere anyway.
+        assert_eq!(
+            pool.select_sender(&[dependency(ADDRESSES[0], 7)], &txs),
+            Some(ADDRESSES[0])
+        );
+        // Once the dependency is confirmed, the operation is free to move to another account.
+        assert_ne!(pool.select_sender(&[], &txs), Some(ADDRESSES[0]));
+    }
+
+    #[test]
+    fn selecting_sender_skips_busy_accounts() {
+        let pool = OperatorPool::new(ADDRESSES.to_vec());
+        // The first account has a stuck transaction.
+        let txs = HashMap::from([(ADDRESSES[0], (5, 4)), (ADDRESSES[1], (1, 0))]);
+        assert_eq!(pool.select_sender(&[], &txs), Some(ADDRESSES[2]));
+        // All accounts are busy; the least loaded one should be selected.
+        let txs = HashMap::from([
+            (ADDRESSES[0], (5, 4)),
+            (ADDRESSES[1], (1, 0)),
+            (ADDRESSES[2], (2, 1)),
+        ]);
+        assert_eq!(pool.select_sender(&[], &txs), Some(ADDRESSES[1]));
+    }
+
+    #[test]
+    fn single_account_pool() {
+        let pool = OperatorPool::new(vec![ADDRESSES[0]]);
+        let txs = HashMap::from([(ADDRESSES[0], (5, 4))]);
+        assert_eq!(
+            pool.select_sender(&[dependency(ADDRESSES[1], 0)], &txs),
+            Some(ADDRESSES[0])
+        );
+    }
+}
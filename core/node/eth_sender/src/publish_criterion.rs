@@ -1,14 +1,13 @@
 use std::{fmt, ops, time::Duration};
 
 use async_trait::async_trait;
-use chrono::Utc;
 use zksync_dal::{Connection, Core, CoreDal};
 use zksync_types::{
     aggregated_operations::L1BatchAggregatedActionType, commitment::L1BatchWithMetadata,
     L1BatchNumber,
 };
 
-use super::metrics::METRICS;
+use super::env::AggregatorEnv;
 
 #[async_trait]
 pub trait L1BatchPublishCriterion: fmt::Debug + Send + Sync {
@@ -16,6 +15,9 @@ pub trait L1BatchPublishCriterion: fmt::Debug + Send + Sync {
     // Takes `&self` receiver for the trait to be object-safe
     fn name(&self) -> &'static str;
 
+    /// Returns the operation and the reason type reported to metrics if this criterion limits published L1 batches.
+    fn aggregation_reason(&self) -> (L1BatchAggregatedActionType, &'static str);
+
     /// Returns `None` if there is no need to publish any L1 batches.
     /// Otherwise, returns the number of the last L1 batch that needs to be published.
     async fn last_l1_batch_to_publish(
@@ -40,6 +42,10 @@ impl L1BatchPublishCriterion for NumberCriterion {
         "l1_batch_number"
     }
 
+    fn aggregation_reason(&self) -> (L1BatchAggregatedActionType, &'static str) {
+        (self.op, "number")
+    }
+
     async fn last_l1_batch_to_publish(
         &mut self,
         _storage: &mut Connection<'_, Core>,
@@ -62,7 +68,6 @@ impl L1BatchPublishCriterion for NumberCriterion {
                 self.op,
                 first..=result.0
             );
-            METRICS.block_aggregation_reason[&(self.op, "number").into()].inc();
             Some(result)
         } else {
             None
@@ -79,6 +84,7 @@ pub struct TimestampDeadlineCriterion {
     /// it means that sender is lagging significantly and we shouldn't apply this criteria to use all capacity
     /// and avoid packing small ranges.
     pub max_allowed_lag: Option<usize>,
+    pub env: AggregatorEnv,
 }
 
 #[async_trait]
@@ -87,6 +93,10 @@ impl L1BatchPublishCriterion for TimestampDeadlineCriterion {
         "timestamp"
     }
 
+    fn aggregation_reason(&self) -> (L1BatchAggregatedActionType, &'static str) {
+        (self.op, "timestamp")
+    }
+
     async fn last_l1_batch_to_publish(
         &mut self,
         _storage: &mut Connection<'_, Core>,
@@ -102,7 +112,7 @@ impl L1BatchPublishCriterion for TimestampDeadlineCriterion {
             }
         }
         let oldest_l1_batch_age_seconds =
-            Utc::now().timestamp() as u64 - first_l1_batch.header.timestamp;
+            (self.env.now_secs() as u64).saturating_sub(first_l1_batch.header.timestamp);
         if oldest_l1_batch_age_seconds >= self.deadline.as_secs() {
             let result = consecutive_l1_batches
                 .last()
@@ -114,7 +124,6 @@ impl L1BatchPublishCriterion for TimestampDeadlineCriterion {
                 self.op,
                 first_l1_batch.header.number.0..=result.0
             );
-            METRICS.block_aggregation_reason[&(self.op, "timestamp").into()].inc();
             Some(result)
         } else {
             None
@@ -213,6 +222,10 @@ impl L1BatchPublishCriterion for L1GasCriterion {
         "gas_limit"
     }
 
+    fn aggregation_reason(&self) -> (L1BatchAggregatedActionType, &'static str) {
+        (self.kind.into(), "gas")
+    }
+
     async fn last_l1_batch_to_publish(
         &mut self,
         storage: &mut Connection<'_, Core>,
@@ -278,7 +291,6 @@ impl L1BatchPublishCriterion for L1GasCriterion {
                 op,
                 first_l1_batch_number..=last_l1_batch.0
             );
-            METRICS.block_aggregation_reason[&(op, "gas").into()].inc();
         }
         last_l1_batch
     }
@@ -0,0 +1,869 @@
+//! Offline what-if replay of `eth_sender` decisions against historical L1 fees.
+//!
+//! The replay re-aggregates historical L1 batches with the publish criteria of [`Aggregator`] and prices
+//! the resulting transactions with [`GasAdjusterFeesOracle`], which observes a recorded L1 fee history served by
+//! [`MockSettlementLayer`]. L1 blocks are simulated one by one: a transaction is included once its max fees cover
+//! the fees of the block, and is resent with bumped fees otherwise.
+//!
+//! The eth_sender state (L1 transactions and their attempts) is simulated in memory. Postgres is only read
+//! in a single readonly transaction, so the replay doesn't interfere with a running eth_sender and can be run
+//! against a read replica. Likewise, the replay reports to detached metrics that are never exported.
+//!
+//! The module is only available with the `replay` feature, so that it isn't a part of production builds.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt, ops,
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use zksync_config::configs::eth_sender::{GasAdjusterConfig, ProofSendingMode, SenderConfig};
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_eth_client::{
+    clients::{DynClient, MockSettlementLayer, L1},
+    BaseFees,
+};
+use zksync_node_fee_model::l1_gas_price::{GasAdjuster, GasAdjusterClient, TxParamsProvider};
+use zksync_object_store::MockObjectStore;
+use zksync_types::{
+    aggregated_operations::L1BatchAggregatedActionType,
+    commitment::{L1BatchCommitmentMode, L1BatchWithMetadata},
+    eth_sender::{EthTxFinalityStatus, TxHistory},
+    pubdata_da::PubdataSendingMode,
+    settlement::SettlementLayer,
+    Address, L1BatchNumber, SLChainId, H256,
+};
+
+use crate::{
+    abstract_l1_interface::OperatorType,
+    env::{AggregatorEnv, SimulatedEnv},
+    eth_fees_oracle::{EthFeesOracle, GasAdjusterFeesOracle},
+    eth_tx_manager::derive_l1_block_cap,
+    publish_criterion::L1GasCriterion,
+    Aggregator,
+};
+
+/// Blob gas consumed by a single EIP-4844 blob.
+const GAS_PER_BLOB: u64 = 131_072;
+/// Number of pubdata bytes packed into a single blob (31 bytes per each of 4096 field elements).
+const PUBDATA_BYTES_PER_BLOB: usize = 31 * 4_096;
+/// Calldata gas per a non-zero byte; pubdata is assumed to be incompressible.
+const CALLDATA_GAS_PER_BYTE: u64 = 16;
+
+const OPERATOR_ADDRESS: Address = Address::repeat_byte(1);
+const BLOB_OPERATOR_ADDRESS: Address = Address::repeat_byte(2);
+
+/// Parameters shared by all variants of a replay.
+#[derive(Debug, Clone)]
+pub struct ReplayParams {
+    /// Range of historical L1 batches to replay. Batches are sealed at their original timestamps.
+    pub l1_batches: ops::RangeInclusive<L1BatchNumber>,
+    /// Recorded L1 fees, one entry per L1 block. The first entries are used to warm up the gas adjuster;
+    /// the block following them is aligned with the timestamp of the first replayed batch.
+    pub fee_history: Vec<BaseFees>,
+    /// Interval between simulated L1 blocks.
+    pub l1_block_time: Duration,
+    /// Number of L1 blocks after inclusion until a transaction is considered finalized.
+    pub finality_blocks: u64,
+    pub commitment_mode: L1BatchCommitmentMode,
+}
+
+/// Configuration variant to replay.
+#[derive(Debug, Clone)]
+pub struct ReplayVariant {
+    pub name: String,
+    pub sender: SenderConfig,
+    pub gas_adjuster: GasAdjusterConfig,
+}
+
+/// L1 spend in wei.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct L1Spend {
+    pub base_fee: u128,
+    pub priority_fee: u128,
+    pub blob_fee: u128,
+}
+
+impl L1Spend {
+    pub fn total(&self) -> u128 {
+        self.base_fee + self.priority_fee + self.blob_fee
+    }
+}
+
+impl ops::AddAssign for L1Spend {
+    fn add_assign(&mut self, rhs: Self) {
+        self.base_fee += rhs.base_fee;
+        self.priority_fee += rhs.priority_fee;
+        self.blob_fee += rhs.blob_fee;
+    }
+}
+
+/// Statistics collected for a single operation type.
+#[derive(Debug, Clone, Default)]
+pub struct OperationStats {
+    /// Number of L1 transactions.
+    pub tx_count: usize,
+    /// Number of sent transaction attempts, including resends.
+    pub attempt_count: usize,
+    pub spend: L1Spend,
+    /// Latencies between the L1 batch timestamp and finalization of the operation for this batch.
+    pub finality_latencies: Vec<Duration>,
+}
+
+impl OperationStats {
+    fn latency_percentile(&self, percentile: usize) -> Option<Duration> {
+        let mut latencies = self.finality_latencies.clone();
+        latencies.sort_unstable();
+        let index = (latencies.len() * percentile / 100).min(latencies.len().checked_sub(1)?);
+        Some(latencies[index])
+    }
+}
+
+/// Outcome of replaying a single variant.
+#[derive(Debug, Clone)]
+pub struct ReplayReport {
+    pub variant: String,
+    /// Number of simulated L1 blocks.
+    pub l1_blocks: u64,
+    pub operations: HashMap<L1BatchAggregatedActionType, OperationStats>,
+    /// Number of replayed L1 batches that weren't executed and finalized by the end of the fee history.
+    pub unfinalized_l1_batches: u32,
+}
+
+impl ReplayReport {
+    pub fn total_spend(&self) -> L1Spend {
+        let mut total = L1Spend::default();
+        for stats in self.operations.values() {
+            total += stats.spend;
+        }
+        total
+    }
+}
+
+impl fmt::Display for ReplayReport {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const WEI_IN_GWEI: u128 = 1_000_000_000;
+
+        let total = self.total_spend();
+        writeln!(formatter, "Variant `{}`", self.variant)?;
+        writeln!(
+            formatter,
+            "  L1 blocks: {}, unfinalized L1 batches: {}",
+            self.l1_blocks, self.unfinalized_l1_batches
+        )?;
+        writeln!(
+            formatter,
+            "  Total L1 spend: {} gwei (base: {}, priority: {}, blob: {})",
+            total.total() / WEI_IN_GWEI,
+            total.base_fee / WEI_IN_GWEI,
+            total.priority_fee / WEI_IN_GWEI,
+            total.blob_fee / WEI_IN_GWEI
+        )?;
+        let ops = [
+            L1BatchAggregatedActionType::Commit,
+            L1BatchAggregatedActionType::PublishProofOnchain,
+            L1BatchAggregatedActionType::Execute,
+        ];
+        for op in ops {
+            let Some(stats) = self.operations.get(&op) else {
+                continue;
+            };
+            write!(
+                formatter,
+                "  {op}: {} txs, {} attempts, {} gwei",
+                stats.tx_count,
+                stats.attempt_count,
+                stats.spend.total() / WEI_IN_GWEI
+            )?;
+            if let (Some(p50), Some(p95), Some(max)) = (
+                stats.latency_percentile(50),
+                stats.latency_percentile(95),
+                stats.finality_latencies.iter().max(),
+            ) {
+                write!(
+                    formatter,
+                    ", finality latency p50: {p50:?}, p95: {p95:?}, max: {max:?}"
+                )?;
+            }
+            writeln!(formatter)?;
+        }
+        Ok(())
+    }
+}
+
+/// Replayed L1 batch together with its simulated progress.
+#[derive(Debug)]
+struct ReplayedBatch {
+    batch: L1BatchWithMetadata,
+    /// Simulated time when the commit transaction for this batch was confirmed.
+    commit_confirmed_at_ms: Option<u64>,
+}
+
+impl ReplayedBatch {
+    fn timestamp(&self) -> u64 {
+        self.batch.header.timestamp
+    }
+}
+
+/// Transaction tracked by the simulated mempool.
+#[derive(Debug)]
+struct SimulatedTx {
+    eth_tx_id: u32,
+    op: L1BatchAggregatedActionType,
+    sender: Address,
+    l1_batches: ops::RangeInclusive<L1BatchNumber>,
+    gas_used: u64,
+    blob_count: u64,
+    first_sent_at_block: Option<u64>,
+    last_attempt: Option<TxHistory>,
+    included_at_block: Option<u64>,
+}
+
+impl SimulatedTx {
+    fn operator_type(&self) -> OperatorType {
+        if self.blob_count > 0 {
+            OperatorType::Blob
+        } else {
+            OperatorType::NonBlob
+        }
+    }
+}
+
+/// Computes the spend for including a transaction sent with the specified fees into a block with `block_fees`.
+/// Returns `None` if the transaction cannot be included into the block.
+fn inclusion_spend(
+    attempt: &TxHistory,
+    block_fees: &BaseFees,
+    gas_used: u64,
+    blob_count: u64,
+) -> Option<L1Spend> {
+    let max_fee_per_gas = attempt.base_fee_per_gas + attempt.priority_fee_per_gas;
+    let block_base_fee = block_fees.base_fee_per_gas;
+    if max_fee_per_gas < block_base_fee {
+        return None;
+    }
+    let block_blob_fee = u64::try_from(block_fees.base_fee_per_blob_gas).unwrap_or(u64::MAX);
+    if blob_count > 0 && attempt.blob_base_fee_per_gas.unwrap_or(0) < block_blob_fee {
+        return None;
+    }
+
+    let effective_priority_fee = attempt
+        .priority_fee_per_gas
+        .min(max_fee_per_gas - block_base_fee);
+    Some(L1Spend {
+        base_fee: u128::from(gas_used) * u128::from(block_base_fee),
+        priority_fee: u128::from(gas_used) * u128::from(effective_priority_fee),
+        blob_fee: u128::from(blob_count * GAS_PER_BLOB) * u128::from(block_blob_fee),
+    })
+}
+
+#[derive(Debug)]
+struct Replay<'a> {
+    params: &'a ReplayParams,
+    sender_config: SenderConfig,
+    env: Arc<SimulatedEnv>,
+    aggregator: Aggregator,
+    fees_oracle: GasAdjusterFeesOracle,
+    use_blob_operator: bool,
+    batches: BTreeMap<L1BatchNumber, ReplayedBatch>,
+    first_block: u64,
+    /// Last L1 batches for which commit, prove and execute operations were created.
+    last_committed: L1BatchNumber,
+    last_proven: L1BatchNumber,
+    last_executed: L1BatchNumber,
+    pending_txs: Vec<SimulatedTx>,
+    last_finalized_execute: Option<L1BatchNumber>,
+    next_eth_tx_id: u32,
+    next_attempt_id: u32,
+    report: ReplayReport,
+}
+
+impl Replay<'_> {
+    fn block_time_ms(&self, block: u64) -> u64 {
+        let first_timestamp = self
+            .batches
+            .values()
+            .next()
+            .map_or(0, ReplayedBatch::timestamp);
+        let elapsed_ms = (block - self.first_block) * self.params.l1_block_time.as_millis() as u64;
+        first_timestamp * 1_000 + elapsed_ms
+    }
+
+    /// Returns the last L1 batch sealed by the simulated time.
+    fn last_sealed_l1_batch(&self) -> L1BatchNumber {
+        let now_ms = self.env.now_ms();
+        self.batches
+            .iter()
+            .take_while(|(_, batch)| batch.timestamp() * 1_000 <= now_ms)
+            .map(|(&number, _)| number)
+            .last()
+            .unwrap_or(*self.params.l1_batches.start() - 1)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.last_finalized_execute == Some(*self.params.l1_batches.end())
+    }
+
+    /// Returns consecutive L1 batches for which an operation of the specified type can be created, mirroring
+    /// the readiness conditions used by [`Aggregator`] for the eth_sender state in Postgres.
+    fn unpublished_l1_batches(
+        &self,
+        op: L1BatchAggregatedActionType,
+        last_sealed_l1_batch: L1BatchNumber,
+    ) -> Vec<L1BatchWithMetadata> {
+        let now_ms = self.env.now_ms();
+        let (last_published, last_ready, limit) = match op {
+            // The commit operation is not aggregated.
+            L1BatchAggregatedActionType::Commit => (self.last_committed, last_sealed_l1_batch, 1),
+            L1BatchAggregatedActionType::PublishProofOnchain => {
+                (self.last_proven, self.last_committed, 1)
+            }
+            L1BatchAggregatedActionType::Execute => (
+                self.last_executed,
+                self.last_proven,
+                self.sender_config.max_aggregated_blocks_to_execute as usize,
+            ),
+        };
+        self.batches
+            .range(last_published + 1..)
+            .take_while(|&(&number, _)| number <= last_ready)
+            .take_while(|(_, batch)| match op {
+                L1BatchAggregatedActionType::Commit => true,
+                // In the 4844 mode, commit and prove transactions are sent from different accounts,
+                // so proofs are only sent for confirmed commits.
+                L1BatchAggregatedActionType::PublishProofOnchain => {
+                    !self.use_blob_operator || batch.commit_confirmed_at_ms.is_some()
+                }
+                L1BatchAggregatedActionType::Execute => batch
+                    .commit_confirmed_at_ms
+                    .is_some_and(|confirmed_at| confirmed_at < now_ms),
+            })
+            .take(limit)
+            .map(|(_, batch)| batch.batch.clone())
+            .collect()
+    }
+
+    async fn aggregate(&mut self, storage: &mut Connection<'_, Core>) -> anyhow::Result<()> {
+        // Same priority of operations as in `Aggregator::get_next_ready_operation()`.
+        const OPS: [L1BatchAggregatedActionType; 3] = [
+            L1BatchAggregatedActionType::Execute,
+            L1BatchAggregatedActionType::PublishProofOnchain,
+            L1BatchAggregatedActionType::Commit,
+        ];
+
+        'aggregation: loop {
+            let last_sealed_l1_batch = self.last_sealed_l1_batch();
+            for op in OPS {
+                let unpublished = self.unpublished_l1_batches(op, last_sealed_l1_batch);
+                if unpublished.is_empty() {
+                    continue;
+                }
+                let selected = self
+                    .aggregator
+                    .select_l1_batches(storage, op, unpublished, last_sealed_l1_batch)
+                    .await;
+                if let Some(l1_batches) = selected.filter(|batches| !batches.is_empty()) {
+                    self.save_tx(storage, op, &l1_batches).await?;
+                    continue 'aggregation;
+                }
+            }
+            return Ok(());
+        }
+    }
+
+    async fn save_tx(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        op_type: L1BatchAggregatedActionType,
+        l1_batches: &[L1BatchWithMetadata],
+    ) -> anyhow::Result<()> {
+        let first_batch = l1_batches.first().unwrap().header.number; // `l1_batches` is non-empty
+        let last_batch = l1_batches.last().unwrap().header.number;
+        let l1_batch_range = first_batch..=last_batch;
+        let mut blob_count = 0;
+        let gas_used = match op_type {
+            L1BatchAggregatedActionType::Commit => {
+                let (pubdata_sending_mode, _) = self
+                    .aggregator
+                    .get_commitment_modes(&l1_batches[0], storage)
+                    .await;
+                let pubdata_len: usize = l1_batches
+                    .iter()
+                    .map(|batch| batch.header.pubdata_input.as_ref().map_or(0, Vec::len))
+                    .sum();
+                let mut gas =
+                    L1GasCriterion::total_commit_validium_gas_amount(l1_batch_range.clone(), false);
+                match pubdata_sending_mode {
+                    PubdataSendingMode::Blobs => {
+                        blob_count = pubdata_len.div_ceil(PUBDATA_BYTES_PER_BLOB).max(1) as u64;
+                    }
+                    PubdataSendingMode::Calldata => {
+                        gas += pubdata_len as u64 * CALLDATA_GAS_PER_BYTE;
+                    }
+                    PubdataSendingMode::Custom | PubdataSendingMode::RelayedL2Calldata => {}
+                }
+                self.last_committed = last_batch;
+                gas
+            }
+            L1BatchAggregatedActionType::PublishProofOnchain => {
+                self.last_proven = last_batch;
+                L1GasCriterion::total_proof_gas_amount(false)
+            }
+            L1BatchAggregatedActionType::Execute => {
+                let mut dependency_roots_per_batch = Vec::with_capacity(l1_batches.len());
+                for batch in l1_batches {
+                    let interop_roots = storage
+                        .interop_root_dal()
+                        .get_interop_roots_batch(batch.header.number)
+                        .await?;
+                    dependency_roots_per_batch.push(interop_roots.len() as u64);
+                }
+                self.last_executed = last_batch;
+                L1GasCriterion::total_execute_gas_amount(
+                    storage,
+                    l1_batch_range.clone(),
+                    dependency_roots_per_batch,
+                    false,
+                )
+                .await
+            }
+        };
+
+        let sender = if op_type == L1BatchAggregatedActionType::Commit && self.use_blob_operator {
+            BLOB_OPERATOR_ADDRESS
+        } else {
+            OPERATOR_ADDRESS
+        };
+        self.next_eth_tx_id += 1;
+        let eth_tx_id = self.next_eth_tx_id;
+        tracing::debug!(
+            "Replayed operation {op_type} for L1 batches {l1_batch_range:?} as simulated eth_tx #{eth_tx_id}"
+        );
+        self.report.operations.entry(op_type).or_default().tx_count += 1;
+        self.pending_txs.push(SimulatedTx {
+            eth_tx_id,
+            op: op_type,
+            sender,
+            l1_batches: l1_batch_range,
+            gas_used,
+            blob_count,
+            first_sent_at_block: None,
+            last_attempt: None,
+            included_at_block: None,
+        });
+        Ok(())
+    }
+
+    /// Sends or resends pending transactions and includes them into the block if possible.
+    fn send_and_include(&mut self, block: u64, block_fees: &BaseFees) {
+        let mut inflight_counts = HashMap::<Address, u64>::new();
+        // Senders that have a transaction not included into this block; further transactions cannot be included
+        // because of the nonce gap.
+        let mut stuck_senders = vec![];
+
+        for tx in &mut self.pending_txs {
+            if tx.included_at_block.is_some() {
+                continue;
+            }
+            let inflight_count = inflight_counts.entry(tx.sender).or_default();
+            if tx.last_attempt.is_none() && *inflight_count >= self.sender_config.max_txs_in_flight
+            {
+                stuck_senders.push(tx.sender);
+                continue;
+            }
+
+            let time_in_mempool_in_l1_blocks =
+                tx.first_sent_at_block.map_or(0, |sent_at| block - sent_at) as u32;
+            match self.fees_oracle.calculate_fees(
+                &tx.last_attempt,
+                time_in_mempool_in_l1_blocks,
+                tx.operator_type(),
+            ) {
+                Ok(fees) => {
+                    self.next_attempt_id += 1;
+                    tx.first_sent_at_block.get_or_insert(block);
+                    tx.last_attempt = Some(TxHistory {
+                        id: self.next_attempt_id,
+                        eth_tx_id: tx.eth_tx_id,
+                        chain_id: None,
+                        tx_type: tx.op.into(),
+                        base_fee_per_gas: fees.base_fee_per_gas,
+                        priority_fee_per_gas: fees.priority_fee_per_gas,
+                        blob_base_fee_per_gas: fees.blob_base_fee_per_gas,
+                        tx_hash: H256::from_low_u64_be(self.next_attempt_id.into()),
+                        signed_raw_tx: vec![],
+                        sent_at_block: Some(block as u32),
+                        max_gas_per_pubdata: None,
+                        eth_tx_finality_status: EthTxFinalityStatus::Pending,
+                        sent_successfully: true,
+                    });
+                    self.report
+                        .operations
+                        .entry(tx.op)
+                        .or_default()
+                        .attempt_count += 1;
+                }
+                Err(err) => {
+                    // Fees weren't bumped enough to resend the transaction, or are above the configured limit.
+                    tracing::debug!("Not (re)sending eth_tx #{}: {err}", tx.eth_tx_id);
+                }
+            }
+
+            let Some(attempt) = &tx.last_attempt else {
+                stuck_senders.push(tx.sender);
+                continue;
+            };
+            let spend = (!stuck_senders.contains(&tx.sender))
+                .then(|| inclusion_spend(attempt, block_fees, tx.gas_used, tx.blob_count))
+                .flatten();
+            if let Some(spend) = spend {
+                tx.included_at_block = Some(block);
+                self.report.operations.entry(tx.op).or_default().spend += spend;
+            } else {
+                *inflight_count += 1;
+                stuck_senders.push(tx.sender);
+            }
+        }
+    }
+
+    /// Confirms transactions that have reached finality.
+    fn finalize(&mut self, block: u64) {
+        let finality_blocks = self.params.finality_blocks;
+        let now_ms = self.block_time_ms(block);
+        let (finalized, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending_txs)
+            .into_iter()
+            .partition(|tx| {
+                tx.included_at_block
+                    .is_some_and(|included_at| included_at + finality_blocks <= block)
+            });
+        self.pending_txs = pending;
+
+        for tx in finalized {
+            let stats = self.report.operations.entry(tx.op).or_default();
+            for number in tx.l1_batches.start().0..=tx.l1_batches.end().0 {
+                let Some(batch) = self.batches.get_mut(&L1BatchNumber(number)) else {
+                    continue;
+                };
+                if tx.op == L1BatchAggregatedActionType::Commit {
+                    batch.commit_confirmed_at_ms = Some(now_ms);
+                }
+                let latency_ms = now_ms.saturating_sub(batch.timestamp() * 1_000);
+                stats
+                    .finality_latencies
+                    .push(Duration::from_millis(latency_ms));
+            }
+            if tx.op == L1BatchAggregatedActionType::Execute {
+                self.last_finalized_execute = Some(*tx.l1_batches.end());
+            }
+        }
+    }
+}
+
+/// Replays eth_sender decisions for the specified config variant. Postgres is only read, in a single readonly
+/// transaction, so that the replay observes a consistent snapshot of the database.
+pub async fn replay(
+    pool: &ConnectionPool<Core>,
+    params: &ReplayParams,
+    variant: &ReplayVariant,
+) -> anyhow::Result<ReplayReport> {
+    let (first_batch, last_batch) = (*params.l1_batches.start(), *params.l1_batches.end());
+    anyhow::ensure!(
+        first_batch > L1BatchNumber(0) && first_batch <= last_batch,
+        "invalid L1 batch range to replay: {:?}",
+        params.l1_batches
+    );
+
+    let mut connection = pool.connection_tagged("eth_sender_replay").await?;
+    let mut storage = connection
+        .transaction_builder()?
+        .set_readonly()
+        .build()
+        .await?;
+    let mut batches = BTreeMap::new();
+    for number in first_batch.0..=last_batch.0 {
+        let number = L1BatchNumber(number);
+        let batch = storage
+            .blocks_dal()
+            .get_l1_batch_metadata_with_prover(number, variant.sender.prover)
+            .await?
+            .with_context(|| format!("L1 batch #{number} is not in Postgres or has no metadata"))?;
+        batches.insert(
+            number,
+            ReplayedBatch {
+                batch,
+                commit_confirmed_at_ms: None,
+            },
+        );
+    }
+
+    let gas_adjuster_config = GasAdjusterConfig {
+        // External fee sources reflect current fees rather than historical ones.
+        fee_sources: None,
+        ..variant.gas_adjuster.clone()
+    };
+    let first_block = gas_adjuster_config
+        .max_base_fee_samples
+        .max(gas_adjuster_config.num_samples_for_blob_base_fee_estimate)
+        as u64;
+    anyhow::ensure!(
+        params.fee_history.len() as u64 > first_block,
+        "fee history is too short: {} L1 blocks, while at least {first_block} are needed to warm up gas adjuster",
+        params.fee_history.len()
+    );
+    let settlement_layer = MockSettlementLayer::builder()
+        .with_fee_history(params.fee_history.clone())
+        .build();
+    settlement_layer.advance_block_number(first_block, EthTxFinalityStatus::Finalized);
+    let client: Box<DynClient<L1>> = Box::new(settlement_layer.clone().into_client());
+    let gas_adjuster = Arc::new(
+        GasAdjuster::new(
+            GasAdjusterClient::from(client),
+            gas_adjuster_config,
+            variant.sender.pubdata_sending_mode,
+            params.commitment_mode,
+            pool.clone(),
+        )
+        .await
+        .context("failed creating gas adjuster")?
+        .with_detached_metrics(),
+    );
+
+    let sender_config = SenderConfig {
+        // Proofs aren't simulated; proof operations are priced as if real proofs were sent.
+        proof_sending_mode: ProofSendingMode::SkipEveryProof,
+        ..variant.sender.clone()
+    };
+    let time_in_mempool_in_l1_blocks_cap = match sender_config.time_in_mempool_multiplier_cap {
+        Some(multiplier_cap) => derive_l1_block_cap(multiplier_cap, gas_adjuster.get_parameter_b()),
+        None => sender_config.time_in_mempool_in_l1_blocks_cap,
+    };
+    let fees_oracle = GasAdjusterFeesOracle {
+        gas_adjuster: gas_adjuster.clone(),
+        max_acceptable_priority_fee_in_gwei: sender_config.max_acceptable_priority_fee_in_gwei,
+        time_in_mempool_in_l1_blocks_cap,
+        max_acceptable_base_fee_in_wei: sender_config.max_acceptable_base_fee_in_wei,
+    };
+
+    let use_blob_operator = sender_config.pubdata_sending_mode == PubdataSendingMode::Blobs
+        && params.commitment_mode == L1BatchCommitmentMode::Rollup;
+    let env = Arc::new(SimulatedEnv::default());
+    let aggregator = Aggregator::with_env(
+        sender_config.clone(),
+        MockObjectStore::arc(),
+        use_blob_operator,
+        params.commitment_mode,
+        pool.clone(),
+        // The chain ID isn't used by the aggregator.
+        SettlementLayer::L1(SLChainId(0)),
+        AggregatorEnv::Simulated(env.clone()),
+    )
+    .await?;
+
+    let mut replay = Replay {
+        params,
+        sender_config,
+        env,
+        aggregator,
+        fees_oracle,
+        use_blob_operator,
+        batches,
+        first_block,
+        last_committed: first_batch - 1,
+        last_proven: first_batch - 1,
+        last_executed: first_batch - 1,
+        pending_txs: vec![],
+        last_finalized_execute: None,
+        next_eth_tx_id: 0,
+        next_attempt_id: 0,
+        report: ReplayReport {
+            variant: variant.name.clone(),
+            l1_blocks: 0,
+            operations: HashMap::new(),
+            unfinalized_l1_batches: 0,
+        },
+    };
+
+    for block in first_block..params.fee_history.len() as u64 {
+        replay.env.set_now_ms(replay.block_time_ms(block));
+        gas_adjuster.keep_updated().await?;
+        replay.aggregate(&mut storage).await?;
+        replay.send_and_include(block, &params.fee_history[block as usize]);
+        replay.finalize(block);
+        replay.report.l1_blocks += 1;
+        if replay.is_finished() {
+            break;
+        }
+        settlement_layer.advance_block_number(1, EthTxFinalityStatus::Finalized);
+    }
+
+    let last_finalized = replay.last_finalized_execute.unwrap_or(first_batch - 1);
+    replay.report.unfinalized_l1_batches = last_batch.0 - last_finalized.0;
+    Ok(replay.report)
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_config::configs::eth_sender::EthConfig;
+    use zksync_node_test_utils::{create_l1_batch, l1_batch_metadata_to_commitment_artifacts};
+    use zksync_types::{
+        protocol_version::{ProtocolSemanticVersion, VersionPatch},
+        ProtocolVersion, ProtocolVersionId,
+    };
+
+    use super::*;
+    use crate::tests::default_l1_batch_metadata;
+
+    fn attempt(base_fee: u64, priority_fee: u64, blob_fee: Option<u64>) -> TxHistory {
+        TxHistory {
+            id: 1,
+            eth_tx_id: 1,
+            chain_id: None,
+            tx_type: L1BatchAggregatedActionType::Commit.into(),
+            base_fee_per_gas: base_fee,
+            priority_fee_per_gas: priority_fee,
+            blob_base_fee_per_gas: blob_fee,
+            tx_hash: H256::zero(),
+            signed_raw_tx: vec![],
+            sent_at_block: Some(0),
+            max_gas_per_pubdata: None,
+            eth_tx_finality_status: EthTxFinalityStatus::Pending,
+            sent_successfully: true,
+        }
+    }
+
+    fn block_fees(base_fee: u64, blob_fee: u64) -> BaseFees {
+        BaseFees {
+            base_fee_per_gas: base_fee,
+            base_fee_per_blob_gas: blob_fee.into(),
+            l2_pubdata_price: 0.into(),
+        }
+    }
+
+    #[test]
+    fn computing_inclusion_spend() {
+        let spend = inclusion_spend(&attempt(100, 10, None), &block_fees(80, 1), 1_000, 0);
+        assert_eq!(
+            spend,
+            Some(L1Spend {
+                base_fee: 80_000,
+                priority_fee: 10_000,
+                blob_fee: 0,
+            })
+        );
+
+        // The priority fee is capped by the max fee per gas.
+        let spend = inclusion_spend(&attempt(100, 10, None), &block_fees(105, 1), 1_000, 0);
+        assert_eq!(spend.unwrap().priority_fee, 5_000);
+
+        let spend = inclusion_spend(&attempt(100, 10, Some(5)), &block_fees(80, 3), 1_000, 2);
+        assert_eq!(spend.unwrap().blob_fee, 3 * 2 * u128::from(GAS_PER_BLOB));
+    }
+
+    #[test]
+    fn underpriced_tx_is_not_included() {
+        assert_eq!(
+            inclusion_spend(&attempt(100, 10, None), &block_fees(111, 1), 1_000, 0),
+            None
+        );
+        assert_eq!(
+            inclusion_spend(&attempt(100, 10, Some(5)), &block_fees(80, 6), 1_000, 1),
+            None
+        );
+    }
+
+    async fn insert_l1_batches(pool: &ConnectionPool<Core>, numbers: ops::RangeInclusive<u32>) {
+        let mut storage = pool.connection().await.unwrap();
+        let protocol_version = ProtocolVersion {
+            version: ProtocolSemanticVersion {
+                minor: ProtocolVersionId::latest(),
+                patch: VersionPatch(0),
+            },
+            ..Default::default()
+        };
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&protocol_version)
+            .await
+            .unwrap();
+
+        for number in numbers {
+            let header = create_l1_batch(number);
+            storage
+                .blocks_dal()
+                .insert_mock_l1_batch(&header)
+                .await
+                .unwrap();
+            let metadata = default_l1_batch_metadata();
+            storage
+                .blocks_dal()
+                .save_l1_batch_tree_data(header.number, &metadata.tree_data())
+                .await
+                .unwrap();
+            storage
+                .blocks_dal()
+                .save_l1_batch_commitment_artifacts(
+                    header.number,
+                    &l1_batch_metadata_to_commitment_artifacts(&metadata),
+                )
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn replay_does_not_change_postgres() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        insert_l1_batches(&pool, 0..=3).await;
+
+        let EthConfig {
+            sender,
+            gas_adjuster,
+            ..
+        } = EthConfig::for_tests();
+        let gas_adjuster = GasAdjusterConfig {
+            max_base_fee_samples: 5,
+            num_samples_for_blob_base_fee_estimate: 5,
+            ..gas_adjuster
+        };
+        let params = ReplayParams {
+            l1_batches: L1BatchNumber(1)..=L1BatchNumber(3),
+            fee_history: vec![block_fees(1_000_000_000, 1); 100],
+            l1_block_time: Duration::from_secs(12),
+            finality_blocks: 2,
+            commitment_mode: L1BatchCommitmentMode::Rollup,
+        };
+        let variant = ReplayVariant {
+            name: "test".to_owned(),
+            sender,
+            gas_adjuster,
+        };
+
+        let report = replay(&pool, &params, &variant).await.unwrap();
+        assert_eq!(report.unfinalized_l1_batches, 0);
+        let commit_stats = &report.operations[&L1BatchAggregatedActionType::Commit];
+        assert_eq!(commit_stats.tx_count, 3);
+        assert_eq!(commit_stats.finality_latencies.len(), 3);
+        let execute_stats = &report.operations[&L1BatchAggregatedActionType::Execute];
+        assert_eq!(execute_stats.finality_latencies.len(), 3);
+        assert!(report.total_spend().total() > 0);
+
+        // The replay must not have saved any L1 transactions.
+        let mut storage = pool.connection().await.unwrap();
+        for number in 1..=3 {
+            let eth_tx_id = storage
+                .blocks_dal()
+                .get_eth_commit_tx_id(L1BatchNumber(number))
+                .await
+                .unwrap();
+            assert_eq!(eth_tx_id, None);
+        }
+        let last_committed = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_committed_on_eth()
+            .await
+            .unwrap();
+        assert_eq!(last_committed, None);
+    }
+}
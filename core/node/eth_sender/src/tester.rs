@@ -311,6 +311,8 @@ impl EthSenderTester {
             aggregator,
             gateway.clone(),
             use_blob_operator.then(|| gateway_blobs.clone() as Box<dyn BoundEthInterface>),
+            vec![],
+            vec![],
             // ZKsync contract address
             Address::random(),
             STATE_TRANSITION_MANAGER_CONTRACT_ADDRESS,
@@ -328,6 +330,8 @@ impl EthSenderTester {
             Some(gateway.clone()),
             Some(gateway_blobs.clone()),
             None,
+            vec![],
+            vec![],
         );
 
         let connection_pool_clone = connection_pool.clone();
@@ -373,6 +377,8 @@ impl EthSenderTester {
             None,
             None,
             Some(self.l2_gateway.clone()),
+            vec![],
+            vec![],
         );
         self.settlement_layer = SettlementLayer::Gateway(10.into());
         tracing::info!("Switched eth-sender tester to use Gateway!");
@@ -593,6 +599,7 @@ impl EthSenderTester {
             )
             .await
             .unwrap()
+            .expect("operation was postponed")
     }
 
     pub async fn send_tx(&mut self, tx: EthTx, confirm: bool) -> H256 {
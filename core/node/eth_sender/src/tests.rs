@@ -14,7 +14,7 @@ use zksync_l1_contract_interface::{
 };
 use zksync_node_test_utils::create_l1_batch;
 use zksync_types::{
-    aggregated_operations::L1BatchAggregatedActionType,
+    aggregated_operations::{AggregatedActionType, L1BatchAggregatedActionType},
     api::TransactionRequest,
     block::L1BatchHeader,
     commitment::{
@@ -302,7 +302,8 @@ async fn resend_each_block(commitment_mode: L1BatchCommitmentMode) -> anyhow::Re
             false,
             false,
         )
-        .await?;
+        .await?
+        .expect("operation was postponed");
 
     let hash = tester
         .manager
@@ -353,6 +354,7 @@ async fn resend_each_block(commitment_mode: L1BatchCommitmentMode) -> anyhow::Re
             &mut tester.conn.connection().await.unwrap(),
             block_numbers,
             OperatorType::NonBlob,
+            tester.manager.operator_address(OperatorType::NonBlob),
         )
         .await?
         .unwrap();
@@ -1131,6 +1133,8 @@ async fn test_signing_eip712_tx() {
         ethereum_client: None,
         ethereum_client_blobs: None,
         sl_client: Some(sign_client),
+        additional_ethereum_clients: vec![],
+        additional_ethereum_clients_blobs: vec![],
         wait_confirmations: Some(10),
     };
 
@@ -1220,3 +1224,44 @@ async fn manager_monitors_even_unsuccesfully_sent_txs() {
         .is_some();
     assert!(is_confirmed);
 }
+
+#[test_log::test(tokio::test)]
+async fn transactions_of_retired_operators_are_not_sent() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut tester = EthSenderTester::new(
+        pool.clone(),
+        vec![100; 100],
+        false,
+        true,
+        L1BatchCommitmentMode::Rollup,
+        SettlementLayer::L1(10.into()),
+    )
+    .await;
+
+    let retired_operator = Address::repeat_byte(0x42);
+    let tx = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .save_eth_tx(
+            0,
+            vec![],
+            AggregatedActionType::L1Batch(L1BatchAggregatedActionType::Execute),
+            Address::random(),
+            None,
+            Some(retired_operator),
+            None,
+            false,
+        )
+        .await
+        .unwrap();
+
+    let block = tester.get_block_numbers().await.latest;
+    let err = tester
+        .manager
+        .send_eth_tx(&mut pool.connection().await.unwrap(), &tx, 0, block)
+        .await
+        .unwrap_err();
+    assert_matches!(err, EthSenderError::UnknownOperatorAccount(address) if address == retired_operator);
+    assert_eq!(tester.gateway.sent_tx_count(), 0);
+}
@@ -0,0 +1,27 @@
+//! External fee source metrics.
+
+use std::time::Duration;
+
+use vise::{Buckets, Counter, Gauge, Histogram, LabeledFamily, Metrics};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_gas_adjuster_fee_source")]
+pub(super) struct FeeSourceMetrics {
+    /// Latency of fetching a sample from an external fee source.
+    #[metrics(buckets = Buckets::LATENCIES, labels = ["source"])]
+    pub fetch_latency: LabeledFamily<&'static str, Histogram<Duration>>,
+    /// Number of errors encountered when fetching samples from an external fee source.
+    #[metrics(labels = ["source"])]
+    pub fetch_errors: LabeledFamily<&'static str, Counter>,
+    /// Number of samples rejected as outliers during aggregation.
+    pub rejected_samples: Counter,
+    /// Aggregated base fee per gas.
+    pub aggregated_base_fee_per_gas: Gauge<u64>,
+    /// Aggregated blob base fee.
+    pub aggregated_blob_base_fee: Gauge<u64>,
+    /// Aggregated priority fee per gas.
+    pub aggregated_priority_fee_per_gas: Gauge<u64>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<FeeSourceMetrics> = vise::Global::new();
@@ -0,0 +1,409 @@
+//! Pluggable sources of settlement layer fee estimates that can be blended into [`GasAdjuster`] values.
+//!
+//! [`GasAdjuster`]: super::GasAdjuster
+
+use std::{fmt, time::Duration};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use zksync_config::configs::eth_sender::FeeSourcesConfig;
+use zksync_eth_client::EthFeeInterface;
+use zksync_web3_decl::client::{Client, DynClient, L1, L2};
+
+use self::metrics::METRICS;
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+/// Number of wei in a gwei.
+const WEI_PER_GWEI: f64 = 1_000_000_000.0;
+
+/// Fee sample provided by a [`FeeSource`]. Each value is optional since sources may not provide all kinds of fees;
+/// missing values are not taken into account during aggregation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeeSample {
+    pub base_fee_per_gas: Option<u64>,
+    pub blob_base_fee: Option<u64>,
+    pub priority_fee_per_gas: Option<u64>,
+}
+
+/// Fees produced by [`MedianFeeAggregator`]. Also used to pass primary fees (i.e., ones derived
+/// from the fee history of the settlement layer client) to the aggregator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AggregatedFees {
+    pub base_fee_per_gas: u64,
+    pub blob_base_fee: u64,
+    pub priority_fee_per_gas: u64,
+}
+
+/// External source of fee estimates.
+#[async_trait]
+pub trait FeeSource: fmt::Debug + Send + Sync + 'static {
+    /// Name of the source used in logs and metrics.
+    fn name(&self) -> &'static str;
+
+    /// Fetches the current fee sample from the source.
+    async fn fetch_sample(&self) -> anyhow::Result<FeeSample>;
+}
+
+/// Source returning a fixed sample. Useful for tests and manual overrides.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedFeeSource(pub FeeSample);
+
+#[async_trait]
+impl FeeSource for FixedFeeSource {
+    fn name(&self) -> &'static str {
+        "fixed"
+    }
+
+    async fn fetch_sample(&self) -> anyhow::Result<FeeSample> {
+        Ok(self.0)
+    }
+}
+
+/// Source taking a percentile of base fees and blob base fees returned by `eth_feeHistory`
+/// of a (secondary) settlement layer provider.
+#[derive(Debug)]
+pub struct FeeHistoryFeeSource {
+    client: Box<dyn EthFeeInterface>,
+    block_count: usize,
+    percentile: u8,
+}
+
+impl FeeHistoryFeeSource {
+    pub fn new(client: Box<dyn EthFeeInterface>, block_count: usize, percentile: u8) -> Self {
+        assert!(block_count > 0, "block count must be positive");
+        assert!(percentile <= 100, "percentile must be in 0..=100");
+        Self {
+            client,
+            block_count,
+            percentile,
+        }
+    }
+}
+
+#[async_trait]
+impl FeeSource for FeeHistoryFeeSource {
+    fn name(&self) -> &'static str {
+        "fee_history"
+    }
+
+    async fn fetch_sample(&self) -> anyhow::Result<FeeSample> {
+        // Subtracting 1 for the same reason as in `GasAdjuster`: the latest block may not be available yet.
+        let current_block = self
+            .client
+            .block_number()
+            .await?
+            .as_usize()
+            .saturating_sub(1);
+        let history = self
+            .client
+            .base_fee_history(current_block, self.block_count)
+            .await?;
+        anyhow::ensure!(!history.is_empty(), "provider returned empty fee history");
+
+        let base_fees = history.iter().map(|fees| fees.base_fee_per_gas).collect();
+        let blob_base_fees = history
+            .iter()
+            .map(|fees| u64::try_from(fees.base_fee_per_blob_gas).unwrap_or(u64::MAX))
+            .collect();
+        Ok(FeeSample {
+            base_fee_per_gas: Some(percentile(base_fees, self.percentile)),
+            blob_base_fee: Some(percentile(blob_base_fees, self.percentile)),
+            priority_fee_per_gas: None,
+        })
+    }
+}
+
+/// Source querying a gas station HTTP API that returns a JSON object with fee estimates in gwei.
+/// Values are located using JSON pointers and may be either JSON numbers or decimal strings.
+#[derive(Debug)]
+pub struct GasStationFeeSource {
+    client: reqwest::Client,
+    url: String,
+    base_fee_pointer: String,
+    priority_fee_pointer: String,
+}
+
+impl GasStationFeeSource {
+    pub fn new(
+        url: String,
+        base_fee_pointer: String,
+        priority_fee_pointer: String,
+        request_timeout: Duration,
+    ) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(request_timeout)
+            .build()
+            .context("failed building HTTP client")?;
+        Ok(Self {
+            client,
+            url,
+            base_fee_pointer,
+            priority_fee_pointer,
+        })
+    }
+
+    fn extract_gwei(response: &serde_json::Value, pointer: &str) -> anyhow::Result<Option<u64>> {
+        let Some(value) = response.pointer(pointer) else {
+            return Ok(None);
+        };
+        let gwei = match value {
+            serde_json::Value::Number(number) => number.as_f64(),
+            serde_json::Value::String(s) => s.parse::<f64>().ok(),
+            _ => None,
+        };
+        let gwei =
+            gwei.with_context(|| format!("value at `{pointer}` is not a number: {value}"))?;
+        anyhow::ensure!(
+            gwei.is_finite() && gwei >= 0.0,
+            "value at `{pointer}` is invalid: {gwei}"
+        );
+        Ok(Some((gwei * WEI_PER_GWEI) as u64))
+    }
+}
+
+#[async_trait]
+impl FeeSource for GasStationFeeSource {
+    fn name(&self) -> &'static str {
+        "gas_station"
+    }
+
+    async fn fetch_sample(&self) -> anyhow::Result<FeeSample> {
+        let response: serde_json::Value = self
+            .client
+            .get(&self.url)
+            .send()
+            .await
+            .context("failed sending request")?
+            .error_for_status()?
+            .json()
+            .await
+            .context("failed parsing response")?;
+
+        let base_fee_per_gas = Self::extract_gwei(&response, &self.base_fee_pointer)?;
+        let priority_fee_per_gas = Self::extract_gwei(&response, &self.priority_fee_pointer)?;
+        anyhow::ensure!(
+            base_fee_per_gas.is_some() || priority_fee_per_gas.is_some(),
+            "response contains neither base fee nor priority fee"
+        );
+        Ok(FeeSample {
+            base_fee_per_gas,
+            blob_base_fee: None,
+            priority_fee_per_gas,
+        })
+    }
+}
+
+/// Parameters of fee aggregation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AggregationParams {
+    /// Samples deviating from the median of all samples by more than this fraction are rejected.
+    pub max_relative_deviation: f64,
+    /// Lower bound for the aggregated value relative to the primary value.
+    pub min_ratio_to_primary: f64,
+    /// Upper bound for the aggregated value relative to the primary value.
+    pub max_ratio_to_primary: f64,
+}
+
+impl AggregationParams {
+    /// Aggregates the primary value with samples from external sources. Returns the aggregated value
+    /// and the number of samples rejected as outliers.
+    fn aggregate(&self, primary: u64, samples: &[u64]) -> (u64, usize) {
+        let mut all_samples = Vec::with_capacity(samples.len() + 1);
+        all_samples.push(primary);
+        all_samples.extend_from_slice(samples);
+
+        let median = median(all_samples.clone());
+        let max_deviation = median as f64 * self.max_relative_deviation;
+        let accepted: Vec<_> = all_samples
+            .into_iter()
+            .filter(|&sample| (sample as f64 - median as f64).abs() <= max_deviation)
+            .collect();
+        let rejected_count = samples.len() + 1 - accepted.len();
+        // `accepted` may be empty if there are two diverging middle samples; in this case, their average is used.
+        let aggregated = median_or(accepted, median);
+
+        let lower_bound = (primary as f64 * self.min_ratio_to_primary) as u64;
+        let upper_bound = (primary as f64 * self.max_ratio_to_primary) as u64;
+        (
+            aggregated.clamp(lower_bound, upper_bound.max(lower_bound)),
+            rejected_count,
+        )
+    }
+}
+
+/// Aggregates fees from several [`FeeSource`]s by taking a median after outlier rejection.
+/// The primary fees (derived from the settlement layer client) always participate in aggregation
+/// and bound the aggregated values.
+#[derive(Debug)]
+pub struct MedianFeeAggregator {
+    sources: Vec<Box<dyn FeeSource>>,
+    params: AggregationParams,
+    request_timeout: Duration,
+}
+
+impl MedianFeeAggregator {
+    pub fn new(params: AggregationParams, request_timeout: Duration) -> Self {
+        Self {
+            sources: vec![],
+            params,
+            request_timeout,
+        }
+    }
+
+    /// Creates an aggregator with sources specified in the provided config. If `gateway_mode` is set,
+    /// the fee history source is queried as a Gateway provider rather than an L1 one.
+    pub fn from_config(config: &FeeSourcesConfig, gateway_mode: bool) -> anyhow::Result<Self> {
+        let params = AggregationParams {
+            max_relative_deviation: config.max_relative_deviation,
+            min_ratio_to_primary: config.min_ratio_to_primary,
+            max_ratio_to_primary: config.max_ratio_to_primary,
+        };
+        anyhow::ensure!(
+            params.min_ratio_to_primary <= params.max_ratio_to_primary,
+            "`min_ratio_to_primary` must not exceed `max_ratio_to_primary`"
+        );
+        anyhow::ensure!(
+            config.fee_history_percentile <= 100,
+            "`fee_history_percentile` must be in 0..=100"
+        );
+        anyhow::ensure!(
+            config.fee_history_block_count > 0,
+            "`fee_history_block_count` must be positive"
+        );
+        let mut this = Self::new(params, config.request_timeout);
+
+        if let Some(url) = &config.fee_history_rpc_url {
+            let client: Box<dyn EthFeeInterface> = if gateway_mode {
+                let client = Client::<L2>::http(url.clone())
+                    .context("failed creating client for fee history source")?
+                    .build();
+                let client: Box<DynClient<L2>> = Box::new(client);
+                Box::new(client.for_component("gas_adjuster_fee_source"))
+            } else {
+                let client = Client::<L1>::http(url.clone())
+                    .context("failed creating client for fee history source")?
+                    .build();
+                let client: Box<DynClient<L1>> = Box::new(client);
+                Box::new(client.for_component("gas_adjuster_fee_source"))
+            };
+            this = this.with_source(FeeHistoryFeeSource::new(
+                client,
+                config.fee_history_block_count,
+                config.fee_history_percentile,
+            ));
+        }
+        if let Some(url) = &config.gas_station_url {
+            this = this.with_source(GasStationFeeSource::new(
+                url.clone(),
+                config.gas_station_base_fee_pointer.clone(),
+                config.gas_station_priority_fee_pointer.clone(),
+                config.request_timeout,
+            )?);
+        }
+        Ok(this)
+    }
+
+    /// Adds a fee source to this aggregator.
+    #[must_use]
+    pub fn with_source(mut self, source: impl FeeSource) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+
+    async fn fetch_samples(&self) -> Vec<FeeSample> {
+        let fetches = self.sources.iter().map(|source| async move {
+            let name = source.name();
+            let latency = METRICS.fetch_latency[&name].start();
+            let result = tokio::time::timeout(self.request_timeout, source.fetch_sample()).await;
+            latency.observe();
+            match result {
+                Ok(Ok(sample)) => Some(sample),
+                Ok(Err(err)) => {
+                    tracing::warn!("Failed fetching fee sample from source `{name}`: {err:#}");
+                    METRICS.fetch_errors[&name].inc();
+                    None
+                }
+                Err(_) => {
+                    tracing::warn!("Timed out fetching fee sample from source `{name}`");
+                    METRICS.fetch_errors[&name].inc();
+                    None
+                }
+            }
+        });
+        futures::future::join_all(fetches)
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Fetches samples from all sources and aggregates them with the `primary` fees.
+    /// Sources that fail or time out are ignored.
+    pub async fn aggregate(&self, primary: AggregatedFees) -> AggregatedFees {
+        let samples = self.fetch_samples().await;
+        let aggregated = self.aggregate_samples(primary, &samples);
+        tracing::debug!(
+            "Aggregated fees {aggregated:?} from primary fees {primary:?} and samples {samples:?}"
+        );
+        METRICS
+            .aggregated_base_fee_per_gas
+            .set(aggregated.base_fee_per_gas);
+        METRICS
+            .aggregated_blob_base_fee
+            .set(aggregated.blob_base_fee);
+        METRICS
+            .aggregated_priority_fee_per_gas
+            .set(aggregated.priority_fee_per_gas);
+        aggregated
+    }
+
+    fn aggregate_samples(&self, primary: AggregatedFees, samples: &[FeeSample]) -> AggregatedFees {
+        let mut total_rejected = 0;
+        let mut aggregate = |primary_value, select: fn(&FeeSample) -> Option<u64>| {
+            let values: Vec<_> = samples.iter().filter_map(select).collect();
+            let (aggregated, rejected) = self.params.aggregate(primary_value, &values);
+            total_rejected += rejected;
+            aggregated
+        };
+
+        let aggregated = AggregatedFees {
+            base_fee_per_gas: aggregate(primary.base_fee_per_gas, |sample| sample.base_fee_per_gas),
+            blob_base_fee: aggregate(primary.blob_base_fee, |sample| sample.blob_base_fee),
+            priority_fee_per_gas: aggregate(primary.priority_fee_per_gas, |sample| {
+                sample.priority_fee_per_gas
+            }),
+        };
+        METRICS.rejected_samples.inc_by(total_rejected as u64);
+        aggregated
+    }
+}
+
+fn median(values: Vec<u64>) -> u64 {
+    median_or(values, 0)
+}
+
+/// Computes a median of `values`. For an even number of values, the two middle values are averaged (rounding down),
+/// so that the median isn't biased towards either of them.
+fn median_or(mut values: Vec<u64>, default: u64) -> u64 {
+    if values.is_empty() {
+        return default;
+    }
+    let len = values.len();
+    let (lower, &mut upper_median, _) = values.select_nth_unstable(len / 2);
+    if len % 2 == 1 {
+        return upper_median;
+    }
+    // `unwrap()` is safe: `lower` contains `len / 2 >= 1` values.
+    let lower_median = *lower.iter().max().unwrap();
+    lower_median + (upper_median - lower_median) / 2
+}
+
+/// Computes a nearest-rank percentile. `values` must be non-empty.
+fn percentile(mut values: Vec<u64>, percentile: u8) -> u64 {
+    values.sort_unstable();
+    let rank = (usize::from(percentile) * (values.len() - 1) + 50) / 100;
+    values[rank]
+}
@@ -0,0 +1,206 @@
+use std::time::Duration;
+
+use zksync_eth_client::{clients::MockSettlementLayer, BaseFees};
+use zksync_types::eth_sender::EthTxFinalityStatus;
+use zksync_web3_decl::client::{DynClient, L1};
+
+use super::*;
+
+const PARAMS: AggregationParams = AggregationParams {
+    max_relative_deviation: 0.5,
+    min_ratio_to_primary: 0.5,
+    max_ratio_to_primary: 2.0,
+};
+
+const PRIMARY: AggregatedFees = AggregatedFees {
+    base_fee_per_gas: 100,
+    blob_base_fee: 10,
+    priority_fee_per_gas: 5,
+};
+
+#[test]
+fn computing_percentile() {
+    let values = vec![5, 1, 4, 2, 3];
+    assert_eq!(percentile(values.clone(), 0), 1);
+    assert_eq!(percentile(values.clone(), 50), 3);
+    assert_eq!(percentile(values.clone(), 75), 4);
+    assert_eq!(percentile(values, 100), 5);
+}
+
+#[test]
+fn computing_median() {
+    assert_eq!(median(vec![]), 0);
+    assert_eq!(median(vec![3, 1, 2]), 2);
+    assert_eq!(median(vec![100, 110]), 105);
+    assert_eq!(median(vec![4, 1, 3, 2]), 2);
+    assert_eq!(median(vec![u64::MAX, u64::MAX - 2]), u64::MAX - 1);
+}
+
+#[test]
+fn aggregating_without_samples_returns_primary() {
+    assert_eq!(PARAMS.aggregate(100, &[]), (100, 0));
+}
+
+#[test]
+fn aggregating_takes_median() {
+    assert_eq!(PARAMS.aggregate(100, &[120, 130]), (120, 0));
+    assert_eq!(PARAMS.aggregate(100, &[90, 80, 110, 95]), (95, 0));
+    // For an even number of samples, the middle samples are averaged.
+    assert_eq!(PARAMS.aggregate(100, &[120]), (110, 0));
+    assert_eq!(PARAMS.aggregate(100, &[90, 120, 130]), (110, 0));
+    // Both samples are rejected as outliers relative to their average.
+    assert_eq!(PARAMS.aggregate(100, &[400]), (200, 2));
+}
+
+#[test]
+fn aggregating_rejects_outliers() {
+    // Median of all samples is 110; 1_000 and 10 deviate by more than 50%.
+    assert_eq!(PARAMS.aggregate(100, &[110, 120, 1_000, 10]), (110, 2));
+}
+
+#[test]
+fn aggregating_clamps_to_primary() {
+    // Samples are consistent with each other, but far from the primary value.
+    assert_eq!(PARAMS.aggregate(100, &[400, 410, 420]), (200, 1));
+    assert_eq!(PARAMS.aggregate(100, &[20, 21, 22]), (50, 1));
+    // Zero primary value (e.g., blob base fee for L2 settlement layers) is preserved.
+    assert_eq!(PARAMS.aggregate(0, &[20, 21, 22]).0, 0);
+}
+
+#[tokio::test]
+async fn aggregating_samples_from_sources() {
+    let aggregator = MedianFeeAggregator::new(PARAMS, Duration::from_secs(1))
+        .with_source(FixedFeeSource(FeeSample {
+            base_fee_per_gas: Some(120),
+            blob_base_fee: None,
+            priority_fee_per_gas: Some(7),
+        }))
+        .with_source(FixedFeeSource(FeeSample {
+            base_fee_per_gas: Some(130),
+            blob_base_fee: Some(12),
+            priority_fee_per_gas: Some(6),
+        }));
+
+    let aggregated = aggregator.aggregate(PRIMARY).await;
+    assert_eq!(
+        aggregated,
+        AggregatedFees {
+            base_fee_per_gas: 120,
+            blob_base_fee: 11,
+            priority_fee_per_gas: 6,
+        }
+    );
+}
+
+#[derive(Debug)]
+struct FailingFeeSource;
+
+#[async_trait]
+impl FeeSource for FailingFeeSource {
+    fn name(&self) -> &'static str {
+        "failing"
+    }
+
+    async fn fetch_sample(&self) -> anyhow::Result<FeeSample> {
+        anyhow::bail!("source is down")
+    }
+}
+
+#[tokio::test]
+async fn failing_sources_are_ignored() {
+    let aggregator = MedianFeeAggregator::new(PARAMS, Duration::from_secs(1))
+        .with_source(FailingFeeSource)
+        .with_source(FixedFeeSource(FeeSample {
+            base_fee_per_gas: Some(110),
+            ..FeeSample::default()
+        }));
+
+    let aggregated = aggregator.aggregate(PRIMARY).await;
+    assert_eq!(
+        aggregated,
+        AggregatedFees {
+            base_fee_per_gas: 105,
+            ..PRIMARY
+        }
+    );
+}
+
+#[tokio::test]
+async fn fee_history_source() {
+    let base_fees = (1..=10)
+        .map(|i| BaseFees {
+            base_fee_per_gas: i * 10,
+            base_fee_per_blob_gas: i.into(),
+            l2_pubdata_price: 0.into(),
+        })
+        .collect();
+    let eth_client = MockSettlementLayer::builder()
+        .with_fee_history(base_fees)
+        .build();
+    eth_client.advance_block_number(10, EthTxFinalityStatus::Finalized);
+    let client: Box<DynClient<L1>> = Box::new(eth_client.into_client());
+
+    let source = FeeHistoryFeeSource::new(Box::new(client), 5, 50);
+    let sample = source.fetch_sample().await.unwrap();
+    // Blocks 5..=9 are sampled.
+    assert_eq!(
+        sample,
+        FeeSample {
+            base_fee_per_gas: Some(80),
+            blob_base_fee: Some(8),
+            priority_fee_per_gas: None,
+        }
+    );
+}
+
+#[test]
+fn extracting_gas_station_values() {
+    let response = serde_json::json!({
+        "data": { "baseFee": 12.5, "priorityFee": "1.5" },
+        "invalid": true,
+    });
+    let extract = |pointer| GasStationFeeSource::extract_gwei(&response, pointer);
+    assert_eq!(extract("/data/baseFee").unwrap(), Some(12_500_000_000));
+    assert_eq!(extract("/data/priorityFee").unwrap(), Some(1_500_000_000));
+    assert_eq!(extract("/data/missing").unwrap(), None);
+    extract("/invalid").unwrap_err();
+}
+
+#[test]
+fn invalid_config_is_rejected() {
+    let config = FeeSourcesConfig {
+        fee_history_rpc_url: Some("http://localhost:8545/".parse().unwrap()),
+        fee_history_block_count: 0,
+        ..FeeSourcesConfig::default()
+    };
+    let err = MedianFeeAggregator::from_config(&config, false)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("fee_history_block_count"), "{err}");
+
+    let config = FeeSourcesConfig {
+        fee_history_percentile: 101,
+        ..FeeSourcesConfig::default()
+    };
+    let err = MedianFeeAggregator::from_config(&config, false)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("fee_history_percentile"), "{err}");
+}
+
+#[tokio::test]
+async fn creating_aggregator_from_config() {
+    let config = FeeSourcesConfig {
+        fee_history_rpc_url: Some("http://localhost:8545/".parse().unwrap()),
+        ..FeeSourcesConfig::default()
+    };
+    for gateway_mode in [false, true] {
+        let aggregator = MedianFeeAggregator::from_config(&config, gateway_mode).unwrap();
+        let source_names: Vec<_> = aggregator
+            .sources
+            .iter()
+            .map(|source| source.name())
+            .collect();
+        assert_eq!(source_names, ["fee_history"]);
+    }
+}
@@ -16,3 +16,9 @@ pub(super) struct GasAdjusterMetrics {
 
 #[vise::register]
 pub(super) static METRICS: vise::Global<GasAdjusterMetrics> = vise::Global::new();
+
+/// Metrics for adjusters created with [`GasAdjuster::with_detached_metrics()`].
+/// Not registered, so the reported values are never exported.
+///
+/// [`GasAdjuster::with_detached_metrics()`]: super::GasAdjuster::with_detached_metrics()
+pub(super) static DETACHED_METRICS: vise::Global<GasAdjusterMetrics> = vise::Global::new();
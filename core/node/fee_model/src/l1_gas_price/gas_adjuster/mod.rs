@@ -5,6 +5,7 @@ use std::{
     sync::{Arc, RwLock},
 };
 
+use anyhow::Context as _;
 use tokio::sync::watch;
 use zksync_config::GasAdjusterConfig;
 use zksync_dal::{ConnectionPool, Core};
@@ -15,8 +16,11 @@ use zksync_types::{
 };
 use zksync_web3_decl::client::{DynClient, L1, L2};
 
-use self::metrics::METRICS;
-use super::TxParamsProvider;
+use self::metrics::{GasAdjusterMetrics, DETACHED_METRICS, METRICS};
+use super::{
+    fee_source::{AggregatedFees, MedianFeeAggregator},
+    TxParamsProvider,
+};
 use crate::l1_gas_price::blob_base_fee_predictor::predict_blob_base_fee;
 
 mod metrics;
@@ -26,12 +30,15 @@ mod tests;
 #[derive(Debug)]
 pub struct GasAdjusterClient {
     pub(crate) inner: Box<dyn EthFeeInterface>,
+    /// Whether the settlement layer is Gateway (i.e., an L2 chain) rather than L1.
+    pub(crate) gateway_mode: bool,
 }
 
 impl From<Box<DynClient<L1>>> for GasAdjusterClient {
     fn from(inner: Box<DynClient<L1>>) -> Self {
         Self {
             inner: Box::new(inner.for_component("gas_adjuster")),
+            gateway_mode: false,
         }
     }
 }
@@ -40,6 +47,7 @@ impl From<Box<DynClient<L2>>> for GasAdjusterClient {
     fn from(inner: Box<DynClient<L2>>) -> Self {
         Self {
             inner: Box::new(inner.for_component("gas_adjuster")),
+            gateway_mode: true,
         }
     }
 }
@@ -48,6 +56,9 @@ impl From<Box<DynClient<L2>>> for GasAdjusterClient {
 ///
 /// It also tracks the median `blob_base_fee` from the last `max_blob_base_fee_sample` blocks.
 /// It is used to adjust the base_fee of transactions sent to L1.
+///
+/// If a [`MedianFeeAggregator`] is configured, the medians (and the default priority fee) are blended
+/// with samples from external fee sources on each update.
 #[derive(Debug)]
 pub struct GasAdjuster {
     pub(super) base_fee_statistics: GasStatistics<u64>,
@@ -67,6 +78,10 @@ pub struct GasAdjuster {
     client: GasAdjusterClient,
     connection_pool: ConnectionPool<Core>,
     commitment_mode: L1BatchCommitmentMode,
+    fee_aggregator: Option<MedianFeeAggregator>,
+    // Fees computed by `fee_aggregator` during the latest update; `None` if there's no aggregator.
+    pub(super) aggregated_fees: RwLock<Option<AggregatedFees>>,
+    metrics: &'static GasAdjusterMetrics,
 }
 
 impl GasAdjuster {
@@ -117,7 +132,14 @@ impl GasAdjuster {
                 .map(|base_fee| base_fee.gas_per_pubdata()),
         );
 
-        Ok(Self {
+        let fee_aggregator = config
+            .fee_sources
+            .as_ref()
+            .map(|sources| MedianFeeAggregator::from_config(sources, client.gateway_mode))
+            .transpose()
+            .context("failed initializing external fee sources")?;
+
+        let this = Self {
             base_fee_statistics,
             blob_base_fee_statistics,
             l2_pubdata_price_statistics,
@@ -127,7 +149,57 @@ impl GasAdjuster {
             client,
             commitment_mode,
             connection_pool,
-        })
+            fee_aggregator,
+            aggregated_fees: RwLock::new(None),
+            metrics: &METRICS,
+        };
+        this.update_aggregated_fees().await;
+        Ok(this)
+    }
+
+    /// Sets the aggregator blending external fee sources into the values provided by this adjuster,
+    /// replacing the one created from the config.
+    pub async fn with_fee_aggregator(mut self, aggregator: MedianFeeAggregator) -> Self {
+        self.fee_aggregator = Some(aggregator);
+        self.update_aggregated_fees().await;
+        self
+    }
+
+    /// Makes this adjuster report to metrics that are never exported. Should be used for adjusters that don't observe
+    /// the actual settlement layer, e.g. in offline simulations, so that they don't interfere with production metrics.
+    pub fn with_detached_metrics(mut self) -> Self {
+        self.metrics = &DETACHED_METRICS;
+        self
+    }
+
+    async fn update_aggregated_fees(&self) {
+        let Some(aggregator) = &self.fee_aggregator else {
+            return;
+        };
+        let blob_base_fee = self.blob_base_fee_statistics.median();
+        let primary = AggregatedFees {
+            base_fee_per_gas: self.base_fee_statistics.median(),
+            blob_base_fee: u64::try_from(blob_base_fee).unwrap_or(u64::MAX),
+            priority_fee_per_gas: self.config.default_priority_fee_per_gas,
+        };
+        let aggregated = aggregator.aggregate(primary).await;
+        *self.aggregated_fees.write().unwrap() = Some(aggregated);
+    }
+
+    /// Returns the median base fee, taking external fee sources into account.
+    fn base_fee_median(&self) -> u64 {
+        match *self.aggregated_fees.read().unwrap() {
+            Some(fees) => fees.base_fee_per_gas,
+            None => self.base_fee_statistics.median(),
+        }
+    }
+
+    /// Returns the median blob base fee, taking external fee sources into account.
+    fn blob_base_fee_median(&self) -> U256 {
+        match *self.aggregated_fees.read().unwrap() {
+            Some(fees) => fees.blob_base_fee.into(),
+            None => self.blob_base_fee_statistics.median(),
+        }
     }
 
     /// Performs an actualization routine for `GasAdjuster`.
@@ -157,7 +229,7 @@ impl GasAdjuster {
             // We shouldn't rely on L1 provider to return consistent results, so we check that we have at least one new sample.
             if let Some(current_base_fee_per_gas) = fee_data.last().map(|fee| fee.base_fee_per_gas)
             {
-                METRICS
+                self.metrics
                     .current_base_fee_per_gas
                     .set(current_base_fee_per_gas);
             }
@@ -173,7 +245,7 @@ impl GasAdjuster {
                 if current_blob_base_fee > U256::from(u64::MAX) {
                     tracing::error!("Failed to report current_blob_base_fee = {current_blob_base_fee}, it exceeds u64::MAX");
                 } else {
-                    METRICS
+                    self.metrics
                         .current_blob_base_fee
                         .set(current_blob_base_fee.as_u64());
                 }
@@ -189,7 +261,7 @@ impl GasAdjuster {
                 if current_l2_pubdata_price > U256::from(u64::MAX) {
                     tracing::error!("Failed to report current_l2_pubdata_price = {current_l2_pubdata_price}, it exceeds u64::MAX");
                 } else {
-                    METRICS
+                    self.metrics
                         .current_l2_pubdata_price
                         .set(current_l2_pubdata_price.as_u64());
                 }
@@ -200,6 +272,8 @@ impl GasAdjuster {
             self.gas_per_pubdata_price_statistic
                 .add_samples(fee_data.iter().map(|base_fee| base_fee.gas_per_pubdata()));
         }
+
+        self.update_aggregated_fees().await;
         Ok(())
     }
 
@@ -259,7 +333,7 @@ impl GasAdjuster {
             PubdataSendingMode::Blobs => {
                 const BLOB_GAS_PER_BYTE: u64 = 1; // `BYTES_PER_BLOB` = `GAS_PER_BLOB` = 2 ^ 17.
 
-                let blob_base_fee_median = self.blob_base_fee_statistics.median();
+                let blob_base_fee_median = self.blob_base_fee_median();
 
                 // Check if blob base fee overflows `u64` before converting. Can happen only in very extreme cases.
                 if blob_base_fee_median > U256::from(u64::MAX) {
@@ -267,7 +341,7 @@ impl GasAdjuster {
                     tracing::error!("Blob base fee is too high: {blob_base_fee_median}, using max allowed: {max_allowed}");
                     return max_allowed;
                 }
-                METRICS
+                self.metrics
                     .median_blob_base_fee
                     .set(blob_base_fee_median.as_u64());
 
@@ -340,8 +414,8 @@ impl TxParamsProvider for GasAdjuster {
     // In other words, in order to pay less fees, we are ready to wait longer.
     // But the longer we wait, the more we are ready to pay.
     fn get_base_fee(&self, time_in_mempool_in_l1_blocks: u32) -> u64 {
-        let median = self.base_fee_statistics.median();
-        METRICS.median_base_fee_per_gas.set(median);
+        let median = self.base_fee_median();
+        self.metrics.median_base_fee_per_gas.set(median);
         self.calculate_price_with_formula(time_in_mempool_in_l1_blocks, median)
     }
 
@@ -374,7 +448,8 @@ impl TxParamsProvider for GasAdjuster {
         }
     }
 
-    // Priority fee is set to constant, sourced from config.
+    // Priority fee is set to constant, sourced from config (unless external fee sources are configured,
+    // in which case the constant is blended with external estimates).
     // Reasoning behind this is the following:
     // High `priority_fee` means high demand for block space,
     // which means `base_fee` will increase, which means `priority_fee`
@@ -382,7 +457,10 @@ impl TxParamsProvider for GasAdjuster {
     // `base_fee` will balance out `priority_fee` in such a way that
     // `priority_fee` will be a small fraction of the overall fee.
     fn get_priority_fee(&self) -> u64 {
-        self.config.default_priority_fee_per_gas
+        match *self.aggregated_fees.read().unwrap() {
+            Some(fees) => fees.priority_fee_per_gas,
+            None => self.config.default_priority_fee_per_gas,
+        }
     }
 
     fn get_blob_tx_base_fee(&self, time_in_mempool_in_l1_blocks: u32) -> u64 {
@@ -390,7 +468,7 @@ impl TxParamsProvider for GasAdjuster {
     }
 
     fn get_blob_tx_blob_base_fee(&self, time_in_mempool_in_l1_blocks: u32) -> u64 {
-        let median = self.blob_base_fee_statistics.median();
+        let median = self.blob_base_fee_median();
         let median_u64 = if median > U256::from(u64::MAX) {
             tracing::error!(
                 "Blob base fee median is too high: {median}, using u64::MAX {}",
@@ -400,7 +478,7 @@ impl TxParamsProvider for GasAdjuster {
         } else {
             median.as_u64()
         };
-        METRICS.median_blob_base_fee.set(median_u64);
+        self.metrics.median_blob_base_fee.set(median_u64);
         self.calculate_price_with_formula(time_in_mempool_in_l1_blocks, median_u64)
     }
 
@@ -11,7 +11,10 @@ use zksync_types::{
 use zksync_web3_decl::client::{DynClient, L1, L2};
 
 use super::{GasAdjuster, GasStatistics, GasStatisticsInner};
-use crate::l1_gas_price::GasAdjusterClient;
+use crate::l1_gas_price::{
+    AggregatedFees, AggregationParams, FeeSample, FixedFeeSource, GasAdjusterClient,
+    MedianFeeAggregator, TxParamsProvider,
+};
 
 /// Check that we compute the median correctly
 #[test]
@@ -74,6 +77,7 @@ fn test_config() -> GasAdjusterConfig {
         num_samples_for_blob_base_fee_estimate: 3,
         internal_pubdata_pricing_multiplier: 1.0,
         max_blob_base_fee: u64::MAX,
+        fee_sources: None,
     }
 }
 
@@ -216,3 +220,63 @@ async fn kept_updated_l2(commitment_mode: L1BatchCommitmentMode) {
         expected_median_blob_base_fee.into()
     );
 }
+
+#[tokio::test]
+async fn blending_external_fee_sources() {
+    let base_fees = TEST_BLOCK_FEES
+        .into_iter()
+        .zip(TEST_BLOB_FEES)
+        .map(|(block, blob)| BaseFees {
+            base_fee_per_gas: block,
+            base_fee_per_blob_gas: blob.into(),
+            l2_pubdata_price: 0.into(),
+        })
+        .collect();
+    let eth_client = MockSettlementLayer::builder()
+        .with_fee_history(base_fees)
+        .build();
+    eth_client.advance_block_number(6, EthTxFinalityStatus::Finalized);
+
+    let config = test_config();
+    let client: Box<DynClient<L1>> = Box::new(eth_client.clone().into_client());
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let adjuster = GasAdjuster::new(
+        GasAdjusterClient::from(client),
+        config,
+        PubdataSendingMode::Blobs,
+        L1BatchCommitmentMode::Rollup,
+        pool,
+    )
+    .await
+    .unwrap();
+    assert_eq!(*adjuster.aggregated_fees.read().unwrap(), None);
+    assert_eq!(adjuster.get_priority_fee(), 5);
+
+    let params = AggregationParams {
+        max_relative_deviation: 0.5,
+        min_ratio_to_primary: 0.5,
+        max_ratio_to_primary: 2.0,
+    };
+    let aggregator = MedianFeeAggregator::new(params, Duration::from_secs(1)).with_source(
+        FixedFeeSource(FeeSample {
+            base_fee_per_gas: Some(8),
+            blob_base_fee: None,
+            priority_fee_per_gas: Some(7),
+        }),
+    );
+    let adjuster = adjuster.with_fee_aggregator(aggregator).await;
+
+    assert_eq!(
+        *adjuster.aggregated_fees.read().unwrap(),
+        Some(AggregatedFees {
+            base_fee_per_gas: 7,
+            blob_base_fee: 393216 * 2,
+            priority_fee_per_gas: 6,
+        })
+    );
+    // The base fee median from the settlement layer is still 6, but the aggregated value is used.
+    assert_eq!(read(&adjuster.base_fee_statistics).median(), 6);
+    assert_eq!(adjuster.get_base_fee(0), 10);
+    assert_eq!(adjuster.get_priority_fee(), 6);
+    assert_eq!(adjuster.get_blob_tx_blob_base_fee(0), 393216 * 3);
+}
@@ -3,11 +3,16 @@
 use std::fmt;
 
 pub use self::{
+    fee_source::{
+        AggregatedFees, AggregationParams, FeeHistoryFeeSource, FeeSample, FeeSource,
+        FixedFeeSource, GasStationFeeSource, MedianFeeAggregator,
+    },
     gas_adjuster::{GasAdjuster, GasAdjusterClient},
     main_node_fetcher::MainNodeFeeParamsFetcher,
 };
 
 mod blob_base_fee_predictor;
+mod fee_source;
 mod gas_adjuster;
 mod main_node_fetcher;
 
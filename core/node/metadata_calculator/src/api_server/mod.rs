@@ -10,7 +10,7 @@ use axum::{
     response::{IntoResponse, Response},
     routing, Json, Router,
 };
-use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde::{de, de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
 use tokio::sync::watch;
 use zksync_health_check::{CheckHealth, Health, HealthStatus};
 use zksync_merkle_tree::{
@@ -18,7 +18,8 @@ use zksync_merkle_tree::{
     NoVersionError, ValueHash,
 };
 use zksync_shared_resources::tree::{
-    MerkleTreeInfo, TreeApiClient, TreeApiError, TreeEntryWithProof,
+    MerkleTreeInfo, TreeApiClient, TreeApiError, TreeEntryWithProof, TreeMultiProof,
+    TreeMultiProofEntry, TreeRangeAbsenceProof,
 };
 use zksync_types::{u256_to_h256, web3, L1BatchNumber, H256, U256};
 
@@ -33,11 +34,30 @@ mod tests;
 struct TreeProofsRequest {
     l1_batch_number: L1BatchNumber,
     hashed_keys: Vec<U256>,
+    /// If set, a single compact multi-proof is returned instead of proofs for each key.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    compact: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct TreeProofsResponse {
+    /// Empty for compact proofs.
     entries: Vec<TreeEntryWithProof>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    multi_proof: Option<TreeMultiProof>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TreeRangeAbsenceRequest {
+    l1_batch_number: L1BatchNumber,
+    start_key: U256,
+    end_key: U256,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TreeRangeAbsenceResponse {
+    /// `None` if the range is not empty.
+    proof: Option<TreeRangeAbsenceProof>,
 }
 
 fn map_entry_with_proof(src: zksync_merkle_tree::TreeEntryWithProof) -> TreeEntryWithProof {
@@ -50,6 +70,20 @@ fn map_entry_with_proof(src: zksync_merkle_tree::TreeEntryWithProof) -> TreeEntr
     }
 }
 
+fn map_multi_proof(src: zksync_merkle_tree::TreeMultiProof) -> TreeMultiProof {
+    let entries = src.entries.into_iter().zip(src.path_lengths);
+    let entries = entries.map(|(entry, path_length)| TreeMultiProofEntry {
+        key: entry.key,
+        value: entry.value,
+        index: entry.leaf_index,
+        path_length,
+    });
+    TreeMultiProof {
+        entries: entries.collect(),
+        hashes: src.hashes,
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct HexNodeKey(NodeKey);
 
@@ -165,6 +199,19 @@ struct StaleKeysResponse {
 #[derive(Debug)]
 enum TreeApiServerError {
     NoTreeVersion(NoVersionError),
+    InvalidRequest(String),
+}
+
+impl From<TreeApiServerError> for TreeApiError {
+    fn from(err: TreeApiServerError) -> Self {
+        match err {
+            TreeApiServerError::NoTreeVersion(err) => Self::NoVersion {
+                missing_version: err.missing_version,
+                version_count: err.version_count,
+            },
+            TreeApiServerError::InvalidRequest(message) => Self::Internal(anyhow::anyhow!(message)),
+        }
+    }
 }
 
 // Contains the same fields as `NoVersionError` and is serializable.
@@ -217,6 +264,15 @@ impl IntoResponse for TreeApiServerError {
                 };
                 (StatusCode::NOT_FOUND, headers, Json(body)).into_response()
             }
+            Self::InvalidRequest(detail) => {
+                let body = Problem {
+                    r#type: "/errors#invalid-request",
+                    title: "Invalid request",
+                    detail,
+                    data: serde_json::json!({}),
+                };
+                (StatusCode::BAD_REQUEST, headers, Json(body)).into_response()
+            }
         }
     }
 }
@@ -259,6 +315,39 @@ impl TreeApiClient for LazyAsyncTreeReader {
             Err(TreeApiError::NotReady(None))
         }
     }
+
+    async fn get_multi_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeMultiProof, TreeApiError> {
+        if let Some(reader) = self.read() {
+            reader
+                .get_multi_proof_inner(l1_batch_number, hashed_keys)
+                .await
+                .map_err(|err| TreeApiServerError::NoTreeVersion(err).into())
+        } else {
+            Err(TreeApiError::NotReady(None))
+        }
+    }
+
+    async fn get_range_absence_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        start_key: U256,
+        end_key: U256,
+    ) -> Result<Option<TreeRangeAbsenceProof>, TreeApiError> {
+        if let Some(reader) = self.read() {
+            let request = TreeRangeAbsenceRequest {
+                l1_batch_number,
+                start_key,
+                end_key,
+            };
+            Ok(reader.get_range_absence_proof_inner(request).await?)
+        } else {
+            Err(TreeApiError::NotReady(None))
+        }
+    }
 }
 
 /// [`TreeApiClient`] implementation requesting data from a Merkle tree API server.
@@ -267,6 +356,7 @@ pub struct TreeApiHttpClient {
     inner: reqwest::Client,
     info_url: String,
     proofs_url: String,
+    range_absence_url: String,
 }
 
 impl TreeApiHttpClient {
@@ -285,8 +375,53 @@ impl TreeApiHttpClient {
             inner: client,
             info_url: url_base.to_owned(),
             proofs_url: format!("{url_base}/proofs"),
+            range_absence_url: format!("{url_base}/proofs/range-absence"),
         }
     }
+
+    async fn post<Req: Serialize, Resp: DeserializeOwned>(
+        &self,
+        url: &str,
+        request: &Req,
+        l1_batch_number: L1BatchNumber,
+        response_description: &str,
+    ) -> Result<Resp, TreeApiError> {
+        let response = self
+            .inner
+            .post(url)
+            .json(request)
+            .send()
+            .await
+            .map_err(|err| {
+                client_error(
+                    err,
+                    format_args!("{response_description} for L1 batch #{l1_batch_number}"),
+                )
+            })?;
+
+        let is_problem = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .is_some_and(|header| *header == PROBLEM_CONTENT_TYPE);
+        if response.status() == StatusCode::NOT_FOUND && is_problem {
+            // Try to parse `NoVersionError` from the response body.
+            let problem_data: NoVersionErrorData = response
+                .json()
+                .await
+                .context("failed parsing error response")?;
+            return Err(TreeApiError::NoVersion {
+                missing_version: problem_data.missing_version,
+                version_count: problem_data.version_count,
+            });
+        }
+
+        let response = response.error_for_status().with_context(|| {
+            format!("requesting {response_description} for L1 batch #{l1_batch_number} returned non-OK response")
+        })?;
+        Ok(response.json().await.with_context(|| {
+            format!("failed deserializing {response_description} for L1 batch #{l1_batch_number}")
+        })?)
+    }
 }
 
 #[async_trait]
@@ -331,42 +466,55 @@ impl TreeApiClient for TreeApiHttpClient {
         l1_batch_number: L1BatchNumber,
         hashed_keys: Vec<U256>,
     ) -> Result<Vec<TreeEntryWithProof>, TreeApiError> {
-        let response = self
-            .inner
-            .post(&self.proofs_url)
-            .json(&TreeProofsRequest {
-                l1_batch_number,
-                hashed_keys,
-            })
-            .send()
-            .await
-            .map_err(|err| {
-                client_error(err, format_args!("proofs for L1 batch #{l1_batch_number}"))
-            })?;
+        let request = TreeProofsRequest {
+            l1_batch_number,
+            hashed_keys,
+            compact: false,
+        };
+        let response: TreeProofsResponse = self
+            .post(&self.proofs_url, &request, l1_batch_number, "proofs")
+            .await?;
+        Ok(response.entries)
+    }
 
-        let is_problem = response
-            .headers()
-            .get(header::CONTENT_TYPE)
-            .is_some_and(|header| *header == PROBLEM_CONTENT_TYPE);
-        if response.status() == StatusCode::NOT_FOUND && is_problem {
-            // Try to parse `NoVersionError` from the response body.
-            let problem_data: NoVersionErrorData = response
-                .json()
-                .await
-                .context("failed parsing error response")?;
-            return Err(TreeApiError::NoVersion {
-                missing_version: problem_data.missing_version,
-                version_count: problem_data.version_count,
-            });
-        }
+    async fn get_multi_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeMultiProof, TreeApiError> {
+        let request = TreeProofsRequest {
+            l1_batch_number,
+            hashed_keys,
+            compact: true,
+        };
+        let response: TreeProofsResponse = self
+            .post(&self.proofs_url, &request, l1_batch_number, "multi-proof")
+            .await?;
+        Ok(response
+            .multi_proof
+            .context("server returned no multi-proof; is it outdated?")?)
+    }
 
-        let response = response.error_for_status().with_context(|| {
-            format!("requesting proofs for L1 batch #{l1_batch_number} returned non-OK response")
-        })?;
-        let response: TreeProofsResponse = response.json().await.with_context(|| {
-            format!("failed deserializing proofs for L1 batch #{l1_batch_number}")
-        })?;
-        Ok(response.entries)
+    async fn get_range_absence_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        start_key: U256,
+        end_key: U256,
+    ) -> Result<Option<TreeRangeAbsenceProof>, TreeApiError> {
+        let request = TreeRangeAbsenceRequest {
+            l1_batch_number,
+            start_key,
+            end_key,
+        };
+        let response: TreeRangeAbsenceResponse = self
+            .post(
+                &self.range_absence_url,
+                &request,
+                l1_batch_number,
+                "range absence proof",
+            )
+            .await?;
+        Ok(response.proof)
     }
 }
 
@@ -394,14 +542,87 @@ impl AsyncTreeReader {
         State(this): State<Self>,
         Json(request): Json<TreeProofsRequest>,
     ) -> Result<Json<TreeProofsResponse>, TreeApiServerError> {
-        let latency = API_METRICS.latency[&MerkleTreeApiMethod::GetProofs].start();
-        let entries = this
-            .get_proofs_inner(request.l1_batch_number, request.hashed_keys)
+        let method = if request.compact {
+            MerkleTreeApiMethod::GetMultiProof
+        } else {
+            MerkleTreeApiMethod::GetProofs
+        };
+        let latency = API_METRICS.latency[&method].start();
+        let response = if request.compact {
+            let multi_proof = this
+                .get_multi_proof_inner(request.l1_batch_number, request.hashed_keys)
+                .await
+                .map_err(TreeApiServerError::NoTreeVersion)?;
+            TreeProofsResponse {
+                entries: vec![],
+                multi_proof: Some(multi_proof),
+            }
+        } else {
+            let entries = this
+                .get_proofs_inner(request.l1_batch_number, request.hashed_keys)
+                .await
+                .map_err(TreeApiServerError::NoTreeVersion)?;
+            TreeProofsResponse {
+                entries,
+                multi_proof: None,
+            }
+        };
+        latency.observe();
+        Ok(Json(response))
+    }
+
+    async fn get_multi_proof_inner(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeMultiProof, NoVersionError> {
+        if hashed_keys.is_empty() {
+            // A multi-proof cannot be created for no keys; still, check that the tree version exists.
+            self.clone()
+                .entries_with_proofs(l1_batch_number, vec![])
+                .await?;
+            return Ok(TreeMultiProof::default());
+        }
+        let multi_proof = self
+            .clone()
+            .entries_with_multi_proof(l1_batch_number, hashed_keys)
+            .await?;
+        Ok(map_multi_proof(multi_proof))
+    }
+
+    async fn get_range_absence_proof_inner(
+        &self,
+        request: TreeRangeAbsenceRequest,
+    ) -> Result<Option<TreeRangeAbsenceProof>, TreeApiServerError> {
+        let TreeRangeAbsenceRequest {
+            l1_batch_number,
+            start_key,
+            end_key,
+        } = request;
+        if start_key > end_key {
+            return Err(TreeApiServerError::InvalidRequest(format!(
+                "range start {start_key:#x} is greater than its end {end_key:#x}"
+            )));
+        }
+        let proof = self
+            .clone()
+            .range_absence_proof(l1_batch_number, start_key, end_key)
             .await
             .map_err(TreeApiServerError::NoTreeVersion)?;
-        let response = TreeProofsResponse { entries };
+        Ok(proof.map(|proof| TreeRangeAbsenceProof {
+            start: map_entry_with_proof(proof.start),
+            end: map_entry_with_proof(proof.end),
+        }))
+    }
+
+    async fn get_range_absence_proof_handler(
+        State(this): State<Self>,
+        Json(request): Json<TreeRangeAbsenceRequest>,
+    ) -> Result<Json<TreeRangeAbsenceResponse>, TreeApiServerError> {
+        let latency = API_METRICS.latency[&MerkleTreeApiMethod::GetRangeAbsenceProof].start();
+        let proof = this.get_range_absence_proof_inner(request).await?;
         latency.observe();
-        Ok(Json(response))
+        Ok(Json(TreeRangeAbsenceResponse { proof }))
     }
 
     async fn get_nodes_handler(
@@ -454,6 +675,10 @@ impl AsyncTreeReader {
         let app = Router::new()
             .route("/", routing::get(Self::info_handler))
             .route("/proofs", routing::post(Self::get_proofs_handler))
+            .route(
+                "/proofs/range-absence",
+                routing::post(Self::get_range_absence_proof_handler),
+            )
             .route("/debug/nodes", routing::post(Self::get_nodes_handler))
             .route(
                 "/debug/stale-keys",
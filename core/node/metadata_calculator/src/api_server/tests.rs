@@ -8,7 +8,9 @@ use tokio::{
     io::AsyncWriteExt,
     net::{TcpListener, TcpSocket},
 };
+use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
 use zksync_dal::{ConnectionPool, Core};
+use zksync_merkle_tree::TreeEntry;
 
 use super::*;
 use crate::tests::{gen_storage_logs, reset_db_state, run_calculator, setup_calculator};
@@ -55,7 +57,7 @@ async fn merkle_tree_api() {
     hashed_keys.extend((0_u8..10).map(|byte| U256::from_big_endian(&[byte; 32])));
 
     let proofs = api_client
-        .get_proofs(L1BatchNumber(5), hashed_keys)
+        .get_proofs(L1BatchNumber(5), hashed_keys.clone())
         .await
         .unwrap();
     assert_eq!(proofs.len(), 20);
@@ -65,6 +67,39 @@ async fn merkle_tree_api() {
         assert!(!proof.merkle_path.is_empty());
     }
 
+    let multi_proof = api_client
+        .get_multi_proof(L1BatchNumber(5), hashed_keys.clone())
+        .await
+        .unwrap();
+    assert_eq!(multi_proof.entries.len(), 20);
+    assert_multi_proof_is_valid(multi_proof, tree_info.root_hash);
+
+    let existing_key = hashed_keys[0];
+    let range_proof = api_client
+        .get_range_absence_proof(L1BatchNumber(5), existing_key, existing_key + 1)
+        .await
+        .unwrap();
+    assert!(range_proof.is_none());
+    let (start_key, end_key) = (existing_key + 1, existing_key + 10);
+    let range_proof = api_client
+        .get_range_absence_proof(L1BatchNumber(5), start_key, end_key)
+        .await
+        .unwrap()
+        .expect("range is empty");
+    let range_proof = zksync_merkle_tree::TreeRangeAbsenceProof {
+        start: map_api_entry_with_proof(start_key, range_proof.start),
+        end: map_api_entry_with_proof(end_key, range_proof.end),
+    };
+    range_proof
+        .verify(&Blake2Hasher, tree_info.root_hash)
+        .unwrap();
+
+    let err = api_client
+        .get_range_absence_proof(L1BatchNumber(5), end_key, start_key)
+        .await
+        .unwrap_err();
+    assert_matches!(err, TreeApiError::Internal(_));
+
     let err = api_client
         .get_proofs(L1BatchNumber(10), vec![])
         .await
@@ -123,6 +158,35 @@ async fn merkle_tree_api() {
     api_server_task.await.unwrap().unwrap();
 }
 
+fn assert_multi_proof_is_valid(multi_proof: TreeMultiProof, root_hash: H256) {
+    let (entries, path_lengths) = multi_proof
+        .entries
+        .into_iter()
+        .map(|entry| {
+            let tree_entry = TreeEntry::new(entry.key, entry.index, entry.value);
+            (tree_entry, entry.path_length)
+        })
+        .unzip();
+    let multi_proof = zksync_merkle_tree::TreeMultiProof {
+        entries,
+        path_lengths,
+        hashes: multi_proof.hashes,
+    };
+    multi_proof.verify(&Blake2Hasher, root_hash).unwrap();
+}
+
+fn map_api_entry_with_proof(
+    key: U256,
+    entry: TreeEntryWithProof,
+) -> zksync_merkle_tree::TreeEntryWithProof {
+    let mut merkle_path = entry.merkle_path;
+    merkle_path.reverse(); // The API uses root-to-leaf enumeration direction
+    zksync_merkle_tree::TreeEntryWithProof {
+        base: TreeEntry::new(key, entry.index, entry.value),
+        merkle_path,
+    }
+}
+
 fn assert_raw_nodes_response(response: &serde_json::Value) {
     let response = response.as_object().expect("not an object");
     let response = response["nodes"].as_object().expect("not an object");
@@ -228,4 +292,22 @@ async fn local_merkle_tree_client() {
     };
     assert_eq!(version_count, 6);
     assert_eq!(missing_version, 10);
+
+    let err = tree_reader
+        .get_multi_proof(L1BatchNumber(10), vec![U256::zero()])
+        .await
+        .unwrap_err();
+    assert_matches!(err, TreeApiError::NoVersion { .. });
+
+    let hashed_keys: Vec<_> = gen_storage_logs(20..30, 1)[0]
+        .iter()
+        .map(|log| log.key.hashed_key_u256())
+        .collect();
+    let multi_proof = tree_reader
+        .get_multi_proof(L1BatchNumber(5), hashed_keys)
+        .await
+        .unwrap();
+    assert_eq!(multi_proof.entries.len(), 10);
+    assert!(multi_proof.entries.iter().all(|entry| entry.index > 0));
+    assert_multi_proof_is_valid(multi_proof, tree_info.root_hash);
 }
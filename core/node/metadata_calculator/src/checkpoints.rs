@@ -0,0 +1,101 @@
+//! Periodic creation of Merkle tree checkpoints.
+
+use std::{
+    ffi::OsString,
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context as _;
+use tokio::{fs, task::JoinHandle};
+use zksync_object_store::RocksdbCheckpointStore;
+use zksync_types::L1BatchNumber;
+
+use crate::helpers::AsyncTree;
+
+/// Configuration of Merkle tree checkpoints created by [`MetadataCalculator`](crate::MetadataCalculator).
+#[derive(Debug, Clone)]
+pub struct MerkleTreeCheckpointsConfig {
+    /// Store to upload checkpoints to.
+    pub store: RocksdbCheckpointStore,
+    /// Interval in L1 batches between checkpoints.
+    pub interval: NonZeroU32,
+    /// Number of most recent checkpoints retained in the store.
+    pub retained_count: usize,
+}
+
+/// Creates tree checkpoints after the tree is saved, and uploads them to the object store in the background.
+#[derive(Debug)]
+pub(crate) struct TreeCheckpointer {
+    config: MerkleTreeCheckpointsConfig,
+    checkpoint_path: PathBuf,
+    /// Next L1 batch number of the tree, starting from which a new checkpoint should be created.
+    /// Lazily initialized from the latest checkpoint in the store.
+    next_checkpoint: Option<L1BatchNumber>,
+    upload_task: Option<JoinHandle<anyhow::Result<()>>>,
+}
+
+impl TreeCheckpointer {
+    pub fn new(config: MerkleTreeCheckpointsConfig, db_path: &Path) -> Self {
+        let mut checkpoint_path = OsString::from(db_path.as_os_str());
+        checkpoint_path.push(".checkpoint");
+        Self {
+            config,
+            checkpoint_path: checkpoint_path.into(),
+            next_checkpoint: None,
+            upload_task: None,
+        }
+    }
+
+    /// Creates a checkpoint if the tree has advanced far enough since the previous one, and the previous checkpoint
+    /// is uploaded. Must be called when the tree has no unsaved changes.
+    pub async fn maybe_create_checkpoint(&mut self, tree: &mut AsyncTree) -> anyhow::Result<()> {
+        if let Some(upload_task) = &self.upload_task {
+            if !upload_task.is_finished() {
+                return Ok(());
+            }
+            // `unwrap()` is safe by construction
+            let upload_task = self.upload_task.take().unwrap();
+            upload_task
+                .await
+                .context("checkpoint upload panicked")?
+                .context("failed uploading checkpoint")?;
+        }
+
+        let interval = self.config.interval.get();
+        let next_checkpoint = if let Some(number) = self.next_checkpoint {
+            number
+        } else {
+            let latest = self.config.store.list().await?.last().copied();
+            let number = latest.map_or(L1BatchNumber(0), |number| number + interval);
+            *self.next_checkpoint.insert(number)
+        };
+        if tree.next_l1_batch_number() < next_checkpoint {
+            return Ok(());
+        }
+
+        if fs::try_exists(&self.checkpoint_path).await? {
+            tracing::info!(
+                "Removing leftover Merkle tree checkpoint at `{}`",
+                self.checkpoint_path.display()
+            );
+            fs::remove_dir_all(&self.checkpoint_path).await?;
+        }
+        let next_l1_batch_number = tree.create_checkpoint(self.checkpoint_path.clone()).await?;
+        self.next_checkpoint = Some(next_l1_batch_number + interval);
+        tracing::info!(
+            "Created Merkle tree checkpoint at L1 batch #{next_l1_batch_number}; uploading it in background"
+        );
+
+        let store = self.config.store.clone();
+        let checkpoint_path = self.checkpoint_path.clone();
+        let retained_count = self.config.retained_count;
+        self.upload_task = Some(tokio::spawn(async move {
+            store.upload(next_l1_batch_number, &checkpoint_path).await?;
+            fs::remove_dir_all(&checkpoint_path).await?;
+            store.remove_old(retained_count).await?;
+            Ok(())
+        }));
+        Ok(())
+    }
+}
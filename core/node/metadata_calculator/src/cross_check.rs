@@ -0,0 +1,474 @@
+//! Cross-checking the Merkle tree against the ZK OS Merkle tree implementation.
+//!
+//! Both trees are fed identical per-batch storage logs; after each L1 batch, leaves touched by the batch
+//! and the leaf counts of the trees are compared. Enumeration indices are compared taking into account
+//! that the ZK OS tree has 2 guard leaves (at indices 0 and 1), while the first leaf in the legacy tree has index 1.
+//! Thus, a legacy index `i` corresponds to the ZK OS index `i + 1`, and the ZK OS leaf count exceeds
+//! the legacy leaf count by 2.
+//!
+//! # Limitations
+//!
+//! - Only leaves touched by an L1 batch are compared, together with the leaf counts. Both trees use different
+//!   hash functions, so their root hashes cannot be compared, and comparing full leaf sets after each batch would be
+//!   prohibitively expensive. Hence, a divergence in a leaf not touched by the batch (e.g., caused by a corrupted
+//!   RocksDB instance) is only detected once the leaf is touched by a later batch.
+//! - Writes are fed to the ZK OS tree in the same order as by the ZK OS tree manager, i.e., sorted by the enumeration
+//!   indices assigned by the legacy tree / Postgres. The ZK OS tree assigns indices to inserted leaves in the order
+//!   of insertion, so comparing indices checks that both trees agree on which keys are new, but not the order
+//!   in which indices are assigned.
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use serde::Serialize;
+use tokio::sync::watch;
+use zk_os_merkle_tree::{MerkleTree as ZkOsMerkleTree, RocksDBWrapper, TreeEntry as ZkOsTreeEntry};
+use zksync_config::configs::database::MerkleTreeMode;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_merkle_tree::{domain::ZkSyncTreeReader, TreeInstruction};
+use zksync_types::{u256_to_h256, L1BatchNumber, H256};
+
+use crate::{
+    helpers::L1BatchWithLogs,
+    metrics::{CrossCheckStage, CROSS_CHECK_METRICS},
+    LazyAsyncTreeReader,
+};
+
+/// Leaf in a Merkle tree as seen by the cross-checker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CrossCheckedLeaf {
+    /// Enumeration index of the leaf in the corresponding tree.
+    pub index: u64,
+    /// Value hash of the leaf.
+    pub value: H256,
+}
+
+/// Kind of divergence between the Merkle tree implementations.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TreeDivergenceKind {
+    /// Trees have different leaves (or different enumeration indices for the same leaf) for a key.
+    Entry {
+        /// Hashed storage key.
+        key: H256,
+        /// Leaf in the legacy tree, or `None` if the key is missing.
+        legacy: Option<CrossCheckedLeaf>,
+        /// Leaf in the ZK OS tree, or `None` if the key is missing.
+        zk_os: Option<CrossCheckedLeaf>,
+    },
+    /// Trees have inconsistent leaf counts.
+    LeafCount {
+        /// Leaf count of the legacy tree.
+        legacy: u64,
+        /// Leaf count of the ZK OS tree (including 2 guard leaves).
+        zk_os: u64,
+    },
+}
+
+/// Report about the first divergence between the Merkle tree implementations.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TreeDivergence {
+    /// L1 batch after which the trees diverge.
+    pub l1_batch_number: L1BatchNumber,
+    /// Divergence details.
+    #[serde(flatten)]
+    pub kind: TreeDivergenceKind,
+}
+
+impl fmt::Display for TreeDivergence {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let l1_batch_number = self.l1_batch_number;
+        match &self.kind {
+            TreeDivergenceKind::Entry { key, legacy, zk_os } => write!(
+                formatter,
+                "trees diverge at L1 batch #{l1_batch_number} for key {key:?}: legacy leaf {legacy:?}, ZK OS leaf {zk_os:?}"
+            ),
+            TreeDivergenceKind::LeafCount { legacy, zk_os } => write!(
+                formatter,
+                "trees diverge at L1 batch #{l1_batch_number}: legacy leaf count {legacy}, ZK OS leaf count {zk_os} \
+                 (expected {})",
+                legacy + 2
+            ),
+        }
+    }
+}
+
+/// Cross-checker maintaining a ZK OS Merkle tree alongside the legacy tree. Tree versions correspond to L1 batch numbers.
+#[derive(Debug)]
+pub struct TreeCrossChecker {
+    tree: ZkOsMerkleTree<RocksDBWrapper>,
+}
+
+impl TreeCrossChecker {
+    /// Opens or creates a ZK OS Merkle tree at the specified path.
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let db = RocksDBWrapper::new(path).with_context(|| {
+            format!(
+                "failed opening ZK OS Merkle tree RocksDB at `{}`",
+                path.display()
+            )
+        })?;
+        let tree = ZkOsMerkleTree::new(db).context("failed initializing ZK OS Merkle tree")?;
+        Ok(Self { tree })
+    }
+
+    /// Returns the next L1 batch number that should be cross-checked.
+    pub fn next_l1_batch_number(&self) -> anyhow::Result<L1BatchNumber> {
+        let latest_version = self.tree.latest_version()?;
+        let next_version = latest_version.map_or(0, |version| version + 1);
+        let next_version = u32::try_from(next_version).context("L1 batch number overflow")?;
+        Ok(L1BatchNumber(next_version))
+    }
+
+    /// Removes L1 batches starting from `next_l1_batch_number` from the ZK OS tree, e.g. after the legacy tree was reverted.
+    pub fn truncate(&mut self, next_l1_batch_number: L1BatchNumber) -> anyhow::Result<()> {
+        self.tree
+            .truncate_recent_versions(next_l1_batch_number.0.into())
+            .context("failed truncating ZK OS Merkle tree")
+    }
+
+    /// Applies storage logs for the specified L1 batch to the ZK OS tree and compares the result with the legacy tree,
+    /// which must have processed the same logs for this batch.
+    ///
+    /// Reads in `instructions` are ignored. Returns the first divergence in the order of keys touched
+    /// by the batch; if leaves for all touched keys coincide, leaf counts are compared. Leaves not touched
+    /// by the batch are not compared; see the [module docs](self) for details.
+    pub fn check_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        instructions: &[TreeInstruction],
+        legacy: &ZkSyncTreeReader,
+    ) -> anyhow::Result<Option<TreeDivergence>> {
+        let next_l1_batch_number = self.next_l1_batch_number()?;
+        anyhow::ensure!(
+            next_l1_batch_number == l1_batch_number,
+            "unexpected L1 batch to cross-check: expected #{next_l1_batch_number}, got #{l1_batch_number}"
+        );
+
+        let mut writes: Vec<_> = instructions
+            .iter()
+            .filter_map(|instruction| match instruction {
+                TreeInstruction::Write(entry) => Some(entry),
+                TreeInstruction::Read(_) => None,
+            })
+            .collect();
+        // The ZK OS tree assigns indices to inserted leaves in the order of insertion. Like the ZK OS tree manager,
+        // we order writes by enumeration indices so that insertions are aligned with the legacy tree.
+        writes.sort_unstable_by_key(|entry| entry.leaf_index);
+        let entries: Vec<_> = writes
+            .iter()
+            .map(|entry| ZkOsTreeEntry {
+                key: u256_to_h256(entry.key),
+                value: entry.value,
+            })
+            .collect();
+
+        let latency = CROSS_CHECK_METRICS.latency[&CrossCheckStage::ExtendTree].start();
+        let output = self.tree.extend(&entries).with_context(|| {
+            format!("failed extending ZK OS tree for L1 batch #{l1_batch_number}")
+        })?;
+        latency.observe();
+
+        let latency = CROSS_CHECK_METRICS.latency[&CrossCheckStage::Compare].start();
+        let mut keys: Vec<_> = writes.iter().map(|entry| entry.key).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        let legacy_entries = legacy.entries(l1_batch_number, &keys).with_context(|| {
+            format!("failed getting legacy tree entries for L1 batch #{l1_batch_number}")
+        })?;
+        let zk_os_keys: Vec<_> = keys.iter().copied().map(u256_to_h256).collect();
+        let zk_os_entries = self
+            .tree
+            .entries(l1_batch_number.0.into(), &zk_os_keys)
+            .with_context(|| {
+                format!("failed getting ZK OS tree entries for L1 batch #{l1_batch_number}")
+            })?;
+
+        let divergent_entry = legacy_entries
+            .iter()
+            .zip(zk_os_entries)
+            .zip(zk_os_keys)
+            .find_map(|((legacy_entry, zk_os_entry), key)| {
+                let legacy = (!legacy_entry.is_empty()).then_some(CrossCheckedLeaf {
+                    index: legacy_entry.leaf_index,
+                    value: legacy_entry.value,
+                });
+                let zk_os = zk_os_entry.map(|(index, value)| CrossCheckedLeaf { index, value });
+                let expected_zk_os = legacy.map(|leaf| CrossCheckedLeaf {
+                    index: leaf.index + 1,
+                    ..leaf
+                });
+                (zk_os != expected_zk_os).then_some(TreeDivergenceKind::Entry {
+                    key,
+                    legacy,
+                    zk_os,
+                })
+            });
+        latency.observe();
+        if let Some(kind) = divergent_entry {
+            return Ok(Some(TreeDivergence {
+                l1_batch_number,
+                kind,
+            }));
+        }
+
+        let (_, legacy_leaf_count) = legacy
+            .root_info(l1_batch_number)
+            .with_context(|| format!("legacy tree doesn't contain L1 batch #{l1_batch_number}"))?;
+        if output.leaf_count != legacy_leaf_count + 2 {
+            return Ok(Some(TreeDivergence {
+                l1_batch_number,
+                kind: TreeDivergenceKind::LeafCount {
+                    legacy: legacy_leaf_count,
+                    zk_os: output.leaf_count,
+                },
+            }));
+        }
+        Ok(None)
+    }
+}
+
+/// Loads storage logs fed to the Merkle tree for the specified L1 batch. Returns `None` if the batch is not present
+/// in Postgres.
+pub async fn load_l1_batch_tree_instructions(
+    storage: &mut Connection<'_, Core>,
+    l1_batch_number: L1BatchNumber,
+) -> anyhow::Result<Option<Vec<TreeInstruction>>> {
+    // Reads are ignored by the cross-checker, so there's no need to load protective reads.
+    let l1_batch = L1BatchWithLogs::new(storage, l1_batch_number, MerkleTreeMode::Lightweight)
+        .await
+        .with_context(|| format!("failed loading storage logs for L1 batch #{l1_batch_number}"))?;
+    Ok(l1_batch.map(|l1_batch| l1_batch.storage_logs))
+}
+
+/// Background task cross-checking the Merkle tree maintained by [`MetadataCalculator`](crate::MetadataCalculator)
+/// against a ZK OS Merkle tree. The task stops checking after the first detected divergence, which is logged
+/// and reported in metrics.
+#[derive(Debug)]
+#[must_use = "Task should `run()` in a managed Tokio task"]
+pub struct TreeCrossCheckTask {
+    tree_reader: LazyAsyncTreeReader,
+    pool: ConnectionPool<Core>,
+    db_path: PathBuf,
+    poll_interval: Duration,
+}
+
+impl TreeCrossCheckTask {
+    pub(super) fn new(
+        tree_reader: LazyAsyncTreeReader,
+        pool: ConnectionPool<Core>,
+        db_path: PathBuf,
+    ) -> Self {
+        Self {
+            tree_reader,
+            pool,
+            db_path,
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+
+    async fn ensure_postgres_contains(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<()> {
+        let mut storage = self.pool.connection_tagged("metadata_calculator").await?;
+        let earliest_l1_batch = storage.blocks_dal().get_earliest_l1_batch_number().await?;
+        if let Some(earliest_l1_batch) = earliest_l1_batch {
+            anyhow::ensure!(
+                earliest_l1_batch <= l1_batch_number,
+                "cannot cross-check Merkle trees starting from L1 batch #{l1_batch_number}: earliest L1 batch in Postgres \
+                 is #{earliest_l1_batch}. Cross-checking requires storage logs for all L1 batches starting from genesis; \
+                 it is not supported for nodes recovered from a snapshot or with pruned data"
+            );
+        }
+        Ok(())
+    }
+
+    /// Runs this task indefinitely.
+    #[tracing::instrument(skip_all)]
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let legacy = tokio::select! {
+            res = self.tree_reader.wait() => {
+                match res {
+                    Some(reader) => reader.into_db(),
+                    None => {
+                        tracing::info!("Merkle tree dropped; shutting down tree cross-checking");
+                        return Ok(());
+                    }
+                }
+            }
+            _ = stop_receiver.changed() => {
+                tracing::info!("Stop request received before Merkle tree is initialized; shutting down tree cross-checking");
+                return Ok(());
+            }
+        };
+        let legacy = ZkSyncTreeReader::new(legacy).context("failed creating Merkle tree reader")?;
+
+        let db_path = self.db_path.clone();
+        let mut checker = Some(
+            tokio::task::spawn_blocking(move || TreeCrossChecker::open(&db_path))
+                .await
+                .context("panicked opening ZK OS Merkle tree")??,
+        );
+
+        let mut next_l1_batch_number = checker.as_ref().unwrap().next_l1_batch_number()?;
+        self.ensure_postgres_contains(next_l1_batch_number).await?;
+        tracing::info!(
+            "Started cross-checking Merkle tree against ZK OS Merkle tree at `{}` from L1 batch #{next_l1_batch_number}",
+            self.db_path.display()
+        );
+
+        while !*stop_receiver.borrow_and_update() {
+            let legacy_next_l1_batch_number = legacy.next_l1_batch_number();
+            if legacy_next_l1_batch_number < next_l1_batch_number {
+                tracing::info!(
+                    "Merkle tree was reverted to L1 batch #{legacy_next_l1_batch_number}; truncating cross-checked ZK OS tree \
+                     from L1 batch #{next_l1_batch_number}"
+                );
+                let mut inner = checker.take().unwrap();
+                let inner = tokio::task::spawn_blocking(move || {
+                    inner.truncate(legacy_next_l1_batch_number)?;
+                    anyhow::Ok(inner)
+                })
+                .await
+                .context("panicked truncating ZK OS Merkle tree")??;
+                checker = Some(inner);
+                next_l1_batch_number = legacy_next_l1_batch_number;
+            }
+            if legacy_next_l1_batch_number == next_l1_batch_number {
+                if tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                    .await
+                    .is_ok()
+                {
+                    break;
+                }
+                continue;
+            }
+
+            let mut storage = self.pool.connection_tagged("metadata_calculator").await?;
+            let instructions =
+                load_l1_batch_tree_instructions(&mut storage, next_l1_batch_number)
+                    .await?
+                    .with_context(|| {
+                        format!("L1 batch #{next_l1_batch_number} processed by Merkle tree is missing from Postgres")
+                    })?;
+            drop(storage);
+
+            let mut inner = checker.take().unwrap();
+            let legacy = legacy.clone();
+            let latency = CROSS_CHECK_METRICS.latency[&CrossCheckStage::Total].start();
+            let (inner, divergence) = tokio::task::spawn_blocking(move || {
+                let divergence =
+                    inner.check_l1_batch(next_l1_batch_number, &instructions, &legacy)?;
+                anyhow::Ok((inner, divergence))
+            })
+            .await
+            .context("panicked cross-checking Merkle trees")??;
+            latency.observe();
+            checker = Some(inner);
+
+            if let Some(divergence) = divergence {
+                let report = serde_json::to_string(&divergence)
+                    .context("failed serializing divergence report")?;
+                tracing::error!(%report, "Merkle tree cross-checking failed: {divergence}");
+                CROSS_CHECK_METRICS
+                    .divergent_l1_batch
+                    .set(divergence.l1_batch_number.0.into());
+                // Further checks don't make sense since the ZK OS tree has diverged; wait for the stop request.
+                stop_receiver.changed().await.ok();
+                break;
+            }
+            tracing::debug!("Cross-checked Merkle trees for L1 batch #{next_l1_batch_number}");
+            next_l1_batch_number += 1;
+            CROSS_CHECK_METRICS
+                .next_l1_batch
+                .set(next_l1_batch_number.0.into());
+        }
+        tracing::info!("Stop request received, tree cross-checking is shut down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use tempfile::TempDir;
+    use zksync_merkle_tree::domain::ZkSyncTree;
+    use zksync_storage::RocksDB;
+    use zksync_types::U256;
+
+    use super::*;
+
+    fn create_legacy_tree(temp_dir: &TempDir) -> ZkSyncTree {
+        let db = RocksDB::new(&temp_dir.path().join("legacy")).unwrap();
+        ZkSyncTree::new_lightweight(db.into()).unwrap()
+    }
+
+    fn inserts(keys: impl Iterator<Item = u64>) -> Vec<TreeInstruction> {
+        keys.map(|i| TreeInstruction::write(U256::from(i), i, H256::repeat_byte(i as u8)))
+            .collect()
+    }
+
+    #[test]
+    fn cross_checking_consistent_trees() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut legacy_tree = create_legacy_tree(&temp_dir);
+        let mut checker = TreeCrossChecker::open(&temp_dir.path().join("zk_os")).unwrap();
+        assert_eq!(checker.next_l1_batch_number().unwrap(), L1BatchNumber(0));
+
+        let mut second_batch = inserts(11..16);
+        second_batch.extend(
+            (1..5).map(|i| TreeInstruction::write(U256::from(i), i, H256::repeat_byte(0xff))),
+        );
+        second_batch.push(TreeInstruction::Read(U256::from(7)));
+        for (number, instructions) in [inserts(1..11), second_batch].iter().enumerate() {
+            let l1_batch_number = L1BatchNumber(number as u32);
+            legacy_tree.process_l1_batch(instructions).unwrap();
+            legacy_tree.save().unwrap();
+            let divergence = checker
+                .check_l1_batch(l1_batch_number, instructions, &legacy_tree.reader())
+                .unwrap();
+            assert_eq!(divergence, None);
+        }
+        assert_eq!(checker.next_l1_batch_number().unwrap(), L1BatchNumber(2));
+
+        checker.truncate(L1BatchNumber(1)).unwrap();
+        assert_eq!(checker.next_l1_batch_number().unwrap(), L1BatchNumber(1));
+    }
+
+    #[test]
+    fn cross_checking_divergent_trees() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut legacy_tree = create_legacy_tree(&temp_dir);
+        let mut checker = TreeCrossChecker::open(&temp_dir.path().join("zk_os")).unwrap();
+
+        let instructions = inserts(1..11);
+        legacy_tree.process_l1_batch(&instructions).unwrap();
+        legacy_tree.save().unwrap();
+        checker
+            .check_l1_batch(L1BatchNumber(0), &instructions, &legacy_tree.reader())
+            .unwrap();
+
+        // Swap enumeration indices for 2 inserted keys.
+        let legacy_instructions = inserts(11..13);
+        legacy_tree.process_l1_batch(&legacy_instructions).unwrap();
+        legacy_tree.save().unwrap();
+        let instructions = [
+            TreeInstruction::write(U256::from(11), 12, H256::repeat_byte(11)),
+            TreeInstruction::write(U256::from(12), 11, H256::repeat_byte(12)),
+        ];
+        let divergence = checker
+            .check_l1_batch(L1BatchNumber(1), &instructions, &legacy_tree.reader())
+            .unwrap()
+            .expect("no divergence");
+
+        assert_eq!(divergence.l1_batch_number, L1BatchNumber(1));
+        assert_matches!(
+            divergence.kind,
+            TreeDivergenceKind::Entry { key, legacy, zk_os } if key == u256_to_h256(U256::from(11))
+                && legacy == Some(CrossCheckedLeaf { index: 11, value: H256::repeat_byte(11) })
+                && zk_os == Some(CrossCheckedLeaf { index: 13, value: H256::repeat_byte(11) })
+        );
+    }
+}
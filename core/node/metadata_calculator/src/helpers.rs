@@ -3,6 +3,7 @@
 use std::{
     collections::{BTreeMap, HashSet},
     future::Future,
+    path::PathBuf,
     sync::Arc,
     time::Duration,
 };
@@ -25,7 +26,7 @@ use zksync_merkle_tree::{
     repair::StaleKeysRepairTask,
     unstable::{NodeKey, RawNode},
     Database, Key, MerkleTreeColumnFamily, NoVersionError, RocksDBWrapper, TreeEntry,
-    TreeEntryWithProof, TreeInstruction,
+    TreeEntryWithProof, TreeInstruction, TreeMultiProof, TreeRangeAbsenceProof,
 };
 use zksync_shared_metrics::tree::{LoadChangesStage, TreeUpdateStage, METRICS};
 use zksync_shared_resources::tree::MerkleTreeInfo;
@@ -202,6 +203,40 @@ pub(super) async fn create_readonly_db(
     .context("panicked creating Merkle tree RocksDB")?
 }
 
+pub(super) async fn create_secondary_db(
+    config: MerkleTreeReaderConfig,
+    secondary_path: PathBuf,
+) -> anyhow::Result<RocksDBWrapper> {
+    tokio::task::spawn_blocking(move || {
+        let MerkleTreeReaderConfig {
+            db_path,
+            multi_get_chunk_size,
+            block_cache_capacity,
+            include_indices_and_filters_in_block_cache,
+            ..
+        } = config;
+
+        tracing::info!(
+            "Initializing secondary Merkle tree database for `{db_path:?}` at `{secondary_path:?}` with {multi_get_chunk_size} multi-get chunk size, \
+             {block_cache_capacity}B block cache (indices & filters included: {include_indices_and_filters_in_block_cache:?})"
+        );
+        let db = RocksDB::secondary_with_options(
+            &db_path,
+            &secondary_path,
+            RocksDBOptions {
+                block_cache_capacity: Some(block_cache_capacity),
+                include_indices_and_filters_in_block_cache,
+                ..RocksDBOptions::default()
+            },
+        )?;
+        let mut db = RocksDBWrapper::from(db);
+        db.set_multi_get_chunk_size(multi_get_chunk_size);
+        Ok(db)
+    })
+    .await
+    .context("panicked creating secondary Merkle tree RocksDB")?
+}
+
 /// Wrapper around the "main" tree implementation used by [`MetadataCalculator`].
 ///
 /// Async methods provided by this wrapper are not cancel-safe! This is probably not an issue;
@@ -329,6 +364,18 @@ impl AsyncTree {
     pub fn roll_back_logs(&mut self, last_l1_batch_to_keep: L1BatchNumber) -> anyhow::Result<()> {
         self.as_mut().roll_back_logs(last_l1_batch_to_keep)
     }
+
+    pub async fn create_checkpoint(&mut self, path: PathBuf) -> anyhow::Result<L1BatchNumber> {
+        let tree = self.inner.take().context(Self::INCONSISTENT_MSG)?;
+        let (tree, result) = tokio::task::spawn_blocking(move || {
+            let result = tree.create_checkpoint(&path);
+            (tree, result)
+        })
+        .await
+        .context("Merkle tree panicked during creating checkpoint")?;
+        self.inner = Some(tree);
+        result
+    }
 }
 
 /// Async version of [`ZkSyncTreeReader`].
@@ -406,6 +453,32 @@ impl AsyncTreeReader {
             .unwrap()
     }
 
+    pub async fn entries_with_multi_proof(
+        self,
+        l1_batch_number: L1BatchNumber,
+        keys: Vec<Key>,
+    ) -> Result<TreeMultiProof, NoVersionError> {
+        tokio::task::spawn_blocking(move || {
+            self.inner.entries_with_multi_proof(l1_batch_number, &keys)
+        })
+        .await
+        .unwrap()
+    }
+
+    pub async fn range_absence_proof(
+        self,
+        l1_batch_number: L1BatchNumber,
+        start_key: Key,
+        end_key: Key,
+    ) -> Result<Option<TreeRangeAbsenceProof>, NoVersionError> {
+        tokio::task::spawn_blocking(move || {
+            self.inner
+                .range_absence_proof(l1_batch_number, start_key, end_key)
+        })
+        .await
+        .unwrap()
+    }
+
     pub(crate) async fn raw_nodes(self, keys: Vec<NodeKey>) -> Vec<Option<RawNode>> {
         tokio::task::spawn_blocking(move || self.inner.raw_nodes(&keys))
             .await
@@ -449,7 +522,7 @@ impl WeakAsyncTreeReader {
 }
 
 /// Lazily initialized [`AsyncTreeReader`].
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LazyAsyncTreeReader(pub(super) watch::Receiver<Option<AsyncTreeReader>>);
 
 impl LazyAsyncTreeReader {
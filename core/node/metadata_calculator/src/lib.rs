@@ -21,25 +21,35 @@ use zksync_object_store::ObjectStore;
 use zksync_shared_metrics::tree::METRICS;
 use zksync_types::try_stoppable;
 
-use self::{
-    helpers::{create_db, Delayer, GenericAsyncTree, MerkleTreeHealth, MerkleTreeHealthCheck},
-    pruning::PruningHandles,
-    updater::TreeUpdater,
-};
 pub use self::{
+    checkpoints::MerkleTreeCheckpointsConfig,
+    cross_check::{
+        load_l1_batch_tree_instructions, CrossCheckedLeaf, TreeCrossCheckTask, TreeCrossChecker,
+        TreeDivergence, TreeDivergenceKind,
+    },
     helpers::{AsyncTreeReader, LazyAsyncTreeReader},
     pruning::MerkleTreePruningTask,
     repair::StaleKeysRepairTask,
+    replica::TreeReplicaTask,
+};
+use self::{
+    checkpoints::TreeCheckpointer,
+    helpers::{create_db, Delayer, GenericAsyncTree, MerkleTreeHealth, MerkleTreeHealthCheck},
+    pruning::PruningHandles,
+    updater::TreeUpdater,
 };
 use crate::helpers::create_readonly_db;
 
 pub mod api_server;
+mod checkpoints;
+mod cross_check;
 mod helpers;
 mod metrics;
 pub mod node;
 mod pruning;
 mod recovery;
 mod repair;
+mod replica;
 #[cfg(test)]
 pub(crate) mod tests;
 mod updater;
@@ -142,6 +152,7 @@ pub struct MetadataCalculator {
     delayer: Delayer,
     health_updater: HealthUpdater,
     max_l1_batches_per_iter: usize,
+    checkpoints: Option<MerkleTreeCheckpointsConfig>,
 }
 
 impl MetadataCalculator {
@@ -183,6 +194,7 @@ impl MetadataCalculator {
             delayer: Delayer::new(config.delay_interval),
             health_updater,
             max_l1_batches_per_iter: config.max_l1_batches_per_iter,
+            checkpoints: None,
             config,
         })
     }
@@ -194,6 +206,13 @@ impl MetadataCalculator {
         self
     }
 
+    /// Enables periodically creating tree checkpoints and uploading them to the object store.
+    #[must_use]
+    pub fn with_checkpoints(mut self, config: MerkleTreeCheckpointsConfig) -> Self {
+        self.checkpoints = Some(config);
+        self
+    }
+
     /// Returns a health check for this calculator.
     pub fn tree_health_check(&self) -> impl CheckHealth {
         MerkleTreeHealthCheck::new(self.health_updater.subscribe(), self.tree_reader())
@@ -218,6 +237,12 @@ impl MetadataCalculator {
         StaleKeysRepairTask::new(self.tree_reader())
     }
 
+    /// Returns a task cross-checking the tree against a ZK OS Merkle tree stored at `db_path`.
+    /// This method should be called once.
+    pub fn tree_cross_check_task(&self, db_path: PathBuf) -> TreeCrossCheckTask {
+        TreeCrossCheckTask::new(self.tree_reader(), self.pool.clone(), db_path)
+    }
+
     async fn create_tree(&self) -> anyhow::Result<GenericAsyncTree> {
         self.health_updater
             .update(MerkleTreeHealth::Initialization.into());
@@ -269,12 +294,16 @@ impl MetadataCalculator {
         self.health_updater
             .update(MerkleTreeHealth::MainLoop(tree_info).into());
 
-        let updater = TreeUpdater::new(
+        let mut updater = TreeUpdater::new(
             tree,
             self.max_l1_batches_per_iter,
             self.object_store,
             self.config.sealed_batches_have_protective_reads,
         );
+        if let Some(checkpoints) = self.checkpoints {
+            updater =
+                updater.with_checkpointer(TreeCheckpointer::new(checkpoints, &self.config.db_path));
+        }
         updater
             .loop_updating_tree(self.delayer, &self.pool, stop_receiver)
             .await
@@ -59,3 +59,43 @@ pub(super) struct MetadataCalculatorRecoveryMetrics {
 #[vise::register]
 pub(super) static RECOVERY_METRICS: vise::Global<MetadataCalculatorRecoveryMetrics> =
     vise::Global::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "stage", rename_all = "snake_case")]
+pub(super) enum CrossCheckStage {
+    ExtendTree,
+    Compare,
+    Total,
+}
+
+/// Metrics for cross-checking the Merkle tree against the ZK OS Merkle tree.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_metadata_calculator_cross_check")]
+pub(super) struct CrossCheckMetrics {
+    /// Next L1 batch to be cross-checked.
+    pub next_l1_batch: Gauge<u64>,
+    /// L1 batch at which the trees have diverged. Not set if no divergence was detected.
+    pub divergent_l1_batch: Gauge<u64>,
+    /// Latency of a cross-checking stage for a single L1 batch.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub latency: Family<CrossCheckStage, Histogram<Duration>>,
+}
+
+#[vise::register]
+pub(super) static CROSS_CHECK_METRICS: vise::Global<CrossCheckMetrics> = vise::Global::new();
+
+/// Metrics for Merkle tree replicas backed by secondary RocksDB instances.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_metadata_calculator_replica")]
+pub(super) struct ReplicaMetrics {
+    /// Next L1 batch that the tree replica will observe.
+    pub next_l1_batch: Gauge<u64>,
+    /// Lag of the tree replica relative to the primary tree, measured in L1 batches.
+    pub lag: Gauge<u64>,
+    /// Latency of catching up the replica with the primary tree.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub catch_up_latency: Histogram<Duration>,
+}
+
+#[vise::register]
+pub(super) static REPLICA_METRICS: vise::Global<ReplicaMetrics> = vise::Global::new();
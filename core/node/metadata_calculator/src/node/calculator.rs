@@ -1,20 +1,26 @@
-use std::{net::Ipv4Addr, sync::Arc, time::Duration};
+use std::{net::Ipv4Addr, path::PathBuf, sync::Arc, time::Duration};
 
 use anyhow::Context;
-use zksync_config::configs::{api::MerkleTreeApiConfig, database::MerkleTreeMode};
+use zksync_config::configs::{
+    api::MerkleTreeApiConfig,
+    database::{MerkleTreeMode, RocksdbCheckpointsConfig},
+};
 use zksync_dal::node::{MasterPool, PoolResource, ReplicaPool};
 use zksync_health_check::AppHealthCheck;
 use zksync_node_framework::{
     service::ShutdownHook, FromContext, IntoContext, StopReceiver, Task, TaskId, WiringError,
     WiringLayer,
 };
-use zksync_object_store::ObjectStore;
+use zksync_object_store::{
+    ObjectStore, ObjectStoreFactory, RocksdbCheckpointKind, RocksdbCheckpointStore,
+};
 use zksync_shared_resources::tree::TreeApiClient;
 use zksync_storage::RocksDB;
 
 use super::tree_api_server::TreeApiTask;
 use crate::{
-    MerkleTreePruningTask, MetadataCalculator, MetadataCalculatorConfig, StaleKeysRepairTask,
+    MerkleTreeCheckpointsConfig, MerkleTreePruningTask, MetadataCalculator,
+    MetadataCalculatorConfig, StaleKeysRepairTask, TreeCrossCheckTask,
 };
 
 /// Wiring layer for Metadata calculator and Tree API.
@@ -24,6 +30,8 @@ pub struct MetadataCalculatorLayer {
     tree_api_config: Option<MerkleTreeApiConfig>,
     pruning_config: Option<Duration>,
     stale_keys_repair_enabled: bool,
+    checkpoints_config: Option<RocksdbCheckpointsConfig>,
+    cross_check_path: Option<PathBuf>,
 }
 
 #[derive(Debug, FromContext)]
@@ -50,6 +58,9 @@ pub struct Output {
     /// Only provided if enabled in the config.
     #[context(task)]
     stale_keys_repair_task: Option<StaleKeysRepairTask>,
+    /// Only provided if enabled in the config.
+    #[context(task)]
+    tree_cross_check_task: Option<TreeCrossCheckTask>,
     rocksdb_shutdown_hook: ShutdownHook,
 }
 
@@ -60,6 +71,8 @@ impl MetadataCalculatorLayer {
             tree_api_config: None,
             pruning_config: None,
             stale_keys_repair_enabled: false,
+            checkpoints_config: None,
+            cross_check_path: None,
         }
     }
 
@@ -77,6 +90,17 @@ impl MetadataCalculatorLayer {
         self.stale_keys_repair_enabled = true;
         self
     }
+
+    pub fn with_checkpoints(mut self, checkpoints_config: RocksdbCheckpointsConfig) -> Self {
+        self.checkpoints_config = Some(checkpoints_config);
+        self
+    }
+
+    /// Enables cross-checking the Merkle tree against a ZK OS Merkle tree stored at the specified path.
+    pub fn with_cross_check(mut self, db_path: PathBuf) -> Self {
+        self.cross_check_path = Some(db_path);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -110,6 +134,25 @@ impl WiringLayer for MetadataCalculatorLayer {
             .await?
             .with_recovery_pool(recovery_pool);
 
+        if let Some(config) = self.checkpoints_config {
+            if let (Some(interval), Some(object_store_config)) =
+                (config.merkle_tree_interval, config.object_store)
+            {
+                let object_store = ObjectStoreFactory::new(object_store_config)
+                    .create_store()
+                    .await?;
+                metadata_calculator =
+                    metadata_calculator.with_checkpoints(MerkleTreeCheckpointsConfig {
+                        store: RocksdbCheckpointStore::new(
+                            object_store,
+                            RocksdbCheckpointKind::MerkleTree,
+                        ),
+                        interval,
+                        retained_count: config.retained_count.get(),
+                    });
+            }
+        }
+
         app_health
             .insert_custom_component(Arc::new(metadata_calculator.tree_health_check()))
             .map_err(WiringError::internal)?;
@@ -140,6 +183,10 @@ impl WiringLayer for MetadataCalculatorLayer {
             None
         };
 
+        let tree_cross_check_task = self
+            .cross_check_path
+            .map(|db_path| metadata_calculator.tree_cross_check_task(db_path));
+
         let tree_api_client = Arc::new(metadata_calculator.tree_reader());
 
         let rocksdb_shutdown_hook = ShutdownHook::new("rocksdb_terminaton", async {
@@ -155,6 +202,7 @@ impl WiringLayer for MetadataCalculatorLayer {
             tree_api_task,
             pruning_task,
             stale_keys_repair_task,
+            tree_cross_check_task,
             rocksdb_shutdown_hook,
         })
     }
@@ -182,6 +230,17 @@ impl Task for StaleKeysRepairTask {
     }
 }
 
+#[async_trait::async_trait]
+impl Task for TreeCrossCheckTask {
+    fn id(&self) -> TaskId {
+        "merkle_tree_cross_check_task".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
+
 #[async_trait::async_trait]
 impl Task for MerkleTreePruningTask {
     fn id(&self) -> TaskId {
@@ -4,12 +4,14 @@ use std::{
 };
 
 use zksync_config::configs::api::MerkleTreeApiConfig;
+use zksync_dal::node::{PoolResource, ReplicaPool};
+use zksync_health_check::AppHealthCheck;
 use zksync_node_framework::{
-    task::TaskKind, IntoContext, StopReceiver, Task, TaskId, WiringError, WiringLayer,
+    task::TaskKind, FromContext, IntoContext, StopReceiver, Task, TaskId, WiringError, WiringLayer,
 };
 use zksync_shared_resources::tree::TreeApiClient;
 
-use crate::{LazyAsyncTreeReader, MerkleTreeReaderConfig, TreeReaderTask};
+use crate::{LazyAsyncTreeReader, MerkleTreeReaderConfig, TreeReaderTask, TreeReplicaTask};
 
 #[derive(Debug)]
 pub struct TreeApiTask {
@@ -36,7 +38,11 @@ impl Task for TreeApiTask {
     }
 }
 
-/// Mutually exclusive with [`MetadataCalculatorLayer`].
+/// Mutually exclusive with [`MetadataCalculatorLayer`], unless [`MerkleTreeApiConfig::secondary_path`] is set.
+/// In the latter case, the tree is served from a secondary RocksDB instance, which can be opened concurrently
+/// with the tree component (potentially running in another process).
+///
+/// [`MetadataCalculatorLayer`]: super::MetadataCalculatorLayer
 #[derive(Debug)]
 pub struct TreeApiServerLayer {
     config: MerkleTreeReaderConfig,
@@ -49,35 +55,69 @@ impl TreeApiServerLayer {
     }
 }
 
+#[derive(Debug, FromContext)]
+pub struct Input {
+    /// Only needed if the tree is served from a secondary RocksDB instance.
+    replica_pool: Option<PoolResource<ReplicaPool>>,
+    #[context(default)]
+    app_health: Arc<AppHealthCheck>,
+}
+
 #[derive(Debug, IntoContext)]
 pub struct Output {
     tree_api_client: Arc<dyn TreeApiClient>,
+    /// Only provided if the tree is served from a primary RocksDB instance.
     #[context(task)]
-    tree_reader_task: TreeReaderTask,
+    tree_reader_task: Option<TreeReaderTask>,
+    /// Only provided if the tree is served from a secondary RocksDB instance.
+    #[context(task)]
+    tree_replica_task: Option<TreeReplicaTask>,
     #[context(task)]
     tree_api_task: TreeApiTask,
 }
 
 #[async_trait::async_trait]
 impl WiringLayer for TreeApiServerLayer {
-    type Input = ();
+    type Input = Input;
     type Output = Output;
 
     fn layer_name(&self) -> &'static str {
         "tree_api_server"
     }
 
-    async fn wire(self, (): Self::Input) -> Result<Self::Output, WiringError> {
-        let tree_reader_task = TreeReaderTask::new(self.config);
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let (tree_reader, tree_reader_task, tree_replica_task) =
+            if let Some(secondary_path) = self.api_config.secondary_path {
+                let replica_pool = input.replica_pool.ok_or_else(|| {
+                    WiringError::Configuration("Replica pool is required for tree replica".into())
+                })?;
+                let task = TreeReplicaTask::new(
+                    self.config,
+                    secondary_path,
+                    replica_pool.get_singleton().await?,
+                    self.api_config.replica_catch_up_interval,
+                    self.api_config.replica_max_lag,
+                );
+                input
+                    .app_health
+                    .insert_component(task.health_check())
+                    .map_err(WiringError::internal)?;
+                (task.tree_reader(), None, Some(task))
+            } else {
+                let task = TreeReaderTask::new(self.config);
+                (task.tree_reader(), Some(task), None)
+            };
+
         let bind_addr = (Ipv4Addr::UNSPECIFIED, self.api_config.port).into();
         let tree_api_task = TreeApiTask {
             bind_addr,
-            tree_reader: tree_reader_task.tree_reader(),
+            tree_reader: tree_reader.clone(),
         };
         Ok(Output {
-            tree_api_client: Arc::new(tree_reader_task.tree_reader()),
+            tree_api_client: Arc::new(tree_reader),
             tree_api_task,
             tree_reader_task,
+            tree_replica_task,
         })
     }
 }
@@ -96,3 +136,14 @@ impl Task for TreeReaderTask {
         (*self).run(stop_receiver.0).await
     }
 }
+
+#[async_trait::async_trait]
+impl Task for TreeReplicaTask {
+    fn id(&self) -> TaskId {
+        "merkle_tree_replica_task".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -0,0 +1,250 @@
+//! Merkle tree replicas backed by secondary RocksDB instances.
+
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_config::configs::database::MerkleTreeMode;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_types::L1BatchNumber;
+
+use crate::{
+    helpers::{create_secondary_db, AsyncTreeReader, LazyAsyncTreeReader},
+    metrics::REPLICA_METRICS,
+    MerkleTreeReaderConfig,
+};
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+enum TreeReplicaHealth {
+    Initialization,
+    Running {
+        next_l1_batch_number: L1BatchNumber,
+        primary_next_l1_batch_number: L1BatchNumber,
+        lag: u32,
+        max_lag: u32,
+    },
+    ShuttingDown,
+}
+
+impl From<TreeReplicaHealth> for Health {
+    fn from(health: TreeReplicaHealth) -> Self {
+        let status = match &health {
+            TreeReplicaHealth::Initialization => HealthStatus::Affected,
+            TreeReplicaHealth::Running { lag, max_lag, .. } => {
+                if lag <= max_lag {
+                    HealthStatus::Ready
+                } else {
+                    HealthStatus::Affected
+                }
+            }
+            TreeReplicaHealth::ShuttingDown => HealthStatus::ShuttingDown,
+        };
+        Health::from(status).with_details(health)
+    }
+}
+
+/// Alternative to [`TreeReaderTask`](crate::TreeReaderTask) that opens the Merkle tree RocksDB as a secondary instance.
+/// Unlike a readonly instance, a secondary instance can be opened concurrently with the primary instance
+/// written to by [`MetadataCalculator`](crate::MetadataCalculator), potentially in another process. The task periodically catches up
+/// the replica with the primary instance and reports replica lag (relative to the tree data persisted in Postgres)
+/// in its health check.
+#[derive(Debug)]
+#[must_use = "Task should `run()` in a managed Tokio task"]
+pub struct TreeReplicaTask {
+    config: MerkleTreeReaderConfig,
+    secondary_path: PathBuf,
+    pool: ConnectionPool<Core>,
+    catch_up_interval: Duration,
+    max_lag: u32,
+    tree_reader: watch::Sender<Option<AsyncTreeReader>>,
+    health_updater: HealthUpdater,
+}
+
+impl TreeReplicaTask {
+    /// Creates a new task. `secondary_path` is the directory for secondary RocksDB instance files; it must differ from
+    /// the primary DB path. The replica is considered healthy if it lags behind the primary tree by at most `max_lag` L1 batches.
+    pub fn new(
+        config: MerkleTreeReaderConfig,
+        secondary_path: PathBuf,
+        pool: ConnectionPool<Core>,
+        catch_up_interval: Duration,
+        max_lag: u32,
+    ) -> Self {
+        Self {
+            config,
+            secondary_path,
+            pool,
+            catch_up_interval,
+            max_lag,
+            tree_reader: watch::channel(None).0,
+            health_updater: ReactiveHealthCheck::new("tree_replica").1,
+        }
+    }
+
+    /// Returns a reference to the tree reader.
+    pub fn tree_reader(&self) -> LazyAsyncTreeReader {
+        LazyAsyncTreeReader(self.tree_reader.subscribe())
+    }
+
+    /// Returns the health check for this task.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    /// Runs this task. The task exits on error, or when a stop request is received.
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        self.health_updater
+            .update(TreeReplicaHealth::Initialization.into());
+
+        let db = tokio::select! {
+            db_result = create_secondary_db(self.config, self.secondary_path) => db_result?,
+            _ = stop_receiver.changed() => {
+                tracing::info!("Stop request received before Merkle tree replica is initialized; shutting down");
+                return Ok(());
+            }
+        };
+        let raw_db = db.clone().into_inner();
+        let reader = AsyncTreeReader::new(db, MerkleTreeMode::Lightweight)?;
+        self.tree_reader.send_replace(Some(reader.clone()));
+        tracing::info!("Initialized Merkle tree replica");
+
+        while !*stop_receiver.borrow_and_update() {
+            let started_at = Instant::now();
+            let db = raw_db.clone();
+            tokio::task::spawn_blocking(move || db.try_catch_up_with_primary())
+                .await
+                .context("panicked catching up Merkle tree replica")?
+                .context("failed catching up Merkle tree replica with the primary instance")?;
+            REPLICA_METRICS
+                .catch_up_latency
+                .observe(started_at.elapsed());
+
+            let next_l1_batch_number = reader.clone().info().await.next_l1_batch_number;
+            let mut storage = self.pool.connection_tagged("tree_replica").await?;
+            let primary_next_l1_batch_number = storage
+                .blocks_dal()
+                .get_last_l1_batch_number_with_tree_data()
+                .await?
+                .map_or(L1BatchNumber(0), |number| number + 1);
+            drop(storage);
+
+            let lag = primary_next_l1_batch_number
+                .0
+                .saturating_sub(next_l1_batch_number.0);
+            REPLICA_METRICS
+                .next_l1_batch
+                .set(next_l1_batch_number.0.into());
+            REPLICA_METRICS.lag.set(lag.into());
+            if lag > self.max_lag {
+                tracing::warn!(
+                    "Merkle tree replica lags behind the primary tree: next L1 batch is #{next_l1_batch_number}, \
+                     while the primary tree is at #{primary_next_l1_batch_number}"
+                );
+            }
+            let health = TreeReplicaHealth::Running {
+                next_l1_batch_number,
+                primary_next_l1_batch_number,
+                lag,
+                max_lag: self.max_lag,
+            };
+            self.health_updater.update(health.into());
+
+            if tokio::time::timeout(self.catch_up_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+
+        self.health_updater
+            .update(TreeReplicaHealth::ShuttingDown.into());
+        tracing::info!("Stop request received, Merkle tree replica is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+    use zksync_node_genesis::{insert_genesis_batch, GenesisParamsInitials};
+
+    use super::*;
+    use crate::{
+        tests::{extend_db_state, gen_storage_logs, mock_config, reset_db_state},
+        MetadataCalculator,
+    };
+
+    const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+    #[tokio::test]
+    async fn replica_catches_up_with_primary_tree() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let config = mock_config(temp_dir.path());
+        let reader_config = MerkleTreeReaderConfig {
+            db_path: config.db_path.clone(),
+            max_open_files: None,
+            multi_get_chunk_size: 500,
+            block_cache_capacity: 0,
+            include_indices_and_filters_in_block_cache: false,
+        };
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParamsInitials::mock())
+            .await
+            .unwrap();
+        reset_db_state(&pool, 5).await;
+
+        let calculator = MetadataCalculator::new(config, None, pool.clone())
+            .await
+            .unwrap();
+        let primary_reader = calculator.tree_reader();
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let calculator_handle = tokio::spawn(calculator.run(stop_receiver.clone()));
+        let primary_reader = primary_reader.wait().await.unwrap();
+
+        let replica_task = TreeReplicaTask::new(
+            reader_config,
+            temp_dir.path().join("secondary"),
+            pool.clone(),
+            POLL_INTERVAL,
+            0,
+        );
+        let replica_reader = replica_task.tree_reader();
+        let mut health_check = replica_task.health_check();
+        let replica_handle = tokio::spawn(replica_task.run(stop_receiver));
+        let replica_reader = replica_reader.wait().await.unwrap();
+
+        while replica_reader.clone().info().await.next_l1_batch_number < L1BatchNumber(6) {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        health_check
+            .wait_for(|health| matches!(health.status(), HealthStatus::Ready))
+            .await;
+
+        // Add more L1 batches and check that the replica observes them.
+        let new_logs = gen_storage_logs(100..200, 5);
+        extend_db_state(&mut storage, new_logs).await;
+        while replica_reader.clone().info().await.next_l1_batch_number < L1BatchNumber(11) {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        health_check
+            .wait_for(|health| matches!(health.status(), HealthStatus::Ready))
+            .await;
+
+        let primary_info = primary_reader.info().await;
+        let replica_info = replica_reader.info().await;
+        assert_eq!(replica_info.root_hash, primary_info.root_hash);
+        assert_eq!(replica_info.leaf_count, primary_info.leaf_count);
+
+        stop_sender.send_replace(true);
+        calculator_handle.await.unwrap().unwrap();
+        replica_handle.await.unwrap().unwrap();
+    }
+}
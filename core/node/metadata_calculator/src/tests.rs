@@ -1,6 +1,13 @@
 //! Tests for the metadata calculator component life cycle.
 
-use std::{future::Future, ops, panic, path::Path, sync::Arc, time::Duration};
+use std::{
+    future::Future,
+    num::NonZeroU32,
+    ops, panic,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use assert_matches::assert_matches;
 use tempfile::TempDir;
@@ -18,7 +25,9 @@ use zksync_node_genesis::{insert_genesis_batch, GenesisParamsInitials};
 use zksync_node_test_utils::{
     create_l1_batch, create_l2_block, generate_storage_logs, insert_initial_writes_for_batch,
 };
-use zksync_object_store::{MockObjectStore, ObjectStore};
+use zksync_object_store::{
+    MockObjectStore, ObjectStore, RocksdbCheckpointKind, RocksdbCheckpointStore,
+};
 use zksync_prover_interface::inputs::WitnessInputMerklePaths;
 use zksync_storage::RocksDB;
 use zksync_types::{
@@ -27,8 +36,8 @@ use zksync_types::{
 };
 
 use super::{
-    helpers::L1BatchWithLogs, GenericAsyncTree, MetadataCalculator, MetadataCalculatorConfig,
-    MetadataCalculatorRecoveryConfig,
+    helpers::L1BatchWithLogs, GenericAsyncTree, MerkleTreeCheckpointsConfig, MetadataCalculator,
+    MetadataCalculatorConfig, MetadataCalculatorRecoveryConfig,
 };
 use crate::helpers::{AsyncTree, Delayer};
 
@@ -388,6 +397,47 @@ async fn multi_l1_batch_workflow(sealed_protective_reads: bool) {
     }
 }
 
+#[tokio::test]
+async fn creating_tree_checkpoints() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (calculator, _) = setup_calculator(&temp_dir.path().join("tree"), pool.clone(), true).await;
+    let store =
+        RocksdbCheckpointStore::new(MockObjectStore::arc(), RocksdbCheckpointKind::MerkleTree);
+    let calculator = calculator.with_checkpoints(MerkleTreeCheckpointsConfig {
+        store: store.clone(),
+        interval: NonZeroU32::new(2).unwrap(),
+        retained_count: 2,
+    });
+    reset_db_state(&pool, 5).await;
+    let root_hash = run_calculator(calculator).await;
+
+    // Checkpoints are uploaded in the background.
+    let started_at = Instant::now();
+    let checkpoints = loop {
+        assert!(
+            started_at.elapsed() < RUN_TIMEOUT,
+            "timed out waiting for checkpoint"
+        );
+        let checkpoints = store.list().await.unwrap();
+        if !checkpoints.is_empty() {
+            break checkpoints;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    };
+    assert_eq!(checkpoints, [L1BatchNumber(6)]);
+
+    let restored_path = temp_dir.path().join("restored");
+    store
+        .download(L1BatchNumber(6), &restored_path)
+        .await
+        .unwrap();
+    let db = RocksDB::new(&restored_path).unwrap();
+    let tree = ZkSyncTree::new(db.into()).unwrap();
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(6));
+    assert_eq!(tree.root_hash(), root_hash);
+}
+
 #[test_casing(2, [false, true])]
 #[tokio::test]
 async fn error_on_pruned_next_l1_batch(sealed_protective_reads: bool) {
@@ -15,7 +15,10 @@ use zksync_types::{
     L1BatchNumber, OrStopped,
 };
 
-use super::helpers::{AsyncTree, Delayer, L1BatchWithLogs};
+use super::{
+    checkpoints::TreeCheckpointer,
+    helpers::{AsyncTree, Delayer, L1BatchWithLogs},
+};
 
 #[derive(Debug)]
 pub(super) struct TreeUpdater {
@@ -23,6 +26,7 @@ pub(super) struct TreeUpdater {
     max_l1_batches_per_iter: usize,
     object_store: Option<Arc<dyn ObjectStore>>,
     sealed_batches_have_protective_reads: bool,
+    checkpointer: Option<TreeCheckpointer>,
 }
 
 impl TreeUpdater {
@@ -37,9 +41,15 @@ impl TreeUpdater {
             max_l1_batches_per_iter,
             object_store,
             sealed_batches_have_protective_reads,
+            checkpointer: None,
         }
     }
 
+    pub fn with_checkpointer(mut self, checkpointer: TreeCheckpointer) -> Self {
+        self.checkpointer = Some(checkpointer);
+        self
+    }
+
     #[tracing::instrument(skip_all, fields(l1_batch.number = l1_batch.stats.number))]
     async fn process_l1_batch(
         &mut self,
@@ -174,6 +184,13 @@ impl TreeUpdater {
         save_rocksdb_latency.observe();
         update_tree_metrics(&updated_batch_stats, total_logs, start);
 
+        if let Some(checkpointer) = &mut self.checkpointer {
+            // Checkpoints are auxiliary, so errors are logged rather than propagated.
+            if let Err(err) = checkpointer.maybe_create_checkpoint(&mut self.tree).await {
+                tracing::warn!("Failed creating Merkle tree checkpoint: {err:#}");
+            }
+        }
+
         Ok(last_l1_batch_number + 1)
     }
 
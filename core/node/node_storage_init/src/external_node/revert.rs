@@ -1,6 +1,8 @@
+use anyhow::Context as _;
 use tokio::sync::watch;
 use zksync_block_reverter::BlockReverter;
-use zksync_dal::{ConnectionPool, Core};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater};
 use zksync_reorg_detector::{Error as ReorgError, ReorgDetector};
 use zksync_types::{L1BatchNumber, OrStopped};
 use zksync_web3_decl::client::{DynClient, L2};
@@ -12,6 +14,49 @@ pub struct ExternalNodeReverter {
     pub client: Box<DynClient<L2>>,
     pub pool: ConnectionPool<Core>,
     pub reverter: Option<BlockReverter>,
+    /// Max number of L1 batches that can be rolled back automatically. If not set, the rollback depth is unbounded.
+    pub max_revert_depth: Option<u32>,
+    /// Health updater reporting performed automatic rollbacks.
+    pub health_updater: Option<HealthUpdater>,
+}
+
+impl ExternalNodeReverter {
+    async fn update_health(&self) -> anyhow::Result<()> {
+        let Some(health_updater) = &self.health_updater else {
+            return Ok(());
+        };
+
+        let mut storage = self.pool.connection_tagged("en").await?;
+        let revert_count = storage.reorg_reverts_dal().get_revert_count().await?;
+        let last_revert = storage.reorg_reverts_dal().get_latest_revert().await?;
+        drop(storage);
+
+        let last_revert = last_revert.map(|revert| {
+            serde_json::json!({
+                "reverted_from_l1_batch": revert.reverted_from_l1_batch,
+                "last_retained_l1_batch": revert.last_retained_l1_batch,
+                "depth": revert.depth(),
+                "created_at": revert.created_at.to_string(),
+            })
+        });
+        let health_details = serde_json::json!({
+            "max_depth": self.max_revert_depth,
+            "revert_count": revert_count,
+            "last_revert": last_revert,
+        });
+        health_updater.update(Health::from(HealthStatus::Ready).with_details(health_details));
+        Ok(())
+    }
+}
+
+impl Drop for ExternalNodeReverter {
+    fn drop(&mut self) {
+        // The reverter is dropped once storage initialization completes; retain the reported rollback info
+        // rather than marking the component as shut down.
+        if let Some(health_updater) = self.health_updater.take() {
+            health_updater.freeze();
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -23,16 +68,44 @@ impl RevertStorage for ExternalNodeReverter {
             );
         };
 
+        let mut storage = self.pool.connection_tagged("en").await?;
+        let last_sealed_l1_batch = storage
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await?
+            .context("no L1 batches in storage")?;
+        drop(storage);
+
+        let depth = last_sealed_l1_batch.0.saturating_sub(to_batch.0);
+        if let Some(max_depth) = self.max_revert_depth {
+            anyhow::ensure!(
+                depth <= max_depth,
+                "Reorg requires rolling back {depth} L1 batches (#{last_sealed_l1_batch} -> #{to_batch}), \
+                 which exceeds the max depth of automatic rollback ({max_depth}). Revert the node manually \
+                 using the `revert {to_batch}` command, or increase `node_sync.reorg_recovery.max_depth`"
+            );
+        }
+
+        // The rollback is recorded before it's performed, so that it's audited even if it fails midway
+        // (rolling back Postgres, RocksDB instances and snapshot files cannot be done atomically).
+        let mut storage = self.pool.connection_tagged("en").await?;
+        storage
+            .reorg_reverts_dal()
+            .insert_revert(last_sealed_l1_batch, to_batch)
+            .await?;
+        drop(storage);
+
         tracing::info!("Reverting to l1 batch number {to_batch}");
         block_reverter.roll_back(to_batch).await?;
         tracing::info!("Revert successfully completed");
-        Ok(())
+        self.update_health().await
     }
 
     async fn is_reorg_needed(
         &self,
         stop_receiver: watch::Receiver<bool>,
     ) -> Result<bool, OrStopped> {
+        self.update_health().await?;
         ReorgDetector::new(self.client.clone(), self.pool.clone())
             .check_reorg_presence(stop_receiver, true)
             .await
@@ -42,6 +115,8 @@ impl RevertStorage for ExternalNodeReverter {
         &self,
         stop_receiver: watch::Receiver<bool>,
     ) -> Result<Option<L1BatchNumber>, OrStopped> {
+        self.update_health().await?;
+
         let mut reorg_detector = ReorgDetector::new(self.client.clone(), self.pool.clone());
         match reorg_detector.run_once(stop_receiver).await {
             Ok(()) => {
@@ -57,3 +132,115 @@ impl RevertStorage for ExternalNodeReverter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_block_reverter::NodeRole;
+    use zksync_dal::Connection;
+    use zksync_health_check::{CheckHealth, ReactiveHealthCheck};
+    use zksync_node_genesis::{insert_genesis_batch, GenesisParamsInitials};
+    use zksync_node_test_utils::{create_l1_batch, create_l2_block};
+    use zksync_web3_decl::client::MockClient;
+
+    use super::*;
+
+    async fn seal_l1_batches(storage: &mut Connection<'_, Core>, last_l1_batch: u32) {
+        insert_genesis_batch(storage, &GenesisParamsInitials::mock())
+            .await
+            .unwrap();
+        for number in 1..=last_l1_batch {
+            storage
+                .blocks_dal()
+                .insert_l2_block(&create_l2_block(number))
+                .await
+                .unwrap();
+            storage
+                .blocks_dal()
+                .insert_mock_l1_batch(&create_l1_batch(number))
+                .await
+                .unwrap();
+            storage
+                .blocks_dal()
+                .mark_l2_blocks_as_executed_in_l1_batch(L1BatchNumber(number))
+                .await
+                .unwrap();
+        }
+    }
+
+    fn create_reverter(
+        pool: &ConnectionPool<Core>,
+        max_revert_depth: u32,
+    ) -> (ExternalNodeReverter, ReactiveHealthCheck) {
+        let (health_check, health_updater) = ReactiveHealthCheck::new("reorg_recovery");
+        let mut block_reverter = BlockReverter::new(NodeRole::External, pool.clone());
+        block_reverter.enable_rolling_back_postgres();
+        let reverter = ExternalNodeReverter {
+            client: Box::new(MockClient::builder(L2::default()).build()),
+            pool: pool.clone(),
+            reverter: Some(block_reverter),
+            max_revert_depth: Some(max_revert_depth),
+            health_updater: Some(health_updater),
+        };
+        (reverter, health_check)
+    }
+
+    #[tokio::test]
+    async fn rollback_within_max_depth_is_recorded() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        seal_l1_batches(&mut storage, 5).await;
+
+        let (reverter, health_check) = create_reverter(&pool, 3);
+        reverter.revert_storage(L1BatchNumber(2)).await.unwrap();
+
+        let last_l1_batch = storage
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await
+            .unwrap();
+        assert_eq!(last_l1_batch, Some(L1BatchNumber(2)));
+        let revert = storage
+            .reorg_reverts_dal()
+            .get_latest_revert()
+            .await
+            .unwrap()
+            .expect("rollback is not recorded");
+        assert_eq!(revert.reverted_from_l1_batch, L1BatchNumber(5));
+        assert_eq!(revert.last_retained_l1_batch, L1BatchNumber(2));
+        assert_eq!(revert.depth(), 3);
+
+        let health = health_check.check_health().await;
+        assert_eq!(health.status(), HealthStatus::Ready);
+        let details = health.details().unwrap();
+        assert_eq!(details["revert_count"], 1);
+        assert_eq!(details["last_revert"]["depth"], 3);
+    }
+
+    #[tokio::test]
+    async fn rollback_deeper_than_max_depth_is_rejected() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        seal_l1_batches(&mut storage, 5).await;
+
+        let (reverter, _health_check) = create_reverter(&pool, 2);
+        let err = reverter
+            .revert_storage(L1BatchNumber(2))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("exceeds the max depth"), "{err}");
+
+        let last_l1_batch = storage
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await
+            .unwrap();
+        assert_eq!(last_l1_batch, Some(L1BatchNumber(5)));
+        let revert_count = storage
+            .reorg_reverts_dal()
+            .get_revert_count()
+            .await
+            .unwrap();
+        assert_eq!(revert_count, 0);
+    }
+}
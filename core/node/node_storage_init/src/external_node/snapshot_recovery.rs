@@ -7,7 +7,8 @@ use zksync_health_check::AppHealthCheck;
 use zksync_object_store::ObjectStoreFactory;
 use zksync_shared_metrics::{SnapshotRecoveryStage, APP_METRICS};
 use zksync_snapshots_applier::{
-    RecoveryCompletionStatus, SnapshotsApplierConfig, SnapshotsApplierTask,
+    PeerSnapshotSource, RecoveryCompletionStatus, SnapshotArchiveSource, SnapshotPeers,
+    SnapshotsApplierConfig, SnapshotsApplierTask,
 };
 use zksync_types::OrStopped;
 use zksync_web3_decl::client::{DynClient, L2};
@@ -40,24 +41,41 @@ impl InitializeStorage for ExternalNodeSnapshotRecovery {
             );
         }
 
-        let object_store_config =
-            self.recovery_config.object_store_config.clone().context(
-                "Snapshot object store must be presented if snapshot recovery is activated",
-            )?;
-        let object_store = ObjectStoreFactory::new(object_store_config)
-            .create_store()
-            .await?;
-
         let config = SnapshotsApplierConfig {
             max_concurrency: self.max_concurrency,
             ..SnapshotsApplierConfig::default()
         };
-        let mut snapshots_applier_task = SnapshotsApplierTask::new(
-            config,
-            self.pool.clone(),
-            Box::new(self.client.clone().for_component("snapshot_recovery")),
-            object_store,
-        );
+        let mut snapshots_applier_task = if let Some(archive) = &self.recovery_config.archive {
+            tracing::info!(
+                "Recovering from snapshot archive `{}` with expected root hash {:?}",
+                archive.path.display(),
+                archive.root_hash
+            );
+            let source = SnapshotArchiveSource::open(&archive.path, archive.root_hash).await?;
+            SnapshotsApplierTask::from_archive(config, self.pool.clone(), source)
+        } else if let Some(peers) = &self.recovery_config.peers {
+            tracing::info!("Recovering from snapshot served by peers: {:?}", peers.urls);
+            let candidates = SnapshotPeers::discover(&peers.urls, peers.request_timeout).await?;
+            let source = PeerSnapshotSource::new(
+                candidates,
+                Box::new(self.client.clone().for_component("snapshot_recovery")),
+            )
+            .await?;
+            SnapshotsApplierTask::from_peers(config, self.pool.clone(), source)
+        } else {
+            let object_store_config = self.recovery_config.object_store_config.clone().context(
+                "Snapshot object store must be presented if snapshot recovery is activated",
+            )?;
+            let object_store = ObjectStoreFactory::new(object_store_config)
+                .create_store()
+                .await?;
+            SnapshotsApplierTask::new(
+                config,
+                self.pool.clone(),
+                Box::new(self.client.clone().for_component("snapshot_recovery")),
+                object_store,
+            )
+        };
         if let Some(snapshot_l1_batch) = self.recovery_config.snapshot_l1_batch_override {
             tracing::info!(
                 "Using a specific snapshot with L1 batch #{snapshot_l1_batch}; this may not work \
@@ -69,6 +87,8 @@ impl InitializeStorage for ExternalNodeSnapshotRecovery {
             tracing::info!("Dropping storage key preimages for snapshot storage logs");
             snapshots_applier_task.drop_storage_key_preimages();
         }
+        snapshots_applier_task
+            .set_tree_verification_path(self.recovery_config.tree_verification_path.clone());
         self.app_health
             .insert_component(snapshots_applier_task.health_check())
             .map_err(OrStopped::internal)?;
@@ -88,10 +108,13 @@ impl InitializeStorage for ExternalNodeSnapshotRecovery {
 
     async fn is_initialized(&self) -> anyhow::Result<bool> {
         let mut storage = self.pool.connection_tagged("en").await?;
-        let completed = matches!(
-            SnapshotsApplierTask::is_recovery_completed(&mut storage, &self.client).await?,
-            RecoveryCompletionStatus::Completed
-        );
+        let status = if let Some(archive) = &self.recovery_config.archive {
+            let source = SnapshotArchiveSource::open(&archive.path, archive.root_hash).await?;
+            SnapshotsApplierTask::is_recovery_completed(&mut storage, &source).await?
+        } else {
+            SnapshotsApplierTask::is_recovery_completed(&mut storage, &self.client).await?
+        };
+        let completed = matches!(status, RecoveryCompletionStatus::Completed);
         Ok(completed)
     }
 }
@@ -133,6 +156,9 @@ mod tests {
                 snapshot_l1_batch_override: None,
                 drop_storage_key_preimages: false,
                 object_store_config: None,
+                tree_verification_path: "/tmp/snapshot_verification".into(),
+                archive: None,
+                peers: None,
             },
             app_health,
         };
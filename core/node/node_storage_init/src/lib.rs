@@ -1,9 +1,9 @@
-use std::{future::Future, sync::Arc, time::Duration};
+use std::{future::Future, path::PathBuf, sync::Arc, time::Duration};
 
 use tokio::sync::watch;
 use zksync_config::ObjectStoreConfig;
 use zksync_dal::{ConnectionPool, Core, CoreDal as _};
-use zksync_types::{try_stoppable, L1BatchNumber, OrStopped, StopContext};
+use zksync_types::{try_stoppable, L1BatchNumber, OrStopped, StopContext, H256};
 
 pub use crate::traits::{InitializeStorage, RevertStorage};
 
@@ -11,6 +11,7 @@ pub mod external_node;
 pub mod main_node;
 pub mod node;
 mod traits;
+pub mod tree_checkpoint;
 
 #[derive(Debug)]
 pub struct SnapshotRecoveryConfig {
@@ -18,6 +19,35 @@ pub struct SnapshotRecoveryConfig {
     pub snapshot_l1_batch_override: Option<L1BatchNumber>,
     pub drop_storage_key_preimages: bool,
     pub object_store_config: Option<ObjectStoreConfig>,
+    /// Directory for the scratch Merkle tree used to verify recovery from incremental snapshots and snapshot archives.
+    pub tree_verification_path: PathBuf,
+    /// If specified, the snapshot is recovered from a local archive instead of the main node and object store.
+    pub archive: Option<SnapshotArchiveConfig>,
+    /// If specified (and `archive` is not), the snapshot is fetched from peer nodes instead of the object store.
+    pub peers: Option<SnapshotPeersConfig>,
+}
+
+/// Local snapshot archive to recover from.
+#[derive(Debug, Clone)]
+pub struct SnapshotArchiveConfig {
+    pub path: PathBuf,
+    /// Trusted root hash of the snapshot L1 batch.
+    pub root_hash: H256,
+}
+
+/// Peer nodes serving a snapshot archive to recover from.
+#[derive(Debug, Clone)]
+pub struct SnapshotPeersConfig {
+    /// Base URLs of the peers.
+    pub urls: Vec<String>,
+    pub request_timeout: Duration,
+}
+
+#[derive(Debug)]
+pub struct TreeCheckpointRestoreConfig {
+    /// Path to the Merkle tree RocksDB instance.
+    pub tree_path: PathBuf,
+    pub object_store_config: ObjectStoreConfig,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -33,6 +63,8 @@ pub struct NodeInitializationStrategy {
     pub genesis: Arc<dyn InitializeStorage>,
     pub snapshot_recovery: Option<Arc<dyn InitializeStorage>>,
     pub block_reverter: Option<Arc<dyn RevertStorage>>,
+    /// Restores the Merkle tree from a checkpoint if the tree is not initialized.
+    pub tree_checkpoint: Option<Arc<dyn InitializeStorage>>,
 }
 
 /// Node storage initializer.
@@ -131,6 +163,15 @@ impl NodeStorageInitializer {
             }
         }
 
+        // Restore the Merkle tree before it's potentially rolled back below.
+        if let Some(tree_checkpoint) = &self.strategy.tree_checkpoint {
+            try_stoppable!(
+                tree_checkpoint
+                    .initialize_storage(stop_receiver.clone())
+                    .await
+            );
+        }
+
         // Now we may check whether we're in the invalid state and should perform a rollback.
         if let Some(reverter) = &self.strategy.block_reverter {
             if let Some(to_batch) =
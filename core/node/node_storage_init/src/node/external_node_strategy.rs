@@ -2,7 +2,7 @@ use std::{num::NonZeroUsize, sync::Arc};
 
 use zksync_block_reverter::node::BlockReverterResource;
 use zksync_dal::node::{MasterPool, PoolResource};
-use zksync_health_check::AppHealthCheck;
+use zksync_health_check::{AppHealthCheck, ReactiveHealthCheck};
 use zksync_node_framework::{
     wiring_layer::{WiringError, WiringLayer},
     FromContext,
@@ -12,7 +12,9 @@ use zksync_web3_decl::client::{DynClient, L2};
 
 use crate::{
     external_node::{ExternalNodeGenesis, ExternalNodeReverter, ExternalNodeSnapshotRecovery},
+    tree_checkpoint::TreeCheckpointRestore,
     InitializeStorage, NodeInitializationStrategy, RevertStorage, SnapshotRecoveryConfig,
+    TreeCheckpointRestoreConfig,
 };
 
 /// Wiring layer for external node initialization strategy.
@@ -21,6 +23,10 @@ pub struct ExternalNodeInitStrategyLayer {
     pub l2_chain_id: L2ChainId,
     pub max_postgres_concurrency: NonZeroUsize,
     pub snapshot_recovery_config: Option<SnapshotRecoveryConfig>,
+    pub tree_checkpoint_restore_config: Option<TreeCheckpointRestoreConfig>,
+    /// Max depth of automatic rollbacks after a detected reorg. If set, rollbacks are bounded by this depth
+    /// and reported in the `reorg_recovery` health check.
+    pub max_reorg_revert_depth: Option<u32>,
 }
 
 #[derive(Debug, FromContext)]
@@ -73,17 +79,37 @@ impl WiringLayer for ExternalNodeInitStrategyLayer {
                     pool: recovery_pool,
                     max_concurrency: self.max_postgres_concurrency,
                     recovery_config,
-                    app_health: input.app_health,
+                    app_health: input.app_health.clone(),
                 });
                 Some(recovery)
             }
             None => None,
         };
+        let tree_checkpoint = self.tree_checkpoint_restore_config.map(|config| {
+            Arc::new(TreeCheckpointRestore {
+                pool: pool.clone(),
+                config,
+            }) as Arc<dyn InitializeStorage>
+        });
+
+        let health_updater = if self.max_reorg_revert_depth.is_some() {
+            let (health_check, health_updater) = ReactiveHealthCheck::new("reorg_recovery");
+            input
+                .app_health
+                .insert_component(health_check)
+                .map_err(WiringError::internal)?;
+            Some(health_updater)
+        } else {
+            None
+        };
+
         // We always want to detect reorgs, even if we can't roll them back.
         let block_reverter = ExternalNodeReverter {
             client,
             pool,
             reverter: block_reverter,
+            max_revert_depth: self.max_reorg_revert_depth,
+            health_updater,
         };
         let block_reverter = Some(Arc::new(block_reverter) as Arc<dyn RevertStorage>);
 
@@ -91,6 +117,7 @@ impl WiringLayer for ExternalNodeInitStrategyLayer {
             genesis,
             snapshot_recovery,
             block_reverter,
+            tree_checkpoint,
         })
     }
 }
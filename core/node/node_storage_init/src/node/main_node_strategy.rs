@@ -9,13 +9,17 @@ use zksync_node_framework::{
 use zksync_shared_resources::contracts::SettlementLayerContractsResource;
 use zksync_web3_decl::client::{DynClient, L1};
 
-use crate::{main_node::MainNodeGenesis, NodeInitializationStrategy};
+use crate::{
+    main_node::MainNodeGenesis, tree_checkpoint::TreeCheckpointRestore, InitializeStorage,
+    NodeInitializationStrategy, TreeCheckpointRestoreConfig,
+};
 
 /// Wiring layer for main node initialization strategy.
 #[derive(Debug)]
 pub struct MainNodeInitStrategyLayer {
     pub genesis: GenesisConfig,
     pub event_expiration_blocks: u64,
+    pub tree_checkpoint_restore_config: Option<TreeCheckpointRestoreConfig>,
 }
 
 #[derive(Debug, FromContext)]
@@ -41,13 +45,18 @@ impl WiringLayer for MainNodeInitStrategyLayer {
             genesis: self.genesis,
             event_expiration_blocks: self.event_expiration_blocks,
             l1_client: input.l1_client,
-            pool,
+            pool: pool.clone(),
+        });
+
+        let tree_checkpoint = self.tree_checkpoint_restore_config.map(|config| {
+            Arc::new(TreeCheckpointRestore { pool, config }) as Arc<dyn InitializeStorage>
         });
 
         Ok(NodeInitializationStrategy {
             genesis,
             snapshot_recovery: None,
             block_reverter: None,
+            tree_checkpoint,
         })
     }
 }
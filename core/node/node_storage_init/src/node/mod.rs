@@ -8,10 +8,12 @@ use zksync_node_framework::{
 };
 use zksync_types::try_stoppable;
 
+pub use self::snapshot_archive_server::SnapshotArchiveServerLayer;
 use crate::{NodeInitializationStrategy, NodeStorageInitializer};
 
 pub mod external_node_strategy;
 pub mod main_node_strategy;
+mod snapshot_archive_server;
 
 /// Wiring layer for `NodeStorageInializer`.
 ///
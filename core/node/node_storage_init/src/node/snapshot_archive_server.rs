@@ -0,0 +1,81 @@
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+
+use anyhow::Context as _;
+use tokio::net::TcpListener;
+use zksync_node_framework::{
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+use zksync_object_store::SnapshotArchive;
+use zksync_snapshots_applier::SnapshotArchiveServer;
+
+/// Wiring layer serving a snapshot archive to peer nodes over HTTP.
+///
+/// ## Adds tasks
+///
+/// - `SnapshotArchiveServerTask`
+#[derive(Debug)]
+pub struct SnapshotArchiveServerLayer {
+    archive_path: PathBuf,
+    bind_addr: SocketAddr,
+}
+
+impl SnapshotArchiveServerLayer {
+    pub fn new(archive_path: PathBuf, bind_addr: SocketAddr) -> Self {
+        Self {
+            archive_path,
+            bind_addr,
+        }
+    }
+}
+
+#[derive(Debug, IntoContext)]
+pub struct Output {
+    #[context(task)]
+    server: SnapshotArchiveServerTask,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for SnapshotArchiveServerLayer {
+    type Input = ();
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "snapshot_archive_server_layer"
+    }
+
+    async fn wire(self, (): Self::Input) -> Result<Self::Output, WiringError> {
+        let archive = SnapshotArchive::open(&self.archive_path).await?;
+        Ok(Output {
+            server: SnapshotArchiveServerTask {
+                server: SnapshotArchiveServer::new(Arc::new(archive)),
+                bind_addr: self.bind_addr,
+            },
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct SnapshotArchiveServerTask {
+    server: SnapshotArchiveServer,
+    bind_addr: SocketAddr,
+}
+
+#[async_trait::async_trait]
+impl Task for SnapshotArchiveServerTask {
+    fn id(&self) -> TaskId {
+        "snapshot_archive_server".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        let listener = TcpListener::bind(self.bind_addr).await.with_context(|| {
+            format!(
+                "failed binding snapshot archive server to {}",
+                self.bind_addr
+            )
+        })?;
+        self.server.run(listener, stop_receiver.0).await
+    }
+}
@@ -0,0 +1,99 @@
+//! Restoring Merkle tree from RocksDB checkpoints.
+
+use anyhow::Context as _;
+use tokio::{fs, sync::watch};
+use zksync_dal::{ConnectionPool, Core, CoreDal as _};
+use zksync_object_store::{ObjectStoreFactory, RocksdbCheckpointKind, RocksdbCheckpointStore};
+use zksync_types::OrStopped;
+
+use crate::{InitializeStorage, TreeCheckpointRestoreConfig};
+
+/// Restores the Merkle tree RocksDB instance from the latest suitable checkpoint in the object store.
+/// This is preferred to tree recovery from a snapshot or rebuilding the tree from genesis.
+///
+/// The tree is considered initialized if its directory exists and is non-empty. If there are no suitable
+/// checkpoints, initialization is a no-op; the tree will be recovered or built by the metadata calculator as usual.
+#[derive(Debug)]
+pub struct TreeCheckpointRestore {
+    pub pool: ConnectionPool<Core>,
+    pub config: TreeCheckpointRestoreConfig,
+}
+
+impl TreeCheckpointRestore {
+    async fn restore(&self) -> anyhow::Result<()> {
+        let tree_path = &self.config.tree_path;
+        let object_store = ObjectStoreFactory::new(self.config.object_store_config.clone())
+            .create_store()
+            .await?;
+        let store = RocksdbCheckpointStore::new(object_store, RocksdbCheckpointKind::MerkleTree);
+
+        let mut storage = self.pool.connection_tagged("node_init").await?;
+        let Some(sealed_l1_batch) = storage.blocks_dal().get_sealed_l1_batch_number().await? else {
+            return Ok(());
+        };
+        // Postgres doesn't contain storage logs necessary to catch up from L1 batches preceding snapshot recovery or pruning.
+        let snapshot_recovery = storage
+            .snapshot_recovery_dal()
+            .get_applied_snapshot_status()
+            .await?;
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        drop(storage);
+        let first_l1_batch = [
+            snapshot_recovery.map(|status| status.l1_batch_number),
+            pruning_info.last_soft_pruned.map(|info| info.l1_batch),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+        .unwrap_or_default();
+
+        let Some(next_l1_batch_number) = store
+            .latest_in_range((first_l1_batch + 1)..=(sealed_l1_batch + 1))
+            .await?
+        else {
+            tracing::info!(
+                "No Merkle tree checkpoints suitable for L1 batches #{first_l1_batch}..=#{sealed_l1_batch}"
+            );
+            return Ok(());
+        };
+
+        tracing::info!("Restoring Merkle tree from checkpoint at L1 batch #{next_l1_batch_number}");
+        if fs::try_exists(tree_path).await? {
+            fs::remove_dir(tree_path).await?;
+        }
+        store.download(next_l1_batch_number, tree_path).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl InitializeStorage for TreeCheckpointRestore {
+    async fn is_initialized(&self) -> anyhow::Result<bool> {
+        let tree_path = &self.config.tree_path;
+        if !fs::try_exists(tree_path).await? {
+            return Ok(false);
+        }
+        Ok(fs::read_dir(tree_path).await?.next_entry().await?.is_some())
+    }
+
+    async fn initialize_storage(
+        &self,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> Result<(), OrStopped> {
+        if self.is_initialized().await? {
+            tracing::info!(
+                "Merkle tree at `{}` is already initialized",
+                self.config.tree_path.display()
+            );
+            return Ok(());
+        }
+
+        tokio::select! {
+            res = self.restore() => {
+                res.context("failed restoring Merkle tree from checkpoint")?;
+                Ok(())
+            }
+            _ = stop_receiver.changed() => Err(OrStopped::Stopped),
+        }
+    }
+}
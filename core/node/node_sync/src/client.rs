@@ -8,7 +8,8 @@ use zksync_system_constants::ACCOUNT_CODE_STORAGE_ADDRESS;
 use zksync_types::{
     api::{self, en},
     bytecode::BytecodeHash,
-    get_code_key, h256_to_u256, Address, L2BlockNumber, ProtocolVersionId, H256, U64,
+    get_code_key, h256_to_u256, Address, L1BatchNumber, L2BlockNumber, ProtocolVersionId, H256,
+    U64,
 };
 use zksync_web3_decl::{
     client::{DynClient, L2},
@@ -43,6 +44,13 @@ pub trait MainNodeClient: 'static + Send + Sync + fmt::Debug {
     ) -> EnrichedClientResult<Option<en::SyncBlock>>;
 
     async fn fetch_genesis_config(&self) -> EnrichedClientResult<GenesisConfig>;
+
+    async fn fetch_l1_batch_number(&self) -> EnrichedClientResult<L1BatchNumber>;
+
+    async fn fetch_l2_block_state_diff(
+        &self,
+        number: L2BlockNumber,
+    ) -> EnrichedClientResult<Option<en::L2BlockStateDiff>>;
 }
 
 #[async_trait]
@@ -171,4 +179,24 @@ impl MainNodeClient for Box<DynClient<L2>> {
             .with_arg("with_transactions", &with_transactions)
             .await
     }
+
+    async fn fetch_l1_batch_number(&self) -> EnrichedClientResult<L1BatchNumber> {
+        let number = self
+            .get_l1_batch_number()
+            .rpc_context("get_l1_batch_number")
+            .await?;
+        let number = u32::try_from(number)
+            .map_err(|err| EnrichedClientError::custom(err, "u32::try_from"))?;
+        Ok(L1BatchNumber(number))
+    }
+
+    async fn fetch_l2_block_state_diff(
+        &self,
+        number: L2BlockNumber,
+    ) -> EnrichedClientResult<Option<en::L2BlockStateDiff>> {
+        self.sync_l2_block_state_diff(number)
+            .rpc_context("fetch_l2_block_state_diff")
+            .with_arg("number", &number)
+            .await
+    }
 }
@@ -4,7 +4,11 @@ use std::time::Duration;
 
 use serde::Serialize;
 use tokio::sync::watch;
-use zksync_da_client::{types::InclusionData, DataAvailabilityClient};
+use zksync_da_client::{
+    chunking::{decode_chunked_id, get_chunked_inclusion_data},
+    types::InclusionData,
+    DataAvailabilityClient,
+};
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_types::{commitment::PubdataType, L1BatchNumber};
@@ -185,13 +189,18 @@ impl DataAvailabilityFetcher {
         let inclusion_data = if expected_inclusion_data.is_empty() {
             InclusionData::default()
         } else {
-            let inclusion_data_from_rpc = self
-                .da_client
-                .get_inclusion_data(da_details.blob_id.as_str())
-                .await
-                .map_err(|err| {
-                    to_retriable_error(anyhow::anyhow!("Error fetching inclusion data: {err}"))
-                })?;
+            // Blobs split by the main node because of the DA blob size limit are reassembled from their chunks.
+            let chunk_blob_ids = decode_chunked_id(&da_details.blob_id).map_err(to_fatal_error)?;
+            let inclusion_data_from_rpc = if let Some(chunk_blob_ids) = &chunk_blob_ids {
+                get_chunked_inclusion_data(self.da_client.as_ref(), chunk_blob_ids).await
+            } else {
+                self.da_client
+                    .get_inclusion_data(da_details.blob_id.as_str())
+                    .await
+            };
+            let inclusion_data_from_rpc = inclusion_data_from_rpc.map_err(|err| {
+                to_retriable_error(anyhow::anyhow!("Error fetching inclusion data: {err}"))
+            })?;
 
             match inclusion_data_from_rpc {
                 Some(data) => data,
@@ -24,6 +24,7 @@ use zksync_vm_executor::storage::L1BatchParamsProvider;
 
 use super::{
     client::MainNodeClient,
+    fast_sync::FastSyncCompletion,
     sync_action::{ActionQueue, SyncAction},
 };
 
@@ -41,6 +42,7 @@ pub struct ExternalIO {
     actions: ActionQueue,
     main_node_client: Box<dyn MainNodeClient>,
     chain_id: L2ChainId,
+    fast_sync: Option<FastSyncCompletion>,
 }
 
 impl ExternalIO {
@@ -57,9 +59,17 @@ impl ExternalIO {
             actions,
             main_node_client,
             chain_id,
+            fast_sync: None,
         })
     }
 
+    /// Makes the IO wait until fast sync is completed before initializing. Fast sync writes L2 blocks and L1 batches
+    /// directly to Postgres, so the IO cursor can only be loaded after it has finished.
+    pub fn with_fast_sync(mut self, completion: FastSyncCompletion) -> Self {
+        self.fast_sync = Some(completion);
+        self
+    }
+
     async fn get_base_system_contract(
         &self,
         hash: H256,
@@ -104,53 +114,52 @@ impl ExternalIO {
             }
         })
     }
+}
 
-    async fn ensure_protocol_version_is_saved(
-        &self,
-        protocol_version: ProtocolVersionId,
-    ) -> anyhow::Result<()> {
-        let base_system_contract_hashes = self
-            .pool
-            .connection_tagged("sync_layer")
-            .await?
-            .protocol_versions_dal()
-            .get_base_system_contract_hashes_by_version_id(protocol_version)
-            .await?;
-        if base_system_contract_hashes.is_some() {
-            return Ok(());
-        }
-        tracing::info!("Fetching protocol version {protocol_version:?} from the main node");
-
-        let protocol_version_info = self
-            .main_node_client
-            .fetch_protocol_version(protocol_version)
-            .await
-            .context("failed to fetch protocol version from the main node")?
-            .context("protocol version is missing on the main node")?;
-        self.pool
-            .connection_tagged("sync_layer")
-            .await?
-            .protocol_versions_dal()
-            .save_protocol_version(
-                ProtocolSemanticVersion {
-                    minor: protocol_version_info
-                        .minor_version
-                        .try_into()
-                        .context("cannot convert protocol version")?,
-                    patch: VersionPatch(0),
-                },
-                protocol_version_info.timestamp,
-                Default::default(), // verification keys are unused for EN
-                BaseSystemContractsHashes {
-                    bootloader: protocol_version_info.bootloader_code_hash,
-                    default_aa: protocol_version_info.default_account_code_hash,
-                    evm_emulator: protocol_version_info.evm_emulator_code_hash,
-                },
-                protocol_version_info.l2_system_upgrade_tx_hash,
-            )
-            .await?;
-        Ok(())
+/// Ensures that the specified protocol version is persisted in Postgres, fetching it from the main node if necessary.
+pub(crate) async fn ensure_protocol_version_is_saved(
+    pool: &ConnectionPool<Core>,
+    main_node_client: &dyn MainNodeClient,
+    protocol_version: ProtocolVersionId,
+) -> anyhow::Result<()> {
+    let base_system_contract_hashes = pool
+        .connection_tagged("sync_layer")
+        .await?
+        .protocol_versions_dal()
+        .get_base_system_contract_hashes_by_version_id(protocol_version)
+        .await?;
+    if base_system_contract_hashes.is_some() {
+        return Ok(());
     }
+    tracing::info!("Fetching protocol version {protocol_version:?} from the main node");
+
+    let protocol_version_info = main_node_client
+        .fetch_protocol_version(protocol_version)
+        .await
+        .context("failed to fetch protocol version from the main node")?
+        .context("protocol version is missing on the main node")?;
+    pool.connection_tagged("sync_layer")
+        .await?
+        .protocol_versions_dal()
+        .save_protocol_version(
+            ProtocolSemanticVersion {
+                minor: protocol_version_info
+                    .minor_version
+                    .try_into()
+                    .context("cannot convert protocol version")?,
+                patch: VersionPatch(0),
+            },
+            protocol_version_info.timestamp,
+            Default::default(), // verification keys are unused for EN
+            BaseSystemContractsHashes {
+                bootloader: protocol_version_info.bootloader_code_hash,
+                default_aa: protocol_version_info.default_account_code_hash,
+                evm_emulator: protocol_version_info.evm_emulator_code_hash,
+            },
+            protocol_version_info.l2_system_upgrade_tx_hash,
+        )
+        .await?;
+    Ok(())
 }
 
 #[async_trait]
@@ -182,6 +191,11 @@ impl StateKeeperIO for ExternalIO {
     }
 
     async fn initialize(&mut self) -> anyhow::Result<(IoCursor, Option<PendingBatchData>)> {
+        if let Some(mut fast_sync) = self.fast_sync.take() {
+            tracing::info!("Waiting for fast sync to complete before initializing ExternalIO");
+            fast_sync.wait().await;
+        }
+
         let mut storage = self.pool.connection_tagged("sync_layer").await?;
         let cursor = IoCursor::new(&mut storage).await?;
         self.l1_batch_params_provider
@@ -309,8 +323,12 @@ impl StateKeeperIO for ExternalIO {
                     cursor.next_l2_block
                 );
 
-                self.ensure_protocol_version_is_saved(params.protocol_version)
-                    .await?;
+                ensure_protocol_version_is_saved(
+                    &self.pool,
+                    self.main_node_client.as_ref(),
+                    params.protocol_version,
+                )
+                .await?;
                 self.pool
                     .connection_tagged("sync_layer")
                     .await?
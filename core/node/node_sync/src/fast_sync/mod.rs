@@ -0,0 +1,896 @@
+//! Fast sync for the external node: applying L2 block outputs fetched from the main node
+//! instead of re-executing transactions.
+
+use std::{collections::HashMap, num::NonZeroU32, time::Duration};
+
+use anyhow::Context as _;
+use kzg::ZK_SYNC_BYTES_PER_BLOB;
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_config::configs::node_sync::FastSyncConfig;
+use zksync_contracts::BaseSystemContractsHashes;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
+use zksync_eth_client::EthInterface;
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_state_keeper::io::{
+    common::IoCursor, seal_logic::l2_block_seal_subtasks::extract_added_tokens,
+};
+use zksync_system_constants::L2_NATIVE_TOKEN_VAULT_ADDRESS;
+use zksync_types::{
+    api::{self, en},
+    block::{build_bloom, L2BlockHeader},
+    fee_model::BatchFeeInput,
+    l2_to_l1_log::{L2ToL1Log, UserL2ToL1Log},
+    tx::IncludedTxLocation,
+    AccountTreeId, Address, BloomInput, L1BatchNumber, L2BlockNumber, StorageKey, StorageLog,
+    Transaction, H256,
+};
+use zksync_vm_executor::storage::L1BatchParamsProvider;
+use zksync_vm_interface::{
+    CircuitStatistic, TransactionExecutionResult, TxExecutionStatus, VmEvent, VmExecutionMetrics,
+};
+use zksync_web3_decl::{
+    client::{DynClient, L2},
+    error::EnrichedClientError,
+};
+
+use crate::{
+    client::MainNodeClient,
+    external_io::ensure_protocol_version_is_saved,
+    fetcher::FetchedBlock,
+    metrics::FAST_SYNC_METRICS,
+    tree_data_fetcher::{
+        provider::{CombinedDataProvider, MissingData, SLDataProvider, TreeDataProvider},
+        TreeDataFetcherError,
+    },
+};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, thiserror::Error)]
+enum FastSyncError {
+    #[error("error fetching data")]
+    Rpc(#[from] EnrichedClientError),
+    #[error("internal error")]
+    Internal(#[from] anyhow::Error),
+}
+
+impl From<DalError> for FastSyncError {
+    fn from(err: DalError) -> Self {
+        Self::Internal(err.generalize())
+    }
+}
+
+impl From<TreeDataFetcherError> for FastSyncError {
+    fn from(err: TreeDataFetcherError) -> Self {
+        match err {
+            TreeDataFetcherError::Rpc(err) => Self::Rpc(err),
+            TreeDataFetcherError::Internal(err) => Self::Internal(err),
+        }
+    }
+}
+
+impl FastSyncError {
+    fn is_retriable(&self) -> bool {
+        match self {
+            Self::Rpc(err) => err.is_retryable(),
+            Self::Internal(_) => false,
+        }
+    }
+
+    fn into_anyhow(self) -> anyhow::Error {
+        match self {
+            Self::Internal(err) => err,
+            Self::Rpc(err) => err.into(),
+        }
+    }
+}
+
+type FastSyncResult<T> = Result<T, FastSyncError>;
+
+/// Handle allowing to wait until fast sync is completed.
+#[derive(Debug, Clone)]
+pub struct FastSyncCompletion(watch::Receiver<bool>);
+
+impl FastSyncCompletion {
+    /// Waits until fast sync is completed. Also returns if [`FastSyncer`] was dropped without completing
+    /// (e.g., because the node is shutting down); since fast sync applies whole L1 batches atomically,
+    /// the node state is consistent in this case as well.
+    pub async fn wait(&mut self) {
+        self.0.wait_for(|&completed| completed).await.ok();
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct FastSyncHealth {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_synced_l1_batch: Option<L1BatchNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_l1_batch_to_verify: Option<L1BatchNumber>,
+    completed: bool,
+}
+
+impl From<FastSyncHealth> for Health {
+    fn from(health: FastSyncHealth) -> Self {
+        Self::from(HealthStatus::Ready).with_details(health)
+    }
+}
+
+#[derive(Debug)]
+enum StepOutcome {
+    SyncedBatch(L1BatchNumber),
+    /// Too many applied L1 batches are not verified yet.
+    WaitingForVerification,
+    /// The next L1 batch is not fully available on the main node yet.
+    NoProgress,
+    /// The node is close enough to the main node to switch to re-executing transactions.
+    ReachedTip {
+        last_sealed_l1_batch: Option<L1BatchNumber>,
+    },
+}
+
+/// L2 block data fetched from the main node.
+#[derive(Debug)]
+struct FetchedBlockWithDiff {
+    block: FetchedBlock,
+    diff: en::L2BlockStateDiff,
+}
+
+/// Component applying L2 blocks and L1 batches to Postgres directly from the outputs of their execution on the main node,
+/// rather than by re-executing transactions in the state keeper.
+///
+/// # Overview
+///
+/// Fast sync processes whole L1 batches; all data for a batch is persisted in a single Postgres transaction.
+/// The first L1 batch to apply may be partially executed by the state keeper; in this case, fast sync
+/// applies the remaining L2 blocks of the batch.
+///
+/// Applied L2 blocks are checked to have the expected hashes. State root hashes of applied L1 batches
+/// are computed by the Merkle tree (i.e., Metadata calculator) and are compared with the root hashes from L1
+/// `BlockCommit` events (or, if they are not available yet, from the main node). The number of applied L1 batches
+/// awaiting verification is limited; a root hash mismatch is a fatal error. The next L1 batch to verify is persisted
+/// in Postgres, so that batches applied before a node restart are still verified after it.
+///
+/// Once the node is close to the main node, fast sync waits for all applied batches to be verified and completes,
+/// so that the state keeper can take over; see [`FastSyncCompletion`].
+#[derive(Debug)]
+pub struct FastSyncer {
+    pool: ConnectionPool<Core>,
+    main_node_client: Box<dyn MainNodeClient>,
+    data_provider: CombinedDataProvider,
+    l2_legacy_shared_bridge_addr: Option<Address>,
+    tip_distance: u32,
+    max_unverified_batches: NonZeroU32,
+    next_batch_to_verify: Option<L1BatchNumber>,
+    completion_sender: watch::Sender<bool>,
+    health_updater: HealthUpdater,
+    poll_interval: Duration,
+}
+
+impl FastSyncer {
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+    /// Creates a new syncer connected to the main node.
+    pub fn new(
+        client: Box<DynClient<L2>>,
+        pool: ConnectionPool<Core>,
+        config: &FastSyncConfig,
+    ) -> Self {
+        let client = client.for_component("fast_sync");
+        Self::with_providers(Box::new(client.clone()), client, pool, config)
+    }
+
+    fn with_providers(
+        main_node_client: Box<dyn MainNodeClient>,
+        tree_data_provider: impl TreeDataProvider,
+        pool: ConnectionPool<Core>,
+        config: &FastSyncConfig,
+    ) -> Self {
+        Self {
+            pool,
+            main_node_client,
+            data_provider: CombinedDataProvider::new(tree_data_provider),
+            l2_legacy_shared_bridge_addr: None,
+            tip_distance: config.tip_distance,
+            max_unverified_batches: config.max_unverified_batches,
+            next_batch_to_verify: None,
+            completion_sender: watch::channel(false).0,
+            health_updater: ReactiveHealthCheck::new("fast_sync").1,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Verifies state root hashes against L1 (namely, `BlockCommit` events emitted by the diamond proxy) if possible.
+    /// The main node will still be used as a fallback for batches not committed on L1.
+    pub async fn with_sl_data(
+        mut self,
+        sl_client: Box<dyn EthInterface>,
+        sl_diamond_proxy_addr: Address,
+    ) -> anyhow::Result<Self> {
+        let sl_provider = SLDataProvider::new(sl_client, sl_diamond_proxy_addr).await?;
+        self.data_provider.set_sl(sl_provider);
+        Ok(self)
+    }
+
+    /// Sets the legacy shared bridge address used to detect deployed tokens, similar to the state keeper.
+    pub fn with_l2_legacy_shared_bridge_addr(mut self, address: Option<Address>) -> Self {
+        self.l2_legacy_shared_bridge_addr = address;
+        self
+    }
+
+    /// Returns a health check for this syncer.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    /// Returns a handle allowing to wait until fast sync is completed.
+    pub fn completion(&self) -> FastSyncCompletion {
+        FastSyncCompletion(self.completion_sender.subscribe())
+    }
+
+    async fn step(&mut self) -> FastSyncResult<StepOutcome> {
+        let mut storage = self.pool.connection_tagged("fast_sync").await?;
+        let cursor = IoCursor::new(&mut storage).await?;
+        let last_sealed_l1_batch = storage.blocks_dal().get_sealed_l1_batch_number().await?;
+        drop(storage);
+
+        let main_node_l1_batch = self.main_node_client.fetch_l1_batch_number().await?;
+        let local_l1_batch = last_sealed_l1_batch.unwrap_or(L1BatchNumber(0));
+        if main_node_l1_batch.0.saturating_sub(local_l1_batch.0) <= self.tip_distance {
+            tracing::info!(
+                "Local L1 batch #{local_l1_batch} is within {} batches from the main node L1 batch #{main_node_l1_batch}",
+                self.tip_distance
+            );
+            return Ok(StepOutcome::ReachedTip {
+                last_sealed_l1_batch,
+            });
+        }
+
+        if let Some(next_batch_to_verify) = self.next_batch_to_verify {
+            let unverified_batches = cursor.l1_batch.0.saturating_sub(next_batch_to_verify.0);
+            if unverified_batches >= self.max_unverified_batches.get() {
+                tracing::debug!(
+                    "{unverified_batches} applied L1 batches starting from #{next_batch_to_verify} are not verified; \
+                     waiting for the Merkle tree to catch up"
+                );
+                return Ok(StepOutcome::WaitingForVerification);
+            }
+        }
+
+        let latency = FAST_SYNC_METRICS.l1_batch_latency.start();
+        let Some(blocks) = self.fetch_l1_batch(&cursor).await? else {
+            return Ok(StepOutcome::NoProgress);
+        };
+        let first_block = &blocks[0].block;
+        ensure_protocol_version_is_saved(
+            &self.pool,
+            self.main_node_client.as_ref(),
+            first_block.protocol_version,
+        )
+        .await?;
+
+        let last_l2_block = blocks.last().unwrap().block.number;
+        let l2_block_count = blocks.len();
+        self.apply_l1_batch(&cursor, blocks).await?;
+        latency.observe();
+
+        FAST_SYNC_METRICS
+            .synced_l1_batch
+            .set(cursor.l1_batch.0.into());
+        FAST_SYNC_METRICS
+            .synced_l2_blocks
+            .inc_by(l2_block_count as u64);
+        tracing::info!(
+            "Applied L1 batch #{} with {l2_block_count} L2 blocks (last L2 block: #{last_l2_block})",
+            cursor.l1_batch
+        );
+        self.next_batch_to_verify.get_or_insert(cursor.l1_batch);
+        Ok(StepOutcome::SyncedBatch(cursor.l1_batch))
+    }
+
+    /// Fetches all remaining L2 blocks in the next L1 batch. Returns `None` if the batch is not sealed on the main node.
+    async fn fetch_l1_batch(
+        &self,
+        cursor: &IoCursor,
+    ) -> FastSyncResult<Option<Vec<FetchedBlockWithDiff>>> {
+        let mut blocks = vec![];
+        let mut number = cursor.next_l2_block;
+        loop {
+            let block = self.main_node_client.fetch_l2_block(number, true).await?;
+            let diff = self
+                .main_node_client
+                .fetch_l2_block_state_diff(number)
+                .await?;
+            let (Some(block), Some(diff)) = (block, diff) else {
+                tracing::debug!("L2 block #{number} is not available on the main node yet");
+                return Ok(None);
+            };
+            let block = FetchedBlock::try_from(block)?;
+            if block.l1_batch_number != cursor.l1_batch {
+                let err = anyhow::anyhow!(
+                    "L2 block #{number} received from the main node belongs to L1 batch #{}, while #{} was expected",
+                    block.l1_batch_number,
+                    cursor.l1_batch
+                );
+                return Err(err.into());
+            }
+            if diff.number != number {
+                let err = anyhow::anyhow!(
+                    "Main node returned state diff for L2 block #{} instead of #{number}",
+                    diff.number
+                );
+                return Err(err.into());
+            }
+
+            let is_last_in_batch = diff.l1_batch.is_some();
+            let is_unsealed_batch_end = block.last_in_batch && !is_last_in_batch;
+            blocks.push(FetchedBlockWithDiff { block, diff });
+            if is_last_in_batch {
+                return Ok(Some(blocks));
+            } else if is_unsealed_batch_end {
+                tracing::debug!(
+                    "L1 batch #{} is not sealed on the main node yet",
+                    cursor.l1_batch
+                );
+                return Ok(None);
+            }
+            number += 1;
+        }
+    }
+
+    async fn apply_l1_batch(
+        &self,
+        cursor: &IoCursor,
+        blocks: Vec<FetchedBlockWithDiff>,
+    ) -> anyhow::Result<()> {
+        let l1_batch_number = cursor.l1_batch;
+        let seal_data = blocks
+            .last()
+            .and_then(|block| block.diff.l1_batch.clone())
+            .context("missing L1 batch seal data")?;
+        anyhow::ensure!(
+            seal_data.header.number == l1_batch_number,
+            "L1 batch seal data for #{} returned by the main node is for unexpected batch #{}",
+            l1_batch_number,
+            seal_data.header.number
+        );
+
+        let mut connection = self.pool.connection_tagged("fast_sync").await?;
+        let mut transaction = connection.start_transaction().await?;
+
+        // Some L2 blocks in the batch may have been executed by the state keeper before fast sync has started.
+        let mut l1_batch_params_provider = L1BatchParamsProvider::uninitialized();
+        l1_batch_params_provider
+            .initialize(&mut transaction)
+            .await
+            .context("failed initializing L1 batch params provider")?;
+        let first_pending_l2_block = l1_batch_params_provider
+            .load_first_l2_block_in_batch(&mut transaction, l1_batch_number)
+            .await?
+            .map(|block| block.number());
+        let mut batch_tx_hashes = vec![];
+        if let Some(first_pending_l2_block) = first_pending_l2_block {
+            let pending_transactions = transaction
+                .transactions_web3_dal()
+                .get_raw_l2_blocks_transactions(first_pending_l2_block..cursor.next_l2_block)
+                .await?;
+            let mut pending_transactions: Vec<_> = pending_transactions.into_iter().collect();
+            pending_transactions.sort_unstable_by_key(|(number, _)| *number);
+            batch_tx_hashes.extend(
+                pending_transactions
+                    .iter()
+                    .flat_map(|(_, txs)| txs.iter().map(Transaction::hash)),
+            );
+        }
+
+        transaction
+            .blocks_dal()
+            .ensure_unsealed_l1_batch_exists(seal_data.header.to_unsealed_header())
+            .await?;
+
+        let mut prev_l2_block_hash = cursor.prev_l2_block_hash;
+        for FetchedBlockWithDiff { block, diff } in blocks {
+            let number = block.number;
+            let tx_hashes: Vec<_> = block.transactions.iter().map(|tx| tx.hash()).collect();
+            let header_fields = L2BlockHeaderFields {
+                fee_account_address: seal_data.header.fee_address,
+                base_system_contracts_hashes: seal_data.header.base_system_contracts_hashes,
+            };
+            prev_l2_block_hash = self
+                .apply_l2_block(
+                    &mut transaction,
+                    block,
+                    diff,
+                    prev_l2_block_hash,
+                    header_fields,
+                )
+                .await
+                .with_context(|| format!("failed applying L2 block #{number}"))?;
+            batch_tx_hashes.extend(tx_hashes);
+        }
+
+        Self::seal_l1_batch(&mut transaction, seal_data, &batch_tx_hashes)
+            .await
+            .with_context(|| format!("failed sealing L1 batch #{l1_batch_number}"))?;
+        if self.next_batch_to_verify.is_none() {
+            // Persist the verification cursor atomically with the batch, so that it's not lost on node restart.
+            transaction
+                .blocks_dal()
+                .set_fast_sync_next_l1_batch_to_verify(Some(l1_batch_number))
+                .await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    async fn apply_l2_block(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        block: FetchedBlock,
+        diff: en::L2BlockStateDiff,
+        prev_l2_block_hash: H256,
+        header_fields: L2BlockHeaderFields,
+    ) -> anyhow::Result<H256> {
+        let number = block.number;
+        let hash = block.compute_hash(prev_l2_block_hash);
+        if let Some(reference_hash) = block.reference_hash {
+            anyhow::ensure!(
+                hash == reference_hash,
+                "Mismatch between the locally computed ({hash:?}) and received ({reference_hash:?}) hash \
+                 for L2 block #{number}; this can be caused by a chain reorg"
+            );
+        }
+        anyhow::ensure!(
+            block.transactions.len() == diff.receipts.len(),
+            "Main node returned {} receipts for {} transactions",
+            diff.receipts.len(),
+            block.transactions.len()
+        );
+
+        let transactions: Vec<Transaction> = block
+            .transactions
+            .into_iter()
+            .map(Transaction::from)
+            .collect();
+        let (mut l1_tx_count, mut l2_tx_count) = (0_u16, 0_u16);
+        let mut executed_transactions = Vec::with_capacity(transactions.len());
+        let mut user_l2_to_l1_logs = vec![];
+        for (transaction, receipt) in transactions.into_iter().zip(&diff.receipts) {
+            if transaction.is_l1() {
+                l1_tx_count += 1;
+            } else {
+                l2_tx_count += 1;
+            }
+            let location = IncludedTxLocation {
+                tx_hash: transaction.hash(),
+                tx_index_in_l2_block: receipt.transaction_index.as_u32(),
+            };
+            let logs = receipt
+                .l2_to_l1_logs
+                .iter()
+                .map(user_l2_to_l1_log)
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            user_l2_to_l1_logs.push((location, logs));
+            executed_transactions.push(execution_result(transaction, receipt)?);
+        }
+
+        storage
+            .transactions_dal()
+            .mark_txs_as_executed_in_l2_block(
+                number,
+                &executed_transactions,
+                diff.base_fee_per_gas.into(),
+                block.protocol_version,
+                true,
+            )
+            .await?;
+
+        let storage_logs: Vec<_> = diff
+            .storage_writes
+            .iter()
+            .map(|write| {
+                let key = StorageKey::new(AccountTreeId::new(write.address), write.key);
+                StorageLog::new_write_log(key, write.value)
+            })
+            .collect();
+        storage
+            .storage_logs_dal()
+            .insert_storage_logs(number, &storage_logs)
+            .await?;
+
+        let factory_deps: HashMap<_, _> = diff
+            .factory_deps
+            .into_iter()
+            .map(|dep| (dep.bytecode_hash, dep.bytecode.0))
+            .collect();
+        if !factory_deps.is_empty() {
+            storage
+                .factory_deps_dal()
+                .insert_factory_deps(number, &factory_deps)
+                .await?;
+        }
+
+        let events = vm_events(block.l1_batch_number, &diff.events)?;
+        let token_deployer_address = self
+            .l2_legacy_shared_bridge_addr
+            .unwrap_or(L2_NATIVE_TOKEN_VAULT_ADDRESS);
+        let added_tokens = extract_added_tokens(token_deployer_address, &events);
+        if !added_tokens.is_empty() {
+            storage.tokens_dal().add_tokens(&added_tokens).await?;
+        }
+
+        let grouped_events = group_events(&diff.events, &events);
+        storage
+            .events_dal()
+            .save_events(number, &grouped_events)
+            .await?;
+        let user_l2_to_l1_logs: Vec<_> = user_l2_to_l1_logs
+            .iter()
+            .filter(|(_, logs)| !logs.is_empty())
+            .map(|(location, logs)| (*location, logs.iter().collect::<Vec<_>>()))
+            .collect();
+        if !user_l2_to_l1_logs.is_empty() {
+            storage
+                .events_dal()
+                .save_user_l2_to_l1_logs(number, &user_l2_to_l1_logs)
+                .await?;
+        }
+        storage
+            .interop_root_dal()
+            .mark_interop_roots_as_executed(&block.interop_roots, number, true)
+            .await?;
+
+        let logs_bloom = build_bloom(events.iter().flat_map(|event| {
+            event
+                .indexed_topics
+                .iter()
+                .map(|topic| BloomInput::Raw(topic.as_bytes()))
+                .chain([BloomInput::Raw(event.address.as_bytes())])
+        }));
+        let header = L2BlockHeader {
+            number,
+            timestamp: block.timestamp,
+            hash,
+            l1_tx_count,
+            l2_tx_count,
+            fee_account_address: header_fields.fee_account_address,
+            base_fee_per_gas: diff.base_fee_per_gas,
+            batch_fee_input: BatchFeeInput::for_protocol_version(
+                block.protocol_version,
+                block.l2_fair_gas_price,
+                block.fair_pubdata_price,
+                block.l1_gas_price,
+            ),
+            gas_per_pubdata_limit: diff.gas_per_pubdata_limit,
+            base_system_contracts_hashes: header_fields.base_system_contracts_hashes,
+            protocol_version: Some(block.protocol_version),
+            virtual_blocks: block.virtual_blocks,
+            gas_limit: diff.gas_limit,
+            logs_bloom,
+            pubdata_params: block.pubdata_params,
+            rolling_txs_hash: diff.rolling_txs_hash,
+        };
+        storage.blocks_dal().insert_l2_block(&header).await?;
+        Ok(hash)
+    }
+
+    async fn seal_l1_batch(
+        storage: &mut Connection<'_, Core>,
+        seal_data: en::L1BatchSealData,
+        tx_hashes: &[H256],
+    ) -> anyhow::Result<()> {
+        let number = seal_data.header.number;
+        storage
+            .blocks_dal()
+            .mark_l1_batch_as_sealed(
+                &seal_data.header,
+                &seal_data.initial_bootloader_contents,
+                &seal_data.storage_refunds,
+                &seal_data.pubdata_costs,
+                // Predicted circuit statistics are only used by the main node
+                CircuitStatistic::default(),
+                ZK_SYNC_BYTES_PER_BLOB as u64,
+                seal_data.header.interop_fee,
+            )
+            .await?;
+        storage
+            .blocks_dal()
+            .mark_l2_blocks_as_executed_in_l1_batch(number)
+            .await?;
+        storage
+            .transactions_dal()
+            .mark_txs_as_executed_in_l1_batch(number, tx_hashes)
+            .await?;
+
+        // Enumeration indices are assigned sequentially on insertion, so they must match the ones on the main node.
+        let last_index = storage
+            .storage_logs_dedup_dal()
+            .max_enumeration_index_by_l1_batch(number - 1)
+            .await?
+            .unwrap_or(0);
+        for (i, &(hashed_key, index)) in seal_data.initial_writes.iter().enumerate() {
+            let expected_index = last_index + i as u64 + 1;
+            anyhow::ensure!(
+                index == expected_index,
+                "Unexpected enumeration index for initial write {hashed_key:?} in L1 batch #{number}: \
+                 expected {expected_index}, got {index}"
+            );
+        }
+        let hashed_keys: Vec<_> = seal_data
+            .initial_writes
+            .iter()
+            .map(|&(hashed_key, _)| hashed_key)
+            .collect();
+        storage
+            .storage_logs_dedup_dal()
+            .insert_initial_writes(number, &hashed_keys)
+            .await?;
+        Ok(())
+    }
+
+    /// Verifies state root hashes for all applied L1 batches processed by the Merkle tree.
+    async fn verify_batches(&mut self) -> FastSyncResult<()> {
+        while let Some(l1_batch_number) = self.next_batch_to_verify {
+            let mut storage = self.pool.connection_tagged("fast_sync").await?;
+            let Some(tree_data) = storage
+                .blocks_dal()
+                .get_l1_batch_tree_data(l1_batch_number)
+                .await?
+            else {
+                return Ok(());
+            };
+            let (_, last_l2_block_number) = storage
+                .blocks_dal()
+                .get_l2_block_range_of_l1_batch(l1_batch_number)
+                .await?
+                .with_context(|| {
+                    format!("L1 batch #{l1_batch_number} disappeared from Postgres")
+                })?;
+            let last_l2_block = storage
+                .blocks_dal()
+                .get_l2_block_header(last_l2_block_number)
+                .await?
+                .with_context(|| {
+                    format!("L2 block #{last_l2_block_number} disappeared from Postgres")
+                })?;
+            drop(storage);
+
+            let expected_root_hash = match self
+                .data_provider
+                .batch_details(l1_batch_number, &last_l2_block)
+                .await?
+            {
+                Ok(root_hash) => root_hash,
+                Err(MissingData::RootHash) => {
+                    tracing::debug!(
+                        "Root hash for L1 batch #{l1_batch_number} is not available externally yet"
+                    );
+                    return Ok(());
+                }
+                Err(err @ (MissingData::Batch | MissingData::PossibleReorg)) => {
+                    let err = anyhow::anyhow!(
+                        "Cannot verify root hash for L1 batch #{l1_batch_number}: {err}"
+                    );
+                    return Err(err.into());
+                }
+            };
+            if expected_root_hash != tree_data.hash {
+                let err = anyhow::anyhow!(
+                    "Root hash mismatch for L1 batch #{l1_batch_number}: computed {:?}, expected {expected_root_hash:?}",
+                    tree_data.hash
+                );
+                return Err(err.into());
+            }
+
+            tracing::debug!("Verified root hash for L1 batch #{l1_batch_number}");
+            FAST_SYNC_METRICS
+                .verified_l1_batch
+                .set(l1_batch_number.0.into());
+            self.set_next_batch_to_verify(Some(l1_batch_number + 1))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn set_next_batch_to_verify(
+        &mut self,
+        l1_batch_number: Option<L1BatchNumber>,
+    ) -> FastSyncResult<()> {
+        self.pool
+            .connection_tagged("fast_sync")
+            .await?
+            .blocks_dal()
+            .set_fast_sync_next_l1_batch_to_verify(l1_batch_number)
+            .await?;
+        self.next_batch_to_verify = l1_batch_number;
+        Ok(())
+    }
+
+    fn update_health(&self, last_synced_l1_batch: Option<L1BatchNumber>, completed: bool) {
+        let health = FastSyncHealth {
+            last_synced_l1_batch,
+            next_l1_batch_to_verify: self.next_batch_to_verify,
+            completed,
+        };
+        self.health_updater.update(health.into());
+    }
+
+    /// Runs fast sync until the node is close to the main node, a fatal error occurs or a stop request is received.
+    /// Retriable errors (e.g., no network connection) are handled gracefully by retrying after a delay.
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        // Batches applied before a node restart may be unverified, even if the Merkle tree has processed them
+        // (e.g., if their root hashes were not available externally), so verification resumes from the persisted cursor.
+        self.next_batch_to_verify = self
+            .pool
+            .connection_tagged("fast_sync")
+            .await?
+            .blocks_dal()
+            .get_fast_sync_next_l1_batch_to_verify()
+            .await?;
+        let mut last_synced_l1_batch = None;
+        self.update_health(last_synced_l1_batch, false);
+
+        while !*stop_receiver.borrow_and_update() {
+            let step_outcome = match self.verify_batches().await {
+                Ok(()) => self.step().await,
+                Err(err) => Err(err),
+            };
+            let need_to_sleep = match step_outcome {
+                Ok(StepOutcome::SyncedBatch(number)) => {
+                    last_synced_l1_batch = Some(number);
+                    self.update_health(last_synced_l1_batch, false);
+                    false
+                }
+                Ok(StepOutcome::WaitingForVerification | StepOutcome::NoProgress) => {
+                    self.update_health(last_synced_l1_batch, false);
+                    true
+                }
+                Ok(StepOutcome::ReachedTip {
+                    last_sealed_l1_batch,
+                }) => {
+                    let all_verified = match (last_sealed_l1_batch, self.next_batch_to_verify) {
+                        (Some(last_sealed), Some(next_to_verify)) => next_to_verify > last_sealed,
+                        _ => true,
+                    };
+                    if all_verified {
+                        tracing::info!(
+                            "Fast sync is completed; switching to re-executing transactions"
+                        );
+                        // Batches executed by the state keeper don't need to be verified by fast sync.
+                        self.set_next_batch_to_verify(None)
+                            .await
+                            .map_err(FastSyncError::into_anyhow)?;
+                        self.update_health(last_synced_l1_batch, true);
+                        self.completion_sender.send_replace(true);
+                        return Ok(());
+                    }
+                    self.update_health(last_synced_l1_batch, false);
+                    true
+                }
+                Err(err) if err.is_retriable() => {
+                    tracing::warn!(
+                        "Transient error in fast sync, will retry after a delay: {err:?}"
+                    );
+                    let health = Health::from(HealthStatus::Affected)
+                        .with_details(serde_json::json!({ "error": err.to_string() }));
+                    self.health_updater.update(health);
+                    true
+                }
+                Err(err) => {
+                    tracing::error!("Fatal error in fast sync: {err:?}");
+                    return Err(err.into_anyhow());
+                }
+            };
+
+            if need_to_sleep
+                && tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                    .await
+                    .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop request received; fast sync is shutting down");
+        Ok(())
+    }
+}
+
+/// L2 block header fields that are shared among all L2 blocks in the batch.
+#[derive(Debug, Clone, Copy)]
+struct L2BlockHeaderFields {
+    fee_account_address: Address,
+    base_system_contracts_hashes: BaseSystemContractsHashes,
+}
+
+fn execution_result(
+    transaction: Transaction,
+    receipt: &api::TransactionReceipt,
+) -> anyhow::Result<TransactionExecutionResult> {
+    let hash = transaction.hash();
+    anyhow::ensure!(
+        receipt.transaction_hash == hash,
+        "Receipt for transaction {:?} returned in place of transaction {hash:?}",
+        receipt.transaction_hash
+    );
+    let gas_used = receipt.gas_used.unwrap_or_default();
+    let refunded_gas = transaction.gas_limit().saturating_sub(gas_used);
+    let execution_status = if receipt.status.is_zero() {
+        TxExecutionStatus::Failure
+    } else {
+        TxExecutionStatus::Success
+    };
+    Ok(TransactionExecutionResult {
+        hash,
+        execution_info: VmExecutionMetrics {
+            gas_used: gas_used.as_usize(),
+            ..VmExecutionMetrics::default()
+        },
+        execution_status,
+        refunded_gas: refunded_gas
+            .try_into()
+            .map_err(|err| anyhow::anyhow!("refunded gas overflow for {hash:?}: {err}"))?,
+        // Call traces and revert reasons are not provided by the main node.
+        call_traces: vec![],
+        revert_reason: None,
+        transaction,
+    })
+}
+
+fn user_l2_to_l1_log(log: &api::L2ToL1Log) -> anyhow::Result<UserL2ToL1Log> {
+    let tx_index_in_l1_batch = log
+        .tx_index_in_l1_batch
+        .context("missing `tx_index_in_l1_batch` for L2-to-L1 log")?;
+    Ok(UserL2ToL1Log(L2ToL1Log {
+        shard_id: log.shard_id.as_u32().try_into().context("shard ID")?,
+        is_service: log.is_service,
+        tx_number_in_block: tx_index_in_l1_batch
+            .as_u32()
+            .try_into()
+            .context("transaction index in L1 batch")?,
+        sender: log.sender,
+        key: log.key,
+        value: log.value,
+    }))
+}
+
+fn vm_events(l1_batch_number: L1BatchNumber, logs: &[api::Log]) -> anyhow::Result<Vec<VmEvent>> {
+    logs.iter()
+        .map(|log| {
+            let tx_index = log
+                .transaction_index
+                .context("missing transaction index for event")?;
+            Ok(VmEvent {
+                location: (l1_batch_number, tx_index.as_u32()),
+                address: log.address,
+                indexed_topics: log.topics.clone(),
+                value: log.data.0.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Groups consecutive events by transactions, as expected by the events DAL.
+fn group_events<'a>(
+    logs: &[api::Log],
+    events: &'a [VmEvent],
+) -> Vec<(IncludedTxLocation, Vec<&'a VmEvent>)> {
+    let mut grouped: Vec<(IncludedTxLocation, Vec<&VmEvent>)> = vec![];
+    for (log, event) in logs.iter().zip(events) {
+        let location = IncludedTxLocation {
+            // Events emitted outside of transactions (e.g., in the fictive L2 block) have a zero hash.
+            tx_hash: log.transaction_hash.unwrap_or_default(),
+            tx_index_in_l2_block: event.location.1,
+        };
+        match grouped.last_mut() {
+            Some((last_location, events))
+                if last_location.tx_hash == location.tx_hash
+                    && last_location.tx_index_in_l2_block == location.tx_index_in_l2_block =>
+            {
+                events.push(event);
+            }
+            _ => grouped.push((location, vec![event])),
+        }
+    }
+    grouped
+}
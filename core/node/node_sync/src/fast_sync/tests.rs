@@ -0,0 +1,457 @@
+//! Tests for fast sync.
+
+use std::collections::HashMap;
+
+use assert_matches::assert_matches;
+use async_trait::async_trait;
+use test_casing::test_casing;
+use tokio::task::JoinHandle;
+use zksync_dal::ConnectionPool;
+use zksync_node_genesis::{insert_genesis_batch, GenesisParamsInitials};
+use zksync_types::{
+    api::en::{L1BatchSealData, L2BlockStateDiff, StorageWrite, SyncBlock},
+    block::{L1BatchHeader, L1BatchTreeData, L2BlockHasher},
+    commitment::PubdataParams,
+    settlement::SettlementLayer,
+    ProtocolVersionId, U256,
+};
+
+use super::*;
+use crate::{testonly::MockMainNodeClient, tree_data_fetcher::provider::TreeDataProviderResult};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+fn mock_root_hash(number: L1BatchNumber) -> H256 {
+    H256::from_low_u64_be(number.0.into())
+}
+
+#[derive(Debug)]
+struct MockTreeDataProvider(HashMap<L1BatchNumber, H256>);
+
+#[async_trait]
+impl TreeDataProvider for MockTreeDataProvider {
+    async fn batch_details(
+        &mut self,
+        number: L1BatchNumber,
+        _last_l2_block: &L2BlockHeader,
+    ) -> TreeDataProviderResult {
+        Ok(self.0.get(&number).copied().ok_or(MissingData::RootHash))
+    }
+}
+
+/// Emulates the main node with L1 batches each consisting of a single L2 block without transactions.
+async fn mock_main_node(
+    storage: &mut Connection<'_, Core>,
+    l1_batch_count: u32,
+) -> MockMainNodeClient {
+    let genesis_block = storage
+        .blocks_dal()
+        .get_l2_block_header(L2BlockNumber(0))
+        .await
+        .unwrap()
+        .unwrap();
+    let genesis_batch = storage
+        .blocks_dal()
+        .get_l1_batch_header(L1BatchNumber(0))
+        .await
+        .unwrap()
+        .unwrap();
+    let last_index = storage
+        .storage_logs_dedup_dal()
+        .max_enumeration_index_by_l1_batch(L1BatchNumber(0))
+        .await
+        .unwrap()
+        .unwrap_or(0);
+
+    let protocol_version = ProtocolVersionId::latest();
+    let mut client = MockMainNodeClient::default();
+    let mut prev_hash = genesis_block.hash;
+    for number in 0..=l1_batch_count {
+        let timestamp = genesis_block.timestamp + u64::from(number);
+        let hash = if number == 0 {
+            genesis_block.hash
+        } else {
+            L2BlockHasher::new(L2BlockNumber(number), timestamp, prev_hash)
+                .finalize(protocol_version)
+        };
+        prev_hash = hash;
+
+        client.l2_blocks.push(SyncBlock {
+            number: L2BlockNumber(number),
+            l1_batch_number: L1BatchNumber(number),
+            last_in_batch: true,
+            timestamp,
+            l1_gas_price: 2,
+            l2_fair_gas_price: 3,
+            fair_pubdata_price: Some(4),
+            base_system_contracts_hashes: genesis_batch.base_system_contracts_hashes,
+            operator_address: Address::repeat_byte(1),
+            transactions: Some(vec![]),
+            virtual_blocks: Some(1),
+            hash: Some(hash),
+            protocol_version,
+            pubdata_params: Some(PubdataParams::genesis()),
+            pubdata_limit: Some(100_000),
+            interop_roots: Some(vec![]),
+            settlement_layer: Some(SettlementLayer::for_tests()),
+            interop_fee: Some(U256::zero()),
+        });
+
+        let storage_write = StorageWrite {
+            address: Address::repeat_byte(0x23),
+            key: H256::from_low_u64_be(number.into()),
+            value: H256::repeat_byte(0xff),
+        };
+        let hashed_key =
+            StorageKey::new(AccountTreeId::new(storage_write.address), storage_write.key)
+                .hashed_key();
+        let mut header = L1BatchHeader::new(
+            L1BatchNumber(number),
+            timestamp,
+            genesis_batch.base_system_contracts_hashes,
+            protocol_version,
+            SettlementLayer::for_tests(),
+        );
+        header.fee_address = Address::repeat_byte(1);
+        client.state_diffs.push(L2BlockStateDiff {
+            number: L2BlockNumber(number),
+            base_fee_per_gas: 100,
+            gas_per_pubdata_limit: 800,
+            gas_limit: 1_000_000,
+            rolling_txs_hash: Some(H256::zero()),
+            storage_writes: vec![storage_write],
+            factory_deps: vec![],
+            events: vec![],
+            receipts: vec![],
+            l1_batch: Some(L1BatchSealData {
+                header,
+                initial_bootloader_contents: vec![],
+                initial_writes: vec![(hashed_key, last_index + u64::from(number))],
+                storage_refunds: vec![],
+                pubdata_costs: vec![],
+            }),
+        });
+    }
+    client
+}
+
+/// Emulates the Merkle tree by persisting mock root hashes for all sealed L1 batches.
+fn spawn_mock_tree(
+    pool: ConnectionPool<Core>,
+    mut stop_receiver: watch::Receiver<bool>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while !*stop_receiver.borrow() {
+            let mut storage = pool.connection().await.unwrap();
+            let sealed = storage
+                .blocks_dal()
+                .get_sealed_l1_batch_number()
+                .await
+                .unwrap();
+            let with_tree_data = storage
+                .blocks_dal()
+                .get_last_l1_batch_number_with_tree_data()
+                .await
+                .unwrap();
+            if let (Some(sealed), Some(with_tree_data)) = (sealed, with_tree_data) {
+                for number in (with_tree_data.0 + 1)..=sealed.0 {
+                    let number = L1BatchNumber(number);
+                    let tree_data = L1BatchTreeData {
+                        hash: mock_root_hash(number),
+                        rollup_last_leaf_index: 1,
+                    };
+                    storage
+                        .blocks_dal()
+                        .save_l1_batch_tree_data(number, &tree_data)
+                        .await
+                        .unwrap();
+                }
+            }
+            drop(storage);
+            tokio::time::timeout(POLL_INTERVAL, stop_receiver.changed())
+                .await
+                .ok();
+        }
+    })
+}
+
+fn create_syncer(
+    pool: ConnectionPool<Core>,
+    client: MockMainNodeClient,
+    root_hashes: HashMap<L1BatchNumber, H256>,
+    config: &FastSyncConfig,
+) -> FastSyncer {
+    let mut syncer = FastSyncer::with_providers(
+        Box::new(client),
+        MockTreeDataProvider(root_hashes),
+        pool,
+        config,
+    );
+    syncer.poll_interval = POLL_INTERVAL;
+    syncer
+}
+
+#[test_casing(3, [1, 2, 10])]
+#[tokio::test]
+async fn syncing_l1_batches(max_unverified_batches: u32) {
+    const L1_BATCH_COUNT: u32 = 5;
+    const TIP_DISTANCE: u32 = 1;
+
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParamsInitials::mock())
+        .await
+        .unwrap();
+    let client = mock_main_node(&mut storage, L1_BATCH_COUNT).await;
+    let root_hashes = (1..=L1_BATCH_COUNT)
+        .map(|number| {
+            let number = L1BatchNumber(number);
+            (number, mock_root_hash(number))
+        })
+        .collect();
+
+    let config = FastSyncConfig {
+        enabled: true,
+        tip_distance: TIP_DISTANCE,
+        max_unverified_batches: NonZeroU32::new(max_unverified_batches).unwrap(),
+    };
+    let syncer = create_syncer(pool.clone(), client, root_hashes, &config);
+    let mut completion = syncer.completion();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let tree_handle = spawn_mock_tree(pool.clone(), stop_receiver.clone());
+    let syncer_handle = tokio::spawn(syncer.run(stop_receiver));
+
+    completion.wait().await;
+    syncer_handle.await.unwrap().unwrap();
+    stop_sender.send_replace(true);
+    tree_handle.await.unwrap();
+
+    let last_synced_batch = L1BatchNumber(L1_BATCH_COUNT - TIP_DISTANCE);
+    let sealed_batch = storage
+        .blocks_dal()
+        .get_sealed_l1_batch_number()
+        .await
+        .unwrap();
+    assert_eq!(sealed_batch, Some(last_synced_batch));
+    assert!(!storage.blocks_dal().pending_batch_exists().await.unwrap());
+
+    for number in 1..=last_synced_batch.0 {
+        let l2_block = storage
+            .blocks_dal()
+            .get_l2_block_header(L2BlockNumber(number))
+            .await
+            .unwrap()
+            .expect("L2 block is not persisted");
+        assert_eq!(l2_block.base_fee_per_gas, 100);
+        assert_eq!(l2_block.gas_limit, 1_000_000);
+        let range = storage
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(L1BatchNumber(number))
+            .await
+            .unwrap();
+        assert_eq!(range, Some((L2BlockNumber(number), L2BlockNumber(number))));
+
+        let storage_logs = storage
+            .storage_logs_dal()
+            .get_storage_logs_for_l2_block(L2BlockNumber(number))
+            .await
+            .unwrap();
+        assert_eq!(storage_logs.len(), 1);
+        let initial_writes = storage
+            .storage_logs_dedup_dal()
+            .initial_writes_for_batch(L1BatchNumber(number))
+            .await
+            .unwrap();
+        assert_eq!(initial_writes.len(), 1);
+        assert_eq!(initial_writes[0].0, storage_logs[0].key.hashed_key());
+    }
+
+    // The syncer should be able to continue from the state left by the previous run.
+    let client = mock_main_node(&mut storage, L1_BATCH_COUNT + 3).await;
+    let root_hashes = (1..=L1_BATCH_COUNT + 3)
+        .map(|number| {
+            let number = L1BatchNumber(number);
+            (number, mock_root_hash(number))
+        })
+        .collect();
+    let syncer = create_syncer(pool.clone(), client, root_hashes, &config);
+    let mut completion = syncer.completion();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let tree_handle = spawn_mock_tree(pool.clone(), stop_receiver.clone());
+    syncer.run(stop_receiver).await.unwrap();
+    completion.wait().await;
+    stop_sender.send_replace(true);
+    tree_handle.await.unwrap();
+
+    let sealed_batch = storage
+        .blocks_dal()
+        .get_sealed_l1_batch_number()
+        .await
+        .unwrap();
+    assert_eq!(
+        sealed_batch,
+        Some(L1BatchNumber(L1_BATCH_COUNT + 3 - TIP_DISTANCE))
+    );
+}
+
+#[tokio::test]
+async fn fast_sync_is_noop_near_tip() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParamsInitials::mock())
+        .await
+        .unwrap();
+    let client = mock_main_node(&mut storage, 2).await;
+    let config = FastSyncConfig::default();
+    let syncer = create_syncer(pool.clone(), client, HashMap::new(), &config);
+    let mut completion = syncer.completion();
+
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    syncer.run(stop_receiver).await.unwrap();
+    completion.wait().await;
+
+    let sealed_batch = storage
+        .blocks_dal()
+        .get_sealed_l1_batch_number()
+        .await
+        .unwrap();
+    assert_eq!(sealed_batch, Some(L1BatchNumber(0)));
+}
+
+#[tokio::test]
+async fn root_hash_mismatch_is_fatal() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParamsInitials::mock())
+        .await
+        .unwrap();
+    let client = mock_main_node(&mut storage, 5).await;
+    let root_hashes = HashMap::from([(L1BatchNumber(1), H256::repeat_byte(0xde))]);
+    let config = FastSyncConfig {
+        enabled: true,
+        tip_distance: 0,
+        max_unverified_batches: NonZeroU32::new(1).unwrap(),
+    };
+    let syncer = create_syncer(pool.clone(), client, root_hashes, &config);
+
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let tree_handle = spawn_mock_tree(pool.clone(), stop_receiver.clone());
+    let err = syncer.run(stop_receiver).await.unwrap_err();
+    stop_sender.send_replace(true);
+    tree_handle.await.unwrap();
+
+    let err = format!("{err:#}");
+    assert!(err.contains("Root hash mismatch"), "{err}");
+    // Only a single batch should be applied because of `max_unverified_batches`.
+    let sealed_batch = storage
+        .blocks_dal()
+        .get_sealed_l1_batch_number()
+        .await
+        .unwrap();
+    assert_eq!(sealed_batch, Some(L1BatchNumber(1)));
+}
+
+#[tokio::test]
+async fn l2_block_hash_mismatch_is_fatal() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParamsInitials::mock())
+        .await
+        .unwrap();
+    let mut client = mock_main_node(&mut storage, 5).await;
+    client.l2_blocks[1].hash = Some(H256::repeat_byte(1));
+    let config = FastSyncConfig {
+        enabled: true,
+        tip_distance: 0,
+        max_unverified_batches: NonZeroU32::new(10).unwrap(),
+    };
+    let mut syncer = create_syncer(pool.clone(), client, HashMap::new(), &config);
+
+    let err = syncer.step().await.unwrap_err();
+    assert_matches!(
+        err,
+        FastSyncError::Internal(err) if format!("{err:#}").contains("this can be caused by a chain reorg")
+    );
+    let sealed_batch = storage
+        .blocks_dal()
+        .get_sealed_l1_batch_number()
+        .await
+        .unwrap();
+    assert_eq!(sealed_batch, Some(L1BatchNumber(0)));
+}
+
+#[tokio::test]
+async fn unverified_batches_are_verified_after_restart() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParamsInitials::mock())
+        .await
+        .unwrap();
+    let client = mock_main_node(&mut storage, 5).await;
+    let config = FastSyncConfig {
+        enabled: true,
+        tip_distance: 0,
+        max_unverified_batches: NonZeroU32::new(2).unwrap(),
+    };
+    // Root hashes are not available externally, so applied batches cannot be verified.
+    let syncer = create_syncer(pool.clone(), client, HashMap::new(), &config);
+
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let tree_handle = spawn_mock_tree(pool.clone(), stop_receiver.clone());
+    let syncer_handle = tokio::spawn(syncer.run(stop_receiver));
+    // Wait until the tree processes all applied batches.
+    loop {
+        let with_tree_data = storage
+            .blocks_dal()
+            .get_last_l1_batch_number_with_tree_data()
+            .await
+            .unwrap();
+        if with_tree_data == Some(L1BatchNumber(2)) {
+            break;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    stop_sender.send_replace(true);
+    syncer_handle.await.unwrap().unwrap();
+    tree_handle.await.unwrap();
+
+    let next_batch_to_verify = storage
+        .blocks_dal()
+        .get_fast_sync_next_l1_batch_to_verify()
+        .await
+        .unwrap();
+    assert_eq!(next_batch_to_verify, Some(L1BatchNumber(1)));
+
+    // After the restart, verification must resume from L1 batch #1 even though the tree has processed it.
+    let root_hashes = HashMap::from([(L1BatchNumber(1), H256::repeat_byte(0xde))]);
+    let client = mock_main_node(&mut storage, 5).await;
+    let syncer = create_syncer(pool.clone(), client, root_hashes, &config);
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let err = syncer.run(stop_receiver).await.unwrap_err();
+    let err = format!("{err:#}");
+    assert!(err.contains("Root hash mismatch for L1 batch #1"), "{err}");
+
+    // With correct root hashes, fast sync completes and resets the cursor.
+    let root_hashes = (1..=5)
+        .map(|number| {
+            let number = L1BatchNumber(number);
+            (number, mock_root_hash(number))
+        })
+        .collect();
+    let client = mock_main_node(&mut storage, 5).await;
+    let syncer = create_syncer(pool.clone(), client, root_hashes, &config);
+    let mut completion = syncer.completion();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let tree_handle = spawn_mock_tree(pool.clone(), stop_receiver.clone());
+    syncer.run(stop_receiver).await.unwrap();
+    completion.wait().await;
+    stop_sender.send_replace(true);
+    tree_handle.await.unwrap();
+
+    let next_batch_to_verify = storage
+        .blocks_dal()
+        .get_fast_sync_next_l1_batch_to_verify()
+        .await
+        .unwrap();
+    assert_eq!(next_batch_to_verify, None);
+}
@@ -62,7 +62,7 @@ pub struct FetchedBlock {
 }
 
 impl FetchedBlock {
-    fn compute_hash(&self, prev_l2_block_hash: H256) -> H256 {
+    pub(crate) fn compute_hash(&self, prev_l2_block_hash: H256) -> H256 {
         let mut hasher = L2BlockHasher::new(self.number, self.timestamp, prev_l2_block_hash);
         for tx in &self.transactions {
             hasher.push_tx_hash(tx.hash());
@@ -2,6 +2,7 @@ pub mod batch_transaction_fetcher;
 mod client;
 pub mod data_availability_fetcher;
 pub mod external_io;
+pub mod fast_sync;
 pub mod fetcher;
 pub mod genesis;
 mod metrics;
@@ -13,6 +14,7 @@ pub mod testonly;
 mod tests;
 pub mod transaction_finality_updater;
 pub mod tree_data_fetcher;
+pub mod upstream_monitor;
 pub mod validate_chain_ids_task;
 
 pub use self::{
@@ -2,7 +2,7 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics};
+use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics};
 use zksync_types::aggregated_operations::L1BatchAggregatedActionType;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
@@ -56,3 +56,34 @@ pub(super) struct ActionQueueMetrics {
 
 #[vise::register]
 pub(super) static QUEUE_METRICS: vise::Global<ActionQueueMetrics> = vise::Global::new();
+
+/// Metrics for fast sync.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "external_node_fast_sync")]
+pub(super) struct FastSyncMetrics {
+    /// Last L1 batch applied by fast sync.
+    pub synced_l1_batch: Gauge<u64>,
+    /// Last L1 batch with the verified state root hash.
+    pub verified_l1_batch: Gauge<u64>,
+    /// Number of L2 blocks applied by fast sync.
+    pub synced_l2_blocks: Counter,
+    /// Latency of applying a single L1 batch (including fetching its data from the main node).
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub l1_batch_latency: Histogram<Duration>,
+}
+
+#[vise::register]
+pub(super) static FAST_SYNC_METRICS: vise::Global<FastSyncMetrics> = vise::Global::new();
+
+/// Metrics for upstreams the external node syncs from.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "external_node_upstreams")]
+pub(super) struct UpstreamMetrics {
+    /// Index of the currently used upstream; 0 corresponds to the main node.
+    pub active: Gauge<u64>,
+    /// Number of healthy upstreams, including the main node.
+    pub healthy: Gauge<usize>,
+}
+
+#[vise::register]
+pub(super) static UPSTREAM_METRICS: vise::Global<UpstreamMetrics> = vise::Global::new();
@@ -16,7 +16,7 @@ use zksync_types::L2ChainId;
 use zksync_web3_decl::client::{DynClient, L2};
 
 use super::resources::ActionQueueSenderResource;
-use crate::{ActionQueue, ExternalIO};
+use crate::{fast_sync::FastSyncCompletion, ActionQueue, ExternalIO};
 
 /// Wiring layer for `ExternalIO`, an IO part of state keeper used by the external node.
 #[derive(Debug)]
@@ -31,6 +31,7 @@ pub struct Input {
     pool: PoolResource<MasterPool>,
     main_node_client: Box<DynClient<L2>>,
     l1_batch_commit_data_generator_mode: L1BatchCommitmentModeResource,
+    fast_sync: Option<FastSyncCompletion>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -72,13 +73,16 @@ impl WiringLayer for ExternalIOLayer {
 
         // Create external IO resource.
         let io_pool = input.pool.get().await.context("Get master pool")?;
-        let io = ExternalIO::new(
+        let mut io = ExternalIO::new(
             io_pool,
             action_queue,
             Box::new(input.main_node_client.for_component("external_io")),
             self.chain_id,
         )
         .context("Failed initializing I/O for external node state keeper")?;
+        if let Some(fast_sync) = input.fast_sync {
+            io = io.with_fast_sync(fast_sync);
+        }
 
         // Create sealer.
         let sealer: Arc<dyn ConditionalSealer> = if self.should_verify_seal_criteria {
@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use zksync_config::configs::node_sync::FastSyncConfig;
+use zksync_dal::node::{MasterPool, PoolResource};
+use zksync_eth_client::{node::contracts::SettlementLayerContractsResource, EthInterface};
+use zksync_health_check::AppHealthCheck;
+use zksync_node_framework::{
+    service::StopReceiver,
+    task::{Task, TaskId, TaskKind},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+use zksync_shared_resources::contracts::L2ContractsResource;
+use zksync_types::L2_ASSET_ROUTER_ADDRESS;
+use zksync_web3_decl::{
+    client::{DynClient, L2},
+    node::SettlementLayerClient,
+};
+
+use crate::fast_sync::{FastSyncCompletion, FastSyncer};
+
+/// Wiring layer for [`FastSyncer`].
+///
+/// The produced [`FastSyncCompletion`] resource should be used by the components writing L2 blocks
+/// (`ExternalIO` and the consensus fetcher) to wait until fast sync is completed.
+#[derive(Debug)]
+pub struct FastSyncLayer {
+    config: FastSyncConfig,
+}
+
+impl FastSyncLayer {
+    pub fn new(config: FastSyncConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[derive(Debug, FromContext)]
+pub struct Input {
+    master_pool: PoolResource<MasterPool>,
+    main_node_client: Box<DynClient<L2>>,
+    gateway_client: SettlementLayerClient,
+    settlement_layer_contracts_resource: SettlementLayerContractsResource,
+    l2_contracts: L2ContractsResource,
+    #[context(default)]
+    app_health: Arc<AppHealthCheck>,
+}
+
+#[derive(Debug, IntoContext)]
+pub struct Output {
+    completion: FastSyncCompletion,
+    #[context(task)]
+    task: FastSyncer,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for FastSyncLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "fast_sync_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let pool = input.master_pool.get().await?;
+        let sl_client: Box<dyn EthInterface> = match input.gateway_client {
+            SettlementLayerClient::L1(client) => Box::new(client),
+            SettlementLayerClient::Gateway(client) => Box::new(client),
+        };
+
+        let l2_contracts = &input.l2_contracts.0;
+        let l2_legacy_shared_bridge_addr =
+            if l2_contracts.shared_bridge_addr == L2_ASSET_ROUTER_ADDRESS {
+                l2_contracts.legacy_shared_bridge_addr
+            } else {
+                Some(l2_contracts.shared_bridge_addr)
+            };
+
+        let task = FastSyncer::new(input.main_node_client, pool, &self.config)
+            .with_l2_legacy_shared_bridge_addr(l2_legacy_shared_bridge_addr)
+            .with_sl_data(
+                sl_client,
+                input
+                    .settlement_layer_contracts_resource
+                    .0
+                    .chain_contracts_config
+                    .diamond_proxy_addr,
+            )
+            .await?;
+
+        input
+            .app_health
+            .insert_component(task.health_check())
+            .map_err(WiringError::internal)?;
+
+        Ok(Output {
+            completion: task.completion(),
+            task,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for FastSyncer {
+    fn kind(&self) -> TaskKind {
+        // Fast sync exits once the node is close to the main node; this must not stop the node.
+        TaskKind::OneshotTask
+    }
+
+    fn id(&self) -> TaskId {
+        "fast_sync".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -1,18 +1,21 @@
 pub use self::{
     batch_transaction_fetcher::BatchStatusUpdaterLayer,
     data_availability_fetcher::DataAvailabilityFetcherLayer, external_io::ExternalIOLayer,
-    miniblock_precommit_fetcher::MiniblockPrecommitFetcherLayer,
+    fast_sync::FastSyncLayer, miniblock_precommit_fetcher::MiniblockPrecommitFetcherLayer,
     resources::ActionQueueSenderResource, sync_state_updater::SyncStateUpdaterLayer,
     transaction_finality_updater::BatchTransactionUpdaterLayer,
-    tree_data_fetcher::TreeDataFetcherLayer, validate_chain_ids::ValidateChainIdsLayer,
+    tree_data_fetcher::TreeDataFetcherLayer, upstream_monitor::UpstreamMonitorLayer,
+    validate_chain_ids::ValidateChainIdsLayer,
 };
 
 mod batch_transaction_fetcher;
 mod data_availability_fetcher;
 mod external_io;
+mod fast_sync;
 mod miniblock_precommit_fetcher;
 mod resources;
 mod sync_state_updater;
 mod transaction_finality_updater;
 mod tree_data_fetcher;
+mod upstream_monitor;
 mod validate_chain_ids;
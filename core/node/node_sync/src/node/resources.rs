@@ -1,6 +1,6 @@
 use zksync_node_framework::resource::{Resource, Unique};
 
-use crate::ActionQueueSender;
+use crate::{fast_sync::FastSyncCompletion, ActionQueueSender};
 
 /// A resource that provides [`ActionQueueSender`] to the service.
 /// This resource is unique, e.g. it's expected to be consumed by a single service.
@@ -18,3 +18,9 @@ impl From<ActionQueueSender> for ActionQueueSenderResource {
         Self(Unique::new(sender))
     }
 }
+
+impl Resource for FastSyncCompletion {
+    fn name() -> String {
+        "external_node/fast_sync_completion".into()
+    }
+}
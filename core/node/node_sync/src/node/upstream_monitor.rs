@@ -0,0 +1,89 @@
+use std::{sync::Arc, time::Duration};
+
+use zksync_health_check::AppHealthCheck;
+use zksync_node_framework::{
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+use zksync_types::{L1ChainId, L2ChainId};
+use zksync_web3_decl::client::{FailoverClient, L2};
+
+use crate::upstream_monitor::UpstreamMonitor;
+
+/// Wiring layer for [`UpstreamMonitor`]. Should be added only if the main node client is configured
+/// with additional upstreams.
+///
+/// ## Requests resources
+///
+/// - `FailoverClient<L2>`
+/// - `AppHealthCheck`
+///
+/// ## Adds tasks
+///
+/// - `UpstreamMonitor`
+#[derive(Debug)]
+pub struct UpstreamMonitorLayer {
+    l1_chain_id: L1ChainId,
+    l2_chain_id: L2ChainId,
+    poll_interval: Duration,
+}
+
+impl UpstreamMonitorLayer {
+    pub fn new(l1_chain_id: L1ChainId, l2_chain_id: L2ChainId, poll_interval: Duration) -> Self {
+        Self {
+            l1_chain_id,
+            l2_chain_id,
+            poll_interval,
+        }
+    }
+}
+
+#[derive(Debug, FromContext)]
+pub struct Input {
+    failover_client: FailoverClient<L2>,
+    #[context(default)]
+    app_health: Arc<AppHealthCheck>,
+}
+
+#[derive(Debug, IntoContext)]
+pub struct Output {
+    #[context(task)]
+    task: UpstreamMonitor,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for UpstreamMonitorLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "upstream_monitor_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let task = UpstreamMonitor::new(
+            input.failover_client,
+            self.l1_chain_id,
+            self.l2_chain_id,
+            self.poll_interval,
+        );
+        input
+            .app_health
+            .insert_component(task.health_check())
+            .map_err(WiringError::internal)?;
+        Ok(Output { task })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for UpstreamMonitor {
+    fn id(&self) -> TaskId {
+        "upstream_monitor".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use zksync_config::GenesisConfig;
 use zksync_eth_client::EnrichedClientError;
 use zksync_node_genesis::mock_genesis_config;
-use zksync_types::{api, Address, L2BlockNumber, ProtocolVersionId, H256};
+use zksync_types::{api, Address, L1BatchNumber, L2BlockNumber, ProtocolVersionId, H256};
 use zksync_web3_decl::error::EnrichedClientResult;
 
 use super::MainNodeClient;
@@ -14,6 +14,9 @@ pub struct MockMainNodeClient {
     pub block_number_offset: u32,
     pub protocol_versions: HashMap<u16, api::ProtocolVersionInfo>,
     pub system_contracts: HashMap<H256, Vec<u8>>,
+    /// State diffs served by [`MainNodeClient::fetch_l2_block_state_diff()`], indexed
+    /// in the same way as `l2_blocks`.
+    pub state_diffs: Vec<api::en::L2BlockStateDiff>,
 }
 
 #[async_trait::async_trait]
@@ -74,4 +77,23 @@ impl MainNodeClient for MockMainNodeClient {
     async fn fetch_genesis_config(&self) -> EnrichedClientResult<GenesisConfig> {
         Ok(mock_genesis_config())
     }
+
+    async fn fetch_l1_batch_number(&self) -> EnrichedClientResult<L1BatchNumber> {
+        let last_sealed_batch = self
+            .state_diffs
+            .iter()
+            .filter_map(|diff| Some(diff.l1_batch.as_ref()?.header.number))
+            .max();
+        Ok(last_sealed_batch.unwrap_or(L1BatchNumber(0)))
+    }
+
+    async fn fetch_l2_block_state_diff(
+        &self,
+        number: L2BlockNumber,
+    ) -> EnrichedClientResult<Option<api::en::L2BlockStateDiff>> {
+        let Some(block_index) = number.0.checked_sub(self.block_number_offset) else {
+            return Ok(None);
+        };
+        Ok(self.state_diffs.get(block_index as usize).cloned())
+    }
 }
@@ -26,7 +26,7 @@ use self::{
 use crate::tree_data_fetcher::provider::CombinedDataProvider;
 
 mod metrics;
-mod provider;
+pub(crate) mod provider;
 #[cfg(test)]
 mod tests;
 
@@ -45,7 +45,7 @@ impl From<DalError> for TreeDataFetcherError {
 }
 
 impl TreeDataFetcherError {
-    fn is_retriable(&self) -> bool {
+    pub(crate) fn is_retriable(&self) -> bool {
         match self {
             Self::Rpc(err) => err.is_retryable(),
             Self::Internal(_) => false,
@@ -53,7 +53,7 @@ impl TreeDataFetcherError {
     }
 }
 
-type TreeDataFetcherResult<T> = Result<T, TreeDataFetcherError>;
+pub(crate) type TreeDataFetcherResult<T> = Result<T, TreeDataFetcherError>;
 
 #[derive(Debug, Serialize)]
 #[serde(untagged)]
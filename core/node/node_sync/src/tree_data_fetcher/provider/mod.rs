@@ -22,7 +22,7 @@ use super::{
 mod tests;
 
 #[derive(Debug, thiserror::Error)]
-pub(super) enum MissingData {
+pub(crate) enum MissingData {
     /// The provider lacks a requested L1 batch.
     #[error("no requested L1 batch")]
     Batch,
@@ -33,11 +33,11 @@ pub(super) enum MissingData {
     PossibleReorg,
 }
 
-pub(super) type TreeDataProviderResult = TreeDataFetcherResult<Result<H256, MissingData>>;
+pub(crate) type TreeDataProviderResult = TreeDataFetcherResult<Result<H256, MissingData>>;
 
 /// External provider of tree data, such as main node (via JSON-RPC).
 #[async_trait]
-pub(super) trait TreeDataProvider: fmt::Debug + Send + Sync + 'static {
+pub(crate) trait TreeDataProvider: fmt::Debug + Send + Sync + 'static {
     /// Fetches a state root hash for the L1 batch with the specified number.
     /// The method receives a header of the last L2 block in the batch, which can be used to check L1 batch consistency etc.
     ///
@@ -105,7 +105,7 @@ struct PastL1BatchInfo {
 /// for the event using binary search, or uses an L1 block number of the `BlockCommit` event for the previously queried L1 batch
 /// (provided it's not too far behind the seal timestamp of the batch).
 #[derive(Debug)]
-pub(super) struct SLDataProvider {
+pub(crate) struct SLDataProvider {
     client: Box<dyn EthInterface>,
     chain_id: SLChainId,
     diamond_proxy_addr: Address,
@@ -304,7 +304,7 @@ impl TreeDataProvider for SLDataProvider {
 
 /// Data provider combining [`SLDataProvider`] with a fallback provider.
 #[derive(Debug)]
-pub(super) struct CombinedDataProvider {
+pub(crate) struct CombinedDataProvider {
     l1: Option<SLDataProvider>,
     // Generic to allow for tests.
     rpc: Box<dyn TreeDataProvider>,
@@ -0,0 +1,332 @@
+//! Monitoring of L2 upstreams the external node syncs from.
+
+use std::time::Duration;
+
+use futures::future;
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_types::{L1ChainId, L2ChainId, H256};
+use zksync_web3_decl::{
+    client::{DynClient, FailoverClient, UpstreamStatus, L2},
+    error::{ClientRpcContext, EnrichedClientError},
+    namespaces::{EnNamespaceClient, EthNamespaceClient},
+};
+
+use crate::{
+    metrics::UPSTREAM_METRICS,
+    validate_chain_ids_task::{check_l1_chain_id, check_l2_chain_id, NodeCheckError},
+};
+
+/// Genesis data that must be shared by all upstreams.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct GenesisData {
+    root_hash: H256,
+    commitment: H256,
+}
+
+#[derive(Debug, Serialize)]
+struct UpstreamsHealth {
+    active_upstream: usize,
+    upstreams: Vec<UpstreamStatus>,
+}
+
+impl From<UpstreamsHealth> for Health {
+    fn from(health: UpstreamsHealth) -> Self {
+        let status = if health.upstreams.contains(&UpstreamStatus::Invalid) {
+            HealthStatus::Affected
+        } else {
+            HealthStatus::Ready
+        };
+        Self::from(status).with_details(health)
+    }
+}
+
+/// Task validating upstreams of a [`FailoverClient`] and tracking their latest L2 blocks, so that the client
+/// can choose the upstream to send requests to.
+///
+/// Additional upstreams are validated by checking their L1 and L2 chain IDs (similarly to
+/// [`ValidateChainIdsTask`](crate::validate_chain_ids_task::ValidateChainIdsTask)) and comparing their genesis
+/// with the one returned by the main node. Upstreams failing validation are never used.
+#[derive(Debug)]
+pub struct UpstreamMonitor {
+    client: FailoverClient<L2>,
+    upstreams: Vec<Box<DynClient<L2>>>,
+    l1_chain_id: L1ChainId,
+    l2_chain_id: L2ChainId,
+    poll_interval: Duration,
+    main_node_genesis: Option<GenesisData>,
+    validated: Vec<bool>,
+    health_updater: HealthUpdater,
+}
+
+impl UpstreamMonitor {
+    pub fn new(
+        client: FailoverClient<L2>,
+        l1_chain_id: L1ChainId,
+        l2_chain_id: L2ChainId,
+        poll_interval: Duration,
+    ) -> Self {
+        let upstreams: Vec<_> = client
+            .upstreams()
+            .iter()
+            .map(|upstream| upstream.clone().for_component("upstream_monitor"))
+            .collect();
+        // The main node is trusted and doesn't need to be validated.
+        let validated = (0..upstreams.len()).map(|i| i == 0).collect();
+        Self {
+            client,
+            upstreams,
+            l1_chain_id,
+            l2_chain_id,
+            poll_interval,
+            main_node_genesis: None,
+            validated,
+            health_updater: ReactiveHealthCheck::new("upstream_monitor").1,
+        }
+    }
+
+    /// Returns a health check for this monitor.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    async fn fetch_genesis(client: &DynClient<L2>) -> Result<GenesisData, EnrichedClientError> {
+        let genesis = client
+            .genesis_config()
+            .rpc_context("genesis_config")
+            .await?;
+        Ok(GenesisData {
+            root_hash: genesis.genesis_root_hash,
+            commitment: genesis.genesis_commitment,
+        })
+    }
+
+    /// Validates an additional upstream. Returns `Ok(false)` if validation cannot be performed yet.
+    async fn validate_upstream(
+        &self,
+        index: usize,
+        main_node_genesis: Option<GenesisData>,
+    ) -> Result<bool, NodeCheckError> {
+        let Some(main_node_genesis) = main_node_genesis else {
+            return Ok(false);
+        };
+        let client = self.upstreams[index].as_ref();
+        let node_name = format!("upstream #{index}");
+        check_l1_chain_id(client, &node_name, self.l1_chain_id).await?;
+        check_l2_chain_id(client, &node_name, self.l2_chain_id).await?;
+
+        let genesis = Self::fetch_genesis(client).await?;
+        if genesis != main_node_genesis {
+            return Err(NodeCheckError::Mismatch(anyhow::anyhow!(
+                "Genesis of {node_name} ({genesis:?}) doesn't match the one of the main node ({main_node_genesis:?})"
+            )));
+        }
+        tracing::info!("Validated {node_name}");
+        Ok(true)
+    }
+
+    async fn check_upstream(
+        &self,
+        index: usize,
+        main_node_genesis: Option<GenesisData>,
+    ) -> UpstreamStatus {
+        if !self.validated[index] {
+            match self.validate_upstream(index, main_node_genesis).await {
+                Ok(true) => { /* validated */ }
+                Ok(false) => return UpstreamStatus::Unvalidated,
+                Err(NodeCheckError::Rpc(err)) => {
+                    tracing::warn!("Error validating upstream #{index}, will retry: {err}");
+                    return UpstreamStatus::Unvalidated;
+                }
+                Err(NodeCheckError::Mismatch(err)) => {
+                    tracing::error!("Upstream #{index} is invalid and will not be used: {err:#}");
+                    return UpstreamStatus::Invalid;
+                }
+            }
+        }
+
+        match self.upstreams[index]
+            .get_block_number()
+            .rpc_context("get_block_number")
+            .await
+        {
+            Ok(block_number) => UpstreamStatus::Healthy {
+                block_number: block_number.as_u64(),
+            },
+            Err(err) => {
+                tracing::warn!("Error getting latest L2 block from upstream #{index}: {err}");
+                UpstreamStatus::Failing
+            }
+        }
+    }
+
+    async fn poll_upstreams(&mut self) {
+        if self.main_node_genesis.is_none() {
+            match Self::fetch_genesis(self.upstreams[0].as_ref()).await {
+                Ok(genesis) => self.main_node_genesis = Some(genesis),
+                Err(err) => tracing::warn!("Error fetching genesis from main node: {err}"),
+            }
+        }
+
+        let current_statuses = self.client.statuses();
+        let this = &*self;
+        let checks = (0..this.upstreams.len())
+            .filter(|&i| current_statuses[i] != UpstreamStatus::Invalid)
+            .map(|i| async move { (i, this.check_upstream(i, this.main_node_genesis).await) });
+        let new_statuses = future::join_all(checks).await;
+
+        for (i, status) in new_statuses {
+            if matches!(status, UpstreamStatus::Healthy { .. }) {
+                self.validated[i] = true;
+            }
+            self.client.update_status(i, status);
+        }
+
+        let active_upstream = self.client.active_upstream();
+        let statuses = self.client.statuses();
+        UPSTREAM_METRICS.active.set(active_upstream as u64);
+        UPSTREAM_METRICS.healthy.set(
+            statuses
+                .iter()
+                .filter(|status| matches!(status, UpstreamStatus::Healthy { .. }))
+                .count(),
+        );
+        let health = UpstreamsHealth {
+            active_upstream,
+            upstreams: statuses,
+        };
+        self.health_updater.update(health.into());
+    }
+
+    /// Runs this monitor until a stop request is received.
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow_and_update() {
+            self.poll_upstreams().await;
+            if tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop request received, upstream monitor is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use zksync_health_check::CheckHealth;
+    use zksync_types::{
+        commitment::L1BatchCommitmentMode, protocol_version::ProtocolSemanticVersion, Address,
+        ProtocolVersionId, U64,
+    };
+    use zksync_web3_decl::{client::MockClient, types::GenesisConfigDto};
+
+    use super::*;
+
+    const L1_CHAIN_ID: L1ChainId = L1ChainId(9);
+
+    fn mock_genesis(root_hash: H256) -> GenesisConfigDto {
+        GenesisConfigDto {
+            genesis_root_hash: root_hash,
+            rollup_last_leaf_index: 26,
+            snark_wrapper_vk_hash: H256::repeat_byte(0x02),
+            fflonk_snark_wrapper_vk_hash: None,
+            fee_account: Address::zero(),
+            genesis_commitment: H256::repeat_byte(0x17),
+            bootloader_hash: H256::zero(),
+            default_aa_hash: H256::zero(),
+            evm_emulator_hash: None,
+            l1_chain_id: L1_CHAIN_ID,
+            protocol_version: ProtocolSemanticVersion {
+                minor: ProtocolVersionId::latest(),
+                patch: 0.into(),
+            },
+            l2_chain_id: L2ChainId::default(),
+            dummy_verifier: false,
+            l1_batch_commit_data_generator_mode: L1BatchCommitmentMode::Rollup,
+        }
+    }
+
+    fn mock_upstream(
+        l2_chain_id: L2ChainId,
+        genesis_root_hash: H256,
+        block_number: u64,
+    ) -> Box<DynClient<L2>> {
+        let client = MockClient::builder(L2::default())
+            .method("eth_chainId", move || Ok(U64::from(l2_chain_id.as_u64())))
+            .method("zks_L1ChainId", || Ok(U64::from(L1_CHAIN_ID.0)))
+            .method("en_genesisConfig", move || {
+                Ok(mock_genesis(genesis_root_hash))
+            })
+            .method("eth_blockNumber", move || Ok(U64::from(block_number)))
+            .build();
+        Box::new(client)
+    }
+
+    fn create_monitor(upstreams: Vec<Box<DynClient<L2>>>) -> UpstreamMonitor {
+        let client = FailoverClient::new(upstreams, 5);
+        UpstreamMonitor::new(
+            client,
+            L1_CHAIN_ID,
+            L2ChainId::default(),
+            Duration::from_millis(10),
+        )
+    }
+
+    #[tokio::test]
+    async fn validating_upstreams() {
+        let genesis_root_hash = H256::repeat_byte(1);
+        let mut monitor = create_monitor(vec![
+            mock_upstream(L2ChainId::default(), genesis_root_hash, 100),
+            mock_upstream(L2ChainId::from(271), genesis_root_hash, 100),
+            mock_upstream(L2ChainId::default(), H256::repeat_byte(2), 100),
+            mock_upstream(L2ChainId::default(), genesis_root_hash, 90),
+            mock_upstream(L2ChainId::default(), genesis_root_hash, 99),
+        ]);
+        monitor.poll_upstreams().await;
+
+        assert_eq!(
+            monitor.client.statuses(),
+            [
+                UpstreamStatus::Healthy { block_number: 100 },
+                UpstreamStatus::Invalid, // L2 chain ID mismatch
+                UpstreamStatus::Invalid, // genesis mismatch
+                UpstreamStatus::Healthy { block_number: 90 },
+                UpstreamStatus::Healthy { block_number: 99 },
+            ]
+        );
+        // Upstream #3 lags behind too much.
+        assert_eq!(monitor.client.active_upstream(), 4);
+        assert_eq!(monitor.validated, [true, false, false, true, true]);
+
+        let health = monitor.health_check().check_health().await;
+        assert_matches!(health.status(), HealthStatus::Affected);
+    }
+
+    #[tokio::test]
+    async fn upstreams_are_not_used_without_main_node_genesis() {
+        let main_node = MockClient::builder(L2::default())
+            .method("eth_blockNumber", || Ok(U64::from(100)))
+            .build();
+        let mut monitor = create_monitor(vec![
+            Box::new(main_node),
+            mock_upstream(L2ChainId::default(), H256::repeat_byte(1), 100),
+        ]);
+        monitor.poll_upstreams().await;
+
+        assert_eq!(
+            monitor.client.statuses(),
+            [
+                UpstreamStatus::Healthy { block_number: 100 },
+                UpstreamStatus::Unvalidated,
+            ]
+        );
+        assert_eq!(monitor.client.active_upstream(), 0);
+        let health = monitor.health_check().check_health().await;
+        assert_matches!(health.status(), HealthStatus::Ready);
+    }
+}
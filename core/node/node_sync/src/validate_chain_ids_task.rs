@@ -8,10 +8,61 @@ use zksync_eth_client::EthInterface;
 use zksync_types::{L1ChainId, L2ChainId, SLChainId};
 use zksync_web3_decl::{
     client::{DynClient, L1, L2},
-    error::ClientRpcContext,
+    error::{ClientRpcContext, EnrichedClientError},
     namespaces::{EthNamespaceClient, ZksNamespaceClient},
 };
 
+/// Error checking data returned by an L2 node (the main node or another node serving the same API).
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum NodeCheckError {
+    #[error("error fetching data from node")]
+    Rpc(#[from] EnrichedClientError),
+    #[error(transparent)]
+    Mismatch(anyhow::Error),
+}
+
+/// Checks the L1 chain ID returned by an L2 node once. `node_name` is used in errors and logs.
+pub(crate) async fn check_l1_chain_id(
+    client: &DynClient<L2>,
+    node_name: &str,
+    expected: L1ChainId,
+) -> Result<(), NodeCheckError> {
+    let chain_id = client.l1_chain_id().rpc_context("l1_chain_id").await?;
+    let chain_id = chain_id.as_u64();
+    if expected.0 != chain_id {
+        return Err(NodeCheckError::Mismatch(anyhow::anyhow!(
+            "Configured L1 chain ID doesn't match the one from {node_name}. \
+            Make sure your configuration is correct and you are corrected to the right {node_name}. \
+            {node_name} L1 chain ID: {chain_id}. Local config value: {expected}"
+        )));
+    }
+    tracing::info!("Checked that L1 chain ID {chain_id} is returned by {node_name} client");
+    Ok(())
+}
+
+/// Checks the L2 chain ID returned by an L2 node once. `node_name` is used in errors and logs.
+pub(crate) async fn check_l2_chain_id(
+    client: &DynClient<L2>,
+    node_name: &str,
+    expected: L2ChainId,
+) -> Result<(), NodeCheckError> {
+    let chain_id = client.chain_id().rpc_context("chain_id").await?;
+    let chain_id = L2ChainId::try_from(chain_id.as_u64()).map_err(|err| {
+        NodeCheckError::Mismatch(anyhow::anyhow!(
+            "invalid chain ID supplied by {node_name}: {err}"
+        ))
+    })?;
+    if expected != chain_id {
+        return Err(NodeCheckError::Mismatch(anyhow::anyhow!(
+            "Configured L2 chain ID doesn't match the one from {node_name}. \
+            Make sure your configuration is correct and you are corrected to the right {node_name}. \
+            {node_name} L2 chain ID: {chain_id:?}. Local config value: {expected:?}"
+        )));
+    }
+    tracing::info!("Checked that L2 chain ID {chain_id:?} is returned by {node_name} client");
+    Ok(())
+}
+
 /// Task that validates chain IDs using main node and Ethereum clients.
 #[derive(Debug)]
 pub struct ValidateChainIdsTask {
@@ -64,35 +115,20 @@ impl ValidateChainIdsTask {
         expected: L1ChainId,
     ) -> anyhow::Result<()> {
         loop {
-            match main_node_client
-                .l1_chain_id()
-                .rpc_context("l1_chain_id")
-                .await
-            {
-                Ok(chain_id) => {
-                    let chain_id = chain_id.as_u64();
-                    anyhow::ensure!(
-                        expected.0 == chain_id,
-                        "Configured L1 chain ID doesn't match the one from main node. \
-                        Make sure your configuration is correct and you are corrected to the right main node. \
-                        Main node L1 chain ID: {chain_id}. Local config value: {expected}"
-                    );
-                    tracing::info!(
-                        "Checked that L1 chain ID {chain_id} is returned by main node client"
-                    );
-                    return Ok(());
-                }
-                Err(err) if err.is_retryable() => {
+            match check_l1_chain_id(main_node_client.as_ref(), "main node", expected).await {
+                Ok(()) => return Ok(()),
+                Err(NodeCheckError::Rpc(err)) if err.is_retryable() => {
                     tracing::warn!(
                         "Retriable error getting L1 chain ID from main node client, will retry in {:?}: {err}",
                         Self::BACKOFF_INTERVAL
                     );
                     tokio::time::sleep(Self::BACKOFF_INTERVAL).await;
                 }
-                Err(err) => {
+                Err(NodeCheckError::Rpc(err)) => {
                     tracing::error!("Error getting L1 chain ID from main node client: {err}");
                     return Err(err.into());
                 }
+                Err(NodeCheckError::Mismatch(err)) => return Err(err),
             }
         }
     }
@@ -102,33 +138,20 @@ impl ValidateChainIdsTask {
         expected: L2ChainId,
     ) -> anyhow::Result<()> {
         loop {
-            match main_node_client.chain_id().rpc_context("chain_id").await {
-                Ok(chain_id) => {
-                    let chain_id = L2ChainId::try_from(chain_id.as_u64()).map_err(|err| {
-                        anyhow::anyhow!("invalid chain ID supplied by main node: {err}")
-                    })?;
-                    anyhow::ensure!(
-                        expected == chain_id,
-                        "Configured L2 chain ID doesn't match the one from main node. \
-                        Make sure your configuration is correct and you are corrected to the right main node. \
-                        Main node L2 chain ID: {chain_id:?}. Local config value: {expected:?}"
-                    );
-                    tracing::info!(
-                        "Checked that L2 chain ID {chain_id:?} is returned by main node client"
-                    );
-                    return Ok(());
-                }
-                Err(err) if err.is_retryable() => {
+            match check_l2_chain_id(main_node_client.as_ref(), "main node", expected).await {
+                Ok(()) => return Ok(()),
+                Err(NodeCheckError::Rpc(err)) if err.is_retryable() => {
                     tracing::warn!(
                         "Transient error getting L2 chain ID from main node client, will retry in {:?}: {err}",
                         Self::BACKOFF_INTERVAL
                     );
                     tokio::time::sleep(Self::BACKOFF_INTERVAL).await;
                 }
-                Err(err) => {
+                Err(NodeCheckError::Rpc(err)) => {
                     tracing::error!("Error getting L2 chain ID from main node client: {err}");
                     return Err(err.into());
                 }
+                Err(NodeCheckError::Mismatch(err)) => return Err(err),
             }
         }
     }
@@ -0,0 +1,71 @@
+//! Backpressure applied to the state keeper if the DA layer lags behind.
+
+use std::time::Instant;
+
+use zksync_config::configs::chain::DaBackpressureConfig;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_shared_resources::da_backpressure::{DaBackpressure, DaBackpressureState};
+
+use crate::{metrics::KEEPER_METRICS, utils::millis_since_epoch};
+
+/// Gate that stops the state keeper from including new transactions if there are too many sealed L1 batches
+/// awaiting inclusion in the DA layer. The DA backlog is checked once per `check_interval` to reduce the number
+/// of DB queries.
+#[derive(Debug)]
+pub(crate) struct DaBackpressureGate {
+    config: DaBackpressureConfig,
+    pool: ConnectionPool<Core>,
+    backpressure: DaBackpressure,
+    last_checked_at: Option<Instant>,
+    is_engaged: bool,
+}
+
+impl DaBackpressureGate {
+    pub fn new(
+        config: DaBackpressureConfig,
+        pool: ConnectionPool<Core>,
+        backpressure: DaBackpressure,
+    ) -> Self {
+        Self {
+            config,
+            pool,
+            backpressure,
+            last_checked_at: None,
+            is_engaged: false,
+        }
+    }
+
+    /// Checks whether the state keeper should hold off including new transactions.
+    pub async fn is_engaged(&mut self) -> anyhow::Result<bool> {
+        if self
+            .last_checked_at
+            .is_some_and(|last_checked_at| last_checked_at.elapsed() < self.config.check_interval)
+        {
+            return Ok(self.is_engaged);
+        }
+
+        let mut conn = self.pool.connection_tagged("da_backpressure").await?;
+        let backlog = conn.data_availability_dal().get_da_backlog().await?;
+        drop(conn);
+        self.last_checked_at = Some(Instant::now());
+
+        let state = DaBackpressureState::new(&self.config, &backlog, millis_since_epoch());
+        KEEPER_METRICS
+            .da_pending_l1_batches
+            .set(state.pending_l1_batches.into());
+        let is_engaged = state.reason.is_some();
+        if is_engaged != self.is_engaged {
+            if let Some(reason) = &state.reason {
+                tracing::warn!("Engaging DA backpressure: {reason}");
+            } else {
+                tracing::info!("Releasing DA backpressure; {backlog:?}");
+            }
+        }
+        KEEPER_METRICS
+            .da_backpressure_engaged
+            .set(is_engaged.into());
+        self.is_engaged = is_engaged;
+        self.backpressure.update(state);
+        Ok(is_engaged)
+    }
+}
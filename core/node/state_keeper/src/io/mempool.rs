@@ -7,7 +7,7 @@ use std::{
 
 use anyhow::Context as _;
 use async_trait::async_trait;
-use zksync_config::configs::chain::StateKeeperConfig;
+use zksync_config::configs::chain::{DaBackpressureConfig, StateKeeperConfig};
 use zksync_contracts::BaseSystemContracts;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_eth_client::web3_decl::node::SettlementModeResource;
@@ -17,6 +17,7 @@ use zksync_multivm::{
     utils::{derive_base_fee_and_gas_per_pubdata, get_bootloader_max_interop_roots_in_batch},
 };
 use zksync_node_fee_model::BatchFeeModelInputProvider;
+use zksync_shared_resources::da_backpressure::DaBackpressure;
 use zksync_types::{
     block::UnsealedL1BatchHeader,
     commitment::{L2DACommitmentScheme, L2PubdataValidator, PubdataParams, PubdataType},
@@ -33,6 +34,7 @@ use zksync_vm_executor::storage::{get_base_system_contracts_by_version_id, L1Bat
 use crate::{
     io::{
         common::{load_pending_batch, poll_iters, IoCursor},
+        da_backpressure::DaBackpressureGate,
         seal_logic::l2_block_seal_subtasks::L2BlockSealProcess,
         L1BatchParams, L2BlockParams, PendingBatchData, StateKeeperIO,
     },
@@ -74,6 +76,7 @@ pub struct MempoolIO {
     pubdata_limit: u64,
     last_batch_protocol_version: Option<ProtocolVersionId>,
     settlement_mode: SettlementModeResource,
+    da_backpressure: Option<DaBackpressureGate>,
 }
 
 #[async_trait]
@@ -272,6 +275,11 @@ impl StateKeeperIO for MempoolIO {
     ) -> anyhow::Result<Option<Transaction>> {
         let started_at = Instant::now();
         while started_at.elapsed() <= max_wait {
+            if self.is_da_backpressure_engaged().await? {
+                tokio::time::sleep(self.delay_interval).await;
+                continue;
+            }
+
             let get_latency = KEEPER_METRICS.get_tx_from_mempool.start();
             let maybe_tx = self.mempool.next_transaction(&self.filter);
             get_latency.observe();
@@ -534,9 +542,32 @@ impl MempoolIO {
             pubdata_limit: config.seal_criteria.max_pubdata_per_batch.0,
             last_batch_protocol_version: None,
             settlement_mode,
+            da_backpressure: None,
         })
     }
 
+    /// Enables DA backpressure: no new transactions will be included while too many L1 batches
+    /// await inclusion in the DA layer. The current backpressure state is published to the provided handle.
+    pub fn with_da_backpressure(
+        mut self,
+        config: DaBackpressureConfig,
+        backpressure: DaBackpressure,
+    ) -> Self {
+        self.da_backpressure = Some(DaBackpressureGate::new(
+            config,
+            self.pool.clone(),
+            backpressure,
+        ));
+        self
+    }
+
+    async fn is_da_backpressure_engaged(&mut self) -> anyhow::Result<bool> {
+        match &mut self.da_backpressure {
+            Some(gate) => gate.is_engaged().await,
+            None => Ok(false),
+        }
+    }
+
     fn pubdata_params(&self, protocol_version: ProtocolVersionId) -> anyhow::Result<PubdataParams> {
         // Starting from v31 we have to use commitment schema instead of address
         let pubdata_params = match (
@@ -684,7 +715,11 @@ impl MempoolIO {
                 .context("failed creating L2 transaction filter")?;
 
             // We do not populate mempool with upgrade tx so it should be checked separately.
-            if !batch_with_upgrade_tx && !self.mempool.has_next(&self.filter) {
+            // New batches without an upgrade tx are not opened while DA backpressure is engaged.
+            if !batch_with_upgrade_tx
+                && (self.is_da_backpressure_engaged().await?
+                    || !self.mempool.has_next(&self.filter))
+            {
                 tokio::time::sleep(self.delay_interval).await;
                 continue;
             }
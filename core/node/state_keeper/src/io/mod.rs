@@ -18,6 +18,7 @@ pub use self::{
 use super::seal_criteria::{IoSealCriteria, UnexecutableReason};
 
 pub mod common;
+pub(crate) mod da_backpressure;
 pub(crate) mod mempool;
 mod output_handler;
 mod persistence;
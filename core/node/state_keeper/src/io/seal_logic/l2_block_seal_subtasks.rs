@@ -16,7 +16,8 @@ use crate::{
     updates::L2BlockSealCommand,
 };
 
-fn extract_added_tokens(
+/// Extracts information about tokens deployed by the specified token deployer from the VM events.
+pub fn extract_added_tokens(
     l2_token_deployer_addr: Address,
     all_generated_events: &[VmEvent],
 ) -> Vec<TokenInfo> {
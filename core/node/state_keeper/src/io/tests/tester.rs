@@ -86,6 +86,7 @@ impl Tester {
             num_samples_for_blob_base_fee_estimate: 10,
             internal_pubdata_pricing_multiplier: 1.0,
             max_blob_base_fee: u64::MAX,
+            fee_sources: None,
         };
 
         let pool = ConnectionPool::<Core>::test_pool().await;
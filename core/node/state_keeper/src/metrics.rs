@@ -100,6 +100,10 @@ pub struct StateKeeperMetrics {
     /// The time it takes to wait for new L2 block parameters
     #[metrics(buckets = Buckets::LATENCIES)]
     pub wait_for_l2_block_params: Histogram<Duration>,
+    /// Number of sealed L1 batches awaiting inclusion in the DA layer.
+    pub da_pending_l1_batches: Gauge<u64>,
+    /// Whether DA backpressure is currently applied (1) or not (0).
+    pub da_backpressure_engaged: Gauge<u64>,
 }
 
 fn vm_revert_reason_as_metric_label(reason: &VmRevertReason) -> &'static str {
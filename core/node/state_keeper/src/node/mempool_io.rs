@@ -7,6 +7,7 @@ use zksync_config::configs::{
 };
 use zksync_dal::node::{MasterPool, PoolResource};
 use zksync_eth_client::web3_decl::node::SettlementModeResource;
+use zksync_health_check::AppHealthCheck;
 use zksync_node_fee_model::node::SequencerFeeInputResource;
 use zksync_node_framework::{
     service::StopReceiver,
@@ -14,7 +15,10 @@ use zksync_node_framework::{
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
 };
-use zksync_shared_resources::contracts::{L2ContractsResource, ZkChainOnChainConfigResource};
+use zksync_shared_resources::{
+    contracts::{L2ContractsResource, ZkChainOnChainConfigResource},
+    da_backpressure::DaBackpressure,
+};
 use zksync_types::{commitment::PubdataType, L2ChainId};
 use zksync_vm_executor::node::ApiTransactionFilter;
 
@@ -34,6 +38,7 @@ use crate::{
 ///
 /// - `StateKeeperIOResource`
 /// - `ConditionalSealerResource`
+/// - `DaBackpressure` (only if DA backpressure is enabled in the state keeper config)
 ///
 /// ## Adds tasks
 ///
@@ -49,6 +54,7 @@ pub struct MempoolIOLayer {
 
 #[derive(Debug, FromContext)]
 pub struct Input {
+    app_health: Arc<AppHealthCheck>,
     fee_input: SequencerFeeInputResource,
     master_pool: PoolResource<MasterPool>,
     l2_contracts: L2ContractsResource,
@@ -61,6 +67,7 @@ pub struct Output {
     state_keeper_io: StateKeeperIOResource,
     conditional_sealer: Arc<dyn ConditionalSealer>,
     api_transaction_filter: ApiTransactionFilter,
+    da_backpressure: Option<DaBackpressure>,
     #[context(task)]
     mempool_fetcher: MempoolFetcher,
 }
@@ -140,7 +147,7 @@ impl WiringLayer for MempoolIOLayer {
             .await
             .context("Get master pool")?;
 
-        let io = MempoolIO::new(
+        let mut io = MempoolIO::new(
             mempool_guard,
             batch_fee_input_provider,
             mempool_db_pool,
@@ -154,6 +161,19 @@ impl WiringLayer for MempoolIOLayer {
             input.settlement_mode,
         )?;
 
+        let da_backpressure_config = self.state_keeper_config.da_backpressure.clone();
+        let da_backpressure = if da_backpressure_config.is_enabled() {
+            let da_backpressure = DaBackpressure::default();
+            input
+                .app_health
+                .insert_custom_component(Arc::new(da_backpressure.clone()))
+                .map_err(WiringError::internal)?;
+            io = io.with_da_backpressure(da_backpressure_config, da_backpressure.clone());
+            Some(da_backpressure)
+        } else {
+            None
+        };
+
         // Create sealer.
         let sealer = Arc::new(SequencerSealer::new(self.state_keeper_config.seal_criteria));
 
@@ -161,6 +181,7 @@ impl WiringLayer for MempoolIOLayer {
             state_keeper_io: io.into(),
             conditional_sealer: sealer.clone(),
             api_transaction_filter: ApiTransactionFilter(sealer),
+            da_backpressure,
             mempool_fetcher,
         })
     }
@@ -1,13 +1,15 @@
 use std::{path::PathBuf, sync::Arc};
 
 use anyhow::Context;
+use zksync_config::configs::database::RocksdbCheckpointsConfig;
 use zksync_dal::node::{MasterPool, PoolResource, ReplicaPool};
 use zksync_health_check::AppHealthCheck;
 use zksync_node_framework::{
     service::ShutdownHook, task::TaskKind, FromContext, IntoContext, StopReceiver, Task, TaskId,
     WiringError, WiringLayer,
 };
-use zksync_state::{AsyncCatchupTask, RocksdbStorageOptions};
+use zksync_object_store::{ObjectStoreFactory, RocksdbCheckpointKind, RocksdbCheckpointStore};
+use zksync_state::{AsyncCatchupTask, CheckpointTask, RocksdbStorageOptions};
 use zksync_storage::RocksDB;
 use zksync_types::try_stoppable;
 use zksync_vm_executor::whitelist::{DeploymentTxFilter, SharedAllowList};
@@ -20,6 +22,7 @@ use crate::{seal_criteria::ConditionalSealer, AsyncRocksdbCache, StateKeeperBuil
 pub struct StateKeeperLayer {
     state_keeper_db_path: PathBuf,
     rocksdb_options: RocksdbStorageOptions,
+    checkpoints_config: Option<RocksdbCheckpointsConfig>,
 }
 
 #[derive(Debug, FromContext)]
@@ -41,6 +44,8 @@ pub struct Output {
     state_keeper: StateKeeperTask,
     #[context(task)]
     rocksdb_catchup: AsyncCatchupTaskWrapper,
+    #[context(task)]
+    rocksdb_checkpoints: Option<CheckpointTaskWrapper>,
     rocksdb_termination_hook: ShutdownHook,
 }
 
@@ -49,8 +54,16 @@ impl StateKeeperLayer {
         Self {
             state_keeper_db_path,
             rocksdb_options,
+            checkpoints_config: None,
         }
     }
+
+    /// Enables creating state keeper cache checkpoints and / or restoring the cache from them.
+    #[must_use]
+    pub fn with_checkpoints(mut self, config: RocksdbCheckpointsConfig) -> Self {
+        self.checkpoints_config = Some(config);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -96,6 +109,30 @@ impl WiringLayer for StateKeeperLayer {
             .await?;
         rocksdb_catchup = rocksdb_catchup.with_recovery_pool(recovery_pool);
 
+        let mut rocksdb_checkpoints = None;
+        if let Some(config) = self.checkpoints_config {
+            if let Some(object_store_config) = config.object_store {
+                let object_store = ObjectStoreFactory::new(object_store_config)
+                    .create_store()
+                    .await?;
+                let store = RocksdbCheckpointStore::new(
+                    object_store,
+                    RocksdbCheckpointKind::StateKeeperCache,
+                );
+                if let Some(interval) = config.state_keeper_cache_interval {
+                    rocksdb_checkpoints =
+                        Some(CheckpointTaskWrapper(storage_factory.checkpoint_task(
+                            store.clone(),
+                            interval,
+                            config.retained_count.get(),
+                        )));
+                }
+                if config.restore_on_init {
+                    rocksdb_catchup = rocksdb_catchup.with_checkpoint_store(store);
+                }
+            }
+        }
+
         let state_keeper_builder = StateKeeperBuilder::new(
             io,
             batch_executor_base,
@@ -122,6 +159,7 @@ impl WiringLayer for StateKeeperLayer {
         Ok(Output {
             state_keeper,
             rocksdb_catchup: AsyncCatchupTaskWrapper(rocksdb_catchup),
+            rocksdb_checkpoints,
             rocksdb_termination_hook,
         })
     }
@@ -161,3 +199,17 @@ impl Task for AsyncCatchupTaskWrapper {
         self.0.run(stop_receiver.0).await
     }
 }
+
+#[derive(Debug)]
+struct CheckpointTaskWrapper(CheckpointTask);
+
+#[async_trait::async_trait]
+impl Task for CheckpointTaskWrapper {
+    fn id(&self) -> TaskId {
+        "state_keeper/rocksdb_checkpoint_task".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.0.run(stop_receiver.0).await
+    }
+}
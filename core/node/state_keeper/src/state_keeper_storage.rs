@@ -1,11 +1,13 @@
-use std::{fmt::Debug, path::PathBuf};
+use std::{fmt::Debug, num::NonZeroU32, path::PathBuf};
 
 use anyhow::Context;
 use async_trait::async_trait;
 use tokio::sync::watch;
 use zksync_dal::{ConnectionPool, Core};
+use zksync_object_store::RocksdbCheckpointStore;
 use zksync_state::{
-    AsyncCatchupTask, OwnedStorage, ReadStorageFactory, RocksdbCell, RocksdbStorageOptions,
+    AsyncCatchupTask, CheckpointTask, OwnedStorage, ReadStorageFactory, RocksdbCell,
+    RocksdbStorageOptions,
 };
 use zksync_types::{L1BatchNumber, OrStopped, StopContext};
 
@@ -34,6 +36,17 @@ impl AsyncRocksdbCache {
             task.with_db_options(state_keeper_db_options),
         )
     }
+
+    /// Creates a task periodically creating checkpoints of the RocksDB cache. See [`RocksdbCell::checkpoint_task()`].
+    pub fn checkpoint_task(
+        &self,
+        store: RocksdbCheckpointStore,
+        interval: NonZeroU32,
+        retained_count: usize,
+    ) -> CheckpointTask {
+        self.rocksdb_cell
+            .checkpoint_task(store, interval, retained_count)
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,146 @@
+//! Fetching pubdata published in EIP-4844 blobs from a beacon node.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use serde::Deserialize;
+use tokio::sync::OnceCell;
+
+/// Number of field elements in an EIP-4844 blob.
+const FIELD_ELEMENTS_PER_BLOB: usize = 4_096;
+/// Size of a blob field element.
+const BYTES_PER_FIELD_ELEMENT: usize = 32;
+/// ZKsync packs 31 bytes of pubdata into each field element (the most significant byte is zero
+/// so that the element is always less than the BLS12-381 scalar field modulus).
+const PUBDATA_BYTES_PER_FIELD_ELEMENT: usize = 31;
+/// Number of pubdata bytes in a single blob; corresponds to `ZK_SYNC_BYTES_PER_BLOB` used by the commitment logic.
+pub(crate) const PUBDATA_BYTES_PER_BLOB: usize =
+    FIELD_ELEMENTS_PER_BLOB * PUBDATA_BYTES_PER_FIELD_ELEMENT;
+
+/// Extracts pubdata from a blob, i.e. reverses packing pubdata into field elements. The returned pubdata
+/// is padded with zeros to [`PUBDATA_BYTES_PER_BLOB`].
+pub(crate) fn blob_to_pubdata(blob: &[u8]) -> anyhow::Result<Vec<u8>> {
+    anyhow::ensure!(
+        blob.len() == FIELD_ELEMENTS_PER_BLOB * BYTES_PER_FIELD_ELEMENT,
+        "unexpected blob size: {}",
+        blob.len()
+    );
+    let mut pubdata = Vec::with_capacity(PUBDATA_BYTES_PER_BLOB);
+    for (i, element) in blob.chunks(BYTES_PER_FIELD_ELEMENT).enumerate() {
+        anyhow::ensure!(
+            element[0] == 0,
+            "field element #{i} has non-zero most significant byte"
+        );
+        pubdata.extend_from_slice(&element[1..]);
+    }
+    Ok(pubdata)
+}
+
+#[derive(Debug, Deserialize)]
+struct BeaconResponse<T> {
+    data: T,
+}
+
+#[derive(Debug, Deserialize)]
+struct Genesis {
+    genesis_time: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlobSidecar {
+    blob: String,
+}
+
+/// Client of the beacon node API used to fetch blobs. Blobs are pruned by beacon nodes after
+/// ~18 days, so reconstructing old L1 batches requires an archival beacon node.
+#[derive(Debug)]
+pub(crate) struct BeaconClient {
+    client: reqwest::Client,
+    url: String,
+    seconds_per_slot: u64,
+    genesis_time: OnceCell<u64>,
+}
+
+impl BeaconClient {
+    pub fn new(url: String, seconds_per_slot: u64) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(60))
+            .build()
+            .context("failed building HTTP client")?;
+        Ok(Self {
+            client,
+            url: url.trim_end_matches('/').to_owned(),
+            seconds_per_slot,
+            genesis_time: OnceCell::new(),
+        })
+    }
+
+    async fn get<T: for<'de> Deserialize<'de>>(&self, path: &str) -> anyhow::Result<T> {
+        let url = format!("{}{path}", self.url);
+        let response: BeaconResponse<T> = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .with_context(|| format!("failed sending request to {url}"))?
+            .error_for_status()?
+            .json()
+            .await
+            .with_context(|| format!("failed parsing response from {url}"))?;
+        Ok(response.data)
+    }
+
+    async fn slot(&self, l1_block_timestamp: u64) -> anyhow::Result<u64> {
+        let genesis_time = *self
+            .genesis_time
+            .get_or_try_init(|| async {
+                let genesis: Genesis = self.get("/eth/v1/beacon/genesis").await?;
+                genesis
+                    .genesis_time
+                    .parse::<u64>()
+                    .context("invalid genesis time")
+            })
+            .await?;
+        let elapsed = l1_block_timestamp
+            .checked_sub(genesis_time)
+            .context("L1 block precedes beacon chain genesis")?;
+        Ok(elapsed / self.seconds_per_slot)
+    }
+
+    /// Returns pubdata from all blobs included in the L1 block with the specified timestamp.
+    pub async fn blobs_pubdata(&self, l1_block_timestamp: u64) -> anyhow::Result<Vec<Vec<u8>>> {
+        let slot = self.slot(l1_block_timestamp).await?;
+        let sidecars: Vec<BlobSidecar> = self
+            .get(&format!("/eth/v1/beacon/blob_sidecars/{slot}"))
+            .await?;
+        sidecars
+            .into_iter()
+            .map(|sidecar| {
+                let blob = sidecar.blob.strip_prefix("0x").unwrap_or(&sidecar.blob);
+                let blob = hex::decode(blob).context("blob is not hex-encoded")?;
+                blob_to_pubdata(&blob)
+            })
+            .collect::<anyhow::Result<_>>()
+            .with_context(|| format!("invalid blobs at slot {slot}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracting_pubdata_from_blob() {
+        let pubdata: Vec<u8> = (0..PUBDATA_BYTES_PER_BLOB).map(|i| i as u8).collect();
+        let blob: Vec<u8> = pubdata
+            .chunks(PUBDATA_BYTES_PER_FIELD_ELEMENT)
+            .flat_map(|chunk| [0].into_iter().chain(chunk.iter().copied()))
+            .collect();
+        assert_eq!(blob_to_pubdata(&blob).unwrap(), pubdata);
+
+        blob_to_pubdata(&blob[1..]).unwrap_err();
+        let mut blob = blob;
+        blob[32] = 1;
+        blob_to_pubdata(&blob).unwrap_err();
+    }
+}
@@ -0,0 +1,186 @@
+//! Parsing of L1 batch commitments from commit transactions.
+
+use anyhow::Context as _;
+use zksync_l1_contract_interface::i_executor::structures::{
+    CommitBatchInfo, EncodingVersion, StoredBatchInfo,
+};
+use zksync_types::{
+    ethabi::{self, ParamType, Token},
+    L1BatchNumber, H256,
+};
+
+/// L1 batch data published in a commit transaction that is required to reconstruct the batch state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommittedBatch {
+    pub number: L1BatchNumber,
+    pub timestamp: u64,
+    /// 1-based index of the next leaf in the Merkle tree after applying the batch.
+    pub index_repeated_storage_changes: u64,
+    /// Root hash of the Merkle tree after applying the batch.
+    pub new_state_root: H256,
+    /// Operator-provided DA input specifying how pubdata was published.
+    pub operator_da_input: Vec<u8>,
+}
+
+impl CommittedBatch {
+    fn from_token(token: Token) -> anyhow::Result<Self> {
+        let tokens = token.into_tuple().context("not a tuple")?;
+        let [Token::Uint(number), Token::Uint(timestamp), Token::Uint(index_repeated_storage_changes), Token::FixedBytes(new_state_root), _, _, _, _, _, Token::Bytes(operator_da_input)]: [Token; 10] =
+            tokens.try_into().ok().context("bad length")?
+        else {
+            anyhow::bail!("bad format");
+        };
+        Ok(Self {
+            number: L1BatchNumber(
+                number
+                    .try_into()
+                    .ok()
+                    .context("overflow")
+                    .context("batch_number")?,
+            ),
+            timestamp: timestamp
+                .try_into()
+                .ok()
+                .context("overflow")
+                .context("timestamp")?,
+            index_repeated_storage_changes: index_repeated_storage_changes
+                .try_into()
+                .ok()
+                .context("overflow")
+                .context("index_repeated_storage_changes")?,
+            new_state_root: H256::from_slice(&new_state_root),
+            operator_da_input,
+        })
+    }
+}
+
+/// Parses the calldata of a commit transaction. Only post-gateway commit functions (with the commit data encoded
+/// as `version || abi.encode(StoredBatchInfo, CommitBatchInfo[])`) are supported.
+pub(crate) fn parse_commit_calldata(
+    calldata: &[u8],
+    commit_functions: &[&ethabi::Function],
+) -> anyhow::Result<Vec<CommittedBatch>> {
+    anyhow::ensure!(calldata.len() >= 4, "calldata is too short");
+    let (selector, input) = calldata.split_at(4);
+    let function = commit_functions
+        .iter()
+        .find(|function| function.short_signature() == selector)
+        .with_context(|| {
+            format!(
+                "unsupported commit function selector: 0x{}",
+                hex::encode(selector)
+            )
+        })?;
+    let mut tokens = function
+        .decode_input(input)
+        .context("failed decoding commit function input")?;
+    let commit_data = tokens
+        .pop()
+        .and_then(Token::into_bytes)
+        .context("unexpected commit function signature: last token is not bytes")?;
+
+    let (&version, encoded_data) = commit_data.split_first().context("commit data is empty")?;
+    let stored_batch_schema = if version == EncodingVersion::PreInterop.value() {
+        StoredBatchInfo::schema_pre_interop()
+    } else if version == EncodingVersion::InteropSupported.value() {
+        StoredBatchInfo::schema_post_interop()
+    } else {
+        anyhow::bail!("unsupported commit data encoding version: {version}");
+    };
+    let mut decoded = ethabi::decode(
+        &[
+            stored_batch_schema,
+            ParamType::Array(Box::new(CommitBatchInfo::post_gateway_schema())),
+        ],
+        encoded_data,
+    )
+    .context("failed decoding commit data")?;
+    let batches = decoded
+        .pop()
+        .and_then(Token::into_array)
+        .context("unexpected commit data format")?;
+    batches
+        .into_iter()
+        .map(|token| CommittedBatch::from_token(token).context("invalid `CommitBatchInfo`"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_contracts::POST_V26_GATEWAY_COMMIT_FUNCTION;
+    use zksync_types::{ProtocolVersionId, U256};
+
+    use super::*;
+
+    fn commit_batch_token(batch: &CommittedBatch) -> Token {
+        Token::Tuple(vec![
+            Token::Uint(batch.number.0.into()),
+            Token::Uint(batch.timestamp.into()),
+            Token::Uint(batch.index_repeated_storage_changes.into()),
+            Token::FixedBytes(batch.new_state_root.as_bytes().to_vec()),
+            Token::Uint(U256::zero()),
+            Token::FixedBytes(vec![0; 32]),
+            Token::FixedBytes(vec![0; 32]),
+            Token::FixedBytes(vec![0; 32]),
+            Token::Bytes(vec![]),
+            Token::Bytes(batch.operator_da_input.clone()),
+        ])
+    }
+
+    #[test]
+    fn parsing_commit_calldata() {
+        let batches = vec![
+            CommittedBatch {
+                number: L1BatchNumber(5),
+                timestamp: 1_000,
+                index_repeated_storage_changes: 100,
+                new_state_root: H256::repeat_byte(1),
+                operator_da_input: vec![1; 65],
+            },
+            CommittedBatch {
+                number: L1BatchNumber(6),
+                timestamp: 1_001,
+                index_repeated_storage_changes: 110,
+                new_state_root: H256::repeat_byte(2),
+                operator_da_input: vec![2; 32],
+            },
+        ];
+        let stored_batch = StoredBatchInfo {
+            batch_number: 4,
+            batch_hash: H256::repeat_byte(3),
+            index_repeated_storage_changes: 90,
+            number_of_layer1_txs: U256::zero(),
+            priority_operations_hash: H256::zero(),
+            dependency_roots_rolling_hash: H256::zero(),
+            l2_logs_tree_root: H256::zero(),
+            timestamp: 999.into(),
+            commitment: H256::zero(),
+        };
+        let function = &*POST_V26_GATEWAY_COMMIT_FUNCTION;
+
+        let encoded_data = ethabi::encode(&[
+            stored_batch.into_token_with_protocol_version(ProtocolVersionId::Version27),
+            Token::Array(batches.iter().map(commit_batch_token).collect()),
+        ]);
+        let commit_data = [
+            &[EncodingVersion::PreInterop.value()],
+            encoded_data.as_slice(),
+        ]
+        .concat();
+        let calldata = function
+            .encode_input(&[
+                Token::Uint(270.into()),
+                Token::Uint(5.into()),
+                Token::Uint(6.into()),
+                Token::Bytes(commit_data),
+            ])
+            .unwrap();
+
+        let parsed = parse_commit_calldata(&calldata, &[function]).unwrap();
+        assert_eq!(parsed, batches);
+
+        let mut calldata = calldata;
+        calldata[0] ^= 1;
+        parse_commit_calldata(&calldata, &[function]).unwrap_err();
+    }
+}
@@ -0,0 +1,465 @@
+//! Reconstruction of the L2 state from pubdata committed on L1.
+//!
+//! [`StateReconstructor`] walks `BlockCommit` events emitted by the diamond proxy, decodes the corresponding
+//! commit transactions, fetches pubdata of each L1 batch (from the transaction calldata, EIP-4844 blobs
+//! or a DA layer) and applies the published state diffs to a Merkle tree and to the `initial_writes`
+//! and `storage_logs` tables in Postgres. After applying each batch, the tree root hash and the enumeration
+//! index are checked against the values committed on L1, so a successful run proves that the published
+//! pubdata is sufficient to restore the chain state.
+//!
+//! # Limitations
+//!
+//! - Only L1 batches committed to L1 with post-gateway protocol versions are supported.
+//! - Only executed L1 batches are reconstructed; committed batches may still be reverted.
+//! - L2 block boundaries are not published on L1, so storage logs of a reconstructed L1 batch are attributed
+//!   to a pseudo L2 block with the same number as the batch. Hence, the target Postgres database must be
+//!   dedicated to the reconstruction; it must be initialized with the genesis state of the chain and must not
+//!   contain any other L2 blocks.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+};
+
+use anyhow::Context as _;
+use zksync_contracts::{hyperchain_contract, POST_V26_GATEWAY_COMMIT_FUNCTION};
+use zksync_da_client::DataAvailabilityClient;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_eth_client::{CallFunctionArgs, EthInterface};
+use zksync_merkle_tree::{domain::ZkSyncTree, TreeInstruction};
+use zksync_multivm::pubdata_builders::DecodedPubdata;
+use zksync_storage::RocksDB;
+use zksync_types::{
+    commitment::L1BatchCommitmentMode,
+    ethabi, h256_to_u256,
+    snapshots::SnapshotStorageLog,
+    u256_to_h256,
+    web3::{BlockId, BlockNumber, FilterBuilder},
+    writes::CompressedStateDiffKey,
+    Address, L1BatchNumber, L2BlockNumber, H256, U256,
+};
+
+pub use crate::commit::CommittedBatch;
+use crate::{blobs::BeaconClient, commit::parse_commit_calldata, pubdata::PubdataFetcher};
+
+mod blobs;
+mod commit;
+mod pubdata;
+#[cfg(test)]
+mod tests;
+
+/// Parameters of [`StateReconstructor`].
+#[derive(Debug, Clone)]
+pub struct ReconstructionParams {
+    /// Address of the chain diamond proxy on L1.
+    pub diamond_proxy_addr: Address,
+    pub commitment_mode: L1BatchCommitmentMode,
+    /// L1 block to start looking for commit transactions from (e.g., the block the diamond proxy was deployed in).
+    pub from_l1_block: u64,
+    /// Max number of L1 blocks to query logs for in a single request.
+    pub logs_block_range: u64,
+    /// Last L1 batch to reconstruct. If not set, all executed L1 batches are reconstructed.
+    pub to_l1_batch: Option<L1BatchNumber>,
+    /// Path to the RocksDB directory with the reconstructed Merkle tree.
+    pub merkle_tree_path: PathBuf,
+    /// URL of the beacon node API used to fetch pubdata published in blobs.
+    pub beacon_api_url: Option<String>,
+    /// Duration of a beacon chain slot in seconds.
+    pub seconds_per_slot: u64,
+    /// Blob IDs of L1 batches on the DA layer for validium chains. If a batch is missing, its number
+    /// is used as the blob ID (which is the case for the object store DA client).
+    pub da_blob_ids: HashMap<L1BatchNumber, String>,
+}
+
+/// Reference to a commit transaction on L1.
+#[derive(Debug, Clone, Copy)]
+struct CommitTx {
+    hash: H256,
+    l1_block_number: u64,
+}
+
+/// Commit transaction with decoded batches.
+#[derive(Debug)]
+struct DecodedCommitTx {
+    hash: H256,
+    l1_block_timestamp: u64,
+    batches: Vec<CommittedBatch>,
+}
+
+/// Reconstructs the L2 state from pubdata committed on L1. See the [crate-level docs](crate) for details.
+#[derive(Debug)]
+pub struct StateReconstructor {
+    params: ReconstructionParams,
+    eth_client: Box<dyn EthInterface>,
+    pool: ConnectionPool<Core>,
+    pubdata_fetcher: PubdataFetcher,
+    commit_functions: Vec<ethabi::Function>,
+    block_commit_event: ethabi::Event,
+}
+
+impl StateReconstructor {
+    pub fn new(
+        params: ReconstructionParams,
+        eth_client: Box<dyn EthInterface>,
+        pool: ConnectionPool<Core>,
+        da_client: Option<Box<dyn DataAvailabilityClient>>,
+    ) -> anyhow::Result<Self> {
+        let contract = hyperchain_contract();
+        let commit_functions = vec![
+            contract
+                .function("commitBatchesSharedBridge")
+                .context("L1 contract does not have `commitBatchesSharedBridge` function")?
+                .clone(),
+            POST_V26_GATEWAY_COMMIT_FUNCTION.clone(),
+        ];
+        let block_commit_event = contract
+            .event("BlockCommit")
+            .context("`BlockCommit` event not found for ZKsync L1 contract")?
+            .clone();
+        let beacon_client = params
+            .beacon_api_url
+            .clone()
+            .map(|url| BeaconClient::new(url, params.seconds_per_slot))
+            .transpose()?;
+        let pubdata_fetcher = PubdataFetcher::new(
+            params.commitment_mode,
+            beacon_client,
+            da_client,
+            params.da_blob_ids.clone(),
+        );
+
+        Ok(Self {
+            params,
+            eth_client,
+            pool,
+            pubdata_fetcher,
+            commit_functions,
+            block_commit_event,
+        })
+    }
+
+    /// Runs the reconstruction, resuming from the last L1 batch in the Merkle tree. Returns the number
+    /// of the last reconstructed L1 batch.
+    pub async fn run(self) -> anyhow::Result<L1BatchNumber> {
+        let mut tree = self.init_tree().await?;
+        let next_l1_batch = tree.next_l1_batch_number();
+        self.roll_back_postgres(next_l1_batch - 1).await?;
+
+        let last_executed_l1_batch = self.last_executed_l1_batch().await?;
+        let last_l1_batch = self
+            .params
+            .to_l1_batch
+            .map_or(last_executed_l1_batch, |number| {
+                number.min(last_executed_l1_batch)
+            });
+        if next_l1_batch > last_l1_batch {
+            tracing::info!("State is reconstructed up to L1 batch #{last_l1_batch}; nothing to do");
+            return Ok(next_l1_batch - 1);
+        }
+
+        let commit_txs = self.scan_commit_txs().await?;
+        let mut commit_tx: Option<DecodedCommitTx> = None;
+        for number in next_l1_batch.0..=last_l1_batch.0 {
+            let number = L1BatchNumber(number);
+            let tx_ref = *commit_txs.get(&number).with_context(|| {
+                format!(
+                    "commit transaction for L1 batch #{number} is not found; is `from_l1_block` too large?"
+                )
+            })?;
+            if commit_tx.as_ref().is_none_or(|tx| tx.hash != tx_ref.hash) {
+                commit_tx = Some(self.decode_commit_tx(tx_ref).await?);
+            }
+            let commit_tx = commit_tx.as_ref().unwrap();
+            let batch = commit_tx
+                .batches
+                .iter()
+                .find(|batch| batch.number == number)
+                .with_context(|| {
+                    format!(
+                        "commit transaction {:?} does not contain L1 batch #{number}",
+                        commit_tx.hash
+                    )
+                })?;
+
+            let pubdata = self
+                .pubdata_fetcher
+                .fetch(batch, commit_tx.l1_block_timestamp)
+                .await
+                .with_context(|| format!("failed fetching pubdata for L1 batch #{number}"))?;
+            tree = self
+                .apply_batch(tree, batch, pubdata)
+                .await
+                .with_context(|| format!("failed applying L1 batch #{number}"))?;
+            tracing::info!(
+                "Reconstructed L1 batch #{number} with root hash {:?}",
+                batch.new_state_root
+            );
+        }
+        Ok(last_l1_batch)
+    }
+
+    /// Opens the Merkle tree, initializing it with the genesis state from Postgres if necessary.
+    async fn init_tree(&self) -> anyhow::Result<ZkSyncTree> {
+        let mut storage = self.pool.connection_tagged("state_reconstruction").await?;
+        let sealed_l2_block = storage.blocks_dal().get_sealed_l2_block_number().await?;
+        anyhow::ensure!(
+            sealed_l2_block == Some(L2BlockNumber(0)),
+            "Postgres must only contain the genesis state, but the last sealed L2 block is {sealed_l2_block:?}"
+        );
+        let genesis_root_hash = storage
+            .blocks_dal()
+            .get_l1_batch_state_root(L1BatchNumber(0))
+            .await?
+            .context("genesis L1 batch has no root hash")?;
+
+        let path = self.params.merkle_tree_path.clone();
+        let mut tree = tokio::task::spawn_blocking(move || {
+            let db = RocksDB::new(&path).context("failed initializing RocksDB for Merkle tree")?;
+            ZkSyncTree::new_lightweight(db.into()).context("failed initializing Merkle tree")
+        })
+        .await??;
+        if !tree.is_empty() {
+            return Ok(tree);
+        }
+
+        tracing::info!("Initializing Merkle tree with the genesis state");
+        let mut entries = storage
+            .storage_logs_dal()
+            .get_tree_entries_for_l2_block(L2BlockNumber(0), H256::zero()..=H256::repeat_byte(0xff))
+            .await?;
+        drop(storage);
+        entries.sort_unstable_by_key(|entry| entry.leaf_index);
+        let instructions: Vec<_> = entries
+            .iter()
+            .map(|entry| TreeInstruction::write(entry.tree_key(), entry.leaf_index, entry.value))
+            .collect();
+
+        tokio::task::spawn_blocking(move || {
+            let metadata = tree.process_l1_batch(&instructions)?;
+            anyhow::ensure!(
+                metadata.root_hash == genesis_root_hash,
+                "genesis root hash mismatch: Postgres has {genesis_root_hash:?}, tree has {:?}",
+                metadata.root_hash
+            );
+            tree.save()?;
+            Ok(tree)
+        })
+        .await?
+    }
+
+    /// Removes data of L1 batches that were applied to Postgres, but not to the Merkle tree.
+    async fn roll_back_postgres(&self, last_l1_batch_to_keep: L1BatchNumber) -> anyhow::Result<()> {
+        let mut storage = self.pool.connection_tagged("state_reconstruction").await?;
+        let mut transaction = storage.start_transaction().await?;
+        transaction
+            .storage_logs_dal()
+            .roll_back_storage_logs(L2BlockNumber(last_l1_batch_to_keep.0))
+            .await?;
+        transaction
+            .blocks_dal()
+            .delete_initial_writes(last_l1_batch_to_keep)
+            .await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    async fn last_executed_l1_batch(&self) -> anyhow::Result<L1BatchNumber> {
+        let diamond_proxy_addr = self.params.diamond_proxy_addr;
+        let number: U256 = CallFunctionArgs::new("getTotalBatchesExecuted", ())
+            .for_contract(diamond_proxy_addr, &hyperchain_contract())
+            .call(self.eth_client.as_ref())
+            .await
+            .with_context(|| {
+                format!(
+                    "failed calling `getTotalBatchesExecuted` for contract {diamond_proxy_addr:?}"
+                )
+            })?;
+        Ok(L1BatchNumber(number.as_u32()))
+    }
+
+    /// Collects commit transactions for all L1 batches. If a batch was committed several times (i.e., it was reverted
+    /// and recommitted), the latest commit transaction is returned.
+    async fn scan_commit_txs(&self) -> anyhow::Result<BTreeMap<L1BatchNumber, CommitTx>> {
+        let latest_block = self.eth_client.block_number().await?.as_u64();
+        let range = self.params.logs_block_range.max(1);
+        let mut commit_txs = BTreeMap::new();
+        let mut from_block = self.params.from_l1_block;
+        while from_block <= latest_block {
+            let to_block = (from_block + range - 1).min(latest_block);
+            let filter = FilterBuilder::default()
+                .address(vec![self.params.diamond_proxy_addr])
+                .topics(
+                    Some(vec![self.block_commit_event.signature()]),
+                    None,
+                    None,
+                    None,
+                )
+                .from_block(BlockNumber::Number(from_block.into()))
+                .to_block(BlockNumber::Number(to_block.into()))
+                .build();
+            let logs = self.eth_client.logs(&filter).await?;
+            for log in logs {
+                let batch_number = log
+                    .topics
+                    .get(1)
+                    .context("`BlockCommit` event has no batch number")?;
+                let batch_number = L1BatchNumber(h256_to_u256(*batch_number).as_u32());
+                let tx = CommitTx {
+                    hash: log
+                        .transaction_hash
+                        .context("log has no transaction hash")?,
+                    l1_block_number: log
+                        .block_number
+                        .context("log has no block number")?
+                        .as_u64(),
+                };
+                commit_txs.insert(batch_number, tx);
+            }
+            tracing::debug!("Scanned L1 blocks {from_block}..={to_block} for commit transactions");
+            from_block = to_block + 1;
+        }
+        Ok(commit_txs)
+    }
+
+    async fn decode_commit_tx(&self, tx_ref: CommitTx) -> anyhow::Result<DecodedCommitTx> {
+        let tx = self
+            .eth_client
+            .get_tx(tx_ref.hash)
+            .await?
+            .with_context(|| format!("commit transaction {:?} not found on L1", tx_ref.hash))?;
+        let commit_functions: Vec<_> = self.commit_functions.iter().collect();
+        let batches = parse_commit_calldata(&tx.input.0, &commit_functions)
+            .with_context(|| format!("failed parsing commit transaction {:?}", tx_ref.hash))?;
+        let block = self
+            .eth_client
+            .block(BlockId::Number(BlockNumber::Number(
+                tx_ref.l1_block_number.into(),
+            )))
+            .await?
+            .with_context(|| format!("L1 block #{} not found", tx_ref.l1_block_number))?;
+        Ok(DecodedCommitTx {
+            hash: tx_ref.hash,
+            l1_block_timestamp: block.timestamp.as_u64(),
+            batches,
+        })
+    }
+
+    /// Applies state diffs of a batch to Postgres and the Merkle tree, and checks the resulting tree state
+    /// against the values committed on L1.
+    async fn apply_batch(
+        &self,
+        tree: ZkSyncTree,
+        batch: &CommittedBatch,
+        pubdata: DecodedPubdata,
+    ) -> anyhow::Result<ZkSyncTree> {
+        let number = batch.number;
+        let prev_l2_block = L2BlockNumber(number.0 - 1);
+        let mut storage = self.pool.connection_tagged("state_reconstruction").await?;
+
+        let repeated_indices: Vec<_> = pubdata
+            .state_diffs
+            .iter()
+            .filter_map(|diff| match diff.key {
+                CompressedStateDiffKey::Repeated(index) => Some(index),
+                CompressedStateDiffKey::Initial(_) => None,
+            })
+            .collect();
+        let keys_by_index = storage
+            .storage_logs_dedup_dal()
+            .get_hashed_keys_by_enumeration_indices(&repeated_indices)
+            .await?;
+        let hashed_keys = pubdata.state_diffs.iter().map(|diff| match diff.key {
+            CompressedStateDiffKey::Initial(hashed_key) => Ok((hashed_key, None)),
+            CompressedStateDiffKey::Repeated(index) => keys_by_index
+                .get(&index)
+                .map(|&hashed_key| (hashed_key, Some(index)))
+                .with_context(|| format!("unknown enumeration index {index}")),
+        });
+        let hashed_keys: Vec<_> = hashed_keys.collect::<anyhow::Result<_>>()?;
+        let repeated_keys: Vec<_> = hashed_keys
+            .iter()
+            .filter_map(|(hashed_key, index)| index.map(|_| *hashed_key))
+            .collect();
+        let prev_values = storage
+            .storage_logs_dal()
+            .get_storage_values(&repeated_keys, prev_l2_block)
+            .await?;
+        let mut next_index = storage
+            .storage_logs_dedup_dal()
+            .max_enumeration_index_by_l1_batch(number - 1)
+            .await?
+            .unwrap_or(0)
+            + 1;
+
+        let mut initial_writes = vec![];
+        let mut storage_logs = Vec::with_capacity(hashed_keys.len());
+        for ((hashed_key, index), diff) in hashed_keys.into_iter().zip(&pubdata.state_diffs) {
+            let (enumeration_index, prev_value) = match index {
+                Some(index) => {
+                    let prev_value = prev_values
+                        .get(&hashed_key)
+                        .copied()
+                        .flatten()
+                        .unwrap_or_default();
+                    (index, h256_to_u256(prev_value))
+                }
+                None => {
+                    initial_writes.push(hashed_key);
+                    next_index += 1;
+                    (next_index - 1, U256::zero())
+                }
+            };
+            storage_logs.push(SnapshotStorageLog {
+                key: hashed_key,
+                value: u256_to_h256(diff.value.apply(prev_value)),
+                l1_batch_number_of_initial_write: number,
+                enumeration_index,
+            });
+        }
+        anyhow::ensure!(
+            next_index == batch.index_repeated_storage_changes,
+            "enumeration index mismatch: committed {}, reconstructed {next_index}",
+            batch.index_repeated_storage_changes
+        );
+
+        let instructions: Vec<_> = storage_logs
+            .iter()
+            .map(|log| {
+                let key = U256::from_little_endian(log.key.as_bytes());
+                TreeInstruction::write(key, log.enumeration_index, log.value)
+            })
+            .collect();
+        let (mut tree, metadata) = tokio::task::spawn_blocking(move || {
+            let mut tree = tree;
+            let metadata = tree.process_l1_batch(&instructions);
+            (tree, metadata)
+        })
+        .await?;
+        let metadata = metadata?;
+        if metadata.root_hash != batch.new_state_root {
+            tree.reset();
+            anyhow::bail!(
+                "root hash mismatch: committed {:?}, reconstructed {:?}",
+                batch.new_state_root,
+                metadata.root_hash
+            );
+        }
+
+        let mut transaction = storage.start_transaction().await?;
+        transaction
+            .storage_logs_dedup_dal()
+            .insert_initial_writes(number, &initial_writes)
+            .await?;
+        transaction
+            .storage_logs_dal()
+            .insert_storage_logs_from_snapshot(L2BlockNumber(number.0), &storage_logs)
+            .await?;
+        transaction.commit().await?;
+
+        tokio::task::spawn_blocking(move || {
+            tree.save()?;
+            Ok(tree)
+        })
+        .await?
+    }
+}
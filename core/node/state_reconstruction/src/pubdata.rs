@@ -0,0 +1,210 @@
+//! Locating and fetching pubdata of committed L1 batches.
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use zksync_da_client::{
+    chunking::{decode_chunked_id, get_chunked_blob_data},
+    DataAvailabilityClient,
+};
+use zksync_l1_contract_interface::i_executor::structures::{
+    PUBDATA_SOURCE_BLOBS, PUBDATA_SOURCE_CALLDATA,
+};
+use zksync_multivm::pubdata_builders::{decode_settlement_layer_pubdata, DecodedPubdata};
+use zksync_types::{commitment::L1BatchCommitmentMode, web3::keccak256, L1BatchNumber, H256};
+
+use crate::{
+    blobs::{BeaconClient, PUBDATA_BYTES_PER_BLOB},
+    commit::CommittedBatch,
+};
+
+/// Location of the pubdata of an L1 batch as specified by the operator DA input.
+#[derive(Debug, PartialEq)]
+enum PubdataLocation {
+    /// Rollup pubdata published in the commit transaction calldata, followed by blob commitments.
+    Calldata {
+        full_pubdata_hash: H256,
+        data: Vec<u8>,
+    },
+    /// Rollup pubdata published in EIP-4844 blobs with the specified linear hashes.
+    Blobs {
+        full_pubdata_hash: H256,
+        linear_hashes: Vec<H256>,
+    },
+    /// Validium pubdata published to a DA layer (if published at all).
+    DataAvailability,
+}
+
+/// Parses the post-gateway operator DA input.
+fn parse_operator_da_input(
+    input: &[u8],
+    commitment_mode: L1BatchCommitmentMode,
+) -> anyhow::Result<PubdataLocation> {
+    if commitment_mode == L1BatchCommitmentMode::Validium {
+        // The input is the state diff hash optionally followed by the DA inclusion data.
+        return Ok(PubdataLocation::DataAvailability);
+    }
+
+    // Rollup header: `stateDiffHash || fullPubdataHash || blobsCount (u8) || blobLinearHashes`,
+    // followed by the pubdata source byte.
+    anyhow::ensure!(input.len() >= 65, "operator DA input is too short");
+    let full_pubdata_hash = H256::from_slice(&input[32..64]);
+    let blobs_count = usize::from(input[64]);
+    let header_len = 65 + 32 * blobs_count;
+    let linear_hashes = input
+        .get(65..header_len)
+        .context("blob linear hashes are truncated")?
+        .chunks(32)
+        .map(H256::from_slice)
+        .collect();
+    let (&source, data) = input[header_len..]
+        .split_first()
+        .context("pubdata source is missing")?;
+
+    Ok(match source {
+        PUBDATA_SOURCE_CALLDATA => PubdataLocation::Calldata {
+            full_pubdata_hash,
+            data: data.to_vec(),
+        },
+        PUBDATA_SOURCE_BLOBS => PubdataLocation::Blobs {
+            full_pubdata_hash,
+            linear_hashes,
+        },
+        _ => anyhow::bail!("unsupported pubdata source: {source}"),
+    })
+}
+
+/// Decodes pubdata from `data` (which may contain trailing bytes) and checks its hash.
+fn decode_and_verify(data: &[u8], full_pubdata_hash: H256) -> anyhow::Result<DecodedPubdata> {
+    let (pubdata, len) = decode_settlement_layer_pubdata(data)?;
+    let actual_hash = H256(keccak256(&data[..len]));
+    anyhow::ensure!(
+        actual_hash == full_pubdata_hash,
+        "pubdata hash mismatch: committed {full_pubdata_hash:?}, got {actual_hash:?}"
+    );
+    Ok(pubdata)
+}
+
+/// Fetches pubdata of committed L1 batches from the commit transaction calldata, blobs or a DA layer.
+#[derive(Debug)]
+pub(crate) struct PubdataFetcher {
+    commitment_mode: L1BatchCommitmentMode,
+    beacon_client: Option<BeaconClient>,
+    da_client: Option<Box<dyn DataAvailabilityClient>>,
+    da_blob_ids: HashMap<L1BatchNumber, String>,
+}
+
+impl PubdataFetcher {
+    pub fn new(
+        commitment_mode: L1BatchCommitmentMode,
+        beacon_client: Option<BeaconClient>,
+        da_client: Option<Box<dyn DataAvailabilityClient>>,
+        da_blob_ids: HashMap<L1BatchNumber, String>,
+    ) -> Self {
+        Self {
+            commitment_mode,
+            beacon_client,
+            da_client,
+            da_blob_ids,
+        }
+    }
+
+    /// Fetches and decodes pubdata for the `batch` committed in an L1 block with the specified timestamp.
+    pub async fn fetch(
+        &self,
+        batch: &CommittedBatch,
+        l1_block_timestamp: u64,
+    ) -> anyhow::Result<DecodedPubdata> {
+        match parse_operator_da_input(&batch.operator_da_input, self.commitment_mode)? {
+            PubdataLocation::Calldata {
+                full_pubdata_hash,
+                data,
+            } => decode_and_verify(&data, full_pubdata_hash),
+            PubdataLocation::Blobs {
+                full_pubdata_hash,
+                linear_hashes,
+            } => {
+                let beacon_client = self
+                    .beacon_client
+                    .as_ref()
+                    .context("pubdata is published in blobs, but beacon API URL is not provided")?;
+                let blobs = beacon_client.blobs_pubdata(l1_block_timestamp).await?;
+                let mut blobs_by_hash: HashMap<_, _> = blobs
+                    .into_iter()
+                    .map(|blob| (H256(keccak256(&blob)), blob))
+                    .collect();
+
+                let mut data = Vec::with_capacity(linear_hashes.len() * PUBDATA_BYTES_PER_BLOB);
+                for hash in linear_hashes {
+                    let blob = blobs_by_hash.remove(&hash).with_context(|| {
+                        format!("blob with linear hash {hash:?} is not found in the L1 block")
+                    })?;
+                    data.extend(blob);
+                }
+                decode_and_verify(&data, full_pubdata_hash)
+            }
+            PubdataLocation::DataAvailability => {
+                let da_client = self.da_client.as_deref().context(
+                    "L1 batch pubdata is published to a DA layer, but DA client is not configured",
+                )?;
+                let blob_id = self
+                    .da_blob_ids
+                    .get(&batch.number)
+                    .cloned()
+                    .unwrap_or_else(|| batch.number.0.to_string());
+                let data = match decode_chunked_id(&blob_id)? {
+                    Some(chunk_blob_ids) => get_chunked_blob_data(da_client, &chunk_blob_ids).await,
+                    None => da_client.get_blob_data(&blob_id).await,
+                };
+                let data = data
+                    .map_err(|err| err.error)
+                    .with_context(|| format!("failed fetching DA blob {blob_id}"))?
+                    .with_context(|| format!("DA blob {blob_id} is not found"))?;
+                // Pubdata hash is not committed for validiums; the state root check verifies the pubdata.
+                Ok(decode_settlement_layer_pubdata(&data)?.0)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_rollup_operator_da_input() {
+        let header: Vec<u8> = [[1; 32], [2; 32]]
+            .concat()
+            .into_iter()
+            .chain([2])
+            .chain([[3; 32], [4; 32]].concat())
+            .collect();
+
+        let input = [header.as_slice(), &[PUBDATA_SOURCE_CALLDATA], &[5; 10]].concat();
+        let location = parse_operator_da_input(&input, L1BatchCommitmentMode::Rollup).unwrap();
+        assert_eq!(
+            location,
+            PubdataLocation::Calldata {
+                full_pubdata_hash: H256::repeat_byte(2),
+                data: vec![5; 10],
+            }
+        );
+
+        let input = [header.as_slice(), &[PUBDATA_SOURCE_BLOBS], &[0; 128]].concat();
+        let location = parse_operator_da_input(&input, L1BatchCommitmentMode::Rollup).unwrap();
+        assert_eq!(
+            location,
+            PubdataLocation::Blobs {
+                full_pubdata_hash: H256::repeat_byte(2),
+                linear_hashes: vec![H256::repeat_byte(3), H256::repeat_byte(4)],
+            }
+        );
+
+        parse_operator_da_input(&header, L1BatchCommitmentMode::Rollup).unwrap_err();
+        let input = [header.as_slice(), &[7]].concat();
+        parse_operator_da_input(&input, L1BatchCommitmentMode::Rollup).unwrap_err();
+
+        let location = parse_operator_da_input(&[1; 32], L1BatchCommitmentMode::Validium).unwrap();
+        assert_eq!(location, PubdataLocation::DataAvailability);
+    }
+}
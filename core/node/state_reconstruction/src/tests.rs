@@ -0,0 +1,462 @@
+//! End-to-end tests for state reconstruction.
+
+use std::collections::HashMap;
+
+use tempfile::TempDir;
+use zksync_contracts::POST_V26_GATEWAY_COMMIT_FUNCTION;
+use zksync_dal::Connection;
+use zksync_l1_contract_interface::i_executor::structures::{
+    EncodingVersion, StoredBatchInfo, PUBDATA_SOURCE_CALLDATA,
+};
+use zksync_merkle_tree::{MerkleTree, PatchSet, TreeEntry};
+use zksync_multivm::{
+    interface::pubdata::{PubdataBuilder, PubdataInput},
+    pubdata_builders::FullPubdataBuilder,
+};
+use zksync_node_genesis::{insert_genesis_batch, GenesisParamsInitials};
+use zksync_types::{
+    commitment::{L2DACommitmentScheme, L2PubdataValidator},
+    ethabi::Token,
+    get_system_context_key,
+    web3::{self, keccak256},
+    writes::StateDiffRecord,
+    AccountTreeId, ProtocolVersionId, StorageKey, SYSTEM_CONTEXT_CHAIN_ID_POSITION, U64,
+};
+use zksync_web3_decl::client::{MockClient, L1};
+
+use super::*;
+
+const DIAMOND_PROXY_ADDR: Address = Address::repeat_byte(0x42);
+const LATEST_L1_BLOCK: u64 = 20;
+
+fn storage_key(key: u64) -> StorageKey {
+    StorageKey::new(
+        AccountTreeId::new(Address::repeat_byte(0x01)),
+        H256::from_low_u64_be(key),
+    )
+}
+
+fn state_diff(
+    key: StorageKey,
+    enumeration_index: u64,
+    initial: H256,
+    value: H256,
+) -> StateDiffRecord {
+    StateDiffRecord {
+        address: *key.address(),
+        key: h256_to_u256(*key.key()),
+        derived_key: key.hashed_key().0,
+        enumeration_index,
+        initial_value: h256_to_u256(initial),
+        final_value: h256_to_u256(value),
+    }
+}
+
+/// Builds rollup operator DA input with pubdata published in calldata.
+fn rollup_operator_da_input(state_diffs: Vec<StateDiffRecord>) -> Vec<u8> {
+    let input = PubdataInput {
+        state_diffs,
+        ..PubdataInput::default()
+    };
+    let builder = FullPubdataBuilder::new(L2PubdataValidator::CommitmentScheme(
+        L2DACommitmentScheme::BlobsAndPubdataKeccak256,
+    ));
+    let pubdata = builder.settlement_layer_pubdata(&input, ProtocolVersionId::latest());
+    [
+        &[0; 32][..],         // state diff hash (not checked)
+        &keccak256(&pubdata), // full pubdata hash
+        &[0],                 // blobs count
+        &[PUBDATA_SOURCE_CALLDATA],
+        &pubdata,
+    ]
+    .concat()
+}
+
+fn commit_calldata(batches: &[CommittedBatch]) -> Vec<u8> {
+    let first_batch = batches[0].number;
+    let last_batch = batches.last().unwrap().number;
+    let prev_batch = StoredBatchInfo {
+        batch_number: (first_batch.0 - 1).into(),
+        batch_hash: H256::zero(),
+        index_repeated_storage_changes: 0,
+        number_of_layer1_txs: U256::zero(),
+        priority_operations_hash: H256::zero(),
+        dependency_roots_rolling_hash: H256::zero(),
+        l2_logs_tree_root: H256::zero(),
+        timestamp: U256::zero(),
+        commitment: H256::zero(),
+    };
+    let batch_tokens = batches.iter().map(|batch| {
+        Token::Tuple(vec![
+            Token::Uint(batch.number.0.into()),
+            Token::Uint(batch.timestamp.into()),
+            Token::Uint(batch.index_repeated_storage_changes.into()),
+            Token::FixedBytes(batch.new_state_root.as_bytes().to_vec()),
+            Token::Uint(U256::zero()),
+            Token::FixedBytes(vec![0; 32]),
+            Token::FixedBytes(vec![0; 32]),
+            Token::FixedBytes(vec![0; 32]),
+            Token::Bytes(vec![]),
+            Token::Bytes(batch.operator_da_input.clone()),
+        ])
+    });
+    let encoded_data = ethabi::encode(&[
+        prev_batch.into_token_with_protocol_version(ProtocolVersionId::Version27),
+        Token::Array(batch_tokens.collect()),
+    ]);
+    let commit_data = [
+        &[EncodingVersion::PreInterop.value()],
+        encoded_data.as_slice(),
+    ]
+    .concat();
+    POST_V26_GATEWAY_COMMIT_FUNCTION
+        .encode_input(&[
+            Token::Uint(270.into()),
+            Token::Uint(first_batch.0.into()),
+            Token::Uint(last_batch.0.into()),
+            Token::Bytes(commit_data),
+        ])
+        .unwrap()
+}
+
+/// Chain emulating state transitions of L1 batches on top of the genesis state, with the root hashes
+/// computed using an independent in-memory Merkle tree.
+struct TestChain {
+    tree: MerkleTree<PatchSet>,
+    /// Hashed key -> (enumeration index, value).
+    state: HashMap<H256, (u64, H256)>,
+    next_index: u64,
+    next_l1_batch: L1BatchNumber,
+}
+
+impl TestChain {
+    async fn new(storage: &mut Connection<'_, Core>) -> Self {
+        let entries = storage
+            .storage_logs_dal()
+            .get_tree_entries_for_l2_block(L2BlockNumber(0), H256::zero()..=H256::repeat_byte(0xff))
+            .await
+            .unwrap();
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        let tree_entries = entries
+            .iter()
+            .map(|entry| TreeEntry::new(entry.tree_key(), entry.leaf_index, entry.value))
+            .collect();
+        tree.extend(tree_entries).unwrap();
+
+        Self {
+            tree,
+            state: entries
+                .iter()
+                .map(|entry| (entry.key, (entry.leaf_index, entry.value)))
+                .collect(),
+            next_index: entries.iter().map(|entry| entry.leaf_index).max().unwrap() + 1,
+            next_l1_batch: L1BatchNumber(1),
+        }
+    }
+
+    fn push_batch(&mut self, writes: &[(StorageKey, H256)]) -> CommittedBatch {
+        let number = self.next_l1_batch;
+        self.next_l1_batch += 1;
+
+        // Initial writes are enumerated in the same order as they are published in pubdata.
+        let mut writes = writes.to_vec();
+        writes.sort_unstable_by_key(|(key, _)| (*key.address(), *key.key()));
+        let mut state_diffs = vec![];
+        let mut tree_entries = vec![];
+        for (key, value) in writes {
+            let hashed_key = key.hashed_key();
+            let (enumeration_index, initial_value) =
+                self.state.get(&hashed_key).copied().unwrap_or_default();
+            let leaf_index = if enumeration_index == 0 {
+                self.next_index += 1;
+                self.next_index - 1
+            } else {
+                enumeration_index
+            };
+            self.state.insert(hashed_key, (leaf_index, value));
+
+            let tree_key = U256::from_little_endian(hashed_key.as_bytes());
+            tree_entries.push(TreeEntry::new(tree_key, leaf_index, value));
+            state_diffs.push(state_diff(key, enumeration_index, initial_value, value));
+        }
+        let root_hash = self.tree.extend(tree_entries).unwrap().root_hash;
+
+        CommittedBatch {
+            number,
+            timestamp: number.0.into(),
+            index_repeated_storage_changes: self.next_index,
+            new_state_root: root_hash,
+            operator_da_input: rollup_operator_da_input(state_diffs),
+        }
+    }
+}
+
+fn filter_logs(logs: &[web3::Log], filter: web3::Filter) -> Vec<web3::Log> {
+    let Some(web3::BlockNumber::Number(from_block)) = filter.from_block else {
+        panic!("Unexpected filter: {filter:?}");
+    };
+    let Some(web3::BlockNumber::Number(to_block)) = filter.to_block else {
+        panic!("Unexpected filter: {filter:?}");
+    };
+    assert!(from_block <= to_block && to_block <= U64::from(LATEST_L1_BLOCK));
+    let filter_addresses = filter.address.unwrap().flatten();
+    assert_eq!(filter_addresses, [DIAMOND_PROXY_ADDR]);
+
+    logs.iter()
+        .filter(|log| (from_block..=to_block).contains(&log.block_number.unwrap()))
+        .cloned()
+        .collect()
+}
+
+/// Mocks an L1 client with the specified commit transactions (each committing one or more batches
+/// in the specified L1 block).
+fn mock_l1_client(
+    commit_txs: &[(u64, Vec<CommittedBatch>)],
+    last_executed_batch: L1BatchNumber,
+) -> Box<dyn EthInterface> {
+    let block_commit_signature = hyperchain_contract()
+        .event("BlockCommit")
+        .unwrap()
+        .signature();
+    let mut logs = vec![];
+    let mut calldata_by_hash = HashMap::new();
+    for (i, (l1_block_number, batches)) in commit_txs.iter().enumerate() {
+        let tx_hash = H256::from_low_u64_be(i as u64 + 1);
+        calldata_by_hash.insert(tx_hash, commit_calldata(batches));
+        logs.extend(batches.iter().map(|batch| web3::Log {
+            address: DIAMOND_PROXY_ADDR,
+            topics: vec![
+                block_commit_signature,
+                H256::from_low_u64_be(batch.number.0.into()),
+                H256::zero(),
+                H256::zero(),
+            ],
+            transaction_hash: Some(tx_hash),
+            block_number: Some((*l1_block_number).into()),
+            ..web3::Log::default()
+        }));
+    }
+
+    let client = MockClient::builder(L1::default())
+        .method("eth_blockNumber", || Ok(U64::from(LATEST_L1_BLOCK)))
+        .method("eth_getLogs", move |filter: web3::Filter| {
+            Ok(filter_logs(&logs, filter))
+        })
+        .method("eth_getTransactionByHash", move |hash: H256| {
+            Ok(calldata_by_hash
+                .get(&hash)
+                .map(|calldata| web3::Transaction {
+                    hash,
+                    input: calldata.clone().into(),
+                    ..web3::Transaction::default()
+                }))
+        })
+        .method(
+            "eth_getBlockByNumber",
+            |number: web3::BlockNumber, with_txs: bool| {
+                assert!(!with_txs);
+                let web3::BlockNumber::Number(number) = number else {
+                    panic!("Unexpected block number: {number:?}");
+                };
+                Ok(Some(web3::Block::<H256> {
+                    number: Some(number),
+                    timestamp: (number.as_u64() * 12).into(),
+                    ..web3::Block::default()
+                }))
+            },
+        )
+        .method(
+            "eth_call",
+            move |req: web3::CallRequest, _block: BlockId| {
+                let expected_input = hyperchain_contract()
+                    .function("getTotalBatchesExecuted")
+                    .unwrap()
+                    .encode_input(&[])
+                    .unwrap();
+                assert_eq!(req.to, Some(DIAMOND_PROXY_ADDR));
+                assert_eq!(req.data, Some(expected_input.into()));
+                Ok(web3::Bytes(ethabi::encode(&[Token::Uint(
+                    last_executed_batch.0.into(),
+                )])))
+            },
+        )
+        .build();
+    Box::new(client)
+}
+
+fn reconstruction_params(merkle_tree_path: &TempDir) -> ReconstructionParams {
+    ReconstructionParams {
+        diamond_proxy_addr: DIAMOND_PROXY_ADDR,
+        commitment_mode: L1BatchCommitmentMode::Rollup,
+        from_l1_block: 0,
+        logs_block_range: 4,
+        to_l1_batch: None,
+        merkle_tree_path: merkle_tree_path.path().to_owned(),
+        beacon_api_url: None,
+        seconds_per_slot: 12,
+        da_blob_ids: HashMap::new(),
+    }
+}
+
+async fn prepare_postgres() -> (ConnectionPool<Core>, TestChain) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParamsInitials::mock())
+        .await
+        .unwrap();
+    let chain = TestChain::new(&mut storage).await;
+    (pool, chain)
+}
+
+async fn storage_logs_for_batch(
+    storage: &mut Connection<'_, Core>,
+    number: L1BatchNumber,
+) -> HashMap<H256, H256> {
+    let logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    logs.into_iter()
+        .filter(|log| log.l2_block_number == L2BlockNumber(number.0))
+        .map(|log| (log.hashed_key, log.value))
+        .collect()
+}
+
+fn expected_logs(writes: &[(StorageKey, H256)]) -> HashMap<H256, H256> {
+    writes
+        .iter()
+        .map(|(key, value)| (key.hashed_key(), *value))
+        .collect()
+}
+
+#[tokio::test]
+async fn reconstructing_state_from_l1() {
+    let (pool, mut chain) = prepare_postgres().await;
+    // Overwrites a slot initialized at genesis as well, to check repeated writes for keys not written by the reconstructor.
+    let genesis_key = get_system_context_key(SYSTEM_CONTEXT_CHAIN_ID_POSITION);
+    assert!(chain.state.contains_key(&genesis_key.hashed_key()));
+    let batch_writes = [
+        vec![
+            (storage_key(1), H256::repeat_byte(1)),
+            (storage_key(2), H256::repeat_byte(2)),
+        ],
+        vec![
+            (storage_key(1), H256::repeat_byte(3)),
+            (storage_key(3), H256::repeat_byte(4)),
+        ],
+        vec![
+            (storage_key(2), H256::zero()),
+            (storage_key(4), H256::repeat_byte(5)),
+        ],
+        vec![(genesis_key, H256::repeat_byte(0xee))],
+    ];
+    let batches: Vec<_> = batch_writes
+        .iter()
+        .map(|writes| chain.push_batch(writes))
+        .collect();
+
+    // Batches #1 and #2 are committed in a single transaction.
+    let commit_txs = [
+        (3, batches[..2].to_vec()),
+        (9, vec![batches[2].clone()]),
+        (15, vec![batches[3].clone()]),
+    ];
+    let merkle_tree_path = TempDir::new().unwrap();
+    let reconstructor = StateReconstructor::new(
+        reconstruction_params(&merkle_tree_path),
+        mock_l1_client(&commit_txs, L1BatchNumber(4)),
+        pool.clone(),
+        None,
+    )
+    .unwrap();
+    let last_batch = reconstructor.run().await.unwrap();
+    assert_eq!(last_batch, L1BatchNumber(4));
+
+    let mut storage = pool.connection().await.unwrap();
+    for (writes, batch) in batch_writes.iter().zip(&batches) {
+        let logs = storage_logs_for_batch(&mut storage, batch.number).await;
+        assert_eq!(logs, expected_logs(writes), "L1 batch #{}", batch.number);
+    }
+    let max_index = storage
+        .storage_logs_dedup_dal()
+        .max_enumeration_index_by_l1_batch(L1BatchNumber(4))
+        .await
+        .unwrap();
+    assert_eq!(max_index, Some(chain.next_index - 1));
+    drop(storage);
+
+    // Restarting the reconstructor should be a no-op.
+    let reconstructor = StateReconstructor::new(
+        reconstruction_params(&merkle_tree_path),
+        mock_l1_client(&commit_txs, L1BatchNumber(4)),
+        pool.clone(),
+        None,
+    )
+    .unwrap();
+    let last_batch = reconstructor.run().await.unwrap();
+    assert_eq!(last_batch, L1BatchNumber(4));
+}
+
+#[tokio::test]
+async fn tampered_batch_is_rejected() {
+    let (pool, mut chain) = prepare_postgres().await;
+    let first_writes = [(storage_key(1), H256::repeat_byte(1))];
+    let second_writes = [
+        (storage_key(1), H256::repeat_byte(2)),
+        (storage_key(2), H256::repeat_byte(3)),
+    ];
+    let first_batch = chain.push_batch(&first_writes);
+    let second_batch = chain.push_batch(&second_writes);
+
+    // Publish a different value for one of the slots, keeping the committed root hash and enumeration index intact.
+    let first_index = chain.state[&storage_key(1).hashed_key()].0;
+    let tampered_batch = CommittedBatch {
+        operator_da_input: rollup_operator_da_input(vec![
+            state_diff(
+                storage_key(1),
+                first_index,
+                H256::repeat_byte(1),
+                H256::repeat_byte(2),
+            ),
+            state_diff(storage_key(2), 0, H256::zero(), H256::repeat_byte(0xff)),
+        ]),
+        ..second_batch.clone()
+    };
+
+    let merkle_tree_path = TempDir::new().unwrap();
+    let commit_txs = [(3, vec![first_batch.clone()]), (5, vec![tampered_batch])];
+    let reconstructor = StateReconstructor::new(
+        reconstruction_params(&merkle_tree_path),
+        mock_l1_client(&commit_txs, L1BatchNumber(2)),
+        pool.clone(),
+        None,
+    )
+    .unwrap();
+    let err = reconstructor.run().await.unwrap_err();
+    let err = format!("{err:#}");
+    assert!(err.contains("root hash mismatch"), "{err}");
+
+    // The tampered batch must not be persisted, while the previous batch must be retained.
+    let mut storage = pool.connection().await.unwrap();
+    let logs = storage_logs_for_batch(&mut storage, L1BatchNumber(1)).await;
+    assert_eq!(logs, expected_logs(&first_writes));
+    let logs = storage_logs_for_batch(&mut storage, L1BatchNumber(2)).await;
+    assert!(logs.is_empty(), "{logs:?}");
+    drop(storage);
+
+    // Reconstruction with the correct pubdata should resume from the tampered batch.
+    let commit_txs = [(3, vec![first_batch]), (5, vec![second_batch])];
+    let reconstructor = StateReconstructor::new(
+        reconstruction_params(&merkle_tree_path),
+        mock_l1_client(&commit_txs, L1BatchNumber(2)),
+        pool.clone(),
+        None,
+    )
+    .unwrap();
+    let last_batch = reconstructor.run().await.unwrap();
+    assert_eq!(last_batch, L1BatchNumber(2));
+
+    let mut storage = pool.connection().await.unwrap();
+    let logs = storage_logs_for_batch(&mut storage, L1BatchNumber(2)).await;
+    assert_eq!(logs, expected_logs(&second_writes));
+}
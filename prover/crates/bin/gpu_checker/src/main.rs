@@ -227,6 +227,7 @@ async fn main() -> anyhow::Result<()> {
         },
         max_retries: 1,
         local_mirror_path: None,
+        encryption: None,
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
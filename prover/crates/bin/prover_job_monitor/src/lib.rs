@@ -2,6 +2,7 @@ pub mod attempts_reporter;
 pub mod autoscaler_queue_reporter;
 pub mod job_requeuer;
 pub(crate) mod metrics;
+pub mod object_store_gc;
 pub mod queue_reporter;
 pub mod witness_job_queuer;
 
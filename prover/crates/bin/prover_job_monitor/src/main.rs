@@ -14,11 +14,14 @@ use zksync_config::{
     full_config_schema,
     sources::ConfigFilePaths,
 };
+use zksync_dal::Core;
+use zksync_object_store::ObjectStoreFactory;
 use zksync_prover_dal::{ConnectionPool, Prover};
 use zksync_prover_job_monitor::{
     attempts_reporter::ProverJobAttemptsReporter,
     autoscaler_queue_reporter::get_queue_reporter_router,
     job_requeuer::{ProofCompressorJobRequeuer, ProverJobRequeuer, WitnessGeneratorJobRequeuer},
+    object_store_gc::{ArtifactKind, ObjectStoreGarbageCollector, RetentionRule},
     prover_jobs_archiver::ProverJobsArchiver,
     queue_reporter::{
         ProofCompressorQueueReporter, ProverQueueReporter, WitnessGeneratorQueueReporter,
@@ -91,6 +94,14 @@ async fn main() -> anyhow::Result<()> {
     .await
     .context("failed to build a connection pool")?;
 
+    let object_store_gc = create_object_store_gc(
+        connection_pool.clone(),
+        &prover_job_monitor_config,
+        &prover_config,
+        &database_secrets,
+    )
+    .await?;
+
     let graceful_shutdown_timeout = prover_job_monitor_config.graceful_shutdown_timeout;
 
     let mut tasks = vec![tokio::spawn(
@@ -103,6 +114,7 @@ async fn main() -> anyhow::Result<()> {
         proof_compressor_config,
         prover_config,
         witness_generator_config,
+        object_store_gc,
         stop_receiver.clone(),
     )?);
     let mut tasks = ManagedTasks::new(tasks);
@@ -140,12 +152,67 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Creates the object store garbage collector if it's enabled in the config.
+async fn create_object_store_gc(
+    connection_pool: ConnectionPool<Prover>,
+    config: &ProverJobMonitorConfig,
+    prover_config: &FriProverConfig,
+    database_secrets: &PostgresSecrets,
+) -> anyhow::Result<Option<ObjectStoreGarbageCollector>> {
+    let Some(chain_id) = config.object_store_gc_chain_id else {
+        return Ok(None);
+    };
+    let rules: Vec<_> = [
+        (
+            ArtifactKind::WitnessInputs,
+            config.object_store_gc_witness_inputs_retained_batches,
+        ),
+        (
+            ArtifactKind::Circuits,
+            config.object_store_gc_circuits_retained_batches,
+        ),
+        (
+            ArtifactKind::Proofs,
+            config.object_store_gc_proofs_retained_batches,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(kind, retained_batches)| {
+        Some(RetentionRule {
+            kind,
+            retained_batches: retained_batches?,
+        })
+    })
+    .collect();
+    if rules.is_empty() {
+        tracing::info!("Object store GC is enabled, but no retention rules are configured");
+        return Ok(None);
+    }
+
+    let core_pool = ConnectionPool::<Core>::singleton(database_secrets.replica_url()?)
+        .build()
+        .await
+        .context("failed to build a core connection pool")?;
+    let object_store = ObjectStoreFactory::new(prover_config.prover_object_store.clone())
+        .create_store()
+        .await?;
+    Ok(Some(ObjectStoreGarbageCollector::new(
+        connection_pool,
+        core_pool,
+        object_store,
+        chain_id,
+        rules,
+        config.object_store_gc_max_l1_batches_per_run.get(),
+    )))
+}
+
 fn get_tasks(
     connection_pool: ConnectionPool<Prover>,
     prover_job_monitor_config: ProverJobMonitorConfig,
     proof_compressor_config: FriProofCompressorConfig,
     prover_config: FriProverConfig,
     witness_generator_config: FriWitnessGeneratorConfig,
+    object_store_gc: Option<ObjectStoreGarbageCollector>,
     stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<Vec<JoinHandle<anyhow::Result<()>>>> {
     let mut task_runner = TaskRunner::default();
@@ -162,6 +229,14 @@ fn get_tasks(
         prover_jobs_archiver,
     );
 
+    if let Some(object_store_gc) = object_store_gc {
+        task_runner.add(
+            "ObjectStoreGarbageCollector",
+            prover_job_monitor_config.object_store_gc_run_interval,
+            object_store_gc,
+        );
+    }
+
     // job re-queuers
     let proof_compressor_job_requeuer = ProofCompressorJobRequeuer::new(
         connection_pool.clone(),
@@ -1,6 +1,8 @@
 use vise::{Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, LabeledFamily, Metrics};
 use zksync_types::{protocol_version::ProtocolSemanticVersion, L2ChainId};
 
+use crate::object_store_gc::ArtifactKind;
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "prover_job_monitor")]
 pub(crate) struct ProverJobMonitorMetrics {
@@ -8,6 +10,12 @@ pub(crate) struct ProverJobMonitorMetrics {
     pub gpu_prover_archived: Counter,
     #[metrics(labels = ["job_type"])]
     pub reached_max_attempts: LabeledFamily<JobType, Gauge>,
+    /// Number of objects removed by the object store garbage collector.
+    #[metrics(labels = ["kind"])]
+    pub object_store_gc_removed_objects: LabeledFamily<ArtifactKind, Counter>,
+    /// Last L1 batch for which artifacts were removed by the object store garbage collector.
+    #[metrics(labels = ["kind"])]
+    pub object_store_gc_last_collected_l1_batch: LabeledFamily<ArtifactKind, Gauge<u64>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
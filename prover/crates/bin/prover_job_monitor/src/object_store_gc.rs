@@ -0,0 +1,450 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use vise::EncodeLabelValue;
+use zksync_dal::{Core, CoreDal};
+use zksync_object_store::{Bucket, ObjectStore, ObjectStoreError};
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
+use zksync_prover_task::Task;
+use zksync_types::{L1BatchId, L1BatchNumber, L2ChainId};
+
+use crate::metrics::PROVER_JOB_MONITOR_METRICS;
+
+/// Kind of prover artifacts in the object store with a separate retention rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    /// Witness inputs for basic witness generation.
+    WitnessInputs,
+    /// Circuits for prover jobs and inputs for aggregation witness jobs.
+    Circuits,
+    /// FRI proofs and final proofs for L1.
+    Proofs,
+}
+
+impl ArtifactKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::WitnessInputs => "witness_inputs",
+            Self::Circuits => "circuits",
+            Self::Proofs => "proofs",
+        }
+    }
+}
+
+/// Retention rule for a kind of prover artifacts.
+#[derive(Debug, Clone, Copy)]
+pub struct RetentionRule {
+    pub kind: ArtifactKind,
+    /// Number of the latest collectable L1 batches for which artifacts are retained.
+    pub retained_batches: u32,
+}
+
+/// `ObjectStoreGarbageCollector` is a task that removes prover artifacts for old L1 batches from the object store.
+///
+/// An L1 batch is collectable once it's executed on L1 (according to the core database), and proofs for it and all
+/// previous batches are sent to the server (according to the prover database). Artifacts of each kind are removed
+/// for collectable batches except for the latest [`RetentionRule::retained_batches`] ones. Collection progress
+/// is persisted in the prover database, so batches are processed once.
+///
+/// Only artifacts with keys qualified with the chain ID are collected; artifacts with legacy keys must be removed
+/// manually.
+#[derive(Debug)]
+pub struct ObjectStoreGarbageCollector {
+    core_pool: ConnectionPool<Core>,
+    collector: ArtifactCollector,
+}
+
+impl ObjectStoreGarbageCollector {
+    pub fn new(
+        prover_pool: ConnectionPool<Prover>,
+        core_pool: ConnectionPool<Core>,
+        object_store: Arc<dyn ObjectStore>,
+        chain_id: L2ChainId,
+        rules: Vec<RetentionRule>,
+        max_l1_batches_per_run: u32,
+    ) -> Self {
+        Self {
+            core_pool,
+            collector: ArtifactCollector {
+                prover_pool,
+                object_store,
+                chain_id,
+                rules,
+                max_l1_batches_per_run,
+            },
+        }
+    }
+
+    async fn last_collectable_l1_batch(&self) -> anyhow::Result<Option<L1BatchNumber>> {
+        let mut core_connection = self
+            .core_pool
+            .connection()
+            .await
+            .context("failed to get core database connection")?;
+        let last_executed = core_connection
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await?;
+        drop(core_connection);
+
+        let mut prover_connection = self
+            .collector
+            .prover_pool
+            .connection()
+            .await
+            .context("failed to get prover database connection")?;
+        let last_sent_to_server = prover_connection
+            .fri_proof_compressor_dal()
+            .get_last_batch_sent_to_server(self.collector.chain_id)
+            .await?;
+
+        Ok(last_executed
+            .zip(last_sent_to_server)
+            .map(|(executed, sent)| executed.min(sent)))
+    }
+}
+
+/// Removes artifacts according to retention rules given the last collectable L1 batch.
+#[derive(Debug)]
+struct ArtifactCollector {
+    prover_pool: ConnectionPool<Prover>,
+    object_store: Arc<dyn ObjectStore>,
+    chain_id: L2ChainId,
+    rules: Vec<RetentionRule>,
+    max_l1_batches_per_run: u32,
+}
+
+impl ArtifactCollector {
+    async fn collect_all(&self, last_collectable: L1BatchNumber) -> anyhow::Result<()> {
+        for &rule in &self.rules {
+            self.collect(rule, last_collectable)
+                .await
+                .with_context(|| format!("failed collecting {:?}", rule.kind))?;
+        }
+        Ok(())
+    }
+
+    async fn collect(
+        &self,
+        rule: RetentionRule,
+        last_collectable: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        let Some(last_to_collect) = last_collectable.0.checked_sub(rule.retained_batches) else {
+            return Ok(());
+        };
+        let mut connection = self
+            .prover_pool
+            .connection()
+            .await
+            .context("failed to get database connection")?;
+        let last_collected = connection
+            .fri_object_store_gc_dal()
+            .get_last_collected_l1_batch(self.chain_id, rule.kind.as_str())
+            .await?;
+        drop(connection);
+
+        let first_to_collect = last_collected.map_or(0, |number| number.0 + 1);
+        if first_to_collect > last_to_collect {
+            return Ok(());
+        }
+        let last_to_collect =
+            last_to_collect.min(first_to_collect + self.max_l1_batches_per_run - 1);
+
+        let mut removed_objects = 0;
+        for number in first_to_collect..=last_to_collect {
+            let batch_id = L1BatchId::new(self.chain_id, L1BatchNumber(number));
+            removed_objects += self.collect_batch(rule.kind, batch_id).await?;
+        }
+        tracing::info!(
+            "Removed {removed_objects} objects with {:?} for L1 batches #{first_to_collect}..=#{last_to_collect}",
+            rule.kind
+        );
+        PROVER_JOB_MONITOR_METRICS.object_store_gc_removed_objects[&rule.kind]
+            .inc_by(removed_objects as u64);
+        PROVER_JOB_MONITOR_METRICS.object_store_gc_last_collected_l1_batch[&rule.kind]
+            .set(last_to_collect.into());
+
+        self.prover_pool
+            .connection()
+            .await
+            .context("failed to get database connection")?
+            .fri_object_store_gc_dal()
+            .set_last_collected_l1_batch(
+                self.chain_id,
+                rule.kind.as_str(),
+                L1BatchNumber(last_to_collect),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Removes artifacts of the specified kind for a single L1 batch. Returns the number of removed objects.
+    async fn collect_batch(
+        &self,
+        kind: ArtifactKind,
+        batch_id: L1BatchId,
+    ) -> anyhow::Result<usize> {
+        let number = batch_id.batch_number().0;
+        let chain_id = batch_id.chain_id();
+        // Prefixes correspond to keys produced by `StoredObject::encode_key()` for the relevant object types.
+        let prefixes = match kind {
+            ArtifactKind::WitnessInputs => {
+                vec![(
+                    Bucket::WitnessInput,
+                    format!("witness_inputs_{number}_{chain_id}."),
+                )]
+            }
+            ArtifactKind::Circuits => vec![
+                (Bucket::ProverJobsFri, format!("{number}_{chain_id}_")),
+                (
+                    Bucket::LeafAggregationWitnessJobsFri,
+                    format!("closed_form_inputs_{number}_{chain_id}_"),
+                ),
+                (
+                    Bucket::NodeAggregationWitnessJobsFri,
+                    format!("aggregations_{number}_{chain_id}_"),
+                ),
+                (
+                    Bucket::SchedulerWitnessJobsFri,
+                    format!("scheduler_witness_{number}_{chain_id}."),
+                ),
+                (
+                    Bucket::SchedulerWitnessJobsFri,
+                    format!("aux_output_witness_{number}_{chain_id}."),
+                ),
+            ],
+            ArtifactKind::Proofs => vec![(
+                Bucket::ProofsFri,
+                format!("l1_batch_proof_{number}_{chain_id}_"),
+            )],
+        };
+
+        let mut keys = vec![];
+        for (bucket, prefix) in prefixes {
+            let bucket_keys = self.object_store.list_raw(bucket, &prefix).await?;
+            keys.extend(bucket_keys.into_iter().map(|key| (bucket, key)));
+        }
+        if kind == ArtifactKind::Proofs {
+            // FRI proofs are keyed by prover job IDs rather than by L1 batches.
+            let job_ids = self
+                .prover_pool
+                .connection()
+                .await
+                .context("failed to get database connection")?
+                .fri_prover_jobs_dal()
+                .get_prover_job_ids_for_batch(batch_id)
+                .await;
+            keys.extend(
+                job_ids
+                    .into_iter()
+                    .map(|id| (Bucket::ProofsFri, format!("proof_{id}_{chain_id}.bin"))),
+            );
+        }
+
+        let mut removed_objects = 0;
+        for (bucket, key) in keys {
+            match self.object_store.remove_raw(bucket, &key).await {
+                Ok(()) => removed_objects += 1,
+                Err(ObjectStoreError::KeyNotFound(_)) => { /* already removed */ }
+                Err(err) => {
+                    return Err(anyhow::Error::from(err))
+                        .with_context(|| format!("failed removing `{key}` from bucket {bucket}"));
+                }
+            }
+        }
+        Ok(removed_objects)
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for ObjectStoreGarbageCollector {
+    async fn invoke(&self) -> anyhow::Result<()> {
+        let Some(last_collectable) = self.last_collectable_l1_batch().await? else {
+            return Ok(());
+        };
+        self.collector.collect_all(last_collectable).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_object_store::MockObjectStore;
+
+    use super::*;
+
+    const CHAIN_ID: u32 = 270;
+    const OTHER_CHAIN_ID: u32 = 271;
+
+    fn artifact_keys(number: u32, chain_id: u32) -> Vec<(Bucket, String)> {
+        vec![
+            (
+                Bucket::WitnessInput,
+                format!("witness_inputs_{number}_{chain_id}.bin"),
+            ),
+            (
+                Bucket::ProverJobsFri,
+                format!("{number}_{chain_id}_1_0_0.bin"),
+            ),
+            (
+                Bucket::SchedulerWitnessJobsFri,
+                format!("scheduler_witness_{number}_{chain_id}.bin"),
+            ),
+            (
+                Bucket::ProofsFri,
+                format!("l1_batch_proof_{number}_{chain_id}_0_28_0.bin"),
+            ),
+        ]
+    }
+
+    async fn put_artifacts(object_store: &dyn ObjectStore, keys: &[(Bucket, String)]) {
+        for (bucket, key) in keys {
+            object_store.put_raw(*bucket, key, vec![1]).await.unwrap();
+        }
+    }
+
+    async fn all_keys(object_store: &dyn ObjectStore) -> Vec<String> {
+        let mut keys = vec![];
+        for bucket in [
+            Bucket::WitnessInput,
+            Bucket::ProverJobsFri,
+            Bucket::SchedulerWitnessJobsFri,
+            Bucket::ProofsFri,
+        ] {
+            keys.extend(object_store.list_raw(bucket, "").await.unwrap());
+        }
+        keys.sort_unstable();
+        keys
+    }
+
+    async fn last_collected(
+        pool: &ConnectionPool<Prover>,
+        kind: ArtifactKind,
+    ) -> Option<L1BatchNumber> {
+        pool.connection()
+            .await
+            .unwrap()
+            .fri_object_store_gc_dal()
+            .get_last_collected_l1_batch(CHAIN_ID.into(), kind.as_str())
+            .await
+            .unwrap()
+    }
+
+    fn collector(
+        prover_pool: ConnectionPool<Prover>,
+        object_store: Arc<dyn ObjectStore>,
+        rules: Vec<RetentionRule>,
+        max_l1_batches_per_run: u32,
+    ) -> ArtifactCollector {
+        ArtifactCollector {
+            prover_pool,
+            object_store,
+            chain_id: CHAIN_ID.into(),
+            rules,
+            max_l1_batches_per_run,
+        }
+    }
+
+    #[tokio::test]
+    async fn retention_rules_are_applied() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let object_store = MockObjectStore::arc();
+        for number in 1..=3 {
+            put_artifacts(&*object_store, &artifact_keys(number, CHAIN_ID)).await;
+        }
+        // Keys for other chains and legacy keys must be retained.
+        let other_chain_keys = artifact_keys(1, OTHER_CHAIN_ID);
+        put_artifacts(&*object_store, &other_chain_keys).await;
+        let legacy_key = (Bucket::WitnessInput, "witness_inputs_1.bin".to_owned());
+        put_artifacts(&*object_store, &[legacy_key.clone()]).await;
+
+        let rules = vec![
+            RetentionRule {
+                kind: ArtifactKind::WitnessInputs,
+                retained_batches: 1,
+            },
+            RetentionRule {
+                kind: ArtifactKind::Circuits,
+                retained_batches: 0,
+            },
+            RetentionRule {
+                kind: ArtifactKind::Proofs,
+                retained_batches: 2,
+            },
+        ];
+        let collector = collector(pool.clone(), object_store.clone(), rules, 100);
+        collector.collect_all(L1BatchNumber(3)).await.unwrap();
+
+        let mut expected_keys = vec![
+            "witness_inputs_3_270.bin".to_owned(),
+            "l1_batch_proof_2_270_0_28_0.bin".to_owned(),
+            "l1_batch_proof_3_270_0_28_0.bin".to_owned(),
+            legacy_key.1,
+        ];
+        expected_keys.extend(other_chain_keys.into_iter().map(|(_, key)| key));
+        expected_keys.sort_unstable();
+        assert_eq!(all_keys(&*object_store).await, expected_keys);
+
+        assert_eq!(
+            last_collected(&pool, ArtifactKind::WitnessInputs).await,
+            Some(L1BatchNumber(2))
+        );
+        assert_eq!(
+            last_collected(&pool, ArtifactKind::Circuits).await,
+            Some(L1BatchNumber(3))
+        );
+        assert_eq!(
+            last_collected(&pool, ArtifactKind::Proofs).await,
+            Some(L1BatchNumber(1))
+        );
+    }
+
+    #[tokio::test]
+    async fn collection_is_limited_and_resumed() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let object_store = MockObjectStore::arc();
+        for number in 1..=3 {
+            put_artifacts(&*object_store, &artifact_keys(number, CHAIN_ID)).await;
+        }
+        let rules = vec![RetentionRule {
+            kind: ArtifactKind::WitnessInputs,
+            retained_batches: 0,
+        }];
+        // Batch #0 has no artifacts, but is still processed.
+        let collector = collector(pool.clone(), object_store.clone(), rules, 2);
+
+        collector.collect_all(L1BatchNumber(3)).await.unwrap();
+        let keys = object_store
+            .list_raw(Bucket::WitnessInput, "")
+            .await
+            .unwrap();
+        assert_eq!(
+            keys,
+            ["witness_inputs_2_270.bin", "witness_inputs_3_270.bin"]
+        );
+        assert_eq!(
+            last_collected(&pool, ArtifactKind::WitnessInputs).await,
+            Some(L1BatchNumber(1))
+        );
+
+        collector.collect_all(L1BatchNumber(3)).await.unwrap();
+        let keys = object_store
+            .list_raw(Bucket::WitnessInput, "")
+            .await
+            .unwrap();
+        assert!(keys.is_empty(), "{keys:?}");
+        // Artifacts for other kinds must be retained.
+        let keys = object_store
+            .list_raw(Bucket::ProverJobsFri, "")
+            .await
+            .unwrap();
+        assert_eq!(keys.len(), 3);
+
+        // Repeated collection is a no-op.
+        collector.collect_all(L1BatchNumber(3)).await.unwrap();
+        assert_eq!(
+            last_collected(&pool, ArtifactKind::WitnessInputs).await,
+            Some(L1BatchNumber(3))
+        );
+    }
+}
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS object_store_gc_progress_fri (
+    chain_id INTEGER NOT NULL,
+    artifact_kind TEXT NOT NULL,
+    last_collected_l1_batch_number BIGINT NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (chain_id, artifact_kind)
+);
@@ -0,0 +1,69 @@
+use zksync_basic_types::{L1BatchNumber, L2ChainId};
+use zksync_db_connection::{connection::Connection, error::DalError, instrument::InstrumentExt};
+
+use crate::Prover;
+
+/// Progress of the garbage collection of prover artifacts in the object store.
+#[derive(Debug)]
+pub struct FriObjectStoreGcDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Prover>,
+}
+
+impl FriObjectStoreGcDal<'_, '_> {
+    /// Returns the last L1 batch for which artifacts of the specified kind were collected.
+    pub async fn get_last_collected_l1_batch(
+        &mut self,
+        chain_id: L2ChainId,
+        artifact_kind: &str,
+    ) -> Result<Option<L1BatchNumber>, DalError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                last_collected_l1_batch_number
+            FROM
+                object_store_gc_progress_fri
+            WHERE
+                chain_id = $1
+                AND artifact_kind = $2
+            "#,
+            chain_id.inner() as i64,
+            artifact_kind
+        )
+        .instrument("get_last_collected_l1_batch")
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| L1BatchNumber(row.last_collected_l1_batch_number as u32)))
+    }
+
+    pub async fn set_last_collected_l1_batch(
+        &mut self,
+        chain_id: L2ChainId,
+        artifact_kind: &str,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<(), DalError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            object_store_gc_progress_fri (
+                chain_id, artifact_kind, last_collected_l1_batch_number, updated_at
+            )
+            VALUES
+            ($1, $2, $3, NOW())
+            ON CONFLICT (chain_id, artifact_kind) DO
+            UPDATE
+            SET
+            last_collected_l1_batch_number = $3,
+            updated_at = NOW()
+            "#,
+            chain_id.inner() as i64,
+            artifact_kind,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("set_last_collected_l1_batch")
+        .execute(self.storage)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -7,7 +7,7 @@ use zksync_basic_types::{
     prover_dal::{
         JobCountStatistics, ProofCompressionJobInfo, ProofCompressionJobStatus, StuckJobs,
     },
-    L1BatchId, L2ChainId,
+    L1BatchId, L1BatchNumber, L2ChainId,
 };
 use zksync_db_connection::{connection::Connection, error::DalError, instrument::InstrumentExt};
 
@@ -274,6 +274,47 @@ impl FriProofCompressorDal<'_, '_> {
         Ok(())
     }
 
+    /// Returns the last L1 batch such that proofs for it and all previous batches of the chain
+    /// are sent to the server.
+    pub async fn get_last_batch_sent_to_server(
+        &mut self,
+        chain_id: L2ChainId,
+    ) -> Result<Option<L1BatchNumber>, DalError> {
+        let l1_batch_number = sqlx::query_scalar!(
+            r#"
+            SELECT
+                COALESCE(
+                    (
+                        SELECT
+                            MIN(l1_batch_number) - 1
+                        FROM
+                            proof_compression_jobs_fri
+                        WHERE
+                            chain_id = $1
+                            AND status <> $2
+                    ),
+                    (
+                        SELECT
+                            MAX(l1_batch_number)
+                        FROM
+                            proof_compression_jobs_fri
+                        WHERE
+                            chain_id = $1
+                    )
+                ) AS "l1_batch_number"
+            "#,
+            chain_id.inner() as i64,
+            ProofCompressionJobStatus::SentToServer.to_string(),
+        )
+        .instrument("get_last_batch_sent_to_server")
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(l1_batch_number
+            .filter(|&number| number >= 0)
+            .map(|number| L1BatchNumber(number as u32)))
+    }
+
     pub async fn get_jobs_stats(&mut self) -> HashMap<ProtocolSemanticVersion, JobCountStatistics> {
         sqlx::query!(
             r#"
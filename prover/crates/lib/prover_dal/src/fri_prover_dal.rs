@@ -1041,6 +1041,37 @@ impl FriProverDal<'_, '_> {
         .collect::<_>()
     }
 
+    /// Returns IDs of all prover jobs for the specified batch, including archived ones.
+    pub async fn get_prover_job_ids_for_batch(&mut self, batch_id: L1BatchId) -> Vec<u32> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT
+                id AS "id!"
+            FROM
+                prover_jobs_fri
+            WHERE
+                l1_batch_number = $1
+                AND chain_id = $2
+            UNION ALL
+            SELECT
+                id AS "id!"
+            FROM
+                prover_jobs_fri_archive
+            WHERE
+                l1_batch_number = $1
+                AND chain_id = $2
+            "#,
+            batch_id.batch_number().0 as i64,
+            batch_id.chain_id().inner() as i64,
+        )
+        .fetch_all(self.storage.conn())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|id| id as u32)
+        .collect()
+    }
+
     pub async fn check_reached_max_attempts(&mut self, max_attempts: u32) -> usize {
         sqlx::query_scalar!(
             r#"
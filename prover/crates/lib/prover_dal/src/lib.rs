@@ -8,6 +8,7 @@ pub use zksync_db_connection::{
 
 use crate::{
     cli_test_dal::CliTestDal,
+    fri_object_store_gc_dal::FriObjectStoreGcDal,
     fri_proof_compressor_dal::FriProofCompressorDal,
     fri_protocol_versions_dal::FriProtocolVersionsDal,
     fri_prover_dal::FriProverDal,
@@ -19,6 +20,7 @@ use crate::{
 };
 
 pub mod cli_test_dal;
+pub mod fri_object_store_gc_dal;
 pub mod fri_proof_compressor_dal;
 pub mod fri_protocol_versions_dal;
 pub mod fri_prover_dal;
@@ -56,6 +58,8 @@ where
     fn fri_protocol_versions_dal(&mut self) -> FriProtocolVersionsDal<'_, 'a>;
 
     fn fri_proof_compressor_dal(&mut self) -> FriProofCompressorDal<'_, 'a>;
+
+    fn fri_object_store_gc_dal(&mut self) -> FriObjectStoreGcDal<'_, 'a>;
 }
 
 #[derive(Clone, Debug)]
@@ -107,4 +111,8 @@ impl<'a> ProverDal<'a> for Connection<'a, Prover> {
     fn fri_proof_compressor_dal(&mut self) -> FriProofCompressorDal<'_, 'a> {
         FriProofCompressorDal { storage: self }
     }
+
+    fn fri_object_store_gc_dal(&mut self) -> FriObjectStoreGcDal<'_, 'a> {
+        FriObjectStoreGcDal { storage: self }
+    }
 }